@@ -338,6 +338,9 @@ async fn main() -> anyhow::Result<()> {
             }
             if rollback_tree {
                 block_reverter.enable_rolling_back_merkle_tree(db_config.merkle_tree.path);
+                if let Some(checkpoints_path) = db_config.merkle_tree.checkpoints_path {
+                    block_reverter.use_merkle_tree_checkpoints(checkpoints_path);
+                }
             }
             if rollback_sk_cache {
                 block_reverter.add_rocksdb_storage_path_to_rollback(db_config.state_keeper_db_path);
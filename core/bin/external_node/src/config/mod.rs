@@ -3,6 +3,7 @@ use std::{
     ffi::OsString,
     fmt,
     future::Future,
+    net::SocketAddr,
     num::{NonZeroU32, NonZeroU64, NonZeroUsize},
     path::PathBuf,
     str::FromStr,
@@ -14,7 +15,7 @@ use serde::{de, Deserialize, Deserializer};
 use smart_config::{ConfigRepository, ConfigSchema, ConfigSources, DescribeConfig, Prefixed};
 use zksync_config::{
     configs::{
-        api::{MaxResponseSize, MaxResponseSizeOverrides},
+        api::{MaxResponseSize, MaxResponseSizeOverrides, MerkleTreeApiConfig},
         consensus::{ConsensusConfig, ConsensusSecrets},
         contracts::{
             chain::{ChainContracts, L2Contracts},
@@ -31,12 +32,14 @@ use zksync_consensus_crypto::TextFmt;
 use zksync_consensus_roles as roles;
 #[cfg(test)]
 use zksync_dal::{ConnectionPool, Core};
-use zksync_metadata_calculator::MetadataCalculatorRecoveryConfig;
+use zksync_metadata_calculator::{MerkleTreeCheckpointsConfig, MetadataCalculatorRecoveryConfig};
 use zksync_node_api_server::{
     tx_sender::{TimestampAsserterParams, TxSenderConfig},
     web3::{state::InternalApiConfigBase, Namespace},
 };
+use zksync_node_db_pruner::{DbSizeLimits, PrunedTable};
 use zksync_snapshots_applier::SnapshotsApplierConfig;
+use zksync_state::StorageCachesBudgetConfig;
 use zksync_types::{
     commitment::L1BatchCommitmentMode, url::SensitiveUrl, Address, L1BatchNumber, L1ChainId,
     L2ChainId, SLChainId, ETHEREUM_ADDRESS,
@@ -353,6 +356,15 @@ pub(crate) struct OptionalENConfig {
     /// values cache will be disabled.
     #[serde(default = "OptionalENConfig::default_latest_values_cache_size_mb")]
     latest_values_cache_size_mb: usize,
+    /// Memory budget in MiBs shared by the API server storage caches. If set, the caches use the W-TinyLFU admission policy,
+    /// and the budget is periodically redistributed among them based on their hit rates; the cache sizes above are used
+    /// as the initial distribution.
+    storage_caches_memory_budget_mb: Option<usize>,
+    /// Interval between redistributing the storage caches memory budget.
+    #[serde(default = "OptionalENConfig::default_storage_caches_resize_interval_sec")]
+    storage_caches_resize_interval_sec: u64,
+    /// Path to the file with the most accessed keys of the storage caches, which is used to warm up the caches on restart.
+    storage_caches_hot_set_path: Option<PathBuf>,
     /// Enabled JSON RPC API namespaces.
     api_namespaces: Option<Vec<Namespace>>,
     /// Whether to support HTTP methods that install filters and query filter changes.
@@ -446,6 +458,15 @@ pub(crate) struct OptionalENConfig {
     /// Enables the stale keys repair task for the Merkle tree.
     #[serde(default)]
     pub merkle_tree_repair_stale_keys: bool,
+    /// Path to the directory with Merkle tree checkpoints. If not specified, checkpoints are not created.
+    /// Checkpoints allow rolling back the tree to pruned versions, e.g. on a deep reorg.
+    pub merkle_tree_checkpoints_path: Option<PathBuf>,
+    /// Interval in L1 batches between consecutive Merkle tree checkpoints.
+    #[serde(default = "OptionalENConfig::default_merkle_tree_checkpoint_interval")]
+    pub merkle_tree_checkpoint_interval: NonZeroU32,
+    /// Number of the latest Merkle tree checkpoints to retain.
+    #[serde(default = "OptionalENConfig::default_merkle_tree_retained_checkpoints")]
+    pub merkle_tree_retained_checkpoints: usize,
 
     // Postgres config (new parameters)
     /// Threshold in milliseconds for the DB connection lifetime to denote it as long-living and log its details.
@@ -491,6 +512,14 @@ pub(crate) struct OptionalENConfig {
 
     #[serde(skip)]
     pub snapshots_recovery_object_store: Option<ObjectStoreConfig>,
+    /// Address of the key expected to sign snapshot headers. If set, snapshot recovery will fail for snapshots
+    /// without a valid signature by this key.
+    #[serde(default)]
+    pub snapshots_recovery_header_signer: Option<Address>,
+    /// If set, snapshot files downloaded during recovery (i.e., ones in the local mirror of the snapshot recovery
+    /// object store) will be served over HTTP on this address.
+    #[serde(default)]
+    pub snapshots_recovery_serve_addr: Option<SocketAddr>,
 
     /// Enables pruning of the historical node state (Postgres and Merkle tree). The node will retain
     /// recent state and will continuously remove (prune) old enough parts of the state in the background.
@@ -510,6 +539,22 @@ pub(crate) struct OptionalENConfig {
     /// If set to 0, L1 batches will not be retained based on their timestamp. The default value is 7 days.
     #[serde(default = "OptionalENConfig::default_pruning_data_retention_sec")]
     pruning_data_retention_sec: u64,
+    /// If set, L1 batches will only be pruned while the estimated size of the Postgres database (in MiB)
+    /// exceeds this value. To prune purely based on the database size, set `pruning_data_retention_sec` to 0.
+    pruning_max_database_size_mb: Option<u64>,
+    /// If set, L1 batches will only be pruned while the estimated size of the `storage_logs` table (in MiB)
+    /// exceeds this value.
+    pruning_max_storage_logs_size_mb: Option<u64>,
+    /// If set, L1 batches will only be pruned while the estimated size of the `events` table (in MiB)
+    /// exceeds this value.
+    pruning_max_events_size_mb: Option<u64>,
+    /// If set, L1 batches will only be pruned while the estimated size of the `transactions` table (in MiB)
+    /// exceeds this value.
+    pruning_max_transactions_size_mb: Option<u64>,
+    /// If set, the pruner will not modify the database and will only report pruning steps it would perform
+    /// via logs and the health check.
+    #[serde(default)]
+    pub pruning_dry_run: bool,
     /// Gateway RPC URL, needed for operating during migration.
     pub gateway_url: Option<SensitiveUrl>,
     /// Interval for bridge addresses refreshing in seconds.
@@ -554,6 +599,13 @@ impl OptionalENConfig {
                 / BYTES_IN_MEGABYTE,
             latest_values_cache_size_mb: web3_json_rpc.latest_values_cache_size_mb.0 as usize
                 / BYTES_IN_MEGABYTE,
+            storage_caches_memory_budget_mb: web3_json_rpc
+                .storage_caches_memory_budget_mb
+                .map(|budget| budget.0 as usize / BYTES_IN_MEGABYTE),
+            storage_caches_resize_interval_sec: web3_json_rpc
+                .storage_caches_resize_interval_sec
+                .as_secs(),
+            storage_caches_hot_set_path: web3_json_rpc.storage_caches_hot_set_path.clone(),
             filters_disabled: web3_json_rpc.filters_disabled,
             mempool_cache_update_interval_ms: web3_json_rpc
                 .mempool_cache_update_interval
@@ -592,6 +644,9 @@ impl OptionalENConfig {
                 .db_config
                 .experimental
                 .merkle_tree_repair_stale_keys,
+            merkle_tree_checkpoints_path: merkle_tree.checkpoints_path.clone(),
+            merkle_tree_checkpoint_interval: merkle_tree.checkpoint_interval,
+            merkle_tree_retained_checkpoints: merkle_tree.retained_checkpoints,
             database_long_connection_threshold_ms: Some(
                 general_config
                     .postgres_config
@@ -624,12 +679,37 @@ impl OptionalENConfig {
                 .snapshot_recovery
                 .as_ref()
                 .and_then(|config| config.object_store.clone()),
+            snapshots_recovery_header_signer: general_config
+                .snapshot_recovery
+                .as_ref()
+                .and_then(|config| config.header_signer),
+            snapshots_recovery_serve_addr: general_config
+                .snapshot_recovery
+                .as_ref()
+                .and_then(|config| config.serve_addr),
             pruning_chunk_size: general_config.pruning.chunk_size.get(),
             pruning_removal_delay_sec: NonZeroU64::new(
                 general_config.pruning.removal_delay_sec.as_secs(),
             )
             .unwrap_or_else(|| NonZeroU64::new(1).unwrap()),
             pruning_data_retention_sec: general_config.pruning.data_retention_sec.as_secs(),
+            pruning_max_database_size_mb: general_config
+                .pruning
+                .max_database_size_mb
+                .map(|size| size.0 / BYTES_IN_MEGABYTE as u64),
+            pruning_max_storage_logs_size_mb: general_config
+                .pruning
+                .max_storage_logs_size_mb
+                .map(|size| size.0 / BYTES_IN_MEGABYTE as u64),
+            pruning_max_events_size_mb: general_config
+                .pruning
+                .max_events_size_mb
+                .map(|size| size.0 / BYTES_IN_MEGABYTE as u64),
+            pruning_max_transactions_size_mb: general_config
+                .pruning
+                .max_transactions_size_mb
+                .map(|size| size.0 / BYTES_IN_MEGABYTE as u64),
+            pruning_dry_run: general_config.pruning.dry_run,
             protective_reads_persistence_enabled: general_config
                 .db_config
                 .experimental
@@ -702,6 +782,14 @@ impl OptionalENConfig {
         20
     }
 
+    fn default_merkle_tree_checkpoint_interval() -> NonZeroU32 {
+        NonZeroU32::new(1_000).unwrap()
+    }
+
+    const fn default_merkle_tree_retained_checkpoints() -> usize {
+        3
+    }
+
     const fn default_vm_concurrency_limit() -> usize {
         // The default limit is large so that it does not create a bottleneck on its own.
         // VM execution can still be limited by Tokio runtime parallelism and/or the number
@@ -721,6 +809,10 @@ impl OptionalENConfig {
         128
     }
 
+    const fn default_storage_caches_resize_interval_sec() -> u64 {
+        60
+    }
+
     const fn default_merkle_tree_multi_get_chunk_size() -> usize {
         500
     }
@@ -835,6 +927,22 @@ impl OptionalENConfig {
         Duration::from_secs(self.merkle_tree_stalled_writes_timeout_sec)
     }
 
+    pub fn storage_caches_budget(&self) -> Option<StorageCachesBudgetConfig> {
+        let budget = self.storage_caches_memory_budget_mb? * BYTES_IN_MEGABYTE;
+        let mut config = StorageCachesBudgetConfig::new(budget as u64);
+        config.resize_interval = Duration::from_secs(self.storage_caches_resize_interval_sec);
+        config.hot_set_path = self.storage_caches_hot_set_path.clone();
+        Some(config)
+    }
+
+    pub fn merkle_tree_checkpoints(&self) -> Option<MerkleTreeCheckpointsConfig> {
+        Some(MerkleTreeCheckpointsConfig {
+            path: self.merkle_tree_checkpoints_path.clone()?,
+            interval: self.merkle_tree_checkpoint_interval,
+            retained_count: self.merkle_tree_retained_checkpoints,
+        })
+    }
+
     pub fn long_connection_threshold(&self) -> Option<Duration> {
         self.database_long_connection_threshold_ms
             .map(Duration::from_millis)
@@ -881,6 +989,28 @@ impl OptionalENConfig {
         Duration::from_secs(self.pruning_data_retention_sec)
     }
 
+    pub fn pruning_size_limits(&self) -> DbSizeLimits {
+        let to_bytes = |size_mb: u64| size_mb * BYTES_IN_MEGABYTE as u64;
+        let table_limits = [
+            (
+                PrunedTable::StorageLogs,
+                self.pruning_max_storage_logs_size_mb,
+            ),
+            (PrunedTable::Events, self.pruning_max_events_size_mb),
+            (
+                PrunedTable::Transactions,
+                self.pruning_max_transactions_size_mb,
+            ),
+        ];
+        DbSizeLimits {
+            database: self.pruning_max_database_size_mb.map(to_bytes),
+            tables: table_limits
+                .into_iter()
+                .filter_map(|(table, size_mb)| Some((table, to_bytes(size_mb?))))
+                .collect(),
+        }
+    }
+
     pub fn bridge_addresses_refresh_interval(&self) -> Duration {
         self.bridge_addresses_refresh_interval_sec
             .map_or_else(|| Duration::from_secs(30), |n| Duration::from_secs(n.get()))
@@ -1135,6 +1265,9 @@ pub struct ApiComponentConfig {
     /// local tree component running and in this case needs to send requests
     /// to some external tree API.
     pub tree_api_remote_url: Option<String>,
+    /// Address of an archive node used to serve historical state queries for blocks pruned on this EN.
+    /// Responses of the archive node are verified using Merkle proofs.
+    pub archive_proxy_url: Option<SensitiveUrl>,
 }
 
 impl ApiComponentConfig {
@@ -1144,6 +1277,10 @@ impl ApiComponentConfig {
                 .api_config
                 .as_ref()
                 .and_then(|a| a.web3_json_rpc.tree_api_url.clone()),
+            archive_proxy_url: general_config
+                .api_config
+                .as_ref()
+                .and_then(|a| a.web3_json_rpc.archive_proxy_url.clone()),
         }
     }
 }
@@ -1151,15 +1288,52 @@ impl ApiComponentConfig {
 #[derive(Debug, Deserialize)]
 pub struct TreeComponentConfig {
     pub api_port: Option<u16>,
+    /// Path to store the secondary RocksDB instance data for a standalone tree API server. If set, the standalone server
+    /// opens the tree in the secondary mode, so that it can run alongside the tree component in another process.
+    pub secondary_path: Option<PathBuf>,
+    /// Interval between catching up with the primary tree instance in the secondary mode.
+    #[serde(default = "TreeComponentConfig::default_catch_up_interval_ms")]
+    pub catch_up_interval_ms: u64,
+    /// Maximum number of keys in a single multiproof request to the tree API server.
+    #[serde(default = "TreeComponentConfig::default_max_multiproof_keys")]
+    pub max_multiproof_keys: usize,
 }
 
 impl TreeComponentConfig {
+    const fn default_catch_up_interval_ms() -> u64 {
+        1_000
+    }
+
+    const fn default_max_multiproof_keys() -> usize {
+        100_000
+    }
+
     fn from_configs(general_config: &GeneralConfig) -> Self {
-        let api_port = general_config
-            .api_config
-            .as_ref()
-            .map(|a| a.merkle_tree.port);
-        TreeComponentConfig { api_port }
+        let api_config = general_config.api_config.as_ref().map(|a| &a.merkle_tree);
+        TreeComponentConfig {
+            api_port: api_config.map(|config| config.port),
+            secondary_path: api_config.and_then(|config| config.secondary_path.clone()),
+            catch_up_interval_ms: api_config
+                .map_or(Self::default_catch_up_interval_ms(), |config| {
+                    config.catch_up_interval.as_millis() as u64
+                }),
+            max_multiproof_keys: api_config.map_or(Self::default_max_multiproof_keys(), |config| {
+                config.max_multiproof_keys
+            }),
+        }
+    }
+
+    pub fn catch_up_interval(&self) -> Duration {
+        Duration::from_millis(self.catch_up_interval_ms)
+    }
+
+    pub fn api_config(&self) -> anyhow::Result<MerkleTreeApiConfig> {
+        Ok(MerkleTreeApiConfig {
+            port: self.api_port.context("should contain tree api port")?,
+            secondary_path: self.secondary_path.clone(),
+            catch_up_interval: self.catch_up_interval(),
+            max_multiproof_keys: self.max_multiproof_keys,
+        })
     }
 }
 
@@ -1335,8 +1509,14 @@ impl ExternalNodeConfig {
             consensus_secrets: ConsensusSecrets::default(),
             api_component: ApiComponentConfig {
                 tree_api_remote_url: None,
+                archive_proxy_url: None,
+            },
+            tree_component: TreeComponentConfig {
+                api_port: None,
+                secondary_path: None,
+                catch_up_interval_ms: TreeComponentConfig::default_catch_up_interval_ms(),
+                max_multiproof_keys: TreeComponentConfig::default_max_multiproof_keys(),
             },
-            tree_component: TreeComponentConfig { api_port: None },
             data_availability: (None, None),
         }
     }
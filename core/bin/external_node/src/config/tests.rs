@@ -89,6 +89,7 @@ fn parsing_optional_config_from_empty_env() {
     assert_eq!(config.vm_concurrency_limit, 2_048);
     assert_eq!(config.factory_deps_cache_size(), 128 * BYTES_IN_MEGABYTE);
     assert_eq!(config.latest_values_cache_size(), 128 * BYTES_IN_MEGABYTE);
+    assert!(config.storage_caches_budget().is_none());
     assert_eq!(config.merkle_tree_multi_get_chunk_size, 500);
     assert_eq!(
         config.merkle_tree_block_cache_size(),
@@ -119,6 +120,8 @@ fn parsing_optional_config_from_env() {
         ("EN_VM_CONCURRENCY_LIMIT", "1000"),
         ("EN_FACTORY_DEPS_CACHE_SIZE_MB", "64"),
         ("EN_LATEST_VALUES_CACHE_SIZE_MB", "50"),
+        ("EN_STORAGE_CACHES_MEMORY_BUDGET_MB", "256"),
+        ("EN_STORAGE_CACHES_RESIZE_INTERVAL_SEC", "30"),
         ("EN_MERKLE_TREE_MULTI_GET_CHUNK_SIZE", "1000"),
         ("EN_MERKLE_TREE_BLOCK_CACHE_SIZE_MB", "32"),
         ("EN_MAX_RESPONSE_BODY_SIZE_MB", "1"),
@@ -148,6 +151,10 @@ fn parsing_optional_config_from_env() {
     assert_eq!(config.vm_concurrency_limit, 1_000);
     assert_eq!(config.factory_deps_cache_size(), 64 * BYTES_IN_MEGABYTE);
     assert_eq!(config.latest_values_cache_size(), 50 * BYTES_IN_MEGABYTE);
+    let budget = config.storage_caches_budget().unwrap();
+    assert_eq!(budget.memory_budget, 256 * BYTES_IN_MEGABYTE as u64);
+    assert_eq!(budget.resize_interval, Duration::from_secs(30));
+    assert_eq!(budget.hot_set_path, None);
     assert_eq!(config.merkle_tree_multi_get_chunk_size, 1_000);
     assert_eq!(
         config.merkle_tree_block_cache_size(),
@@ -8,9 +8,7 @@ use zksync_block_reverter::{node::BlockReverterLayer, NodeRole};
 use zksync_commitment_generator::node::CommitmentGeneratorLayer;
 use zksync_config::{
     configs::{
-        api::{HealthCheckConfig, MerkleTreeApiConfig},
-        chain::TimestampAsserterConfig,
-        database::MerkleTreeMode,
+        api::HealthCheckConfig, chain::TimestampAsserterConfig, database::MerkleTreeMode,
         DataAvailabilitySecrets, DatabaseSecrets,
     },
     DAClientConfig, PostgresConfig,
@@ -31,7 +29,7 @@ use zksync_metadata_calculator::{
 use zksync_node_api_server::{
     node::{
         HealthCheckLayer, MempoolCacheLayer, PostgresStorageCachesConfig, ProxySinkLayer,
-        TxSenderLayer, Web3ServerLayer, Web3ServerOptionalConfig,
+        SnapshotsServerLayer, TxSenderLayer, Web3ServerLayer, Web3ServerOptionalConfig,
     },
     web3::{state::InternalApiConfigBase, Namespace},
 };
@@ -262,7 +260,9 @@ impl ExternalNodeBuilder {
                 self.config.optional.pruning_removal_delay(),
                 self.config.optional.pruning_chunk_size,
                 self.config.optional.pruning_data_retention(),
-            );
+            )
+            .with_size_limits(self.config.optional.pruning_size_limits())
+            .with_dry_run(self.config.optional.pruning_dry_run);
             self.node.add_layer(layer);
         } else {
             tracing::info!("Pruning is disabled");
@@ -381,6 +381,7 @@ impl ExternalNodeBuilder {
                     .experimental
                     .snapshots_recovery_tree_parallel_persistence_buffer,
             },
+            checkpoints: self.config.optional.merkle_tree_checkpoints(),
         };
 
         // Configure basic tree layer.
@@ -388,13 +389,7 @@ impl ExternalNodeBuilder {
 
         // Add tree API if needed.
         if with_tree_api {
-            let merkle_tree_api_config = MerkleTreeApiConfig {
-                port: self
-                    .config
-                    .tree_component
-                    .api_port
-                    .context("should contain tree api port")?,
-            };
+            let merkle_tree_api_config = self.config.tree_component.api_config()?;
             layer = layer.with_tree_api_config(merkle_tree_api_config);
         }
 
@@ -422,14 +417,10 @@ impl ExternalNodeBuilder {
                 .config
                 .optional
                 .merkle_tree_include_indices_and_filters_in_block_cache,
+            secondary_db_path: self.config.tree_component.secondary_path.clone(),
+            catch_up_interval: self.config.tree_component.catch_up_interval(),
         };
-        let api_config = MerkleTreeApiConfig {
-            port: self
-                .config
-                .tree_component
-                .api_port
-                .context("should contain tree api port")?,
-        };
+        let api_config = self.config.tree_component.api_config()?;
         self.node
             .add_layer(TreeApiServerLayer::new(reader_config, api_config));
         Ok(self)
@@ -441,6 +432,7 @@ impl ExternalNodeBuilder {
             initial_writes_cache_size: self.config.optional.initial_writes_cache_size() as u64,
             latest_values_cache_size: self.config.optional.latest_values_cache_size() as u64,
             latest_values_max_block_lag: 20, // reasonable default
+            budget: self.config.optional.storage_caches_budget(),
         };
         let max_vm_concurrency = self.config.optional.vm_concurrency_limit;
         let tx_sender_layer = TxSenderLayer::new(
@@ -511,6 +503,7 @@ impl ExternalNodeBuilder {
             polling_interval: Some(self.config.optional.polling_interval()),
             websocket_requests_per_minute_limit: None, // To be set by WS server layer method if required.
             replication_lag_limit: None,               // TODO: Support replication lag limit
+            archive_proxy_url: self.config.api_component.archive_proxy_url.clone(),
         }
     }
 
@@ -541,6 +534,24 @@ impl ExternalNodeBuilder {
         Ok(self)
     }
 
+    fn add_snapshots_server_layer(mut self) -> anyhow::Result<Self> {
+        let Some(bind_address) = self.config.optional.snapshots_recovery_serve_addr else {
+            return Ok(self);
+        };
+        let snapshots_path = self
+            .config
+            .optional
+            .snapshots_recovery_object_store
+            .as_ref()
+            .and_then(|config| config.local_mirror_path.clone())
+            .context(
+                "serving snapshot files requires `local_mirror_path` to be set for snapshot recovery object store",
+            )?;
+        self.node
+            .add_layer(SnapshotsServerLayer::new(snapshots_path, bind_address));
+        Ok(self)
+    }
+
     fn add_reorg_detector_layer(mut self) -> anyhow::Result<Self> {
         self.node.add_layer(ReorgDetectorLayer);
         Ok(self)
@@ -554,6 +565,9 @@ impl ExternalNodeBuilder {
             .enable_rolling_back_postgres()
             .enable_rolling_back_merkle_tree(self.config.required.merkle_tree_path.clone())
             .enable_rolling_back_state_keeper_cache(self.config.required.state_cache_path.clone());
+        if let Some(checkpoints_path) = &self.config.optional.merkle_tree_checkpoints_path {
+            layer.use_merkle_tree_checkpoints(checkpoints_path.clone());
+        }
         self.node.add_layer(layer);
         Ok(self)
     }
@@ -583,6 +597,7 @@ impl ExternalNodeBuilder {
                         .experimental
                         .snapshots_recovery_drop_storage_key_preimages,
                     object_store_config: config.optional.snapshots_recovery_object_store.clone(),
+                    header_signer: config.optional.snapshots_recovery_header_signer,
                 });
         self.node.add_layer(ExternalNodeInitStrategyLayer {
             l2_chain_id: self.config.required.l2_chain_id,
@@ -698,7 +713,8 @@ impl ExternalNodeBuilder {
                         .add_consistency_checker_layer()?
                         .add_commitment_generator_layer()?
                         .add_batch_status_updater_layer()?
-                        .add_logs_bloom_backfill_layer()?;
+                        .add_logs_bloom_backfill_layer()?
+                        .add_snapshots_server_layer()?;
                 }
             }
         }
@@ -0,0 +1,114 @@
+use std::{num::NonZeroU32, path::PathBuf};
+
+use anyhow::Context as _;
+use clap::Parser;
+use tokio::sync::watch;
+use zksync_config::{
+    configs::{chain::StateKeeperConfig, DatabaseSecrets, GenesisConfigWrapper},
+    full_config_schema,
+    sources::ConfigFilePaths,
+    ConfigRepositoryExt, PostgresConfig,
+};
+use zksync_dal::{ConnectionPool, Core};
+use zksync_types::L1BatchNumber;
+use zksync_vm_runner::impls::SealCriteriaSimulator;
+
+/// Replays a range of historic L1 batches and reports how they would be sealed with the state keeper config
+/// provided in the general config (or env vars). Only reads from Postgres.
+#[derive(Debug, Parser)]
+#[command(
+    author = "Matter Labs",
+    version,
+    about = "Seal criteria simulator",
+    long_about = None
+)]
+struct Cli {
+    /// First L1 batch to replay (inclusive).
+    #[arg(long)]
+    first_batch: u32,
+    /// Last L1 batch to replay (inclusive).
+    #[arg(long)]
+    last_batch: u32,
+    /// Maximum number of L1 batches to execute in parallel.
+    #[arg(long, default_value = "4")]
+    window_size: NonZeroU32,
+    /// If set, the JSON report will be written to this file; otherwise, it will be printed to stdout.
+    #[arg(long, value_name = "FILE")]
+    output: Option<PathBuf>,
+    /// Path to yaml config. If set, it will be used instead of env vars
+    #[arg(long)]
+    config_path: Option<PathBuf>,
+    /// Path to yaml secrets config. If set, it will be used instead of env vars
+    #[arg(long)]
+    secrets_path: Option<PathBuf>,
+    /// Path to yaml genesis config. If set, it will be used instead of env vars
+    #[arg(long)]
+    genesis_path: Option<PathBuf>,
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let opts = Cli::parse();
+
+    let config_file_paths = ConfigFilePaths {
+        general: opts.config_path,
+        secrets: opts.secrets_path,
+        genesis: opts.genesis_path,
+        ..ConfigFilePaths::default()
+    };
+    let config_sources =
+        tokio::task::spawn_blocking(|| config_file_paths.into_config_sources("ZKSYNC_")).await??;
+    let _guard = config_sources.observability()?.install()?;
+
+    let schema = full_config_schema(false);
+    let repo = config_sources.build_repository(&schema);
+    let state_keeper_config: StateKeeperConfig = repo.parse()?;
+    let postgres_config: PostgresConfig = repo.parse()?;
+    let database_secrets: DatabaseSecrets = repo.parse()?;
+    let chain_id = repo
+        .parse::<GenesisConfigWrapper>()?
+        .genesis
+        .context("genesis config is required to determine L2 chain ID")?
+        .l2_chain_id;
+
+    let pool = ConnectionPool::<Core>::builder(
+        database_secrets.replica_url()?,
+        postgres_config.max_connections()?,
+    )
+    .build()
+    .await
+    .context("failed to build a connection pool")?;
+
+    let (stop_sender, stop_receiver) = watch::channel(false);
+    tokio::spawn(async move {
+        if tokio::signal::ctrl_c().await.is_ok() {
+            tracing::info!("Received stop signal, interrupting simulation");
+            stop_sender.send_replace(true);
+        }
+    });
+
+    let simulator = SealCriteriaSimulator::new(
+        pool,
+        chain_id,
+        state_keeper_config,
+        L1BatchNumber(opts.first_batch)..=L1BatchNumber(opts.last_batch),
+        opts.window_size,
+    );
+    let report = simulator.run(stop_receiver).await?;
+    tracing::info!(
+        "Simulated {} L1 batches for {} historic L1 batches ({} transactions)",
+        report.batches.len(),
+        opts.last_batch - opts.first_batch + 1,
+        report.tx_count
+    );
+
+    let report = serde_json::to_string_pretty(&report).context("failed serializing report")?;
+    if let Some(path) = &opts.output {
+        tokio::fs::write(path, report)
+            .await
+            .with_context(|| format!("failed writing report to {path:?}"))?;
+    } else {
+        println!("{report}");
+    }
+    Ok(())
+}
@@ -1,44 +1,62 @@
 //! [`SnapshotCreator`] and tightly related types.
 
-use std::{sync::Arc, time::Duration};
+use std::{collections::HashMap, num::NonZeroU32, sync::Arc, time::Duration};
 
 use anyhow::Context as _;
-use tokio::sync::Semaphore;
+use tokio::{
+    sync::{Mutex, Semaphore},
+    time,
+};
 use zksync_config::SnapshotsCreatorConfig;
 use zksync_dal::{Connection, ConnectionPool, Core, CoreDal, DalResult};
-use zksync_object_store::{ObjectStore, StoredObject};
+use zksync_health_check::HealthUpdater;
+use zksync_object_store::{ObjectStore, ObjectStoreError, StoredObject};
 use zksync_types::{
     snapshots::{
         uniform_hashed_keys_chunk, SnapshotFactoryDependencies, SnapshotFactoryDependency,
-        SnapshotMetadata, SnapshotStorageLog, SnapshotStorageLogsChunk,
-        SnapshotStorageLogsStorageKey, SnapshotVersion,
+        SnapshotHeader, SnapshotMetadata, SnapshotStorageLog, SnapshotStorageLogKey,
+        SnapshotStorageLogsChunk, SnapshotStorageLogsStorageKey, SnapshotVersion,
     },
-    L1BatchNumber, L2BlockNumber,
+    K256PrivateKey, L1BatchNumber, L2BlockNumber, StorageKey, H256,
 };
 
-use crate::metrics::{FactoryDepsStage, StorageChunkStage, METRICS};
 #[cfg(test)]
 use crate::tests::HandleEvent;
+use crate::{
+    health::ChunkProgressTracker,
+    metrics::{FactoryDepsStage, StorageChunkStage, METRICS},
+};
 
 /// Encapsulates progress of creating a particular storage snapshot.
 #[derive(Debug)]
 struct SnapshotProgress {
     version: SnapshotVersion,
     l1_batch_number: L1BatchNumber,
+    /// L1 batch of the base snapshot if this is a delta snapshot.
+    base_l1_batch_number: Option<L1BatchNumber>,
     /// `true` if the snapshot is new (i.e., its progress is not recovered from Postgres).
     is_new_snapshot: bool,
     chunk_count: u64,
     remaining_chunk_ids: Vec<u64>,
+    /// Content hashes for remaining chunks that could have been uploaded to the object store before a restart.
+    pending_chunk_hashes: HashMap<u64, H256>,
 }
 
 impl SnapshotProgress {
-    fn new(version: SnapshotVersion, l1_batch_number: L1BatchNumber, chunk_count: u64) -> Self {
+    fn new(
+        version: SnapshotVersion,
+        l1_batch_number: L1BatchNumber,
+        base_l1_batch_number: Option<L1BatchNumber>,
+        chunk_count: u64,
+    ) -> Self {
         Self {
             version,
             l1_batch_number,
+            base_l1_batch_number,
             is_new_snapshot: true,
             chunk_count,
             remaining_chunk_ids: (0..chunk_count).collect(),
+            pending_chunk_hashes: HashMap::new(),
         }
     }
 
@@ -49,13 +67,52 @@ impl SnapshotProgress {
             .enumerate()
             .filter_map(|(chunk_id, path)| path.is_none().then_some(chunk_id as u64))
             .collect();
+        let pending_chunk_hashes = snapshot
+            .storage_logs_filepaths
+            .iter()
+            .zip(&snapshot.storage_logs_hashes)
+            .enumerate()
+            .filter_map(|(chunk_id, (path, hash))| {
+                let hash = (*hash)?;
+                path.is_none().then_some((chunk_id as u64, hash))
+            })
+            .collect();
 
         Self {
             version: snapshot.version,
             l1_batch_number: snapshot.l1_batch_number,
+            base_l1_batch_number: snapshot.base_l1_batch_number,
             is_new_snapshot: false,
             chunk_count: snapshot.storage_logs_filepaths.len() as u64,
             remaining_chunk_ids,
+            pending_chunk_hashes,
+        }
+    }
+}
+
+/// Limits the concurrency and, optionally, the rate of storage log queries to the replica Postgres.
+#[derive(Debug)]
+struct QueryLimiter {
+    semaphore: Semaphore,
+    interval: Option<Mutex<time::Interval>>,
+}
+
+impl QueryLimiter {
+    fn new(concurrent_queries_count: u32, queries_per_minute: Option<NonZeroU32>) -> Self {
+        let interval = queries_per_minute.map(|queries_per_minute| {
+            let mut interval = time::interval(Duration::from_secs(60) / queries_per_minute.get());
+            interval.set_missed_tick_behavior(time::MissedTickBehavior::Delay);
+            Mutex::new(interval)
+        });
+        Self {
+            semaphore: Semaphore::new(concurrent_queries_count as usize),
+            interval,
+        }
+    }
+
+    async fn wait_for_rate_limit(&self) {
+        if let Some(interval) = &self.interval {
+            interval.lock().await.tick().await;
         }
     }
 }
@@ -66,6 +123,10 @@ pub(crate) struct SnapshotCreator {
     pub blob_store: Arc<dyn ObjectStore>,
     pub master_pool: ConnectionPool<Core>,
     pub replica_pool: ConnectionPool<Core>,
+    /// Key used to sign headers of created snapshots. If not set, snapshots are not signed.
+    pub header_signing_key: Option<K256PrivateKey>,
+    /// Updater for the health check reporting snapshot creation progress.
+    pub health_updater: HealthUpdater,
     #[cfg(test)]
     pub event_listener: Box<dyn HandleEvent>,
 }
@@ -79,26 +140,104 @@ impl SnapshotCreator {
 
     async fn process_storage_logs_single_chunk(
         &self,
-        semaphore: &Semaphore,
+        query_limiter: &QueryLimiter,
         progress: &SnapshotProgress,
+        progress_tracker: &ChunkProgressTracker<'_>,
         l2_block_number: L2BlockNumber,
+        base_l2_block_number: Option<L2BlockNumber>,
         chunk_id: u64,
     ) -> anyhow::Result<()> {
         let chunk_count = progress.chunk_count;
         let l1_batch_number = progress.l1_batch_number;
 
-        let _permit = semaphore.acquire().await?;
+        let _permit = query_limiter.semaphore.acquire().await?;
         #[cfg(test)]
         if self.event_listener.on_chunk_started().should_exit() {
             return Ok(());
         }
 
-        let hashed_keys_range = uniform_hashed_keys_chunk(chunk_id, chunk_count);
+        let uploaded_chunk =
+            if let Some(&content_hash) = progress.pending_chunk_hashes.get(&chunk_id) {
+                self.find_uploaded_storage_logs_chunk(
+                    progress.version,
+                    chunk_id,
+                    l1_batch_number,
+                    content_hash,
+                )
+                .await
+                .map(|filepath| (filepath, content_hash))
+            } else {
+                None
+            };
+        let (output_filepath, content_hash) = if let Some(uploaded_chunk) = uploaded_chunk {
+            tracing::info!(
+                "Reusing chunk {chunk_id} uploaded before restart from location: {}",
+                uploaded_chunk.0
+            );
+            METRICS.reused_storage_logs_chunks.inc();
+            uploaded_chunk
+        } else {
+            let (output_filepath, content_hash) = self
+                .create_storage_logs_chunk(
+                    query_limiter,
+                    progress,
+                    l2_block_number,
+                    base_l2_block_number,
+                    chunk_id,
+                )
+                .await?;
+            #[cfg(test)]
+            if self.event_listener.on_chunk_uploaded().should_exit() {
+                return Ok(());
+            }
+            (output_filepath, content_hash)
+        };
+
+        let mut master_conn = self
+            .master_pool
+            .connection_tagged("snapshots_creator")
+            .await?;
+        master_conn
+            .snapshots_dal()
+            .add_storage_logs_filepath_for_snapshot(
+                l1_batch_number,
+                chunk_id,
+                &output_filepath,
+                content_hash,
+            )
+            .await?;
+        #[cfg(test)]
+        self.event_listener.on_chunk_saved();
+
+        let details = progress_tracker.chunk_processed();
+        tracing::info!(
+            "Saved chunk {chunk_id} to location: {output_filepath} (overall progress {}/{chunk_count}, {:.1}%, ETA: {})",
+            details.processed_chunk_count,
+            details.percent_complete,
+            details
+                .eta_sec
+                .map_or_else(|| "unknown".to_owned(), |eta| format!("{eta}s"))
+        );
+        Ok(())
+    }
+
+    /// Loads a storage logs chunk from the replica Postgres and uploads it to the object store.
+    async fn create_storage_logs_chunk(
+        &self,
+        query_limiter: &QueryLimiter,
+        progress: &SnapshotProgress,
+        l2_block_number: L2BlockNumber,
+        base_l2_block_number: Option<L2BlockNumber>,
+        chunk_id: u64,
+    ) -> anyhow::Result<(String, H256)> {
+        let l1_batch_number = progress.l1_batch_number;
+        let hashed_keys_range = uniform_hashed_keys_chunk(chunk_id, progress.chunk_count);
+        query_limiter.wait_for_rate_limit().await;
         let mut conn = self.connect_to_replica().await?;
 
         let latency =
             METRICS.storage_logs_processing_duration[&StorageChunkStage::LoadFromPostgres].start();
-        let (output_filepath, latency) = match progress.version {
+        let (output_filepath, content_hash, latency) = match progress.version {
             SnapshotVersion::Version0 => {
                 #[allow(deprecated)] // support of version 0 snapshots will be removed eventually
                 let logs = conn
@@ -136,39 +275,61 @@ impl SnapshotCreator {
                 self.store_storage_logs_chunk(l1_batch_number, chunk_id, logs)
                     .await?
             }
-        };
-
-        let mut master_conn = self
-            .master_pool
-            .connection_tagged("snapshots_creator")
-            .await?;
-        master_conn
-            .snapshots_dal()
-            .add_storage_logs_filepath_for_snapshot(l1_batch_number, chunk_id, &output_filepath)
-            .await?;
-        #[cfg(test)]
-        self.event_listener.on_chunk_saved();
+            SnapshotVersion::Version2 => {
+                let base_l2_block_number =
+                    base_l2_block_number.context("delta snapshot doesn't have a base snapshot")?;
+                let logs = conn
+                    .snapshots_creator_dal()
+                    .get_storage_logs_delta_chunk(
+                        base_l2_block_number,
+                        l2_block_number,
+                        l1_batch_number,
+                        hashed_keys_range,
+                    )
+                    .await
+                    .context("error fetching storage logs")?;
+                drop(conn);
 
-        let tasks_left = METRICS.storage_logs_chunks_left_to_process.dec_by(1) - 1;
-        tracing::info!(
-            "Saved chunk {chunk_id} (overall progress {}/{chunk_count}) in {latency:?} to location: {output_filepath}",
-            chunk_count - tasks_left as u64
-        );
-        Ok(())
+                let latency = latency.observe();
+                tracing::info!(
+                    "Loaded delta chunk {chunk_id} ({} logs) from Postgres in {latency:?}",
+                    logs.len()
+                );
+                self.store_storage_logs_chunk(l1_batch_number, chunk_id, logs)
+                    .await?
+            }
+        };
+        tracing::info!("Uploaded chunk {chunk_id} in {latency:?}");
+        Ok((output_filepath, content_hash))
     }
 
+    /// Stores a storage logs chunk in the object store. The content hash of the chunk is recorded in Postgres
+    /// *before* the upload, so that the uploaded chunk can be reused if the creator is restarted
+    /// before the chunk filepath is recorded.
     async fn store_storage_logs_chunk<K>(
         &self,
         l1_batch_number: L1BatchNumber,
         chunk_id: u64,
         logs: Vec<SnapshotStorageLog<K>>,
-    ) -> anyhow::Result<(String, Duration)>
+    ) -> anyhow::Result<(String, H256, Duration)>
     where
+        K: SnapshotStorageLogKey,
         for<'a> SnapshotStorageLogsChunk<K>: StoredObject<Key<'a> = SnapshotStorageLogsStorageKey>,
     {
+        let storage_logs_chunk = SnapshotStorageLogsChunk { storage_logs: logs };
+        let content_hash = storage_logs_chunk.content_hash();
+        let mut master_conn = self
+            .master_pool
+            .connection_tagged("snapshots_creator")
+            .await?;
+        master_conn
+            .snapshots_dal()
+            .set_storage_logs_chunk_hash(l1_batch_number, chunk_id, content_hash)
+            .await?;
+        drop(master_conn);
+
         let latency =
             METRICS.storage_logs_processing_duration[&StorageChunkStage::SaveToGcs].start();
-        let storage_logs_chunk = SnapshotStorageLogsChunk { storage_logs: logs };
         let key = SnapshotStorageLogsStorageKey {
             l1_batch_number,
             chunk_id,
@@ -178,28 +339,97 @@ impl SnapshotCreator {
             .put(key, &storage_logs_chunk)
             .await
             .context("Error storing storage logs chunk in blob store")?;
+        let output_filepath = self.storage_logs_chunk_filepath::<K>(&filename);
+        let latency = latency.observe();
+        Ok((output_filepath, content_hash, latency))
+    }
+
+    fn storage_logs_chunk_filepath<K>(&self, filename: &str) -> String
+    where
+        SnapshotStorageLogsChunk<K>: StoredObject,
+    {
         let output_filepath_prefix = self
             .blob_store
             .get_storage_prefix::<SnapshotStorageLogsChunk<K>>();
-        let output_filepath = format!("{output_filepath_prefix}/{filename}");
-        let latency = latency.observe();
-        Ok((output_filepath, latency))
+        format!("{output_filepath_prefix}/{filename}")
+    }
+
+    /// Checks whether a storage logs chunk with the specified content hash was uploaded to the object store
+    /// before the creator was restarted. Returns the chunk filepath if the uploaded chunk can be reused.
+    async fn find_uploaded_storage_logs_chunk(
+        &self,
+        version: SnapshotVersion,
+        chunk_id: u64,
+        l1_batch_number: L1BatchNumber,
+        expected_hash: H256,
+    ) -> Option<String> {
+        let key = SnapshotStorageLogsStorageKey {
+            l1_batch_number,
+            chunk_id,
+        };
+        let result = match version {
+            SnapshotVersion::Version0 => {
+                self.load_uploaded_storage_logs_chunk::<StorageKey>(key)
+                    .await
+            }
+            SnapshotVersion::Version1 | SnapshotVersion::Version2 => {
+                self.load_uploaded_storage_logs_chunk::<H256>(key).await
+            }
+        };
+
+        match result {
+            Ok((filepath, content_hash)) if content_hash == expected_hash => Some(filepath),
+            Ok((_, content_hash)) => {
+                tracing::info!(
+                    "Uploaded chunk {chunk_id} has unexpected content hash {content_hash:?} (expected: {expected_hash:?}); \
+                     the chunk will be re-created"
+                );
+                None
+            }
+            Err(ObjectStoreError::KeyNotFound(_)) => None,
+            Err(err) => {
+                tracing::warn!(
+                    "Failed loading uploaded chunk {chunk_id}; the chunk will be re-created: {err}"
+                );
+                None
+            }
+        }
+    }
+
+    async fn load_uploaded_storage_logs_chunk<K>(
+        &self,
+        key: SnapshotStorageLogsStorageKey,
+    ) -> Result<(String, H256), ObjectStoreError>
+    where
+        K: SnapshotStorageLogKey,
+        for<'a> SnapshotStorageLogsChunk<K>: StoredObject<Key<'a> = SnapshotStorageLogsStorageKey>,
+    {
+        let chunk: SnapshotStorageLogsChunk<K> = self.blob_store.get(key).await?;
+        let filename = SnapshotStorageLogsChunk::<K>::encode_key(key);
+        let filepath = self.storage_logs_chunk_filepath::<K>(&filename);
+        Ok((filepath, chunk.content_hash()))
     }
 
     async fn process_factory_deps(
         &self,
         l2_block_number: L2BlockNumber,
+        base_l2_block_number: Option<L2BlockNumber>,
         l1_batch_number: L1BatchNumber,
-    ) -> anyhow::Result<String> {
+    ) -> anyhow::Result<(String, H256)> {
         let mut conn = self.connect_to_replica().await?;
 
         tracing::info!("Loading factory deps from Postgres...");
         let latency =
             METRICS.factory_deps_processing_duration[&FactoryDepsStage::LoadFromPostgres].start();
-        let factory_deps = conn
-            .snapshots_creator_dal()
-            .get_all_factory_deps(l2_block_number)
-            .await?;
+        let factory_deps = if let Some(base_l2_block_number) = base_l2_block_number {
+            conn.snapshots_creator_dal()
+                .get_factory_deps_in_range(base_l2_block_number + 1..=l2_block_number)
+                .await?
+        } else {
+            conn.snapshots_creator_dal()
+                .get_all_factory_deps(l2_block_number)
+                .await?
+        };
         drop(conn);
         let latency = latency.observe();
         tracing::info!("Loaded {} factory deps in {latency:?}", factory_deps.len());
@@ -215,6 +445,7 @@ impl SnapshotCreator {
             })
             .collect();
         let factory_deps = SnapshotFactoryDependencies { factory_deps };
+        let content_hash = factory_deps.content_hash();
         let filename = self
             .blob_store
             .put(l1_batch_number, &factory_deps)
@@ -230,7 +461,117 @@ impl SnapshotCreator {
             factory_deps.factory_deps.len()
         );
 
-        Ok(output_filepath)
+        Ok((output_filepath, content_hash))
+    }
+
+    /// Signs the header of the snapshot if the snapshot is complete and the signing key is configured.
+    async fn sign_snapshot_header(
+        &self,
+        l1_batch_number: L1BatchNumber,
+        l2_block_number: L2BlockNumber,
+    ) -> anyhow::Result<()> {
+        let Some(signing_key) = &self.header_signing_key else {
+            return Ok(());
+        };
+
+        let mut master_conn = self
+            .master_pool
+            .connection_tagged("snapshots_creator")
+            .await?;
+        let metadata = master_conn
+            .snapshots_dal()
+            .get_snapshot_metadata(l1_batch_number)
+            .await?
+            .with_context(|| format!("snapshot for L1 batch #{l1_batch_number} disappeared"))?;
+        let Some(mut header) = SnapshotHeader::new(metadata, l2_block_number) else {
+            tracing::info!(
+                "Snapshot for L1 batch #{l1_batch_number} is incomplete; not signing its header"
+            );
+            return Ok(());
+        };
+        header
+            .sign(signing_key)
+            .context("failed signing snapshot header")?;
+        // `unwrap()` is safe: the signature is set by `sign()`
+        let signature = header.signature.as_ref().unwrap();
+        master_conn
+            .snapshots_dal()
+            .set_header_signature(l1_batch_number, signature)
+            .await?;
+        tracing::info!(
+            "Signed header of snapshot for L1 batch #{l1_batch_number} with key for {:?}",
+            signing_key.address()
+        );
+        Ok(())
+    }
+
+    /// Signs the header of a complete snapshot if it isn't signed yet, e.g. because the creator was restarted
+    /// after producing the last chunk of the snapshot, but before signing its header.
+    async fn sign_complete_snapshot_header(
+        &self,
+        snapshot: &SnapshotMetadata,
+    ) -> anyhow::Result<()> {
+        if snapshot.header_signature.is_some() || self.header_signing_key.is_none() {
+            return Ok(());
+        }
+
+        let l1_batch_number = snapshot.l1_batch_number;
+        let (_, last_l2_block_number_in_batch) = self
+            .connect_to_replica()
+            .await?
+            .blocks_dal()
+            .get_l2_block_range_of_l1_batch(l1_batch_number)
+            .await?
+            .context("No L2 blocks for L1 batch")?;
+        tracing::info!(
+            "Snapshot for L1 batch #{l1_batch_number} is complete, but its header is not signed"
+        );
+        self.sign_snapshot_header(l1_batch_number, last_l2_block_number_in_batch)
+            .await
+    }
+
+    /// Selects the base snapshot for a delta snapshot at `l1_batch_number`. Returns `None` if there is no suitable
+    /// base snapshot, or if the delta chain would become too long.
+    async fn select_delta_base(
+        config: &SnapshotsCreatorConfig,
+        l1_batch_number: L1BatchNumber,
+        conn: &mut Connection<'_, Core>,
+    ) -> anyhow::Result<Option<L1BatchNumber>> {
+        let complete_snapshots = conn.snapshots_dal().get_all_complete_snapshots().await?;
+        let Some(base_l1_batch_number) = complete_snapshots
+            .snapshots_l1_batch_numbers
+            .into_iter()
+            .find(|&number| number < l1_batch_number)
+        else {
+            tracing::info!("No complete snapshots before L1 batch #{l1_batch_number}");
+            return Ok(None);
+        };
+
+        // Compute the number of delta snapshots in the base chain, including the created snapshot.
+        let mut chain_length = 1;
+        let mut current = base_l1_batch_number;
+        loop {
+            let metadata = conn
+                .snapshots_dal()
+                .get_snapshot_metadata(current)
+                .await?
+                .with_context(|| format!("snapshot for L1 batch #{current} disappeared"))?;
+            let Some(base) = metadata.base_l1_batch_number else {
+                break;
+            };
+            chain_length += 1;
+            current = base;
+        }
+
+        if chain_length > config.max_delta_chain_length {
+            tracing::info!(
+                "Delta snapshot on top of snapshot for L1 batch #{base_l1_batch_number} would have chain length \
+                 {chain_length}, which exceeds the configured maximum {}",
+                config.max_delta_chain_length
+            );
+            return Ok(None);
+        }
+        Ok(Some(base_l1_batch_number))
     }
 
     /// Returns `Ok(None)` if the created snapshot would coincide with `latest_snapshot`.
@@ -240,8 +581,18 @@ impl SnapshotCreator {
         min_chunk_count: u64,
         conn: &mut Connection<'_, Core>,
     ) -> anyhow::Result<Option<SnapshotProgress>> {
-        let snapshot_version = SnapshotVersion::try_from(config.version)
+        let mut snapshot_version = SnapshotVersion::try_from(config.version)
             .context("invalid snapshot version specified in config")?;
+        let base_l1_batch_number = if snapshot_version.is_delta() {
+            let base = Self::select_delta_base(config, l1_batch_number, conn).await?;
+            if base.is_none() {
+                tracing::info!("Creating a full snapshot for L1 batch #{l1_batch_number} instead of a delta one");
+                snapshot_version = SnapshotVersion::Version1;
+            }
+            base
+        } else {
+            None
+        };
 
         // Sanity check: the selected L1 batch should have Merkle tree data; otherwise, it could be impossible
         // to recover from the generated snapshot.
@@ -255,10 +606,26 @@ impl SnapshotCreator {
                 )
             })?;
 
-        let distinct_storage_logs_keys_count = conn
-            .snapshots_creator_dal()
-            .get_distinct_storage_logs_keys_count(l1_batch_number)
-            .await?;
+        let distinct_storage_logs_keys_count =
+            if let Some(base_l1_batch_number) = base_l1_batch_number {
+                let (_, base_l2_block_number) = conn
+                    .blocks_dal()
+                    .get_l2_block_range_of_l1_batch(base_l1_batch_number)
+                    .await?
+                    .context("No L2 blocks for base L1 batch")?;
+                let (_, l2_block_number) = conn
+                    .blocks_dal()
+                    .get_l2_block_range_of_l1_batch(l1_batch_number)
+                    .await?
+                    .context("No L2 blocks for L1 batch")?;
+                conn.snapshots_creator_dal()
+                    .get_storage_logs_row_count_in_range(base_l2_block_number + 1..=l2_block_number)
+                    .await?
+            } else {
+                conn.snapshots_creator_dal()
+                    .get_distinct_storage_logs_keys_count(l1_batch_number)
+                    .await?
+            };
         let chunk_size = config.storage_logs_chunk_size;
         // We force the minimum number of chunks to avoid situations where only one chunk is created in tests.
         let chunk_count = distinct_storage_logs_keys_count
@@ -272,6 +639,7 @@ impl SnapshotCreator {
         Ok(Some(SnapshotProgress::new(
             snapshot_version,
             l1_batch_number,
+            base_l1_batch_number,
             chunk_count,
         )))
     }
@@ -282,22 +650,27 @@ impl SnapshotCreator {
         config: &SnapshotsCreatorConfig,
         min_chunk_count: u64,
     ) -> anyhow::Result<Option<SnapshotProgress>> {
+        // Snapshot data is loaded from the replica, so the L1 batch is selected based on the replica state.
+        // This allows creating snapshots using a replica lagging behind the main node.
+        let sealed_l1_batch_number = self
+            .connect_to_replica()
+            .await?
+            .blocks_dal()
+            .get_sealed_l1_batch_number()
+            .await?;
+        let sealed_l1_batch_number =
+            sealed_l1_batch_number.context("No L1 batches in replica Postgres")?;
+
         let mut master_conn = self
             .master_pool
             .connection_tagged("snapshots_creator")
             .await?;
-
-        let sealed_l1_batch_number = master_conn
-            .blocks_dal()
-            .get_sealed_l1_batch_number()
-            .await?;
-        let sealed_l1_batch_number = sealed_l1_batch_number.context("No L1 batches in Postgres")?;
         let (requested_l1_batch_number, existing_snapshot) = if let Some(l1_batch_number) =
             config.l1_batch_number
         {
             anyhow::ensure!(
                 l1_batch_number <= sealed_l1_batch_number,
-                "Requested a snapshot for L1 batch #{l1_batch_number} that doesn't exist in Postgres (latest L1 batch: {sealed_l1_batch_number})"
+                "Requested a snapshot for L1 batch #{l1_batch_number} that doesn't exist in replica Postgres (latest L1 batch: {sealed_l1_batch_number})"
             );
 
             let existing_snapshot = master_conn
@@ -312,20 +685,25 @@ impl SnapshotCreator {
                 "Cannot create snapshot when only the genesis L1 batch is present in Postgres"
             );
             let requested_l1_batch_number = sealed_l1_batch_number - 1;
-
-            // Continue creating a pending snapshot if it exists, even if it doesn't correspond to the latest L1 batch.
-            // OTOH, a completed snapshot does not matter, unless it corresponds to `requested_l1_batch_number` (in which case it doesn't need to be created again).
-            let existing_snapshot = master_conn
+            let newest_snapshot = master_conn
                 .snapshots_dal()
                 .get_newest_snapshot_metadata()
-                .await?
-                .filter(|snapshot| {
-                    !snapshot.is_complete() || snapshot.l1_batch_number == requested_l1_batch_number
-                });
-            (requested_l1_batch_number, existing_snapshot)
+                .await?;
+            (requested_l1_batch_number, newest_snapshot)
         };
         drop(master_conn);
 
+        if let Some(snapshot) = existing_snapshot.as_ref() {
+            if snapshot.is_complete() {
+                self.sign_complete_snapshot_header(snapshot).await?;
+            }
+        }
+        // Continue creating a pending snapshot if it exists, even if it doesn't correspond to the latest L1 batch.
+        // OTOH, a completed snapshot does not matter, unless it corresponds to `requested_l1_batch_number` (in which case it doesn't need to be created again).
+        let existing_snapshot = existing_snapshot.filter(|snapshot| {
+            !snapshot.is_complete() || snapshot.l1_batch_number == requested_l1_batch_number
+        });
+
         match existing_snapshot {
             Some(snapshot) if snapshot.is_complete() => {
                 tracing::info!("Snapshot for the requested L1 batch is complete: {snapshot:?}");
@@ -369,6 +747,21 @@ impl SnapshotCreator {
             .get_l2_block_range_of_l1_batch(progress.l1_batch_number)
             .await?
             .context("No L2 blocks for L1 batch")?;
+        let base_l2_block_number = if let Some(base_l1_batch_number) = progress.base_l1_batch_number
+        {
+            let (_, base_l2_block_number) = conn
+                .blocks_dal()
+                .get_l2_block_range_of_l1_batch(base_l1_batch_number)
+                .await?
+                .context("No L2 blocks for base L1 batch")?;
+            tracing::info!(
+                "Creating delta snapshot on top of snapshot for L1 batch #{base_l1_batch_number} \
+                 (L2 block {base_l2_block_number})"
+            );
+            Some(base_l2_block_number)
+        } else {
+            None
+        };
         drop(conn);
 
         METRICS.storage_logs_chunks_count.set(progress.chunk_count);
@@ -379,42 +772,68 @@ impl SnapshotCreator {
         );
 
         if progress.is_new_snapshot {
-            let factory_deps_output_file = self
-                .process_factory_deps(last_l2_block_number_in_batch, progress.l1_batch_number)
+            let (factory_deps_output_file, factory_deps_hash) = self
+                .process_factory_deps(
+                    last_l2_block_number_in_batch,
+                    base_l2_block_number,
+                    progress.l1_batch_number,
+                )
                 .await?;
 
             let mut master_conn = self
                 .master_pool
                 .connection_tagged("snapshots_creator")
                 .await?;
-            master_conn
-                .snapshots_dal()
-                .add_snapshot(
+            let mut dal = master_conn.snapshots_dal();
+            if let Some(base_l1_batch_number) = progress.base_l1_batch_number {
+                dal.add_delta_snapshot(
+                    progress.l1_batch_number,
+                    base_l1_batch_number,
+                    progress.chunk_count,
+                    &factory_deps_output_file,
+                    factory_deps_hash,
+                )
+                .await?;
+            } else {
+                dal.add_snapshot(
                     progress.version,
                     progress.l1_batch_number,
                     progress.chunk_count,
                     &factory_deps_output_file,
+                    factory_deps_hash,
                 )
                 .await?;
+            }
         }
 
-        METRICS
-            .storage_logs_chunks_left_to_process
-            .set(progress.remaining_chunk_ids.len());
-        let semaphore = Semaphore::new(config.concurrent_queries_count as usize);
+        let query_limiter = QueryLimiter::new(
+            config.concurrent_queries_count,
+            config.max_storage_logs_queries_per_minute,
+        );
+        let progress_tracker = ChunkProgressTracker::new(
+            &self.health_updater,
+            progress.l1_batch_number,
+            progress.version.into(),
+            progress.chunk_count,
+            progress.remaining_chunk_ids.len() as u64,
+        );
         let tasks = progress
             .remaining_chunk_ids
             .iter()
             .copied()
             .map(|chunk_id| {
                 self.process_storage_logs_single_chunk(
-                    &semaphore,
+                    &query_limiter,
                     &progress,
+                    &progress_tracker,
                     last_l2_block_number_in_batch,
+                    base_l2_block_number,
                     chunk_id,
                 )
             });
         futures::future::try_join_all(tasks).await?;
+        self.sign_snapshot_header(progress.l1_batch_number, last_l2_block_number_in_batch)
+            .await?;
 
         METRICS
             .snapshot_l1_batch
@@ -427,6 +846,8 @@ impl SnapshotCreator {
             "storage_logs_chunks_count: {}",
             METRICS.storage_logs_chunks_count.get()
         );
+        // Keep the final progress in the health check.
+        self.health_updater.freeze();
         Ok(())
     }
 }
@@ -0,0 +1,101 @@
+//! Progress reporting for the snapshot creator.
+
+use std::{
+    sync::atomic::{AtomicU64, Ordering},
+    time::{Duration, Instant},
+};
+
+use serde::Serialize;
+use zksync_health_check::{Health, HealthStatus, HealthUpdater};
+use zksync_types::L1BatchNumber;
+
+use crate::metrics::METRICS;
+
+/// Health check details reported by the snapshot creator.
+#[derive(Debug, Serialize)]
+pub(crate) struct SnapshotCreatorHealthDetails {
+    pub l1_batch_number: L1BatchNumber,
+    pub version: u16,
+    pub chunk_count: u64,
+    pub processed_chunk_count: u64,
+    pub percent_complete: f64,
+    /// Estimated time to completion in seconds. Only available after at least one chunk is processed
+    /// during the current run.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub eta_sec: Option<u64>,
+}
+
+/// Tracks progress of processing storage log chunks and reports it via the health check and metrics.
+#[derive(Debug)]
+pub(crate) struct ChunkProgressTracker<'a> {
+    health_updater: &'a HealthUpdater,
+    l1_batch_number: L1BatchNumber,
+    version: u16,
+    chunk_count: u64,
+    /// Number of chunks processed before the current run (i.e., recovered from Postgres).
+    initially_processed_count: u64,
+    processed_in_run_count: AtomicU64,
+    started_at: Instant,
+}
+
+impl<'a> ChunkProgressTracker<'a> {
+    pub fn new(
+        health_updater: &'a HealthUpdater,
+        l1_batch_number: L1BatchNumber,
+        version: u16,
+        chunk_count: u64,
+        remaining_chunk_count: u64,
+    ) -> Self {
+        let this = Self {
+            health_updater,
+            l1_batch_number,
+            version,
+            chunk_count,
+            initially_processed_count: chunk_count - remaining_chunk_count,
+            processed_in_run_count: AtomicU64::new(0),
+            started_at: Instant::now(),
+        };
+        this.report(0);
+        this
+    }
+
+    /// Records a processed chunk and returns the updated progress details.
+    pub fn chunk_processed(&self) -> SnapshotCreatorHealthDetails {
+        let processed_in_run_count =
+            self.processed_in_run_count.fetch_add(1, Ordering::Relaxed) + 1;
+        self.report(processed_in_run_count)
+    }
+
+    fn report(&self, processed_in_run_count: u64) -> SnapshotCreatorHealthDetails {
+        let processed_chunk_count = self.initially_processed_count + processed_in_run_count;
+        let remaining_chunk_count = self.chunk_count - processed_chunk_count;
+        let eta = (processed_in_run_count > 0).then(|| {
+            self.started_at
+                .elapsed()
+                .mul_f64(remaining_chunk_count as f64 / processed_in_run_count as f64)
+        });
+
+        METRICS
+            .storage_logs_chunks_left_to_process
+            .set(remaining_chunk_count as usize);
+        if let Some(eta) = eta {
+            METRICS.storage_logs_processing_eta.set(eta);
+        }
+
+        let details = SnapshotCreatorHealthDetails {
+            l1_batch_number: self.l1_batch_number,
+            version: self.version,
+            chunk_count: self.chunk_count,
+            processed_chunk_count,
+            percent_complete: if self.chunk_count == 0 {
+                100.0
+            } else {
+                processed_chunk_count as f64 * 100.0 / self.chunk_count as f64
+            },
+            eta_sec: eta.as_ref().map(Duration::as_secs),
+        };
+        self.health_updater
+            .update(Health::from(HealthStatus::Ready).with_details(&details));
+        details
+    }
+}
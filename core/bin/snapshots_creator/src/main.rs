@@ -3,27 +3,37 @@
 //! # Assumptions
 //!
 //! The snapshot creator is fault-tolerant; if it stops in the middle of creating a snapshot,
-//! this snapshot will be continued from roughly the same point after the restart. If this is
-//! undesired, remove the `snapshots` table record corresponding to the pending snapshot.
+//! this snapshot will be continued from roughly the same point after the restart. Storage log chunks
+//! that were uploaded to the object store, but not recorded in Postgres before the restart are reused
+//! if their content hash matches the one recorded before the upload. If resuming is undesired,
+//! remove the `snapshots` table record corresponding to the pending snapshot.
+//!
+//! Snapshot data is loaded from the replica Postgres (if configured), so the creator can run
+//! while the main node is running. The load on the replica can be limited using
+//! the `max_storage_logs_queries_per_minute` config param.
 //!
 //! It is assumed that the snapshot creator is run as a singleton process (no more than 1 instance
 //! at a time).
 
+use std::{net::SocketAddr, sync::Arc};
+
 use anyhow::Context as _;
 use structopt::StructOpt;
 use tokio::{sync::watch, task::JoinHandle};
 use zksync_config::{
-    configs::{DatabaseSecrets, PrometheusConfig},
+    configs::{DatabaseSecrets, PrometheusConfig, SnapshotsCreatorSecrets},
     full_config_schema,
     sources::ConfigFilePaths,
     ConfigRepositoryExt, SnapshotsCreatorConfig,
 };
 use zksync_dal::{ConnectionPool, Core};
+use zksync_health_check::{server::HealthCheckHandle, AppHealthCheck, ReactiveHealthCheck};
 use zksync_object_store::ObjectStoreFactory;
 
 use crate::creator::SnapshotCreator;
 
 mod creator;
+mod health;
 mod metrics;
 #[cfg(test)]
 mod tests;
@@ -42,6 +52,24 @@ fn maybe_enable_prometheus_metrics(
     }
 }
 
+fn maybe_enable_healthcheck_server(
+    port: Option<u16>,
+    health_check: ReactiveHealthCheck,
+) -> anyhow::Result<Option<HealthCheckHandle>> {
+    let Some(port) = port else {
+        tracing::info!("Starting without healthcheck server");
+        return Ok(None);
+    };
+    let app_health_check = Arc::new(AppHealthCheck::new(None, None));
+    app_health_check.insert_component(health_check)?;
+    let bind_address = SocketAddr::from(([0, 0, 0, 0], port));
+    tracing::info!("Starting healthcheck server on {bind_address}");
+    Ok(Some(HealthCheckHandle::spawn_server(
+        bind_address,
+        app_health_check,
+    )))
+}
+
 /// Minimum number of storage log chunks to produce.
 const MIN_CHUNK_COUNT: u64 = 10;
 
@@ -75,11 +103,15 @@ async fn main() -> anyhow::Result<()> {
     let schema = full_config_schema(false);
     let repo = config_sources.build_repository(&schema);
     let database_secrets: DatabaseSecrets = repo.parse()?;
+    let creator_secrets: SnapshotsCreatorSecrets = repo.parse()?;
     let creator_config: SnapshotsCreatorConfig = repo.parse()?;
     let prometheus_config: PrometheusConfig = repo.parse()?;
 
     let prometheus_exporter_task =
         maybe_enable_prometheus_metrics(&prometheus_config, stop_receiver);
+    let (health_check, health_updater) = ReactiveHealthCheck::new("snapshots_creator");
+    let healthcheck_handle =
+        maybe_enable_healthcheck_server(creator_config.healthcheck_port, health_check)?;
     tracing::info!("Starting snapshots creator");
 
     let object_store_config = creator_config.object_store.clone();
@@ -102,6 +134,8 @@ async fn main() -> anyhow::Result<()> {
         blob_store,
         master_pool,
         replica_pool,
+        header_signing_key: creator_secrets.header_signing_key,
+        health_updater,
         #[cfg(test)]
         event_listener: Box::new(()),
     };
@@ -114,5 +148,8 @@ async fn main() -> anyhow::Result<()> {
             .await?
             .context("Prometheus did not finish gracefully")?;
     }
+    if let Some(healthcheck_handle) = healthcheck_handle {
+        healthcheck_handle.stop().await;
+    }
     Ok(())
 }
@@ -2,7 +2,9 @@
 
 use std::time::Duration;
 
-use vise::{Buckets, EncodeLabelSet, EncodeLabelValue, Family, Gauge, Histogram, Metrics, Unit};
+use vise::{
+    Buckets, Counter, EncodeLabelSet, EncodeLabelValue, Family, Gauge, Histogram, Metrics, Unit,
+};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue, EncodeLabelSet)]
 #[metrics(label = "stage", rename_all = "snake_case")]
@@ -25,6 +27,12 @@ pub(crate) struct SnapshotsCreatorMetrics {
     pub storage_logs_chunks_count: Gauge<u64>,
     /// Number of chunks left to process for the snapshot being currently generated.
     pub storage_logs_chunks_left_to_process: Gauge<usize>,
+    /// Estimated time to process the remaining chunks for the snapshot being currently generated.
+    #[metrics(unit = Unit::Seconds)]
+    pub storage_logs_processing_eta: Gauge<Duration>,
+    /// Number of storage log chunks that were uploaded to the object store before a restart and were reused
+    /// instead of being queried from Postgres again.
+    pub reused_storage_logs_chunks: Counter,
     /// Total latency of snapshot generation.
     #[metrics(buckets = Buckets::LATENCIES, unit = Unit::Seconds)]
     pub snapshot_generation_duration: Histogram<Duration>,
@@ -3,25 +3,30 @@
 use std::{
     collections::{HashMap, HashSet},
     fmt,
+    num::NonZeroU32,
     sync::{
         atomic::{AtomicUsize, Ordering},
         Arc,
     },
+    time::{Duration, Instant},
 };
 
+use assert_matches::assert_matches;
+use async_trait::async_trait;
 use rand::{thread_rng, Rng};
 use test_casing::test_casing;
 use zksync_config::{ObjectStoreConfig, SnapshotsCreatorConfig};
 use zksync_dal::{Connection, CoreDal};
-use zksync_object_store::{MockObjectStore, ObjectStore};
+use zksync_health_check::{CheckHealth, HealthStatus, ReactiveHealthCheck};
+use zksync_object_store::{Bucket, MockObjectStore, ObjectStore, ObjectStoreError};
 use zksync_types::{
     block::{L1BatchHeader, L1BatchTreeData, L2BlockHeader},
     snapshots::{
-        SnapshotFactoryDependencies, SnapshotFactoryDependency, SnapshotStorageLog,
-        SnapshotStorageLogsChunk, SnapshotStorageLogsStorageKey,
+        SnapshotFactoryDependencies, SnapshotFactoryDependency, SnapshotHeader, SnapshotStorageLog,
+        SnapshotStorageLogsChunk, SnapshotStorageLogsStorageKey, SnapshotVersion,
     },
-    AccountTreeId, Address, L1BatchNumber, L2BlockNumber, ProtocolVersion, StorageKey, StorageLog,
-    H256,
+    AccountTreeId, Address, K256PrivateKey, L1BatchNumber, L2BlockNumber, ProtocolVersion,
+    StorageKey, StorageLog, H256,
 };
 
 use super::*;
@@ -29,9 +34,12 @@ use super::*;
 fn test_config() -> SnapshotsCreatorConfig {
     SnapshotsCreatorConfig {
         version: 1,
+        max_delta_chain_length: 7,
         l1_batch_number: None,
         storage_logs_chunk_size: 1_000_000,
         concurrent_queries_count: 10,
+        max_storage_logs_queries_per_minute: None,
+        healthcheck_port: None,
         object_store: ObjectStoreConfig::for_tests(),
     }
 }
@@ -79,12 +87,25 @@ impl HandleEvent for UnreachableEventListener {
     }
 }
 
+/// Uploads storage log chunks, but exits before recording their filepaths in Postgres, emulating a restart.
+#[derive(Debug)]
+struct ExitOnUploadEventListener;
+
+impl HandleEvent for ExitOnUploadEventListener {
+    fn on_chunk_uploaded(&self) -> TestBehavior {
+        TestBehavior::new(true)
+    }
+}
+
 impl SnapshotCreator {
     fn for_tests(blob_store: Arc<dyn ObjectStore>, pool: ConnectionPool<Core>) -> Self {
+        let (_, health_updater) = ReactiveHealthCheck::new("snapshots_creator");
         Self {
             blob_store,
             master_pool: pool.clone(),
             replica_pool: pool,
+            header_signing_key: None,
+            health_updater,
             event_listener: Box::new(()),
         }
     }
@@ -102,6 +123,13 @@ impl SnapshotCreator {
             ..self
         }
     }
+
+    fn exit_on_chunk_upload(self) -> Self {
+        Self {
+            event_listener: Box::new(ExitOnUploadEventListener),
+            ..self
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -124,6 +152,10 @@ pub(crate) trait HandleEvent: fmt::Debug {
         TestBehavior::new(false)
     }
 
+    fn on_chunk_uploaded(&self) -> TestBehavior {
+        TestBehavior::new(false)
+    }
+
     fn on_chunk_saved(&self) {
         // Do nothing
     }
@@ -131,6 +163,47 @@ pub(crate) trait HandleEvent: fmt::Debug {
 
 impl HandleEvent for () {}
 
+/// Object store wrapper counting uploaded objects.
+#[derive(Debug)]
+struct CountingObjectStore {
+    inner: Arc<dyn ObjectStore>,
+    put_count: AtomicUsize,
+}
+
+impl CountingObjectStore {
+    fn new(inner: Arc<dyn ObjectStore>) -> Self {
+        Self {
+            inner,
+            put_count: AtomicUsize::new(0),
+        }
+    }
+}
+
+#[async_trait]
+impl ObjectStore for CountingObjectStore {
+    async fn get_raw(&self, bucket: Bucket, key: &str) -> Result<Vec<u8>, ObjectStoreError> {
+        self.inner.get_raw(bucket, key).await
+    }
+
+    async fn put_raw(
+        &self,
+        bucket: Bucket,
+        key: &str,
+        value: Vec<u8>,
+    ) -> Result<(), ObjectStoreError> {
+        self.put_count.fetch_add(1, Ordering::SeqCst);
+        self.inner.put_raw(bucket, key, value).await
+    }
+
+    async fn remove_raw(&self, bucket: Bucket, key: &str) -> Result<(), ObjectStoreError> {
+        self.inner.remove_raw(bucket, key).await
+    }
+
+    fn storage_prefix_raw(&self, bucket: Bucket) -> String {
+        self.inner.storage_prefix_raw(bucket)
+    }
+}
+
 fn gen_storage_logs(rng: &mut impl Rng, count: usize) -> Vec<StorageLog> {
     (0..count)
         .map(|_| {
@@ -343,9 +416,19 @@ async fn persisting_snapshot_factory_deps() {
         .unwrap();
     let snapshot_l1_batch_number = L1BatchNumber(8);
 
-    let SnapshotFactoryDependencies { factory_deps } =
+    let factory_deps: SnapshotFactoryDependencies =
         object_store.get(snapshot_l1_batch_number).await.unwrap();
-    let actual_deps: HashSet<_> = factory_deps.into_iter().collect();
+    let snapshot_metadata = conn
+        .snapshots_dal()
+        .get_snapshot_metadata(snapshot_l1_batch_number)
+        .await
+        .unwrap()
+        .expect("No snapshot metadata");
+    assert_eq!(
+        snapshot_metadata.factory_deps_hash,
+        Some(factory_deps.content_hash())
+    );
+    let actual_deps: HashSet<_> = factory_deps.factory_deps.into_iter().collect();
     assert_eq!(actual_deps, expected_outputs.deps);
 }
 
@@ -366,6 +449,90 @@ async fn persisting_snapshot_logs() {
     assert_storage_logs(&*object_store, snapshot_l1_batch_number, &expected_outputs).await;
 }
 
+#[tokio::test]
+async fn persisting_signed_snapshot_with_chunk_hashes() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut rng = thread_rng();
+    let object_store = MockObjectStore::arc();
+    let mut conn = pool.connection().await.unwrap();
+    prepare_postgres(&mut rng, &mut conn, 10).await;
+
+    let signing_key = K256PrivateKey::random();
+    let mut creator = SnapshotCreator::for_tests(object_store.clone(), pool.clone());
+    creator.header_signing_key = Some(signing_key.clone());
+    creator.run(test_config(), MIN_CHUNK_COUNT).await.unwrap();
+    let snapshot_l1_batch_number = L1BatchNumber(8);
+
+    let snapshot_metadata = conn
+        .snapshots_dal()
+        .get_snapshot_metadata(snapshot_l1_batch_number)
+        .await
+        .unwrap()
+        .expect("No snapshot metadata");
+    for (chunk_id, content_hash) in snapshot_metadata.storage_logs_hashes.iter().enumerate() {
+        let key = SnapshotStorageLogsStorageKey {
+            l1_batch_number: snapshot_l1_batch_number,
+            chunk_id: chunk_id as u64,
+        };
+        let chunk: SnapshotStorageLogsChunk = object_store.get(key).await.unwrap();
+        assert_eq!(*content_hash, Some(chunk.content_hash()));
+    }
+
+    let (_, l2_block_number) = conn
+        .blocks_dal()
+        .get_l2_block_range_of_l1_batch(snapshot_l1_batch_number)
+        .await
+        .unwrap()
+        .unwrap();
+    let header = SnapshotHeader::new(snapshot_metadata, l2_block_number).unwrap();
+    header.verify_signature(signing_key.address()).unwrap();
+}
+
+#[tokio::test]
+async fn signing_header_of_complete_snapshot_on_restart() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut rng = thread_rng();
+    let object_store = MockObjectStore::arc();
+    let mut conn = pool.connection().await.unwrap();
+    prepare_postgres(&mut rng, &mut conn, 10).await;
+    let snapshot_l1_batch_number = L1BatchNumber(8);
+
+    // Emulate the creator being restarted after producing the last chunk, but before signing the header.
+    SnapshotCreator::for_tests(object_store.clone(), pool.clone())
+        .run(test_config(), MIN_CHUNK_COUNT)
+        .await
+        .unwrap();
+    let snapshot_metadata = conn
+        .snapshots_dal()
+        .get_snapshot_metadata(snapshot_l1_batch_number)
+        .await
+        .unwrap()
+        .expect("No snapshot metadata");
+    assert!(snapshot_metadata.is_complete());
+    assert!(snapshot_metadata.header_signature.is_none());
+
+    let signing_key = K256PrivateKey::random();
+    let mut creator =
+        SnapshotCreator::for_tests(object_store.clone(), pool.clone()).panic_on_chunk_start();
+    creator.header_signing_key = Some(signing_key.clone());
+    creator.run(test_config(), MIN_CHUNK_COUNT).await.unwrap();
+
+    let snapshot_metadata = conn
+        .snapshots_dal()
+        .get_snapshot_metadata(snapshot_l1_batch_number)
+        .await
+        .unwrap()
+        .expect("No snapshot metadata");
+    let (_, l2_block_number) = conn
+        .blocks_dal()
+        .get_l2_block_range_of_l1_batch(snapshot_l1_batch_number)
+        .await
+        .unwrap()
+        .unwrap();
+    let header = SnapshotHeader::new(snapshot_metadata, l2_block_number).unwrap();
+    header.verify_signature(signing_key.address()).unwrap();
+}
+
 #[tokio::test]
 async fn persisting_snapshot_logs_with_specified_l1_batch() {
     let pool = ConnectionPool::<Core>::test_pool().await;
@@ -455,6 +622,119 @@ async fn persisting_snapshot_logs_for_v0_snapshot() {
     assert_eq!(actual_logs, expected_outputs.storage_logs);
 }
 
+#[tokio::test]
+async fn persisting_delta_snapshot() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut rng = thread_rng();
+    let object_store = MockObjectStore::arc();
+    let mut conn = pool.connection().await.unwrap();
+    let expected_outputs = prepare_postgres(&mut rng, &mut conn, 10).await;
+
+    let base_l1_batch_number = L1BatchNumber(4);
+    let mut config = test_config();
+    config.l1_batch_number = Some(base_l1_batch_number);
+    SnapshotCreator::for_tests(object_store.clone(), pool.clone())
+        .run(config, MIN_CHUNK_COUNT)
+        .await
+        .unwrap();
+
+    let config = SnapshotsCreatorConfig {
+        version: 2,
+        ..test_config()
+    };
+    SnapshotCreator::for_tests(object_store.clone(), pool.clone())
+        .run(config, MIN_CHUNK_COUNT)
+        .await
+        .unwrap();
+
+    let snapshot_l1_batch_number = L1BatchNumber(8);
+    let snapshot_metadata = conn
+        .snapshots_dal()
+        .get_snapshot_metadata(snapshot_l1_batch_number)
+        .await
+        .unwrap()
+        .expect("No snapshot metadata");
+    assert!(snapshot_metadata.is_complete(), "{snapshot_metadata:#?}");
+    assert_eq!(snapshot_metadata.version, SnapshotVersion::Version2);
+    assert_eq!(
+        snapshot_metadata.base_l1_batch_number,
+        Some(base_l1_batch_number)
+    );
+
+    let mut actual_logs = HashSet::new();
+    for chunk_id in 0..snapshot_metadata.storage_logs_filepaths.len() as u64 {
+        let key = SnapshotStorageLogsStorageKey {
+            l1_batch_number: snapshot_l1_batch_number,
+            chunk_id,
+        };
+        let chunk: SnapshotStorageLogsChunk = object_store.get(key).await.unwrap();
+        actual_logs.extend(chunk.storage_logs);
+    }
+    let expected_logs: HashSet<_> = expected_outputs
+        .storage_logs
+        .iter()
+        .filter(|log| {
+            log.l1_batch_number_of_initial_write > base_l1_batch_number
+                && log.l1_batch_number_of_initial_write <= snapshot_l1_batch_number
+        })
+        .cloned()
+        .collect();
+    assert_eq!(actual_logs, expected_logs);
+
+    // Factory deps should only contain deps deployed after the base snapshot (10 per L2 block).
+    let SnapshotFactoryDependencies { factory_deps } =
+        object_store.get(snapshot_l1_batch_number).await.unwrap();
+    assert_eq!(factory_deps.len(), 40);
+    for dep in &factory_deps {
+        assert!(expected_outputs.deps.contains(dep), "{dep:?}");
+    }
+}
+
+#[tokio::test]
+async fn delta_snapshot_falls_back_to_full_snapshot() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut rng = thread_rng();
+    let object_store = MockObjectStore::arc();
+    let mut conn = pool.connection().await.unwrap();
+    let expected_outputs = prepare_postgres(&mut rng, &mut conn, 10).await;
+
+    let config = SnapshotsCreatorConfig {
+        version: 2,
+        max_delta_chain_length: 1,
+        ..test_config()
+    };
+    // The first snapshot has no base, so it must be full. Afterwards, full and delta snapshots should alternate
+    // because of the maximum chain length.
+    for (l1_batch_number, expected_base) in [(2, None), (4, Some(2)), (6, None), (8, Some(6))] {
+        let l1_batch_number = L1BatchNumber(l1_batch_number);
+        let config = SnapshotsCreatorConfig {
+            l1_batch_number: Some(l1_batch_number),
+            ..config.clone()
+        };
+        SnapshotCreator::for_tests(object_store.clone(), pool.clone())
+            .run(config, MIN_CHUNK_COUNT)
+            .await
+            .unwrap();
+
+        let snapshot_metadata = conn
+            .snapshots_dal()
+            .get_snapshot_metadata(l1_batch_number)
+            .await
+            .unwrap()
+            .expect("No snapshot metadata");
+        assert_eq!(
+            snapshot_metadata.base_l1_batch_number,
+            expected_base.map(L1BatchNumber)
+        );
+        if expected_base.is_none() {
+            assert_eq!(snapshot_metadata.version, SnapshotVersion::Version1);
+            assert_storage_logs(&*object_store, l1_batch_number, &expected_outputs).await;
+        } else {
+            assert_eq!(snapshot_metadata.version, SnapshotVersion::Version2);
+        }
+    }
+}
+
 #[test_casing(2, [false, true])]
 #[tokio::test]
 async fn recovery_workflow(specify_batch_after_recovery: bool) {
@@ -622,6 +902,131 @@ async fn recovery_workflow_with_varying_chunk_size() {
     assert_storage_logs(&*object_store, snapshot_l1_batch_number, &expected_outputs).await;
 }
 
+#[tokio::test]
+async fn recovery_workflow_with_uploaded_chunks() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut rng = thread_rng();
+    let object_store = MockObjectStore::arc();
+    let mut conn = pool.connection().await.unwrap();
+    let expected_outputs = prepare_postgres(&mut rng, &mut conn, 10).await;
+
+    // Upload all chunks, but exit before recording their filepaths.
+    SnapshotCreator::for_tests(object_store.clone(), pool.clone())
+        .exit_on_chunk_upload()
+        .run(sequential_test_config(), MIN_CHUNK_COUNT)
+        .await
+        .unwrap();
+
+    let snapshot_l1_batch_number = L1BatchNumber(8);
+    let snapshot_metadata = conn
+        .snapshots_dal()
+        .get_snapshot_metadata(snapshot_l1_batch_number)
+        .await
+        .unwrap()
+        .expect("No snapshot metadata");
+    assert!(snapshot_metadata
+        .storage_logs_filepaths
+        .iter()
+        .all(Option::is_none));
+    assert!(snapshot_metadata
+        .storage_logs_hashes
+        .iter()
+        .all(Option::is_some));
+
+    // Corrupt one of the uploaded chunks; it should be re-created.
+    let corrupted_key = SnapshotStorageLogsStorageKey {
+        l1_batch_number: snapshot_l1_batch_number,
+        chunk_id: 3,
+    };
+    let empty_chunk = SnapshotStorageLogsChunk::<H256> {
+        storage_logs: vec![],
+    };
+    object_store.put(corrupted_key, &empty_chunk).await.unwrap();
+
+    let counting_store = Arc::new(CountingObjectStore::new(object_store.clone()));
+    SnapshotCreator::for_tests(counting_store.clone(), pool.clone())
+        .run(sequential_test_config(), MIN_CHUNK_COUNT)
+        .await
+        .unwrap();
+    assert_eq!(counting_store.put_count.load(Ordering::SeqCst), 1);
+
+    assert_storage_logs(&*object_store, snapshot_l1_batch_number, &expected_outputs).await;
+    let snapshot_metadata = conn
+        .snapshots_dal()
+        .get_snapshot_metadata(snapshot_l1_batch_number)
+        .await
+        .unwrap()
+        .expect("No snapshot metadata");
+    assert!(snapshot_metadata.is_complete(), "{snapshot_metadata:#?}");
+}
+
+#[tokio::test]
+async fn reporting_progress_via_health_check() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut rng = thread_rng();
+    let object_store = MockObjectStore::arc();
+    let mut conn = pool.connection().await.unwrap();
+    prepare_postgres(&mut rng, &mut conn, 10).await;
+
+    let creator = SnapshotCreator::for_tests(object_store.clone(), pool.clone());
+    let health_check = creator.health_updater.subscribe();
+    creator
+        .stop_after_chunk_count(4)
+        .run(sequential_test_config(), MIN_CHUNK_COUNT)
+        .await
+        .unwrap();
+
+    let health = health_check.check_health().await;
+    assert_matches!(health.status(), HealthStatus::Ready);
+    let details = health.details().unwrap();
+    assert_eq!(details["l1_batch_number"], 8);
+    assert_eq!(details["version"], 1);
+    assert_eq!(details["chunk_count"], MIN_CHUNK_COUNT);
+    assert_eq!(details["processed_chunk_count"], 4);
+    assert_eq!(details["percent_complete"], 40.0);
+    assert!(details["eta_sec"].is_u64(), "{details:?}");
+
+    let creator = SnapshotCreator::for_tests(object_store, pool);
+    let health_check = creator.health_updater.subscribe();
+    creator
+        .run(sequential_test_config(), MIN_CHUNK_COUNT)
+        .await
+        .unwrap();
+
+    let health = health_check.check_health().await;
+    assert_matches!(health.status(), HealthStatus::Ready);
+    let details = health.details().unwrap();
+    assert_eq!(details["processed_chunk_count"], MIN_CHUNK_COUNT);
+    assert_eq!(details["percent_complete"], 100.0);
+    assert_eq!(details["eta_sec"], 0);
+}
+
+#[tokio::test]
+async fn rate_limiting_storage_logs_queries() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut rng = thread_rng();
+    let object_store = MockObjectStore::arc();
+    let mut conn = pool.connection().await.unwrap();
+    let expected_outputs = prepare_postgres(&mut rng, &mut conn, 10).await;
+
+    let config = SnapshotsCreatorConfig {
+        // Corresponds to 1 query per 20ms.
+        max_storage_logs_queries_per_minute: NonZeroU32::new(3_000),
+        ..test_config()
+    };
+    let started_at = Instant::now();
+    SnapshotCreator::for_tests(object_store.clone(), pool.clone())
+        .run(config, MIN_CHUNK_COUNT)
+        .await
+        .unwrap();
+    // The first query is not delayed.
+    let min_duration = Duration::from_millis(20) * (MIN_CHUNK_COUNT as u32 - 1);
+    assert!(started_at.elapsed() >= min_duration);
+
+    let snapshot_l1_batch_number = L1BatchNumber(8);
+    assert_storage_logs(&*object_store, snapshot_l1_batch_number, &expected_outputs).await;
+}
+
 #[tokio::test]
 async fn creator_fails_if_specified_l1_batch_is_missing() {
     let pool = ConnectionPool::<Core>::test_pool().await;
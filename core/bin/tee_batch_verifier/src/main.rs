@@ -0,0 +1,190 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::Instant,
+};
+
+use anyhow::Context as _;
+use clap::{Args, Parser, Subcommand};
+use url::Url;
+use zksync_tee_prover_interface::inputs::TeeVerifierInput;
+use zksync_tee_verifier::Verify;
+use zksync_types::{url::SensitiveUrl, L1BatchNumber, H256};
+use zksync_web3_decl::{
+    client::{Client, L2},
+    namespaces::{UnstableNamespaceClient, ZksNamespaceClient},
+};
+
+use crate::signatures::check_tee_proofs;
+
+mod signatures;
+
+/// Re-verifies an L1 batch proven by TEE provers: replays the TEE verifier input for the batch, compares
+/// the resulting root hash with the reference one and checks all TEE proofs submitted for the batch.
+#[derive(Debug, Parser)]
+#[command(
+    author = "Matter Labs",
+    version,
+    about = "TEE batch verifier",
+    long_about = None
+)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Fetches the TEE verifier input for a batch and writes it to a file.
+    Export(ExportArgs),
+    /// Replays the TEE verifier input for a batch and checks the root hash and TEE proof signatures.
+    Verify(VerifyArgs),
+}
+
+#[derive(Debug, Args)]
+struct ExportArgs {
+    /// L1 batch to export inputs for.
+    #[arg(long = "l1-batch")]
+    l1_batch: u32,
+    /// Base URL of the TEE proof data handler, e.g. `http://127.0.0.1:4320`.
+    #[arg(long)]
+    proof_data_handler_url: Url,
+    /// File to write the JSON-serialized verifier input to.
+    #[arg(long, value_name = "FILE")]
+    output: PathBuf,
+}
+
+#[derive(Debug, Args)]
+struct VerifyArgs {
+    /// L1 batch to verify.
+    #[arg(long = "l1-batch")]
+    l1_batch: u32,
+    /// Base URL of the TEE proof data handler to fetch the verifier input from.
+    #[arg(long, required_unless_present = "input", conflicts_with = "input")]
+    proof_data_handler_url: Option<Url>,
+    /// File with the JSON-serialized verifier input, as produced by the `export` command.
+    #[arg(long, value_name = "FILE")]
+    input: Option<PathBuf>,
+    /// L2 JSON-RPC URL of the main node. Used to fetch the reference root hash (via `zks_getL1BatchDetails`)
+    /// and TEE proofs (via `unstable_getTeeProofs`) for the batch.
+    #[arg(long)]
+    rpc_url: SensitiveUrl,
+    /// Reference root hash for the batch, e.g. taken from the commit transaction on L1. If specified,
+    /// it takes precedence over the root hash returned by the main node.
+    #[arg(long)]
+    expected_root_hash: Option<H256>,
+}
+
+impl ExportArgs {
+    async fn run(self) -> anyhow::Result<()> {
+        let l1_batch_number = L1BatchNumber(self.l1_batch);
+        let input = fetch_input(&self.proof_data_handler_url, l1_batch_number).await?;
+        let serialized =
+            serde_json::to_vec(&input).context("failed serializing TEE verifier input")?;
+        fs::write(&self.output, serialized)
+            .with_context(|| format!("failed writing TEE verifier input to {:?}", self.output))?;
+        tracing::info!(
+            "Exported TEE verifier input for L1 batch #{l1_batch_number} to {:?}",
+            self.output
+        );
+        Ok(())
+    }
+}
+
+impl VerifyArgs {
+    async fn run(self) -> anyhow::Result<()> {
+        let l1_batch_number = L1BatchNumber(self.l1_batch);
+        let input = match (&self.input, &self.proof_data_handler_url) {
+            (Some(path), _) => read_input(path)?,
+            (None, Some(url)) => fetch_input(url, l1_batch_number).await?,
+            (None, None) => unreachable!("enforced by clap"),
+        };
+
+        let client: Client<L2> = Client::http(self.rpc_url)
+            .context("failed creating L2 client")?
+            .build();
+        let expected_root_hash = if let Some(root_hash) = self.expected_root_hash {
+            root_hash
+        } else {
+            let details = client
+                .get_l1_batch_details(l1_batch_number)
+                .await
+                .context("failed fetching L1 batch details")?
+                .with_context(|| {
+                    format!("L1 batch #{l1_batch_number} is not known to main node")
+                })?;
+            details.base.root_hash.with_context(|| {
+                format!("root hash for L1 batch #{l1_batch_number} is not computed yet")
+            })?
+        };
+
+        tracing::info!("Replaying TEE verifier input for L1 batch #{l1_batch_number}");
+        let started_at = Instant::now();
+        let TeeVerifierInput::V1(input) = input else {
+            anyhow::bail!("only TeeVerifierInput::V1 verification is supported");
+        };
+        let result = tokio::task::spawn_blocking(move || input.verify())
+            .await
+            .context("verifier panicked")?
+            .context("failed replaying TEE verifier input")?;
+        tracing::info!("Replayed L1 batch in {:?}", started_at.elapsed());
+
+        anyhow::ensure!(
+            result.batch_number == l1_batch_number,
+            "verifier input is for L1 batch #{}, while #{l1_batch_number} was requested",
+            result.batch_number
+        );
+        let root_hash = result.value_hash;
+        anyhow::ensure!(
+            root_hash == expected_root_hash,
+            "root hash mismatch for L1 batch #{l1_batch_number}: replayed {root_hash:?}, expected {expected_root_hash:?}"
+        );
+        tracing::info!("Root hash for L1 batch #{l1_batch_number} matches: {root_hash:?}");
+
+        let proofs = client
+            .tee_proofs(l1_batch_number, None)
+            .await
+            .context("failed fetching TEE proofs")?;
+        check_tee_proofs(&proofs, root_hash)?;
+        tracing::info!(
+            "Verified {} TEE proof(s) for L1 batch #{l1_batch_number}",
+            proofs.len()
+        );
+        Ok(())
+    }
+}
+
+/// Fetches the verifier input for the specified batch from the TEE proof data handler.
+async fn fetch_input(
+    base_url: &Url,
+    l1_batch_number: L1BatchNumber,
+) -> anyhow::Result<TeeVerifierInput> {
+    let url = base_url
+        .join(&format!("/tee/proof_inputs/{l1_batch_number}"))
+        .context("invalid proof data handler URL")?;
+    tracing::info!("Fetching TEE verifier input for L1 batch #{l1_batch_number} from {url}");
+    let response = reqwest::get(url)
+        .await
+        .context("failed requesting TEE verifier input")?
+        .error_for_status()
+        .context("proof data handler returned an error")?;
+    response
+        .json()
+        .await
+        .context("failed deserializing TEE verifier input")
+}
+
+fn read_input(path: &Path) -> anyhow::Result<TeeVerifierInput> {
+    let raw = fs::read(path)
+        .with_context(|| format!("failed reading TEE verifier input from {path:?}"))?;
+    serde_json::from_slice(&raw).context("failed deserializing TEE verifier input")
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let _observability_guard = zksync_vlog::ObservabilityBuilder::new().build();
+    match Cli::parse().command {
+        Command::Export(args) => args.run().await,
+        Command::Verify(args) => args.run().await,
+    }
+}
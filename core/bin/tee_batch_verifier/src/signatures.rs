@@ -0,0 +1,100 @@
+//! Checks for TEE proof signatures.
+
+use anyhow::Context as _;
+use secp256k1::PublicKey;
+use zksync_tee_prover_interface::signatures::verify_root_hash_signature;
+use zksync_types::{api::TeeProof, H256};
+
+/// Checks that `signature` is a signature of `root_hash` made with the private key corresponding to `pubkey`.
+fn check_signature(pubkey: &[u8], signature: &[u8], root_hash: H256) -> anyhow::Result<()> {
+    let pubkey = PublicKey::from_slice(pubkey).context("invalid public key")?;
+    verify_root_hash_signature(&pubkey, signature, root_hash)?;
+    Ok(())
+}
+
+/// Checks signatures of all `proofs` for a batch against its root hash. Proofs that are not submitted yet
+/// (i.e., have no signature) are skipped. Returns an error if there are no submitted proofs or
+/// if any of them has an invalid signature.
+pub(crate) fn check_tee_proofs(proofs: &[TeeProof], root_hash: H256) -> anyhow::Result<()> {
+    let mut submitted_count = 0;
+    let mut errors = vec![];
+    for proof in proofs {
+        let tee_type = proof
+            .tee_type
+            .map_or_else(|| "unknown".to_owned(), |ty| ty.to_string());
+        let (Some(pubkey), Some(signature)) = (&proof.pubkey, &proof.signature) else {
+            tracing::info!(
+                "Skipping {tee_type} proof without signature (status: {})",
+                proof.status
+            );
+            continue;
+        };
+        submitted_count += 1;
+
+        match check_signature(pubkey, signature, root_hash) {
+            Ok(()) => {
+                tracing::info!("{tee_type} proof by 0x{} is valid", hex::encode(pubkey));
+            }
+            Err(err) => {
+                tracing::error!(
+                    "{tee_type} proof by 0x{} is invalid: {err:#}",
+                    hex::encode(pubkey)
+                );
+                errors.push(err);
+            }
+        }
+    }
+
+    anyhow::ensure!(
+        submitted_count > 0,
+        "no TEE proofs are submitted for the batch"
+    );
+    anyhow::ensure!(
+        errors.is_empty(),
+        "{} out of {submitted_count} TEE proof(s) have invalid signatures",
+        errors.len()
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use secp256k1::{SecretKey, SECP256K1};
+    use zksync_tee_prover_interface::signatures::sign_root_hash;
+    use zksync_types::{tee_types::TeeType, L1BatchNumber};
+
+    use super::*;
+
+    fn mock_proof(secret_key: &SecretKey, root_hash: H256) -> TeeProof {
+        let signature = sign_root_hash(secret_key, root_hash);
+        TeeProof {
+            l1_batch_number: L1BatchNumber(1),
+            tee_type: Some(TeeType::Sgx),
+            pubkey: Some(secret_key.public_key(SECP256K1).serialize().to_vec()),
+            signature: Some(signature),
+            proof: None,
+            proved_at: Default::default(),
+            status: "generated".to_owned(),
+            attestation: None,
+            batch_status: None,
+        }
+    }
+
+    #[test]
+    fn checking_tee_proofs() {
+        let secret_key = SecretKey::from_slice(&[1; 32]).unwrap();
+        let root_hash = H256::repeat_byte(0x23);
+        let proof = mock_proof(&secret_key, root_hash);
+        check_tee_proofs(&[proof.clone()], root_hash).unwrap();
+
+        let err = check_tee_proofs(&[proof.clone()], H256::repeat_byte(0x42)).unwrap_err();
+        assert!(err.to_string().contains("invalid signatures"), "{err}");
+
+        let pending_proof = TeeProof {
+            signature: None,
+            ..proof
+        };
+        let err = check_tee_proofs(&[pending_proof], root_hash).unwrap_err();
+        assert!(err.to_string().contains("no TEE proofs"), "{err}");
+    }
+}
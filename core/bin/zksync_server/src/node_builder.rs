@@ -43,14 +43,14 @@ use zksync_gateway_migrator::node::{GatewayMigratorLayer, MainNodeConfig, Settle
 use zksync_house_keeper::node::HouseKeeperLayer;
 use zksync_logs_bloom_backfill::node::LogsBloomBackfillLayer;
 use zksync_metadata_calculator::{
-    node::{MetadataCalculatorLayer, TreeApiClientLayer},
-    MetadataCalculatorConfig,
+    node::{MetadataCalculatorLayer, TreeApiClientLayer, TreeApiServerLayer},
+    MerkleTreeReaderConfig, MetadataCalculatorConfig,
 };
 use zksync_node_api_server::{
     node::{
-        DeploymentAllowListLayer, HealthCheckLayer, MasterPoolSinkLayer, MempoolCacheLayer,
-        PostgresStorageCachesConfig, TxSenderLayer, Web3ServerLayer, Web3ServerOptionalConfig,
-        WhitelistedMasterPoolSinkLayer,
+        AddressDenyListLayer, DeploymentAllowListLayer, HealthCheckLayer, MasterPoolSinkLayer,
+        MempoolCacheLayer, PostgresStorageCachesConfig, TxSenderLayer, Web3ServerLayer,
+        Web3ServerOptionalConfig, WhitelistedMasterPoolSinkLayer,
     },
     tx_sender::TxSenderConfig,
     web3::{state::InternalApiConfigBase, Namespace},
@@ -63,7 +63,7 @@ use zksync_node_storage_init::node::{
 };
 use zksync_object_store::node::ObjectStoreLayer;
 use zksync_proof_data_handler::node::ProofDataHandlerLayer;
-use zksync_state::RocksdbStorageOptions;
+use zksync_state::{RocksdbStorageOptions, StorageCachesBudgetConfig};
 use zksync_state_keeper::node::{
     MainBatchExecutorLayer, MempoolIOLayer, OutputHandlerLayer, StateKeeperLayer,
 };
@@ -71,6 +71,7 @@ use zksync_tee_proof_data_handler::node::TeeProofDataHandlerLayer;
 use zksync_types::{
     commitment::{L1BatchCommitmentMode, PubdataType},
     pubdata_da::PubdataSendingMode,
+    tee_types::TeeQuorumPolicy,
     Address, SHARED_BRIDGE_ETHER_TOKEN_ADDRESS,
 };
 use zksync_vlog::node::{PrometheusExporterLayer, SigintHandlerLayer};
@@ -267,6 +268,16 @@ impl MainNodeBuilder {
         Ok(self)
     }
 
+    /// Adds a standalone Merkle tree API server following the tree updated by another process.
+    fn add_tree_replica_api_layer(mut self) -> anyhow::Result<Self> {
+        let api_config = try_load_config!(self.configs.api_config).merkle_tree;
+        let reader_config =
+            MerkleTreeReaderConfig::for_main_node(&self.configs.db_config.merkle_tree, &api_config);
+        self.node
+            .add_layer(TreeApiServerLayer::new(reader_config, api_config));
+        Ok(self)
+    }
+
     fn add_state_keeper_layer(mut self) -> anyhow::Result<Self> {
         // Bytecode compression is currently mandatory for the transactions processed by the sequencer.
         const OPTIONAL_BYTECODE_COMPRESSION: bool = false;
@@ -343,6 +354,7 @@ impl MainNodeBuilder {
         let gateway_config = try_load_config!(self.configs.prover_gateway);
         self.node.add_layer(ProofDataHandlerLayer::new(
             try_load_config!(self.configs.proof_data_handler_config),
+            self.secrets.proof_data_handler.clone(),
             self.genesis_config.l1_batch_commit_data_generator_mode,
             self.genesis_config.l2_chain_id,
             gateway_config.api_mode,
@@ -350,6 +362,14 @@ impl MainNodeBuilder {
         Ok(self)
     }
 
+    fn tee_quorum_policy(&self) -> TeeQuorumPolicy {
+        self.configs
+            .tee_proof_data_handler_config
+            .as_ref()
+            .map(|config| config.quorum.policy())
+            .unwrap_or_default()
+    }
+
     fn add_tee_proof_data_handler_layer(mut self) -> anyhow::Result<Self> {
         self.node.add_layer(TeeProofDataHandlerLayer::new(
             try_load_config!(self.configs.tee_proof_data_handler_config),
@@ -376,6 +396,17 @@ impl MainNodeBuilder {
         Ok(self)
     }
 
+    fn add_address_deny_list_layer(mut self) -> anyhow::Result<Self> {
+        let deny_list = try_load_config!(self.configs.state_keeper_config).address_deny_list;
+
+        if let Some(deny_list) = deny_list {
+            self.node.add_layer(AddressDenyListLayer {
+                address_deny_list: deny_list,
+            });
+        }
+        Ok(self)
+    }
+
     fn add_bridge_addresses_updater_layer(mut self) -> anyhow::Result<Self> {
         self.node.add_layer(BridgeAddressesUpdaterLayer {
             refresh_interval: Duration::from_secs(30),
@@ -388,11 +419,18 @@ impl MainNodeBuilder {
         let rpc_config = try_load_config!(self.configs.api_config).web3_json_rpc;
         let deployment_allowlist = sk_config.deployment_allowlist.clone();
 
+        let storage_caches_budget = rpc_config.storage_caches_memory_budget_mb.map(|budget| {
+            let mut config = StorageCachesBudgetConfig::new(budget.0);
+            config.resize_interval = rpc_config.storage_caches_resize_interval_sec;
+            config.hot_set_path = rpc_config.storage_caches_hot_set_path.clone();
+            config
+        });
         let postgres_storage_caches_config = PostgresStorageCachesConfig {
             factory_deps_cache_size: rpc_config.factory_deps_cache_size_mb.0,
             initial_writes_cache_size: rpc_config.initial_writes_cache_size_mb.0,
             latest_values_cache_size: rpc_config.latest_values_cache_size_mb.0,
             latest_values_max_block_lag: rpc_config.latest_values_max_block_lag.get(),
+            budget: storage_caches_budget,
         };
         let vm_config = self.configs.experimental_vm_config.clone();
 
@@ -460,11 +498,13 @@ impl MainNodeBuilder {
             batch_request_size_limit: Some(rpc_config.max_batch_request_size),
             response_body_size_limit: Some(rpc_config.max_response_body_size()),
             with_extended_tracing: rpc_config.extended_api_tracing,
+            archive_proxy_url: rpc_config.archive_proxy_url.clone(),
             ..Default::default()
         };
         let http_port = rpc_config.http_port;
         let internal_config_base = InternalApiConfigBase::new(&self.genesis_config, &rpc_config)
-            .with_l1_to_l2_txs_paused(self.configs.mempool_config.l1_to_l2_txs_paused);
+            .with_l1_to_l2_txs_paused(self.configs.mempool_config.l1_to_l2_txs_paused)
+            .with_tee_quorum_policy(self.tee_quorum_policy());
 
         self.node.add_layer(Web3ServerLayer::http(
             http_port,
@@ -505,11 +545,13 @@ impl MainNodeBuilder {
             ),
             replication_lag_limit: circuit_breaker_config.replication_lag_limit_sec,
             with_extended_tracing: rpc_config.extended_api_tracing,
+            archive_proxy_url: rpc_config.archive_proxy_url.clone(),
             ..Default::default()
         };
         let ws_port = rpc_config.ws_port;
         let internal_config_base = InternalApiConfigBase::new(&self.genesis_config, &rpc_config)
-            .with_l1_to_l2_txs_paused(self.configs.mempool_config.l1_to_l2_txs_paused);
+            .with_l1_to_l2_txs_paused(self.configs.mempool_config.l1_to_l2_txs_paused)
+            .with_tee_quorum_policy(self.tee_quorum_policy());
 
         self.node.add_layer(Web3ServerLayer::ws(
             ws_port,
@@ -527,10 +569,16 @@ impl MainNodeBuilder {
     }
 
     fn add_eth_tx_aggregator_layer(mut self) -> anyhow::Result<Self> {
-        self.node.add_layer(EthTxAggregatorLayer::new(
+        let mut layer = EthTxAggregatorLayer::new(
             self.genesis_config.l2_chain_id,
             self.genesis_config.l1_batch_commit_data_generator_mode,
-        ));
+        );
+        if let Some(tee_config) = &self.configs.tee_proof_data_handler_config {
+            if tee_config.quorum.wait_before_execute {
+                layer = layer.with_tee_quorum_policy(tee_config.quorum.policy());
+            }
+        }
+        self.node.add_layer(layer);
 
         Ok(self)
     }
@@ -791,6 +839,7 @@ impl MainNodeBuilder {
                     // which is why we consider it to be responsible for the storage initialization.
                     self = self
                         .add_allow_list_task_layer()?
+                        .add_address_deny_list_layer()?
                         .add_l1_gas_layer()?
                         .add_storage_initialization_layer(LayerKind::Task)?
                         .add_state_keeper_layer()?
@@ -799,6 +848,7 @@ impl MainNodeBuilder {
                 Component::HttpApi => {
                     self = self
                         .add_allow_list_task_layer()?
+                        .add_address_deny_list_layer()?
                         .add_bridge_addresses_updater_layer()?
                         .add_l1_gas_layer()?
                         .add_tx_sender_layer()?
@@ -809,6 +859,7 @@ impl MainNodeBuilder {
                 Component::WsApi => {
                     self = self
                         .add_allow_list_task_layer()?
+                        .add_address_deny_list_layer()?
                         .add_bridge_addresses_updater_layer()?
                         .add_l1_gas_layer()?
                         .add_tx_sender_layer()?
@@ -824,11 +875,20 @@ impl MainNodeBuilder {
                     self = self.add_metadata_calculator_layer(with_tree_api)?;
                 }
                 Component::TreeApi => {
-                    anyhow::ensure!(
-                        components.contains(&Component::Tree),
-                        "Merkle tree API cannot be started without a tree component"
-                    );
-                    // Do nothing, will be handled by the `Tree` component.
+                    if components.contains(&Component::Tree) {
+                        // Do nothing, will be handled by the `Tree` component.
+                    } else {
+                        let has_secondary_path = self
+                            .configs
+                            .api_config
+                            .as_ref()
+                            .is_some_and(|config| config.merkle_tree.secondary_path.is_some());
+                        anyhow::ensure!(
+                            has_secondary_path,
+                            "Merkle tree API cannot be started without a tree component unless `api.merkle_tree.secondary_path` is set"
+                        );
+                        self = self.add_tree_replica_api_layer()?;
+                    }
                 }
                 Component::EthWatcher => {
                     self = self.add_eth_watch_layer()?;
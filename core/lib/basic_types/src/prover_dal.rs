@@ -418,3 +418,44 @@ pub struct ProofGenerationTime {
     pub time_taken: NaiveTime,
     pub created_at: NaiveDateTime,
 }
+
+/// Priority lane of an L1 batch in prover job queues. Jobs of batches in higher lanes are picked
+/// before jobs of batches in lower lanes, regardless of the batch age.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, EnumString, Display,
+)]
+#[strum(serialize_all = "snake_case")]
+pub enum BatchPriority {
+    #[default]
+    Normal = 0,
+    High = 1,
+    Urgent = 2,
+}
+
+impl BatchPriority {
+    pub const ALL: [Self; 3] = [Self::Normal, Self::High, Self::Urgent];
+    /// Highest lane starvation protection can move batches to; the highest lane is reserved for operators.
+    pub const MAX_BOOSTED: Self = Self::High;
+
+    pub fn from_db(lane: i16) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|priority| *priority as i16 == lane)
+    }
+
+    /// Returns the next higher lane, or this lane if it's the highest one.
+    pub fn boosted(self) -> Self {
+        Self::from_db(self as i16 + 1).unwrap_or(self)
+    }
+}
+
+/// Number of queued jobs of a certain type in a priority lane.
+#[derive(Debug, Clone)]
+pub struct QueuedJobsInLane {
+    /// Job type, e.g. `prover_fri` or `basic_witness_generator`.
+    pub job_type: String,
+    pub priority: BatchPriority,
+    pub queued: usize,
+    /// Seal time of the oldest batch with queued jobs in the lane.
+    pub oldest_batch_sealed_at: NaiveDateTime,
+}
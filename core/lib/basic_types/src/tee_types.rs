@@ -1,4 +1,4 @@
-use std::fmt;
+use std::{collections::HashSet, fmt, str::FromStr};
 
 use serde::{Deserialize, Serialize};
 
@@ -21,6 +21,82 @@ impl fmt::Display for TeeType {
     }
 }
 
+impl FromStr for TeeType {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "none" => Ok(Self::None),
+            "sgx" => Ok(Self::Sgx),
+            "tdx" => Ok(Self::Tdx),
+            _ => Err(anyhow::anyhow!("unknown TEE type: {s}")),
+        }
+    }
+}
+
+/// TEE verification status of an L1 batch determined by a [`TeeQuorumPolicy`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TeeBatchStatus {
+    /// No proofs counting towards the quorum were submitted for the batch.
+    Unproven,
+    /// Some proofs were submitted for the batch, but they don't satisfy the quorum.
+    PartiallyProven,
+    /// Proofs submitted for the batch satisfy the quorum.
+    Verified,
+}
+
+/// Policy determining whether an L1 batch is verified by TEE provers.
+///
+/// Only proofs signed by keys with a registered attestation count towards the quorum. Proofs signed
+/// by the same key are counted once, so the quorum is always reached by distinct attested keys.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TeeQuorumPolicy {
+    /// TEE types each of which must provide a proof for the batch.
+    pub required_tee_types: Vec<TeeType>,
+    /// Minimum total number of proofs from distinct keys.
+    pub min_proofs: usize,
+}
+
+impl Default for TeeQuorumPolicy {
+    /// Returns a policy requiring a single proof of any TEE type.
+    fn default() -> Self {
+        Self {
+            required_tee_types: vec![],
+            min_proofs: 1,
+        }
+    }
+}
+
+impl TeeQuorumPolicy {
+    /// Determines the status of a batch given the TEE types and public keys of attested proofs submitted for it.
+    pub fn batch_status<'a>(
+        &self,
+        proofs: impl IntoIterator<Item = (TeeType, &'a [u8])>,
+    ) -> TeeBatchStatus {
+        let mut pubkeys = HashSet::new();
+        let mut tee_types = HashSet::new();
+        for (tee_type, pubkey) in proofs {
+            if pubkeys.insert(pubkey) {
+                tee_types.insert(tee_type);
+            }
+        }
+
+        if pubkeys.is_empty() {
+            return TeeBatchStatus::Unproven;
+        }
+        let has_required_types = self
+            .required_tee_types
+            .iter()
+            .all(|tee_type| tee_types.contains(tee_type));
+        if has_required_types && pubkeys.len() >= self.min_proofs {
+            TeeBatchStatus::Verified
+        } else {
+            TeeBatchStatus::PartiallyProven
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use serde_json;
@@ -64,4 +140,43 @@ mod tests {
         assert_eq!(TeeType::Sgx.to_string(), "sgx");
         assert_eq!(TeeType::Tdx.to_string(), "tdx");
     }
+
+    #[test]
+    fn test_parse_teetype() {
+        for tee_type in [TeeType::None, TeeType::Sgx, TeeType::Tdx] {
+            assert_eq!(tee_type.to_string().parse::<TeeType>().unwrap(), tee_type);
+        }
+        assert!("SGX".parse::<TeeType>().is_err());
+    }
+
+    #[test]
+    fn test_tee_quorum_policy() {
+        let policy = TeeQuorumPolicy {
+            required_tee_types: vec![TeeType::Sgx, TeeType::Tdx],
+            min_proofs: 2,
+        };
+        let sgx_key: &[u8] = &[1; 33];
+        let tdx_key: &[u8] = &[2; 33];
+
+        assert_eq!(policy.batch_status([]), TeeBatchStatus::Unproven);
+        assert_eq!(
+            policy.batch_status([(TeeType::Sgx, sgx_key)]),
+            TeeBatchStatus::PartiallyProven
+        );
+        assert_eq!(
+            policy.batch_status([(TeeType::Sgx, sgx_key), (TeeType::Tdx, tdx_key)]),
+            TeeBatchStatus::Verified
+        );
+        // Proofs signed by the same key don't satisfy the quorum.
+        assert_eq!(
+            policy.batch_status([(TeeType::Sgx, sgx_key), (TeeType::Tdx, sgx_key)]),
+            TeeBatchStatus::PartiallyProven
+        );
+
+        let default_policy = TeeQuorumPolicy::default();
+        assert_eq!(
+            default_policy.batch_status([(TeeType::Tdx, tdx_key)]),
+            TeeBatchStatus::Verified
+        );
+    }
 }
@@ -2,6 +2,7 @@ use std::{
     collections::HashMap,
     net::SocketAddr,
     num::{NonZeroU32, NonZeroUsize},
+    path::PathBuf,
     str::FromStr,
     time::Duration,
 };
@@ -13,7 +14,7 @@ use smart_config::{
     metadata::{SizeUnit, TimeUnit},
     ByteSize, DescribeConfig, DeserializeConfig,
 };
-use zksync_basic_types::Address;
+use zksync_basic_types::{url::SensitiveUrl, Address};
 
 /// API configuration.
 #[derive(Debug, Clone, PartialEq, DescribeConfig, DeserializeConfig)]
@@ -200,6 +201,18 @@ pub struct Web3JsonRpcConfig {
     /// can lead to spurious resets when Postgres lags for whatever reason (e.g., when sealing L1 batches).
     #[config(default_t = NonZeroU32::new(20).unwrap())]
     pub latest_values_max_block_lag: NonZeroU32,
+    /// Memory budget in MiBs shared by the factory deps, initial writes and latest values caches. If set, the caches use
+    /// the W-TinyLFU admission policy, and the budget is periodically redistributed among them based on their hit rates;
+    /// the cache sizes specified above are used as the initial distribution of the budget. If not set, each cache uses
+    /// the LRU eviction policy and has a fixed size.
+    #[config(with = Optional(SizeUnit::MiB))]
+    pub storage_caches_memory_budget_mb: Option<ByteSize>,
+    /// Interval between redistributing the storage caches memory budget. Only used if `storage_caches_memory_budget_mb` is set.
+    #[config(default_t = 1 * TimeUnit::Minutes, with = TimeUnit::Seconds)]
+    pub storage_caches_resize_interval_sec: Duration,
+    /// Path to the file with the most accessed keys of the storage caches. If set, the keys are persisted periodically and on shutdown,
+    /// and are used to warm up the caches on restart. Only used if `storage_caches_memory_budget_mb` is set.
+    pub storage_caches_hot_set_path: Option<PathBuf>,
     /// Limit for fee history block range.
     #[config(default_t = 1_024)]
     pub fee_history_limit: u64,
@@ -219,6 +232,12 @@ pub struct Web3JsonRpcConfig {
     pub websocket_requests_per_minute_limit: NonZeroU32,
     /// Tree API url, currently used to proxy `getProof` calls to the tree
     pub tree_api_url: Option<String>,
+    /// URL of an archive node used to serve historical state queries (`eth_getBalance`, `eth_getStorageAt`)
+    /// for pruned L2 blocks. Values are requested together with Merkle proofs (`zks_getProof`) and are only returned
+    /// if the proofs verify against root hashes of pruned L1 batches retained by the node. If not set, queries
+    /// for pruned blocks fail.
+    #[config(secret, with = Optional(Serde![str]))]
+    pub archive_proxy_url: Option<SensitiveUrl>,
     /// Polling period for mempool cache update - how often the mempool cache is updated from the database.
     /// In milliseconds. Default is 50 milliseconds.
     #[config(default_t = Duration::from_millis(50), with = TimeUnit::Millis)]
@@ -308,6 +327,16 @@ pub struct MerkleTreeApiConfig {
     /// Port to bind the Merkle tree API server to.
     #[config(default_t = 3_072)]
     pub port: u16,
+    /// Path to store the secondary RocksDB instance data for a standalone Merkle tree API server (i.e., one running
+    /// without the tree component). If specified, the server opens the tree RocksDB in the secondary mode, so that
+    /// it can run alongside the tree component and periodically catch up with its updates.
+    pub secondary_path: Option<PathBuf>,
+    /// Interval between catching up with the primary tree instance in the secondary mode.
+    #[config(default_t = Duration::from_secs(1), with = TimeUnit::Millis)]
+    pub catch_up_interval: Duration,
+    /// Maximum number of keys in a single multiproof request. Requests with more keys are rejected.
+    #[config(default_t = 100_000)]
+    pub max_multiproof_keys: usize,
 }
 
 #[cfg(test)]
@@ -358,6 +387,9 @@ mod tests {
                 initial_writes_cache_size_mb: ByteSize::new(32, SizeUnit::MiB),
                 latest_values_cache_size_mb: ByteSize::new(256, SizeUnit::MiB),
                 latest_values_max_block_lag: NonZeroU32::new(50).unwrap(),
+                storage_caches_memory_budget_mb: Some(ByteSize::new(512, SizeUnit::MiB)),
+                storage_caches_resize_interval_sec: Duration::from_secs(30),
+                storage_caches_hot_set_path: Some("/db/cache-hot-set".into()),
                 fee_history_limit: 100,
                 max_batch_request_size: 200,
                 max_response_body_size_mb: ByteSize::new(10, SizeUnit::MiB),
@@ -370,6 +402,7 @@ mod tests {
                 .collect(),
                 websocket_requests_per_minute_limit: NonZeroU32::new(10).unwrap(),
                 tree_api_url: Some("http://tree/".into()),
+                archive_proxy_url: Some("http://archive/".parse().unwrap()),
                 mempool_cache_update_interval: Duration::from_millis(50),
                 mempool_cache_size: 10000,
                 whitelisted_tokens_for_aa: vec![
@@ -384,7 +417,12 @@ mod tests {
                 slow_time_limit_ms: Some(Duration::from_millis(250)),
                 hard_time_limit_ms: Some(Duration::from_millis(2_000)),
             },
-            merkle_tree: MerkleTreeApiConfig { port: 8082 },
+            merkle_tree: MerkleTreeApiConfig {
+                port: 8082,
+                secondary_path: Some("/db/tree-replica".into()),
+                catch_up_interval: Duration::from_millis(500),
+                max_multiproof_keys: 10_000,
+            },
         }
     }
 
@@ -415,6 +453,9 @@ mod tests {
             API_WEB3_JSON_RPC_INITIAL_WRITES_CACHE_SIZE_MB=32
             API_WEB3_JSON_RPC_LATEST_VALUES_CACHE_SIZE_MB=256
             API_WEB3_JSON_RPC_LATEST_VALUES_MAX_BLOCK_LAG=50
+            API_WEB3_JSON_RPC_STORAGE_CACHES_MEMORY_BUDGET_MB=512
+            API_WEB3_JSON_RPC_STORAGE_CACHES_RESIZE_INTERVAL_SEC=30
+            API_WEB3_JSON_RPC_STORAGE_CACHES_HOT_SET_PATH=/db/cache-hot-set
             API_WEB3_JSON_RPC_FEE_HISTORY_LIMIT=100
             API_WEB3_JSON_RPC_MAX_BATCH_REQUEST_SIZE=200
             API_WEB3_JSON_RPC_WEBSOCKET_REQUESTS_PER_MINUTE_LIMIT=10
@@ -423,6 +464,7 @@ mod tests {
             API_CONTRACT_VERIFICATION_PORT="3070"
             API_CONTRACT_VERIFICATION_URL="http://127.0.0.1:3070"
             API_WEB3_JSON_RPC_TREE_API_URL="http://tree/"
+            API_WEB3_JSON_RPC_ARCHIVE_PROXY_URL="http://archive/"
             API_WEB3_JSON_RPC_MAX_RESPONSE_BODY_SIZE_MB=10
             API_WEB3_JSON_RPC_MAX_RESPONSE_BODY_SIZE_OVERRIDES_MB="eth_call=1, eth_getTransactionReceipt=None, zks_getProof=32"
             API_PROMETHEUS_LISTENER_PORT="3312"
@@ -432,6 +474,9 @@ mod tests {
             API_HEALTHCHECK_SLOW_TIME_LIMIT_MS=250
             API_HEALTHCHECK_HARD_TIME_LIMIT_MS=2000
             API_MERKLE_TREE_PORT=8082
+            API_MERKLE_TREE_SECONDARY_PATH=/db/tree-replica
+            API_MERKLE_TREE_CATCH_UP_INTERVAL=500
+            API_MERKLE_TREE_MAX_MULTIPROOF_KEYS=10000
         "#;
         let env = Environment::from_dotenv("test.env", env)
             .unwrap()
@@ -466,6 +511,9 @@ mod tests {
             factory_deps_cache_size_mb: 128
             latest_values_cache_size_mb: 256
             latest_values_max_block_lag: 50
+            storage_caches_memory_budget_mb: 512
+            storage_caches_resize_interval_sec: 30
+            storage_caches_hot_set_path: /db/cache-hot-set
             mempool_cache_size: 10000
             mempool_cache_update_interval: 50
             pubsub_polling_interval: 200
@@ -483,6 +531,7 @@ mod tests {
             extended_api_tracing: true
             estimate_gas_optimize_search: true
             tree_api_url: "http://tree/"
+            archive_proxy_url: "http://archive/"
           prometheus:
             listener_port: 3312
             pushgateway_url: http://127.0.0.1:9091
@@ -493,6 +542,9 @@ mod tests {
             hard_time_limit_ms: 2000
           merkle_tree:
             port: 8082
+            secondary_path: /db/tree-replica
+            catch_up_interval: 500
+            max_multiproof_keys: 10000
         "#;
 
         let yaml = Yaml::new("test.yml", serde_yaml::from_str(yaml).unwrap()).unwrap();
@@ -1,4 +1,4 @@
-use std::{collections::HashSet, time::Duration};
+use std::{collections::HashSet, path::PathBuf, time::Duration};
 
 use serde::{Deserialize, Serialize};
 use smart_config::{
@@ -127,6 +127,14 @@ pub struct StateKeeperConfig {
 
     #[config(nest)]
     pub deployment_allowlist: Option<DeploymentAllowlist>,
+    /// If set, L2 blocks are sealed based on the target block time adapted to the mempool load
+    /// instead of the fixed `l2_block_commit_deadline_ms`.
+    #[config(nest)]
+    pub dynamic_l2_block_time: Option<DynamicL2BlockTimeConfig>,
+    /// If set, L2 transactions interacting with addresses from the deny list are rejected
+    /// both on submission and when fetched into the mempool.
+    #[config(nest)]
+    pub address_deny_list: Option<AddressDenyListConfig>,
 }
 
 impl StateKeeperConfig {
@@ -159,10 +167,27 @@ impl StateKeeperConfig {
             max_circuits_per_batch: 24100,
             protective_reads_persistence_enabled: true,
             deployment_allowlist: None,
+            dynamic_l2_block_time: None,
+            address_deny_list: None,
         }
     }
 }
 
+/// Configuration of the adaptive L2 block time. The target block time is interpolated linearly between
+/// `max_l2_block_time` (empty mempool) and `min_l2_block_time` (mempool with `busy_mempool_size` or more transactions).
+#[derive(Debug, Clone, PartialEq, DescribeConfig, DeserializeConfig)]
+pub struct DynamicL2BlockTimeConfig {
+    /// Minimum L2 block time used when the mempool is busy.
+    #[config(default_t = Duration::from_millis(250))]
+    pub min_l2_block_time: Duration,
+    /// Maximum L2 block time used when the mempool is idle.
+    #[config(default_t = Duration::from_secs(2))]
+    pub max_l2_block_time: Duration,
+    /// Number of L2 transactions in the mempool at which the mempool is considered busy.
+    #[config(default_t = 1_000)]
+    pub busy_mempool_size: u64,
+}
+
 #[derive(Debug, Clone, PartialEq, DescribeConfig, DeserializeConfig)]
 #[config(derive(Default))]
 pub struct OperationsManagerConfig {
@@ -233,6 +258,40 @@ pub struct DeploymentAllowlistDynamic {
     pub refresh_interval: Duration,
 }
 
+/// Configuration of the address deny list used to screen L2 transactions.
+#[derive(Debug, Clone, PartialEq, DescribeConfig, DeserializeConfig)]
+pub struct AddressDenyListConfig {
+    #[config(flatten)]
+    pub source: AddressDenyListSource,
+    /// Interval between deny list reloads.
+    #[config(default_t = 1 * TimeUnit::Minutes)]
+    pub refresh_interval: Duration,
+    /// Maximum time since the last successful reload after which the list is considered stale.
+    /// Transactions are rejected while the list is stale or was never loaded.
+    #[config(default_t = 5 * TimeUnit::Minutes)]
+    pub max_staleness: Duration,
+    /// Whether to additionally screen addresses touched during sandbox execution of submitted transactions
+    /// (i.e., contracts emitting events or having their storage modified).
+    #[config(default)]
+    pub screen_touched_addresses: bool,
+}
+
+/// Source of the address deny list. In both cases, the list is a JSON object of the form `{ "addresses": [..] }`.
+#[derive(Debug, Clone, PartialEq, DescribeConfig, DeserializeConfig)]
+#[config(tag = "source")]
+pub enum AddressDenyListSource {
+    /// Deny list is read from a local file.
+    File {
+        /// Path to the deny list file.
+        file_path: PathBuf,
+    },
+    /// Deny list is fetched from an HTTP endpoint.
+    Http {
+        /// HTTP URL to fetch the deny list from.
+        http_file_url: String,
+    },
+}
+
 #[cfg(test)]
 mod tests {
     use smart_config::{testing::test_complete, Environment, Yaml};
@@ -269,6 +328,19 @@ mod tests {
                 http_file_url: "http://deployment-allowlist/".to_owned(),
                 refresh_interval: Duration::from_secs(120),
             })),
+            dynamic_l2_block_time: Some(DynamicL2BlockTimeConfig {
+                min_l2_block_time: Duration::from_millis(200),
+                max_l2_block_time: Duration::from_secs(3),
+                busy_mempool_size: 500,
+            }),
+            address_deny_list: Some(AddressDenyListConfig {
+                source: AddressDenyListSource::File {
+                    file_path: "/etc/zksync/deny_list.json".into(),
+                },
+                refresh_interval: Duration::from_secs(30),
+                max_staleness: Duration::from_secs(120),
+                screen_touched_addresses: true,
+            }),
         }
     }
 
@@ -302,6 +374,14 @@ mod tests {
             CHAIN_STATE_KEEPER_DEPLOYMENT_ALLOWLIST_SOURCE=Dynamic
             CHAIN_STATE_KEEPER_DEPLOYMENT_ALLOWLIST_HTTP_FILE_URL=http://deployment-allowlist/
             CHAIN_STATE_KEEPER_DEPLOYMENT_ALLOWLIST_REFRESH_INTERVAL=2 min
+            CHAIN_STATE_KEEPER_DYNAMIC_L2_BLOCK_TIME_MIN_L2_BLOCK_TIME=200 ms
+            CHAIN_STATE_KEEPER_DYNAMIC_L2_BLOCK_TIME_MAX_L2_BLOCK_TIME=3 secs
+            CHAIN_STATE_KEEPER_DYNAMIC_L2_BLOCK_TIME_BUSY_MEMPOOL_SIZE=500
+            CHAIN_STATE_KEEPER_ADDRESS_DENY_LIST_SOURCE=File
+            CHAIN_STATE_KEEPER_ADDRESS_DENY_LIST_FILE_PATH=/etc/zksync/deny_list.json
+            CHAIN_STATE_KEEPER_ADDRESS_DENY_LIST_REFRESH_INTERVAL=30 secs
+            CHAIN_STATE_KEEPER_ADDRESS_DENY_LIST_MAX_STALENESS=2 min
+            CHAIN_STATE_KEEPER_ADDRESS_DENY_LIST_SCREEN_TOUCHED_ADDRESSES=true
         "#;
         let env = Environment::from_dotenv("test.env", env)
             .unwrap()
@@ -341,6 +421,16 @@ mod tests {
             source: Url
             http_file_url: http://deployment-allowlist/
             refresh_interval_secs: 120
+          dynamic_l2_block_time:
+            min_l2_block_time_ms: 200
+            max_l2_block_time_secs: 3
+            busy_mempool_size: 500
+          address_deny_list:
+            source: File
+            file_path: /etc/zksync/deny_list.json
+            refresh_interval_secs: 30
+            max_staleness_secs: 120
+            screen_touched_addresses: true
         "#;
 
         let yaml = Yaml::new("test.yml", serde_yaml::from_str(yaml).unwrap()).unwrap();
@@ -1,4 +1,4 @@
-use std::{path::PathBuf, time::Duration};
+use std::{num::NonZeroU32, path::PathBuf, time::Duration};
 
 use anyhow::Context as _;
 use serde::{Deserialize, Serialize};
@@ -55,6 +55,16 @@ pub struct MerkleTreeConfig {
     /// Maximum number of L1 batches to be processed by the Merkle tree at a time.
     #[config(default_t = 20)]
     pub max_l1_batches_per_iter: usize,
+    /// Path to the directory with Merkle tree checkpoints. If not specified, checkpoints are not created.
+    /// Checkpoints allow rolling back the tree to versions that were already pruned, and should be located
+    /// on the same filesystem as the tree so that they are cheap to create and restore.
+    pub checkpoints_path: Option<PathBuf>,
+    /// Interval in L1 batches between consecutive Merkle tree checkpoints.
+    #[config(default_t = NonZeroU32::new(1_000).unwrap())]
+    pub checkpoint_interval: NonZeroU32,
+    /// Number of the latest Merkle tree checkpoints to retain; older checkpoints are removed.
+    #[config(default_t = 3)]
+    pub retained_checkpoints: usize,
 }
 
 impl MerkleTreeConfig {
@@ -68,6 +78,9 @@ impl MerkleTreeConfig {
             memtable_capacity_mb: ByteSize::new(256, SizeUnit::MiB),
             stalled_writes_timeout_sec: Duration::from_secs(30),
             max_l1_batches_per_iter: 20,
+            checkpoints_path: None,
+            checkpoint_interval: NonZeroU32::new(1_000).unwrap(),
+            retained_checkpoints: 3,
         }
     }
 }
@@ -122,8 +135,6 @@ impl PostgresConfig {
 
 #[cfg(test)]
 mod tests {
-    use std::num::NonZeroU32;
-
     use smart_config::{
         testing::{test_complete, Tester},
         Environment, Yaml,
@@ -138,6 +149,12 @@ mod tests {
         assert_eq!(config.merkle_tree.multi_get_chunk_size, 250);
         assert_eq!(config.merkle_tree.max_l1_batches_per_iter, 50);
         assert_eq!(config.merkle_tree.memtable_capacity_mb, ByteSize(512 << 20));
+        assert_eq!(
+            config.merkle_tree.checkpoints_path.as_deref(),
+            Some("/db/tree_checkpoints".as_ref())
+        );
+        assert_eq!(config.merkle_tree.checkpoint_interval.get(), 500);
+        assert_eq!(config.merkle_tree.retained_checkpoints, 5);
         assert_eq!(
             config.merkle_tree.stalled_writes_timeout_sec,
             Duration::from_secs(60)
@@ -165,6 +182,9 @@ mod tests {
             DATABASE_MERKLE_TREE_STALLED_WRITES_TIMEOUT_SEC=60
             DATABASE_MERKLE_TREE_MAX_L1_BATCHES_PER_ITER=50
             DATABASE_MERKLE_TREE_BLOCK_CACHE_SIZE_MB=128
+            DATABASE_MERKLE_TREE_CHECKPOINTS_PATH="/db/tree_checkpoints"
+            DATABASE_MERKLE_TREE_CHECKPOINT_INTERVAL=500
+            DATABASE_MERKLE_TREE_RETAINED_CHECKPOINTS=5
             DATABASE_EXPERIMENTAL_STATE_KEEPER_DB_BLOCK_CACHE_CAPACITY_MB=64
             DATABASE_EXPERIMENTAL_PROCESSING_DELAY_MS=0
             DATABASE_EXPERIMENTAL_STATE_KEEPER_DB_MAX_OPEN_FILES=100
@@ -192,6 +212,9 @@ mod tests {
             memtable_capacity_mb: 512
             stalled_writes_timeout_sec: 60
             max_l1_batches_per_iter: 50
+            checkpoints_path: /db/tree_checkpoints
+            checkpoint_interval: 500
+            retained_checkpoints: 5
           experimental:
             state_keeper_db_block_cache_capacity_mb: 64
             reads_persistence_enabled: false
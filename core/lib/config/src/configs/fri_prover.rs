@@ -14,6 +14,13 @@ pub struct FriProverConfig {
     pub max_attempts: u32,
     #[config(default_t = 10 * TimeUnit::Minutes, with = TimeUnit::Seconds)]
     pub generation_timeout_in_secs: Duration,
+    /// Duration of a job lease. Jobs whose lease is not renewed within this time (e.g., because the prover crashed)
+    /// are requeued by Prover Job Monitor.
+    #[config(default_t = 2 * TimeUnit::Minutes)]
+    pub job_lease_duration: Duration,
+    /// How often leases of jobs are renewed while they are being processed. Should be a fraction of `job_lease_duration`.
+    #[config(default_t = Duration::from_secs(30))]
+    pub job_lease_renewal_interval: Duration,
     #[config(nest)]
     pub prover_object_store: ObjectStoreConfig,
 }
@@ -34,6 +41,8 @@ mod tests {
             prometheus_port: 3315,
             max_attempts: 10,
             generation_timeout_in_secs: Duration::from_secs(300),
+            job_lease_duration: Duration::from_secs(180),
+            job_lease_renewal_interval: Duration::from_secs(20),
             prover_object_store: ObjectStoreConfig {
                 mode: ObjectStoreMode::GCSWithCredentialFile {
                     bucket_base_url: "/base/url".to_owned(),
@@ -52,6 +61,8 @@ mod tests {
             FRI_PROVER_PROMETHEUS_PORT="3315"
             FRI_PROVER_MAX_ATTEMPTS="10"
             FRI_PROVER_GENERATION_TIMEOUT_IN_SECS="300"
+            FRI_PROVER_JOB_LEASE_DURATION_MS="180000"
+            FRI_PROVER_JOB_LEASE_RENEWAL_INTERVAL_MS="20000"
             FRI_PROVER_PROVER_OBJECT_STORE_BUCKET_BASE_URL="/base/url"
             FRI_PROVER_PROVER_OBJECT_STORE_MODE="GCSWithCredentialFile"
             FRI_PROVER_PROVER_OBJECT_STORE_GCS_CREDENTIAL_FILE_PATH="/path/to/credentials1.json"
@@ -73,6 +84,8 @@ mod tests {
           prometheus_port: 3315
           max_attempts: 10
           generation_timeout_in_secs: 300
+          job_lease_duration: 3 min
+          job_lease_renewal_interval: 20s
           prover_object_store:
             mode: GCSWithCredentialFile
             bucket_base_url: "/base/url"
@@ -15,6 +15,14 @@ pub struct FriProverGatewayConfig {
     #[config(default)]
     pub api_mode: ApiMode,
     pub port: Option<u16>,
+    /// Whether to long-poll the server for new proof generation data instead of polling it every
+    /// `api_poll_duration_secs`. Falls back to polling if the server doesn't support long-polling.
+    /// Requires `proof_data_handler.push_auth_token` secret.
+    #[config(default)]
+    pub push_mode: bool,
+    /// Maximum time a long-poll request is held until new data becomes available.
+    #[config(default_t = Duration::from_secs(30), with = TimeUnit::Seconds)]
+    pub long_poll_timeout_secs: Duration,
 
     // Configurations for prometheus
     #[config(default_t = 3314)]
@@ -47,6 +55,8 @@ mod tests {
             api_poll_duration_secs: Duration::from_secs(100),
             api_mode: ApiMode::ProverCluster,
             port: Some(8080),
+            push_mode: true,
+            long_poll_timeout_secs: Duration::from_secs(60),
             prometheus_listener_port: 3316,
         }
     }
@@ -58,6 +68,8 @@ mod tests {
             FRI_PROVER_GATEWAY_API_POLL_DURATION_SECS="100"
             FRI_PROVER_GATEWAY_API_MODE=ProverCluster
             FRI_PROVER_GATEWAY_PORT=8080
+            FRI_PROVER_GATEWAY_PUSH_MODE=true
+            FRI_PROVER_GATEWAY_LONG_POLL_TIMEOUT_SECS=60
             FRI_PROVER_GATEWAY_PROMETHEUS_LISTENER_PORT=3316
         "#;
         let env = Environment::from_dotenv("test.env", env)
@@ -75,6 +87,8 @@ mod tests {
           api_poll_duration_secs: 100
           api_mode: ProverCluster
           port: 8080
+          push_mode: true
+          long_poll_timeout_secs: 60
           prometheus_listener_port: 3316
         "#;
         let yaml = Yaml::new("test.yml", serde_yaml::from_str(yaml).unwrap()).unwrap();
@@ -26,11 +26,12 @@ pub use self::{
     prover_job_monitor::ProverJobMonitorConfig,
     pruning::PruningConfig,
     secrets::{
-        ContractVerifierSecrets, DataAvailabilitySecrets, DatabaseSecrets, L1Secrets, Secrets,
+        ContractVerifierSecrets, DataAvailabilitySecrets, DatabaseSecrets, L1Secrets,
+        ProofDataHandlerSecrets, Secrets, SnapshotsCreatorSecrets,
     },
     snapshot_recovery::SnapshotRecoveryConfig,
     snapshots_creator::SnapshotsCreatorConfig,
-    tee_proof_data_handler::TeeProofDataHandlerConfig,
+    tee_proof_data_handler::{TeeProofDataHandlerConfig, TeeQuorumConfig},
     utils::PrometheusConfig,
     vm_runner::{BasicWitnessInputProducerConfig, ProtectiveReadsWriterConfig},
 };
@@ -66,6 +66,11 @@ pub enum ObjectStoreMode {
         endpoint: Option<String>,
         region: Option<String>,
     },
+    /// Read-only store fetching objects over HTTP(S) using the same layout as [`Self::FileBacked`]
+    /// (i.e., `{bucket_base_url}/{bucket}/{key}`). Can be used to recover from a snapshot served by another node.
+    HttpReadOnly {
+        bucket_base_url: String,
+    },
     #[config(default)]
     FileBacked {
         file_backed_base_path: PathBuf,
@@ -150,6 +155,25 @@ mod tests {
         );
     }
 
+    #[test]
+    fn http_from_env() {
+        let env = r#"
+            OBJECT_STORE_MODE="HttpReadOnly"
+            OBJECT_STORE_BUCKET_BASE_URL="http://127.0.0.1:3080/"
+        "#;
+        let env = Environment::from_dotenv("test.env", env)
+            .unwrap()
+            .strip_prefix("OBJECT_STORE_");
+
+        let config: ObjectStoreConfig = test(env).unwrap();
+        assert_eq!(
+            config.mode,
+            ObjectStoreMode::HttpReadOnly {
+                bucket_base_url: "http://127.0.0.1:3080/".to_owned(),
+            }
+        );
+    }
+
     #[test]
     fn file_backed_from_yaml() {
         let yaml = r#"
@@ -14,6 +14,14 @@ pub struct ProofDataHandlerConfig {
     pub proof_gen_data_submit_interval_in_secs: Duration,
     #[config(default_t = true)]
     pub fetch_zero_chain_id_proofs: bool,
+    /// Whether to long-poll the gateway for generated proofs instead of polling it every
+    /// `proof_fetch_interval_in_secs`. Falls back to polling if the gateway doesn't support
+    /// long-polling. Requires `proof_data_handler.push_auth_token` secret.
+    #[config(default)]
+    pub push_mode: bool,
+    /// Maximum time a long-poll request is held until new data becomes available.
+    #[config(default_t = Duration::from_secs(30), with = TimeUnit::Seconds)]
+    pub long_poll_timeout_in_secs: Duration,
 }
 
 #[cfg(test)]
@@ -30,6 +38,8 @@ mod tests {
             proof_fetch_interval_in_secs: Duration::from_secs(15),
             proof_gen_data_submit_interval_in_secs: Duration::from_secs(20),
             fetch_zero_chain_id_proofs: false,
+            push_mode: true,
+            long_poll_timeout_in_secs: Duration::from_secs(60),
         }
     }
 
@@ -42,6 +52,8 @@ mod tests {
             PROOF_DATA_HANDLER_PROOF_FETCH_INTERVAL_IN_SECS=15
             PROOF_DATA_HANDLER_PROOF_GEN_DATA_SUBMIT_INTERVAL_IN_SECS=20
             PROOF_DATA_HANDLER_FETCH_ZERO_CHAIN_ID_PROOFS=false
+            PROOF_DATA_HANDLER_PUSH_MODE=true
+            PROOF_DATA_HANDLER_LONG_POLL_TIMEOUT_IN_SECS=60
         "#;
         let env = Environment::from_dotenv("test.env", env)
             .unwrap()
@@ -60,6 +72,8 @@ mod tests {
           proof_fetch_interval_in_secs: 15
           proof_gen_data_submit_interval_in_secs: 20
           fetch_zero_chain_id_proofs: false
+          push_mode: true
+          long_poll_timeout_in_secs: 60
         "#;
         let yaml = Yaml::new("test.yml", serde_yaml::from_str(yaml).unwrap()).unwrap();
         let config: ProofDataHandlerConfig = test_complete(yaml).unwrap();
@@ -50,6 +50,16 @@ pub struct ProverJobMonitorConfig {
     /// The interval between runs for Witness Job Queuer.
     #[config(default_t = Duration::from_secs(10))]
     pub witness_job_queuer_run_interval: Duration,
+    /// The interval between runs for Batch Priority Booster.
+    #[config(default_t = Duration::from_secs(60))]
+    pub batch_priority_booster_run_interval: Duration,
+    /// The amount of time a job can stay queued after which its batch is moved one priority lane up,
+    /// so that it is not starved by batches in higher lanes. A batch is moved at most once per this timeout.
+    #[config(default_t = 3 * TimeUnit::Hours)]
+    pub batch_priority_starvation_timeout: Duration,
+    /// The interval between runs for Batch Priority Queue Reporter.
+    #[config(default_t = Duration::from_secs(10))]
+    pub batch_priority_queue_reporter_run_interval: Duration,
     /// HTTP port of the ProverJobMonitor to send requests to.
     pub http_port: u16,
 }
@@ -76,6 +86,9 @@ mod tests {
             prover_queue_reporter_run_interval: Duration::from_secs(10),
             witness_generator_queue_reporter_run_interval: Duration::from_secs(10),
             witness_job_queuer_run_interval: Duration::from_secs(10),
+            batch_priority_booster_run_interval: Duration::from_secs(60),
+            batch_priority_starvation_timeout: Duration::from_secs(7200),
+            batch_priority_queue_reporter_run_interval: Duration::from_secs(10),
             http_port: 3074,
         }
     }
@@ -97,6 +110,9 @@ mod tests {
             PROVER_JOB_MONITOR_PROVER_QUEUE_REPORTER_RUN_INTERVAL_MS=10000
             PROVER_JOB_MONITOR_WITNESS_GENERATOR_QUEUE_REPORTER_RUN_INTERVAL_MS=10000
             PROVER_JOB_MONITOR_WITNESS_JOB_QUEUER_RUN_INTERVAL_MS=10000
+            PROVER_JOB_MONITOR_BATCH_PRIORITY_BOOSTER_RUN_INTERVAL_MS=60000
+            PROVER_JOB_MONITOR_BATCH_PRIORITY_STARVATION_TIMEOUT_MS=7200000
+            PROVER_JOB_MONITOR_BATCH_PRIORITY_QUEUE_REPORTER_RUN_INTERVAL_MS=10000
             PROVER_JOB_MONITOR_HTTP_PORT=3074
         "#;
         let env = Environment::from_dotenv("test.env", env)
@@ -124,6 +140,9 @@ mod tests {
           prover_queue_reporter_run_interval_ms: 10000
           witness_generator_queue_reporter_run_interval_ms: 10000
           witness_job_queuer_run_interval_ms: 10000
+          batch_priority_booster_run_interval_ms: 60000
+          batch_priority_starvation_timeout_ms: 7200000
+          batch_priority_queue_reporter_run_interval_ms: 10000
           http_port: 3074
         "#;
         let yaml = Yaml::new("test.yml", serde_yaml::from_str(yaml).unwrap()).unwrap();
@@ -148,6 +167,9 @@ mod tests {
           prover_queue_reporter_run_interval: '10 sec'
           witness_generator_queue_reporter_run_interval: '10s'
           witness_job_queuer_run_interval: '10s'
+          batch_priority_booster_run_interval: '1 min'
+          batch_priority_starvation_timeout: '2 hours'
+          batch_priority_queue_reporter_run_interval: '10s'
           http_port: 3074
         "#;
         let yaml = Yaml::new("test.yml", serde_yaml::from_str(yaml).unwrap()).unwrap();
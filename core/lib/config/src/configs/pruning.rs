@@ -1,6 +1,10 @@
 use std::{num::NonZeroU32, time::Duration};
 
-use smart_config::{metadata::TimeUnit, DescribeConfig, DeserializeConfig};
+use smart_config::{
+    de::Optional,
+    metadata::{SizeUnit, TimeUnit},
+    ByteSize, DescribeConfig, DeserializeConfig,
+};
 
 #[derive(Debug, Clone, PartialEq, DescribeConfig, DeserializeConfig)]
 #[config(derive(Default))]
@@ -21,6 +25,28 @@ pub struct PruningConfig {
     /// If set to 0, L1 batches will not be retained based on their timestamp. The default value is 1 hour.
     #[config(default_t = 1 * TimeUnit::Hours, with = TimeUnit::Seconds)]
     pub data_retention_sec: Duration,
+    /// If set, L1 batches will only be pruned while the estimated size of the Postgres database exceeds this value.
+    /// The estimate accounts for live data in the pruned tables, so that it decreases after pruning even if the space
+    /// is not returned to the OS. Like other retention criteria, this one is combined with the age-based criterion;
+    /// i.e., to prune purely based on the database size, set `data_retention_sec` to 0.
+    #[config(with = Optional(SizeUnit::MiB))]
+    pub max_database_size_mb: Option<ByteSize>,
+    /// If set, L1 batches will only be pruned while the estimated size of the `storage_logs` table
+    /// (including indexes) exceeds this value.
+    #[config(with = Optional(SizeUnit::MiB))]
+    pub max_storage_logs_size_mb: Option<ByteSize>,
+    /// If set, L1 batches will only be pruned while the estimated size of the `events` table
+    /// (including indexes) exceeds this value.
+    #[config(with = Optional(SizeUnit::MiB))]
+    pub max_events_size_mb: Option<ByteSize>,
+    /// If set, L1 batches will only be pruned while the estimated size of the `transactions` table
+    /// (including indexes) exceeds this value.
+    #[config(with = Optional(SizeUnit::MiB))]
+    pub max_transactions_size_mb: Option<ByteSize>,
+    /// If set, the pruner will not modify the database; instead, it will report the next pruning step
+    /// and the estimated amount of data it would free via logs and the health check.
+    #[config(default)]
+    pub dry_run: bool,
 }
 
 #[cfg(test)]
@@ -35,6 +61,11 @@ mod tests {
             chunk_size: NonZeroU32::new(10).unwrap(),
             removal_delay_sec: Duration::from_secs(60),
             data_retention_sec: Duration::from_secs(3600),
+            max_database_size_mb: Some(ByteSize::new(100, SizeUnit::GiB)),
+            max_storage_logs_size_mb: Some(ByteSize::new(40, SizeUnit::GiB)),
+            max_events_size_mb: Some(ByteSize::new(20, SizeUnit::GiB)),
+            max_transactions_size_mb: Some(ByteSize::new(512, SizeUnit::MiB)),
+            dry_run: true,
         }
     }
 
@@ -45,6 +76,11 @@ mod tests {
             EN_PRUNING_DATA_RETENTION_SEC=3600
             EN_PRUNING_CHUNK_SIZE=10
             EN_PRUNING_REMOVAL_DELAY_SEC=60
+            EN_PRUNING_MAX_DATABASE_SIZE_MB=102400
+            EN_PRUNING_MAX_STORAGE_LOGS_SIZE_MB=40960
+            EN_PRUNING_MAX_EVENTS_SIZE_MB=20480
+            EN_PRUNING_MAX_TRANSACTIONS_SIZE_MB=512
+            EN_PRUNING_DRY_RUN=true
         "#;
         let env = Environment::from_dotenv("test.env", env)
             .unwrap()
@@ -61,6 +97,11 @@ mod tests {
             data_retention_sec: 3600
             enabled: true
             removal_delay_sec: 60
+            max_database_size_mb: 102400
+            max_storage_logs_size_mb: 40960
+            max_events_size_mb: 20480
+            max_transactions_size_mb: 512
+            dry_run: true
         "#;
         let yaml = Yaml::new("test.yml", serde_yaml::from_str(yaml).unwrap()).unwrap();
         let config: PruningConfig = test_complete(yaml).unwrap();
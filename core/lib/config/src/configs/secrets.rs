@@ -6,10 +6,12 @@ use smart_config::{
     DescribeConfig, DeserializeConfig,
 };
 use zksync_basic_types::{secrets::APIKey, url::SensitiveUrl};
+use zksync_crypto_primitives::K256PrivateKey;
 
 use crate::configs::{
     consensus::ConsensusSecrets,
     da_client::{avail::AvailSecrets, celestia::CelestiaSecrets, eigen::EigenSecrets},
+    wallets::K256PrivateKeyDeserializer,
 };
 
 #[derive(Debug, Clone, DescribeConfig, DeserializeConfig)]
@@ -56,6 +58,24 @@ pub struct ContractVerifierSecrets {
     pub etherscan_api_key: Option<APIKey>,
 }
 
+#[derive(Debug, Clone, DescribeConfig, DeserializeConfig)]
+#[config(derive(Default))]
+pub struct SnapshotsCreatorSecrets {
+    /// Private key used to sign headers of created snapshots, so that external nodes can verify
+    /// snapshot integrity. If not set, snapshots are not signed.
+    #[config(secret, with = Optional(K256PrivateKeyDeserializer))]
+    pub header_signing_key: Option<K256PrivateKey>,
+}
+
+#[derive(Debug, Clone, DescribeConfig, DeserializeConfig)]
+#[config(derive(Default))]
+pub struct ProofDataHandlerSecrets {
+    /// Token shared by the proof data handler and the prover gateway to authenticate long-poll requests
+    /// in push mode. Long-poll endpoints are only served if the token is set.
+    #[config(with = Optional(FromSecretString))]
+    pub push_auth_token: Option<APIKey>,
+}
+
 #[derive(Debug, Clone, DescribeConfig, DeserializeConfig)]
 pub struct Secrets {
     #[config(nest)]
@@ -68,6 +88,10 @@ pub struct Secrets {
     pub data_availability: Option<DataAvailabilitySecrets>,
     #[config(nest)]
     pub contract_verifier: ContractVerifierSecrets,
+    #[config(nest)]
+    pub snapshots_creator: SnapshotsCreatorSecrets,
+    #[config(nest)]
+    pub proof_data_handler: ProofDataHandlerSecrets,
 }
 
 impl DatabaseSecrets {
@@ -95,6 +119,7 @@ impl DatabaseSecrets {
 mod tests {
     use secrecy::ExposeSecret;
     use smart_config::{testing::test_complete, Environment, Yaml};
+    use zksync_basic_types::H256;
 
     use super::*;
 
@@ -139,6 +164,21 @@ mod tests {
             avail.gas_relay_api_key.unwrap().0.expose_secret(),
             "SUPER_SECRET"
         );
+
+        let signing_key = secrets.snapshots_creator.header_signing_key.unwrap();
+        assert_eq!(
+            H256(*signing_key.expose_secret().as_ref()),
+            H256::repeat_byte(0x42)
+        );
+
+        assert_eq!(
+            secrets
+                .proof_data_handler
+                .push_auth_token
+                .unwrap()
+                .expose_secret(),
+            "push-token"
+        );
     }
 
     // Migration path: change `DA_SECRETS_*` -> `DA_*`
@@ -158,6 +198,10 @@ mod tests {
 
             CONTRACT_VERIFIER_ETHERSCAN_API_KEY=correct horse battery staple
 
+            SNAPSHOTS_CREATOR_HEADER_SIGNING_KEY=0x4242424242424242424242424242424242424242424242424242424242424242
+
+            PROOF_DATA_HANDLER_PUSH_AUTH_TOKEN=push-token
+
             CONSENSUS_VALIDATOR_KEY="validator:secret:bls12_381:2e78025015c2b4ba44b081d404c5446442dac74d5a20334c90af90a0b9987866"
             CONSENSUS_NODE_KEY="node:secret:ed25519:d1aaab7e5bc33cce10418d832a43b6aa00f67f2499d48a62fe79a190f1d6b0a3"
         "#;
@@ -181,6 +225,10 @@ mod tests {
               node_key: node:secret:ed25519:d1aaab7e5bc33cce10418d832a43b6aa00f67f2499d48a62fe79a190f1d6b0a3
             contract_verifier:
               etherscan_api_key: null
+            snapshots_creator:
+              header_signing_key: '0x4242424242424242424242424242424242424242424242424242424242424242'
+            proof_data_handler:
+              push_auth_token: push-token
             da:
               client: Avail
               seed_phrase: 'correct horse battery staple'
@@ -1,10 +1,10 @@
-use std::num::NonZeroUsize;
+use std::{net::SocketAddr, num::NonZeroUsize};
 
 use smart_config::{
     de::{Optional, Serde},
     DescribeConfig, DeserializeConfig,
 };
-use zksync_basic_types::L1BatchNumber;
+use zksync_basic_types::{Address, L1BatchNumber};
 
 use crate::ObjectStoreConfig;
 
@@ -51,6 +51,14 @@ pub struct SnapshotRecoveryConfig {
     /// This is a temporary flag that will eventually be removed together with version 0 snapshot support.
     #[config(default)]
     pub drop_storage_key_preimages: bool,
+    /// Address of the key expected to sign snapshot headers. If set, recovery will fail for snapshots
+    /// without a valid signature by this key.
+    pub header_signer: Option<Address>,
+    /// If set, the node will serve snapshot files it has downloaded during recovery over HTTP on this address, so that
+    /// other nodes can recover from them using the `HttpReadOnly` object store mode. Requires `local_mirror_path`
+    /// to be set for `object_store`; only files stored in the local mirror are served. Should generally be a loopback
+    /// or private address.
+    pub serve_addr: Option<SocketAddr>,
     #[config(nest)]
     pub tree: TreeRecoveryConfig,
     #[config(nest)]
@@ -72,6 +80,8 @@ mod tests {
             enabled: false,
             l1_batch: Some(L1BatchNumber(1234)),
             drop_storage_key_preimages: true,
+            header_signer: Some(Address::repeat_byte(0x11)),
+            serve_addr: Some("127.0.0.1:3080".parse().unwrap()),
             tree: TreeRecoveryConfig {
                 chunk_size: 250000,
                 parallel_persistence_buffer: Some(NonZeroUsize::new(4).unwrap()),
@@ -113,6 +123,8 @@ mod tests {
             EN_SNAPSHOTS_RECOVERY_ENABLED=false
             EN_SNAPSHOTS_RECOVERY_L1_BATCH=1234
             EN_SNAPSHOTS_RECOVERY_DROP_STORAGE_KEY_PREIMAGES=true
+            EN_SNAPSHOTS_RECOVERY_HEADER_SIGNER=0x1111111111111111111111111111111111111111
+            EN_SNAPSHOTS_RECOVERY_SERVE_ADDR=127.0.0.1:3080
             EN_SNAPSHOTS_RECOVERY_TREE_CHUNK_SIZE=250000
             EN_SNAPSHOTS_RECOVERY_TREE_PARALLEL_PERSISTENCE_BUFFER=4
 
@@ -137,6 +149,8 @@ mod tests {
           enabled: false
           l1_batch: 1234
           drop_storage_key_preimages: true
+          header_signer: '0x1111111111111111111111111111111111111111'
+          serve_addr: 127.0.0.1:3080
           postgres:
             max_concurrency: 10
           tree:
@@ -1,3 +1,5 @@
+use std::num::NonZeroU32;
+
 use smart_config::{
     de::{Optional, Serde},
     DescribeConfig, DeserializeConfig,
@@ -8,10 +10,16 @@ use crate::ObjectStoreConfig;
 
 #[derive(Debug, Clone, PartialEq, DescribeConfig, DeserializeConfig)]
 pub struct SnapshotsCreatorConfig {
-    /// Version of snapshots to create.
+    /// Version of snapshots to create. Version 2 corresponds to delta snapshots built on top of the newest
+    /// complete snapshot; see `max_delta_chain_length` for details.
     // Raw integer version is used because `SnapshotVersion` is defined in `zksync_types` crate.
     #[config(default)]
     pub version: u16,
+    /// Maximum number of delta snapshots that can be chained on top of a full snapshot. If creating a delta snapshot
+    /// would exceed this length, or there is no base snapshot, a full snapshot (version 1) is created instead.
+    /// Only used if `version` is 2.
+    #[config(default_t = 7)]
+    pub max_delta_chain_length: u32,
     /// L1 batch number to create the snapshot for. If not specified, a snapshot will be created
     /// for the current penultimate L1 batch.
     ///
@@ -24,6 +32,13 @@ pub struct SnapshotsCreatorConfig {
     pub storage_logs_chunk_size: u64,
     #[config(default_t = 25)]
     pub concurrent_queries_count: u32,
+    /// Maximum number of storage log chunk queries to the replica Postgres per minute. Allows to limit the DB load
+    /// when creating a snapshot while the main node is running. If not specified, queries are not rate-limited.
+    pub max_storage_logs_queries_per_minute: Option<NonZeroU32>,
+    /// Port to serve the health check on. The health check reports snapshot creation progress, including
+    /// the percentage of processed storage log chunks and the estimated time to completion.
+    /// If not specified, the health check is not served.
+    pub healthcheck_port: Option<u16>,
     #[config(nest)]
     pub object_store: ObjectStoreConfig,
 }
@@ -56,9 +71,12 @@ mod tests {
     fn expected_config() -> SnapshotsCreatorConfig {
         SnapshotsCreatorConfig {
             version: 0,
+            max_delta_chain_length: 3,
             l1_batch_number: Some(L1BatchNumber(1234)),
             storage_logs_chunk_size: 200000,
             concurrent_queries_count: 20,
+            max_storage_logs_queries_per_minute: NonZeroU32::new(120),
+            healthcheck_port: Some(3081),
             object_store: ObjectStoreConfig {
                 mode: ObjectStoreMode::FileBacked {
                     file_backed_base_path: "./chains/era/artifacts/".into(),
@@ -76,7 +94,10 @@ mod tests {
             SNAPSHOTS_CREATOR_STORAGE_LOGS_CHUNK_SIZE=200000
             SNAPSHOTS_CREATOR_CONCURRENT_QUERIES_COUNT=20
             SNAPSHOTS_CREATOR_VERSION=0
+            SNAPSHOTS_CREATOR_MAX_DELTA_CHAIN_LENGTH=3
             SNAPSHOTS_CREATOR_L1_BATCH_NUMBER=1234
+            SNAPSHOTS_CREATOR_MAX_STORAGE_LOGS_QUERIES_PER_MINUTE=120
+            SNAPSHOTS_CREATOR_HEALTHCHECK_PORT=3081
 
             SNAPSHOTS_OBJECT_STORE_MODE=FileBacked
             SNAPSHOTS_OBJECT_STORE_MAX_RETRIES=100
@@ -103,7 +124,10 @@ mod tests {
             file_backed_base_path: ./chains/era/artifacts/
             max_retries: 100
           version: 0
+          max_delta_chain_length: 3
           l1_batch_number: 1234
+          max_storage_logs_queries_per_minute: 120
+          healthcheck_port: 3081
         "#;
         let yaml = Yaml::new("test.yml", serde_yaml::from_str(yaml).unwrap()).unwrap();
         let schema = create_schema();
@@ -1,7 +1,14 @@
-use std::time::Duration;
+use std::{path::PathBuf, time::Duration};
 
-use smart_config::{de::Serde, metadata::TimeUnit, DescribeConfig, DeserializeConfig};
-use zksync_basic_types::L1BatchNumber;
+use smart_config::{
+    de::{Delimited, Serde},
+    metadata::TimeUnit,
+    DescribeConfig, DeserializeConfig,
+};
+use zksync_basic_types::{
+    tee_types::{TeeQuorumPolicy, TeeType},
+    L1BatchNumber,
+};
 
 #[derive(Debug, Clone, PartialEq, DescribeConfig, DeserializeConfig)]
 pub struct TeeProofDataHandlerConfig {
@@ -17,6 +24,58 @@ pub struct TeeProofDataHandlerConfig {
     /// Timeout in hours after which a batch will be permanently ignored if repeated retries failed.
     #[config(default_t = 10 * TimeUnit::Days, with = TimeUnit::Hours)]
     pub batch_permanently_ignored_timeout_in_hours: Duration,
+    /// Whether to verify DCAP attestation quotes registered by TEE provers. Should only be disabled for testing
+    /// (e.g., with mock attestations). If disabled, attestations are stored as is; signatures of submitted proofs
+    /// are verified regardless of this setting.
+    #[config(default_t = true)]
+    pub verify_attestation_quotes: bool,
+    /// Time during which a verified attestation quote is trusted without re-verification. Quotes are verified
+    /// when registered; once this time elapses, the quote is re-verified on the next proof submission, so that
+    /// updated collateral and allowlists are taken into account.
+    #[config(default_t = 1 * TimeUnit::Hours)]
+    pub attestation_cache_ttl: Duration,
+    /// Path to a JSON file with DCAP collateral (PCK CRLs, TCB info and QE identity together with
+    /// their issuer chains) used to verify SGX quotes. The collateral can be fetched in advance from
+    /// Intel PCS or a PCCS instance, so that verification doesn't require network access.
+    pub sgx_collateral_path: Option<PathBuf>,
+    /// Path to a JSON file with DCAP collateral used to verify TDX quotes.
+    pub tdx_collateral_path: Option<PathBuf>,
+    /// Hex-encoded MRENCLAVE values of SGX enclaves allowed to register attestations.
+    #[config(default, with = Delimited(","))]
+    pub sgx_mrenclave_allowlist: Vec<String>,
+    /// Hex-encoded MRTD values of TDX trust domains allowed to register attestations.
+    #[config(default, with = Delimited(","))]
+    pub tdx_mrtd_allowlist: Vec<String>,
+    /// TCB statuses of attestation quotes accepted in addition to `UpToDate`, e.g. `SWHardeningNeeded`.
+    /// Quotes with other TCB statuses are rejected.
+    #[config(default, with = Delimited(","))]
+    pub allowed_tcb_statuses: Vec<String>,
+    /// Policy determining when a batch is considered verified by TEE provers.
+    #[config(nest)]
+    pub quorum: TeeQuorumConfig,
+}
+
+/// Configuration of the TEE quorum policy.
+#[derive(Debug, Clone, PartialEq, DescribeConfig, DeserializeConfig)]
+pub struct TeeQuorumConfig {
+    /// TEE types each of which must provide a proof for a batch to be considered TEE-verified.
+    #[config(default, with = Serde![array])]
+    pub required_tee_types: Vec<TeeType>,
+    /// Minimum number of proofs signed by distinct attested keys for a batch to be considered TEE-verified.
+    #[config(default_t = 1)]
+    pub min_proofs: usize,
+    /// If set, batches will only be executed on L1 once they are TEE-verified.
+    #[config(default)]
+    pub wait_before_execute: bool,
+}
+
+impl TeeQuorumConfig {
+    pub fn policy(&self) -> TeeQuorumPolicy {
+        TeeQuorumPolicy {
+            required_tee_types: self.required_tee_types.clone(),
+            min_proofs: self.min_proofs,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -31,6 +90,20 @@ mod tests {
             first_processed_batch: L1BatchNumber(123),
             proof_generation_timeout_in_secs: Duration::from_secs(90),
             batch_permanently_ignored_timeout_in_hours: 5 * TimeUnit::Days,
+            verify_attestation_quotes: false,
+            attestation_cache_ttl: Duration::from_secs(1_800),
+            sgx_collateral_path: Some("/etc/tee/sgx-collateral.json".into()),
+            tdx_collateral_path: Some("/etc/tee/tdx-collateral.json".into()),
+            sgx_mrenclave_allowlist: vec![
+                "4f1a2c3e5d6b7a8c9d0e1f2a3b4c5d6e7f8091a2b3c4d5e6f708192a3b4c5d6e".to_owned(),
+            ],
+            tdx_mrtd_allowlist: vec![],
+            allowed_tcb_statuses: vec!["SWHardeningNeeded".to_owned()],
+            quorum: TeeQuorumConfig {
+                required_tee_types: vec![TeeType::Sgx, TeeType::Tdx],
+                min_proofs: 2,
+                wait_before_execute: true,
+            },
         }
     }
 
@@ -41,6 +114,18 @@ mod tests {
           first_processed_batch: 123
           proof_generation_timeout_in_secs: 90
           batch_permanently_ignored_timeout_in_hours: 120
+          verify_attestation_quotes: false
+          attestation_cache_ttl: '30 min'
+          sgx_collateral_path: /etc/tee/sgx-collateral.json
+          tdx_collateral_path: /etc/tee/tdx-collateral.json
+          sgx_mrenclave_allowlist:
+          - "4f1a2c3e5d6b7a8c9d0e1f2a3b4c5d6e7f8091a2b3c4d5e6f708192a3b4c5d6e"
+          tdx_mrtd_allowlist: []
+          allowed_tcb_statuses: [SWHardeningNeeded]
+          quorum:
+            required_tee_types: [sgx, tdx]
+            min_proofs: 2
+            wait_before_execute: true
         "#;
         let yaml = serde_yaml::from_str(yaml).unwrap();
         let yaml = Yaml::new("test.yml", yaml).unwrap();
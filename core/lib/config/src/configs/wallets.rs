@@ -25,7 +25,7 @@ impl AddressWallet {
 }
 
 #[derive(Debug)]
-struct K256PrivateKeyDeserializer;
+pub(crate) struct K256PrivateKeyDeserializer;
 
 impl DeserializeParam<K256PrivateKey> for K256PrivateKeyDeserializer {
     const EXPECTING: BasicTypes = BasicTypes::STRING;
@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS transaction_bundle_members;
+DROP TABLE IF EXISTS transaction_bundles;
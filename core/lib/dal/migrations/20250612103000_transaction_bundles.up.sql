@@ -0,0 +1,18 @@
+CREATE TABLE IF NOT EXISTS transaction_bundles (
+    hash BYTEA PRIMARY KEY,
+    min_timestamp BIGINT,
+    max_timestamp BIGINT,
+    max_l2_block_number BIGINT,
+    error VARCHAR,
+    created_at TIMESTAMP NOT NULL,
+    updated_at TIMESTAMP NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS transaction_bundle_members (
+    tx_hash BYTEA PRIMARY KEY,
+    bundle_hash BYTEA NOT NULL REFERENCES transaction_bundles (hash) ON DELETE CASCADE,
+    index_in_bundle INT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS transaction_bundle_members_bundle_hash_idx
+    ON transaction_bundle_members (bundle_hash, index_in_bundle);
@@ -0,0 +1,2 @@
+ALTER TABLE snapshots DROP COLUMN IF EXISTS header_signature;
+ALTER TABLE snapshots DROP COLUMN IF EXISTS storage_logs_hashes;
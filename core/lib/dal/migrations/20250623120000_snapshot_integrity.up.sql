@@ -0,0 +1,2 @@
+ALTER TABLE snapshots ADD COLUMN IF NOT EXISTS storage_logs_hashes BYTEA[] NOT NULL DEFAULT '{}';
+ALTER TABLE snapshots ADD COLUMN IF NOT EXISTS header_signature BYTEA;
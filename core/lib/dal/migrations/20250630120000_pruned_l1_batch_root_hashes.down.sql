@@ -0,0 +1 @@
+DROP TABLE IF EXISTS pruned_l1_batch_root_hashes;
@@ -0,0 +1,7 @@
+CREATE TABLE IF NOT EXISTS pruned_l1_batch_root_hashes (
+    l1_batch_number BIGINT PRIMARY KEY,
+    first_miniblock_number BIGINT NOT NULL,
+    last_miniblock_number BIGINT NOT NULL UNIQUE,
+    root_hash BYTEA NOT NULL,
+    created_at TIMESTAMP NOT NULL
+);
@@ -0,0 +1 @@
+ALTER TABLE snapshots ADD COLUMN IF NOT EXISTS factory_deps_hash BYTEA;
@@ -0,0 +1 @@
+ALTER TABLE snapshots DROP CONSTRAINT IF EXISTS snapshots_base_l1_batch_number_fkey;
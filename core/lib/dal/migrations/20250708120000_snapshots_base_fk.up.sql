@@ -0,0 +1,2 @@
+ALTER TABLE snapshots ADD CONSTRAINT snapshots_base_l1_batch_number_fkey
+    FOREIGN KEY (base_l1_batch_number) REFERENCES snapshots (l1_batch_number);
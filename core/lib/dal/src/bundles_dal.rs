@@ -0,0 +1,370 @@
+use std::collections::HashMap;
+
+use zksync_db_connection::{connection::Connection, error::DalResult, instrument::InstrumentExt};
+use zksync_types::{
+    bundle::{BundleConstraints, TransactionBundle},
+    L2BlockNumber, H256,
+};
+
+use crate::{models::storage_transaction::StorageTransaction, Core};
+
+/// Transaction bundle waiting to be executed by the state keeper.
+#[derive(Debug, Clone)]
+pub struct PendingBundle {
+    pub bundle: TransactionBundle,
+    /// Hashes of bundle transactions missing from the storage (e.g., because they were replaced by another
+    /// transaction with the same initiator and nonce). Bundles with missing transactions cannot be executed.
+    pub missing_tx_hashes: Vec<H256>,
+}
+
+#[derive(Debug)]
+pub struct BundlesDal<'a, 'c> {
+    pub(crate) storage: &'a mut Connection<'c, Core>,
+}
+
+impl BundlesDal<'_, '_> {
+    /// Inserts a bundle consisting of the specified transactions. Transactions must be already inserted
+    /// into the `transactions` table; this method should be called in the same DB transaction as their insertion
+    /// so that the mempool never observes bundle transactions as standalone ones.
+    ///
+    /// Returns `false` if the bundle is a duplicate.
+    pub async fn insert_bundle(
+        &mut self,
+        bundle_hash: H256,
+        tx_hashes: &[H256],
+        constraints: &BundleConstraints,
+    ) -> DalResult<bool> {
+        let inserted = sqlx::query!(
+            r#"
+            INSERT INTO
+            transaction_bundles (
+                hash,
+                min_timestamp,
+                max_timestamp,
+                max_l2_block_number,
+                created_at,
+                updated_at
+            )
+            VALUES
+            ($1, $2, $3, $4, NOW(), NOW())
+            ON CONFLICT (hash) DO NOTHING
+            "#,
+            bundle_hash.as_bytes(),
+            constraints.min_timestamp.map(|ts| ts as i64),
+            constraints.max_timestamp.map(|ts| ts as i64),
+            constraints
+                .max_l2_block_number
+                .map(|number| i64::from(number.0)),
+        )
+        .instrument("insert_bundle")
+        .with_arg("bundle_hash", &bundle_hash)
+        .execute(self.storage)
+        .await?
+        .rows_affected()
+            > 0;
+        if !inserted {
+            return Ok(false);
+        }
+
+        let tx_hashes: Vec<_> = tx_hashes.iter().map(H256::as_bytes).collect();
+        let indices: Vec<_> = (0..tx_hashes.len() as i32).collect();
+        sqlx::query!(
+            r#"
+            INSERT INTO
+            transaction_bundle_members (tx_hash, bundle_hash, index_in_bundle)
+            SELECT
+                u.tx_hash,
+                $3,
+                u.index_in_bundle
+            FROM
+                UNNEST($1::bytea [], $2::integer []) AS u (tx_hash, index_in_bundle)
+            "#,
+            &tx_hashes as &[&[u8]],
+            &indices,
+            bundle_hash.as_bytes(),
+        )
+        .instrument("insert_bundle#insert_members")
+        .with_arg("bundle_hash", &bundle_hash)
+        .with_arg("tx_hashes.len", &tx_hashes.len())
+        .execute(self.storage)
+        .await?;
+        Ok(true)
+    }
+
+    /// Loads up to `limit` oldest bundles that are neither rejected nor (partially) included into an L2 block.
+    pub async fn get_pending_bundles(&mut self, limit: usize) -> DalResult<Vec<PendingBundle>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                hash,
+                min_timestamp,
+                max_timestamp,
+                max_l2_block_number,
+                ARRAY(
+                    SELECT
+                        tx_hash
+                    FROM
+                        transaction_bundle_members
+                    WHERE
+                        bundle_hash = transaction_bundles.hash
+                    ORDER BY
+                        index_in_bundle
+                ) AS "tx_hashes!"
+            FROM
+                transaction_bundles
+            WHERE
+                error IS NULL
+                AND NOT EXISTS (
+                    SELECT
+                        1
+                    FROM
+                        transaction_bundle_members
+                    JOIN transactions ON transactions.hash = transaction_bundle_members.tx_hash
+                    WHERE
+                        transaction_bundle_members.bundle_hash = transaction_bundles.hash
+                        AND transactions.miniblock_number IS NOT NULL
+                )
+            ORDER BY
+                created_at
+            LIMIT
+                $1
+            "#,
+            limit as i64
+        )
+        .instrument("get_pending_bundles")
+        .with_arg("limit", &limit)
+        .fetch_all(self.storage)
+        .await?;
+
+        let bundle_hashes: Vec<_> = rows.iter().map(|row| row.hash.as_slice()).collect();
+        let mut transactions: HashMap<_, _> = sqlx::query_as!(
+            StorageTransaction,
+            r#"
+            SELECT
+                transactions.*
+            FROM
+                transaction_bundle_members
+            JOIN transactions ON transactions.hash = transaction_bundle_members.tx_hash
+            WHERE
+                transaction_bundle_members.bundle_hash = ANY($1)
+            "#,
+            &bundle_hashes as &[&[u8]]
+        )
+        .instrument("get_pending_bundles#get_transactions")
+        .with_arg("bundle_hashes.len", &bundle_hashes.len())
+        .fetch_all(self.storage)
+        .await?
+        .into_iter()
+        .map(|tx| (H256::from_slice(&tx.hash), tx))
+        .collect();
+
+        let mut bundles = Vec::with_capacity(rows.len());
+        for row in rows {
+            let bundle_hash = H256::from_slice(&row.hash);
+            let mut bundle_transactions = Vec::with_capacity(row.tx_hashes.len());
+            let mut missing_tx_hashes = vec![];
+            for tx_hash in row.tx_hashes {
+                let tx_hash = H256::from_slice(&tx_hash);
+                match transactions.remove(&tx_hash) {
+                    Some(tx) => bundle_transactions.push(tx.into()),
+                    None => missing_tx_hashes.push(tx_hash),
+                }
+            }
+
+            bundles.push(PendingBundle {
+                bundle: TransactionBundle {
+                    hash: bundle_hash,
+                    transactions: bundle_transactions,
+                    constraints: BundleConstraints {
+                        min_timestamp: row.min_timestamp.map(|ts| ts as u64),
+                        max_timestamp: row.max_timestamp.map(|ts| ts as u64),
+                        max_l2_block_number: row
+                            .max_l2_block_number
+                            .map(|number| L2BlockNumber(number as u32)),
+                    },
+                },
+                missing_tx_hashes,
+            });
+        }
+        Ok(bundles)
+    }
+
+    /// Marks the bundle and all its non-executed transactions as rejected with the specified error.
+    pub async fn mark_bundle_as_rejected(
+        &mut self,
+        bundle_hash: H256,
+        error: &str,
+    ) -> DalResult<()> {
+        let mut transaction = self.storage.start_transaction().await?;
+        sqlx::query!(
+            r#"
+            UPDATE transaction_bundles
+            SET
+                error = $2,
+                updated_at = NOW()
+            WHERE
+                hash = $1
+            "#,
+            bundle_hash.as_bytes(),
+            error
+        )
+        .instrument("mark_bundle_as_rejected")
+        .with_arg("bundle_hash", &bundle_hash)
+        .execute(&mut transaction)
+        .await?;
+
+        sqlx::query!(
+            r#"
+            UPDATE transactions
+            SET
+                error = $2,
+                in_mempool = FALSE,
+                updated_at = NOW()
+            FROM
+                transaction_bundle_members
+            WHERE
+                transaction_bundle_members.bundle_hash = $1
+                AND transactions.hash = transaction_bundle_members.tx_hash
+                AND transactions.miniblock_number IS NULL
+            "#,
+            bundle_hash.as_bytes(),
+            error
+        )
+        .instrument("mark_bundle_as_rejected#update_transactions")
+        .with_arg("bundle_hash", &bundle_hash)
+        .execute(&mut transaction)
+        .await?;
+        transaction.commit().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_types::{l2::L2Tx, ProtocolVersionId};
+
+    use super::*;
+    use crate::{
+        tests::{mock_execution_result, mock_l2_transaction},
+        ConnectionPool, CoreDal,
+    };
+
+    async fn insert_bundle_txs(conn: &mut Connection<'_, Core>, txs: &[L2Tx]) -> H256 {
+        let tx_hashes: Vec<_> = txs.iter().map(L2Tx::hash).collect();
+        for tx in txs {
+            conn.transactions_dal()
+                .insert_transaction_l2(tx, Default::default(), Default::default())
+                .await
+                .unwrap();
+        }
+        let bundle_hash = TransactionBundle::compute_hash(tx_hashes.iter().copied());
+        let inserted = conn
+            .bundles_dal()
+            .insert_bundle(bundle_hash, &tx_hashes, &BundleConstraints::default())
+            .await
+            .unwrap();
+        assert!(inserted);
+        bundle_hash
+    }
+
+    #[tokio::test]
+    async fn inserting_and_loading_bundles() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        let txs = [mock_l2_transaction(), mock_l2_transaction()];
+        let bundle_hash = insert_bundle_txs(&mut conn, &txs).await;
+
+        let inserted_again = conn
+            .bundles_dal()
+            .insert_bundle(bundle_hash, &[], &BundleConstraints::default())
+            .await
+            .unwrap();
+        assert!(!inserted_again);
+
+        let pending = conn.bundles_dal().get_pending_bundles(10).await.unwrap();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].bundle.hash, bundle_hash);
+        assert!(pending[0].missing_tx_hashes.is_empty());
+        let loaded_hashes: Vec<_> = pending[0].bundle.tx_hashes().collect();
+        let expected_hashes: Vec<_> = txs.iter().map(L2Tx::hash).collect();
+        assert_eq!(loaded_hashes, expected_hashes);
+
+        // Bundle transactions must not be picked up by the mempool.
+        let mempool_txs = conn
+            .transactions_dal()
+            .sync_mempool(&[], &[], 0, 0, true, 100)
+            .await
+            .unwrap();
+        assert!(mempool_txs.is_empty(), "{mempool_txs:?}");
+
+        conn.bundles_dal()
+            .mark_bundle_as_rejected(bundle_hash, "rejected: test")
+            .await
+            .unwrap();
+        let pending = conn.bundles_dal().get_pending_bundles(10).await.unwrap();
+        assert!(pending.is_empty());
+        let tx = conn
+            .transactions_dal()
+            .get_storage_tx_by_hash(expected_hashes[0])
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(tx.error.as_deref(), Some("rejected: test"));
+    }
+
+    #[tokio::test]
+    async fn loading_multiple_bundles() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        let first_txs = [mock_l2_transaction(), mock_l2_transaction()];
+        let first_hash = insert_bundle_txs(&mut conn, &first_txs).await;
+        let second_txs = [mock_l2_transaction()];
+        let second_hash = insert_bundle_txs(&mut conn, &second_txs).await;
+
+        let pending = conn.bundles_dal().get_pending_bundles(10).await.unwrap();
+        let pending: HashMap<_, _> = pending
+            .into_iter()
+            .map(|pending| (pending.bundle.hash, pending))
+            .collect();
+        assert_eq!(pending.len(), 2);
+        for (bundle_hash, txs) in [
+            (first_hash, first_txs.as_slice()),
+            (second_hash, second_txs.as_slice()),
+        ] {
+            let loaded = &pending[&bundle_hash];
+            assert!(loaded.missing_tx_hashes.is_empty());
+            let loaded_hashes: Vec<_> = loaded.bundle.tx_hashes().collect();
+            let expected_hashes: Vec<_> = txs.iter().map(L2Tx::hash).collect();
+            assert_eq!(loaded_hashes, expected_hashes);
+        }
+    }
+
+    #[tokio::test]
+    async fn executed_bundles_are_not_pending() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        let tx = mock_l2_transaction();
+        let tx_hash = tx.hash();
+        insert_bundle_txs(&mut conn, &[tx.clone()]).await;
+
+        conn.transactions_dal()
+            .mark_txs_as_executed_in_l2_block(
+                L2BlockNumber(1),
+                &[mock_execution_result(tx)],
+                1.into(),
+                ProtocolVersionId::latest(),
+                false,
+            )
+            .await
+            .unwrap();
+
+        let pending = conn.bundles_dal().get_pending_bundles(10).await.unwrap();
+        assert!(pending.is_empty(), "{pending:?}");
+        let tx = conn
+            .transactions_dal()
+            .get_storage_tx_by_hash(tx_hash)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(tx.miniblock_number, Some(1));
+    }
+}
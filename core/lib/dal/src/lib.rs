@@ -13,7 +13,8 @@ pub use zksync_db_connection::{
 
 use crate::{
     base_token_dal::BaseTokenDal, blocks_dal::BlocksDal, blocks_web3_dal::BlocksWeb3Dal,
-    consensus_dal::ConsensusDal, contract_verification_dal::ContractVerificationDal,
+    bundles_dal::BundlesDal, consensus_dal::ConsensusDal,
+    contract_verification_dal::ContractVerificationDal,
     custom_genesis_export_dal::CustomGenesisExportDal, data_availability_dal::DataAvailabilityDal,
     eth_sender_dal::EthSenderDal, eth_watcher_dal::EthWatcherDal,
     etherscan_verification_dal::EtherscanVerificationDal, events_dal::EventsDal,
@@ -32,6 +33,7 @@ use crate::{
 pub mod base_token_dal;
 pub mod blocks_dal;
 pub mod blocks_web3_dal;
+pub mod bundles_dal;
 pub mod consensus;
 pub mod consensus_dal;
 pub mod contract_verification_dal;
@@ -90,6 +92,8 @@ where
 
     fn blocks_web3_dal(&mut self) -> BlocksWeb3Dal<'_, 'a>;
 
+    fn bundles_dal(&mut self) -> BundlesDal<'_, 'a>;
+
     fn consensus_dal(&mut self) -> ConsensusDal<'_, 'a>;
 
     fn eth_sender_dal(&mut self) -> EthSenderDal<'_, 'a>;
@@ -172,6 +176,10 @@ impl<'a> CoreDal<'a> for Connection<'a, Core> {
         BlocksWeb3Dal { storage: self }
     }
 
+    fn bundles_dal(&mut self) -> BundlesDal<'_, 'a> {
+        BundlesDal { storage: self }
+    }
+
     fn consensus_dal(&mut self) -> ConsensusDal<'_, 'a> {
         ConsensusDal { storage: self }
     }
@@ -10,6 +10,7 @@ pub struct StorageTeeProof {
     pub proof: Option<Vec<u8>>,
     pub updated_at: NaiveDateTime,
     pub status: String,
+    pub tee_type: String,
     pub attestation: Option<Vec<u8>>,
 }
 
@@ -22,6 +22,15 @@ pub struct HardPruningInfo {
     pub l1_batch_root_hash: Option<H256>,
 }
 
+/// Root hash of a pruned L1 batch retained after hard pruning.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PrunedL1BatchRootHash {
+    pub l1_batch_number: L1BatchNumber,
+    /// Last L2 block in the batch. The root hash corresponds to the storage state after this block.
+    pub last_l2_block: L2BlockNumber,
+    pub root_hash: H256,
+}
+
 /// Information about Postgres pruning.
 #[derive(Debug, Clone, Copy, Default, PartialEq)]
 pub struct PruningInfo {
@@ -232,6 +241,8 @@ impl PruningDal<'_, '_> {
         let deleted_storage_logs = self
             .prune_storage_logs(first_l2_block_to_prune..=last_l2_block_to_prune)
             .await?;
+        self.retain_l1_batch_root_hashes(last_l1_batch_to_prune)
+            .await?;
         let deleted_l1_batches = self.delete_l1_batches(last_l1_batch_to_prune).await?;
         let deleted_l2_blocks = self.delete_l2_blocks(last_l2_block_to_prune).await?;
 
@@ -403,6 +414,79 @@ impl PruningDal<'_, '_> {
         Ok(execution_result.rows_affected())
     }
 
+    /// Copies root hashes of the pruned L1 batches so that they can be used to verify historical state
+    /// after pruning (e.g., Merkle proofs returned by an archive node).
+    async fn retain_l1_batch_root_hashes(
+        &mut self,
+        last_l1_batch_to_prune: L1BatchNumber,
+    ) -> DalResult<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO
+            pruned_l1_batch_root_hashes (
+                l1_batch_number,
+                first_miniblock_number,
+                last_miniblock_number,
+                root_hash,
+                created_at
+            )
+            SELECT
+                l1_batches.number,
+                MIN(miniblocks.number),
+                MAX(miniblocks.number),
+                l1_batches.hash,
+                NOW()
+            FROM
+                l1_batches
+            INNER JOIN miniblocks ON miniblocks.l1_batch_number = l1_batches.number
+            WHERE
+                l1_batches.number <= $1
+                AND l1_batches.hash IS NOT NULL
+            GROUP BY
+                l1_batches.number
+            ON CONFLICT (l1_batch_number) DO NOTHING
+            "#,
+            i64::from(last_l1_batch_to_prune.0),
+        )
+        .instrument("hard_prune_batches_range#retain_l1_batch_root_hashes")
+        .with_arg("last_l1_batch_to_prune", &last_l1_batch_to_prune)
+        .report_latency()
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+
+    /// Returns the root hash of the pruned L1 batch containing the specified L2 block. Returns `None`
+    /// if the L2 block doesn't belong to a pruned L1 batch with a retained root hash.
+    pub async fn get_pruned_l1_batch_root_hash(
+        &mut self,
+        l2_block: L2BlockNumber,
+    ) -> DalResult<Option<PrunedL1BatchRootHash>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                l1_batch_number,
+                last_miniblock_number,
+                root_hash
+            FROM
+                pruned_l1_batch_root_hashes
+            WHERE
+                $1 BETWEEN first_miniblock_number AND last_miniblock_number
+            "#,
+            i64::from(l2_block.0),
+        )
+        .instrument("get_pruned_l1_batch_root_hash")
+        .with_arg("l2_block", &l2_block)
+        .fetch_optional(self.storage)
+        .await?;
+
+        Ok(row.map(|row| PrunedL1BatchRootHash {
+            l1_batch_number: L1BatchNumber(row.l1_batch_number as u32),
+            last_l2_block: L2BlockNumber(row.last_miniblock_number as u32),
+            root_hash: H256::from_slice(&row.root_hash),
+        }))
+    }
+
     async fn delete_l1_batches(&mut self, last_l1_batch_to_prune: L1BatchNumber) -> DalResult<u64> {
         let execution_result = sqlx::query!(
             r#"
@@ -430,6 +430,29 @@ async fn l1_batches_can_be_hard_pruned() {
     assert_eq!(stats.deleted_l2_to_l1_logs, 40);
 
     assert_l1_batches_not_exist(&mut transaction, L1BatchNumber(1)..=L1BatchNumber(9)).await;
+
+    // Root hashes of pruned L1 batches must be retained and mapped from all L2 blocks in the batch.
+    for l1_batch_number in 0..=9 {
+        let expected = PrunedL1BatchRootHash {
+            l1_batch_number: L1BatchNumber(l1_batch_number),
+            last_l2_block: L2BlockNumber(l1_batch_number * 2 + 1),
+            root_hash: H256::from_low_u64_be(l1_batch_number.into()),
+        };
+        for l2_block in [l1_batch_number * 2, l1_batch_number * 2 + 1] {
+            let root_hash = transaction
+                .pruning_dal()
+                .get_pruned_l1_batch_root_hash(L2BlockNumber(l2_block))
+                .await
+                .unwrap();
+            assert_eq!(root_hash, Some(expected));
+        }
+    }
+    let root_hash = transaction
+        .pruning_dal()
+        .get_pruned_l1_batch_root_hash(L2BlockNumber(20))
+        .await
+        .unwrap();
+    assert_eq!(root_hash, None);
 }
 
 #[tokio::test]
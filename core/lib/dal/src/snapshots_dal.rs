@@ -5,7 +5,7 @@ use zksync_db_connection::{
 };
 use zksync_types::{
     snapshots::{AllSnapshots, SnapshotMetadata, SnapshotVersion},
-    L1BatchNumber,
+    L1BatchNumber, PackedEthSignature, H256,
 };
 
 use crate::Core;
@@ -14,8 +14,12 @@ use crate::Core;
 struct StorageSnapshotMetadata {
     version: i32,
     l1_batch_number: i64,
+    base_l1_batch_number: Option<i64>,
     storage_logs_filepaths: Vec<String>,
+    storage_logs_hashes: Vec<Vec<u8>>,
     factory_deps_filepath: String,
+    factory_deps_hash: Option<Vec<u8>>,
+    header_signature: Option<Vec<u8>>,
 }
 
 impl TryFrom<StorageSnapshotMetadata> for SnapshotMetadata {
@@ -24,16 +28,34 @@ impl TryFrom<StorageSnapshotMetadata> for SnapshotMetadata {
     fn try_from(row: StorageSnapshotMetadata) -> Result<Self, Self::Error> {
         let int_version = u16::try_from(row.version).decode_column("version")?;
         let version = SnapshotVersion::try_from(int_version).decode_column("version")?;
+        let mut storage_logs_hashes: Vec<_> = row
+            .storage_logs_hashes
+            .into_iter()
+            .map(|hash| (!hash.is_empty()).then(|| H256::from_slice(&hash)))
+            .collect();
+        // Hashes are not recorded for legacy snapshots.
+        storage_logs_hashes.resize(row.storage_logs_filepaths.len(), None);
+        let header_signature = row
+            .header_signature
+            .map(|bytes| PackedEthSignature::deserialize_packed(&bytes))
+            .transpose()
+            .decode_column("header_signature")?;
 
         Ok(Self {
             version,
             l1_batch_number: L1BatchNumber(row.l1_batch_number as u32),
+            base_l1_batch_number: row
+                .base_l1_batch_number
+                .map(|number| L1BatchNumber(number as u32)),
             storage_logs_filepaths: row
                 .storage_logs_filepaths
                 .into_iter()
                 .map(|path| (!path.is_empty()).then_some(path))
                 .collect(),
+            storage_logs_hashes,
             factory_deps_filepath: row.factory_deps_filepath,
+            factory_deps_hash: row.factory_deps_hash.map(|hash| H256::from_slice(&hash)),
+            header_signature,
         })
     }
 }
@@ -50,6 +72,7 @@ impl SnapshotsDal<'_, '_> {
         l1_batch_number: L1BatchNumber,
         storage_logs_chunk_count: u64,
         factory_deps_filepaths: &str,
+        factory_deps_hash: H256,
     ) -> DalResult<()> {
         sqlx::query!(
             r#"
@@ -58,17 +81,29 @@ impl SnapshotsDal<'_, '_> {
                 version,
                 l1_batch_number,
                 storage_logs_filepaths,
+                storage_logs_hashes,
                 factory_deps_filepath,
+                factory_deps_hash,
                 created_at,
                 updated_at
             )
             VALUES
-            ($1, $2, ARRAY_FILL(''::TEXT, ARRAY[$3::INTEGER]), $4, NOW(), NOW())
+            (
+                $1,
+                $2,
+                ARRAY_FILL(''::TEXT, ARRAY[$3::INTEGER]),
+                ARRAY_FILL(''::BYTEA, ARRAY[$3::INTEGER]),
+                $4,
+                $5,
+                NOW(),
+                NOW()
+            )
             "#,
             version as i32,
             l1_batch_number.0 as i32,
             storage_logs_chunk_count as i32,
             factory_deps_filepaths,
+            factory_deps_hash.as_bytes(),
         )
         .instrument("add_snapshot")
         .with_arg("version", &version)
@@ -79,17 +114,105 @@ impl SnapshotsDal<'_, '_> {
         Ok(())
     }
 
+    /// Adds a delta snapshot (i.e., one with [`SnapshotVersion::Version2`]) on top of the snapshot
+    /// for `base_l1_batch_number`.
+    pub async fn add_delta_snapshot(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+        base_l1_batch_number: L1BatchNumber,
+        storage_logs_chunk_count: u64,
+        factory_deps_filepaths: &str,
+        factory_deps_hash: H256,
+    ) -> DalResult<()> {
+        let version = SnapshotVersion::Version2;
+        sqlx::query!(
+            r#"
+            INSERT INTO
+            snapshots (
+                version,
+                l1_batch_number,
+                base_l1_batch_number,
+                storage_logs_filepaths,
+                storage_logs_hashes,
+                factory_deps_filepath,
+                factory_deps_hash,
+                created_at,
+                updated_at
+            )
+            VALUES
+            (
+                $1,
+                $2,
+                $3,
+                ARRAY_FILL(''::TEXT, ARRAY[$4::INTEGER]),
+                ARRAY_FILL(''::BYTEA, ARRAY[$4::INTEGER]),
+                $5,
+                $6,
+                NOW(),
+                NOW()
+            )
+            "#,
+            version as i32,
+            l1_batch_number.0 as i32,
+            i64::from(base_l1_batch_number.0),
+            storage_logs_chunk_count as i32,
+            factory_deps_filepaths,
+            factory_deps_hash.as_bytes(),
+        )
+        .instrument("add_delta_snapshot")
+        .with_arg("l1_batch_number", &l1_batch_number)
+        .with_arg("base_l1_batch_number", &base_l1_batch_number)
+        .report_latency()
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+
+    /// Records the content hash of a storage logs chunk before the chunk is uploaded to the object store.
+    /// This allows to reuse the uploaded chunk if the snapshot creator is restarted before
+    /// [`Self::add_storage_logs_filepath_for_snapshot()`] is called.
+    pub async fn set_storage_logs_chunk_hash(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+        chunk_id: u64,
+        content_hash: H256,
+    ) -> DalResult<()> {
+        sqlx::query!(
+            r#"
+            UPDATE snapshots
+            SET
+                storage_logs_hashes[$2] = $3,
+                updated_at = NOW()
+            WHERE
+                l1_batch_number = $1
+            "#,
+            l1_batch_number.0 as i32,
+            chunk_id as i32 + 1,
+            content_hash.as_bytes(),
+        )
+        .instrument("set_storage_logs_chunk_hash")
+        .with_arg("l1_batch_number", &l1_batch_number)
+        .with_arg("chunk_id", &chunk_id)
+        .execute(self.storage)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Records a produced storage logs chunk together with its content hash.
     pub async fn add_storage_logs_filepath_for_snapshot(
         &mut self,
         l1_batch_number: L1BatchNumber,
         chunk_id: u64,
         storage_logs_filepath: &str,
+        content_hash: H256,
     ) -> DalResult<()> {
         sqlx::query!(
             r#"
             UPDATE snapshots
             SET
                 storage_logs_filepaths[$2] = $3,
+                storage_logs_hashes[$2] = $4,
                 updated_at = NOW()
             WHERE
                 l1_batch_number = $1
@@ -97,6 +220,7 @@ impl SnapshotsDal<'_, '_> {
             l1_batch_number.0 as i32,
             chunk_id as i32 + 1,
             storage_logs_filepath,
+            content_hash.as_bytes(),
         )
         .instrument("add_storage_logs_filepath_for_snapshot")
         .with_arg("l1_batch_number", &l1_batch_number)
@@ -107,6 +231,31 @@ impl SnapshotsDal<'_, '_> {
         Ok(())
     }
 
+    /// Sets the operator signature for the snapshot header. Should be called once the snapshot is complete.
+    pub async fn set_header_signature(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+        signature: &PackedEthSignature,
+    ) -> DalResult<()> {
+        sqlx::query!(
+            r#"
+            UPDATE snapshots
+            SET
+                header_signature = $2,
+                updated_at = NOW()
+            WHERE
+                l1_batch_number = $1
+            "#,
+            l1_batch_number.0 as i32,
+            &signature.serialize_packed()[..],
+        )
+        .instrument("set_header_signature")
+        .with_arg("l1_batch_number", &l1_batch_number)
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+
     pub async fn get_all_complete_snapshots(&mut self) -> DalResult<AllSnapshots> {
         let rows = sqlx::query!(
             r#"
@@ -142,8 +291,12 @@ impl SnapshotsDal<'_, '_> {
             SELECT
                 VERSION,
                 L1_BATCH_NUMBER,
+                BASE_L1_BATCH_NUMBER,
                 FACTORY_DEPS_FILEPATH,
-                STORAGE_LOGS_FILEPATHS
+                FACTORY_DEPS_HASH,
+                STORAGE_LOGS_FILEPATHS,
+                STORAGE_LOGS_HASHES,
+                HEADER_SIGNATURE
             FROM
                 SNAPSHOTS
             ORDER BY
@@ -169,8 +322,12 @@ impl SnapshotsDal<'_, '_> {
             SELECT
                 VERSION,
                 L1_BATCH_NUMBER,
+                BASE_L1_BATCH_NUMBER,
                 FACTORY_DEPS_FILEPATH,
-                STORAGE_LOGS_FILEPATHS
+                FACTORY_DEPS_HASH,
+                STORAGE_LOGS_FILEPATHS,
+                STORAGE_LOGS_HASHES,
+                HEADER_SIGNATURE
             FROM
                 SNAPSHOTS
             WHERE
@@ -200,8 +357,12 @@ impl SnapshotsDal<'_, '_> {
             RETURNING
             version,
             l1_batch_number,
+            base_l1_batch_number,
             factory_deps_filepath,
-            storage_logs_filepaths
+            factory_deps_hash,
+            storage_logs_filepaths,
+            storage_logs_hashes,
+            header_signature
             "#,
             last_retained_l1_batch_number.0 as i32
         )
@@ -218,7 +379,9 @@ impl SnapshotsDal<'_, '_> {
 
 #[cfg(test)]
 mod tests {
-    use zksync_types::{snapshots::SnapshotVersion, L1BatchNumber};
+    use zksync_types::{
+        snapshots::SnapshotVersion, K256PrivateKey, L1BatchNumber, PackedEthSignature, H256,
+    };
 
     use crate::{ConnectionPool, Core, CoreDal};
 
@@ -233,6 +396,7 @@ mod tests {
             l1_batch_number,
             2,
             "gs:///bucket/factory_deps.bin",
+            H256::repeat_byte(0xff),
         )
         .await
         .expect("Failed to add snapshot");
@@ -248,6 +412,7 @@ mod tests {
                 l1_batch_number,
                 i,
                 "gs:///bucket/chunk.bin",
+                H256::repeat_byte(i as u8),
             )
             .await
             .unwrap();
@@ -265,6 +430,10 @@ mod tests {
             .unwrap()
             .expect("snapshot is not persisted");
         assert_eq!(snapshot_metadata.l1_batch_number, l1_batch_number);
+        assert_eq!(
+            snapshot_metadata.factory_deps_hash,
+            Some(H256::repeat_byte(0xff))
+        );
     }
 
     #[tokio::test]
@@ -278,6 +447,7 @@ mod tests {
             l1_batch_number,
             2,
             "gs:///bucket/factory_deps.bin",
+            H256::repeat_byte(0xff),
         )
         .await
         .unwrap();
@@ -287,6 +457,7 @@ mod tests {
                 l1_batch_number,
                 i,
                 "gs:///bucket/chunk.bin",
+                H256::repeat_byte(i as u8),
             )
             .await
             .unwrap();
@@ -341,42 +512,163 @@ mod tests {
             l1_batch_number,
             2,
             "gs:///bucket/factory_deps.bin",
+            H256::repeat_byte(0xff),
         )
         .await
         .expect("Failed to add snapshot");
 
         let storage_log_filepaths = ["gs:///bucket/test_file1.bin", "gs:///bucket/test_file2.bin"];
-        dal.add_storage_logs_filepath_for_snapshot(l1_batch_number, 1, storage_log_filepaths[1])
-            .await
-            .unwrap();
+        let content_hashes = [H256::repeat_byte(1), H256::repeat_byte(2)];
+        dal.add_storage_logs_filepath_for_snapshot(
+            l1_batch_number,
+            1,
+            storage_log_filepaths[1],
+            content_hashes[1],
+        )
+        .await
+        .unwrap();
 
-        let files = dal
+        let metadata = dal
             .get_snapshot_metadata(l1_batch_number)
             .await
             .expect("Failed to retrieve snapshot")
-            .unwrap()
-            .storage_logs_filepaths;
+            .unwrap();
         assert_eq!(
-            files,
+            metadata.storage_logs_filepaths,
             [None, Some("gs:///bucket/test_file2.bin".to_string())]
         );
+        assert_eq!(
+            metadata.storage_logs_hashes,
+            [None, Some(content_hashes[1])]
+        );
 
-        dal.add_storage_logs_filepath_for_snapshot(l1_batch_number, 0, storage_log_filepaths[0])
-            .await
-            .unwrap();
+        dal.add_storage_logs_filepath_for_snapshot(
+            l1_batch_number,
+            0,
+            storage_log_filepaths[0],
+            content_hashes[0],
+        )
+        .await
+        .unwrap();
 
-        let files = dal
+        let metadata = dal
             .get_snapshot_metadata(l1_batch_number)
             .await
             .expect("Failed to retrieve snapshot")
-            .unwrap()
-            .storage_logs_filepaths;
+            .unwrap();
         assert_eq!(
-            files,
+            metadata.storage_logs_filepaths,
             [
                 Some("gs:///bucket/test_file1.bin".to_string()),
                 Some("gs:///bucket/test_file2.bin".to_string())
             ]
         );
+        assert_eq!(metadata.storage_logs_hashes, content_hashes.map(Some));
+        assert!(metadata.header_signature.is_none());
+
+        let signature =
+            PackedEthSignature::sign_raw(&K256PrivateKey::random(), &H256::repeat_byte(3)).unwrap();
+        dal.set_header_signature(l1_batch_number, &signature)
+            .await
+            .unwrap();
+        let metadata = dal
+            .get_snapshot_metadata(l1_batch_number)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(metadata.header_signature, Some(signature));
+    }
+
+    #[tokio::test]
+    async fn adding_delta_snapshot() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        let mut dal = conn.snapshots_dal();
+        let base_l1_batch_number = L1BatchNumber(100);
+        dal.add_snapshot(
+            SnapshotVersion::Version1,
+            base_l1_batch_number,
+            1,
+            "gs:///bucket/factory_deps.bin",
+            H256::repeat_byte(0xff),
+        )
+        .await
+        .unwrap();
+        let l1_batch_number = L1BatchNumber(110);
+        dal.add_delta_snapshot(
+            l1_batch_number,
+            base_l1_batch_number,
+            1,
+            "gs:///bucket/factory_deps_delta.bin",
+            H256::repeat_byte(0xfe),
+        )
+        .await
+        .unwrap();
+
+        let base_metadata = dal
+            .get_snapshot_metadata(base_l1_batch_number)
+            .await
+            .unwrap()
+            .expect("snapshot is not persisted");
+        assert_eq!(base_metadata.base_l1_batch_number, None);
+        let metadata = dal
+            .get_newest_snapshot_metadata()
+            .await
+            .unwrap()
+            .expect("snapshot is not persisted");
+        assert_eq!(metadata.version, SnapshotVersion::Version2);
+        assert_eq!(metadata.l1_batch_number, l1_batch_number);
+        assert_eq!(metadata.base_l1_batch_number, Some(base_l1_batch_number));
+
+        let deleted_snapshots = dal
+            .delete_snapshots_after(base_l1_batch_number)
+            .await
+            .unwrap();
+        assert_eq!(deleted_snapshots.len(), 1);
+        assert_eq!(
+            deleted_snapshots[0].base_l1_batch_number,
+            Some(base_l1_batch_number)
+        );
+
+        // Delta snapshots must reference an existing base snapshot. This check must go last since the failed query
+        // aborts the test transaction.
+        dal.add_delta_snapshot(
+            l1_batch_number,
+            L1BatchNumber(105),
+            1,
+            "gs:///bucket/factory_deps_delta.bin",
+            H256::repeat_byte(0xfe),
+        )
+        .await
+        .unwrap_err();
+    }
+
+    #[tokio::test]
+    async fn deleting_base_snapshot_with_deltas() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        let mut dal = conn.snapshots_dal();
+        dal.add_snapshot(
+            SnapshotVersion::Version1,
+            L1BatchNumber(100),
+            1,
+            "gs:///bucket/factory_deps.bin",
+            H256::repeat_byte(0xff),
+        )
+        .await
+        .unwrap();
+        dal.add_delta_snapshot(
+            L1BatchNumber(110),
+            L1BatchNumber(100),
+            1,
+            "gs:///bucket/factory_deps_delta.bin",
+            H256::repeat_byte(0xfe),
+        )
+        .await
+        .unwrap();
+
+        // Deleting the base together with its deltas is fine.
+        let deleted_snapshots = dal.delete_snapshots_after(L1BatchNumber(99)).await.unwrap();
+        assert_eq!(deleted_snapshots.len(), 2);
     }
 }
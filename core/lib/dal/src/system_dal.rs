@@ -14,6 +14,43 @@ pub(crate) struct TableSize {
     pub total_size: u64,
 }
 
+/// Size statistics for a Postgres table.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TableSizeStats {
+    /// Size of the table on disk, including indexes and TOAST data.
+    pub total_size: u64,
+    /// Size of the main table data on disk, excluding indexes and TOAST data.
+    pub relation_size: u64,
+    /// Estimated number of live rows in the table.
+    pub live_rows: u64,
+    /// Average row width in bytes according to Postgres statistics. `None` if the table wasn't analyzed yet.
+    pub avg_row_width: Option<u64>,
+}
+
+impl TableSizeStats {
+    /// Approximate per-row storage overhead (tuple header and item pointer).
+    const ROW_OVERHEAD: u64 = 28;
+
+    /// Estimates the size of live data in the table, including indexes and TOAST data.
+    ///
+    /// Unlike [`Self::total_size`], this estimate decreases after rows are deleted, even if the freed space
+    /// is not returned to the OS (which only happens after `VACUUM FULL` or an equivalent operation).
+    /// Indexes and TOAST data are assumed to take space proportional to the table data.
+    pub fn estimated_live_size(&self) -> u64 {
+        let Some(avg_row_width) = self.avg_row_width else {
+            return self.total_size;
+        };
+        if self.relation_size == 0 {
+            return self.total_size;
+        }
+
+        let live_relation_size = self.live_rows * (avg_row_width + Self::ROW_OVERHEAD);
+        let live_size =
+            live_relation_size as f64 * self.total_size as f64 / self.relation_size as f64;
+        (live_size as u64).min(self.total_size)
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DatabaseMigration {
     pub version: i64,
@@ -99,6 +136,61 @@ impl SystemDal<'_, '_> {
         Ok(table_sizes.collect())
     }
 
+    /// Returns size statistics for the specified table in the public schema, or `None` if the table doesn't exist.
+    pub async fn get_table_size_stats(
+        &mut self,
+        table_name: &str,
+    ) -> DalResult<Option<TableSizeStats>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                PG_TOTAL_RELATION_SIZE(pg_class.oid) AS "total_size!",
+                PG_RELATION_SIZE(pg_class.oid) AS "relation_size!",
+                pg_stat_user_tables.n_live_tup AS "live_rows?",
+                (
+                    SELECT
+                        SUM(avg_width)
+                    FROM
+                        pg_stats
+                    WHERE
+                        pg_stats.schemaname = 'public'
+                        AND pg_stats.tablename::TEXT = $1
+                ) AS avg_row_width
+            FROM
+                pg_class
+            LEFT JOIN pg_stat_user_tables ON pg_stat_user_tables.relid = pg_class.oid
+            WHERE
+                pg_class.oid = TO_REGCLASS('public.' || QUOTE_IDENT($1))
+            "#,
+            table_name
+        )
+        .instrument("get_table_size_stats")
+        .with_arg("table_name", &table_name)
+        .fetch_optional(self.storage)
+        .await?;
+
+        Ok(row.map(|row| TableSizeStats {
+            total_size: row.total_size as u64,
+            relation_size: row.relation_size as u64,
+            live_rows: row.live_rows.unwrap_or(0) as u64,
+            avg_row_width: row.avg_row_width.map(|width| width as u64),
+        }))
+    }
+
+    /// Returns the on-disk size of the current database in bytes.
+    pub async fn get_database_size(&mut self) -> DalResult<u64> {
+        let size = sqlx::query_scalar!(
+            r#"
+            SELECT
+                PG_DATABASE_SIZE(CURRENT_DATABASE()) AS "size!"
+            "#
+        )
+        .instrument("get_database_size")
+        .fetch_one(self.storage)
+        .await?;
+        Ok(size as u64)
+    }
+
     pub async fn get_last_migration(&mut self) -> DalResult<DatabaseMigration> {
         let row = sqlx::query!(
             r#"
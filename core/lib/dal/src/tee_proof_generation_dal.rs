@@ -1,5 +1,5 @@
 #![doc = include_str!("../doc/TeeProofGenerationDal.md")]
-use std::time::Duration;
+use std::{ops, time::Duration};
 
 use chrono::{DateTime, Utc};
 use strum::{Display, EnumString};
@@ -41,6 +41,14 @@ pub enum TeeProofGenerationJobStatus {
     PermanentlyIgnored,
 }
 
+/// Proof generated for an L1 batch by a TEE prover with a registered attestation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AttestedTeeProof {
+    pub l1_batch_number: L1BatchNumber,
+    pub tee_type: TeeType,
+    pub pubkey: Vec<u8>,
+}
+
 /// Represents a locked batch picked by a TEE prover. A batch is locked when taken by a TEE prover
 /// ([TeeProofGenerationJobStatus::PickedByProver]). It can transition to one of three states:
 /// 1. [TeeProofGenerationJobStatus::Generated].
@@ -268,6 +276,27 @@ impl TeeProofGenerationDal<'_, '_> {
         Ok(())
     }
 
+    /// Returns the attestation registered for the specified public key, if any.
+    pub async fn get_attestation(&mut self, pubkey: &[u8]) -> DalResult<Option<Vec<u8>>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                attestation
+            FROM
+                tee_attestations
+            WHERE
+                pubkey = $1
+            "#,
+            pubkey
+        )
+        .instrument("get_attestation")
+        .with_arg("pubkey", &pubkey)
+        .fetch_optional(self.storage)
+        .await?;
+
+        Ok(row.and_then(|row| row.attestation))
+    }
+
     pub async fn get_tee_proofs(
         &mut self,
         batch_number: L1BatchNumber,
@@ -283,6 +312,7 @@ impl TeeProofGenerationDal<'_, '_> {
                 tp.proof,
                 tp.updated_at,
                 tp.status,
+                tp.tee_type,
                 ta.attestation
             FROM
                 tee_proof_generation_details tp
@@ -310,6 +340,48 @@ impl TeeProofGenerationDal<'_, '_> {
         Ok(proofs)
     }
 
+    /// Returns generated proofs signed by keys with a registered attestation for the specified range
+    /// of L1 batches. Proofs are ordered by the L1 batch number.
+    pub async fn get_attested_tee_proofs(
+        &mut self,
+        l1_batches: ops::RangeInclusive<L1BatchNumber>,
+    ) -> DalResult<Vec<AttestedTeeProof>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                tp.l1_batch_number,
+                tp.tee_type,
+                tp.pubkey AS "pubkey!"
+            FROM
+                tee_proof_generation_details tp
+            JOIN tee_attestations ta ON tp.pubkey = ta.pubkey
+            WHERE
+                tp.l1_batch_number BETWEEN $1 AND $2
+                AND tp.status = $3
+                AND ta.attestation IS NOT NULL
+            ORDER BY
+                tp.l1_batch_number,
+                tp.tee_type
+            "#,
+            i64::from(l1_batches.start().0),
+            i64::from(l1_batches.end().0),
+            TeeProofGenerationJobStatus::Generated.to_string()
+        )
+        .instrument("get_attested_tee_proofs")
+        .with_arg("l1_batches", &l1_batches)
+        .fetch_all(self.storage)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| AttestedTeeProof {
+                l1_batch_number: L1BatchNumber(row.l1_batch_number as u32),
+                tee_type: row.tee_type.parse().expect("invalid TEE type in DB"),
+                pubkey: row.pubkey,
+            })
+            .collect())
+    }
+
     /// For testing purposes only.
     pub async fn insert_tee_proof_generation_job(
         &mut self,
@@ -1889,6 +1889,14 @@ impl TransactionsDal<'_, '_> {
                                     )
                                 )
                                 AND tx_format != $4
+                                AND NOT EXISTS (
+                                    SELECT
+                                        1
+                                    FROM
+                                        transaction_bundle_members
+                                    WHERE
+                                        transaction_bundle_members.tx_hash = transactions.hash
+                                )
                             ORDER BY
                                 is_priority DESC,
                                 priority_op_id,
@@ -17,6 +17,8 @@ use crate::metrics::{AppHealthCheckConfig, CheckResult, METRICS};
 mod metrics;
 #[cfg(feature = "node_framework")]
 pub mod node;
+#[cfg(feature = "server")]
+pub mod server;
 
 #[cfg(test)]
 mod tests;
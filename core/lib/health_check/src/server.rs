@@ -1,8 +1,11 @@
+//! HTTP server exposing application health at `/health`.
+
 use std::{net::SocketAddr, sync::Arc, time::Duration};
 
 use axum::{extract::State, http::StatusCode, routing::get, Json, Router};
 use tokio::sync::watch;
-use zksync_health_check::{AppHealth, AppHealthCheck};
+
+use crate::{AppHealth, AppHealthCheck};
 
 async fn check_health(
     app_health_check: State<Arc<AppHealthCheck>>,
@@ -246,6 +246,25 @@ impl MempoolStore {
         }
     }
 
+    /// Advances the nonce of the account after its transactions were executed bypassing the mempool
+    /// (e.g., as a part of a transaction bundle). Has no effect if the account is not tracked by the mempool.
+    pub fn advance_account_nonce(&mut self, address: Address, next_nonce: Nonce) {
+        let Some(account) = self.l2_transactions_per_account.get_mut(&address) else {
+            return;
+        };
+        let (removed, previous_score, new_score) = account.advance_nonce(next_nonce);
+        if let Some(score) = previous_score {
+            self.l2_priority_queue.remove(&score);
+        }
+        if let Some(score) = new_score {
+            self.l2_priority_queue.insert(score);
+        }
+        self.size = self
+            .size
+            .checked_sub(removed as u64)
+            .expect("mempool size can't be negative");
+    }
+
     pub fn get_mempool_info(&mut self) -> MempoolInfo {
         MempoolInfo {
             stashed_accounts: std::mem::take(&mut self.stashed_accounts),
@@ -408,6 +408,38 @@ fn mempool_does_not_purge_all_accounts() {
     assert!(!mempool.has_next(&L2TxFilter::default()));
 }
 
+#[test]
+fn advancing_account_nonce() {
+    let mut mempool = MempoolStore::new(PriorityOpId(0), 100);
+    let account = Address::random();
+    let other_account = Address::random();
+    let transactions = vec![
+        gen_l2_tx(account, Nonce(0)),
+        gen_l2_tx(account, Nonce(1)),
+        gen_l2_tx(account, Nonce(3)),
+        gen_l2_tx(other_account, Nonce(0)),
+    ];
+    mempool.insert_without_constraints(transactions, HashMap::new());
+    assert_eq!(mempool.stats().l2_transaction_count, 4);
+
+    // Transactions with nonces 0..=2 were executed in a bundle.
+    mempool.advance_account_nonce(account, Nonce(3));
+    assert_eq!(mempool.account_nonce(account), Some(Nonce(3)));
+    assert_eq!(mempool.stats().l2_transaction_count, 2);
+    // Advancing to a lower nonce is a no-op.
+    mempool.advance_account_nonce(account, Nonce(1));
+    assert_eq!(mempool.account_nonce(account), Some(Nonce(3)));
+    // Untracked accounts are ignored.
+    mempool.advance_account_nonce(Address::random(), Nonce(1));
+
+    let mut next_txs = HashSet::new();
+    next_txs.insert(view(mempool.next_transaction(&L2TxFilter::default())));
+    next_txs.insert(view(mempool.next_transaction(&L2TxFilter::default())));
+    assert_eq!(next_txs, HashSet::from([(account, 3), (other_account, 0)]));
+    assert_eq!(mempool.next_transaction(&L2TxFilter::default()), None);
+    assert_eq!(mempool.stats().l2_transaction_count, 0);
+}
+
 fn gen_l2_tx(address: Address, nonce: Nonce) -> Transaction {
     gen_l2_tx_with_timestamp(address, nonce, unix_timestamp_ms())
 }
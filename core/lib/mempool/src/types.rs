@@ -79,6 +79,34 @@ impl AccountTransactions {
             .map(|(tx, c)| (Self::score_for_transaction(tx), c.clone()))
     }
 
+    /// Advances the account nonce to `next_nonce` after transactions were executed bypassing the mempool
+    /// (e.g., as a part of a transaction bundle). Transactions with lower nonces are dropped. Returns the number
+    /// of dropped transactions, and the scores of the previous and the new next transaction of the account.
+    pub fn advance_nonce(
+        &mut self,
+        next_nonce: Nonce,
+    ) -> (usize, Option<MempoolScore>, Option<MempoolScore>) {
+        if next_nonce <= self.nonce {
+            return (0, None, None);
+        }
+        let previous_score = self
+            .transactions
+            .get(&self.nonce)
+            .map(|(tx, _)| Self::score_for_transaction(tx));
+        let initial_len = self.transactions.len();
+        self.transactions.retain(|&nonce, _| nonce >= next_nonce);
+        self.nonce = next_nonce;
+        let new_score = self
+            .transactions
+            .get(&self.nonce)
+            .map(|(tx, _)| Self::score_for_transaction(tx));
+        (
+            initial_len - self.transactions.len(),
+            previous_score,
+            new_score,
+        )
+    }
+
     pub fn len(&self) -> usize {
         self.transactions.len()
     }
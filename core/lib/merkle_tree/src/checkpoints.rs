@@ -0,0 +1,313 @@
+//! Merkle tree checkpoints based on RocksDB checkpoints.
+//!
+//! A checkpoint is a full, openable copy of the tree RocksDB taken at a certain tree version. Checkpoints allow
+//! rolling back the tree to versions that cannot be reached by [truncating](crate::MerkleTree::truncate_recent_versions())
+//! the tree, e.g. because they were pruned.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::Context as _;
+use zksync_types::L1BatchNumber;
+
+use crate::{
+    domain::ZkSyncTreeReader,
+    metrics::{CheckpointStage, CHECKPOINT_METRICS},
+};
+
+/// Manager of Merkle tree checkpoints stored in a single directory.
+///
+/// Each checkpoint is stored in a subdirectory named after the last L1 batch included into the checkpoint,
+/// e.g. `l1_batch_1000`. Checkpoints are first created in a temporary subdirectory, so incomplete checkpoints
+/// (e.g., ones interrupted by a process crash) are never listed.
+#[derive(Debug, Clone)]
+pub struct TreeCheckpoints {
+    root: PathBuf,
+}
+
+impl TreeCheckpoints {
+    const DIR_PREFIX: &'static str = "l1_batch_";
+    const TMP_SUFFIX: &'static str = ".tmp";
+    const RESTORED_SUFFIX: &'static str = ".restored.tmp";
+    const REPLACED_SUFFIX: &'static str = ".replaced.tmp";
+
+    /// Creates a manager for checkpoints stored in the specified directory. The directory is lazily created
+    /// when the first checkpoint is created.
+    pub fn new(root: PathBuf) -> Self {
+        Self { root }
+    }
+
+    /// Returns the path to the directory with checkpoints.
+    pub fn path(&self) -> &Path {
+        &self.root
+    }
+
+    fn checkpoint_path(&self, l1_batch_number: L1BatchNumber) -> PathBuf {
+        self.root
+            .join(format!("{}{}", Self::DIR_PREFIX, l1_batch_number.0))
+    }
+
+    /// Creates a checkpoint for the latest tree version persisted in RocksDB. The tree must not be modified
+    /// concurrently; otherwise, the checkpoint may be attributed to a wrong version.
+    ///
+    /// Returns the L1 batch number of the checkpoint, or `None` if the tree is empty.
+    ///
+    /// # Errors
+    ///
+    /// Proxies RocksDB and filesystem I/O errors.
+    pub fn create(&self, tree: &ZkSyncTreeReader) -> anyhow::Result<Option<L1BatchNumber>> {
+        let Some(l1_batch_number) = tree.next_l1_batch_number().0.checked_sub(1) else {
+            return Ok(None);
+        };
+        let l1_batch_number = L1BatchNumber(l1_batch_number);
+        let checkpoint_path = self.checkpoint_path(l1_batch_number);
+        if checkpoint_path.exists() {
+            tracing::info!("Merkle tree checkpoint for L1 batch #{l1_batch_number} already exists");
+            return Ok(Some(l1_batch_number));
+        }
+
+        let latency = CHECKPOINT_METRICS.latency[&CheckpointStage::Create].start();
+        fs::create_dir_all(&self.root).with_context(|| {
+            format!(
+                "failed creating checkpoints directory `{}`",
+                self.root.display()
+            )
+        })?;
+        let tmp_path = Self::sibling_path(&checkpoint_path, Self::TMP_SUFFIX);
+        if tmp_path.exists() {
+            tracing::info!(
+                "Removing incomplete Merkle tree checkpoint at `{}`",
+                tmp_path.display()
+            );
+            fs::remove_dir_all(&tmp_path).with_context(|| {
+                format!(
+                    "failed removing incomplete checkpoint `{}`",
+                    tmp_path.display()
+                )
+            })?;
+        }
+
+        tree.db().create_checkpoint(&tmp_path)?;
+        fs::rename(&tmp_path, &checkpoint_path).with_context(|| {
+            format!(
+                "failed moving checkpoint `{}` to `{}`",
+                tmp_path.display(),
+                checkpoint_path.display()
+            )
+        })?;
+        let latency = latency.observe();
+        tracing::info!(
+            "Created Merkle tree checkpoint for L1 batch #{l1_batch_number} at `{}` in {latency:?}",
+            checkpoint_path.display()
+        );
+        self.report_metrics()?;
+        Ok(Some(l1_batch_number))
+    }
+
+    /// Lists L1 batch numbers of all stored checkpoints in the ascending order.
+    ///
+    /// # Errors
+    ///
+    /// Proxies filesystem I/O errors.
+    pub fn list(&self) -> anyhow::Result<Vec<L1BatchNumber>> {
+        if !self.root.exists() {
+            return Ok(vec![]);
+        }
+        let entries = fs::read_dir(&self.root).with_context(|| {
+            format!(
+                "failed reading checkpoints directory `{}`",
+                self.root.display()
+            )
+        })?;
+
+        let mut l1_batch_numbers = vec![];
+        for entry in entries {
+            let entry = entry.context("failed reading checkpoints directory entry")?;
+            let file_name = entry.file_name();
+            let number = file_name
+                .to_str()
+                .and_then(|name| name.strip_prefix(Self::DIR_PREFIX))
+                .and_then(|number| number.parse::<u32>().ok());
+            if let Some(number) = number {
+                l1_batch_numbers.push(L1BatchNumber(number));
+            }
+        }
+        l1_batch_numbers.sort_unstable();
+        Ok(l1_batch_numbers)
+    }
+
+    /// Returns the latest checkpoint with the L1 batch number not exceeding `l1_batch_number`.
+    ///
+    /// # Errors
+    ///
+    /// Proxies filesystem I/O errors.
+    pub fn nearest(&self, l1_batch_number: L1BatchNumber) -> anyhow::Result<Option<L1BatchNumber>> {
+        let checkpoints = self.list()?;
+        Ok(checkpoints
+            .into_iter()
+            .rev()
+            .find(|&number| number <= l1_batch_number))
+    }
+
+    /// Removes all checkpoints except for `retained_count` latest ones. Returns L1 batch numbers of the removed checkpoints.
+    ///
+    /// # Errors
+    ///
+    /// Proxies filesystem I/O errors.
+    pub fn retain_latest(&self, retained_count: usize) -> anyhow::Result<Vec<L1BatchNumber>> {
+        let mut checkpoints = self.list()?;
+        let removed_count = checkpoints.len().saturating_sub(retained_count);
+        checkpoints.truncate(removed_count);
+        self.remove(&checkpoints)?;
+        Ok(checkpoints)
+    }
+
+    /// Removes all checkpoints taken after the specified L1 batch. This should be called when the tree is rolled back,
+    /// so that checkpoints with the rolled back state are never restored. Returns L1 batch numbers of the removed checkpoints.
+    ///
+    /// # Errors
+    ///
+    /// Proxies filesystem I/O errors.
+    pub fn remove_after(
+        &self,
+        l1_batch_number: L1BatchNumber,
+    ) -> anyhow::Result<Vec<L1BatchNumber>> {
+        let mut checkpoints = self.list()?;
+        checkpoints.retain(|&number| number > l1_batch_number);
+        self.remove(&checkpoints)?;
+        Ok(checkpoints)
+    }
+
+    fn remove(&self, l1_batch_numbers: &[L1BatchNumber]) -> anyhow::Result<()> {
+        if l1_batch_numbers.is_empty() {
+            return Ok(());
+        }
+
+        let latency = CHECKPOINT_METRICS.latency[&CheckpointStage::Prune].start();
+        for &l1_batch_number in l1_batch_numbers {
+            let checkpoint_path = self.checkpoint_path(l1_batch_number);
+            fs::remove_dir_all(&checkpoint_path).with_context(|| {
+                format!("failed removing checkpoint `{}`", checkpoint_path.display())
+            })?;
+        }
+        let latency = latency.observe();
+        tracing::info!(
+            "Removed Merkle tree checkpoints for L1 batches {l1_batch_numbers:?} in {latency:?}"
+        );
+        self.report_metrics()
+    }
+
+    /// Restores the tree at `tree_path` from the checkpoint for the specified L1 batch. All existing data
+    /// at `tree_path` is replaced. The tree RocksDB must not be opened when calling this method.
+    ///
+    /// The checkpoint is first copied to a sibling temporary directory, which then replaces `tree_path`,
+    /// so the existing tree is left intact if restoring fails midway. Immutable SST files are hard-linked
+    /// from the checkpoint if possible, so restoring is cheap if the checkpoints are stored on the same filesystem
+    /// as the tree.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the checkpoint doesn't exist, and proxies filesystem I/O errors.
+    pub fn restore(&self, l1_batch_number: L1BatchNumber, tree_path: &Path) -> anyhow::Result<()> {
+        let checkpoint_path = self.checkpoint_path(l1_batch_number);
+        anyhow::ensure!(
+            checkpoint_path.is_dir(),
+            "Merkle tree checkpoint for L1 batch #{l1_batch_number} doesn't exist"
+        );
+
+        let latency = CHECKPOINT_METRICS.latency[&CheckpointStage::Restore].start();
+        let restored_path = Self::sibling_path(tree_path, Self::RESTORED_SUFFIX);
+        let replaced_path = Self::sibling_path(tree_path, Self::REPLACED_SUFFIX);
+        // Both directories may be left over from an interrupted restore. If the tree itself is missing,
+        // the interruption happened when swapping directories, so the restore is completed from scratch.
+        for path in [&restored_path, &replaced_path] {
+            if path.exists() {
+                tracing::info!(
+                    "Removing leftover directory `{}` from interrupted Merkle tree restore",
+                    path.display()
+                );
+                fs::remove_dir_all(path)
+                    .with_context(|| format!("failed removing `{}`", path.display()))?;
+            }
+        }
+        fs::create_dir_all(&restored_path).with_context(|| {
+            format!(
+                "failed creating Merkle tree directory `{}`",
+                restored_path.display()
+            )
+        })?;
+
+        let entries = fs::read_dir(&checkpoint_path).with_context(|| {
+            format!("failed reading checkpoint `{}`", checkpoint_path.display())
+        })?;
+        for entry in entries {
+            let entry = entry.context("failed reading checkpoint entry")?;
+            let source = entry.path();
+            let target = restored_path.join(entry.file_name());
+            // Only SST files are never modified by RocksDB; other files (e.g., WALs) may be appended to,
+            // so they are copied to keep the checkpoint intact.
+            let is_sst = source.extension().is_some_and(|ext| ext == "sst");
+            let linked = is_sst && fs::hard_link(&source, &target).is_ok();
+            if !linked {
+                fs::copy(&source, &target).with_context(|| {
+                    format!(
+                        "failed copying `{}` to `{}`",
+                        source.display(),
+                        target.display()
+                    )
+                })?;
+            }
+        }
+
+        // A directory cannot be atomically replaced with a non-empty one, so the existing tree is moved aside first
+        // and is only removed once the restored tree is in place.
+        if tree_path.exists() {
+            fs::rename(tree_path, &replaced_path).with_context(|| {
+                format!(
+                    "failed moving Merkle tree `{}` to `{}`",
+                    tree_path.display(),
+                    replaced_path.display()
+                )
+            })?;
+        }
+        fs::rename(&restored_path, tree_path).with_context(|| {
+            format!(
+                "failed moving restored Merkle tree `{}` to `{}`",
+                restored_path.display(),
+                tree_path.display()
+            )
+        })?;
+        if replaced_path.exists() {
+            fs::remove_dir_all(&replaced_path).with_context(|| {
+                format!(
+                    "failed removing replaced Merkle tree `{}`",
+                    replaced_path.display()
+                )
+            })?;
+        }
+
+        let latency = latency.observe();
+        tracing::info!(
+            "Restored Merkle tree at `{}` from checkpoint for L1 batch #{l1_batch_number} in {latency:?}",
+            tree_path.display()
+        );
+        Ok(())
+    }
+
+    fn sibling_path(path: &Path, suffix: &str) -> PathBuf {
+        let mut sibling_path = path.to_owned().into_os_string();
+        sibling_path.push(suffix);
+        PathBuf::from(sibling_path)
+    }
+
+    fn report_metrics(&self) -> anyhow::Result<()> {
+        let checkpoints = self.list()?;
+        CHECKPOINT_METRICS.count.set(checkpoints.len());
+        if let Some(latest) = checkpoints.last() {
+            CHECKPOINT_METRICS.latest_version.set(latest.0.into());
+        }
+        Ok(())
+    }
+}
@@ -2,13 +2,13 @@
 
 use std::mem;
 
-use anyhow::ensure;
+use anyhow::{ensure, Context as _};
 
 use crate::{
     hasher::{HashTree, HasherWithStats},
     types::{
         BlockOutputWithProofs, Key, LeafNode, TreeEntry, TreeEntryWithProof, TreeInstruction,
-        TreeLogEntry, ValueHash, TREE_DEPTH,
+        TreeLogEntry, TreeMultiProof, ValueHash, TREE_DEPTH,
     },
     utils,
 };
@@ -104,6 +104,189 @@ impl TreeEntryWithProof {
     }
 }
 
+/// Node in the union of Merkle paths covered by a [`TreeMultiProof`].
+#[derive(Debug)]
+struct MultiProofNode<T> {
+    /// Position of the node on its level, i.e., the key shifted right by the level depth.
+    position: Key,
+    /// Minimum depth (counting from the leaf level) starting from which adjacent hashes are explicitly
+    /// included into the proof. Adjacent hashes below this depth are empty subtree hashes.
+    min_explicit_depth: usize,
+    payload: T,
+}
+
+/// Folds the union of Merkle paths for the provided leaf nodes (which must be ordered by key and have unique keys)
+/// level by level, starting from the leaf level. Returns the payload for the root node.
+///
+/// `combine_siblings` is called for sibling nodes that both belong to the union, and `combine_with_adjacent`
+/// is called for nodes whose sibling doesn't belong to the union (i.e., the sibling hash must be taken from the proof).
+/// The payload of the parent node for combined siblings is the payload of the left sibling.
+fn fold_multiproof<T>(
+    mut level: Vec<MultiProofNode<T>>,
+    mut combine_siblings: impl FnMut(T, T) -> T,
+    mut combine_with_adjacent: impl FnMut(usize, MultiProofNode<T>) -> anyhow::Result<T>,
+) -> anyhow::Result<T> {
+    for depth in 0..TREE_DEPTH {
+        let mut next_level = Vec::with_capacity(level.len());
+        let mut nodes = level.into_iter().peekable();
+        while let Some(node) = nodes.next() {
+            let parent_position = node.position >> 1;
+            let min_explicit_depth = node.min_explicit_depth;
+            let sibling = nodes.next_if(|next| next.position >> 1 == parent_position);
+            let payload = if let Some(sibling) = sibling {
+                combine_siblings(node.payload, sibling.payload)
+            } else {
+                combine_with_adjacent(depth, node)?
+            };
+            next_level.push(MultiProofNode {
+                position: parent_position,
+                min_explicit_depth,
+                payload,
+            });
+        }
+        level = next_level;
+    }
+
+    let root = level.pop().context("multiproof contains no entries")?;
+    debug_assert!(level.is_empty(), "multiproof has multiple roots");
+    Ok(root.payload)
+}
+
+impl TreeMultiProof {
+    /// Creates a multiproof from proofs for separate entries. Proofs may be provided in any order and may contain
+    /// duplicate keys (in which case, only one of the duplicate proofs is used). All proofs must be obtained
+    /// for the same tree version; otherwise, the created multiproof will be invalid.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any of the provided Merkle paths is longer than the tree depth (256).
+    pub fn new(mut proofs: Vec<TreeEntryWithProof>) -> Self {
+        proofs.sort_unstable_by_key(|proof| proof.base.key);
+        proofs.dedup_by_key(|proof| proof.base.key);
+        if proofs.is_empty() {
+            return Self {
+                entries: vec![],
+                merkle_path_lengths: vec![],
+                hashes: vec![],
+            };
+        }
+
+        let leaves = proofs.iter().enumerate().map(|(i, proof)| {
+            let path_len = proof.merkle_path.len();
+            assert!(
+                path_len <= TREE_DEPTH,
+                "Merkle path for key {:?} is too long: {path_len}",
+                proof.base.key
+            );
+            MultiProofNode {
+                position: proof.base.key,
+                min_explicit_depth: TREE_DEPTH - path_len,
+                payload: i,
+            }
+        });
+        let mut hashes = vec![];
+        fold_multiproof(
+            leaves.collect(),
+            |left_idx, _| left_idx,
+            |depth, node| {
+                if depth >= node.min_explicit_depth {
+                    let merkle_path = &proofs[node.payload].merkle_path;
+                    hashes.push(merkle_path[depth - node.min_explicit_depth]);
+                }
+                Ok(node.payload)
+            },
+        )
+        .expect("folding non-empty multiproof is infallible");
+
+        let merkle_path_lengths = proofs
+            .iter()
+            .map(|proof| u16::try_from(proof.merkle_path.len()).unwrap())
+            // ^ `unwrap()` is safe: path lengths are checked above
+            .collect();
+        Self {
+            entries: proofs.into_iter().map(|proof| proof.base).collect(),
+            merkle_path_lengths,
+            hashes,
+        }
+    }
+
+    /// Verifies this multiproof.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error <=> proof is invalid.
+    pub fn verify(
+        &self,
+        hasher: &dyn HashTree,
+        trusted_root_hash: ValueHash,
+    ) -> anyhow::Result<()> {
+        ensure!(!self.entries.is_empty(), "Multiproof contains no entries");
+        ensure!(
+            self.entries.len() == self.merkle_path_lengths.len(),
+            "Mismatch between the number of entries ({}) and Merkle path lengths ({})",
+            self.entries.len(),
+            self.merkle_path_lengths.len()
+        );
+        ensure!(
+            self.entries
+                .windows(2)
+                .all(|window| window[0].key < window[1].key),
+            "Entries are not ordered by key or contain duplicate keys"
+        );
+
+        let mut leaves = Vec::with_capacity(self.entries.len());
+        for (entry, &path_len) in self.entries.iter().zip(&self.merkle_path_lengths) {
+            if entry.leaf_index == 0 {
+                ensure!(
+                    entry.value.is_zero(),
+                    "Invalid missing value specification for key {:?}: leaf index is zero, but value is non-default",
+                    entry.key
+                );
+            }
+            let path_len = usize::from(path_len);
+            ensure!(
+                path_len <= TREE_DEPTH,
+                "Merkle path for key {:?} is too long: {path_len}",
+                entry.key
+            );
+            leaves.push(MultiProofNode {
+                position: entry.key,
+                min_explicit_depth: TREE_DEPTH - path_len,
+                payload: hasher.hash_leaf(&entry.value, entry.leaf_index),
+            });
+        }
+
+        let mut hashes = self.hashes.iter();
+        let root_hash = fold_multiproof(
+            leaves,
+            |left, right| hasher.hash_branch(&left, &right),
+            |depth, node| {
+                let adjacent_hash = if depth >= node.min_explicit_depth {
+                    *hashes
+                        .next()
+                        .context("Multiproof contains too few hashes")?
+                } else {
+                    hasher.empty_subtree_hash(depth)
+                };
+                Ok(if node.position.bit(0) {
+                    hasher.hash_branch(&adjacent_hash, &node.payload)
+                } else {
+                    hasher.hash_branch(&node.payload, &adjacent_hash)
+                })
+            },
+        )?;
+        ensure!(
+            hashes.next().is_none(),
+            "Multiproof contains redundant hashes"
+        );
+        ensure!(
+            root_hash == trusted_root_hash,
+            "Root hash mismatch: got {root_hash}, want {trusted_root_hash}"
+        );
+        Ok(())
+    }
+}
+
 /// Range digest in a Merkle tree allowing to compute its root hash based on the provided entries.
 ///
 /// - The entries must be ordered by key. I.e., the first entry must have the numerically smallest key,
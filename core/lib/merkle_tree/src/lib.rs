@@ -58,11 +58,12 @@ pub use crate::{
     },
     types::{
         BlockOutput, BlockOutputWithProofs, Key, TreeEntry, TreeEntryWithProof, TreeInstruction,
-        TreeLogEntry, TreeLogEntryWithProof, ValueHash,
+        TreeLogEntry, TreeLogEntryWithProof, TreeMultiProof, ValueHash,
     },
 };
 use crate::{storage::Storage, types::Root};
 
+pub mod checkpoints;
 mod consistency;
 pub mod domain;
 mod errors;
@@ -398,3 +398,26 @@ pub(crate) struct RecoveryMetrics {
 
 #[vise::register]
 pub(crate) static RECOVERY_METRICS: Global<RecoveryMetrics> = Global::new();
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue, EncodeLabelSet)]
+#[metrics(label = "stage", rename_all = "snake_case")]
+pub(crate) enum CheckpointStage {
+    Create,
+    Prune,
+    Restore,
+}
+
+#[derive(Debug, Metrics)]
+#[metrics(prefix = "merkle_tree_checkpoints")]
+pub(crate) struct CheckpointMetrics {
+    /// Latency of a specific operation with tree checkpoints.
+    #[metrics(buckets = Buckets::LATENCIES, unit = Unit::Seconds)]
+    pub latency: Family<CheckpointStage, Histogram<Duration>>,
+    /// Number of checkpoints currently stored.
+    pub count: Gauge<usize>,
+    /// Tree version (= L1 batch number) of the latest stored checkpoint.
+    pub latest_version: Gauge<u64>,
+}
+
+#[vise::register]
+pub(crate) static CHECKPOINT_METRICS: Global<CheckpointMetrics> = Global::new();
@@ -270,6 +270,25 @@ impl RocksDBWrapper {
         StaleKeysRepairData::deserialize(&raw_value).map(Some)
     }
 
+    /// Creates a RocksDB checkpoint of this database at the specified `path`, which must not exist.
+    pub(crate) fn create_checkpoint(&self, path: &Path) -> anyhow::Result<()> {
+        self.db
+            .create_checkpoint(path)
+            .with_context(|| format!("failed creating RocksDB checkpoint at `{}`", path.display()))
+    }
+
+    /// Catches up this database with the primary instance if the database is opened
+    /// [in the secondary mode](RocksDB::open_secondary()). Otherwise, this is a no-op.
+    ///
+    /// # Errors
+    ///
+    /// Proxies RocksDB errors.
+    pub fn try_catch_up_with_primary(&self) -> anyhow::Result<()> {
+        self.db
+            .try_catch_up_with_primary()
+            .context("failed catching up secondary RocksDB instance with primary")
+    }
+
     /// Returns the wrapped RocksDB instance.
     pub fn into_inner(self) -> RocksDB<MerkleTreeColumnFamily> {
         self.db
@@ -116,6 +116,27 @@ pub struct TreeEntryWithProof {
     pub merkle_path: Vec<ValueHash>,
 }
 
+/// Merkle multiproof for several entries in a Merkle tree. Unlike a set of [`TreeEntryWithProof`]s,
+/// a multiproof doesn't duplicate hashes shared by Merkle paths of different entries, and it omits hashes
+/// that can be computed from the proven entries themselves.
+///
+/// A multiproof can be created from separate proofs using [`Self::new()`] and verified using [`Self::verify()`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TreeMultiProof {
+    /// Proven entries ordered by key. Keys are unique.
+    pub entries: Vec<TreeEntry>,
+    /// Lengths of Merkle paths for each of `entries`, with the same semantics as the length of
+    /// [`TreeEntryWithProof::merkle_path`]. That is, hashes for the first `256 - merkle_path_lengths[i]` levels
+    /// of the tree starting from the leaf level are empty subtree hashes and are omitted from the proof.
+    pub merkle_path_lengths: Vec<u16>,
+    /// Hashes of subtrees adjacent to the union of Merkle paths for `entries`, which cannot be computed
+    /// from the entries and are not omitted as described above.
+    ///
+    /// Hashes are ordered by the tree level (starting from the leaf level), and within a level by the key
+    /// (starting from the numerically smallest key).
+    pub hashes: Vec<ValueHash>,
+}
+
 /// Output of inserting a block of entries into a Merkle tree.
 #[derive(Debug, PartialEq, Eq)]
 pub struct BlockOutput {
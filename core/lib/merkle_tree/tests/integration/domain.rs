@@ -6,7 +6,9 @@ use serde::{Deserialize, Serialize};
 use serde_with::{hex::Hex, serde_as};
 use tempfile::TempDir;
 use zksync_crypto_primitives::hasher::blake2::Blake2Hasher;
-use zksync_merkle_tree::{domain::ZkSyncTree, HashTree, TreeEntry, TreeInstruction};
+use zksync_merkle_tree::{
+    checkpoints::TreeCheckpoints, domain::ZkSyncTree, HashTree, TreeEntry, TreeInstruction,
+};
 use zksync_prover_interface::inputs::StorageLogMetadata;
 use zksync_storage::RocksDB;
 use zksync_system_constants::ACCOUNT_CODE_STORAGE_ADDRESS;
@@ -295,6 +297,109 @@ fn revert_blocks() {
     assert_eq!(tree.next_l1_batch_number(), L1BatchNumber(3));
 }
 
+#[test]
+fn restoring_tree_from_checkpoint() {
+    let temp_dir = TempDir::new().expect("failed get temporary directory for RocksDB");
+    let tree_path = temp_dir.path().join("tree");
+    let checkpoints = TreeCheckpoints::new(temp_dir.path().join("checkpoints"));
+    let logs = gen_storage_logs();
+    let batches: Vec<_> = logs.chunks(10).collect();
+
+    let mut root_hashes = vec![];
+    {
+        let db = RocksDB::new(&tree_path).unwrap();
+        let mut tree = ZkSyncTree::new_lightweight(db.into()).unwrap();
+        assert_eq!(checkpoints.create(&tree.reader()).unwrap(), None);
+        for (i, batch) in batches.iter().enumerate() {
+            root_hashes.push(tree.process_l1_batch(batch).unwrap().root_hash);
+            tree.save().unwrap();
+            if i % 3 == 0 {
+                checkpoints.create(&tree.reader()).unwrap();
+            }
+        }
+        // Pruning must not influence checkpoints.
+        let (mut pruner, _handle) = tree.pruner();
+        pruner.prune_up_to(9).unwrap();
+    }
+
+    let all_checkpoints = [0, 3, 6, 9].map(L1BatchNumber);
+    assert_eq!(checkpoints.list().unwrap(), all_checkpoints);
+    assert_eq!(checkpoints.retain_latest(3).unwrap(), [L1BatchNumber(0)]);
+    assert_eq!(
+        checkpoints.nearest(L1BatchNumber(5)).unwrap(),
+        Some(L1BatchNumber(3))
+    );
+    assert_eq!(checkpoints.nearest(L1BatchNumber(2)).unwrap(), None);
+
+    checkpoints.restore(L1BatchNumber(3), &tree_path).unwrap();
+    let db = RocksDB::new(&tree_path).unwrap();
+    let mut tree = ZkSyncTree::new_lightweight(db.into()).unwrap();
+    assert_eq!(tree.next_l1_batch_number(), L1BatchNumber(4));
+    assert_eq!(tree.root_hash(), root_hashes[3]);
+    for (batch, &expected_root_hash) in batches[4..].iter().zip(&root_hashes[4..]) {
+        let root_hash = tree.process_l1_batch(batch).unwrap().root_hash;
+        assert_eq!(root_hash, expected_root_hash);
+    }
+    tree.save().unwrap();
+    drop(tree);
+
+    // Check that the checkpoint wasn't modified by the restored tree.
+    assert_eq!(
+        checkpoints.remove_after(L1BatchNumber(5)).unwrap(),
+        [L1BatchNumber(6), L1BatchNumber(9)]
+    );
+    checkpoints.restore(L1BatchNumber(3), &tree_path).unwrap();
+    let db = RocksDB::new(&tree_path).unwrap();
+    let tree = ZkSyncTree::new_lightweight(db.into()).unwrap();
+    assert_eq!(tree.next_l1_batch_number(), L1BatchNumber(4));
+    assert_eq!(tree.root_hash(), root_hashes[3]);
+    tree.verify_consistency(L1BatchNumber(3)).unwrap();
+}
+
+#[test]
+fn failed_or_interrupted_restore_from_checkpoint() {
+    let temp_dir = TempDir::new().expect("failed get temporary directory for RocksDB");
+    let tree_path = temp_dir.path().join("tree");
+    let checkpoints = TreeCheckpoints::new(temp_dir.path().join("checkpoints"));
+    let logs = gen_storage_logs();
+
+    let root_hash = {
+        let db = RocksDB::new(&tree_path).unwrap();
+        let mut tree = ZkSyncTree::new_lightweight(db.into()).unwrap();
+        tree.process_l1_batch(&logs[..10]).unwrap();
+        tree.save().unwrap();
+        checkpoints.create(&tree.reader()).unwrap();
+        let root_hash = tree.process_l1_batch(&logs[10..20]).unwrap().root_hash;
+        tree.save().unwrap();
+        root_hash
+    };
+
+    // Restoring from a missing checkpoint must not touch the tree.
+    checkpoints
+        .restore(L1BatchNumber(1), &tree_path)
+        .unwrap_err();
+    let db = RocksDB::new(&tree_path).unwrap();
+    let tree = ZkSyncTree::new_lightweight(db.into()).unwrap();
+    assert_eq!(tree.next_l1_batch_number(), L1BatchNumber(2));
+    assert_eq!(tree.root_hash(), root_hash);
+    drop(tree);
+
+    // Emulate a restore interrupted after moving the tree aside.
+    let replaced_path = temp_dir.path().join("tree.replaced.tmp");
+    std::fs::rename(&tree_path, &replaced_path).unwrap();
+    let restored_path = temp_dir.path().join("tree.restored.tmp");
+    std::fs::create_dir(&restored_path).unwrap();
+    std::fs::write(restored_path.join("garbage"), b"garbage").unwrap();
+
+    checkpoints.restore(L1BatchNumber(0), &tree_path).unwrap();
+    assert!(!replaced_path.exists());
+    assert!(!restored_path.exists());
+    let db = RocksDB::new(&tree_path).unwrap();
+    let tree = ZkSyncTree::new_lightweight(db.into()).unwrap();
+    assert_eq!(tree.next_l1_batch_number(), L1BatchNumber(1));
+    assert!(!tree_path.join("garbage").exists());
+}
+
 #[test]
 fn reset_tree() {
     let temp_dir = TempDir::new().expect("failed get temporary directory for RocksDB");
@@ -7,7 +7,7 @@ use test_casing::test_casing;
 use zksync_crypto_primitives::hasher::blake2::Blake2Hasher;
 use zksync_merkle_tree::{
     Database, HashTree, MerkleTree, PatchSet, Patched, PruneDatabase, TreeEntry, TreeInstruction,
-    TreeLogEntry, TreeRangeDigest,
+    TreeLogEntry, TreeMultiProof, TreeRangeDigest,
 };
 use zksync_types::{AccountTreeId, Address, StorageKey, H256, U256};
 
@@ -114,6 +114,92 @@ fn entry_proofs_are_computed_correctly_on_empty_tree(kv_count: u64) {
     }
 }
 
+#[test_casing(8, KV_COUNTS)]
+fn multiproofs_are_computed_correctly(kv_count: u64) {
+    const RNG_SEED: u64 = 321;
+
+    let mut rng = StdRng::seed_from_u64(RNG_SEED);
+    let mut tree = MerkleTree::new(PatchSet::default()).unwrap();
+    let kvs = generate_key_value_pairs(0..kv_count);
+    let expected_hash = compute_tree_hash(kvs.iter().copied());
+    tree.extend(kvs.clone()).unwrap();
+
+    let mut keys: Vec<_> = kvs.iter().map(|entry| entry.key).collect();
+    // Add some missing keys, including ones adjacent to existing keys, and duplicates.
+    let adjacent_keys = kvs
+        .iter()
+        .map(|entry| entry.key ^ (U256::one() << rng.gen_range(0..256)));
+    let missing_keys = generate_key_value_pairs(kv_count..(kv_count + 5))
+        .into_iter()
+        .map(|entry| entry.key);
+    keys.extend(adjacent_keys.take(10).chain(missing_keys));
+    keys.extend(keys[..3].to_vec());
+    keys.shuffle(&mut rng);
+
+    let proofs = tree.entries_with_proofs(0, &keys).unwrap();
+    let separate_hash_count: usize = proofs.iter().map(|proof| proof.merkle_path.len()).sum();
+    let multiproof = TreeMultiProof::new(proofs);
+    assert!(multiproof
+        .entries
+        .windows(2)
+        .all(|window| window[0].key < window[1].key));
+    assert!(multiproof.hashes.len() < separate_hash_count);
+    multiproof.verify(&Blake2Hasher, expected_hash).unwrap();
+    for kv in &kvs {
+        assert!(multiproof.entries.contains(kv));
+    }
+
+    // Check that the multiproof is not valid if it's tampered with.
+    let mut tampered = multiproof.clone();
+    tampered.entries[0].value = H256::repeat_byte(0xff);
+    tampered.entries[0].leaf_index = 1;
+    tampered.verify(&Blake2Hasher, expected_hash).unwrap_err();
+
+    if !multiproof.hashes.is_empty() {
+        let mut tampered = multiproof.clone();
+        tampered.hashes.pop();
+        tampered.verify(&Blake2Hasher, expected_hash).unwrap_err();
+    }
+
+    let mut tampered = multiproof.clone();
+    tampered.hashes.push(H256::zero());
+    tampered.verify(&Blake2Hasher, expected_hash).unwrap_err();
+
+    let mut tampered = multiproof.clone();
+    tampered.entries.swap(0, 1);
+    tampered.verify(&Blake2Hasher, expected_hash).unwrap_err();
+
+    multiproof.verify(&Blake2Hasher, H256::zero()).unwrap_err();
+}
+
+#[test]
+fn multiproof_for_single_entry_matches_entry_proof() {
+    let mut tree = MerkleTree::new(PatchSet::default()).unwrap();
+    let kvs = generate_key_value_pairs(0..100);
+    tree.extend(kvs.clone()).unwrap();
+
+    let [proof] = tree
+        .entries_with_proofs(0, &[kvs[0].key])
+        .unwrap()
+        .try_into()
+        .unwrap();
+    let multiproof = TreeMultiProof::new(vec![proof.clone()]);
+    assert_eq!(multiproof.entries, [proof.base]);
+    assert_eq!(
+        multiproof.merkle_path_lengths,
+        [proof.merkle_path.len() as u16]
+    );
+    assert_eq!(multiproof.hashes, proof.merkle_path);
+    multiproof
+        .verify(&Blake2Hasher, tree.latest_root_hash())
+        .unwrap();
+
+    let empty_multiproof = TreeMultiProof::new(vec![]);
+    empty_multiproof
+        .verify(&Blake2Hasher, tree.latest_root_hash())
+        .unwrap_err();
+}
+
 #[test]
 fn proofs_are_computed_correctly_for_mixed_instructions() {
     const RNG_SEED: u64 = 123;
@@ -7,6 +7,7 @@ use zksync_config::configs::object_store::{ObjectStoreConfig, ObjectStoreMode};
 use crate::{
     file::FileBackedObjectStore,
     gcs::{GoogleCloudStore, GoogleCloudStoreAuthMode},
+    http::HttpObjectStore,
     mirror::MirroringObjectStore,
     raw::{ObjectStore, ObjectStoreError},
     retries::StoreWithRetries,
@@ -134,6 +135,13 @@ impl ObjectStoreFactory {
                 .await?;
                 Self::wrap_mirroring(store, config.local_mirror_path.as_deref()).await
             }
+            ObjectStoreMode::HttpReadOnly { bucket_base_url } => {
+                let store = StoreWithRetries::try_new(config.max_retries, || async {
+                    HttpObjectStore::new(bucket_base_url.clone())
+                })
+                .await?;
+                Self::wrap_mirroring(store, config.local_mirror_path.as_deref()).await
+            }
 
             ObjectStoreMode::FileBacked {
                 file_backed_base_path,
@@ -115,7 +115,7 @@ impl From<AuthError> for ObjectStoreError {
     }
 }
 
-fn is_retriable_http_error(err: &reqwest::Error) -> bool {
+pub(crate) fn is_retriable_http_error(err: &reqwest::Error) -> bool {
     err.is_timeout()
         || err.is_connect()
         // Not all request errors are logically transient, but a significant part of them are (e.g.,
@@ -0,0 +1,94 @@
+//! Read-only [`ObjectStore`] implementation fetching objects over HTTP.
+
+use std::time::Duration;
+
+use async_trait::async_trait;
+use http::StatusCode;
+
+use crate::{
+    gcs::is_retriable_http_error,
+    raw::{Bucket, ObjectStore, ObjectStoreError},
+};
+
+impl From<reqwest::Error> for ObjectStoreError {
+    fn from(err: reqwest::Error) -> Self {
+        if err.status() == Some(StatusCode::NOT_FOUND) {
+            ObjectStoreError::KeyNotFound(err.into())
+        } else {
+            ObjectStoreError::Other {
+                is_retriable: is_retriable_http_error(&err),
+                source: err.into(),
+            }
+        }
+    }
+}
+
+/// Read-only [`ObjectStore`] fetching objects via `GET {base_url}/{bucket}/{key}` requests, i.e., using
+/// the same layout as [`FileBackedObjectStore`](crate::FileBackedObjectStore). Can be pointed to any static
+/// file server, or to another node serving its snapshot files.
+#[derive(Debug)]
+pub struct HttpObjectStore {
+    base_url: String,
+    client: reqwest::Client,
+}
+
+impl HttpObjectStore {
+    const REQUEST_TIMEOUT: Duration = Duration::from_secs(60);
+
+    /// Creates a new store with the specified base URL.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the HTTP client cannot be initialized.
+    pub fn new(base_url: String) -> Result<Self, ObjectStoreError> {
+        let client = reqwest::Client::builder()
+            .timeout(Self::REQUEST_TIMEOUT)
+            .build()
+            .map_err(|err| ObjectStoreError::Initialization {
+                source: err.into(),
+                is_retriable: false,
+            })?;
+        Ok(Self {
+            base_url: base_url.trim_end_matches('/').to_owned(),
+            client,
+        })
+    }
+
+    fn url(&self, bucket: Bucket, key: &str) -> String {
+        format!("{}/{bucket}/{key}", self.base_url)
+    }
+
+    fn read_only_error(&self) -> ObjectStoreError {
+        ObjectStoreError::Other {
+            is_retriable: false,
+            source: format!("HTTP object store at {} is read-only", self.base_url).into(),
+        }
+    }
+}
+
+#[async_trait]
+impl ObjectStore for HttpObjectStore {
+    async fn get_raw(&self, bucket: Bucket, key: &str) -> Result<Vec<u8>, ObjectStoreError> {
+        let url = self.url(bucket, key);
+        tracing::trace!("Fetching data from {url}");
+        let response = self.client.get(&url).send().await?.error_for_status()?;
+        Ok(response.bytes().await?.to_vec())
+    }
+
+    async fn put_raw(
+        &self,
+        _bucket: Bucket,
+        _key: &str,
+        _value: Vec<u8>,
+    ) -> Result<(), ObjectStoreError> {
+        Err(self.read_only_error())
+    }
+
+    async fn remove_raw(&self, _bucket: Bucket, _key: &str) -> Result<(), ObjectStoreError> {
+        Err(self.read_only_error())
+    }
+
+    fn storage_prefix_raw(&self, bucket: Bucket) -> String {
+        format!("{}/{bucket}", self.base_url)
+    }
+}
@@ -3,6 +3,7 @@
 //!
 //! - [File-backed store](FileBackedObjectStore) saving blobs as separate files in the local filesystem
 //! - [GCS-based store](GoogleCloudStore)
+//! - [Read-only HTTP store](HttpObjectStore) fetching objects from a static file server
 //! - [Mock in-memory store](MockObjectStore)
 //!
 //! Normally, these implementations are not used directly. Instead, a store trait object (`Arc<dyn ObjectStore>`)
@@ -26,6 +27,7 @@
 mod factory;
 mod file;
 mod gcs;
+mod http;
 mod metrics;
 mod mirror;
 mod mock;
@@ -48,6 +50,7 @@ pub use self::{
     factory::ObjectStoreFactory,
     file::FileBackedObjectStore,
     gcs::{GoogleCloudStore, GoogleCloudStoreAuthMode},
+    http::HttpObjectStore,
     mock::MockObjectStore,
     objects::StoredObject,
     raw::{Bucket, ObjectStore, ObjectStoreError},
@@ -53,6 +53,21 @@ pub struct PollGeneratedProofsRequest {
     pub l1_batch_id: L1BatchId,
 }
 
+/// Long-poll request for proof generation data: the server holds the request for up to
+/// `timeout_secs` until the data becomes available.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WaitForProofGenerationDataRequest {
+    pub timeout_secs: u64,
+}
+
+/// Long-poll request for a generated proof: the server holds the request for up to
+/// `timeout_secs` until the proof becomes available.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WaitForGeneratedProofRequest {
+    pub l1_batch_id: L1BatchId,
+    pub timeout_secs: u64,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct PollGeneratedProofsResponse {
     pub l1_batch_id: L1BatchId,
@@ -8,6 +8,8 @@ pub mod api;
 /// Inputs for proof generation provided by the core subsystem.
 pub mod inputs;
 pub mod legacy;
+/// Helpers for long-poll requests between the server and the prover gateway.
+pub mod long_poll;
 /// Outputs of proof generation provided by the prover subsystem.
 pub mod outputs;
 
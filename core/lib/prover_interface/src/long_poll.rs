@@ -0,0 +1,276 @@
+//! Helpers for long-poll requests used in push mode: instead of answering right away, the server
+//! holds the request until the requested data becomes available or the timeout passes.
+//!
+//! The awaited data is produced by other processes and is only observable via the database, so held
+//! requests check for it periodically. The resulting load is bounded: each held request performs at most
+//! one check per [`CHECK_INTERVAL`] for at most [`MAX_TIMEOUT`], and at most [`MAX_CONCURRENT_WAITS`]
+//! requests are held at the same time; other requests are rejected with [`SATURATED_STATUS`], so that
+//! clients back off instead of retrying right away.
+
+use std::{
+    future::Future,
+    hint::black_box,
+    sync::atomic::{AtomicBool, Ordering},
+    time::Duration,
+};
+
+use http::{header, HeaderMap, StatusCode};
+use secrecy::ExposeSecret;
+use tokio::{sync::Semaphore, time::Instant};
+use zksync_types::secrets::APIKey;
+
+/// Interval between checks for new data while a long-poll request is held.
+pub const CHECK_INTERVAL: Duration = Duration::from_secs(1);
+/// Upper bound for the timeout requested by the client, so that requests aren't held forever.
+pub const MAX_TIMEOUT: Duration = Duration::from_secs(120);
+/// Maximum number of long-poll requests held at the same time.
+pub const MAX_CONCURRENT_WAITS: usize = 8;
+/// Extra time given to the server to respond on top of the requested timeout.
+pub const RESPONSE_MARGIN: Duration = Duration::from_secs(10);
+/// Status returned for long-poll requests if too many requests are already held.
+pub const SATURATED_STATUS: StatusCode = StatusCode::SERVICE_UNAVAILABLE;
+
+/// Checks the bearer token of a long-poll request. The token is compared in constant time.
+pub fn is_authorized(headers: &HeaderMap, token: &APIKey) -> bool {
+    headers
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .is_some_and(|value| constant_time_eq(value.as_bytes(), token.expose_secret().as_bytes()))
+}
+
+/// Compares byte strings without short-circuiting on the first mismatch. Only the length is leaked.
+fn constant_time_eq(lhs: &[u8], rhs: &[u8]) -> bool {
+    if lhs.len() != rhs.len() {
+        return false;
+    }
+    let diff = lhs
+        .iter()
+        .zip(rhs)
+        .fold(0_u8, |acc, (&l, &r)| black_box(acc | (l ^ r)));
+    diff == 0
+}
+
+/// Error returned by [`LongPollWaiter::wait_for()`].
+#[derive(Debug, PartialEq)]
+pub enum WaitError<E> {
+    /// Too many requests are already held. The request should be rejected with [`SATURATED_STATUS`].
+    Saturated,
+    /// Checking for data failed.
+    Check(E),
+}
+
+/// Holds long-poll requests, limiting the number of concurrently held ones.
+#[derive(Debug)]
+pub struct LongPollWaiter {
+    permits: Semaphore,
+}
+
+impl Default for LongPollWaiter {
+    fn default() -> Self {
+        Self::new(MAX_CONCURRENT_WAITS)
+    }
+}
+
+impl LongPollWaiter {
+    pub fn new(max_concurrent_waits: usize) -> Self {
+        Self {
+            permits: Semaphore::new(max_concurrent_waits),
+        }
+    }
+
+    /// Calls `check` until it returns some data or the timeout passes. If too many requests are
+    /// already held, returns [`WaitError::Saturated`] without calling `check`.
+    pub async fn wait_for<T, E, Fut>(
+        &self,
+        timeout: Duration,
+        mut check: impl FnMut() -> Fut,
+    ) -> Result<Option<T>, WaitError<E>>
+    where
+        Fut: Future<Output = Result<Option<T>, E>>,
+    {
+        let Ok(_permit) = self.permits.try_acquire() else {
+            return Err(WaitError::Saturated);
+        };
+
+        let deadline = Instant::now() + timeout.min(MAX_TIMEOUT);
+        loop {
+            if let Some(data) = check().await.map_err(WaitError::Check)? {
+                return Ok(Some(data));
+            }
+            let now = Instant::now();
+            if now >= deadline {
+                return Ok(None);
+            }
+            tokio::time::sleep(CHECK_INTERVAL.min(deadline - now)).await;
+        }
+    }
+}
+
+/// Tracks whether a client uses long-poll requests. Push mode is disabled once the server responds
+/// with 404, i.e. it doesn't support long-polling (or doesn't have an auth token configured),
+/// in which case the client falls back to polling.
+#[derive(Debug)]
+pub struct PushMode(AtomicBool);
+
+impl PushMode {
+    pub fn new(enabled: bool) -> Self {
+        Self(AtomicBool::new(enabled))
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    /// Handles the status of a failed long-poll request. Returns `true` if push mode was disabled.
+    pub fn handle_error_status(&self, status: Option<StatusCode>) -> bool {
+        if status == Some(StatusCode::NOT_FOUND) {
+            self.0.store(false, Ordering::Relaxed);
+            true
+        } else {
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    use http::HeaderValue;
+
+    use super::*;
+
+    fn headers(authorization: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            header::AUTHORIZATION,
+            HeaderValue::from_str(authorization).unwrap(),
+        );
+        headers
+    }
+
+    #[test]
+    fn authorizing_requests() {
+        let token = APIKey("secret".to_owned().into());
+        assert!(is_authorized(&headers("Bearer secret"), &token));
+
+        assert!(!is_authorized(&HeaderMap::new(), &token));
+        assert!(!is_authorized(&headers("secret"), &token));
+        assert!(!is_authorized(&headers("Basic secret"), &token));
+        assert!(!is_authorized(&headers("Bearer secreT"), &token));
+        assert!(!is_authorized(&headers("Bearer secret2"), &token));
+        assert!(!is_authorized(&headers("Bearer "), &token));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn waiting_for_data() {
+        let waiter = LongPollWaiter::default();
+        let calls = AtomicUsize::new(0);
+        let started_at = Instant::now();
+        let data = waiter
+            .wait_for(Duration::from_secs(10), || async {
+                let call = calls.fetch_add(1, Ordering::Relaxed);
+                Ok::<_, ()>((call == 2).then_some(call))
+            })
+            .await
+            .unwrap();
+        assert_eq!(data, Some(2));
+        assert_eq!(started_at.elapsed(), CHECK_INTERVAL * 2);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn waiting_for_data_times_out() {
+        let waiter = LongPollWaiter::default();
+        let started_at = Instant::now();
+        let data = waiter
+            .wait_for(Duration::from_millis(2_500), || async {
+                Ok::<Option<()>, ()>(None)
+            })
+            .await
+            .unwrap();
+        assert_eq!(data, None);
+        assert_eq!(started_at.elapsed(), Duration::from_millis(2_500));
+
+        // The requested timeout is capped.
+        let started_at = Instant::now();
+        waiter
+            .wait_for(Duration::from_secs(3_600), || async {
+                Ok::<Option<()>, ()>(None)
+            })
+            .await
+            .unwrap();
+        assert_eq!(started_at.elapsed(), MAX_TIMEOUT);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn waiting_for_data_propagates_errors() {
+        let waiter = LongPollWaiter::default();
+        let err = waiter
+            .wait_for(Duration::from_secs(10), || async {
+                Err::<Option<()>, _>("oops")
+            })
+            .await
+            .unwrap_err();
+        assert_eq!(err, WaitError::Check("oops"));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn saturated_waiter_rejects_requests() {
+        let waiter = Arc::new(LongPollWaiter::new(1));
+        let held_wait = tokio::spawn({
+            let waiter = waiter.clone();
+            async move {
+                waiter
+                    .wait_for(Duration::from_secs(10), || async {
+                        Ok::<Option<()>, ()>(None)
+                    })
+                    .await
+            }
+        });
+        tokio::time::sleep(CHECK_INTERVAL / 2).await;
+
+        let calls = AtomicUsize::new(0);
+        let started_at = Instant::now();
+        let err = waiter
+            .wait_for(Duration::from_secs(10), || async {
+                calls.fetch_add(1, Ordering::Relaxed);
+                Ok::<Option<()>, ()>(None)
+            })
+            .await
+            .unwrap_err();
+        assert_eq!(err, WaitError::Saturated);
+        assert_eq!(calls.load(Ordering::Relaxed), 0);
+        assert_eq!(started_at.elapsed(), Duration::ZERO);
+
+        held_wait.await.unwrap().unwrap();
+
+        // Once the held request is answered, new requests can be held again.
+        let started_at = Instant::now();
+        let data = waiter
+            .wait_for(Duration::from_secs(2), || async {
+                calls.fetch_add(1, Ordering::Relaxed);
+                Ok::<Option<()>, ()>(None)
+            })
+            .await
+            .unwrap();
+        assert_eq!(data, None);
+        assert_eq!(calls.load(Ordering::Relaxed), 3);
+        assert_eq!(started_at.elapsed(), Duration::from_secs(2));
+    }
+
+    #[test]
+    fn push_mode_falls_back_to_polling_on_not_found() {
+        let push_mode = PushMode::new(true);
+        assert!(!push_mode.handle_error_status(None));
+        assert!(!push_mode.handle_error_status(Some(StatusCode::UNAUTHORIZED)));
+        assert!(!push_mode.handle_error_status(Some(StatusCode::INTERNAL_SERVER_ERROR)));
+        assert!(push_mode.is_enabled());
+
+        assert!(push_mode.handle_error_status(Some(StatusCode::NOT_FOUND)));
+        assert!(!push_mode.is_enabled());
+    }
+}
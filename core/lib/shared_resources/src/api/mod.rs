@@ -6,8 +6,12 @@ use tokio::sync::RwLock;
 use zksync_node_framework::Resource;
 use zksync_types::{api, Address};
 
-pub use self::sync_state::{SyncState, SyncStateData};
+pub use self::{
+    preconfirmations::Preconfirmations,
+    sync_state::{SyncState, SyncStateData},
+};
 
+mod preconfirmations;
 mod sync_state;
 
 /// Shared bridge addresses.
@@ -0,0 +1,153 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::{Arc, Mutex},
+};
+
+use tokio::sync::broadcast;
+use zksync_node_framework::Resource;
+use zksync_types::{api::TransactionPreconfirmation, H256};
+
+/// Capacity of the broadcast channel for preconfirmations. Subscribers lagging behind by more than this number
+/// of preconfirmations will miss some of them.
+const CHANNEL_CAPACITY: usize = 4_096;
+/// Number of latest preconfirmations retained for lookups by transaction hash.
+const RETAINED_PRECONFIRMATIONS: usize = 10_000;
+
+#[derive(Debug, Default)]
+struct RecentPreconfirmations {
+    by_hash: HashMap<H256, TransactionPreconfirmation>,
+    order: VecDeque<H256>,
+}
+
+impl RecentPreconfirmations {
+    fn insert(&mut self, preconfirmation: TransactionPreconfirmation) {
+        let hash = preconfirmation.transaction_hash;
+        if self.by_hash.insert(hash, preconfirmation).is_none() {
+            self.order.push_back(hash);
+        }
+        while self.order.len() > RETAINED_PRECONFIRMATIONS {
+            if let Some(evicted_hash) = self.order.pop_front() {
+                self.by_hash.remove(&evicted_hash);
+            }
+        }
+    }
+}
+
+/// Stream of transaction preconfirmations, i.e., execution results reported by the state keeper before
+/// the corresponding L2 block is sealed.
+///
+/// Preconfirmations are published by the state keeper and consumed by the Web3 API server. Besides broadcasting,
+/// a bounded number of latest preconfirmations is retained so that they can be looked up by the transaction hash.
+#[derive(Debug, Clone)]
+pub struct Preconfirmations {
+    sender: broadcast::Sender<TransactionPreconfirmation>,
+    recent: Arc<Mutex<RecentPreconfirmations>>,
+}
+
+impl Default for Preconfirmations {
+    fn default() -> Self {
+        Self {
+            sender: broadcast::channel(CHANNEL_CAPACITY).0,
+            recent: Arc::default(),
+        }
+    }
+}
+
+impl Resource for Preconfirmations {
+    fn name() -> String {
+        "api/preconfirmations".into()
+    }
+}
+
+impl Preconfirmations {
+    /// Publishes a preconfirmation, overwriting the previous one for the same transaction.
+    pub fn publish(&self, preconfirmation: TransactionPreconfirmation) {
+        self.recent
+            .lock()
+            .expect("preconfirmations are poisoned")
+            .insert(preconfirmation.clone());
+        // An error only means that there are no subscribers at the moment, which is fine.
+        self.sender.send(preconfirmation).ok();
+    }
+
+    /// Subscribes to preconfirmations published after this call.
+    pub fn subscribe(&self) -> broadcast::Receiver<TransactionPreconfirmation> {
+        self.sender.subscribe()
+    }
+
+    /// Returns the latest preconfirmation for the specified transaction, if it is retained.
+    pub fn get(&self, tx_hash: H256) -> Option<TransactionPreconfirmation> {
+        self.recent
+            .lock()
+            .expect("preconfirmations are poisoned")
+            .by_hash
+            .get(&tx_hash)
+            .cloned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_types::api::PreconfirmationStatus;
+
+    use super::*;
+
+    fn mock_preconfirmation(
+        hash: H256,
+        status: PreconfirmationStatus,
+    ) -> TransactionPreconfirmation {
+        TransactionPreconfirmation {
+            transaction_hash: hash,
+            status,
+            block_number: 1.into(),
+            transaction_index: 0.into(),
+            success: true,
+            gas_used: 21_000.into(),
+            logs: vec![],
+        }
+    }
+
+    #[tokio::test]
+    async fn publishing_preconfirmations() {
+        let preconfirmations = Preconfirmations::default();
+        let mut receiver = preconfirmations.subscribe();
+        let hash = H256::repeat_byte(1);
+
+        preconfirmations.publish(mock_preconfirmation(
+            hash,
+            PreconfirmationStatus::Preconfirmed,
+        ));
+        let received = receiver.recv().await.unwrap();
+        assert_eq!(received.transaction_hash, hash);
+        assert_eq!(
+            preconfirmations.get(hash).unwrap().status,
+            PreconfirmationStatus::Preconfirmed
+        );
+
+        preconfirmations.publish(mock_preconfirmation(
+            hash,
+            PreconfirmationStatus::RolledBack,
+        ));
+        let received = receiver.recv().await.unwrap();
+        assert_eq!(received.status, PreconfirmationStatus::RolledBack);
+        assert_eq!(
+            preconfirmations.get(hash).unwrap().status,
+            PreconfirmationStatus::RolledBack
+        );
+        assert!(preconfirmations.get(H256::zero()).is_none());
+    }
+
+    #[test]
+    fn old_preconfirmations_are_evicted() {
+        let preconfirmations = Preconfirmations::default();
+        for i in 0..=RETAINED_PRECONFIRMATIONS as u64 {
+            let hash = H256::from_low_u64_be(i + 1);
+            preconfirmations.publish(mock_preconfirmation(
+                hash,
+                PreconfirmationStatus::Preconfirmed,
+            ));
+        }
+        assert!(preconfirmations.get(H256::from_low_u64_be(1)).is_none());
+        assert!(preconfirmations.get(H256::from_low_u64_be(2)).is_some());
+    }
+}
@@ -1,7 +1,12 @@
 //! Logic for applying application-level snapshots to Postgres storage.
 
 use std::{
-    cmp::Ordering, collections::HashMap, fmt, mem, num::NonZeroUsize, sync::Arc, time::Duration,
+    cmp::Ordering,
+    collections::HashMap,
+    fmt, mem,
+    num::NonZeroUsize,
+    sync::{Arc, Mutex},
+    time::Duration,
 };
 
 use anyhow::Context as _;
@@ -15,11 +20,12 @@ use zksync_types::{
     api,
     bytecode::{BytecodeHash, BytecodeMarker},
     snapshots::{
-        SnapshotFactoryDependencies, SnapshotHeader, SnapshotRecoveryStatus, SnapshotStorageLog,
-        SnapshotStorageLogsChunk, SnapshotStorageLogsStorageKey, SnapshotVersion,
+        uniform_hashed_keys_chunk, SnapshotFactoryDependencies, SnapshotHeader,
+        SnapshotRecoveryStatus, SnapshotStorageLog, SnapshotStorageLogsChunk,
+        SnapshotStorageLogsStorageKey, SnapshotVersion,
     },
     tokens::TokenInfo,
-    L1BatchNumber, L2BlockNumber, OrStopped, StorageKey, H256,
+    Address, L1BatchNumber, L2BlockNumber, OrStopped, StorageKey, H256,
 };
 use zksync_web3_decl::{
     client::{DynClient, L2},
@@ -253,6 +259,7 @@ pub struct SnapshotApplierTaskStats {
 pub struct SnapshotsApplierTask {
     snapshot_l1_batch: Option<L1BatchNumber>,
     drop_storage_key_preimages: bool,
+    header_signer: Option<Address>,
     config: SnapshotsApplierConfig,
     health_updater: HealthUpdater,
     connection_pool: ConnectionPool<Core>,
@@ -270,6 +277,7 @@ impl SnapshotsApplierTask {
         Self {
             snapshot_l1_batch: None,
             drop_storage_key_preimages: false,
+            header_signer: None,
             config,
             health_updater: ReactiveHealthCheck::new("snapshot_recovery").1,
             connection_pool,
@@ -328,6 +336,11 @@ impl SnapshotsApplierTask {
         self.drop_storage_key_preimages = true;
     }
 
+    /// Requires headers of all snapshots used for recovery to be signed by the specified operator address.
+    pub fn set_header_signer(&mut self, signer: Address) {
+        self.header_signer = Some(signer);
+    }
+
     /// Returns the health check for snapshot recovery.
     pub fn health_check(&self) -> ReactiveHealthCheck {
         self.health_updater.subscribe()
@@ -397,13 +410,159 @@ impl SnapshotsApplierTask {
     }
 }
 
+/// Maximum number of attempts to fetch a storage logs chunk with a content hash not matching the snapshot header.
+const MAX_CHUNK_FETCH_ATTEMPTS: usize = 3;
+
+/// Delta snapshot in a [`SnapshotChain`].
+#[derive(Debug, Clone)]
+struct DeltaSnapshot {
+    l1_batch_number: L1BatchNumber,
+    /// Expected content hashes of storage log chunks ordered by chunk ID; `None` if not provided by the main node.
+    chunk_hashes: Vec<Option<H256>>,
+    /// Expected content hash of factory deps; `None` if not provided by the main node.
+    factory_deps_hash: Option<H256>,
+}
+
+/// Snapshot to recover from. Consists of a full (base) snapshot and zero or more delta snapshots on top of it.
+///
+/// Storage log chunks correspond to chunks of the base snapshot. Since chunks in both base and delta snapshots
+/// are split by uniform hashed key ranges, delta updates for a base chunk (including keys initially written
+/// after the base snapshot) are taken only from overlapping delta chunks and applied on the fly.
+#[derive(Debug, Clone)]
+struct SnapshotChain {
+    base_version: SnapshotVersion,
+    base_l1_batch_number: L1BatchNumber,
+    /// Expected content hashes of base storage log chunks ordered by chunk ID.
+    base_chunk_hashes: Vec<Option<H256>>,
+    /// Expected content hash of base factory deps.
+    base_factory_deps_hash: Option<H256>,
+    /// Ordered by increasing L1 batch number.
+    deltas: Vec<DeltaSnapshot>,
+}
+
+impl SnapshotChain {
+    /// Resolves the chain of snapshots ending with the snapshot described by `header`. If `header_signer` is specified,
+    /// checks that all snapshot headers in the chain are signed by it and contain content hashes for all snapshot data.
+    async fn resolve(
+        main_node_client: &dyn SnapshotsApplierMainNodeClient,
+        mut header: SnapshotHeader,
+        header_signer: Option<Address>,
+    ) -> Result<Self, SnapshotsApplierError> {
+        let mut deltas = vec![];
+        loop {
+            let version = SnapshotRecoveryStrategy::check_snapshot_version(header.version)?;
+            if let Some(signer) = header_signer {
+                header.verify_signature(signer)?;
+                Self::ensure_content_hashes(&header)?;
+            }
+            let chunk_hashes = Self::chunk_hashes(&header)?;
+            if !version.is_delta() {
+                if let Some(base) = header.base_l1_batch_number {
+                    let err = anyhow::anyhow!(
+                        "full snapshot for L1 batch #{} unexpectedly references base snapshot for L1 batch #{base}",
+                        header.l1_batch_number
+                    );
+                    return Err(err.into());
+                }
+                deltas.reverse();
+                return Ok(Self {
+                    base_version: version,
+                    base_l1_batch_number: header.l1_batch_number,
+                    base_chunk_hashes: chunk_hashes,
+                    base_factory_deps_hash: header.factory_deps_hash,
+                    deltas,
+                });
+            }
+
+            let l1_batch_number = header.l1_batch_number;
+            let base_l1_batch_number = header.base_l1_batch_number.with_context(|| {
+                format!(
+                    "delta snapshot for L1 batch #{l1_batch_number} doesn't specify base snapshot"
+                )
+            })?;
+            if base_l1_batch_number >= l1_batch_number {
+                let err = anyhow::anyhow!(
+                    "delta snapshot for L1 batch #{l1_batch_number} has invalid base L1 batch #{base_l1_batch_number}"
+                );
+                return Err(err.into());
+            }
+            deltas.push(DeltaSnapshot {
+                l1_batch_number,
+                chunk_hashes,
+                factory_deps_hash: header.factory_deps_hash,
+            });
+            header = main_node_client
+                .fetch_snapshot(base_l1_batch_number)
+                .await?
+                .with_context(|| {
+                    format!(
+                        "base snapshot for L1 batch #{base_l1_batch_number} is not present on main node"
+                    )
+                })?;
+        }
+    }
+
+    /// Returns expected content hashes of storage log chunks in the snapshot, ordered by chunk ID.
+    fn chunk_hashes(header: &SnapshotHeader) -> anyhow::Result<Vec<Option<H256>>> {
+        let mut chunk_hashes = Vec::with_capacity(header.storage_logs_chunks.len());
+        for (i, chunk) in header.storage_logs_chunks.iter().enumerate() {
+            anyhow::ensure!(
+                chunk.chunk_id == i as u64,
+                "storage log chunks for snapshot for L1 batch #{} are not ordered by ID: {:?}",
+                header.l1_batch_number,
+                header.storage_logs_chunks
+            );
+            chunk_hashes.push(chunk.content_hash);
+        }
+        Ok(chunk_hashes)
+    }
+
+    /// Checks that the header contains content hashes for all storage log chunks and factory deps. Without them,
+    /// the header signature wouldn't cover the snapshot data.
+    fn ensure_content_hashes(header: &SnapshotHeader) -> anyhow::Result<()> {
+        let l1_batch_number = header.l1_batch_number;
+        if let Some(chunk) = header
+            .storage_logs_chunks
+            .iter()
+            .find(|chunk| chunk.content_hash.is_none())
+        {
+            anyhow::bail!(
+                "storage logs chunk {} for signed snapshot for L1 batch #{l1_batch_number} has no content hash",
+                chunk.chunk_id
+            );
+        }
+        anyhow::ensure!(
+            header.factory_deps_hash.is_some(),
+            "factory deps for signed snapshot for L1 batch #{l1_batch_number} have no content hash"
+        );
+        Ok(())
+    }
+
+    /// Returns the total number of storage log chunks to process.
+    fn chunk_count(&self) -> usize {
+        self.base_chunk_hashes.len()
+    }
+
+    /// Returns L1 batch numbers of all snapshots in the chain together with the expected content hashes of their factory deps,
+    /// starting from the base snapshot.
+    fn factory_deps_hashes(&self) -> impl Iterator<Item = (L1BatchNumber, Option<H256>)> + '_ {
+        let delta_hashes = self
+            .deltas
+            .iter()
+            .map(|delta| (delta.l1_batch_number, delta.factory_deps_hash));
+        [(self.base_l1_batch_number, self.base_factory_deps_hash)]
+            .into_iter()
+            .chain(delta_hashes)
+    }
+}
+
 /// Strategy determining how snapshot recovery should proceed.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 enum SnapshotRecoveryStrategy {
     /// Snapshot recovery should proceed from scratch with the specified params.
-    New(SnapshotVersion),
+    New(SnapshotChain),
     /// Snapshot recovery should continue with the specified params.
-    Resumed(SnapshotVersion),
+    Resumed(SnapshotChain),
     /// Snapshot recovery has already been completed.
     Completed,
 }
@@ -413,6 +572,7 @@ impl SnapshotRecoveryStrategy {
         storage: &mut Connection<'_, Core>,
         main_node_client: &dyn SnapshotsApplierMainNodeClient,
         snapshot_l1_batch: Option<L1BatchNumber>,
+        header_signer: Option<Address>,
     ) -> Result<(Self, SnapshotRecoveryStatus), SnapshotsApplierError> {
         let latency =
             METRICS.initial_stage_duration[&InitialStage::FetchMetadataFromMainNode].start();
@@ -436,11 +596,21 @@ impl SnapshotRecoveryStrategy {
                 })?;
             // Old snapshots can theoretically be removed by the node, but in this case the snapshot data may be removed as well,
             // so returning an error looks appropriate here.
-            let snapshot_version = Self::check_snapshot_version(snapshot_header.version)?;
+            let chain =
+                SnapshotChain::resolve(main_node_client, snapshot_header, header_signer).await?;
+            let chunk_count = applied_snapshot_status.storage_logs_chunks_processed.len();
+            if chain.chunk_count() != chunk_count {
+                let err = anyhow::anyhow!(
+                    "snapshot recovery status has {chunk_count} storage log chunks, while the snapshot on main node \
+                     has {} chunks: {chain:?}",
+                    chain.chunk_count()
+                );
+                return Err(SnapshotsApplierError::Fatal(err));
+            }
 
             let latency = latency.observe();
             tracing::info!("Re-initialized snapshots applier after reset/failure in {latency:?}");
-            Ok((Self::Resumed(snapshot_version), applied_snapshot_status))
+            Ok((Self::Resumed(chain), applied_snapshot_status))
         } else {
             let is_genesis_needed = storage.blocks_dal().is_genesis_needed().await?;
             if !is_genesis_needed {
@@ -450,8 +620,12 @@ impl SnapshotRecoveryStrategy {
                 return Err(SnapshotsApplierError::Fatal(err));
             }
 
-            let (recovery_status, snapshot_version) =
-                Self::create_fresh_recovery_status(main_node_client, snapshot_l1_batch).await?;
+            let (recovery_status, chain) = Self::create_fresh_recovery_status(
+                main_node_client,
+                snapshot_l1_batch,
+                header_signer,
+            )
+            .await?;
 
             let storage_logs_count = storage
                 .storage_logs_dal()
@@ -468,14 +642,15 @@ impl SnapshotRecoveryStrategy {
 
             let latency = latency.observe();
             tracing::info!("Initialized fresh snapshots applier in {latency:?}");
-            Ok((Self::New(snapshot_version), recovery_status))
+            Ok((Self::New(chain), recovery_status))
         }
     }
 
     async fn create_fresh_recovery_status(
         main_node_client: &dyn SnapshotsApplierMainNodeClient,
         snapshot_l1_batch: Option<L1BatchNumber>,
-    ) -> Result<(SnapshotRecoveryStatus, SnapshotVersion), SnapshotsApplierError> {
+        header_signer: Option<Address>,
+    ) -> Result<(SnapshotRecoveryStatus, SnapshotChain), SnapshotsApplierError> {
         let l1_batch_number = match snapshot_l1_batch {
             Some(num) => num,
             None => main_node_client
@@ -495,7 +670,16 @@ impl SnapshotRecoveryStrategy {
             version = snapshot.version,
             chunk_count = snapshot.storage_logs_chunks.len()
         );
-        let snapshot_version = Self::check_snapshot_version(snapshot.version)?;
+        let chain = SnapshotChain::resolve(main_node_client, snapshot, header_signer).await?;
+        if !chain.deltas.is_empty() {
+            tracing::info!(
+                "Snapshot is a delta snapshot on top of full snapshot for L1 batch #{} (version {:?}), \
+                 with {} delta(s) in the chain",
+                chain.base_l1_batch_number,
+                chain.base_version,
+                chain.deltas.len()
+            );
+        }
 
         let l1_batch = main_node_client
             .fetch_l1_batch_details(l1_batch_number)
@@ -527,13 +711,13 @@ impl SnapshotRecoveryStrategy {
             l1_batch_number,
             l1_batch_timestamp: l1_batch.base.timestamp,
             l1_batch_root_hash,
-            l2_block_number: snapshot.l2_block_number,
+            l2_block_number,
             l2_block_timestamp: l2_block.base.timestamp,
             l2_block_hash,
             protocol_version,
-            storage_logs_chunks_processed: vec![false; snapshot.storage_logs_chunks.len()],
+            storage_logs_chunks_processed: vec![false; chain.chunk_count()],
         };
-        Ok((status, snapshot_version))
+        Ok((status, chain))
     }
 
     fn check_snapshot_version(raw_version: u16) -> anyhow::Result<SnapshotVersion> {
@@ -543,9 +727,16 @@ impl SnapshotRecoveryStrategy {
             )
         })?;
         anyhow::ensure!(
-            matches!(version, SnapshotVersion::Version0 | SnapshotVersion::Version1),
+            matches!(
+                version,
+                SnapshotVersion::Version0 | SnapshotVersion::Version1 | SnapshotVersion::Version2
+            ),
             "Cannot recover from a snapshot with version {version:?}; the only supported versions are {:?}",
-            [SnapshotVersion::Version0, SnapshotVersion::Version1]
+            [
+                SnapshotVersion::Version0,
+                SnapshotVersion::Version1,
+                SnapshotVersion::Version2
+            ]
         );
         Ok(version)
     }
@@ -559,19 +750,22 @@ enum StorageLogs {
 }
 
 impl StorageLogs {
+    /// Loads storage logs from the object store. Returns loaded logs together with their content hash.
     async fn load(
         blob_store: &dyn ObjectStore,
         key: SnapshotStorageLogsStorageKey,
         version: SnapshotVersion,
-    ) -> Result<Self, ObjectStoreError> {
+    ) -> Result<(Self, H256), ObjectStoreError> {
         match version {
             SnapshotVersion::Version0 => {
                 let logs: SnapshotStorageLogsChunk<StorageKey> = blob_store.get(key).await?;
-                Ok(Self::V0(logs.storage_logs))
+                let content_hash = logs.content_hash();
+                Ok((Self::V0(logs.storage_logs), content_hash))
             }
-            SnapshotVersion::Version1 => {
+            SnapshotVersion::Version1 | SnapshotVersion::Version2 => {
                 let logs: SnapshotStorageLogsChunk = blob_store.get(key).await?;
-                Ok(Self::V1(logs.storage_logs))
+                let content_hash = logs.content_hash();
+                Ok((Self::V1(logs.storage_logs), content_hash))
             }
         }
     }
@@ -608,6 +802,31 @@ impl StorageLogs {
         Ok(())
     }
 
+    /// Updates values of storage logs changed in delta snapshots. Returns the remaining delta logs,
+    /// i.e., ones for keys initially written after the base snapshot.
+    fn apply_delta_updates(
+        &mut self,
+        mut delta_logs: HashMap<H256, SnapshotStorageLog>,
+    ) -> Vec<SnapshotStorageLog> {
+        match self {
+            Self::V0(logs) => {
+                for log in logs {
+                    if let Some(updated_log) = delta_logs.remove(&log.key.hashed_key()) {
+                        log.value = updated_log.value;
+                    }
+                }
+            }
+            Self::V1(logs) => {
+                for log in logs {
+                    if let Some(updated_log) = delta_logs.remove(&log.key) {
+                        log.value = updated_log.value;
+                    }
+                }
+            }
+        }
+        delta_logs.into_values().collect()
+    }
+
     fn drop_key_preimages(&mut self) {
         match self {
             Self::V0(logs) => {
@@ -633,6 +852,19 @@ impl StorageLogs {
     }
 }
 
+/// Storage logs of a delta snapshot chunk partitioned by the IDs of base chunks overlapping with it.
+/// `None` until the chunk is fetched.
+type DeltaChunkPartitions = Option<HashMap<u64, Vec<SnapshotStorageLog>>>;
+
+/// Delta snapshot chunks shared among concurrently processed base chunks, so that each delta chunk is fetched once
+/// even if it overlaps multiple base chunks. Fetched logs are partitioned by base chunk; a partition is removed once
+/// taken by its base chunk, and a delta chunk is evicted once all its partitions are taken.
+#[derive(Debug, Default)]
+struct DeltaChunksCache {
+    /// Keyed by the L1 batch number of the delta snapshot and the delta chunk ID.
+    chunks: Mutex<HashMap<(L1BatchNumber, u64), Arc<tokio::sync::Mutex<DeltaChunkPartitions>>>>,
+}
+
 /// Applying application-level storage snapshots to the Postgres storage.
 #[derive(Debug)]
 struct SnapshotsApplier<'a> {
@@ -641,7 +873,8 @@ struct SnapshotsApplier<'a> {
     blob_store: &'a dyn ObjectStore,
     applied_snapshot_status: SnapshotRecoveryStatus,
     health_updater: &'a HealthUpdater,
-    snapshot_version: SnapshotVersion,
+    chain: SnapshotChain,
+    delta_chunks: DeltaChunksCache,
     max_concurrency: usize,
     drop_storage_key_preimages: bool,
     factory_deps_recovered: bool,
@@ -698,12 +931,13 @@ impl<'a> SnapshotsApplierOrStatus<'a> {
             &mut storage_transaction,
             main_node_client,
             task.snapshot_l1_batch,
+            task.header_signer,
         )
         .await?;
         tracing::info!("Chosen snapshot recovery strategy: {strategy:?} with status: {applied_snapshot_status:?}");
-        let (created_from_scratch, snapshot_version) = match strategy {
-            SnapshotRecoveryStrategy::New(version) => (true, version),
-            SnapshotRecoveryStrategy::Resumed(version) => (false, version),
+        let (created_from_scratch, chain) = match &strategy {
+            SnapshotRecoveryStrategy::New(chain) => (true, chain.clone()),
+            SnapshotRecoveryStrategy::Resumed(chain) => (false, chain.clone()),
             SnapshotRecoveryStrategy::Completed => {
                 return Ok(Self::CompletedStatus(applied_snapshot_status))
             }
@@ -715,7 +949,8 @@ impl<'a> SnapshotsApplierOrStatus<'a> {
             blob_store: task.blob_store.as_ref(),
             applied_snapshot_status,
             health_updater,
-            snapshot_version,
+            chain,
+            delta_chunks: DeltaChunksCache::default(),
             max_concurrency: task.config.max_concurrency.get(),
             drop_storage_key_preimages: task.drop_storage_key_preimages,
             factory_deps_recovered: !created_from_scratch,
@@ -799,8 +1034,27 @@ impl<'a> SnapshotsApplier<'a> {
     ) -> Result<(), SnapshotsApplierError> {
         let latency = METRICS.initial_stage_duration[&InitialStage::ApplyFactoryDeps].start();
 
-        tracing::debug!("Fetching factory dependencies from object store");
-        let l1_batch_number = self.applied_snapshot_status.l1_batch_number;
+        // For delta snapshots, factory deps are split among all snapshots in the chain.
+        for (l1_batch_number, expected_hash) in self.chain.factory_deps_hashes() {
+            self.recover_factory_deps_for_snapshot(l1_batch_number, expected_hash, storage)
+                .await?;
+        }
+
+        let latency = latency.observe();
+        tracing::info!("Applied factory dependencies in {latency:?}");
+
+        Ok(())
+    }
+
+    async fn recover_factory_deps_for_snapshot(
+        &self,
+        l1_batch_number: L1BatchNumber,
+        expected_hash: Option<H256>,
+        storage: &mut Connection<'_, Core>,
+    ) -> Result<(), SnapshotsApplierError> {
+        tracing::debug!(
+            "Fetching factory dependencies for L1 batch #{l1_batch_number} from object store"
+        );
         let factory_deps: SnapshotFactoryDependencies =
             self.blob_store.get(l1_batch_number).await.map_err(|err| {
                 let context = format!(
@@ -812,6 +1066,16 @@ impl<'a> SnapshotsApplier<'a> {
             "Fetched {} factory dependencies from object store",
             factory_deps.factory_deps.len()
         );
+        if let Some(expected_hash) = expected_hash {
+            let content_hash = factory_deps.content_hash();
+            if content_hash != expected_hash {
+                let err = anyhow::anyhow!(
+                    "factory deps for L1 batch #{l1_batch_number} have unexpected content hash {content_hash:?} \
+                     (expected: {expected_hash:?})"
+                );
+                return Err(err.into());
+            }
+        }
 
         // we cannot insert all factory deps because of field size limit triggered by UNNEST
         // in underlying query, see `https://www.postgresql.org/docs/current/limits.html`
@@ -861,11 +1125,180 @@ impl<'a> SnapshotsApplier<'a> {
                 )
                 .await?;
         }
+        Ok(())
+    }
 
-        let latency = latency.observe();
-        tracing::info!("Applied factory dependencies in {latency:?}");
+    /// Loads storage logs from delta snapshots in the chain for keys in the hashed key range of the base chunk `chunk_id`,
+    /// merged by hashed key. Only delta chunks overlapping with this range are used; each of them is fetched once
+    /// and shared with other base chunks via [`DeltaChunksCache`].
+    async fn load_delta_logs(
+        &self,
+        chunk_id: u64,
+    ) -> Result<HashMap<H256, SnapshotStorageLog>, SnapshotsApplierError> {
+        let mut delta_logs = HashMap::new();
+        if self.chain.deltas.is_empty() {
+            return Ok(delta_logs);
+        }
 
-        Ok(())
+        let key_range = uniform_hashed_keys_chunk(chunk_id, self.chain.chunk_count() as u64);
+        for delta in &self.chain.deltas {
+            let delta_chunk_count = delta.chunk_hashes.len() as u64;
+            for delta_chunk_id in 0..delta_chunk_count {
+                let delta_key_range = uniform_hashed_keys_chunk(delta_chunk_id, delta_chunk_count);
+                if delta_key_range.end() < key_range.start()
+                    || delta_key_range.start() > key_range.end()
+                {
+                    continue;
+                }
+
+                let storage_logs = self
+                    .take_delta_logs(delta, delta_chunk_id, chunk_id)
+                    .await?;
+                // Later deltas override earlier ones.
+                delta_logs.extend(storage_logs.into_iter().map(|log| (log.key, log)));
+            }
+        }
+        Ok(delta_logs)
+    }
+
+    /// Takes logs of the delta chunk `delta_chunk_id` for the base chunk `chunk_id`, fetching the delta chunk
+    /// if it isn't cached yet.
+    async fn take_delta_logs(
+        &self,
+        delta: &DeltaSnapshot,
+        delta_chunk_id: u64,
+        chunk_id: u64,
+    ) -> Result<Vec<SnapshotStorageLog>, SnapshotsApplierError> {
+        let cache_key = (delta.l1_batch_number, delta_chunk_id);
+        let cached_chunk = self
+            .delta_chunks
+            .chunks
+            .lock()
+            .unwrap()
+            .entry(cache_key)
+            .or_default()
+            .clone();
+        // Holding the lock while fetching ensures that concurrent base chunks don't fetch the same delta chunk.
+        let mut partitions = cached_chunk.lock().await;
+        let partitions = match &mut *partitions {
+            Some(partitions) => partitions,
+            None => {
+                let storage_key = SnapshotStorageLogsStorageKey {
+                    chunk_id: delta_chunk_id,
+                    l1_batch_number: delta.l1_batch_number,
+                };
+                let expected_hash = delta.chunk_hashes[delta_chunk_id as usize];
+                let storage_logs = self
+                    .fetch_storage_logs(storage_key, SnapshotVersion::Version2, expected_hash)
+                    .await?;
+                storage_logs.validate(&self.applied_snapshot_status)?;
+                let delta_chunk_count = delta.chunk_hashes.len() as u64;
+                partitions.insert(self.partition_delta_logs(
+                    storage_logs.without_preimages(),
+                    delta_chunk_id,
+                    delta_chunk_count,
+                ))
+            }
+        };
+
+        let storage_logs = partitions.remove(&chunk_id).unwrap_or_default();
+        if partitions.is_empty() {
+            self.delta_chunks.chunks.lock().unwrap().remove(&cache_key);
+        }
+        Ok(storage_logs)
+    }
+
+    /// Partitions logs of a delta chunk among base chunks that overlap with it and are not processed yet.
+    fn partition_delta_logs(
+        &self,
+        storage_logs: Vec<SnapshotStorageLog>,
+        delta_chunk_id: u64,
+        delta_chunk_count: u64,
+    ) -> HashMap<u64, Vec<SnapshotStorageLog>> {
+        let delta_key_range = uniform_hashed_keys_chunk(delta_chunk_id, delta_chunk_count);
+        let chunk_count = self.chain.chunk_count() as u64;
+        let processed_chunks = &self.applied_snapshot_status.storage_logs_chunks_processed;
+        let key_ranges: Vec<_> = (0..chunk_count)
+            .filter(|&chunk_id| !processed_chunks[chunk_id as usize])
+            .map(|chunk_id| (chunk_id, uniform_hashed_keys_chunk(chunk_id, chunk_count)))
+            .filter(|(_, key_range)| {
+                key_range.end() >= delta_key_range.start()
+                    && key_range.start() <= delta_key_range.end()
+            })
+            .collect();
+
+        let mut partitions = vec![vec![]; key_ranges.len()];
+        for log in storage_logs {
+            // Logs for processed base chunks are dropped.
+            let partition_idx = key_ranges
+                .iter()
+                .position(|(_, key_range)| key_range.contains(&log.key));
+            if let Some(partition_idx) = partition_idx {
+                partitions[partition_idx].push(log);
+            }
+        }
+        let chunk_ids = key_ranges.into_iter().map(|(chunk_id, _)| chunk_id);
+        chunk_ids.zip(partitions).collect()
+    }
+
+    /// Loads a storage logs chunk of the base snapshot with delta updates applied. Returns the updated chunk
+    /// together with the logs for keys in the chunk range that were initially written after the base snapshot.
+    async fn load_storage_logs_chunk(
+        &self,
+        chunk_id: u64,
+    ) -> Result<(StorageLogs, Vec<SnapshotStorageLog>), SnapshotsApplierError> {
+        let storage_key = SnapshotStorageLogsStorageKey {
+            chunk_id,
+            l1_batch_number: self.chain.base_l1_batch_number,
+        };
+        let expected_hash = self.chain.base_chunk_hashes[chunk_id as usize];
+        let mut storage_logs = self
+            .fetch_storage_logs(storage_key, self.chain.base_version, expected_hash)
+            .await?;
+        let delta_logs = self.load_delta_logs(chunk_id).await?;
+        let new_logs = if delta_logs.is_empty() {
+            vec![]
+        } else {
+            storage_logs.apply_delta_updates(delta_logs)
+        };
+        Ok((storage_logs, new_logs))
+    }
+
+    /// Fetches storage logs from the object store. If `expected_hash` is specified, verifies the content hash
+    /// of the fetched chunk, and re-fetches the chunk if it doesn't match.
+    async fn fetch_storage_logs(
+        &self,
+        storage_key: SnapshotStorageLogsStorageKey,
+        version: SnapshotVersion,
+        expected_hash: Option<H256>,
+    ) -> Result<StorageLogs, SnapshotsApplierError> {
+        for attempt in 1..=MAX_CHUNK_FETCH_ATTEMPTS {
+            let (storage_logs, actual_hash) =
+                StorageLogs::load(self.blob_store, storage_key, version)
+                    .await
+                    .map_err(|err| {
+                        let context =
+                            format!("cannot fetch storage logs {storage_key:?} from object store");
+                        SnapshotsApplierError::object_store(err, context)
+                    })?;
+            let Some(expected_hash) = expected_hash else {
+                return Ok(storage_logs);
+            };
+            if actual_hash == expected_hash {
+                return Ok(storage_logs);
+            }
+
+            METRICS.corrupted_storage_logs_chunks.inc();
+            tracing::warn!(
+                "Storage logs {storage_key:?} fetched from object store have unexpected content hash {actual_hash:?} \
+                 (expected: {expected_hash:?}); attempt {attempt} / {MAX_CHUNK_FETCH_ATTEMPTS}"
+            );
+        }
+
+        let err = anyhow::anyhow!(
+            "storage logs {storage_key:?} fetched from object store are corrupted after {MAX_CHUNK_FETCH_ATTEMPTS} attempts"
+        );
+        Err(SnapshotsApplierError::Retryable(err))
     }
 
     async fn insert_initial_writes_chunk(
@@ -922,27 +1355,16 @@ impl<'a> SnapshotsApplier<'a> {
         let latency =
             METRICS.storage_logs_chunks_duration[&StorageLogsChunksStage::LoadFromGcs].start();
 
-        let storage_key = SnapshotStorageLogsStorageKey {
-            chunk_id,
-            l1_batch_number: self.applied_snapshot_status.l1_batch_number,
-        };
-        let mut storage_logs =
-            StorageLogs::load(self.blob_store, storage_key, self.snapshot_version)
-                .await
-                .map_err(|err| {
-                    let context =
-                        format!("cannot fetch storage logs {storage_key:?} from object store");
-                    SnapshotsApplierError::object_store(err, context)
-                })?;
-
+        let (mut storage_logs, new_logs) = self.load_storage_logs_chunk(chunk_id).await?;
         storage_logs.validate(&self.applied_snapshot_status)?;
         if self.drop_storage_key_preimages {
             storage_logs.drop_key_preimages();
         }
         let latency = latency.observe();
         tracing::info!(
-            "Loaded {} storage logs from GCS for chunk {chunk_id} in {latency:?}",
-            storage_logs.len()
+            "Loaded {} storage logs (+{} logs for keys from delta snapshots) from GCS for chunk {chunk_id} in {latency:?}",
+            storage_logs.len(),
+            new_logs.len()
         );
 
         let latency =
@@ -958,7 +1380,13 @@ impl<'a> SnapshotsApplier<'a> {
 
         self.insert_storage_logs_chunk(&storage_logs, &mut storage_transaction)
             .await?;
-        let storage_logs = storage_logs.without_preimages();
+        let mut storage_logs = storage_logs.without_preimages();
+        if !new_logs.is_empty() {
+            let new_logs = StorageLogs::V1(new_logs);
+            self.insert_storage_logs_chunk(&new_logs, &mut storage_transaction)
+                .await?;
+            storage_logs.extend(new_logs.without_preimages());
+        }
         self.insert_initial_writes_chunk(&storage_logs, &mut storage_transaction)
             .await?;
 
@@ -2,7 +2,9 @@
 
 use std::time::Duration;
 
-use vise::{Buckets, EncodeLabelSet, EncodeLabelValue, Family, Gauge, Histogram, Metrics, Unit};
+use vise::{
+    Buckets, Counter, EncodeLabelSet, EncodeLabelValue, Family, Gauge, Histogram, Metrics, Unit,
+};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue, EncodeLabelSet)]
 #[metrics(label = "stage", rename_all = "snake_case")]
@@ -26,6 +28,8 @@ pub(crate) struct SnapshotsApplierMetrics {
 
     /// Number of chunks left to apply.
     pub storage_logs_chunks_left_to_process: Gauge<usize>,
+    /// Number of fetched storage log chunks with content hash not matching the snapshot header.
+    pub corrupted_storage_logs_chunks: Counter,
 
     /// Total latency of applying snapshot.
     #[metrics(buckets = Buckets::LATENCIES, unit = Unit::Seconds)]
@@ -9,16 +9,19 @@ use assert_matches::assert_matches;
 use test_casing::test_casing;
 use tokio::sync::Barrier;
 use zksync_health_check::CheckHealth;
-use zksync_object_store::MockObjectStore;
+use zksync_object_store::{MockObjectStore, StoredObject};
 use zksync_types::{
     api::{BlockDetails, L1BatchDetails},
     block::L1BatchHeader,
-    get_code_key, L1BatchNumber, ProtocolVersion, ProtocolVersionId,
+    get_code_key,
+    snapshots::SnapshotStorageLogsChunkMetadata,
+    K256PrivateKey, L1BatchNumber, ProtocolVersion, ProtocolVersionId,
 };
 
 use self::utils::{
-    mock_l2_block_header, mock_recovery_status, mock_snapshot_header, mock_tokens, prepare_clients,
-    random_storage_logs, MockMainNodeClient, ObjectStoreWithErrors,
+    chunk_by_hashed_keys, mock_l2_block_header, mock_recovery_status, mock_snapshot_header,
+    mock_tokens, prepare_clients, random_storage_logs, CorruptingObjectStore, MockMainNodeClient,
+    ObjectStoreWithErrors,
 };
 use super::*;
 use crate::tests::utils::{mock_factory_deps, HangingObjectStore};
@@ -251,6 +254,151 @@ async fn applier_recovers_explicitly_specified_snapshot(bytecode_kind: Option<By
     assert_eq!(all_storage_logs.len(), storage_logs.len());
 }
 
+#[tokio::test]
+async fn applier_recovers_delta_snapshot() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    // Chunks correspond to the base snapshot chunks; the delta snapshot is split into a different number of chunks.
+    let expected_status = SnapshotRecoveryStatus {
+        storage_logs_chunks_processed: vec![true; 2],
+        ..mock_recovery_status()
+    };
+    let base_status = SnapshotRecoveryStatus {
+        l1_batch_number: L1BatchNumber(100),
+        l2_block_number: L2BlockNumber(200),
+        storage_logs_chunks_processed: vec![true; 2],
+        ..mock_recovery_status()
+    };
+
+    let base_logs = random_storage_logs::<H256>(base_status.l1_batch_number, 100);
+    let updated_logs = base_logs.iter().step_by(3).map(|log| SnapshotStorageLog {
+        value: H256::random(),
+        ..log.clone()
+    });
+    let new_logs = random_storage_logs::<H256>(expected_status.l1_batch_number, 50)
+        .into_iter()
+        .map(|log| SnapshotStorageLog {
+            enumeration_index: log.enumeration_index + 100,
+            ..log
+        });
+    let delta_logs: Vec<_> = updated_logs.chain(new_logs).collect();
+    let delta_factory_deps = SnapshotFactoryDependencies {
+        factory_deps: vec![],
+    };
+    let (object_store, mut client) =
+        prepare_clients(&expected_status, &delta_factory_deps, &delta_logs).await;
+    let delta_header = client.fetch_newest_snapshot_response.as_mut().unwrap();
+    delta_header.version = SnapshotVersion::Version2.into();
+    delta_header.base_l1_batch_number = Some(base_status.l1_batch_number);
+    // Re-split delta logs into 3 chunks by hashed key ranges, as the snapshot creator does.
+    delta_header.storage_logs_chunks.clear();
+    for (chunk_id, chunk) in chunk_by_hashed_keys(&delta_logs, 3).into_iter().enumerate() {
+        let chunk_id = chunk_id as u64;
+        let key = SnapshotStorageLogsStorageKey {
+            l1_batch_number: expected_status.l1_batch_number,
+            chunk_id,
+        };
+        object_store.put(key, &chunk).await.unwrap();
+        delta_header
+            .storage_logs_chunks
+            .push(SnapshotStorageLogsChunkMetadata {
+                chunk_id,
+                filepath: format!("file{chunk_id}"),
+                content_hash: Some(chunk.content_hash()),
+            });
+    }
+
+    // Put the base snapshot into the same object store.
+    object_store
+        .put(base_status.l1_batch_number, &mock_factory_deps(None))
+        .await
+        .unwrap();
+    for (chunk_id, chunk) in chunk_by_hashed_keys(&base_logs, 2).into_iter().enumerate() {
+        let key = SnapshotStorageLogsStorageKey {
+            l1_batch_number: base_status.l1_batch_number,
+            chunk_id: chunk_id as u64,
+        };
+        object_store.put(key, &chunk).await.unwrap();
+    }
+    client.fetch_snapshot_responses.insert(
+        base_status.l1_batch_number,
+        mock_snapshot_header(SnapshotVersion::Version1.into(), &base_status),
+    );
+
+    // The middle delta chunk overlaps with both base chunks, but must be fetched only once.
+    let fetch_counts = Arc::new(Mutex::new(HashMap::<String, usize>::new()));
+    let object_store = ObjectStoreWithErrors::new(object_store, {
+        let fetch_counts = fetch_counts.clone();
+        move |key| {
+            *fetch_counts
+                .lock()
+                .unwrap()
+                .entry(key.to_owned())
+                .or_default() += 1;
+            Ok(())
+        }
+    });
+    let task = SnapshotsApplierTask::new(
+        SnapshotsApplierConfig::for_tests(),
+        pool.clone(),
+        Box::new(client),
+        Arc::new(object_store),
+    );
+    let (_stop_sender, stop_receiver) = watch::channel(false);
+    let stats = task.run(stop_receiver).await.unwrap();
+    assert!(stats.done_work);
+    let fetch_counts = fetch_counts.lock().unwrap();
+    assert!(
+        fetch_counts.values().all(|&count| count == 1),
+        "{fetch_counts:?}"
+    );
+
+    let mut storage = pool.connection().await.unwrap();
+    let current_db_status = storage
+        .snapshot_recovery_dal()
+        .get_applied_snapshot_status()
+        .await
+        .unwrap();
+    assert_eq!(current_db_status.unwrap(), expected_status);
+
+    let mut expected_logs: HashMap<_, _> =
+        base_logs.into_iter().map(|log| (log.key, log)).collect();
+    expected_logs.extend(delta_logs.into_iter().map(|log| (log.key, log)));
+    let all_storage_logs = storage
+        .storage_logs_dal()
+        .dump_all_storage_logs_for_tests()
+        .await;
+    assert_eq!(all_storage_logs.len(), expected_logs.len());
+    for db_log in all_storage_logs {
+        let expected_log = &expected_logs[&db_log.hashed_key];
+        assert_eq!(db_log.value, expected_log.value);
+        assert_eq!(db_log.l2_block_number, expected_status.l2_block_number);
+    }
+
+    let all_initial_writes = storage
+        .storage_logs_dedup_dal()
+        .dump_all_initial_writes_for_tests()
+        .await;
+    assert_eq!(all_initial_writes.len(), expected_logs.len());
+    for initial_write in all_initial_writes {
+        let log = &expected_logs[&initial_write.hashed_key];
+        assert_eq!(
+            initial_write.l1_batch_number,
+            log.l1_batch_number_of_initial_write
+        );
+        assert_eq!(initial_write.index, log.enumeration_index);
+    }
+
+    // Factory deps from the base snapshot must be recovered as well.
+    let bytecode_hash =
+        BytecodeHash::for_bytecode(&mock_factory_deps(None).factory_deps[0].bytecode.0);
+    let bytecode = storage
+        .factory_deps_dal()
+        .get_sealed_factory_dep(bytecode_hash.value())
+        .await
+        .unwrap();
+    assert!(bytecode.is_some());
+}
+
 #[tokio::test]
 async fn applier_errors_on_unexpected_bytecode_hash() {
     let pool = ConnectionPool::<Core>::test_pool().await;
@@ -647,6 +795,151 @@ async fn applier_returns_error_after_too_many_object_store_retries() {
     }));
 }
 
+#[tokio::test]
+async fn applier_refetches_corrupted_storage_logs_chunk() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let expected_status = mock_recovery_status();
+    let factory_deps = mock_factory_deps(None);
+    let storage_logs = random_storage_logs::<H256>(expected_status.l1_batch_number, 200);
+    let (object_store, client) =
+        prepare_clients(&expected_status, &factory_deps, &storage_logs).await;
+
+    let corrupted_key = SnapshotStorageLogsStorageKey {
+        l1_batch_number: expected_status.l1_batch_number,
+        chunk_id: 0,
+    };
+    let mut corrupted_chunk = SnapshotStorageLogsChunk {
+        storage_logs: storage_logs[..20].to_vec(),
+    };
+    corrupted_chunk.storage_logs[0].value = H256::repeat_byte(0xff);
+    let object_store = CorruptingObjectStore::new(
+        object_store,
+        SnapshotStorageLogsChunk::<H256>::encode_key(corrupted_key),
+        corrupted_chunk.serialize().unwrap(),
+        MAX_CHUNK_FETCH_ATTEMPTS - 1,
+    );
+
+    let task = SnapshotsApplierTask::new(
+        SnapshotsApplierConfig::for_tests(),
+        pool.clone(),
+        Box::new(client),
+        Arc::new(object_store),
+    );
+    let (_stop_sender, stop_receiver) = watch::channel(false);
+    let stats = task.run(stop_receiver).await.unwrap();
+    assert!(stats.done_work);
+
+    let mut storage = pool.connection().await.unwrap();
+    let all_storage_logs = storage
+        .storage_logs_dal()
+        .dump_all_storage_logs_for_tests()
+        .await;
+    assert_eq!(all_storage_logs.len(), storage_logs.len());
+    let expected_values: HashMap<_, _> = storage_logs
+        .iter()
+        .map(|log| (log.key, log.value))
+        .collect();
+    for db_log in all_storage_logs {
+        assert_eq!(db_log.value, expected_values[&db_log.hashed_key]);
+    }
+}
+
+#[tokio::test]
+async fn applier_verifies_snapshot_header_signature() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let expected_status = mock_recovery_status();
+    let factory_deps = mock_factory_deps(None);
+    let storage_logs = random_storage_logs::<H256>(expected_status.l1_batch_number, 100);
+    let (object_store, mut client) =
+        prepare_clients(&expected_status, &factory_deps, &storage_logs).await;
+    let signing_key = K256PrivateKey::random();
+    let header = client.fetch_newest_snapshot_response.as_mut().unwrap();
+    header.sign(&signing_key).unwrap();
+
+    let mut task = SnapshotsApplierTask::new(
+        SnapshotsApplierConfig::for_tests(),
+        pool.clone(),
+        Box::new(client.clone()),
+        object_store.clone(),
+    );
+    task.set_header_signer(Address::repeat_byte(1));
+    let (_stop_sender, stop_receiver) = watch::channel(false);
+    let OrStopped::Internal(err) = task.run(stop_receiver).await.unwrap_err() else {
+        panic!("Recovery unexpectedly stopped");
+    };
+    assert!(format!("{err:#}").contains("unexpected signer"), "{err:#}");
+
+    let mut task = SnapshotsApplierTask::new(
+        SnapshotsApplierConfig::for_tests(),
+        pool,
+        Box::new(client),
+        object_store,
+    );
+    task.set_header_signer(signing_key.address());
+    let (_stop_sender, stop_receiver) = watch::channel(false);
+    let stats = task.run(stop_receiver).await.unwrap();
+    assert!(stats.done_work);
+}
+
+#[test_casing(2, [false, true])]
+#[tokio::test]
+async fn applier_rejects_signed_header_without_content_hashes(drop_chunk_hash: bool) {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let expected_status = mock_recovery_status();
+    let factory_deps = mock_factory_deps(None);
+    let storage_logs = random_storage_logs::<H256>(expected_status.l1_batch_number, 100);
+    let (object_store, mut client) =
+        prepare_clients(&expected_status, &factory_deps, &storage_logs).await;
+    let signing_key = K256PrivateKey::random();
+    let header = client.fetch_newest_snapshot_response.as_mut().unwrap();
+    if drop_chunk_hash {
+        header.storage_logs_chunks[1].content_hash = None;
+    } else {
+        header.factory_deps_hash = None;
+    }
+    header.sign(&signing_key).unwrap();
+
+    let mut task = SnapshotsApplierTask::new(
+        SnapshotsApplierConfig::for_tests(),
+        pool,
+        Box::new(client),
+        object_store,
+    );
+    task.set_header_signer(signing_key.address());
+    let (_stop_sender, stop_receiver) = watch::channel(false);
+    let OrStopped::Internal(err) = task.run(stop_receiver).await.unwrap_err() else {
+        panic!("Recovery unexpectedly stopped");
+    };
+    assert!(format!("{err:#}").contains("no content hash"), "{err:#}");
+}
+
+#[tokio::test]
+async fn applier_rejects_factory_deps_with_unexpected_hash() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let expected_status = mock_recovery_status();
+    let factory_deps = mock_factory_deps(None);
+    let storage_logs = random_storage_logs::<H256>(expected_status.l1_batch_number, 100);
+    let (object_store, mut client) =
+        prepare_clients(&expected_status, &factory_deps, &storage_logs).await;
+    let header = client.fetch_newest_snapshot_response.as_mut().unwrap();
+    header.factory_deps_hash = Some(H256::repeat_byte(1));
+
+    let task = SnapshotsApplierTask::new(
+        SnapshotsApplierConfig::for_tests(),
+        pool,
+        Box::new(client),
+        object_store,
+    );
+    let (_stop_sender, stop_receiver) = watch::channel(false);
+    let OrStopped::Internal(err) = task.run(stop_receiver).await.unwrap_err() else {
+        panic!("Recovery unexpectedly stopped");
+    };
+    assert!(
+        format!("{err:#}").contains("unexpected content hash"),
+        "{err:#}"
+    );
+}
+
 #[tokio::test]
 async fn recovering_tokens() {
     let pool = ConnectionPool::<Core>::test_pool().await;
@@ -3,7 +3,10 @@
 use std::{
     collections::HashMap,
     fmt, future,
-    sync::{Arc, RwLock},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, RwLock,
+    },
 };
 
 use async_trait::async_trait;
@@ -14,9 +17,10 @@ use zksync_types::{
     block::L2BlockHeader,
     bytecode::{BytecodeHash, BytecodeMarker},
     snapshots::{
-        SnapshotFactoryDependencies, SnapshotFactoryDependency, SnapshotHeader,
-        SnapshotRecoveryStatus, SnapshotStorageLog, SnapshotStorageLogsChunk,
-        SnapshotStorageLogsChunkMetadata, SnapshotStorageLogsStorageKey, SnapshotVersion,
+        uniform_hashed_keys_chunk, SnapshotFactoryDependencies, SnapshotFactoryDependency,
+        SnapshotHeader, SnapshotRecoveryStatus, SnapshotStorageLog, SnapshotStorageLogKey,
+        SnapshotStorageLogsChunk, SnapshotStorageLogsChunkMetadata, SnapshotStorageLogsStorageKey,
+        SnapshotVersion,
     },
     tokens::{TokenInfo, TokenMetadata},
     web3::Bytes,
@@ -27,7 +31,7 @@ use zksync_web3_decl::error::{EnrichedClientError, EnrichedClientResult};
 
 use crate::SnapshotsApplierMainNodeClient;
 
-pub(super) trait SnapshotLogKey: Clone {
+pub(super) trait SnapshotLogKey: Clone + SnapshotStorageLogKey {
     const VERSION: SnapshotVersion;
 
     fn random() -> Self;
@@ -54,6 +58,8 @@ pub(super) struct MockMainNodeClient {
     pub fetch_l1_batch_responses: HashMap<L1BatchNumber, api::L1BatchDetails>,
     pub fetch_l2_block_responses: HashMap<L2BlockNumber, api::BlockDetails>,
     pub fetch_newest_snapshot_response: Option<SnapshotHeader>,
+    /// Older snapshots (e.g., base snapshots for delta snapshots).
+    pub fetch_snapshot_responses: HashMap<L1BatchNumber, SnapshotHeader>,
     pub tokens_response: Vec<TokenInfo>,
     pub tokens_response_error: Arc<RwLock<Option<EnrichedClientError>>>,
 }
@@ -99,10 +105,14 @@ impl SnapshotsApplierMainNodeClient for MockMainNodeClient {
         &self,
         l1_batch_number: L1BatchNumber,
     ) -> EnrichedClientResult<Option<SnapshotHeader>> {
-        Ok(self
+        let newest_snapshot = self
             .fetch_newest_snapshot_response
             .clone()
-            .filter(|response| response.l1_batch_number == l1_batch_number))
+            .filter(|response| response.l1_batch_number == l1_batch_number);
+        Ok(
+            newest_snapshot
+                .or_else(|| self.fetch_snapshot_responses.get(&l1_batch_number).cloned()),
+        )
     }
 
     async fn fetch_tokens(
@@ -263,6 +273,24 @@ pub(super) fn random_storage_logs<K: SnapshotLogKey>(
         .collect()
 }
 
+/// Splits storage logs into chunks by uniform hashed key ranges, the same way the snapshot creator does.
+pub(super) fn chunk_by_hashed_keys(
+    logs: &[SnapshotStorageLog],
+    chunk_count: u64,
+) -> Vec<SnapshotStorageLogsChunk> {
+    (0..chunk_count)
+        .map(|chunk_id| {
+            let key_range = uniform_hashed_keys_chunk(chunk_id, chunk_count);
+            let storage_logs = logs
+                .iter()
+                .filter(|log| key_range.contains(&log.key))
+                .cloned()
+                .collect();
+            SnapshotStorageLogsChunk { storage_logs }
+        })
+        .collect()
+}
+
 pub(super) fn mock_recovery_status() -> SnapshotRecoveryStatus {
     SnapshotRecoveryStatus {
         l1_batch_number: L1BatchNumber(123),
@@ -311,9 +339,13 @@ pub(super) fn mock_snapshot_header(
             .map(|chunk_id| SnapshotStorageLogsChunkMetadata {
                 chunk_id,
                 filepath: format!("file{chunk_id}"),
+                content_hash: None,
             })
             .collect(),
         factory_deps_filepath: "some_filepath".to_string(),
+        factory_deps_hash: None,
+        base_l1_batch_number: None,
+        signature: None,
     }
 }
 
@@ -338,6 +370,8 @@ where
         .div_ceil(status.storage_logs_chunks_processed.len());
     assert!(chunk_size > 0);
 
+    let mut header = mock_snapshot_header(K::VERSION.into(), status);
+    header.factory_deps_hash = Some(factory_deps.content_hash());
     for (chunk_id, chunk) in logs.chunks(chunk_size).enumerate() {
         let chunk_storage_logs = SnapshotStorageLogsChunk {
             storage_logs: chunk.to_vec(),
@@ -350,9 +384,10 @@ where
             .put(chunk_key, &chunk_storage_logs)
             .await
             .unwrap();
+        header.storage_logs_chunks[chunk_id].content_hash = Some(chunk_storage_logs.content_hash());
     }
 
-    client.fetch_newest_snapshot_response = Some(mock_snapshot_header(K::VERSION.into(), status));
+    client.fetch_newest_snapshot_response = Some(header);
     client.fetch_l1_batch_responses.insert(
         status.l1_batch_number,
         l1_batch_details(status.l1_batch_number, status.l1_batch_root_hash),
@@ -428,3 +463,63 @@ impl ObjectStore for HangingObjectStore {
         self.inner.storage_prefix_raw(bucket)
     }
 }
+
+/// Object store wrapper that returns corrupted data for the specified object a certain number of times.
+#[derive(Debug)]
+pub(super) struct CorruptingObjectStore {
+    inner: Arc<dyn ObjectStore>,
+    corrupted_key: String,
+    corrupted_value: Vec<u8>,
+    corrupted_fetches_left: AtomicUsize,
+}
+
+impl CorruptingObjectStore {
+    pub fn new(
+        inner: Arc<dyn ObjectStore>,
+        corrupted_key: String,
+        corrupted_value: Vec<u8>,
+        corrupted_fetch_count: usize,
+    ) -> Self {
+        Self {
+            inner,
+            corrupted_key,
+            corrupted_value,
+            corrupted_fetches_left: AtomicUsize::new(corrupted_fetch_count),
+        }
+    }
+}
+
+#[async_trait]
+impl ObjectStore for CorruptingObjectStore {
+    async fn get_raw(&self, bucket: Bucket, key: &str) -> Result<Vec<u8>, ObjectStoreError> {
+        if key == self.corrupted_key {
+            let should_corrupt = self
+                .corrupted_fetches_left
+                .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |count| {
+                    count.checked_sub(1)
+                })
+                .is_ok();
+            if should_corrupt {
+                return Ok(self.corrupted_value.clone());
+            }
+        }
+        self.inner.get_raw(bucket, key).await
+    }
+
+    async fn put_raw(
+        &self,
+        _bucket: Bucket,
+        _key: &str,
+        _value: Vec<u8>,
+    ) -> Result<(), ObjectStoreError> {
+        unreachable!("Should not be used in snapshot applier")
+    }
+
+    async fn remove_raw(&self, _bucket: Bucket, _key: &str) -> Result<(), ObjectStoreError> {
+        unreachable!("Should not be used in snapshot applier")
+    }
+
+    fn storage_prefix_raw(&self, bucket: Bucket) -> String {
+        self.inner.storage_prefix_raw(bucket)
+    }
+}
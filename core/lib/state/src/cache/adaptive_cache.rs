@@ -0,0 +1,676 @@
+use std::{
+    collections::hash_map::RandomState,
+    fmt,
+    hash::{BuildHasher, Hash},
+    mem,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+};
+
+use lru::LruCache;
+
+use crate::cache::{
+    frequency_sketch::FrequencySketch,
+    metrics::{
+        AdaptiveCacheConfig, CacheMetrics, HitRatioInterval, Method, RequestOutcome, METRICS,
+    },
+};
+
+/// Hits and misses recorded by a cache.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct CacheStats {
+    pub hits: u64,
+    pub misses: u64,
+}
+
+impl CacheStats {
+    pub fn requests(&self) -> u64 {
+        self.hits + self.misses
+    }
+
+    #[allow(clippy::cast_precision_loss)] // acceptable for metrics
+    pub fn hit_ratio(&self) -> Option<f64> {
+        let requests = self.requests();
+        (requests > 0).then(|| self.hits as f64 / requests as f64)
+    }
+}
+
+#[derive(Debug)]
+struct WeightedValue<V> {
+    value: V,
+    weight: u32,
+}
+
+/// Accumulator for entries removed from a shard.
+#[derive(Debug, Default)]
+struct Removed {
+    len: u64,
+    size: u64,
+    rejected: u64,
+}
+
+impl Removed {
+    fn push(&mut self, weight: u32) {
+        self.len += 1;
+        self.size += u64::from(weight);
+    }
+}
+
+/// Cache shard implementing W-TinyLFU: new entries are placed into a small LRU window; entries evicted from the window
+/// are admitted into the main LRU segment only if they are accessed more frequently than the main segment victim.
+#[derive(Debug)]
+struct Shard<K: Hash + Eq, V> {
+    window: LruCache<K, WeightedValue<V>>,
+    window_size: u64,
+    main: LruCache<K, WeightedValue<V>>,
+    main_size: u64,
+    sketch: FrequencySketch,
+}
+
+impl<K: Hash + Eq, V: Clone> Shard<K, V> {
+    /// Percentage of the shard capacity allocated to the window segment.
+    const WINDOW_PERCENTAGE: u64 = 1;
+
+    fn new() -> Self {
+        Self {
+            window: LruCache::unbounded(),
+            window_size: 0,
+            main: LruCache::unbounded(),
+            main_size: 0,
+            sketch: FrequencySketch::new(0),
+        }
+    }
+
+    fn get(&mut self, key: &K) -> Option<V> {
+        self.sketch.increment(key);
+        let entry = match self.window.get(key) {
+            Some(entry) => entry,
+            None => self.main.get(key)?,
+        };
+        Some(entry.value.clone())
+    }
+
+    fn insert(&mut self, key: K, value: V, weight: u32, capacity: u64) -> Removed {
+        let mut removed = self.remove(&key);
+        self.sketch.increment(&key);
+        let len = self.window.len() + self.main.len() + 1;
+        let keys = self
+            .window
+            .iter()
+            .chain(self.main.iter())
+            .map(|(key, _)| key);
+        self.sketch.ensure_capacity(len, keys);
+        self.window.put(key, WeightedValue { value, weight });
+        self.window_size += u64::from(weight);
+        self.evict(capacity, &mut removed);
+        removed
+    }
+
+    fn remove(&mut self, key: &K) -> Removed {
+        let mut removed = Removed::default();
+        if let Some(entry) = self.window.pop(key) {
+            self.window_size -= u64::from(entry.weight);
+            removed.push(entry.weight);
+        } else if let Some(entry) = self.main.pop(key) {
+            self.main_size -= u64::from(entry.weight);
+            removed.push(entry.weight);
+        }
+        removed
+    }
+
+    fn evict(&mut self, capacity: u64, removed: &mut Removed) {
+        let window_capacity = capacity * Self::WINDOW_PERCENTAGE / 100;
+        let main_capacity = capacity - window_capacity;
+
+        // The main segment may exceed its capacity if the cache was shrunk.
+        while self.main_size > main_capacity {
+            let Some((_, entry)) = self.main.pop_lru() else {
+                break;
+            };
+            self.main_size -= u64::from(entry.weight);
+            removed.push(entry.weight);
+        }
+
+        while self.window_size > window_capacity {
+            let Some((key, candidate)) = self.window.pop_lru() else {
+                break;
+            };
+            self.window_size -= u64::from(candidate.weight);
+            if self.admit(&key, candidate.weight, main_capacity, removed) {
+                self.main_size += u64::from(candidate.weight);
+                self.main.put(key, candidate);
+            } else {
+                removed.push(candidate.weight);
+                removed.rejected += 1;
+            }
+        }
+    }
+
+    /// Makes room in the main segment for the candidate evicted from the window, provided that the candidate
+    /// is accessed more frequently than all entries that would be evicted. If the candidate loses to any of them,
+    /// the main segment is left intact.
+    fn admit(&mut self, key: &K, weight: u32, main_capacity: u64, removed: &mut Removed) -> bool {
+        if u64::from(weight) > main_capacity {
+            return false;
+        }
+        let candidate_frequency = self.sketch.frequency(key);
+        let required_size = (self.main_size + u64::from(weight)).saturating_sub(main_capacity);
+
+        let mut victim_count = 0;
+        let mut freed_size = 0;
+        // Iterate from the least recently used entry.
+        for (victim_key, victim) in self.main.iter().rev() {
+            if freed_size >= required_size {
+                break;
+            }
+            if self.sketch.frequency(victim_key) >= candidate_frequency {
+                return false;
+            }
+            victim_count += 1;
+            freed_size += u64::from(victim.weight);
+        }
+        if freed_size < required_size {
+            return false;
+        }
+
+        for _ in 0..victim_count {
+            let (_, victim) = self.main.pop_lru().unwrap();
+            self.main_size -= u64::from(victim.weight);
+            removed.push(victim.weight);
+        }
+        true
+    }
+
+    fn clear(&mut self) -> Removed {
+        let removed = Removed {
+            len: (self.window.len() + self.main.len()) as u64,
+            size: self.window_size + self.main_size,
+            rejected: 0,
+        };
+        self.window.clear();
+        self.main.clear();
+        self.window_size = 0;
+        self.main_size = 0;
+        removed
+    }
+}
+
+#[derive(Debug, Default)]
+struct HitRatioState {
+    latest: Option<f64>,
+    awaiting_after_resize: bool,
+}
+
+struct Inner<K: Hash + Eq, V> {
+    name: &'static str,
+    metrics: &'static CacheMetrics,
+    hasher: RandomState,
+    weigher: Box<dyn Fn(&K, &V) -> u32 + Send + Sync>,
+    shards: Box<[Mutex<Shard<K, V>>]>,
+    capacity: AtomicU64,
+    len: AtomicU64,
+    used_memory: AtomicU64,
+    hits: AtomicU64,
+    misses: AtomicU64,
+    is_resized: AtomicBool,
+    hit_ratio: Mutex<HitRatioState>,
+}
+
+/// Cache with W-TinyLFU admission policy and a capacity that can be changed at runtime.
+///
+/// Compared to [`LruCache`](super::lru_cache::LruCache), this cache is more resistant to scans (e.g., reading many storage slots
+/// once during a VM execution), since rarely accessed entries cannot push out frequently accessed ones. The cache is
+/// split into shards (each having an equal share of the cache capacity) in order to reduce lock contention.
+pub struct AdaptiveCache<K: Hash + Eq, V> {
+    inner: Arc<Inner<K, V>>,
+}
+
+impl<K: Hash + Eq, V> fmt::Debug for AdaptiveCache<K, V> {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("AdaptiveCache")
+            .field("name", &self.inner.name)
+            .field("capacity", &self.inner.capacity)
+            .field("len", &self.inner.len)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<K: Hash + Eq, V> Clone for AdaptiveCache<K, V> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<K, V> AdaptiveCache<K, V>
+where
+    K: Copy + Eq + Hash + Send + Sync + 'static,
+    V: Copy + Send + Sync + 'static,
+{
+    /// Creates a new cache with all entries having the same weight determined as the layout size of key + value.
+    // We require `Copy` for key and value types to have a reasonable guarantee that they are stack-allocated, i.e., `mem::size_of()`
+    // describes the entire type size.
+    #[allow(clippy::cast_possible_truncation)] // not triggered in practice
+    pub fn uniform(name: &'static str, capacity: u64) -> Self {
+        Self::weighted(name, capacity, |_, _| {
+            const { (mem::size_of::<K>() + mem::size_of::<V>()) as u32 }
+        })
+    }
+}
+
+impl<K, V> AdaptiveCache<K, V>
+where
+    K: Eq + Hash + Send + Sync + 'static,
+    V: Clone + Send + Sync + 'static,
+{
+    /// Maximum number of shards in a cache.
+    const MAX_SHARDS: u64 = 16;
+    /// Minimum capacity of a single shard in bytes.
+    const MIN_SHARD_CAPACITY: u64 = 1 << 20;
+
+    /// Creates a new cache with a custom weighting function.
+    ///
+    /// The number of shards is determined based on the initial capacity, so a cache created with a small capacity
+    /// and resized to a larger one will experience more lock contention.
+    pub fn weighted<W>(name: &'static str, capacity: u64, weigher: W) -> Self
+    where
+        W: Fn(&K, &V) -> u32 + Send + Sync + 'static,
+    {
+        let shard_count = (capacity / Self::MIN_SHARD_CAPACITY).clamp(1, Self::MAX_SHARDS);
+        tracing::info!(
+            "Configured adaptive cache `{name}` with capacity {capacity}B and {shard_count} shard(s)"
+        );
+        let metrics = &METRICS[&name.into()];
+        let config = AdaptiveCacheConfig {
+            shards: shard_count,
+        };
+        if let Err(err) = metrics.adaptive_info.set(config) {
+            tracing::warn!(
+                "Adaptive cache `{name}` was already created with config {:?}; new config: {:?}",
+                metrics.adaptive_info.get(),
+                err.into_inner()
+            );
+        }
+        metrics.capacity.set(capacity);
+
+        let shards = (0..shard_count).map(|_| Mutex::new(Shard::new())).collect();
+        Self {
+            inner: Arc::new(Inner {
+                name,
+                metrics,
+                hasher: RandomState::new(),
+                weigher: Box::new(weigher),
+                shards,
+                capacity: AtomicU64::new(capacity),
+                len: AtomicU64::new(0),
+                used_memory: AtomicU64::new(0),
+                hits: AtomicU64::new(0),
+                misses: AtomicU64::new(0),
+                is_resized: AtomicBool::new(false),
+                hit_ratio: Mutex::default(),
+            }),
+        }
+    }
+
+    /// Returns the name of this cache.
+    pub fn name(&self) -> &'static str {
+        self.inner.name
+    }
+
+    /// Returns the capacity of this cache in bytes.
+    pub fn capacity(&self) -> u64 {
+        self.inner.capacity.load(Ordering::Relaxed)
+    }
+
+    /// Returns the approximate memory used by this cache in bytes.
+    pub fn used_memory(&self) -> u64 {
+        self.inner.used_memory.load(Ordering::Relaxed)
+    }
+
+    fn shard_capacity(&self) -> u64 {
+        self.capacity() / self.inner.shards.len() as u64
+    }
+
+    fn shard(&self, key: &K) -> &Mutex<Shard<K, V>> {
+        let shards = &self.inner.shards;
+        #[allow(clippy::cast_possible_truncation)] // intentional
+        let idx = self.inner.hasher.hash_one(key) as usize % shards.len();
+        &shards[idx]
+    }
+
+    fn lock_shard(shard: &Mutex<Shard<K, V>>) -> std::sync::MutexGuard<'_, Shard<K, V>> {
+        shard.lock().expect("adaptive cache shard is poisoned")
+    }
+
+    fn account_removed(&self, removed: &Removed) {
+        self.inner.len.fetch_sub(removed.len, Ordering::Relaxed);
+        self.inner
+            .used_memory
+            .fetch_sub(removed.size, Ordering::Relaxed);
+        if removed.rejected > 0 {
+            self.inner
+                .metrics
+                .rejected_admissions
+                .inc_by(removed.rejected);
+        }
+    }
+
+    /// Changes the capacity of this cache. If the capacity is decreased, excess entries are evicted immediately.
+    pub fn set_capacity(&self, capacity: u64) {
+        let prev_capacity = self.inner.capacity.swap(capacity, Ordering::Relaxed);
+        if prev_capacity == capacity {
+            return;
+        }
+        tracing::debug!(
+            "Resizing adaptive cache `{}` from {prev_capacity}B to {capacity}B",
+            self.inner.name
+        );
+        self.inner.metrics.capacity.set(capacity);
+        self.inner.is_resized.store(true, Ordering::Relaxed);
+
+        if capacity < prev_capacity {
+            let shard_capacity = self.shard_capacity();
+            for shard in &*self.inner.shards {
+                let mut removed = Removed::default();
+                Self::lock_shard(shard).evict(shard_capacity, &mut removed);
+                self.account_removed(&removed);
+            }
+            self.report_size();
+        }
+    }
+
+    /// Gets an entry and records an access to it.
+    pub fn get(&self, key: &K) -> Option<V> {
+        if self.capacity() == 0 {
+            // We intentionally don't report metrics if the cache is disabled.
+            return None;
+        }
+
+        let latency = self.inner.metrics.latency[&Method::Get].start();
+        let entry = Self::lock_shard(self.shard(key)).get(key);
+        latency.observe();
+
+        let request_outcome = if entry.is_some() {
+            self.inner.hits.fetch_add(1, Ordering::Relaxed);
+            RequestOutcome::Hit
+        } else {
+            self.inner.misses.fetch_add(1, Ordering::Relaxed);
+            RequestOutcome::Miss
+        };
+        self.inner.metrics.requests[&request_outcome].inc();
+        entry
+    }
+
+    /// Inserts an entry into the cache. Depending on the access frequency of the entry, it may be not retained
+    /// by the cache.
+    pub fn insert(&self, key: K, value: V) {
+        let capacity = self.shard_capacity();
+        if capacity == 0 {
+            return;
+        }
+
+        let latency = self.inner.metrics.latency[&Method::Insert].start();
+        let weight = (self.inner.weigher)(&key, &value);
+        let removed = Self::lock_shard(self.shard(&key)).insert(key, value, weight, capacity);
+        self.inner.len.fetch_add(1, Ordering::Relaxed);
+        self.inner
+            .used_memory
+            .fetch_add(weight.into(), Ordering::Relaxed);
+        self.account_removed(&removed);
+
+        latency.observe();
+        self.report_size();
+    }
+
+    pub(crate) fn report_size(&self) {
+        let metrics = self.inner.metrics;
+        metrics.len.set(self.inner.len.load(Ordering::Relaxed));
+        metrics.used_memory.set(self.used_memory());
+    }
+
+    /// Removes the specified key from this cache.
+    pub fn remove(&self, key: &K) {
+        let removed = Self::lock_shard(self.shard(key)).remove(key);
+        self.account_removed(&removed);
+    }
+
+    /// Removes all entries from this cache.
+    pub fn clear(&self) {
+        for shard in &*self.inner.shards {
+            let removed = Self::lock_shard(shard).clear();
+            self.account_removed(&removed);
+        }
+        self.report_size();
+    }
+
+    /// Returns hits and misses recorded since the previous call and reports the corresponding hit ratio.
+    pub(crate) fn take_stats(&self) -> CacheStats {
+        let stats = CacheStats {
+            hits: self.inner.hits.swap(0, Ordering::Relaxed),
+            misses: self.inner.misses.swap(0, Ordering::Relaxed),
+        };
+        let Some(hit_ratio) = stats.hit_ratio() else {
+            return stats;
+        };
+
+        let metrics = &self.inner.metrics.hit_ratio;
+        metrics[&HitRatioInterval::Latest].set(hit_ratio);
+        let mut state = self
+            .inner
+            .hit_ratio
+            .lock()
+            .expect("hit ratio state is poisoned");
+        if mem::take(&mut state.awaiting_after_resize) {
+            metrics[&HitRatioInterval::AfterResize].set(hit_ratio);
+        }
+        if self.inner.is_resized.swap(false, Ordering::Relaxed) {
+            // The stats were recorded before the resize (or, more precisely, mostly before it since resizing
+            // is performed by the same task that takes stats).
+            metrics[&HitRatioInterval::BeforeResize].set(state.latest.unwrap_or(hit_ratio));
+            state.awaiting_after_resize = true;
+        }
+        state.latest = Some(hit_ratio);
+        stats
+    }
+
+    /// Returns up to `limit` most frequently accessed keys in the cache, starting from the most accessed one.
+    ///
+    /// Each shard is locked only while its keys and their frequencies are copied out; ranking is performed
+    /// after the lock is released.
+    pub fn hot_keys(&self, limit: usize) -> Vec<K>
+    where
+        K: Clone,
+    {
+        let mut keys_with_frequencies = vec![];
+        for shard in &*self.inner.shards {
+            let mut shard_keys: Vec<_> = {
+                let shard = Self::lock_shard(shard);
+                shard
+                    .main
+                    .iter()
+                    .chain(shard.window.iter())
+                    .map(|(key, _)| (shard.sketch.frequency(key), key.clone()))
+                    .collect()
+            };
+            // Only `limit` keys from each shard can make it to the output, so we can discard the rest early.
+            if shard_keys.len() > limit {
+                shard_keys.select_nth_unstable_by_key(limit, |(frequency, _)| {
+                    std::cmp::Reverse(*frequency)
+                });
+                shard_keys.truncate(limit);
+            }
+            keys_with_frequencies.extend(shard_keys);
+        }
+        keys_with_frequencies.sort_unstable_by_key(|(frequency, _)| std::cmp::Reverse(*frequency));
+        keys_with_frequencies
+            .into_iter()
+            .take(limit)
+            .map(|(_, key)| key)
+            .collect()
+    }
+
+    #[cfg(test)]
+    pub(crate) fn estimated_len(&self) -> u64 {
+        self.inner.len.load(Ordering::Relaxed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_types::H256;
+
+    use super::*;
+
+    #[test]
+    fn basic_cache_operations() {
+        let cache = AdaptiveCache::<H256, u64>::uniform("test_adaptive", 1 << 16);
+        assert_eq!(cache.get(&H256::zero()), None);
+        cache.insert(H256::zero(), 1);
+        assert_eq!(cache.get(&H256::zero()), Some(1));
+        assert_eq!(cache.estimated_len(), 1);
+        assert_eq!(cache.used_memory(), 40);
+
+        cache.insert(H256::zero(), 2);
+        assert_eq!(cache.get(&H256::zero()), Some(2));
+        assert_eq!(cache.estimated_len(), 1);
+
+        cache.remove(&H256::zero());
+        assert_eq!(cache.get(&H256::zero()), None);
+        assert_eq!(cache.estimated_len(), 0);
+        assert_eq!(cache.used_memory(), 0);
+
+        let stats = cache.take_stats();
+        assert_eq!(stats, CacheStats { hits: 2, misses: 2 });
+        assert_eq!(cache.take_stats(), CacheStats::default());
+    }
+
+    #[test]
+    fn cache_with_zero_capacity() {
+        let cache = AdaptiveCache::<H256, Vec<u8>>::weighted("test_adaptive", 0, |_, _| 1);
+        cache.insert(H256::zero(), vec![1, 2, 3]);
+        assert_eq!(cache.get(&H256::zero()), None);
+        assert_eq!(cache.estimated_len(), 0);
+    }
+
+    #[test]
+    fn frequently_accessed_entries_survive_scans() {
+        const ENTRY_SIZE: u64 = 40; // `H256` key + `u64` value
+
+        let cache = AdaptiveCache::<H256, u64>::uniform("test_adaptive", 100 * ENTRY_SIZE);
+        let hot_keys: Vec<_> = (0..50).map(H256::from_low_u64_be).collect();
+        let access_hot_keys = || {
+            for (i, key) in hot_keys.iter().enumerate() {
+                if cache.get(key).is_none() {
+                    cache.insert(*key, i as u64);
+                }
+            }
+        };
+        for _ in 0..5 {
+            access_hot_keys();
+        }
+
+        // Scan through many keys that are accessed only once.
+        for chunk_start in (1_000..2_000).step_by(100) {
+            for i in chunk_start..chunk_start + 100 {
+                let key = H256::from_low_u64_be(i);
+                assert_eq!(cache.get(&key), None);
+                cache.insert(key, i);
+            }
+            assert!(cache.used_memory() <= cache.capacity());
+            access_hot_keys();
+        }
+
+        // Frequency estimates are probabilistic, so we allow for a small error.
+        let retained_hot_keys = hot_keys
+            .iter()
+            .filter(|&key| cache.get(key).is_some())
+            .count();
+        assert!(retained_hot_keys >= 45, "{retained_hot_keys}");
+
+        let top_keys = cache.hot_keys(hot_keys.len());
+        assert_eq!(top_keys.len(), hot_keys.len());
+        let hot_top_keys = top_keys
+            .iter()
+            .filter(|&key| hot_keys.contains(key))
+            .count();
+        assert!(hot_top_keys >= 45, "{hot_top_keys}");
+    }
+
+    #[test]
+    fn rejected_candidate_does_not_evict_main_entries() {
+        const ENTRY_SIZE: u32 = 40;
+
+        let mut shard = Shard::<H256, u64>::new();
+        shard.sketch = FrequencySketch::new(128);
+        let capacity = 100 * u64::from(ENTRY_SIZE);
+        let main_capacity = capacity - capacity * Shard::<H256, u64>::WINDOW_PERCENTAGE / 100;
+        // Fill the main segment with entries, the least recently used of which is accessed rarely,
+        // and the others are accessed frequently.
+        let main_keys: Vec<_> = (0..main_capacity / u64::from(ENTRY_SIZE))
+            .map(H256::from_low_u64_be)
+            .collect();
+        for (i, &key) in main_keys.iter().enumerate() {
+            shard.main.put(
+                key,
+                WeightedValue {
+                    value: i as u64,
+                    weight: ENTRY_SIZE,
+                },
+            );
+            shard.main_size += u64::from(ENTRY_SIZE);
+            let access_count = if i == 0 { 1 } else { 5 };
+            for _ in 0..access_count {
+                shard.sketch.increment(&key);
+            }
+        }
+
+        // The candidate is twice as large as a main entry, so admitting it requires evicting 2 entries.
+        // It's accessed more frequently than the first victim, but less frequently than the second one.
+        let candidate = H256::repeat_byte(0xff);
+        for _ in 0..3 {
+            shard.sketch.increment(&candidate);
+        }
+        let mut removed = Removed::default();
+        assert!(!shard.admit(&candidate, 2 * ENTRY_SIZE, main_capacity, &mut removed));
+        assert_eq!(removed.len, 0);
+        assert_eq!(shard.main.len(), main_keys.len());
+        assert!(shard.main.contains(&main_keys[0]));
+
+        // A candidate requiring to evict only the first entry is admitted.
+        assert!(shard.admit(&candidate, ENTRY_SIZE, main_capacity, &mut removed));
+        assert_eq!(removed.len, 1);
+        assert!(!shard.main.contains(&main_keys[0]));
+    }
+
+    #[test]
+    fn resizing_cache() {
+        const ENTRY_SIZE: u64 = 40;
+
+        let cache = AdaptiveCache::<H256, u64>::uniform("test_adaptive", 100 * ENTRY_SIZE);
+        for i in 0..100 {
+            cache.insert(H256::from_low_u64_be(i), i);
+        }
+        assert!(cache.estimated_len() > 90);
+
+        cache.set_capacity(10 * ENTRY_SIZE);
+        assert!(cache.estimated_len() <= 10);
+        assert!(cache.used_memory() <= 10 * ENTRY_SIZE);
+
+        cache.set_capacity(0);
+        assert_eq!(cache.estimated_len(), 0);
+        assert_eq!(cache.get(&H256::zero()), None);
+
+        cache.set_capacity(100 * ENTRY_SIZE);
+        for i in 0..50 {
+            cache.insert(H256::from_low_u64_be(i), i);
+        }
+        assert_eq!(cache.estimated_len(), 50);
+        cache.clear();
+        assert_eq!(cache.estimated_len(), 0);
+        assert_eq!(cache.used_memory(), 0);
+    }
+}
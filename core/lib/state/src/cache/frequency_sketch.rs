@@ -0,0 +1,165 @@
+//! Count-min sketch estimating access frequencies of cache keys.
+
+use std::{
+    array,
+    collections::hash_map::RandomState,
+    hash::{BuildHasher, Hash},
+};
+
+/// Approximate access frequency counter used for TinyLFU cache admission.
+///
+/// The sketch uses several rows of saturating counters; the frequency estimate for a key is the minimum
+/// of its counters. Counters are periodically halved (once the number of recorded accesses reaches 10x the number
+/// of tracked keys), so that the sketch tracks recent popularity rather than all-time popularity.
+#[derive(Debug)]
+pub(crate) struct FrequencySketch {
+    hasher: RandomState,
+    /// `DEPTH` rows of `width` counters each, stored contiguously.
+    counters: Vec<u8>,
+    /// Number of counters in each row; a power of two.
+    width: usize,
+    /// Number of keys the sketch is sized for.
+    expected_len: usize,
+    accesses: usize,
+}
+
+impl FrequencySketch {
+    const DEPTH: usize = 4;
+    const MIN_EXPECTED_LEN: usize = 16;
+    /// Number of counters per tracked key in each row. Greater values reduce the estimation error caused
+    /// by hash collisions.
+    const COUNTERS_PER_KEY: usize = 4;
+    pub(crate) const MAX_FREQUENCY: u8 = 15;
+    const SEEDS: [u64; Self::DEPTH] = [
+        0xc3a5_c85c_97cb_3127,
+        0xb492_b66f_be98_f273,
+        0x9ae1_6a3b_2f90_404f,
+        0xcbf2_9ce4_8422_2325,
+    ];
+
+    pub fn new(expected_len: usize) -> Self {
+        let expected_len = expected_len.max(Self::MIN_EXPECTED_LEN).next_power_of_two();
+        let width = expected_len * Self::COUNTERS_PER_KEY;
+        Self {
+            hasher: RandomState::new(),
+            counters: vec![0; width * Self::DEPTH],
+            width,
+            expected_len,
+            accesses: 0,
+        }
+    }
+
+    /// Grows the sketch if it's too small to track `len` keys. Since counters cannot be rehashed, only frequencies
+    /// of the provided `keys` (i.e., keys currently present in the cache) are carried over to the grown sketch.
+    pub fn ensure_capacity<'a, K: Hash + 'a>(
+        &mut self,
+        len: usize,
+        keys: impl Iterator<Item = &'a K>,
+    ) {
+        if len <= self.expected_len {
+            return;
+        }
+
+        let mut grown = Self::new(len);
+        grown.hasher = self.hasher.clone();
+        for key in keys {
+            let frequency = self.frequency(key);
+            for idx in grown.indices(key) {
+                grown.counters[idx] = grown.counters[idx].max(frequency);
+            }
+        }
+        *self = grown;
+    }
+
+    fn indices<K: Hash>(&self, key: &K) -> [usize; Self::DEPTH] {
+        let hash = self.hasher.hash_one(key);
+        array::from_fn(|row| {
+            let row_hash = (hash ^ Self::SEEDS[row]).wrapping_mul(0x9e37_79b9_7f4a_7c15);
+            #[allow(clippy::cast_possible_truncation)] // intentional
+            let column = (row_hash >> 32) as usize & (self.width - 1);
+            row * self.width + column
+        })
+    }
+
+    /// Records an access to the specified key.
+    pub fn increment<K: Hash>(&mut self, key: &K) {
+        for idx in self.indices(key) {
+            let counter = &mut self.counters[idx];
+            *counter = (*counter + 1).min(Self::MAX_FREQUENCY);
+        }
+
+        self.accesses += 1;
+        if self.accesses >= self.expected_len * 10 {
+            self.age();
+        }
+    }
+
+    /// Returns the estimated access frequency for the specified key.
+    pub fn frequency<K: Hash>(&self, key: &K) -> u8 {
+        self.indices(key)
+            .into_iter()
+            .map(|idx| self.counters[idx])
+            .min()
+            .unwrap_or(0)
+    }
+
+    fn age(&mut self) {
+        for counter in &mut self.counters {
+            *counter /= 2;
+        }
+        self.accesses /= 2;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn frequency_estimates() {
+        let mut sketch = FrequencySketch::new(128);
+        assert_eq!(sketch.frequency(&1_u64), 0);
+        for _ in 0..5 {
+            sketch.increment(&1_u64);
+        }
+        sketch.increment(&2_u64);
+
+        assert!(sketch.frequency(&1_u64) >= 5);
+        assert!(sketch.frequency(&2_u64) >= 1);
+        for _ in 0..100 {
+            sketch.increment(&1_u64);
+        }
+        assert_eq!(sketch.frequency(&1_u64), FrequencySketch::MAX_FREQUENCY);
+    }
+
+    #[test]
+    fn sketch_aging() {
+        let mut sketch = FrequencySketch::new(64);
+        for _ in 0..10 {
+            sketch.increment(&1_u64);
+        }
+        // Record accesses to other keys so that aging is triggered on the last access.
+        for i in 0..629_u64 {
+            sketch.increment(&(i + 1_000));
+        }
+        let frequency = sketch.frequency(&1_u64);
+        assert!(frequency >= 10);
+
+        sketch.increment(&2_000_u64);
+        assert_eq!(sketch.frequency(&1_u64), frequency / 2);
+    }
+
+    #[test]
+    fn growing_sketch() {
+        let mut sketch = FrequencySketch::new(16);
+        for _ in 0..5 {
+            sketch.increment(&1_u64);
+        }
+        sketch.increment(&2_u64);
+
+        sketch.ensure_capacity(100, [1_u64].iter());
+        assert_eq!(sketch.expected_len, 128);
+        assert!(sketch.frequency(&1_u64) >= 5);
+        assert_eq!(sketch.frequency(&2_u64), 0);
+    }
+}
@@ -31,6 +31,18 @@ impl RequestOutcome {
     }
 }
 
+/// Interval for which the hit ratio of an adaptive cache is reported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue)]
+#[metrics(rename_all = "snake_case")]
+pub(super) enum HitRatioInterval {
+    /// Latest interval between hit ratio measurements.
+    Latest,
+    /// Interval immediately preceding the latest cache resize.
+    BeforeResize,
+    /// Interval immediately following the latest cache resize.
+    AfterResize,
+}
+
 /// Buckets for small latencies: from 10 ns to 1 ms.
 const SMALL_LATENCIES: Buckets = Buckets::values(&[
     1e-8, 2.5e-8, 5e-8, 1e-7, 2.5e-7, 5e-7, 1e-6, 2.5e-6, 5e-6, 1e-5, 2.5e-5, 5e-5, 1e-4, 1e-3,
@@ -43,6 +55,12 @@ pub(super) struct LruCacheConfig {
     pub capacity: u64,
 }
 
+#[derive(Debug, EncodeLabelSet)]
+pub(super) struct AdaptiveCacheConfig {
+    /// Number of shards in the cache.
+    pub shards: u64,
+}
+
 #[derive(Debug, EncodeLabelSet)]
 pub(super) struct SequentialCacheConfig {
     /// Cache capacity in number of items.
@@ -67,6 +85,8 @@ pub(super) struct CacheMetrics {
     pub lru_info: Info<LruCacheConfig>,
     /// Configuration of sequential caches.
     pub sequential_info: Info<SequentialCacheConfig>,
+    /// Configuration of adaptive caches.
+    pub adaptive_info: Info<AdaptiveCacheConfig>,
 
     /// Latency of calling a cache method.
     #[metrics(buckets = SMALL_LATENCIES, labels = ["method"])]
@@ -78,6 +98,15 @@ pub(super) struct CacheMetrics {
     pub len: Gauge<u64>,
     /// Approximate memory usage of the cache.
     pub used_memory: Gauge<u64>,
+    /// Current capacity of an adaptive cache.
+    #[metrics(unit = Unit::Bytes)]
+    pub capacity: Gauge<u64>,
+    /// Number of entries not admitted into an adaptive cache because they were accessed less frequently
+    /// than the entries that would be evicted.
+    pub rejected_admissions: Counter,
+    /// Hit ratio of an adaptive cache over different time intervals.
+    #[metrics(labels = ["interval"])]
+    pub hit_ratio: LabeledFamily<HitRatioInterval, Gauge<f64>>,
 }
 
 #[vise::register]
@@ -1,5 +1,7 @@
 //! Generic cache abstraction used by storage implementations.
 
+pub mod adaptive_cache;
+mod frequency_sketch;
 pub mod lru_cache;
 mod metrics;
 pub mod sequential_cache;
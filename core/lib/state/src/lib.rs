@@ -14,7 +14,10 @@ pub use zksync_vm_interface::storage as interface;
 pub use self::{
     cache::{lru_cache::LruCache, sequential_cache::SequentialCache},
     catchup::{AsyncCatchupTask, RocksdbCell},
-    postgres::{PostgresStorage, PostgresStorageCaches, PostgresStorageCachesTask},
+    postgres::{
+        PostgresStorage, PostgresStorageCaches, PostgresStorageCachesBudgetTask,
+        PostgresStorageCachesTask, StorageCachesBudgetConfig,
+    },
     rocksdb::{
         RocksdbStorage, RocksdbStorageBuilder, RocksdbStorageOptions, StateKeeperColumnFamily,
     },
@@ -0,0 +1,750 @@
+//! Shared memory budget for [`PostgresStorageCaches`] and warming up caches on restart.
+
+use std::{
+    fmt,
+    fs::{self, File},
+    io,
+    ops::Range,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use anyhow::Context as _;
+use memmap2::Mmap;
+use tokio::sync::watch;
+use zksync_dal::{ConnectionPool, Core, CoreDal};
+use zksync_types::{L1BatchNumber, L2BlockNumber, H256};
+
+use super::{
+    metrics::{BudgetHitRatio, ResizeOutcome, WarmedUpCache, BUDGET_METRICS},
+    PostgresStorageCaches, TimestampedFactoryDep, ValuesCache,
+};
+use crate::cache::adaptive_cache::{AdaptiveCache, CacheStats};
+
+/// Configuration of the memory budget shared by [`PostgresStorageCaches`].
+#[derive(Debug, Clone)]
+pub struct StorageCachesBudgetConfig {
+    /// Total capacity of all caches in bytes.
+    pub memory_budget: u64,
+    /// Interval between redistributing the budget among caches.
+    pub resize_interval: Duration,
+    /// Path to the file with the most accessed cache keys. If set, the keys are persisted on each resize
+    /// and on shutdown, and are used to warm up caches on restart.
+    pub hot_set_path: Option<PathBuf>,
+    /// Maximum number of keys persisted for each cache.
+    pub hot_set_size: usize,
+}
+
+impl StorageCachesBudgetConfig {
+    /// Creates a config with the specified budget and default values for other params.
+    pub fn new(memory_budget: u64) -> Self {
+        Self {
+            memory_budget,
+            resize_interval: Duration::from_secs(60),
+            hot_set_path: None,
+            hot_set_size: 10_000,
+        }
+    }
+}
+
+/// Type-erased cache participating in the memory budget.
+trait BudgetedCache: fmt::Debug + Send + Sync {
+    fn name(&self) -> &'static str;
+    fn capacity(&self) -> u64;
+    fn set_capacity(&self, capacity: u64);
+    fn used_memory(&self) -> u64;
+    fn take_stats(&self) -> CacheStats;
+}
+
+impl<V: Clone + Send + Sync + 'static> BudgetedCache for AdaptiveCache<H256, V> {
+    fn name(&self) -> &'static str {
+        AdaptiveCache::name(self)
+    }
+
+    fn capacity(&self) -> u64 {
+        AdaptiveCache::capacity(self)
+    }
+
+    fn set_capacity(&self, capacity: u64) {
+        AdaptiveCache::set_capacity(self, capacity);
+    }
+
+    fn used_memory(&self) -> u64 {
+        AdaptiveCache::used_memory(self)
+    }
+
+    fn take_stats(&self) -> CacheStats {
+        AdaptiveCache::take_stats(self)
+    }
+}
+
+/// Budget redistribution performed by the task; used to revert redistributions that have decreased the hit ratio.
+#[derive(Debug, Clone, Copy)]
+struct Resize {
+    donor: usize,
+    receiver: usize,
+    amount: u64,
+    hit_ratio_before: f64,
+}
+
+/// Most accessed keys in [`PostgresStorageCaches`] persisted between restarts.
+#[derive(Debug, Default, PartialEq)]
+struct HotSet {
+    factory_deps: Vec<H256>,
+    /// Keys from both positive and negative initial writes caches.
+    initial_writes: Vec<H256>,
+    values: Vec<H256>,
+}
+
+impl HotSet {
+    const MAGIC: &'static [u8] = b"zkhs";
+    const VERSION: u8 = 1;
+
+    fn sections(&self) -> [&[H256]; 3] {
+        [&self.factory_deps, &self.initial_writes, &self.values]
+    }
+
+    /// Serializes this set as the magic bytes and version, followed by the sections for each cache. Each section is the number
+    /// of keys as a little-endian `u32`, followed by the keys themselves.
+    fn serialize(&self) -> Vec<u8> {
+        let key_count: usize = self.sections().iter().map(|keys| keys.len()).sum();
+        let mut buffer = Vec::with_capacity(Self::MAGIC.len() + 1 + 3 * 4 + key_count * 32);
+        buffer.extend_from_slice(Self::MAGIC);
+        buffer.push(Self::VERSION);
+        for keys in self.sections() {
+            let len = u32::try_from(keys.len()).expect("too many keys in hot set");
+            buffer.extend_from_slice(&len.to_le_bytes());
+            for key in keys {
+                buffer.extend_from_slice(key.as_bytes());
+            }
+        }
+        buffer
+    }
+
+    /// Validates the serialized set and returns byte ranges of the keys for each section.
+    fn parse_sections(bytes: &[u8]) -> anyhow::Result<[Range<usize>; 3]> {
+        let header = bytes
+            .get(..=Self::MAGIC.len())
+            .context("hot set is too short")?;
+        anyhow::ensure!(
+            header[..Self::MAGIC.len()] == *Self::MAGIC,
+            "invalid hot set magic bytes"
+        );
+        let version = header[Self::MAGIC.len()];
+        anyhow::ensure!(
+            version == Self::VERSION,
+            "unsupported hot set version: {version}"
+        );
+
+        let mut offset = header.len();
+        let mut read_section = || {
+            let len = bytes
+                .get(offset..offset + 4)
+                .context("hot set section length is missing")?;
+            let len = u32::from_le_bytes(len.try_into().unwrap()) as usize;
+            let section_len = len
+                .checked_mul(32)
+                .context("hot set section is too large")?;
+            let start = offset + 4;
+            anyhow::ensure!(
+                bytes.len() - start >= section_len,
+                "hot set section is truncated"
+            );
+            offset = start + section_len;
+            anyhow::Ok(start..offset)
+        };
+        let sections = [read_section()?, read_section()?, read_section()?];
+        anyhow::ensure!(offset == bytes.len(), "hot set has trailing bytes");
+        Ok(sections)
+    }
+
+    #[cfg(test)]
+    fn deserialize(bytes: &[u8]) -> anyhow::Result<Self> {
+        let [factory_deps, initial_writes, values] =
+            Self::parse_sections(bytes)?.map(|range| keys_in(&bytes[range]).collect::<Vec<_>>());
+        Ok(Self {
+            factory_deps,
+            initial_writes,
+            values,
+        })
+    }
+
+    /// Saves the set atomically, so that a crash during saving doesn't corrupt the previously saved set.
+    fn save(&self, path: &Path) -> anyhow::Result<()> {
+        let mut tmp_path = path.to_owned().into_os_string();
+        tmp_path.push(".tmp");
+        let tmp_path = PathBuf::from(tmp_path);
+        fs::write(&tmp_path, self.serialize())
+            .with_context(|| format!("failed writing `{}`", tmp_path.display()))?;
+        fs::rename(&tmp_path, path).with_context(|| {
+            format!(
+                "failed moving `{}` to `{}`",
+                tmp_path.display(),
+                path.display()
+            )
+        })
+    }
+}
+
+fn keys_in(section: &[u8]) -> impl Iterator<Item = H256> + '_ {
+    section.chunks_exact(32).map(H256::from_slice)
+}
+
+/// [`HotSet`] memory-mapped from a file. Keys are read directly from the mapping, so that warming up caches
+/// doesn't require reading the entire (potentially large) set into memory.
+#[derive(Debug)]
+struct MappedHotSet {
+    mmap: Mmap,
+    sections: [Range<usize>; 3],
+}
+
+impl MappedHotSet {
+    fn open(path: &Path) -> anyhow::Result<Option<Self>> {
+        let file = match File::open(path) {
+            Ok(file) => file,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => {
+                return Err(err).with_context(|| format!("failed opening `{}`", path.display()))
+            }
+        };
+        // SAFETY: the file is never modified in place; `HotSet::save()` writes a new file and renames it over
+        // the old one, which leaves the mapped file intact.
+        let mmap = unsafe { Mmap::map(&file) }
+            .with_context(|| format!("failed mapping `{}`", path.display()))?;
+        let sections = HotSet::parse_sections(&mmap)?;
+        Ok(Some(Self { mmap, sections }))
+    }
+
+    fn section(&self, idx: usize) -> &[u8] {
+        &self.mmap[self.sections[idx].clone()]
+    }
+
+    fn factory_deps(&self) -> &[u8] {
+        self.section(0)
+    }
+
+    fn initial_writes(&self) -> &[u8] {
+        self.section(1)
+    }
+
+    fn values(&self) -> &[u8] {
+        self.section(2)
+    }
+
+    #[cfg(test)]
+    fn to_hot_set(&self) -> HotSet {
+        HotSet {
+            factory_deps: keys_in(self.factory_deps()).collect(),
+            initial_writes: keys_in(self.initial_writes()).collect(),
+            values: keys_in(self.values()).collect(),
+        }
+    }
+}
+
+/// Task periodically redistributing the memory budget among [`PostgresStorageCaches`] based on their hit rates,
+/// and persisting / loading the most accessed keys.
+///
+/// Each [resize interval](StorageCachesBudgetConfig::resize_interval), a fixed share of the budget is moved
+/// from the cache with the fewest misses to the full cache with the most misses. If the overall hit ratio
+/// decreases after such a move, the move is reverted during the next interval.
+#[derive(Debug)]
+pub struct PostgresStorageCachesBudgetTask {
+    config: StorageCachesBudgetConfig,
+    connection_pool: ConnectionPool<Core>,
+    factory_deps: AdaptiveCache<H256, TimestampedFactoryDep>,
+    initial_writes: AdaptiveCache<H256, L1BatchNumber>,
+    negative_initial_writes: AdaptiveCache<H256, L1BatchNumber>,
+    values: Option<ValuesCache>,
+    /// Caches participating in the budget, including the ones above.
+    caches: Vec<Box<dyn BudgetedCache>>,
+    last_resize: Option<Resize>,
+    /// Donor and receiver of the last reverted resize. Used to prevent immediately repeating the same resize.
+    reverted_resize: Option<(usize, usize)>,
+}
+
+impl PostgresStorageCachesBudgetTask {
+    /// Percentage of the budget moved between caches at once.
+    const RESIZE_STEP_PERCENTAGE: u64 = 5;
+    /// Minimum percentage of the budget retained by each cache.
+    const MIN_SHARE_PERCENTAGE: u64 = 5;
+    /// Percentage of the capacity a cache should use in order to receive more memory.
+    const FULL_CACHE_PERCENTAGE: u64 = 90;
+    /// Number of keys loaded from Postgres at once during warm-up.
+    const WARM_UP_CHUNK_SIZE: usize = 1_000;
+    const VALUES_CACHE_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+    pub(super) fn new(
+        caches: &PostgresStorageCaches,
+        config: StorageCachesBudgetConfig,
+        connection_pool: ConnectionPool<Core>,
+    ) -> Self {
+        const NOT_ADAPTIVE: &str = "memory budget can only be configured for adaptive caches";
+
+        let factory_deps = caches.factory_deps.as_adaptive().expect(NOT_ADAPTIVE);
+        let initial_writes = caches.initial_writes.as_adaptive().expect(NOT_ADAPTIVE);
+        let negative_initial_writes = caches
+            .negative_initial_writes
+            .as_adaptive()
+            .expect(NOT_ADAPTIVE);
+        let values = caches.values.as_ref().map(|values| values.cache.clone());
+        let mut budgeted: Vec<Box<dyn BudgetedCache>> = vec![
+            Box::new(factory_deps.clone()),
+            Box::new(initial_writes.clone()),
+            Box::new(negative_initial_writes.clone()),
+        ];
+        if let Some(values) = &values {
+            budgeted.push(Box::new(values.adaptive_cache().expect(NOT_ADAPTIVE)));
+        }
+
+        // Distribute the budget proportionally to the initial capacities.
+        let total_capacity: u64 = budgeted.iter().map(|cache| cache.capacity()).sum();
+        let cache_count = budgeted.len() as u64;
+        for cache in &budgeted {
+            let capacity = if total_capacity == 0 {
+                config.memory_budget / cache_count
+            } else {
+                let capacity = u128::from(config.memory_budget) * u128::from(cache.capacity())
+                    / u128::from(total_capacity);
+                u64::try_from(capacity).expect("capacity cannot exceed budget")
+            };
+            let min_capacity = config.memory_budget * Self::MIN_SHARE_PERCENTAGE / 100;
+            cache.set_capacity(capacity.max(min_capacity));
+        }
+        tracing::info!(
+            "Distributed {}B memory budget among caches: {:?}",
+            config.memory_budget,
+            budgeted
+                .iter()
+                .map(|cache| (cache.name(), cache.capacity()))
+                .collect::<Vec<_>>()
+        );
+
+        Self {
+            config,
+            connection_pool,
+            factory_deps: factory_deps.clone(),
+            initial_writes: initial_writes.clone(),
+            negative_initial_writes: negative_initial_writes.clone(),
+            values,
+            caches: budgeted,
+            last_resize: None,
+            reverted_resize: None,
+        }
+    }
+
+    fn move_capacity(&self, from: usize, to: usize, amount: u64) {
+        let (donor, receiver) = (&self.caches[from], &self.caches[to]);
+        donor.set_capacity(donor.capacity() - amount);
+        receiver.set_capacity(receiver.capacity() + amount);
+        tracing::debug!(
+            "Moved {amount}B of cache budget from `{}` to `{}`",
+            donor.name(),
+            receiver.name()
+        );
+    }
+
+    /// Redistributes the budget based on the hits / misses recorded since the previous call.
+    fn rebalance(&mut self) {
+        let stats: Vec<_> = self.caches.iter().map(|cache| cache.take_stats()).collect();
+        let total_stats = stats
+            .iter()
+            .fold(CacheStats::default(), |acc, stats| CacheStats {
+                hits: acc.hits + stats.hits,
+                misses: acc.misses + stats.misses,
+            });
+        let Some(hit_ratio) = total_stats.hit_ratio() else {
+            return; // No requests to the caches, so there's nothing to base the decision on
+        };
+        BUDGET_METRICS.hit_ratio[&BudgetHitRatio::Latest].set(hit_ratio);
+
+        if let Some(resize) = self.last_resize.take() {
+            BUDGET_METRICS.hit_ratio[&BudgetHitRatio::AfterResize].set(hit_ratio);
+            if hit_ratio < resize.hit_ratio_before {
+                tracing::debug!(
+                    "Hit ratio decreased after resize ({} -> {hit_ratio}); reverting",
+                    resize.hit_ratio_before
+                );
+                self.move_capacity(resize.receiver, resize.donor, resize.amount);
+                self.reverted_resize = Some((resize.donor, resize.receiver));
+                BUDGET_METRICS.resizes[&ResizeOutcome::Reverted].inc();
+                return;
+            }
+        }
+        let reverted_resize = self.reverted_resize.take();
+
+        let budget = self.config.memory_budget;
+        let amount = budget * Self::RESIZE_STEP_PERCENTAGE / 100;
+        let min_capacity = budget * Self::MIN_SHARE_PERCENTAGE / 100;
+        let receiver = (0..self.caches.len())
+            .filter(|&i| {
+                let cache = &self.caches[i];
+                let is_full =
+                    cache.used_memory() * 100 >= cache.capacity() * Self::FULL_CACHE_PERCENTAGE;
+                is_full && stats[i].misses > 0
+            })
+            .max_by_key(|&i| stats[i].misses);
+        let Some(receiver) = receiver else {
+            return;
+        };
+        let donor = (0..self.caches.len())
+            .filter(|&i| i != receiver && reverted_resize != Some((i, receiver)))
+            .filter(|&i| self.caches[i].capacity() >= min_capacity + amount)
+            .min_by_key(|&i| stats[i].misses);
+        let Some(donor) = donor else {
+            return;
+        };
+        if amount == 0 || stats[donor].misses >= stats[receiver].misses {
+            return;
+        }
+
+        BUDGET_METRICS.hit_ratio[&BudgetHitRatio::BeforeResize].set(hit_ratio);
+        self.move_capacity(donor, receiver, amount);
+        self.last_resize = Some(Resize {
+            donor,
+            receiver,
+            amount,
+            hit_ratio_before: hit_ratio,
+        });
+        BUDGET_METRICS.resizes[&ResizeOutcome::Applied].inc();
+    }
+
+    fn hot_set(&self) -> HotSet {
+        let size = self.config.hot_set_size;
+        let mut initial_writes = self.initial_writes.hot_keys(size);
+        let negative_size = size.saturating_sub(initial_writes.len());
+        initial_writes.extend(self.negative_initial_writes.hot_keys(negative_size));
+        HotSet {
+            factory_deps: self.factory_deps.hot_keys(size),
+            initial_writes,
+            values: self
+                .values
+                .as_ref()
+                .and_then(ValuesCache::adaptive_cache)
+                .map(|values| values.hot_keys(size))
+                .unwrap_or_default(),
+        }
+    }
+
+    async fn save_hot_set(&self) -> anyhow::Result<()> {
+        let Some(path) = self.config.hot_set_path.clone() else {
+            return Ok(());
+        };
+        let hot_set = self.hot_set();
+        tokio::task::spawn_blocking(move || hot_set.save(&path))
+            .await
+            .context("panicked saving hot set")?
+    }
+
+    async fn load_hot_set(&self) -> anyhow::Result<Option<MappedHotSet>> {
+        let Some(path) = self.config.hot_set_path.clone() else {
+            return Ok(None);
+        };
+        tokio::task::spawn_blocking(move || MappedHotSet::open(&path))
+            .await
+            .context("panicked loading hot set")?
+    }
+
+    async fn warm_up(
+        &self,
+        hot_set: MappedHotSet,
+        stop_receiver: &mut watch::Receiver<bool>,
+    ) -> anyhow::Result<()> {
+        let latency = BUDGET_METRICS.warm_up_latency.start();
+        let mut connection = self
+            .connection_pool
+            .connection_tagged("storage_caches_warm_up")
+            .await?;
+
+        for hash in keys_in(hot_set.factory_deps()) {
+            let dep = connection
+                .storage_web3_dal()
+                .get_factory_dep(hash)
+                .await
+                .with_context(|| format!("failed getting factory dep {hash:?}"))?;
+            if let Some((bytecode, inserted_at)) = dep {
+                let dep = TimestampedFactoryDep {
+                    bytecode,
+                    inserted_at,
+                };
+                self.factory_deps.insert(hash, dep);
+                BUDGET_METRICS.warmed_up_entries[&WarmedUpCache::FactoryDeps].inc();
+            }
+        }
+
+        let sealed_l1_batch = connection.blocks_dal().get_sealed_l1_batch_number().await?;
+        // Keys missing from `initial_writes` are certainly not written before the pending L1 batch.
+        let pending_l1_batch = sealed_l1_batch.map_or(L1BatchNumber(0), |number| number + 1);
+        for chunk in hot_set
+            .initial_writes()
+            .chunks(Self::WARM_UP_CHUNK_SIZE * 32)
+        {
+            let chunk: Vec<_> = keys_in(chunk).collect();
+            let initial_writes = connection
+                .storage_logs_dal()
+                .get_l1_batches_and_indices_for_initial_writes(&chunk)
+                .await?;
+            for key in &chunk {
+                if let Some(&(l1_batch_number, _)) = initial_writes.get(key) {
+                    self.initial_writes.insert(*key, l1_batch_number);
+                } else {
+                    self.negative_initial_writes.insert(*key, pending_l1_batch);
+                }
+            }
+            BUDGET_METRICS.warmed_up_entries[&WarmedUpCache::InitialWrites]
+                .inc_by(chunk.len() as u64);
+        }
+        drop(connection);
+
+        if let Some(values) = &self.values {
+            if !hot_set.values().is_empty() {
+                self.warm_up_values(values, hot_set.values(), stop_receiver)
+                    .await?;
+            }
+        }
+
+        let latency = latency.observe();
+        tracing::info!(
+            "Warmed up storage caches with {} factory deps, {} initial writes and {} values in {latency:?}",
+            hot_set.factory_deps().len() / 32,
+            hot_set.initial_writes().len() / 32,
+            hot_set.values().len() / 32
+        );
+        Ok(())
+    }
+
+    /// Values can only be loaded after the values cache is initialized by `PostgresStorageCachesTask`, since values
+    /// are only cached for the latest L2 block.
+    async fn warm_up_values(
+        &self,
+        values: &ValuesCache,
+        keys: &[u8],
+        stop_receiver: &mut watch::Receiver<bool>,
+    ) -> anyhow::Result<()> {
+        while values.valid_for() == L2BlockNumber(0) {
+            if tokio::time::timeout(Self::VALUES_CACHE_POLL_INTERVAL, stop_receiver.changed())
+                .await
+                .is_ok()
+            {
+                return Ok(());
+            }
+        }
+
+        let mut connection = self
+            .connection_pool
+            .connection_tagged("storage_caches_warm_up")
+            .await?;
+        for chunk in keys.chunks(Self::WARM_UP_CHUNK_SIZE * 32) {
+            let chunk: Vec<_> = keys_in(chunk).collect();
+            let l2_block_number = values.valid_for();
+            let loaded_values = connection
+                .storage_logs_dal()
+                .get_storage_values(&chunk, l2_block_number)
+                .await?;
+            for (key, value) in loaded_values {
+                // If the cache has moved on since `l2_block_number` was read, values won't be inserted.
+                values.insert(l2_block_number, key, value.unwrap_or_default());
+            }
+            BUDGET_METRICS.warmed_up_entries[&WarmedUpCache::Values].inc_by(chunk.len() as u64);
+        }
+        Ok(())
+    }
+
+    /// Runs the task.
+    ///
+    /// # Errors
+    ///
+    /// Propagates Postgres and I/O errors. Errors loading the persisted hot set are logged, but are not propagated.
+    #[tracing::instrument(name = "PostgresStorageCachesBudgetTask::run", skip_all)]
+    pub async fn run(mut self, mut stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
+        tracing::info!(
+            memory_budget = self.config.memory_budget,
+            resize_interval = ?self.config.resize_interval,
+            hot_set_path = ?self.config.hot_set_path,
+            "Starting task"
+        );
+
+        match self.load_hot_set().await {
+            Ok(Some(hot_set)) => self.warm_up(hot_set, &mut stop_receiver).await?,
+            Ok(None) => { /* no hot set to warm up from */ }
+            Err(err) => {
+                tracing::warn!(
+                    "Failed loading cache hot set, caches will not be warmed up: {err:#}"
+                );
+            }
+        }
+
+        let mut interval = tokio::time::interval(self.config.resize_interval);
+        interval.tick().await; // The first tick is immediate
+        while !*stop_receiver.borrow() {
+            tokio::select! {
+                _ = stop_receiver.changed() => break,
+                _ = interval.tick() => { /* continue processing */ }
+            }
+            self.rebalance();
+            self.save_hot_set().await?;
+        }
+
+        tracing::info!("Stop request received, saving cache hot set");
+        self.save_hot_set().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_dal::ConnectionPool;
+
+    use super::*;
+    use crate::test_utils::prepare_postgres_with_log_count;
+
+    #[test]
+    fn hot_set_serialization() {
+        let hot_set = HotSet {
+            factory_deps: vec![H256::repeat_byte(1)],
+            initial_writes: vec![],
+            values: (0..10).map(H256::from_low_u64_be).collect(),
+        };
+        let bytes = hot_set.serialize();
+        assert_eq!(bytes.len(), 5 + 3 * 4 + 11 * 32);
+        assert_eq!(HotSet::deserialize(&bytes).unwrap(), hot_set);
+
+        HotSet::deserialize(&bytes[..bytes.len() - 1]).unwrap_err();
+        let mut invalid_bytes = bytes.clone();
+        invalid_bytes[4] = 2; // version
+        HotSet::deserialize(&invalid_bytes).unwrap_err();
+        let mut invalid_bytes = bytes;
+        invalid_bytes.push(0);
+        HotSet::deserialize(&invalid_bytes).unwrap_err();
+    }
+
+    #[test]
+    fn saving_and_loading_hot_set() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("hot_set");
+        assert!(MappedHotSet::open(&path).unwrap().is_none());
+
+        let hot_set = HotSet {
+            factory_deps: vec![H256::repeat_byte(1)],
+            initial_writes: vec![H256::repeat_byte(2), H256::repeat_byte(3)],
+            values: vec![H256::repeat_byte(4)],
+        };
+        hot_set.save(&path).unwrap();
+        let mapped = MappedHotSet::open(&path).unwrap().unwrap();
+        assert_eq!(mapped.to_hot_set(), hot_set);
+
+        // Saving a new set must not affect the existing mapping.
+        HotSet::default().save(&path).unwrap();
+        assert_eq!(mapped.to_hot_set(), hot_set);
+        let mapped = MappedHotSet::open(&path).unwrap().unwrap();
+        assert_eq!(mapped.to_hot_set(), HotSet::default());
+    }
+
+    #[tokio::test]
+    async fn budget_is_redistributed_based_on_misses() {
+        const BUDGET: u64 = 4 << 20;
+
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let caches = PostgresStorageCaches::adaptive(1 << 20, 1 << 20);
+        let mut task = caches.configure_memory_budget(StorageCachesBudgetConfig::new(BUDGET), pool);
+        assert_eq!(caches.factory_deps.capacity(), BUDGET / 2);
+        assert_eq!(caches.initial_writes.capacity(), BUDGET / 4);
+        assert_eq!(caches.negative_initial_writes.capacity(), BUDGET / 4);
+
+        // Fill the initial writes cache and record some hits and a lot of misses for it.
+        let keys: Vec<_> = (0..40_000).map(H256::from_low_u64_be).collect();
+        for &key in &keys {
+            caches.initial_writes.insert(key, L1BatchNumber(1));
+        }
+        let initial_writes = caches.initial_writes.as_adaptive().unwrap();
+        assert!(initial_writes.used_memory() * 10 >= initial_writes.capacity() * 9);
+        for key in initial_writes.hot_keys(100) {
+            assert_eq!(initial_writes.get(&key), Some(L1BatchNumber(1)));
+        }
+        for i in 0..1_000 {
+            let missing_key = H256::from_low_u64_be(1_000_000 + i);
+            assert_eq!(initial_writes.get(&missing_key), None);
+        }
+
+        task.rebalance();
+        let step = BUDGET * PostgresStorageCachesBudgetTask::RESIZE_STEP_PERCENTAGE / 100;
+        assert_eq!(caches.factory_deps.capacity(), BUDGET / 2 - step);
+        assert_eq!(caches.initial_writes.capacity(), BUDGET / 4 + step);
+        assert_eq!(caches.negative_initial_writes.capacity(), BUDGET / 4);
+
+        // Record only misses, so that the hit ratio decreases; the resize should be reverted.
+        for i in 1_000..2_000 {
+            let missing_key = H256::from_low_u64_be(1_000_000 + i);
+            assert_eq!(initial_writes.get(&missing_key), None);
+        }
+        task.rebalance();
+        assert_eq!(caches.factory_deps.capacity(), BUDGET / 2);
+        assert_eq!(caches.initial_writes.capacity(), BUDGET / 4);
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "adaptive caches")]
+    async fn memory_budget_requires_adaptive_caches() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let caches = PostgresStorageCaches::new(1 << 20, 1 << 20);
+        caches.configure_memory_budget(StorageCachesBudgetConfig::new(4 << 20), pool);
+    }
+
+    #[tokio::test]
+    async fn warming_up_caches_from_hot_set() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut connection = pool.connection().await.unwrap();
+        let genesis_logs = prepare_postgres_with_log_count(&mut connection, 20).await;
+        drop(connection);
+        let non_existing_key = H256::repeat_byte(0xff);
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let hot_set_path = dir.path().join("hot_set");
+        let config = StorageCachesBudgetConfig {
+            hot_set_path: Some(hot_set_path.clone()),
+            ..StorageCachesBudgetConfig::new(4 << 20)
+        };
+
+        let caches = PostgresStorageCaches::adaptive(1 << 20, 1 << 20);
+        let task = caches.configure_memory_budget(config.clone(), pool.clone());
+        for log in &genesis_logs {
+            caches
+                .initial_writes
+                .insert(log.key.hashed_key(), L1BatchNumber(0));
+        }
+        caches
+            .negative_initial_writes
+            .insert(non_existing_key, L1BatchNumber(1));
+        let (stop_sender, stop_receiver) = watch::channel(true);
+        task.run(stop_receiver).await.unwrap();
+        drop(stop_sender);
+        assert!(hot_set_path.exists());
+
+        // Emulate a restart.
+        let caches = PostgresStorageCaches::adaptive(1 << 20, 1 << 20);
+        let task = caches.configure_memory_budget(config, pool);
+        let (stop_sender, stop_receiver) = watch::channel(false);
+        let task_handle = tokio::spawn(task.run(stop_receiver));
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while caches.negative_initial_writes.estimated_len() == 0 {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("timed out waiting for warm-up");
+
+        for log in &genesis_logs {
+            let hashed_key = log.key.hashed_key();
+            assert_eq!(
+                caches.initial_writes.get(&hashed_key),
+                Some(L1BatchNumber(0))
+            );
+        }
+        // The sealed L1 batch is #0, so the key is certainly not written before L1 batch #1.
+        assert_eq!(
+            caches.negative_initial_writes.get(&non_existing_key),
+            Some(L1BatchNumber(1))
+        );
+
+        stop_sender.send_replace(true);
+        task_handle.await.unwrap().unwrap();
+    }
+}
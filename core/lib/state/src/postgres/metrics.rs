@@ -60,3 +60,44 @@ pub(super) struct PostgresStorageMetrics {
 
 #[vise::register]
 pub(super) static STORAGE_METRICS: vise::Global<PostgresStorageMetrics> = vise::Global::new();
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelSet, EncodeLabelValue)]
+#[metrics(label = "outcome", rename_all = "snake_case")]
+pub(super) enum ResizeOutcome {
+    Applied,
+    Reverted,
+}
+
+/// Interval for which the overall hit ratio of budgeted caches is reported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelSet, EncodeLabelValue)]
+#[metrics(label = "interval", rename_all = "snake_case")]
+pub(super) enum BudgetHitRatio {
+    Latest,
+    BeforeResize,
+    AfterResize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelSet, EncodeLabelValue)]
+#[metrics(label = "cache", rename_all = "snake_case")]
+pub(super) enum WarmedUpCache {
+    FactoryDeps,
+    InitialWrites,
+    Values,
+}
+
+#[derive(Debug, Metrics)]
+#[metrics(prefix = "server_state_cache_budget")]
+pub(super) struct CachesBudgetMetrics {
+    /// Number of times the memory budget was redistributed among caches.
+    pub resizes: Family<ResizeOutcome, Counter>,
+    /// Overall hit ratio of the budgeted caches over different time intervals.
+    pub hit_ratio: Family<BudgetHitRatio, Gauge<f64>>,
+    /// Latency of warming up caches on startup.
+    #[metrics(buckets = Buckets::LATENCIES)]
+    pub warm_up_latency: Histogram<Duration>,
+    /// Number of entries loaded into caches during warm-up.
+    pub warmed_up_entries: Family<WarmedUpCache, Counter>,
+}
+
+#[vise::register]
+pub(super) static BUDGET_METRICS: vise::Global<CachesBudgetMetrics> = vise::Global::new();
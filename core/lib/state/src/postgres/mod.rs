@@ -11,9 +11,11 @@ use zksync_dal::{Connection, ConnectionPool, Core, CoreDal};
 use zksync_types::{L1BatchNumber, L2BlockNumber, StorageKey, StorageValue, H256};
 use zksync_vm_interface::storage::ReadStorage;
 
+pub use self::budget::{PostgresStorageCachesBudgetTask, StorageCachesBudgetConfig};
 use self::metrics::{Method, ValuesUpdateStage, CACHE_METRICS, STORAGE_METRICS};
-use crate::cache::lru_cache::LruCache;
+use crate::cache::{adaptive_cache::AdaptiveCache, lru_cache::LruCache};
 
+mod budget;
 mod metrics;
 #[cfg(test)]
 mod tests;
@@ -24,11 +26,99 @@ struct TimestampedFactoryDep {
     inserted_at: L2BlockNumber,
 }
 
+/// Cache used in [`PostgresStorageCaches`]. Caches use the LRU eviction policy by default, and the W-TinyLFU admission policy
+/// if they share a memory budget.
+#[derive(Debug, Clone)]
+enum StorageCache<V> {
+    Lru(LruCache<H256, V>),
+    Adaptive(AdaptiveCache<H256, V>),
+}
+
+impl<V: Copy + Send + Sync + 'static> StorageCache<V> {
+    fn uniform(name: &'static str, capacity: u64, adaptive: bool) -> Self {
+        if adaptive {
+            Self::Adaptive(AdaptiveCache::uniform(name, capacity))
+        } else {
+            Self::Lru(LruCache::uniform(name, capacity))
+        }
+    }
+}
+
+impl<V: Clone + Send + Sync + 'static> StorageCache<V> {
+    fn weighted<W>(name: &'static str, capacity: u64, adaptive: bool, weigher: W) -> Self
+    where
+        W: Fn(&H256, &V) -> u32 + Send + Sync + 'static,
+    {
+        if adaptive {
+            Self::Adaptive(AdaptiveCache::weighted(name, capacity, weigher))
+        } else {
+            Self::Lru(LruCache::weighted(name, capacity, weigher))
+        }
+    }
+
+    fn as_adaptive(&self) -> Option<&AdaptiveCache<H256, V>> {
+        match self {
+            Self::Lru(_) => None,
+            Self::Adaptive(cache) => Some(cache),
+        }
+    }
+
+    fn capacity(&self) -> u64 {
+        match self {
+            Self::Lru(cache) => cache.capacity(),
+            Self::Adaptive(cache) => cache.capacity(),
+        }
+    }
+
+    fn get(&self, key: &H256) -> Option<V> {
+        match self {
+            Self::Lru(cache) => cache.get(key),
+            Self::Adaptive(cache) => cache.get(key),
+        }
+    }
+
+    fn insert(&self, key: H256, value: V) {
+        match self {
+            Self::Lru(cache) => cache.insert(key, value),
+            Self::Adaptive(cache) => cache.insert(key, value),
+        }
+    }
+
+    fn remove(&self, key: &H256) {
+        match self {
+            Self::Lru(cache) => cache.remove(key),
+            Self::Adaptive(cache) => cache.remove(key),
+        }
+    }
+
+    fn clear(&self) {
+        match self {
+            Self::Lru(cache) => cache.clear(),
+            Self::Adaptive(cache) => cache.clear(),
+        }
+    }
+
+    fn report_size(&self) {
+        match self {
+            Self::Lru(cache) => cache.report_size(),
+            Self::Adaptive(cache) => cache.report_size(),
+        }
+    }
+
+    #[cfg(test)]
+    fn estimated_len(&self) -> u64 {
+        match self {
+            Self::Lru(cache) => cache.estimated_len(),
+            Self::Adaptive(cache) => cache.estimated_len(),
+        }
+    }
+}
+
 /// Type alias for smart contract source code cache.
-type FactoryDepsCache = LruCache<H256, TimestampedFactoryDep>;
+type FactoryDepsCache = StorageCache<TimestampedFactoryDep>;
 
 /// Type alias for initial writes caches.
-type InitialWritesCache = LruCache<H256, L1BatchNumber>;
+type InitialWritesCache = StorageCache<L1BatchNumber>;
 
 /// [`StorageValue`] together with an L2 block "timestamp" starting from which it is known to be valid.
 ///
@@ -50,7 +140,7 @@ struct ValuesCacheInner {
     /// in `PostgresStorage` (i.e., the latest sealed L2 block for which storage logs should
     /// be taken into account).
     valid_for: L2BlockNumber,
-    values: LruCache<H256, TimestampedStorageValue>,
+    values: StorageCache<TimestampedStorageValue>,
 }
 
 /// Cache for the VM storage. Only caches values for a single VM storage snapshot, which logically
@@ -72,10 +162,10 @@ struct ValuesCacheInner {
 struct ValuesCache(Arc<RwLock<ValuesCacheInner>>);
 
 impl ValuesCache {
-    fn new(capacity: u64) -> Self {
+    fn new(capacity: u64, adaptive: bool) -> Self {
         let inner = ValuesCacheInner {
             valid_for: L2BlockNumber(0),
-            values: LruCache::uniform("values_cache", capacity),
+            values: StorageCache::uniform("values_cache", capacity, adaptive),
         };
         Self(Arc::new(RwLock::new(inner)))
     }
@@ -88,6 +178,17 @@ impl ValuesCache {
             .capacity()
     }
 
+    /// Returns a handle to the underlying adaptive cache, or `None` if the cache uses the LRU policy.
+    /// The handle remains valid after the cache is updated or reset.
+    fn adaptive_cache(&self) -> Option<AdaptiveCache<H256, TimestampedStorageValue>> {
+        self.0
+            .read()
+            .expect("values cache is poisoned")
+            .values
+            .as_adaptive()
+            .cloned()
+    }
+
     /// *NB.* The returned value should be considered immediately stale; at best, it can be
     /// the lower boundary on the current `valid_for` value.
     fn valid_for(&self) -> L2BlockNumber {
@@ -239,6 +340,10 @@ struct ValuesCacheAndUpdater {
 /// - Cache for L1 batch numbers of initial writes for storage keys (never invalidated, except after
 ///   reverting L1 batch execution)
 /// - Cache of the VM storage snapshot corresponding to the latest sealed L2 block
+///
+/// By default, caches use the LRU eviction policy and have fixed capacities. Caches created with [`Self::adaptive()`] use
+/// the W-TinyLFU admission policy instead, and can share a memory budget redistributed based on cache hit rates
+/// (see [`Self::configure_memory_budget()`]).
 #[derive(Debug, Clone)]
 pub struct PostgresStorageCaches {
     factory_deps: FactoryDepsCache,
@@ -255,18 +360,33 @@ pub struct PostgresStorageCaches {
 }
 
 impl PostgresStorageCaches {
-    /// Creates caches with the specified capacities measured in bytes.
-    #[allow(clippy::cast_possible_truncation, clippy::missing_panics_doc)] // not triggered in practice
+    /// Creates LRU caches with the specified capacities measured in bytes.
     pub fn new(factory_deps_capacity: u64, initial_writes_capacity: u64) -> Self {
+        Self::with_policy(factory_deps_capacity, initial_writes_capacity, false)
+    }
+
+    /// Creates caches with the W-TinyLFU admission policy and the specified capacities measured in bytes.
+    /// Such caches are more resistant to scans than LRU caches, and can share a memory budget.
+    pub fn adaptive(factory_deps_capacity: u64, initial_writes_capacity: u64) -> Self {
+        Self::with_policy(factory_deps_capacity, initial_writes_capacity, true)
+    }
+
+    #[allow(clippy::cast_possible_truncation, clippy::missing_panics_doc)] // not triggered in practice
+    fn with_policy(
+        factory_deps_capacity: u64,
+        initial_writes_capacity: u64,
+        adaptive: bool,
+    ) -> Self {
         tracing::debug!(
             "Initialized VM execution cache with {factory_deps_capacity}B capacity for factory deps, \
-             {initial_writes_capacity}B capacity for initial writes"
+             {initial_writes_capacity}B capacity for initial writes (adaptive: {adaptive})"
         );
 
         Self {
             factory_deps: FactoryDepsCache::weighted(
                 "factory_deps_cache",
                 factory_deps_capacity,
+                adaptive,
                 |_, value| {
                     (value.bytecode.len() + mem::size_of::<L2BlockNumber>())
                         .try_into()
@@ -276,10 +396,12 @@ impl PostgresStorageCaches {
             initial_writes: InitialWritesCache::uniform(
                 "initial_writes_cache",
                 initial_writes_capacity / 2,
+                adaptive,
             ),
             negative_initial_writes: InitialWritesCache::uniform(
                 "negative_initial_writes_cache",
                 initial_writes_capacity / 2,
+                adaptive,
             ),
             values: None,
         }
@@ -306,7 +428,8 @@ impl PostgresStorageCaches {
         tracing::debug!("Initializing VM storage values cache with {capacity}B capacity");
 
         let (command_sender, command_receiver) = watch::channel((L2BlockNumber(0), Instant::now()));
-        let values_cache = ValuesCache::new(capacity);
+        let adaptive = self.factory_deps.as_adaptive().is_some();
+        let values_cache = ValuesCache::new(capacity, adaptive);
         self.values = Some(ValuesCacheAndUpdater {
             cache: values_cache.clone(),
             command_sender: Arc::new(command_sender),
@@ -324,6 +447,24 @@ impl PostgresStorageCaches {
         }
     }
 
+    /// Configures a memory budget shared by all caches. The initial cache capacities are scaled proportionally to fit
+    /// into the budget. The returned task periodically redistributes the budget among caches based on their hit rates,
+    /// and optionally warms up caches from the most accessed keys persisted during the previous run.
+    ///
+    /// This method should be called after [`Self::configure_storage_values_cache()`] (if the values cache is used);
+    /// otherwise, the values cache won't participate in the budget.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the caches were not created with [`Self::adaptive()`].
+    pub fn configure_memory_budget(
+        &self,
+        config: StorageCachesBudgetConfig,
+        connection_pool: ConnectionPool<Core>,
+    ) -> PostgresStorageCachesBudgetTask {
+        PostgresStorageCachesBudgetTask::new(self, config, connection_pool)
+    }
+
     /// Schedules an update of the VM storage values cache to the specified L2 block. If the values cache is not configured,
     /// this is a no-op.
     ///
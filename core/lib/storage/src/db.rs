@@ -16,8 +16,9 @@ use std::{
 };
 
 use rocksdb::{
-    perf, properties, BlockBasedOptions, Cache, ColumnFamily, ColumnFamilyDescriptor,
-    DBPinnableSlice, Direction, IteratorMode, Options, PrefixRange, ReadOptions, WriteOptions, DB,
+    checkpoint::Checkpoint, perf, properties, BlockBasedOptions, Cache, ColumnFamily,
+    ColumnFamilyDescriptor, DBPinnableSlice, Direction, IteratorMode, Options, PrefixRange,
+    ReadOptions, WriteOptions, DB,
 };
 use thread_local::ThreadLocal;
 use vise::MetricsFamily;
@@ -377,12 +378,37 @@ impl<CF: NamedColumnFamily> RocksDB<CF> {
     }
 
     pub fn with_options(path: &Path, options: RocksDBOptions) -> Result<Self, rocksdb::Error> {
+        Self::open(path, None, options)
+    }
+
+    /// Opens a secondary instance of the database with the primary instance located at `path`. A secondary instance
+    /// is read-only and can be opened while the primary instance is used by another process. To observe changes
+    /// made by the primary instance, the secondary instance needs to periodically [catch up](Self::try_catch_up_with_primary())
+    /// with the primary.
+    ///
+    /// `secondary_path` is the directory to store secondary instance info logs; it must be different from `path`.
+    pub fn open_secondary(
+        path: &Path,
+        secondary_path: &Path,
+        options: RocksDBOptions,
+    ) -> Result<Self, rocksdb::Error> {
+        Self::open(path, Some(secondary_path), options)
+    }
+
+    fn open(
+        path: &Path,
+        secondary_path: Option<&Path>,
+        options: RocksDBOptions,
+    ) -> Result<Self, rocksdb::Error> {
         let caches = RocksDBCaches::new(options.block_cache_capacity);
         let mut db_options = Self::rocksdb_options(None, None);
-        let max_open_files = if let Some(non_zero) = options.max_open_files {
-            i32::try_from(non_zero.get()).unwrap_or(i32::MAX)
-        } else {
-            -1
+        let max_open_files = match options.max_open_files {
+            // Secondary instances must be able to keep all files open; otherwise, they can fail
+            // when the primary instance deletes files during compaction.
+            Some(non_zero) if secondary_path.is_none() => {
+                i32::try_from(non_zero.get()).unwrap_or(i32::MAX)
+            }
+            _ => -1,
         };
         db_options.set_max_open_files(max_open_files);
         let existing_cfs = DB::list_cf(&db_options, path).unwrap_or_else(|err| {
@@ -439,7 +465,11 @@ impl<CF: NamedColumnFamily> RocksDB<CF> {
             ColumnFamilyDescriptor::new(cf_name, cf_options)
         });
 
-        let db = DB::open_cf_descriptors(&db_options, path, cfs)?;
+        let db = if let Some(secondary_path) = secondary_path {
+            DB::open_cf_descriptors_as_secondary(&db_options, path, secondary_path, cfs)?
+        } else {
+            DB::open_cf_descriptors(&db_options, path, cfs)?
+        };
         let inner = Arc::new(RocksDBInner {
             db,
             db_name: CF::DB_NAME,
@@ -449,13 +479,21 @@ impl<CF: NamedColumnFamily> RocksDB<CF> {
         });
         RocksdbSizeMetrics::register(CF::DB_NAME, Arc::downgrade(&inner));
 
-        tracing::info!(
-            "Initialized RocksDB `{}` at `{}` with {options:?}",
-            CF::DB_NAME,
-            path.display()
-        );
-
-        inner.wait_for_writes_to_resume(&options.stalled_writes_retries);
+        if let Some(secondary_path) = secondary_path {
+            tracing::info!(
+                "Initialized secondary RocksDB `{}` at `{}` (secondary path: `{}`) with {options:?}",
+                CF::DB_NAME,
+                path.display(),
+                secondary_path.display()
+            );
+        } else {
+            tracing::info!(
+                "Initialized RocksDB `{}` at `{}` with {options:?}",
+                CF::DB_NAME,
+                path.display()
+            );
+            inner.wait_for_writes_to_resume(&options.stalled_writes_retries);
+        }
         Ok(Self {
             inner,
             sync_writes: false,
@@ -604,6 +642,20 @@ impl<CF: NamedColumnFamily> RocksDB<CF> {
             .unwrap_or_else(|| panic!("Column family `{}` doesn't exist", cf.name()))
     }
 
+    /// Creates an openable snapshot of this database at the specified `path`, which must not exist.
+    /// If `path` is on the same filesystem as the database, immutable SST files are hard-linked rather than copied,
+    /// so checkpoints are cheap to create. Memtables are flushed before creating a checkpoint.
+    pub fn create_checkpoint(&self, path: &Path) -> Result<(), rocksdb::Error> {
+        let checkpoint = Checkpoint::new(&self.inner.db)?;
+        checkpoint.create_checkpoint(path)
+    }
+
+    /// Catches up a [secondary instance](Self::open_secondary()) with the primary instance of the database.
+    /// For primary instances, this is a no-op.
+    pub fn try_catch_up_with_primary(&self) -> Result<(), rocksdb::Error> {
+        self.inner.db.try_catch_up_with_primary()
+    }
+
     /// Returns size stats for the specified column family.
     pub fn size_stats(&self, cf: CF) -> SizeStats {
         let cf = self.column_family(cf);
@@ -1076,6 +1128,69 @@ mod tests {
         assert_eq!(value.unwrap(), b"value");
     }
 
+    #[test]
+    fn creating_checkpoint() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = RocksDB::<OldColumnFamilies>::new(&temp_dir.path().join("db"))
+            .unwrap()
+            .with_sync_writes();
+        let mut batch = db.new_write_batch();
+        batch.put_cf(OldColumnFamilies::Default, b"test", b"value");
+        db.write(batch).unwrap();
+
+        let checkpoint_path = temp_dir.path().join("checkpoint");
+        db.create_checkpoint(&checkpoint_path).unwrap();
+        let mut batch = db.new_write_batch();
+        batch.put_cf(OldColumnFamilies::Default, b"test", b"new_value");
+        db.write(batch).unwrap();
+        // Checkpoints cannot overwrite existing directories
+        db.create_checkpoint(&checkpoint_path).unwrap_err();
+        drop(db);
+
+        let checkpoint = RocksDB::<OldColumnFamilies>::new(&checkpoint_path).unwrap();
+        let value = checkpoint
+            .get_cf(OldColumnFamilies::Default, b"test")
+            .unwrap();
+        assert_eq!(value.unwrap(), b"value");
+    }
+
+    #[test]
+    fn secondary_instance_catches_up_with_primary() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("db");
+        let db = RocksDB::<OldColumnFamilies>::new(&db_path)
+            .unwrap()
+            .with_sync_writes();
+        let mut batch = db.new_write_batch();
+        batch.put_cf(OldColumnFamilies::Default, b"test", b"value");
+        db.write(batch).unwrap();
+
+        let secondary = RocksDB::<OldColumnFamilies>::open_secondary(
+            &db_path,
+            &temp_dir.path().join("secondary"),
+            RocksDBOptions::default(),
+        )
+        .unwrap();
+        let value = secondary
+            .get_cf(OldColumnFamilies::Default, b"test")
+            .unwrap();
+        assert_eq!(value.unwrap(), b"value");
+
+        let mut batch = db.new_write_batch();
+        batch.put_cf(OldColumnFamilies::Default, b"test", b"new_value");
+        db.write(batch).unwrap();
+        secondary.try_catch_up_with_primary().unwrap();
+        let value = secondary
+            .get_cf(OldColumnFamilies::Default, b"test")
+            .unwrap();
+        assert_eq!(value.unwrap(), b"new_value");
+
+        // Secondary instances are read-only.
+        let mut batch = secondary.new_write_batch();
+        batch.put_cf(OldColumnFamilies::Default, b"test", b"other_value");
+        secondary.write(batch).unwrap_err();
+    }
+
     #[test]
     fn write_batch_can_be_restored_from_bytes() {
         let temp_dir = TempDir::new().unwrap();
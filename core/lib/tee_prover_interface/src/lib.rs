@@ -9,6 +9,8 @@ pub mod api;
 pub mod inputs;
 /// Outputs of proof generation provided by the prover subsystem.
 pub mod outputs;
+/// Signatures of L1 batch root hashes produced by TEE provers.
+pub mod signatures;
 
 // Marker trait for the serialization format of stored data.
 pub trait FormatMarker: private::Sealed {}
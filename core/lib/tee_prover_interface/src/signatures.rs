@@ -0,0 +1,72 @@
+use secp256k1::{ecdsa::Signature, Message, PublicKey, SecretKey, SECP256K1};
+use zksync_types::H256;
+
+/// Signatures are in the 65-byte RSV format with the recovery byte in the "Electrum" notation.
+pub const SIGNATURE_LEN: usize = 65;
+
+/// Errors that can occur when verifying a root hash signature.
+#[derive(Debug, thiserror::Error)]
+pub enum SignatureError {
+    #[error("expected {SIGNATURE_LEN}-byte signature, got {0} bytes")]
+    InvalidLength(usize),
+    #[error("malformed signature: {0}")]
+    Malformed(#[source] secp256k1::Error),
+    #[error("signature doesn't match root hash {0:?}")]
+    Mismatch(H256),
+}
+
+/// Signs the batch `root_hash` with `secret_key`, producing a signature in the format expected
+/// by [`verify_root_hash_signature()`].
+pub fn sign_root_hash(secret_key: &SecretKey, root_hash: H256) -> Vec<u8> {
+    let message = Message::from_slice(root_hash.as_bytes()).expect("root hash has 32 bytes");
+    let (recovery_id, data) = SECP256K1
+        .sign_ecdsa_recoverable(&message, secret_key)
+        .serialize_compact();
+    let mut signature = data.to_vec();
+    signature.push(recovery_id.to_i32() as u8 + 27);
+    signature
+}
+
+/// Verifies that `signature` is a signature of the batch `root_hash` made with the private key
+/// corresponding to `pubkey`. The recovery byte of the signature is ignored.
+pub fn verify_root_hash_signature(
+    pubkey: &PublicKey,
+    signature: &[u8],
+    root_hash: H256,
+) -> Result<(), SignatureError> {
+    if signature.len() != SIGNATURE_LEN {
+        return Err(SignatureError::InvalidLength(signature.len()));
+    }
+    let mut signature =
+        Signature::from_compact(&signature[..64]).map_err(SignatureError::Malformed)?;
+    signature.normalize_s();
+    let message = Message::from_slice(root_hash.as_bytes()).expect("root hash has 32 bytes");
+
+    SECP256K1
+        .verify_ecdsa(&message, &signature, pubkey)
+        .map_err(|_| SignatureError::Mismatch(root_hash))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn verifying_root_hash_signature() {
+        let secret_key = SecretKey::from_slice(&[0x42; 32]).unwrap();
+        let pubkey = PublicKey::from_secret_key(SECP256K1, &secret_key);
+        let root_hash = H256::repeat_byte(0x23);
+        let signature = sign_root_hash(&secret_key, root_hash);
+        verify_root_hash_signature(&pubkey, &signature, root_hash).unwrap();
+
+        let err = verify_root_hash_signature(&pubkey, &signature, H256::zero()).unwrap_err();
+        assert!(matches!(err, SignatureError::Mismatch(_)), "{err}");
+        let err = verify_root_hash_signature(&pubkey, &signature[..64], root_hash).unwrap_err();
+        assert!(matches!(err, SignatureError::InvalidLength(64)), "{err}");
+
+        let other_key = SecretKey::from_slice(&[0x43; 32]).unwrap();
+        let other_signature = sign_root_hash(&other_key, root_hash);
+        let err = verify_root_hash_signature(&pubkey, &other_signature, root_hash).unwrap_err();
+        assert!(matches!(err, SignatureError::Mismatch(_)), "{err}");
+    }
+}
@@ -16,10 +16,11 @@ pub use crate::transaction_request::{
     Eip712Meta, SerializationTransactionError, TransactionRequest,
 };
 use crate::{
+    bundle::BundleConstraints,
     debug_flat_call::{DebugCallFlat, ResultDebugCallFlat},
     protocol_version::L1VerifierConfig,
     server_notification::{GatewayMigrationNotification, GatewayMigrationState},
-    tee_types::TeeType,
+    tee_types::{TeeBatchStatus, TeeType},
     Address, L2BlockNumber, ProtocolVersionId,
 };
 
@@ -979,6 +980,10 @@ pub struct TeeProof {
     pub status: String,
     #[serde_as(as = "Option<Hex>")]
     pub attestation: Option<Vec<u8>>,
+    /// TEE verification status of the batch according to the quorum policy configured on the node.
+    /// The status is the same for all proofs of the batch and takes into account proofs of all TEE types.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub batch_status: Option<TeeBatchStatus>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -989,6 +994,16 @@ pub struct TransactionDetailedResult {
     pub events: Vec<Log>,
 }
 
+/// Request for `zks_sendBundle`: an ordered group of raw transactions executed all-or-nothing within one L2 block.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BundleRequest {
+    /// Raw signed transactions in the order of their execution.
+    pub transactions: Vec<Bytes>,
+    #[serde(flatten)]
+    pub constraints: BundleConstraints,
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ApiStorageLog {
@@ -997,6 +1012,33 @@ pub struct ApiStorageLog {
     pub written_value: U256,
 }
 
+/// Status of a [`TransactionPreconfirmation`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PreconfirmationStatus {
+    /// Transaction was executed by the sequencer and included into the pending L2 block.
+    Preconfirmed,
+    /// Previously preconfirmed transaction was rolled back before its L2 block got sealed (e.g., because
+    /// the sequencer was restarted). The transaction may be executed again with a different result.
+    RolledBack,
+}
+
+/// Execution result of a transaction reported by the sequencer before the L2 block containing
+/// the transaction is sealed.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionPreconfirmation {
+    pub transaction_hash: H256,
+    pub status: PreconfirmationStatus,
+    /// Number of the (not yet sealed) L2 block the transaction is included into.
+    pub block_number: U64,
+    pub transaction_index: Index,
+    /// Whether the transaction executed successfully (i.e., wasn't reverted).
+    pub success: bool,
+    pub gas_used: U256,
+    pub logs: Vec<Log>,
+}
+
 /// Raw transaction execution data.
 /// Data is taken from `TransactionExecutionMetrics`.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -0,0 +1,121 @@
+//! Transaction bundles: ordered groups of L2 transactions that are executed atomically by the state keeper.
+
+use serde::{Deserialize, Serialize};
+
+use crate::{web3::keccak256, L2BlockNumber, Transaction, H256};
+
+/// Constraints on the L2 block a bundle can be included into.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BundleConstraints {
+    /// Minimum timestamp (inclusive) of the L2 block the bundle can be included into.
+    pub min_timestamp: Option<u64>,
+    /// Maximum timestamp (inclusive) of the L2 block the bundle can be included into.
+    pub max_timestamp: Option<u64>,
+    /// Maximum number (inclusive) of the L2 block the bundle can be included into.
+    pub max_l2_block_number: Option<L2BlockNumber>,
+}
+
+/// Result of checking [`BundleConstraints`] against the parameters of an L2 block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BundleEligibility {
+    /// The bundle can be included into the L2 block.
+    Eligible,
+    /// The bundle cannot be included into the L2 block yet, but may be included into a later one.
+    TooEarly,
+    /// The bundle cannot be included into this or any later L2 block.
+    Expired,
+}
+
+impl BundleConstraints {
+    /// Checks whether a bundle with these constraints can be included into the L2 block with the specified params.
+    pub fn check(
+        &self,
+        l2_block_number: L2BlockNumber,
+        l2_block_timestamp: u64,
+    ) -> BundleEligibility {
+        let is_expired = self
+            .max_timestamp
+            .is_some_and(|max| l2_block_timestamp > max)
+            || self
+                .max_l2_block_number
+                .is_some_and(|max| l2_block_number > max);
+        if is_expired {
+            BundleEligibility::Expired
+        } else if self
+            .min_timestamp
+            .is_some_and(|min| l2_block_timestamp < min)
+        {
+            BundleEligibility::TooEarly
+        } else {
+            BundleEligibility::Eligible
+        }
+    }
+}
+
+/// Ordered group of L2 transactions executed all-or-nothing within a single L2 block.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TransactionBundle {
+    /// Bundle hash; see [`Self::compute_hash()`].
+    pub hash: H256,
+    pub transactions: Vec<Transaction>,
+    pub constraints: BundleConstraints,
+}
+
+impl TransactionBundle {
+    /// Computes the bundle hash as `keccak256` of the concatenated hashes of its transactions.
+    pub fn compute_hash(tx_hashes: impl IntoIterator<Item = H256>) -> H256 {
+        let preimage: Vec<u8> = tx_hashes.into_iter().flat_map(|hash| hash.0).collect();
+        H256(keccak256(&preimage))
+    }
+
+    pub fn tx_hashes(&self) -> impl Iterator<Item = H256> + '_ {
+        self.transactions.iter().map(Transaction::hash)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checking_bundle_constraints() {
+        let constraints = BundleConstraints {
+            min_timestamp: Some(10),
+            max_timestamp: Some(20),
+            max_l2_block_number: Some(L2BlockNumber(5)),
+        };
+        assert_eq!(
+            constraints.check(L2BlockNumber(1), 9),
+            BundleEligibility::TooEarly
+        );
+        assert_eq!(
+            constraints.check(L2BlockNumber(1), 10),
+            BundleEligibility::Eligible
+        );
+        assert_eq!(
+            constraints.check(L2BlockNumber(5), 20),
+            BundleEligibility::Eligible
+        );
+        assert_eq!(
+            constraints.check(L2BlockNumber(5), 21),
+            BundleEligibility::Expired
+        );
+        assert_eq!(
+            constraints.check(L2BlockNumber(6), 15),
+            BundleEligibility::Expired
+        );
+        assert_eq!(
+            BundleConstraints::default().check(L2BlockNumber(100), 0),
+            BundleEligibility::Eligible
+        );
+    }
+
+    #[test]
+    fn bundle_hash_depends_on_tx_order() {
+        let hashes = [H256::repeat_byte(1), H256::repeat_byte(2)];
+        let hash = TransactionBundle::compute_hash(hashes);
+        let reversed_hash = TransactionBundle::compute_hash(hashes.into_iter().rev());
+        assert_ne!(hash, reversed_hash);
+    }
+}
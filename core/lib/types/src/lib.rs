@@ -31,6 +31,7 @@ pub mod abi;
 pub mod aggregated_operations;
 pub mod blob;
 pub mod block;
+pub mod bundle;
 pub mod commitment;
 #[cfg(feature = "contract-verification")]
 pub mod contract_verification;
@@ -1,10 +1,15 @@
 use std::ops;
 
+use anyhow::Context as _;
 use num_enum::{IntoPrimitive, TryFromPrimitive};
 use serde::{Deserialize, Serialize};
 use zksync_basic_types::{L1BatchNumber, L2BlockNumber, H256};
 
-use crate::{u256_to_h256, utils, web3::Bytes, ProtocolVersionId, StorageKey, StorageValue, U256};
+use crate::{
+    u256_to_h256, utils,
+    web3::{keccak256, Bytes},
+    Address, K256PrivateKey, PackedEthSignature, ProtocolVersionId, StorageKey, StorageValue, U256,
+};
 
 /// Information about all snapshots persisted by the node.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -25,6 +30,18 @@ pub enum SnapshotVersion {
     /// Snapshot version made compatible with L1 recovery. Differs from `Version0` by including
     /// hashed keys in storage logs instead of `(address, key)` pairs.
     Version1 = 1,
+    /// Delta snapshot on top of a base snapshot. Storage logs have the same format as in `Version1`,
+    /// but only include entries changed after the base snapshot L1 batch; similarly, factory deps only include
+    /// bytecodes deployed after the base snapshot. The base snapshot may be a delta snapshot itself.
+    Version2 = 2,
+}
+
+impl SnapshotVersion {
+    /// Checks whether this version corresponds to a delta snapshot, i.e., one that cannot be recovered from
+    /// without its base snapshot.
+    pub fn is_delta(self) -> bool {
+        matches!(self, Self::Version2)
+    }
 }
 
 /// Storage snapshot metadata. Used in DAL to fetch certain snapshot data.
@@ -33,11 +50,22 @@ pub struct SnapshotMetadata {
     pub version: SnapshotVersion,
     /// L1 batch for the snapshot. The data in the snapshot captures node storage at the end of this batch.
     pub l1_batch_number: L1BatchNumber,
+    /// L1 batch of the base snapshot for delta snapshots; `None` for full snapshots.
+    pub base_l1_batch_number: Option<L1BatchNumber>,
     /// Path to the factory dependencies blob.
     pub factory_deps_filepath: String,
+    /// Content hash of the factory dependencies (see [`SnapshotFactoryDependencies::content_hash()`]); `None` for snapshots
+    /// created by older snapshot creator versions.
+    pub factory_deps_hash: Option<H256>,
     /// Paths to the storage log blobs. Ordered by the chunk ID. If a certain chunk is not produced yet,
     /// the corresponding path is `None`.
     pub storage_logs_filepaths: Vec<Option<String>>,
+    /// Content hashes of the storage log chunks (see [`SnapshotStorageLogsChunk::content_hash()`]). Has the same length
+    /// as `storage_logs_filepaths`; hashes are `None` for chunks not produced yet and for snapshots created
+    /// by older snapshot creator versions.
+    pub storage_logs_hashes: Vec<Option<H256>>,
+    /// Operator signature over the snapshot header, if the snapshot is signed.
+    pub header_signature: Option<PackedEthSignature>,
 }
 
 impl SnapshotMetadata {
@@ -61,6 +89,100 @@ pub struct SnapshotHeader {
     /// Ordered by chunk IDs.
     pub storage_logs_chunks: Vec<SnapshotStorageLogsChunkMetadata>,
     pub factory_deps_filepath: String,
+    /// Hash of the factory dependencies as returned by [`SnapshotFactoryDependencies::content_hash()`]. May be absent
+    /// for snapshots created by older snapshot creator versions.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub factory_deps_hash: Option<H256>,
+    /// For delta snapshots, L1 batch of the snapshot this snapshot is based on. The base snapshot must be
+    /// recovered first (recursively, if it's a delta snapshot itself).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base_l1_batch_number: Option<L1BatchNumber>,
+    /// Operator signature over [`Self::signed_digest()`]. Only present if the snapshot creator is configured
+    /// with a signing key.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<PackedEthSignature>,
+}
+
+impl SnapshotHeader {
+    /// Creates a header for a complete snapshot. Returns `None` if the snapshot is not complete.
+    pub fn new(metadata: SnapshotMetadata, l2_block_number: L2BlockNumber) -> Option<Self> {
+        if !metadata.is_complete() {
+            return None;
+        }
+
+        let storage_logs_chunks = metadata
+            .storage_logs_filepaths
+            .into_iter()
+            .zip(metadata.storage_logs_hashes)
+            .enumerate()
+            .filter_map(|(chunk_id, (filepath, content_hash))| {
+                Some(SnapshotStorageLogsChunkMetadata {
+                    chunk_id: chunk_id as u64,
+                    filepath: filepath?,
+                    content_hash,
+                })
+            })
+            .collect();
+        Some(Self {
+            version: metadata.version.into(),
+            l1_batch_number: metadata.l1_batch_number,
+            l2_block_number,
+            storage_logs_chunks,
+            factory_deps_filepath: metadata.factory_deps_filepath,
+            factory_deps_hash: metadata.factory_deps_hash,
+            base_l1_batch_number: metadata.base_l1_batch_number,
+            signature: metadata.header_signature,
+        })
+    }
+
+    /// Returns the digest signed by the operator. The digest commits to the snapshot version, its L1 batch and L2 block,
+    /// the base snapshot (for delta snapshots) and content hashes of all storage log chunks and factory deps. File paths are not committed to,
+    /// so that snapshot files can be mirrored to a different location without invalidating the signature.
+    pub fn signed_digest(&self) -> H256 {
+        let mut buffer = Vec::with_capacity(64 + self.storage_logs_chunks.len() * 40);
+        buffer.extend_from_slice(b"zksync-snapshot-header");
+        buffer.extend_from_slice(&self.version.to_be_bytes());
+        buffer.extend_from_slice(&self.l1_batch_number.0.to_be_bytes());
+        buffer.extend_from_slice(&self.l2_block_number.0.to_be_bytes());
+        let base_l1_batch_number = self
+            .base_l1_batch_number
+            .map_or(u32::MAX, |number| number.0);
+        buffer.extend_from_slice(&base_l1_batch_number.to_be_bytes());
+        for chunk in &self.storage_logs_chunks {
+            buffer.extend_from_slice(&chunk.chunk_id.to_be_bytes());
+            buffer.extend_from_slice(chunk.content_hash.unwrap_or_default().as_bytes());
+        }
+        buffer.extend_from_slice(self.factory_deps_hash.unwrap_or_default().as_bytes());
+        H256(keccak256(&buffer))
+    }
+
+    /// Signs this header with the provided operator key.
+    pub fn sign(&mut self, signing_key: &K256PrivateKey) -> anyhow::Result<()> {
+        let signature = PackedEthSignature::sign_raw(signing_key, &self.signed_digest())?;
+        self.signature = Some(signature);
+        Ok(())
+    }
+
+    /// Verifies that this header is signed by `expected_signer`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the header is not signed, or the signature is invalid or belongs to another signer.
+    pub fn verify_signature(&self, expected_signer: Address) -> anyhow::Result<()> {
+        let signature = self.signature.as_ref().with_context(|| {
+            format!(
+                "snapshot header for L1 batch #{} is not signed",
+                self.l1_batch_number
+            )
+        })?;
+        let signer = signature.signature_recover_signer(&self.signed_digest())?;
+        anyhow::ensure!(
+            signer == expected_signer,
+            "snapshot header for L1 batch #{} is signed by unexpected signer {signer:?}; expected {expected_signer:?}",
+            self.l1_batch_number
+        );
+        Ok(())
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -69,6 +191,10 @@ pub struct SnapshotStorageLogsChunkMetadata {
     pub chunk_id: u64,
     // can be either be a file available under HTTP(s) or local filesystem path
     pub filepath: String,
+    /// Hash of the chunk content as returned by [`SnapshotStorageLogsChunk::content_hash()`]. May be absent
+    /// for snapshots created by older snapshot creator versions.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content_hash: Option<H256>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -83,6 +209,41 @@ pub struct SnapshotStorageLogsChunk<K = H256> {
     pub storage_logs: Vec<SnapshotStorageLog<K>>,
 }
 
+impl<K: SnapshotStorageLogKey> SnapshotStorageLogsChunk<K> {
+    /// Computes the hash of the chunk content. The hash is computed over hashed keys, so it doesn't depend
+    /// on the snapshot version, or on how the chunk is serialized in the object store.
+    pub fn content_hash(&self) -> H256 {
+        const LOG_ENCODING_LEN: usize = 32 + 32 + 4 + 8;
+
+        let mut buffer = Vec::with_capacity(self.storage_logs.len() * LOG_ENCODING_LEN);
+        for log in &self.storage_logs {
+            buffer.extend_from_slice(log.key.hashed_key().as_bytes());
+            buffer.extend_from_slice(log.value.as_bytes());
+            buffer.extend_from_slice(&log.l1_batch_number_of_initial_write.0.to_be_bytes());
+            buffer.extend_from_slice(&log.enumeration_index.to_be_bytes());
+        }
+        H256(keccak256(&buffer))
+    }
+}
+
+/// Key type used in [`SnapshotStorageLog`]s.
+pub trait SnapshotStorageLogKey {
+    /// Returns the hashed key (i.e., the key used in the Merkle tree).
+    fn hashed_key(&self) -> H256;
+}
+
+impl SnapshotStorageLogKey for H256 {
+    fn hashed_key(&self) -> H256 {
+        *self
+    }
+}
+
+impl SnapshotStorageLogKey for StorageKey {
+    fn hashed_key(&self) -> H256 {
+        StorageKey::hashed_key(self)
+    }
+}
+
 /// Storage log record in a storage snapshot.
 ///
 /// Version 0 and version 1 snapshots differ in the key type; version 0 uses full [`StorageKey`]s (i.e., storage key preimages),
@@ -111,6 +272,20 @@ pub struct SnapshotFactoryDependencies {
     pub factory_deps: Vec<SnapshotFactoryDependency>,
 }
 
+impl SnapshotFactoryDependencies {
+    /// Computes the hash of the factory deps content. Like [`SnapshotStorageLogsChunk::content_hash()`],
+    /// the hash doesn't depend on how the deps are serialized in the object store.
+    pub fn content_hash(&self) -> H256 {
+        let mut buffer = vec![];
+        for dep in &self.factory_deps {
+            buffer.extend_from_slice(dep.hash.unwrap_or_default().as_bytes());
+            buffer.extend_from_slice(&(dep.bytecode.0.len() as u64).to_be_bytes());
+            buffer.extend_from_slice(&dep.bytecode.0);
+        }
+        H256(keccak256(&buffer))
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct SnapshotFactoryDependency {
     pub bytecode: Bytes,
@@ -349,7 +524,7 @@ pub fn uniform_hashed_keys_chunk(chunk_id: u64, chunk_count: u64) -> ops::RangeI
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::h256_to_u256;
+    use crate::{h256_to_u256, AccountTreeId};
 
     #[test]
     fn chunking_is_correct() {
@@ -387,4 +562,94 @@ mod tests {
             assert!(max_chunk_size - min_chunk_size < U256::from(chunks_count));
         }
     }
+
+    #[test]
+    fn chunk_content_hash_does_not_depend_on_key_format() {
+        let logs: Vec<_> = (0..10_u8)
+            .map(|i| SnapshotStorageLog {
+                key: StorageKey::new(
+                    AccountTreeId::new(Address::repeat_byte(i)),
+                    H256::repeat_byte(i),
+                ),
+                value: H256::from_low_u64_be(i.into()),
+                l1_batch_number_of_initial_write: L1BatchNumber(i.into()),
+                enumeration_index: i.into(),
+            })
+            .collect();
+        let chunk_with_preimages = SnapshotStorageLogsChunk {
+            storage_logs: logs.clone(),
+        };
+        let chunk = SnapshotStorageLogsChunk {
+            storage_logs: logs
+                .into_iter()
+                .map(SnapshotStorageLog::drop_key_preimage)
+                .collect(),
+        };
+        assert_eq!(chunk.content_hash(), chunk_with_preimages.content_hash());
+
+        let mut modified_chunk = chunk.clone();
+        modified_chunk.storage_logs[3].value = H256::repeat_byte(0xff);
+        assert_ne!(modified_chunk.content_hash(), chunk.content_hash());
+    }
+
+    #[test]
+    fn signing_snapshot_header() {
+        let signing_key = K256PrivateKey::random();
+        let mut header = SnapshotHeader {
+            version: SnapshotVersion::Version1.into(),
+            l1_batch_number: L1BatchNumber(5),
+            l2_block_number: L2BlockNumber(10),
+            storage_logs_chunks: vec![SnapshotStorageLogsChunkMetadata {
+                chunk_id: 0,
+                filepath: "storage_logs_snapshots/chunk_0".to_owned(),
+                content_hash: Some(H256::repeat_byte(1)),
+            }],
+            factory_deps_filepath: "factory_deps".to_owned(),
+            factory_deps_hash: Some(H256::repeat_byte(3)),
+            base_l1_batch_number: None,
+            signature: None,
+        };
+        header.verify_signature(signing_key.address()).unwrap_err();
+
+        header.sign(&signing_key).unwrap();
+        header.verify_signature(signing_key.address()).unwrap();
+        header
+            .verify_signature(Address::repeat_byte(1))
+            .unwrap_err();
+
+        // Moving files doesn't invalidate the signature.
+        header.storage_logs_chunks[0].filepath = "mirror/chunk_0".to_owned();
+        header.verify_signature(signing_key.address()).unwrap();
+
+        header.storage_logs_chunks[0].content_hash = Some(H256::repeat_byte(2));
+        header.verify_signature(signing_key.address()).unwrap_err();
+        header.storage_logs_chunks[0].content_hash = Some(H256::repeat_byte(1));
+        header.verify_signature(signing_key.address()).unwrap();
+
+        header.factory_deps_hash = Some(H256::repeat_byte(4));
+        header.verify_signature(signing_key.address()).unwrap_err();
+    }
+
+    #[test]
+    fn factory_deps_content_hash() {
+        let deps = SnapshotFactoryDependencies {
+            factory_deps: vec![
+                SnapshotFactoryDependency {
+                    bytecode: Bytes(vec![1; 64]),
+                    hash: Some(H256::repeat_byte(1)),
+                },
+                SnapshotFactoryDependency {
+                    bytecode: Bytes(vec![2; 32]),
+                    hash: None,
+                },
+            ],
+        };
+        let mut modified_deps = SnapshotFactoryDependencies {
+            factory_deps: deps.factory_deps.clone(),
+        };
+        assert_eq!(modified_deps.content_hash(), deps.content_hash());
+
+        modified_deps.factory_deps[1].bytecode.0[0] = 3;
+        assert_ne!(modified_deps.content_hash(), deps.content_hash());
+    }
 }
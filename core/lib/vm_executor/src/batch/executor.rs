@@ -142,6 +142,53 @@ where
         Ok(())
     }
 
+    #[tracing::instrument(skip_all)]
+    async fn start_bundle(&mut self) -> anyhow::Result<()> {
+        let (response_sender, response_receiver) = oneshot::channel();
+        let send_failed = self
+            .commands
+            .send(Command::StartBundle(response_sender))
+            .await
+            .is_err();
+        if send_failed {
+            return Err(self.handle.wait_for_error().await);
+        }
+
+        let latency = EXECUTOR_METRICS.batch_executor_command_response_time
+            [&ExecutorCommand::StartBundle]
+            .start();
+        if response_receiver.await.is_err() {
+            return Err(self.handle.wait_for_error().await);
+        }
+        latency.observe();
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn finish_bundle(&mut self, rollback: bool) -> anyhow::Result<()> {
+        let (response_sender, response_receiver) = oneshot::channel();
+        let send_failed = self
+            .commands
+            .send(Command::FinishBundle {
+                rollback,
+                response: response_sender,
+            })
+            .await
+            .is_err();
+        if send_failed {
+            return Err(self.handle.wait_for_error().await);
+        }
+
+        let latency = EXECUTOR_METRICS.batch_executor_command_response_time
+            [&ExecutorCommand::FinishBundle]
+            .start();
+        if response_receiver.await.is_err() {
+            return Err(self.handle.wait_for_error().await);
+        }
+        latency.observe();
+        Ok(())
+    }
+
     #[tracing::instrument(skip_all)]
     async fn start_next_l2_block(&mut self, env: L2BlockEnv) -> anyhow::Result<()> {
         // While we don't get anything from the channel, it's useful to have it as a confirmation that the operation
@@ -201,5 +248,10 @@ pub(super) enum Command {
     ),
     StartNextL2Block(L2BlockEnv, oneshot::Sender<()>),
     RollbackLastTx(oneshot::Sender<()>),
+    StartBundle(oneshot::Sender<()>),
+    FinishBundle {
+        rollback: bool,
+        response: oneshot::Sender<()>,
+    },
     FinishBatch(oneshot::Sender<FinishedL1Batch>),
 }
@@ -339,6 +339,7 @@ impl<S: ReadStorage + 'static, Tr: BatchTracer> CommandReceiver<S, Tr> {
             }
         }
         let mut batch_finished = false;
+        let mut bundle_active = false;
         let mut prev_storage_stats = StorageViewStats::default();
 
         if let BatchVm::Fast(FastVmInstance::Shadowed(shadowed)) = &mut vm {
@@ -351,9 +352,11 @@ impl<S: ReadStorage + 'static, Tr: BatchTracer> CommandReceiver<S, Tr> {
             match cmd {
                 Command::ExecuteTx(tx, resp) => {
                     let tx_hash = tx.hash();
-                    let (result, latency) = self.execute_tx(*tx, &mut vm).with_context(|| {
-                        format!("fatal error executing transaction {tx_hash:?}")
-                    })?;
+                    let (result, latency) = self
+                        .execute_tx(*tx, &mut vm, bundle_active)
+                        .with_context(|| {
+                            format!("fatal error executing transaction {tx_hash:?}")
+                        })?;
 
                     if self.observe_storage_metrics {
                         let storage_stats = storage_view.borrow().stats();
@@ -366,11 +369,38 @@ impl<S: ReadStorage + 'static, Tr: BatchTracer> CommandReceiver<S, Tr> {
                     }
                 }
                 Command::RollbackLastTx(resp) => {
+                    anyhow::ensure!(
+                        !bundle_active,
+                        "cannot roll back a single transaction while a bundle is active"
+                    );
                     self.rollback_last_tx(&mut vm);
                     if resp.send(()).is_err() {
                         break;
                     }
                 }
+                Command::StartBundle(resp) => {
+                    anyhow::ensure!(!bundle_active, "bundle is already active");
+                    // The snapshot made here is kept until the bundle is finished; transactions executed
+                    // in the bundle don't create their own snapshots.
+                    vm.pop_snapshot_no_rollback();
+                    vm.make_snapshot();
+                    bundle_active = true;
+                    if resp.send(()).is_err() {
+                        break;
+                    }
+                }
+                Command::FinishBundle { rollback, response } => {
+                    anyhow::ensure!(bundle_active, "no active bundle to finish");
+                    if rollback {
+                        self.rollback_bundle(&mut vm);
+                    } else {
+                        vm.pop_snapshot_no_rollback();
+                    }
+                    bundle_active = false;
+                    if response.send(()).is_err() {
+                        break;
+                    }
+                }
                 Command::StartNextL2Block(l2_block_env, resp) => {
                     vm.start_new_l2_block(l2_block_env);
                     if resp.send(()).is_err() {
@@ -409,16 +439,21 @@ impl<S: ReadStorage + 'static, Tr: BatchTracer> CommandReceiver<S, Tr> {
         &self,
         transaction: Transaction,
         vm: &mut BatchVm<S, Tr>,
+        in_bundle: bool,
     ) -> anyhow::Result<(BatchTransactionExecutionResult, Duration)> {
-        // Executing a next transaction means that a previous transaction was either rolled back (in which case its snapshot
-        // was already removed), or that we build on top of it (in which case, it can be removed now).
-        vm.pop_snapshot_no_rollback();
-        // Save pre-execution VM snapshot.
-        vm.make_snapshot();
+        // Transactions in a bundle share the snapshot made at the bundle start.
+        if !in_bundle {
+            // Executing a next transaction means that a previous transaction was either rolled back (in which case its snapshot
+            // was already removed), or that we build on top of it (in which case, it can be removed now).
+            vm.pop_snapshot_no_rollback();
+            // Save pre-execution VM snapshot.
+            vm.make_snapshot();
+        }
 
-        // Execute the transaction.
+        // Execute the transaction. Optional compression relies on rolling back to the pre-transaction snapshot,
+        // so it cannot be used within bundles.
         let latency = KEEPER_METRICS.tx_execution_time[&TxExecutionStage::Execution].start();
-        let result = if self.optional_bytecode_compression {
+        let result = if self.optional_bytecode_compression && !in_bundle {
             self.execute_tx_in_vm_with_optional_compression(&transaction, vm)?
         } else {
             self.execute_tx_in_vm(&transaction, vm)?
@@ -433,6 +468,12 @@ impl<S: ReadStorage + 'static, Tr: BatchTracer> CommandReceiver<S, Tr> {
         latency.observe();
     }
 
+    fn rollback_bundle(&self, vm: &mut BatchVm<S, Tr>) {
+        let latency = KEEPER_METRICS.tx_execution_time[&TxExecutionStage::BundleRollback].start();
+        vm.rollback_to_the_latest_snapshot();
+        latency.observe();
+    }
+
     fn finish_batch(
         &self,
         vm: &mut BatchVm<S, Tr>,
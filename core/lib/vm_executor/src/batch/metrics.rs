@@ -14,6 +14,8 @@ pub(super) enum ExecutorCommand {
     #[metrics(name = "start_next_miniblock")]
     StartNextL2Block,
     RollbackLastTx,
+    StartBundle,
+    FinishBundle,
     FinishBatch,
 }
 
@@ -30,6 +32,7 @@ const GAS_USED_BUCKETS: Buckets = Buckets::values(&[
 pub(super) enum TxExecutionStage {
     Execution,
     TxRollback,
+    BundleRollback,
 }
 
 /// Executor-related metrics.
@@ -0,0 +1,327 @@
+use std::{
+    collections::HashSet,
+    fmt,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use tokio::sync::RwLock;
+use zksync_types::{Address, Transaction, H256};
+
+/// SharedDenyList is a thread-safe wrapper around a HashSet of denied addresses.
+///
+/// A list created with [`Self::refreshable()`] is considered unavailable until it's loaded for the first time,
+/// and becomes unavailable again if it isn't refreshed for longer than the configured max staleness.
+#[derive(Debug, Clone)]
+pub struct SharedDenyList {
+    inner: Arc<RwLock<HashSet<Address>>>,
+    /// Time of the last successful refresh; `None` if the list was never loaded.
+    refreshed_at: Arc<Mutex<Option<Instant>>>,
+    /// `None` means that the list never becomes stale.
+    max_staleness: Option<Duration>,
+}
+
+impl Default for SharedDenyList {
+    fn default() -> Self {
+        Self::new(HashSet::new())
+    }
+}
+
+impl From<Vec<Address>> for SharedDenyList {
+    fn from(addresses: Vec<Address>) -> Self {
+        Self::new(HashSet::from_iter(addresses))
+    }
+}
+
+impl SharedDenyList {
+    /// Creates a static deny list which is always available.
+    pub fn new(addresses: HashSet<Address>) -> Self {
+        Self {
+            inner: Arc::new(RwLock::new(addresses)),
+            refreshed_at: Arc::new(Mutex::new(Some(Instant::now()))),
+            max_staleness: None,
+        }
+    }
+
+    /// Creates an empty deny list that needs to be loaded and then periodically refreshed
+    /// using [`Self::mark_refreshed()`].
+    pub fn refreshable(max_staleness: Duration) -> Self {
+        Self {
+            inner: Arc::default(),
+            refreshed_at: Arc::default(),
+            max_staleness: Some(max_staleness),
+        }
+    }
+
+    /// Marks the list as successfully refreshed (even if its contents didn't change).
+    pub fn mark_refreshed(&self) {
+        *self.refreshed_at.lock().unwrap() = Some(Instant::now());
+    }
+
+    /// Checks whether the list can be used for screening.
+    pub fn check_available(&self) -> Result<(), DenyListUnavailable> {
+        let refreshed_at = *self.refreshed_at.lock().unwrap();
+        let Some(refreshed_at) = refreshed_at else {
+            return Err(DenyListUnavailable::NotLoaded);
+        };
+        match self.max_staleness {
+            Some(max_staleness) if refreshed_at.elapsed() > max_staleness => {
+                Err(DenyListUnavailable::Stale {
+                    since_refresh: refreshed_at.elapsed(),
+                })
+            }
+            _ => Ok(()),
+        }
+    }
+
+    pub fn writer(&self) -> &Arc<RwLock<HashSet<Address>>> {
+        &self.inner
+    }
+
+    pub async fn is_address_denied(&self, address: &Address) -> bool {
+        self.inner.read().await.contains(address)
+    }
+}
+
+/// Error returned when the deny list cannot be used for screening. Screening fails closed in this case.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DenyListUnavailable {
+    /// The list was never loaded.
+    NotLoaded,
+    /// The list wasn't successfully refreshed for too long.
+    Stale { since_refresh: Duration },
+}
+
+impl fmt::Display for DenyListUnavailable {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotLoaded => formatter.write_str("address deny list is not loaded"),
+            Self::Stale { since_refresh } => write!(
+                formatter,
+                "address deny list is stale (last refreshed {since_refresh:?} ago)"
+            ),
+        }
+    }
+}
+
+/// Role of a denied address in a screened transaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeniedAddressRole {
+    /// Transaction initiator.
+    Sender,
+    /// Transaction recipient (i.e., the called contract).
+    Recipient,
+    /// Address touched during transaction execution.
+    Touched,
+}
+
+impl DeniedAddressRole {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Sender => "sender",
+            Self::Recipient => "recipient",
+            Self::Touched => "touched",
+        }
+    }
+}
+
+/// Denied address found in a transaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DeniedAddress {
+    pub address: Address,
+    pub role: DeniedAddressRole,
+}
+
+impl fmt::Display for DeniedAddress {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            formatter,
+            "{} address {:?} is in the deny list",
+            self.role.as_str(),
+            self.address
+        )
+    }
+}
+
+/// AddressScreener checks L2 transactions against the address deny list.
+#[derive(Debug, Clone)]
+pub struct AddressScreener {
+    deny_list: SharedDenyList,
+    screen_touched_addresses: bool,
+}
+
+impl AddressScreener {
+    pub fn new(deny_list: SharedDenyList, screen_touched_addresses: bool) -> Self {
+        Self {
+            deny_list,
+            screen_touched_addresses,
+        }
+    }
+
+    /// Checks whether the underlying deny list can be used for screening.
+    pub fn check_available(&self) -> Result<(), DenyListUnavailable> {
+        self.deny_list.check_available()
+    }
+
+    /// Screens the transaction sender and recipient.
+    pub async fn screen_transaction(
+        &self,
+        tx: &Transaction,
+    ) -> Result<Option<DeniedAddress>, DenyListUnavailable> {
+        self.check_available()?;
+        let deny_list = self.deny_list.inner.read().await;
+        if deny_list.contains(&tx.initiator_account()) {
+            return Ok(Some(DeniedAddress {
+                address: tx.initiator_account(),
+                role: DeniedAddressRole::Sender,
+            }));
+        }
+        let Some(recipient) = tx.execute.contract_address else {
+            return Ok(None);
+        };
+        Ok(deny_list.contains(&recipient).then_some(DeniedAddress {
+            address: recipient,
+            role: DeniedAddressRole::Recipient,
+        }))
+    }
+
+    /// Screens all transactions in a bundle, returning the first denied address together with the hash
+    /// of the transaction it was found in.
+    pub async fn screen_transactions<'a>(
+        &self,
+        txs: impl IntoIterator<Item = &'a Transaction>,
+    ) -> Result<Option<(H256, DeniedAddress)>, DenyListUnavailable> {
+        for tx in txs {
+            if let Some(denied) = self.screen_transaction(tx).await? {
+                return Ok(Some((tx.hash(), denied)));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Screens addresses touched during transaction execution. Returns `Ok(None)` if touched addresses screening is disabled.
+    pub async fn screen_touched_addresses(
+        &self,
+        touched_addresses: impl IntoIterator<Item = Address>,
+    ) -> Result<Option<DeniedAddress>, DenyListUnavailable> {
+        if !self.screen_touched_addresses {
+            return Ok(None);
+        }
+        self.check_available()?;
+        let deny_list = self.deny_list.inner.read().await;
+        Ok(touched_addresses
+            .into_iter()
+            .find(|address| deny_list.contains(address))
+            .map(|address| DeniedAddress {
+                address,
+                role: DeniedAddressRole::Touched,
+            }))
+    }
+}
+
+/// Writes an audit log entry for a transaction rejected because of a denied address.
+pub fn log_denied_transaction(tx_hash: H256, denied: &DeniedAddress, stage: &'static str) {
+    tracing::warn!(
+        target: "address_screening_audit",
+        tx_hash = ?tx_hash,
+        address = ?denied.address,
+        role = denied.role.as_str(),
+        stage,
+        "Rejected transaction {tx_hash:?} at {stage}: {denied}"
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+    use zksync_types::{l2::L2Tx, K256PrivateKey, L2ChainId, Nonce};
+
+    use super::*;
+
+    fn transaction(signer: &K256PrivateKey, recipient: Address) -> Transaction {
+        L2Tx::new_signed(
+            Some(recipient),
+            vec![],
+            Nonce(0),
+            Default::default(),
+            0.into(),
+            L2ChainId::default(),
+            signer,
+            vec![],
+            Default::default(),
+        )
+        .unwrap()
+        .into()
+    }
+
+    #[tokio::test]
+    async fn screening_transactions() {
+        let signer = K256PrivateKey::random();
+        let denied_recipient = Address::repeat_byte(1);
+        let deny_list = SharedDenyList::from(vec![denied_recipient]);
+        let screener = AddressScreener::new(deny_list.clone(), false);
+
+        let tx = transaction(&signer, Address::repeat_byte(2));
+        assert_eq!(screener.screen_transaction(&tx).await, Ok(None));
+        let tx = transaction(&signer, denied_recipient);
+        let denied = screener.screen_transaction(&tx).await.unwrap().unwrap();
+        assert_eq!(denied.role, DeniedAddressRole::Recipient);
+        assert_eq!(denied.address, denied_recipient);
+
+        // Check that the list is hot-reloadable.
+        deny_list.writer().write().await.insert(signer.address());
+        let tx = transaction(&signer, Address::repeat_byte(2));
+        let denied = screener.screen_transaction(&tx).await.unwrap().unwrap();
+        assert_eq!(denied.role, DeniedAddressRole::Sender);
+        let other_tx = transaction(&K256PrivateKey::random(), Address::repeat_byte(2));
+        let (tx_hash, denied) = screener
+            .screen_transactions([&other_tx, &tx])
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(tx_hash, tx.hash());
+        assert_eq!(denied.role, DeniedAddressRole::Sender);
+
+        // Touched addresses are not screened unless enabled.
+        let touched = [Address::repeat_byte(3), denied_recipient];
+        assert_eq!(screener.screen_touched_addresses(touched).await, Ok(None));
+        let screener = AddressScreener::new(deny_list, true);
+        let denied = screener
+            .screen_touched_addresses(touched)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(denied.role, DeniedAddressRole::Touched);
+        assert_eq!(denied.address, denied_recipient);
+    }
+
+    #[tokio::test]
+    async fn screening_fails_closed_for_unavailable_list() {
+        let signer = K256PrivateKey::random();
+        let deny_list = SharedDenyList::refreshable(Duration::from_millis(50));
+        let screener = AddressScreener::new(deny_list.clone(), true);
+        let tx = transaction(&signer, Address::repeat_byte(2));
+
+        assert_eq!(
+            screener.screen_transaction(&tx).await,
+            Err(DenyListUnavailable::NotLoaded)
+        );
+        assert_eq!(
+            screener
+                .screen_touched_addresses([Address::repeat_byte(3)])
+                .await,
+            Err(DenyListUnavailable::NotLoaded)
+        );
+
+        deny_list.mark_refreshed();
+        assert_eq!(screener.screen_transaction(&tx).await, Ok(None));
+
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert_matches!(
+            screener.screen_transaction(&tx).await,
+            Err(DenyListUnavailable::Stale { .. })
+        );
+        deny_list.mark_refreshed();
+        assert_eq!(screener.screen_transaction(&tx).await, Ok(None));
+    }
+}
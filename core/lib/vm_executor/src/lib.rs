@@ -6,6 +6,7 @@
 pub use zksync_multivm::interface::executor as interface;
 
 pub mod batch;
+pub mod deny_list;
 #[cfg(feature = "node_framework")]
 pub mod node;
 pub mod oneshot;
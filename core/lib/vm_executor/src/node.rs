@@ -1,9 +1,15 @@
 use zksync_node_framework::Resource;
 
-use crate::whitelist::SharedAllowList;
+use crate::{deny_list::AddressScreener, whitelist::SharedAllowList};
 
 impl Resource for SharedAllowList {
     fn name() -> String {
         "shared_allow_list".to_string()
     }
 }
+
+impl Resource for AddressScreener {
+    fn name() -> String {
+        "address_screener".to_string()
+    }
+}
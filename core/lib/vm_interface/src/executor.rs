@@ -39,6 +39,14 @@ pub trait BatchExecutor<S>: 'static + Send + fmt::Debug {
     /// Rolls back the last executed transaction.
     async fn rollback_last_tx(&mut self) -> anyhow::Result<()>;
 
+    /// Starts an atomic bundle of transactions. All transactions executed until the following [`Self::finish_bundle()`]
+    /// call can be rolled back together. [`Self::rollback_last_tx()`] must not be called while a bundle is active.
+    async fn start_bundle(&mut self) -> anyhow::Result<()>;
+
+    /// Finishes the bundle started with [`Self::start_bundle()`]. If `rollback` is set, all transactions
+    /// executed as a part of the bundle are rolled back.
+    async fn finish_bundle(&mut self, rollback: bool) -> anyhow::Result<()>;
+
     /// Starts a next L2 block with the specified params.
     async fn start_next_l2_block(&mut self, env: L2BlockEnv) -> anyhow::Result<()>;
 
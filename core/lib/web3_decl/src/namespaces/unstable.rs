@@ -4,7 +4,7 @@ use jsonrpsee::proc_macros::rpc;
 use zksync_types::{
     api::{
         ChainAggProof, DataAvailabilityDetails, GatewayMigrationStatus, L1ToL2TxsStatus, TeeProof,
-        TransactionDetailedResult, TransactionExecutionInfo,
+        TransactionDetailedResult, TransactionExecutionInfo, TransactionPreconfirmation,
     },
     tee_types::TeeType,
     L1BatchNumber, L2ChainId, H256,
@@ -31,6 +31,12 @@ pub trait UnstableNamespace {
         hash: H256,
     ) -> RpcResult<Option<TransactionExecutionInfo>>;
 
+    #[method(name = "getTransactionPreconfirmation")]
+    async fn transaction_preconfirmation(
+        &self,
+        hash: H256,
+    ) -> RpcResult<Option<TransactionPreconfirmation>>;
+
     #[method(name = "getTeeProofs")]
     async fn tee_proofs(
         &self,
@@ -5,8 +5,8 @@ use jsonrpsee::core::RpcResult;
 use jsonrpsee::proc_macros::rpc;
 use zksync_types::{
     api::{
-        state_override::StateOverride, BlockDetails, BridgeAddresses, L1BatchDetails,
-        L2ToL1LogProof, Proof, ProtocolVersion, TransactionDetails,
+        state_override::StateOverride, BlockDetails, BridgeAddresses, BundleRequest,
+        L1BatchDetails, L2ToL1LogProof, Proof, ProtocolVersion, TransactionDetails,
     },
     fee::Fee,
     fee_model::{FeeParams, PubdataIndependentBatchFeeModelInput},
@@ -132,4 +132,9 @@ pub trait ZksNamespace {
 
     #[method(name = "getBatchFeeInput")]
     async fn get_batch_fee_input(&self) -> RpcResult<PubdataIndependentBatchFeeModelInput>;
+
+    /// Submits an ordered bundle of raw L2 transactions that will be executed atomically within a single L2 block.
+    /// Returns the bundle hash.
+    #[method(name = "sendBundle")]
+    async fn send_bundle(&self, bundle: BundleRequest) -> RpcResult<H256>;
 }
@@ -19,8 +19,8 @@ pub use zksync_types::{
     Address, Transaction, H160, H256, H64, U256, U64,
 };
 use zksync_types::{
-    commitment::L1BatchCommitmentMode, protocol_version::ProtocolSemanticVersion, L1ChainId,
-    L2ChainId,
+    api::TransactionPreconfirmation, commitment::L1BatchCommitmentMode,
+    protocol_version::ProtocolSemanticVersion, L1ChainId, L2ChainId,
 };
 
 /// Token in the ZKsync network
@@ -299,6 +299,7 @@ fn topic_to_option<T>(topic: ethabi::Topic<T>) -> Option<Vec<T>> {
 pub enum PubSubResult {
     Header(BlockHeader),
     Log(Log),
+    Preconfirmation(TransactionPreconfirmation),
     TxHash(H256),
     Syncing(bool),
 }
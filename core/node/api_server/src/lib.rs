@@ -3,8 +3,8 @@
 #[macro_use]
 mod utils;
 pub mod execution_sandbox;
-pub mod healthcheck;
 pub mod node;
+pub mod snapshots_server;
 #[cfg(test)]
 mod testonly;
 pub mod tx_sender;
@@ -0,0 +1,69 @@
+use async_trait::async_trait;
+use zksync_config::configs::chain::AddressDenyListConfig;
+use zksync_node_framework::{
+    service::StopReceiver,
+    task::{Task, TaskId, TaskKind},
+    wiring_layer::{WiringError, WiringLayer},
+    IntoContext,
+};
+use zksync_vm_executor::deny_list::AddressScreener;
+
+use crate::tx_sender::deny_list::DenyListTask;
+
+/// Wiring layer for [`DenyListTask`] that maintains the address deny list used to screen L2 transactions.
+/// The list is loaded during wiring, so the node doesn't start if the initial load fails.
+///
+/// ## Adds resources
+///
+/// - `AddressScreener`
+///
+/// ## Adds tasks
+///
+/// - `DenyListTask`
+pub struct AddressDenyListLayer {
+    pub address_deny_list: AddressDenyListConfig,
+}
+
+#[derive(Debug, IntoContext)]
+pub struct Output {
+    pub address_screener: AddressScreener,
+    #[context(task)]
+    pub deny_list_task: DenyListTask,
+}
+
+#[async_trait]
+impl WiringLayer for AddressDenyListLayer {
+    type Input = ();
+    type Output = Output;
+
+    fn layer_name(&self) -> &'static str {
+        "address_deny_list_layer"
+    }
+
+    async fn wire(self, _input: Self::Input) -> Result<Self::Output, WiringError> {
+        let screen_touched_addresses = self.address_deny_list.screen_touched_addresses;
+        let mut deny_list_task = DenyListTask::from_config(self.address_deny_list);
+        deny_list_task.load_initial().await?;
+        let address_screener =
+            AddressScreener::new(deny_list_task.shared(), screen_touched_addresses);
+        Ok(Output {
+            address_screener,
+            deny_list_task,
+        })
+    }
+}
+
+#[async_trait]
+impl Task for DenyListTask {
+    fn id(&self) -> TaskId {
+        "deny_list_task".into()
+    }
+
+    fn kind(&self) -> TaskKind {
+        TaskKind::Task
+    }
+
+    async fn run(self: Box<Self>, stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        (*self).run(stop_receiver.0).await
+    }
+}
@@ -2,7 +2,9 @@ use std::sync::Arc;
 
 use serde::Serialize;
 use zksync_config::configs::api::HealthCheckConfig;
-use zksync_health_check::{node::AppHealthCheckResource, AppHealthCheck};
+use zksync_health_check::{
+    node::AppHealthCheckResource, server::HealthCheckHandle, AppHealthCheck,
+};
 use zksync_node_framework::{
     service::StopReceiver,
     task::{Task, TaskId, TaskKind},
@@ -11,8 +13,6 @@ use zksync_node_framework::{
 };
 use zksync_shared_metrics::metadata::{GitMetadata, RustMetadata, GIT_METRICS, RUST_METRICS};
 
-use crate::healthcheck::HealthCheckHandle;
-
 /// Full metadata of the compiled binary.
 #[derive(Debug, Serialize)]
 pub struct BinMetadata {
@@ -1,17 +1,21 @@
 pub use self::{
     allow_list::DeploymentAllowListLayer,
     caches::MempoolCacheLayer,
+    deny_list::AddressDenyListLayer,
     healtcheck_server::HealthCheckLayer,
     resources::{MempoolCacheResource, TxSenderResource, TxSinkResource},
     server::{Web3ServerLayer, Web3ServerOptionalConfig},
+    snapshots_server::SnapshotsServerLayer,
     tx_sender::{PostgresStorageCachesConfig, TxSenderLayer},
     tx_sink::{MasterPoolSinkLayer, ProxySinkLayer, WhitelistedMasterPoolSinkLayer},
 };
 
 mod allow_list;
 mod caches;
+mod deny_list;
 mod healtcheck_server;
 mod resources;
 mod server;
+mod snapshots_server;
 mod tx_sender;
 mod tx_sink;
@@ -1,5 +1,6 @@
 use std::{num::NonZeroU32, time::Duration};
 
+use anyhow::Context as _;
 use tokio::{sync::oneshot, task::JoinHandle};
 use zksync_circuit_breaker::{
     node::CircuitBreakersResource, replication_lag::ReplicationLagChecker,
@@ -15,14 +16,16 @@ use zksync_node_framework::{
     FromContext, IntoContext,
 };
 use zksync_shared_resources::{
-    api::{BridgeAddressesHandle, SyncState},
+    api::{BridgeAddressesHandle, Preconfirmations, SyncState},
     contracts::{
         L1ChainContractsResource, L1EcosystemContractsResource, L2ContractsResource,
         SettlementLayerContractsResource,
     },
 };
-use zksync_web3_decl::node::{
-    EthInterfaceResource, MainNodeClientResource, SettlementModeResource,
+use zksync_types::url::SensitiveUrl;
+use zksync_web3_decl::{
+    client::{Client, DynClient, L2},
+    node::{EthInterfaceResource, MainNodeClientResource, SettlementModeResource},
 };
 
 use self::sealed_l2_block::SealedL2BlockUpdaterTask;
@@ -50,6 +53,8 @@ pub struct Web3ServerOptionalConfig {
     pub pruning_info_refresh_interval: Option<Duration>,
     // Used by the external node.
     pub polling_interval: Option<Duration>,
+    /// URL of an archive node to proxy historical state queries for pruned blocks to.
+    pub archive_proxy_url: Option<SensitiveUrl>,
 }
 
 impl Web3ServerOptionalConfig {
@@ -100,6 +105,7 @@ enum Transport {
 /// - `PoolResource<ReplicaPool>`
 /// - `TxSenderResource`
 /// - `SyncState` (optional)
+/// - `Preconfirmations` (optional; provided by the state keeper if it runs on the same node)
 /// - `TreeApiClientResource` (optional)
 /// - `MempoolCacheResource`
 /// - `CircuitBreakersResource` (adds a circuit breaker)
@@ -124,6 +130,7 @@ pub struct Input {
     pub replica_pool: PoolResource<ReplicaPool>,
     pub tx_sender: TxSenderResource,
     pub sync_state: Option<SyncState>,
+    pub preconfirmations: Option<Preconfirmations>,
     pub tree_api_client: Option<TreeApiClientResource>,
     pub mempool_cache: MempoolCacheResource,
     #[context(default)]
@@ -189,7 +196,7 @@ impl WiringLayer for Web3ServerLayer {
         }
     }
 
-    async fn wire(self, input: Self::Input) -> Result<Self::Output, WiringError> {
+    async fn wire(mut self, input: Self::Input) -> Result<Self::Output, WiringError> {
         // Get required resources.
         let replica_resource_pool = input.replica_pool;
         let updaters_pool = replica_resource_pool.get_custom(1).await?;
@@ -209,6 +216,18 @@ impl WiringLayer for Web3ServerLayer {
                 .initial_settlement_mode
                 .settlement_layer_for_sending_txs(),
         );
+        let archive_client = self
+            .optional_config
+            .archive_proxy_url
+            .take()
+            .map(|url| {
+                let client = Client::http(url)
+                    .context("failed creating JSON-RPC client for archive node")?
+                    .for_network(internal_api_config.l2_chain_id.into())
+                    .build();
+                anyhow::Ok(Box::new(client) as Box<DynClient<L2>>)
+            })
+            .transpose()?;
         let sealed_l2_block_handle = SealedL2BlockNumber::default();
         let bridge_addresses = input.bridge_addresses;
         bridge_addresses
@@ -227,6 +246,9 @@ impl WiringLayer for Web3ServerLayer {
                 .with_extended_tracing(self.optional_config.with_extended_tracing)
                 .with_sealed_l2_block_handle(sealed_l2_block_handle)
                 .with_bridge_addresses_handle(bridge_addresses);
+        if let Some(preconfirmations) = input.preconfirmations {
+            api_builder = api_builder.with_preconfirmations(preconfirmations);
+        }
         if let Some(client) = tree_api_client {
             api_builder = api_builder.with_tree_api(client);
         }
@@ -244,6 +266,9 @@ impl WiringLayer for Web3ServerLayer {
         if let Some(main_node_client) = input.main_node_client {
             api_builder = api_builder.with_l2_l1_log_proof_handler(main_node_client.0)
         }
+        if let Some(archive_client) = archive_client {
+            api_builder = api_builder.with_archive_proxy(archive_client);
+        }
         let replication_lag_limit = self.optional_config.replication_lag_limit;
         api_builder = self.optional_config.apply(api_builder);
 
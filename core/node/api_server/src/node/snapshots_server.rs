@@ -0,0 +1,75 @@
+use std::{net::SocketAddr, path::PathBuf};
+
+use zksync_config::{configs::object_store::ObjectStoreMode, ObjectStoreConfig};
+use zksync_node_framework::{
+    service::StopReceiver,
+    task::{Task, TaskId},
+    wiring_layer::{WiringError, WiringLayer},
+    IntoContext,
+};
+use zksync_object_store::ObjectStoreFactory;
+
+use crate::snapshots_server::SnapshotsServer;
+
+/// Wiring layer for [`SnapshotsServer`] serving snapshot files from the specified local directory. The directory
+/// must have the [`ObjectStoreMode::FileBacked`] layout (e.g., be a local mirror of the snapshot recovery object store).
+///
+/// ## Adds tasks
+///
+/// - `SnapshotsServer`
+#[derive(Debug)]
+pub struct SnapshotsServerLayer {
+    snapshots_path: PathBuf,
+    bind_address: SocketAddr,
+}
+
+impl SnapshotsServerLayer {
+    pub fn new(snapshots_path: PathBuf, bind_address: SocketAddr) -> Self {
+        Self {
+            snapshots_path,
+            bind_address,
+        }
+    }
+}
+
+#[derive(Debug, IntoContext)]
+pub struct Output {
+    #[context(task)]
+    pub server: SnapshotsServer,
+}
+
+#[async_trait::async_trait]
+impl WiringLayer for SnapshotsServerLayer {
+    type Input = ();
+    type Output = Output;
+
+    fn layer_name(&self) -> &'static str {
+        "snapshots_server_layer"
+    }
+
+    async fn wire(self, (): Self::Input) -> Result<Self::Output, WiringError> {
+        let object_store_config = ObjectStoreConfig {
+            mode: ObjectStoreMode::FileBacked {
+                file_backed_base_path: self.snapshots_path,
+            },
+            max_retries: 0,
+            local_mirror_path: None,
+        };
+        let object_store = ObjectStoreFactory::new(object_store_config)
+            .create_store()
+            .await?;
+        let server = SnapshotsServer::new(object_store, self.bind_address);
+        Ok(Output { server })
+    }
+}
+
+#[async_trait::async_trait]
+impl Task for SnapshotsServer {
+    fn id(&self) -> TaskId {
+        "snapshots_server".into()
+    }
+
+    async fn run(self: Box<Self>, stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        (*self).run(stop_receiver.0).await
+    }
+}
@@ -13,9 +13,13 @@ use zksync_node_framework::{
 };
 use zksync_object_store::node::ObjectStoreResource;
 use zksync_shared_resources::contracts::{L2ContractsResource, SettlementLayerContractsResource};
-use zksync_state::{PostgresStorageCaches, PostgresStorageCachesTask};
+use zksync_state::{
+    PostgresStorageCaches, PostgresStorageCachesBudgetTask, PostgresStorageCachesTask,
+    StorageCachesBudgetConfig,
+};
 use zksync_state_keeper::node::ConditionalSealerResource;
 use zksync_types::{vm::FastVmMode, AccountTreeId, Address};
+use zksync_vm_executor::deny_list::AddressScreener;
 use zksync_web3_decl::{
     client::{DynClient, L2},
     jsonrpsee,
@@ -35,6 +39,9 @@ pub struct PostgresStorageCachesConfig {
     pub initial_writes_cache_size: u64,
     pub latest_values_cache_size: u64,
     pub latest_values_max_block_lag: u32,
+    /// Memory budget shared by all caches. If set, caches use the W-TinyLFU admission policy; otherwise,
+    /// caches use the LRU eviction policy and have fixed sizes.
+    pub budget: Option<StorageCachesBudgetConfig>,
 }
 
 /// Wiring layer for the `TxSender`.
@@ -46,6 +53,7 @@ pub struct PostgresStorageCachesConfig {
 /// - `PoolResource<ReplicaPool>`
 /// - `ConditionalSealerResource` (optional)
 /// - `FeeInputResource`
+/// - `AddressScreener` (optional)
 ///
 /// ## Adds resources
 ///
@@ -54,6 +62,7 @@ pub struct PostgresStorageCachesConfig {
 /// ## Adds tasks
 ///
 /// - `PostgresStorageCachesTask`
+/// - `PostgresStorageCachesBudgetTask` (optional)
 /// - `VmConcurrencyBarrierTask`
 /// - `WhitelistedTokensForAaUpdateTask` (optional)
 #[derive(Debug)]
@@ -77,6 +86,7 @@ pub struct Input {
     pub sl_contracts: SettlementLayerContractsResource,
     pub l2_contracts: L2ContractsResource,
     pub core_object_store: Option<ObjectStoreResource>,
+    pub address_screener: Option<AddressScreener>,
 }
 
 #[derive(Debug, IntoContext)]
@@ -87,6 +97,8 @@ pub struct Output {
     #[context(task)]
     postgres_storage_caches_task: Option<PostgresStorageCachesTaskWrapper>,
     #[context(task)]
+    postgres_storage_caches_budget_task: Option<PostgresStorageCachesBudgetTaskWrapper>,
+    #[context(task)]
     whitelisted_tokens_for_aa_update_task: Option<WhitelistedTokensForAaUpdateTask>,
 }
 
@@ -157,8 +169,12 @@ impl WiringLayer for TxSenderLayer {
             .postgres_storage_caches_config
             .initial_writes_cache_size;
         let values_capacity = self.postgres_storage_caches_config.latest_values_cache_size;
-        let mut storage_caches =
-            PostgresStorageCaches::new(factory_deps_capacity, initial_writes_capacity);
+        // Only adaptive caches can share a memory budget; otherwise, LRU caches are used.
+        let mut storage_caches = if self.postgres_storage_caches_config.budget.is_some() {
+            PostgresStorageCaches::adaptive(factory_deps_capacity, initial_writes_capacity)
+        } else {
+            PostgresStorageCaches::new(factory_deps_capacity, initial_writes_capacity)
+        };
 
         let postgres_storage_caches_task = if values_capacity > 0 {
             let update_task = storage_caches.configure_storage_values_cache(
@@ -171,6 +187,11 @@ impl WiringLayer for TxSenderLayer {
         } else {
             None
         };
+        let postgres_storage_caches_budget_task =
+            self.postgres_storage_caches_config.budget.map(|budget| {
+                let task = storage_caches.configure_memory_budget(budget, replica_pool.clone());
+                PostgresStorageCachesBudgetTaskWrapper(task)
+            });
 
         // Initialize `VmConcurrencyLimiter`.
         let (vm_concurrency_limiter, vm_concurrency_barrier) =
@@ -195,6 +216,9 @@ impl WiringLayer for TxSenderLayer {
         if let Some(sealer) = sealer {
             tx_sender = tx_sender.with_sealer(sealer);
         }
+        if let Some(screener) = input.address_screener {
+            tx_sender = tx_sender.with_address_screener(screener);
+        }
 
         // Add the task for updating the whitelisted tokens for the AA cache.
         let whitelisted_tokens_for_aa_update_task = if self.whitelisted_tokens_for_aa_cache {
@@ -230,6 +254,7 @@ impl WiringLayer for TxSenderLayer {
         Ok(Output {
             tx_sender: tx_sender.into(),
             postgres_storage_caches_task,
+            postgres_storage_caches_budget_task,
             vm_concurrency_barrier,
             whitelisted_tokens_for_aa_update_task,
         })
@@ -250,6 +275,20 @@ impl Task for PostgresStorageCachesTaskWrapper {
     }
 }
 
+#[derive(Debug)]
+struct PostgresStorageCachesBudgetTaskWrapper(PostgresStorageCachesBudgetTask);
+
+#[async_trait::async_trait]
+impl Task for PostgresStorageCachesBudgetTaskWrapper {
+    fn id(&self) -> TaskId {
+        "postgres_storage_caches_budget".into()
+    }
+
+    async fn run(self: Box<Self>, stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        self.0.run(stop_receiver.0).await
+    }
+}
+
 #[async_trait::async_trait]
 impl Task for VmConcurrencyBarrier {
     fn id(&self) -> TaskId {
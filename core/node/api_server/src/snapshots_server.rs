@@ -0,0 +1,173 @@
+//! HTTP server exposing snapshot files stored locally by the node.
+
+use std::{net::SocketAddr, sync::Arc};
+
+use anyhow::Context as _;
+use axum::{
+    extract::{Path, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+    routing::get,
+    Router,
+};
+use tokio::{net::TcpListener, sync::watch};
+use zksync_object_store::{Bucket, ObjectStore, ObjectStoreError};
+
+/// Prefix shared by all snapshot object keys.
+const SNAPSHOT_KEY_PREFIX: &str = "snapshot_l1_batch_";
+
+/// Serves snapshot files (storage log chunks and factory dependencies) stored locally by the node over HTTP.
+///
+/// Files are read from a local object store (in practice, the local mirror of the snapshot recovery object store,
+/// which contains files downloaded and verified during recovery), so the server never proxies requests to the remote
+/// store. Files are served using the same layout as in the object store, i.e. `GET /storage_logs_snapshots/{key}`.
+/// Thus, the server can be used as a source for the `HttpReadOnly` object store mode, which allows
+/// nodes to recover from snapshots served by other nodes rather than from the operator bucket.
+#[derive(Debug)]
+pub struct SnapshotsServer {
+    object_store: Arc<dyn ObjectStore>,
+    bind_address: SocketAddr,
+}
+
+impl SnapshotsServer {
+    pub fn new(object_store: Arc<dyn ObjectStore>, bind_address: SocketAddr) -> Self {
+        Self {
+            object_store,
+            bind_address,
+        }
+    }
+
+    fn router(object_store: Arc<dyn ObjectStore>) -> Router {
+        let path = format!("/{}/:key", Bucket::StorageSnapshot);
+        Router::new()
+            .route(&path, get(Self::get_snapshot_file))
+            .with_state(object_store)
+    }
+
+    async fn get_snapshot_file(
+        State(object_store): State<Arc<dyn ObjectStore>>,
+        Path(key): Path<String>,
+    ) -> Response {
+        // The server may be accessible by other nodes, so only snapshot objects can be requested.
+        let is_valid_key =
+            key.starts_with(SNAPSHOT_KEY_PREFIX) && !key.contains('/') && !key.contains("..");
+        if !is_valid_key {
+            return StatusCode::NOT_FOUND.into_response();
+        }
+
+        match object_store.get_raw(Bucket::StorageSnapshot, &key).await {
+            Ok(bytes) => {
+                ([(header::CONTENT_TYPE, "application/octet-stream")], bytes).into_response()
+            }
+            Err(ObjectStoreError::KeyNotFound(_)) => StatusCode::NOT_FOUND.into_response(),
+            Err(err) => {
+                tracing::warn!("Failed getting snapshot file `{key}` from object store: {err}");
+                if err.is_retriable() {
+                    StatusCode::SERVICE_UNAVAILABLE.into_response()
+                } else {
+                    StatusCode::INTERNAL_SERVER_ERROR.into_response()
+                }
+            }
+        }
+    }
+
+    pub async fn run(self, stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
+        let listener = TcpListener::bind(self.bind_address)
+            .await
+            .with_context(|| format!("Failed binding snapshots server to {}", self.bind_address))?;
+        Self::serve(listener, self.object_store, stop_receiver).await
+    }
+
+    async fn serve(
+        listener: TcpListener,
+        object_store: Arc<dyn ObjectStore>,
+        mut stop_receiver: watch::Receiver<bool>,
+    ) -> anyhow::Result<()> {
+        let local_addr = listener.local_addr()?;
+        tracing::info!("Starting snapshots server on {local_addr}");
+        axum::serve(listener, Self::router(object_store))
+            .with_graceful_shutdown(async move {
+                if stop_receiver.changed().await.is_err() {
+                    tracing::warn!("Stop request sender for snapshots server was dropped without sending a signal");
+                }
+                tracing::info!("Stop request received, snapshots server is shutting down");
+            })
+            .await
+            .context("Snapshots server failed")?;
+        tracing::info!("Snapshots server shut down");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_object_store::{HttpObjectStore, MockObjectStore};
+    use zksync_types::{
+        snapshots::{
+            SnapshotFactoryDependencies, SnapshotStorageLogsChunk, SnapshotStorageLogsStorageKey,
+        },
+        L1BatchNumber, H256,
+    };
+
+    use super::*;
+
+    #[tokio::test]
+    async fn serving_snapshot_files() {
+        let object_store = MockObjectStore::arc();
+        let factory_deps = SnapshotFactoryDependencies {
+            factory_deps: vec![],
+        };
+        object_store
+            .put(L1BatchNumber(1), &factory_deps)
+            .await
+            .unwrap();
+        object_store
+            .put_raw(Bucket::ProverJobs, "snapshot_l1_batch_1", vec![1, 2, 3])
+            .await
+            .unwrap();
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let local_addr = listener.local_addr().unwrap();
+        let (stop_sender, stop_receiver) = watch::channel(false);
+        let server_task = tokio::spawn(SnapshotsServer::serve(
+            listener,
+            object_store,
+            stop_receiver,
+        ));
+
+        let http_store: Arc<dyn ObjectStore> =
+            Arc::new(HttpObjectStore::new(format!("http://{local_addr}/")).unwrap());
+        let fetched_deps: SnapshotFactoryDependencies =
+            http_store.get(L1BatchNumber(1)).await.unwrap();
+        assert!(fetched_deps.factory_deps.is_empty());
+
+        let err = http_store
+            .get::<SnapshotFactoryDependencies>(L1BatchNumber(2))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ObjectStoreError::KeyNotFound(_)), "{err}");
+        let err = http_store
+            .get::<SnapshotStorageLogsChunk<H256>>(SnapshotStorageLogsStorageKey {
+                l1_batch_number: L1BatchNumber(1),
+                chunk_id: 0,
+            })
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ObjectStoreError::KeyNotFound(_)), "{err}");
+        // Objects from other buckets must not be served.
+        let err = http_store
+            .get_raw(Bucket::ProverJobs, "snapshot_l1_batch_1")
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ObjectStoreError::KeyNotFound(_)), "{err}");
+        // The store is read-only.
+        let err = http_store
+            .put(L1BatchNumber(1), &factory_deps)
+            .await
+            .unwrap_err();
+        assert!(!err.is_retriable(), "{err}");
+
+        stop_sender.send_replace(true);
+        server_task.await.unwrap().unwrap();
+    }
+}
@@ -0,0 +1,210 @@
+use std::{collections::HashSet, path::PathBuf, time::Duration};
+
+use anyhow::Context as _;
+use reqwest::Client;
+use serde::Deserialize;
+use tokio::sync::watch;
+use zksync_config::configs::chain::{AddressDenyListConfig, AddressDenyListSource};
+use zksync_types::{web3::keccak256, Address, H256};
+use zksync_vm_executor::deny_list::SharedDenyList;
+
+#[derive(Debug, Deserialize)]
+struct DenyListResponse {
+    addresses: Vec<Address>,
+}
+
+#[derive(Debug, Clone)]
+enum DenyListSource {
+    File(PathBuf),
+    Http { url: String, client: Client },
+}
+
+/// Task that periodically reloads the address deny list from a file or a remote HTTP source.
+///
+/// The list must be loaded with [`Self::load_initial()`] before serving; until then, and whenever
+/// reloads fail for longer than the configured max staleness, screening rejects all transactions.
+#[derive(Debug, Clone)]
+pub struct DenyListTask {
+    source: DenyListSource,
+    refresh_interval: Duration,
+    deny_list: SharedDenyList,
+    /// Tag of the currently loaded list version.
+    tag: Option<String>,
+}
+
+impl DenyListTask {
+    const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+    pub fn from_config(config: AddressDenyListConfig) -> Self {
+        let source = match config.source {
+            AddressDenyListSource::File { file_path } => DenyListSource::File(file_path),
+            AddressDenyListSource::Http { http_file_url } => DenyListSource::Http {
+                url: http_file_url,
+                client: Client::new(),
+            },
+        };
+        Self {
+            source,
+            refresh_interval: config.refresh_interval,
+            deny_list: SharedDenyList::refreshable(config.max_staleness),
+            tag: None,
+        }
+    }
+
+    pub fn shared(&self) -> SharedDenyList {
+        self.deny_list.clone()
+    }
+
+    /// Fetches the deny list. Returns `None` if the list is not modified since the last fetch.
+    async fn fetch(&self, current_tag: Option<&str>) -> anyhow::Result<Option<(Vec<u8>, String)>> {
+        match &self.source {
+            DenyListSource::File(path) => {
+                let raw = tokio::fs::read(path)
+                    .await
+                    .with_context(|| format!("failed reading deny list from {path:?}"))?;
+                // Use the content hash as a tag to detect changes.
+                let tag = format!("{:?}", H256(keccak256(&raw)));
+                if current_tag == Some(tag.as_str()) {
+                    return Ok(None);
+                }
+                Ok(Some((raw, tag)))
+            }
+            DenyListSource::Http { url, client } => {
+                let mut request = client.get(url).timeout(Self::REQUEST_TIMEOUT);
+                if let Some(etag) = current_tag {
+                    request = request.header("If-None-Match", etag);
+                }
+                let response = request.send().await?;
+                if response.status() == reqwest::StatusCode::NOT_MODIFIED {
+                    return Ok(None);
+                }
+                let response = response.error_for_status()?;
+                let etag = response
+                    .headers()
+                    .get("ETag")
+                    .and_then(|value| value.to_str().ok())
+                    .unwrap_or_default()
+                    .to_owned();
+                let raw = response.bytes().await?;
+                Ok(Some((raw.to_vec(), etag)))
+            }
+        }
+    }
+
+    /// Loads the deny list for the first time. Must succeed before the list is used for screening.
+    pub async fn load_initial(&mut self) -> anyhow::Result<()> {
+        let mut tag = None;
+        self.refresh(&mut tag)
+            .await
+            .context("failed loading initial deny list")?;
+        self.tag = tag;
+        Ok(())
+    }
+
+    async fn refresh(&self, tag: &mut Option<String>) -> anyhow::Result<()> {
+        let Some((raw, new_tag)) = self.fetch(tag.as_deref()).await? else {
+            tracing::debug!("Deny list unchanged");
+            self.deny_list.mark_refreshed();
+            return Ok(());
+        };
+        let list: DenyListResponse =
+            serde_json::from_slice(&raw).context("failed parsing deny list")?;
+        let addresses: HashSet<_> = list.addresses.into_iter().collect();
+
+        let mut lock = self.deny_list.writer().write().await;
+        let added = addresses.difference(&lock).count();
+        let removed = lock.difference(&addresses).count();
+        *lock = addresses;
+        *tag = (!new_tag.is_empty()).then_some(new_tag);
+        self.deny_list.mark_refreshed();
+        tracing::info!(
+            target: "address_screening_audit",
+            "Deny list updated: {} entries loaded ({added} added, {removed} removed)",
+            lock.len()
+        );
+        Ok(())
+    }
+
+    pub async fn run(mut self, mut stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
+        let mut tag = self.tag.take();
+
+        while !*stop_receiver.borrow_and_update() {
+            if let Err(err) = self.refresh(&mut tag).await {
+                tracing::warn!("Failed to refresh deny list: {err:#}");
+                if let Err(unavailable) = self.deny_list.check_available() {
+                    tracing::error!(
+                        "Transactions are rejected until the deny list is reloaded: {unavailable}"
+                    );
+                }
+            }
+            let _ = tokio::time::timeout(self.refresh_interval, stop_receiver.changed()).await;
+        }
+
+        tracing::info!("received a stop request; deny list task is shut down");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_vm_executor::deny_list::DenyListUnavailable;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn loading_deny_list_from_file() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("deny_list.json");
+        let denied = Address::repeat_byte(1);
+        let contents = serde_json::json!({ "addresses": [denied] });
+        tokio::fs::write(&path, contents.to_string()).await.unwrap();
+
+        let task = DenyListTask::from_config(AddressDenyListConfig {
+            source: AddressDenyListSource::File {
+                file_path: path.clone(),
+            },
+            refresh_interval: Duration::from_secs(60),
+            max_staleness: Duration::from_secs(300),
+            screen_touched_addresses: false,
+        });
+        let deny_list = task.shared();
+        assert_eq!(
+            deny_list.check_available(),
+            Err(DenyListUnavailable::NotLoaded)
+        );
+        let mut tag = None;
+        task.refresh(&mut tag).await.unwrap();
+        deny_list.check_available().unwrap();
+        assert!(deny_list.is_address_denied(&denied).await);
+        let first_tag = tag.clone().unwrap();
+
+        // Unchanged file should not change the tag.
+        task.refresh(&mut tag).await.unwrap();
+        assert_eq!(tag.as_deref(), Some(first_tag.as_str()));
+
+        let contents = serde_json::json!({ "addresses": [Address::repeat_byte(2)] });
+        tokio::fs::write(&path, contents.to_string()).await.unwrap();
+        task.refresh(&mut tag).await.unwrap();
+        assert_ne!(tag.as_deref(), Some(first_tag.as_str()));
+        assert!(!deny_list.is_address_denied(&denied).await);
+        assert!(deny_list.is_address_denied(&Address::repeat_byte(2)).await);
+    }
+
+    #[tokio::test]
+    async fn initial_load_is_required() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let mut task = DenyListTask::from_config(AddressDenyListConfig {
+            source: AddressDenyListSource::File {
+                file_path: dir.path().join("missing.json"),
+            },
+            refresh_interval: Duration::from_secs(60),
+            max_staleness: Duration::from_secs(300),
+            screen_touched_addresses: false,
+        });
+        task.load_initial().await.unwrap_err();
+        assert_eq!(
+            task.shared().check_available(),
+            Err(DenyListUnavailable::NotLoaded)
+        );
+    }
+}
@@ -5,9 +5,9 @@ use std::{
 
 use tokio::sync::Mutex;
 use zksync_dal::{transactions_dal::L2TxSubmissionResult, ConnectionPool, Core, CoreDal, DalError};
-use zksync_multivm::interface::tracer::ValidationTraces;
+use zksync_multivm::interface::{tracer::ValidationTraces, TransactionExecutionMetrics};
 use zksync_shared_metrics::{TxStage, APP_METRICS};
-use zksync_types::{l2::L2Tx, Address, Nonce, H256};
+use zksync_types::{bundle::BundleConstraints, l2::L2Tx, Address, Nonce, H256};
 
 use super::{tx_sink::TxSink, SubmitTxError};
 use crate::{execution_sandbox::SandboxExecutionOutput, web3::metrics::API_METRICS};
@@ -105,4 +105,57 @@ impl TxSink for MasterPoolSink {
 
         Ok(result)
     }
+
+    async fn submit_bundle(
+        &self,
+        bundle_hash: H256,
+        txs: &[L2Tx],
+        constraints: &BundleConstraints,
+    ) -> Result<(), SubmitTxError> {
+        let mut connection = self
+            .master_pool
+            .connection_tagged("api")
+            .await
+            .map_err(DalError::generalize)?;
+        let mut transaction = connection
+            .start_transaction()
+            .await
+            .map_err(DalError::generalize)?;
+
+        // Bundle transactions are not executed in the sandbox since they may depend on each other,
+        // so their execution metrics are unknown at this point.
+        for tx in txs {
+            let result = transaction
+                .transactions_dal()
+                .insert_transaction_l2(
+                    tx,
+                    TransactionExecutionMetrics::default(),
+                    ValidationTraces::default(),
+                )
+                .await
+                .map_err(DalError::generalize)?;
+            if result != L2TxSubmissionResult::Added {
+                return Err(SubmitTxError::InvalidBundle(format!(
+                    "transaction {:?} cannot be added to the mempool: {result}",
+                    tx.hash()
+                )));
+            }
+        }
+
+        let tx_hashes: Vec<_> = txs.iter().map(L2Tx::hash).collect();
+        let inserted = transaction
+            .bundles_dal()
+            .insert_bundle(bundle_hash, &tx_hashes, constraints)
+            .await
+            .map_err(DalError::generalize)?;
+        if !inserted {
+            return Err(SubmitTxError::InvalidBundle(format!(
+                "bundle {bundle_hash:?} is already submitted"
+            )));
+        }
+        transaction.commit().await.map_err(DalError::generalize)?;
+        APP_METRICS.processed_txs[&TxStage::Mempool(L2TxSubmissionResult::Added)]
+            .inc_by(txs.len() as u64);
+        Ok(())
+    }
 }
@@ -1,6 +1,7 @@
 //! Helper module to submit transactions into the ZKsync Network.
 
 use std::{
+    collections::{HashMap, HashSet},
     sync::{
         atomic::{AtomicUsize, Ordering},
         Arc,
@@ -35,6 +36,7 @@ use zksync_state_keeper::{
 };
 use zksync_types::{
     api::state_override::StateOverride,
+    bundle::{BundleConstraints, TransactionBundle},
     fee_model::BatchFeeInput,
     get_intrinsic_constants, h256_to_u256,
     l2::{error::TxCheckError::TxDuplication, L2Tx},
@@ -43,8 +45,9 @@ use zksync_types::{
     vm::FastVmMode,
     AccountTreeId, Address, L2ChainId, Nonce, ProtocolVersionId, Transaction, H160, H256, U256,
 };
-use zksync_vm_executor::oneshot::{
-    CallOrExecute, EstimateGas, MultiVmBaseSystemContracts, OneshotEnvParameters,
+use zksync_vm_executor::{
+    deny_list::{log_denied_transaction, AddressScreener},
+    oneshot::{CallOrExecute, EstimateGas, MultiVmBaseSystemContracts, OneshotEnvParameters},
 };
 
 pub(super) use self::{gas_estimation::BinarySearchKind, result::SubmitTxError};
@@ -54,6 +57,10 @@ use crate::execution_sandbox::{
     VmConcurrencyBarrier, VmConcurrencyLimiter, SANDBOX_METRICS,
 };
 
+/// Maximum number of transactions in a single bundle.
+const MAX_BUNDLE_LEN: usize = 16;
+
+pub mod deny_list;
 mod gas_estimation;
 pub mod master_pool_sink;
 pub mod proxy;
@@ -177,6 +184,8 @@ pub struct TxSenderBuilder {
     sealer: Option<Arc<dyn ConditionalSealer>>,
     /// Cache for tokens that are white-listed for AA.
     whitelisted_tokens_for_aa_cache: Option<Arc<RwLock<Vec<Address>>>>,
+    /// Screener used to reject transactions interacting with denied addresses.
+    address_screener: Option<AddressScreener>,
 }
 
 impl TxSenderBuilder {
@@ -191,6 +200,7 @@ impl TxSenderBuilder {
             tx_sink,
             sealer: None,
             whitelisted_tokens_for_aa_cache: None,
+            address_screener: None,
         }
     }
 
@@ -204,6 +214,11 @@ impl TxSenderBuilder {
         self
     }
 
+    pub fn with_address_screener(mut self, screener: AddressScreener) -> Self {
+        self.address_screener = Some(screener);
+        self
+    }
+
     pub fn build(
         self,
         batch_fee_input_provider: Arc<dyn BatchFeeModelInputProvider>,
@@ -242,6 +257,7 @@ impl TxSenderBuilder {
             whitelisted_tokens_for_aa_cache,
             sealer,
             executor,
+            address_screener: self.address_screener,
         }))
     }
 }
@@ -314,6 +330,8 @@ pub struct TxSenderInner {
     /// Batch sealer used to check whether transaction can be executed by the sequencer.
     pub(super) sealer: Arc<dyn ConditionalSealer>,
     pub(super) executor: SandboxExecutor,
+    /// Screener used to reject transactions interacting with denied addresses.
+    pub(super) address_screener: Option<AddressScreener>,
 }
 
 /// Health check details for [`TxSender`].
@@ -405,6 +423,7 @@ impl TxSender {
         let tx_hash = tx.hash();
         let stage_latency = SANDBOX_METRICS.start_tx_submit_stage(tx_hash, SubmitTxStage::Validate);
         self.validate_tx(&tx, block_args.protocol_version()).await?;
+        self.screen_txs(std::slice::from_ref(&tx)).await?;
         stage_latency.observe();
 
         let stage_latency = SANDBOX_METRICS.start_tx_submit_stage(tx_hash, SubmitTxStage::DryRun);
@@ -458,6 +477,26 @@ impl TxSender {
         if !execution_output.are_published_bytecodes_ok {
             return Err(SubmitTxError::FailedToPublishCompressedBytecodes);
         }
+        if let Some(screener) = &self.0.address_screener {
+            let touched_addresses = execution_output
+                .events
+                .iter()
+                .map(|event| event.address)
+                .chain(
+                    execution_output
+                        .write_logs
+                        .iter()
+                        .map(|log| *log.key.address()),
+                );
+            let denied = screener
+                .screen_touched_addresses(touched_addresses)
+                .await
+                .map_err(SubmitTxError::AddressScreeningUnavailable)?;
+            if let Some(denied) = denied {
+                log_denied_transaction(tx_hash, &denied, "submission");
+                return Err(SubmitTxError::AddressDenied(denied));
+            }
+        }
         let mut stage_latency =
             SANDBOX_METRICS.start_tx_submit_stage(tx_hash, SubmitTxStage::DbInsert);
         self.ensure_tx_executable(&tx.clone().into(), execution_output.metrics, true)?;
@@ -500,10 +539,141 @@ impl TxSender {
         }
     }
 
+    /// Submits an atomic transaction bundle. Unlike standalone transactions, bundle transactions are not executed
+    /// in the sandbox since later transactions may depend on the effects of earlier ones; only static validation
+    /// is performed. Returns the bundle hash.
+    pub(crate) async fn submit_bundle(
+        &self,
+        txs: Vec<L2Tx>,
+        constraints: BundleConstraints,
+        block_args: BlockArgs,
+    ) -> Result<H256, SubmitTxError> {
+        if txs.is_empty() || txs.len() > MAX_BUNDLE_LEN {
+            return Err(SubmitTxError::InvalidBundle(format!(
+                "bundle must contain 1 to {MAX_BUNDLE_LEN} transactions, got {}",
+                txs.len()
+            )));
+        }
+        if let (Some(min), Some(max)) = (constraints.min_timestamp, constraints.max_timestamp) {
+            if min > max {
+                return Err(SubmitTxError::InvalidBundle(format!(
+                    "min timestamp {min} is greater than max timestamp {max}"
+                )));
+            }
+        }
+        let tx_hashes: Vec<_> = txs.iter().map(L2Tx::hash).collect();
+        let unique_hashes: HashSet<_> = tx_hashes.iter().collect();
+        if unique_hashes.len() != tx_hashes.len() {
+            return Err(SubmitTxError::InvalidBundle(
+                "bundle contains duplicate transactions".to_owned(),
+            ));
+        }
+
+        self.validate_bundle_txs(&txs, block_args.protocol_version())
+            .await?;
+        self.screen_txs(&txs).await?;
+        let bundle_hash = TransactionBundle::compute_hash(tx_hashes);
+        self.0
+            .tx_sink
+            .submit_bundle(bundle_hash, &txs, &constraints)
+            .await?;
+        tracing::info!(
+            "Submitted bundle {bundle_hash:?} with {} transactions",
+            txs.len()
+        );
+        Ok(bundle_hash)
+    }
+
+    /// Screens transactions against the address deny list, if one is configured.
+    async fn screen_txs(&self, txs: &[L2Tx]) -> Result<(), SubmitTxError> {
+        let Some(screener) = &self.0.address_screener else {
+            return Ok(());
+        };
+        let txs: Vec<Transaction> = txs.iter().cloned().map(Into::into).collect();
+        let denied = screener
+            .screen_transactions(&txs)
+            .await
+            .map_err(SubmitTxError::AddressScreeningUnavailable)?;
+        if let Some((tx_hash, denied)) = denied {
+            log_denied_transaction(tx_hash, &denied, "submission");
+            return Err(SubmitTxError::AddressDenied(denied));
+        }
+        Ok(())
+    }
+
     async fn validate_tx(
         &self,
         tx: &L2Tx,
         protocol_version: ProtocolVersionId,
+    ) -> Result<(), SubmitTxError> {
+        self.validate_tx_fields(tx, protocol_version).await?;
+        // We still double-check the nonce manually
+        // to make sure that only the correct nonce is submitted and the transaction's hashes never repeat
+        self.validate_account_nonce(tx).await?;
+        // Even though without enough balance the tx will not pass anyway
+        // we check the user for enough balance explicitly here for better DevEx.
+        self.validate_enough_balance(tx).await?;
+        Ok(())
+    }
+
+    /// Validates bundled transactions. Each transaction goes through the same checks as a standalone one,
+    /// but nonces and balances are checked for the bundle as a whole since transactions from the same initiator
+    /// depend on each other: their nonces must be consecutive, and the initiator must be able to cover all of them.
+    async fn validate_bundle_txs(
+        &self,
+        txs: &[L2Tx],
+        protocol_version: ProtocolVersionId,
+    ) -> Result<(), SubmitTxError> {
+        let mut next_nonces = HashMap::<Address, u32>::new();
+        let mut required_balances = HashMap::<Address, (U256, U256)>::new();
+        for tx in txs {
+            self.validate_tx_fields(tx, protocol_version).await?;
+            // Bundled transactions are not executed in the sandbox, so only the execution-independent criteria apply.
+            self.ensure_tx_executable(
+                &tx.clone().into(),
+                TransactionExecutionMetrics::default(),
+                true,
+            )?;
+
+            let initiator = tx.initiator_account();
+            match next_nonces.get(&initiator) {
+                None => self.validate_account_nonce(tx).await?,
+                Some(&expected_nonce) if tx.nonce().0 != expected_nonce => {
+                    return Err(SubmitTxError::InvalidBundle(format!(
+                        "transaction {:?} has nonce {}, while the previous transaction from {initiator:?} \
+                         requires the next nonce to be {expected_nonce}",
+                        tx.hash(),
+                        tx.nonce().0
+                    )));
+                }
+                Some(_) => { /* nonce follows the previous transaction in the bundle */ }
+            }
+            next_nonces.insert(initiator, tx.nonce().0 + 1);
+
+            // The paymaster is expected to pay for the tx; whatever balance the user has, we don't care.
+            if tx.common_data.paymaster_params.paymaster == Address::default() {
+                let (max_fee, value) = required_balances.entry(initiator).or_default();
+                *max_fee += tx.common_data.fee.gas_limit * tx.common_data.fee.max_fee_per_gas;
+                *value += tx.execute.value;
+            }
+        }
+
+        for (initiator, (max_fee, value)) in required_balances {
+            let balance = self.get_balance(&initiator).await?;
+            if balance < max_fee + value {
+                return Err(SubmitTxError::NotEnoughBalanceForFeeValue(
+                    balance, max_fee, value,
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Validates transaction fields that do not depend on the initiator account state.
+    async fn validate_tx_fields(
+        &self,
+        tx: &L2Tx,
+        protocol_version: ProtocolVersionId,
     ) -> Result<(), SubmitTxError> {
         // This check is intended to ensure that the gas-related values will be safe to convert to u64 in the future computations.
         let max_gas = U256::from(u64::MAX);
@@ -572,13 +742,6 @@ impl TxSender {
         if tx.common_data.fee.gas_limit < min_gas_limit {
             return Err(SubmitTxError::IntrinsicGas);
         }
-
-        // We still double-check the nonce manually
-        // to make sure that only the correct nonce is submitted and the transaction's hashes never repeat
-        self.validate_account_nonce(tx).await?;
-        // Even though without enough balance the tx will not pass anyway
-        // we check the user for enough balance explicitly here for better DevEx.
-        self.validate_enough_balance(tx).await?;
         Ok(())
     }
 
@@ -1,6 +1,7 @@
 use thiserror::Error;
 use zksync_multivm::interface::ExecutionResult;
 use zksync_types::{l2::error::TxCheckError, Address, U256};
+use zksync_vm_executor::deny_list::{DeniedAddress, DenyListUnavailable};
 use zksync_web3_decl::error::EnrichedClientError;
 
 use crate::execution_sandbox::{SandboxExecutionError, ValidationError};
@@ -71,6 +72,14 @@ pub enum SubmitTxError {
     Internal(#[from] anyhow::Error),
     #[error("contract deployer address {0} is not in the allow list")]
     DeployerNotInAllowList(Address),
+    #[error("transaction bundles are not supported by this node")]
+    BundlesNotSupported,
+    #[error("invalid bundle: {0}")]
+    InvalidBundle(String),
+    #[error("transaction rejected by address screening: {0}")]
+    AddressDenied(DeniedAddress),
+    #[error("address screening is unavailable: {0}")]
+    AddressScreeningUnavailable(DenyListUnavailable),
 }
 
 impl SubmitTxError {
@@ -102,6 +111,10 @@ impl SubmitTxError {
             Self::ProxyError(_) => "proxy-error",
             Self::Internal(_) => "internal",
             Self::DeployerNotInAllowList(_) => "deployer-not-in-allow-list",
+            Self::BundlesNotSupported => "bundles-not-supported",
+            Self::InvalidBundle(_) => "invalid-bundle",
+            Self::AddressDenied(_) => "address-denied",
+            Self::AddressScreeningUnavailable(_) => "address-screening-unavailable",
         }
     }
 
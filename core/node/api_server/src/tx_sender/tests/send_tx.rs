@@ -183,6 +183,68 @@ async fn fee_validation_errors() {
     }
 }
 
+#[tokio::test]
+async fn bundle_validation_errors() {
+    let pool = ConnectionPool::<Core>::constrained_test_pool(1).await;
+    let mut storage = pool.connection().await.unwrap();
+    insert_genesis_batch(&mut storage, &GenesisParams::mock())
+        .await
+        .unwrap();
+
+    let l2_chain_id = L2ChainId::default();
+    let tx_executor = SandboxExecutor::mock(MockOneshotExecutor::default()).await;
+    let (tx_sender, _) = create_test_tx_sender(pool.clone(), l2_chain_id, tx_executor).await;
+    let fee_params_provider: &dyn BatchFeeModelInputProvider =
+        &MockBatchFeeParamsProvider::default();
+    let fee_input = fee_params_provider.get_batch_fee_input().await.unwrap();
+    let (base_fee, gas_per_pubdata) =
+        derive_base_fee_and_gas_per_pubdata(fee_input, ProtocolVersionId::latest().into());
+    let first_tx = create_l2_transaction(base_fee, gas_per_pubdata);
+    let mut second_tx = first_tx.clone();
+    second_tx.common_data.nonce = Nonce(1);
+
+    // The balance is enough to cover a single transaction, but not the entire bundle.
+    let max_fee = first_tx.common_data.fee.gas_limit * first_tx.common_data.fee.max_fee_per_gas;
+    StateBuilder::default()
+        .with_balance(first_tx.initiator_account(), max_fee)
+        .apply(storage)
+        .await;
+
+    tx_sender
+        .validate_bundle_txs(&[first_tx.clone()], ProtocolVersionId::latest())
+        .await
+        .unwrap();
+    let err = tx_sender
+        .validate_bundle_txs(
+            &[first_tx.clone(), second_tx.clone()],
+            ProtocolVersionId::latest(),
+        )
+        .await
+        .unwrap_err();
+    assert_matches!(
+        err,
+        SubmitTxError::NotEnoughBalanceForFeeValue(balance, fee, _) if balance == max_fee
+            && fee == max_fee * 2
+    );
+
+    // Nonces of transactions from the same initiator must be consecutive.
+    second_tx.common_data.nonce = Nonce(2);
+    let err = tx_sender
+        .validate_bundle_txs(&[first_tx.clone(), second_tx], ProtocolVersionId::latest())
+        .await
+        .unwrap_err();
+    assert_matches!(err, SubmitTxError::InvalidBundle(_));
+
+    // Transactions in a bundle are subject to the same fee checks as standalone ones.
+    let mut tx = first_tx;
+    tx.common_data.fee.max_fee_per_gas = 1.into();
+    let err = tx_sender
+        .validate_bundle_txs(&[tx], ProtocolVersionId::latest())
+        .await
+        .unwrap_err();
+    assert_matches!(err, SubmitTxError::MaxFeePerGasTooLow);
+}
+
 #[tokio::test]
 async fn sending_transfer() {
     let pool = ConnectionPool::<Core>::constrained_test_pool(1).await;
@@ -2,6 +2,7 @@ use zksync_dal::{transactions_dal::L2TxSubmissionResult, Connection, Core};
 use zksync_multivm::interface::tracer::ValidationTraces;
 use zksync_types::{
     api::{Transaction, TransactionDetails, TransactionId},
+    bundle::BundleConstraints,
     l2::L2Tx,
     Address, Nonce, H256,
 };
@@ -32,6 +33,17 @@ pub trait TxSink: std::fmt::Debug + Send + Sync + 'static {
         validation_traces: ValidationTraces,
     ) -> Result<L2TxSubmissionResult, SubmitTxError>;
 
+    /// Atomically propagates a transaction bundle to the mempool: either all bundle transactions are added,
+    /// or none of them. By default, bundles are not supported.
+    async fn submit_bundle(
+        &self,
+        _bundle_hash: H256,
+        _txs: &[L2Tx],
+        _constraints: &BundleConstraints,
+    ) -> Result<(), SubmitTxError> {
+        Err(SubmitTxError::BundlesNotSupported)
+    }
+
     /// Attempts to look up the pending nonce for the account in the sink-specific storage.
     /// By default, returns `Ok(None)`.
     async fn lookup_pending_nonce(
@@ -0,0 +1,203 @@
+//! Proxying historical state queries for pruned blocks to an archive node.
+
+use anyhow::Context as _;
+use zksync_crypto_primitives::hasher::blake2::Blake2Hasher;
+use zksync_dal::{Connection, Core, CoreDal, DalError};
+use zksync_merkle_tree::{TreeEntry, TreeEntryWithProof};
+use zksync_types::{
+    api::{Proof, StorageProof},
+    L1BatchNumber, L2BlockNumber, StorageKey, H256,
+};
+use zksync_web3_decl::{
+    client::{DynClient, L2},
+    error::{ClientRpcContext, Web3Error},
+    namespaces::{EthNamespaceClient, ZksNamespaceClient},
+};
+
+use super::metrics::{ArchiveProxyResult, API_METRICS};
+
+/// Proxy forwarding historical storage queries for pruned L2 blocks to an archive node.
+///
+/// Responses of the archive node are never trusted as is. Instead, the proxy requests a Merkle proof
+/// for the queried storage slot (via `zks_getProof`) and verifies it against the L1 batch root hash retained
+/// locally by the pruner. Since root hashes are only defined for L1 batches, only the state at the end of an L1 batch
+/// (i.e., at its last L2 block) can be verified. Queries for other L2 blocks in the batch are not proxied; even if
+/// the value reported by the archive node matches the one at the end of the batch, the slot may have had
+/// a different value in the middle of the batch.
+#[derive(Debug, Clone)]
+pub(crate) struct ArchiveProxy {
+    client: Box<DynClient<L2>>,
+}
+
+impl ArchiveProxy {
+    pub fn new(client: Box<DynClient<L2>>) -> Self {
+        Self {
+            client: client.for_component("archive_proxy"),
+        }
+    }
+
+    /// Returns the value of the storage slot at the specified (pruned) L2 block. Returns `Ok(None)` if the value
+    /// cannot be served by the proxy (i.e., the block is not in an L1 batch with a retained root hash, or it is not
+    /// the last block in its batch).
+    pub async fn get_historical_value(
+        &self,
+        connection: &mut Connection<'_, Core>,
+        storage_key: &StorageKey,
+        block_number: L2BlockNumber,
+    ) -> Result<Option<H256>, Web3Error> {
+        let retained_root_hash = connection
+            .pruning_dal()
+            .get_pruned_l1_batch_root_hash(block_number)
+            .await
+            .map_err(DalError::generalize)?;
+        let Some(retained_root_hash) = retained_root_hash else {
+            API_METRICS.archive_proxy_requests[&ArchiveProxyResult::NotRetained].inc();
+            return Ok(None);
+        };
+        if block_number != retained_root_hash.last_l2_block {
+            API_METRICS.archive_proxy_requests[&ArchiveProxyResult::Unverifiable].inc();
+            return Ok(None);
+        }
+        let l1_batch_number = retained_root_hash.l1_batch_number;
+
+        let proof = self
+            .client
+            .get_proof(
+                *storage_key.address(),
+                vec![*storage_key.key()],
+                l1_batch_number,
+            )
+            .rpc_context("get_proof")
+            .with_arg("address", storage_key.address())
+            .with_arg("key", storage_key.key())
+            .with_arg("l1_batch_number", &l1_batch_number)
+            .await?;
+        let proof = proof.with_context(|| {
+            format!("archive node has no proof for L1 batch #{l1_batch_number}")
+        })?;
+
+        let value = match verify_storage_proof(
+            storage_key,
+            proof,
+            l1_batch_number,
+            retained_root_hash.root_hash,
+        ) {
+            Ok(value) => value,
+            Err(err) => {
+                API_METRICS.archive_proxy_requests[&ArchiveProxyResult::VerificationFailed].inc();
+                tracing::warn!(
+                    "Failed verifying archive node response for {storage_key:?} at L2 block #{block_number}: {err:#}"
+                );
+                return Err(err.into());
+            }
+        };
+
+        API_METRICS.archive_proxy_requests[&ArchiveProxyResult::Served].inc();
+        Ok(Some(value))
+    }
+}
+
+/// Verifies a proof for a single storage slot returned by `zks_getProof` against a trusted root hash of an L1 batch,
+/// returning the proven storage value.
+fn verify_storage_proof(
+    storage_key: &StorageKey,
+    proof: Proof,
+    l1_batch_number: L1BatchNumber,
+    trusted_root_hash: H256,
+) -> anyhow::Result<H256> {
+    anyhow::ensure!(
+        proof.address == *storage_key.address(),
+        "archive node returned proof for unexpected address {:?}",
+        proof.address
+    );
+    let [proof] = <[StorageProof; 1]>::try_from(proof.storage_proof).map_err(|proofs| {
+        anyhow::anyhow!(
+            "archive node returned unexpected number of storage proofs: {}",
+            proofs.len()
+        )
+    })?;
+    anyhow::ensure!(
+        proof.key == *storage_key.key(),
+        "archive node returned proof for unexpected key {:?}",
+        proof.key
+    );
+    let entry = TreeEntryWithProof {
+        base: TreeEntry::new(storage_key.hashed_key_u256(), proof.index, proof.value),
+        merkle_path: proof.proof,
+    };
+    entry
+        .verify(&Blake2Hasher, trusted_root_hash)
+        .with_context(|| format!("invalid Merkle proof for L1 batch #{l1_batch_number}"))?;
+    Ok(proof.value)
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_merkle_tree::{MerkleTree, PatchSet};
+    use zksync_types::{AccountTreeId, Address};
+
+    use super::*;
+
+    fn build_tree_with_proof(storage_key: &StorageKey, value: H256) -> (H256, Proof) {
+        let mut tree = MerkleTree::new(PatchSet::default()).unwrap();
+        let other_key = StorageKey::new(AccountTreeId::new(Address::repeat_byte(2)), H256::zero());
+        tree.extend(vec![
+            TreeEntry::new(storage_key.hashed_key_u256(), 1, value),
+            TreeEntry::new(other_key.hashed_key_u256(), 2, H256::repeat_byte(0xff)),
+        ])
+        .unwrap();
+
+        let [entry] = tree
+            .entries_with_proofs(0, &[storage_key.hashed_key_u256()])
+            .unwrap()
+            .try_into()
+            .unwrap();
+        let proof = Proof {
+            address: *storage_key.address(),
+            storage_proof: vec![StorageProof {
+                key: *storage_key.key(),
+                proof: entry.merkle_path,
+                value: entry.base.value,
+                index: entry.base.leaf_index,
+            }],
+        };
+        (tree.latest_root_hash(), proof)
+    }
+
+    #[test]
+    fn verifying_storage_proof() {
+        let storage_key = StorageKey::new(
+            AccountTreeId::new(Address::repeat_byte(1)),
+            H256::from_low_u64_be(1),
+        );
+        let value = H256::repeat_byte(0x23);
+        let (root_hash, proof) = build_tree_with_proof(&storage_key, value);
+
+        let proven_value =
+            verify_storage_proof(&storage_key, proof.clone(), L1BatchNumber(0), root_hash).unwrap();
+        assert_eq!(proven_value, value);
+
+        let mut tampered_proof = proof.clone();
+        tampered_proof.storage_proof[0].value = H256::repeat_byte(0x24);
+        verify_storage_proof(&storage_key, tampered_proof, L1BatchNumber(0), root_hash)
+            .unwrap_err();
+
+        let other_key = StorageKey::new(
+            AccountTreeId::new(Address::repeat_byte(1)),
+            H256::from_low_u64_be(2),
+        );
+        verify_storage_proof(&other_key, proof.clone(), L1BatchNumber(0), root_hash).unwrap_err();
+
+        let mut proof_without_slots = proof.clone();
+        proof_without_slots.storage_proof.clear();
+        verify_storage_proof(
+            &storage_key,
+            proof_without_slots,
+            L1BatchNumber(0),
+            root_hash,
+        )
+        .unwrap_err();
+
+        verify_storage_proof(&storage_key, proof, L1BatchNumber(0), H256::zero()).unwrap_err();
+    }
+}
@@ -1,7 +1,7 @@
 use zksync_types::{
     api::{
         ChainAggProof, DataAvailabilityDetails, GatewayMigrationStatus, L1ToL2TxsStatus, TeeProof,
-        TransactionDetailedResult, TransactionExecutionInfo,
+        TransactionDetailedResult, TransactionExecutionInfo, TransactionPreconfirmation,
     },
     tee_types::TeeType,
     web3, L1BatchNumber, L2ChainId, H256,
@@ -24,6 +24,14 @@ impl UnstableNamespaceServer for UnstableNamespace {
             .map_err(|err| self.current_method().map_err(err))
     }
 
+    async fn transaction_preconfirmation(
+        &self,
+        hash: H256,
+    ) -> RpcResult<Option<TransactionPreconfirmation>> {
+        self.transaction_preconfirmation_impl(hash)
+            .map_err(|err| self.current_method().map_err(err))
+    }
+
     async fn tee_proofs(
         &self,
         l1_batch_number: L1BatchNumber,
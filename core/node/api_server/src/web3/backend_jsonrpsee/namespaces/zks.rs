@@ -2,8 +2,8 @@ use std::collections::HashMap;
 
 use zksync_types::{
     api::{
-        state_override::StateOverride, BlockDetails, BridgeAddresses, L1BatchDetails,
-        L2ToL1LogProof, Proof, ProtocolVersion, TransactionDetails,
+        state_override::StateOverride, BlockDetails, BridgeAddresses, BundleRequest,
+        L1BatchDetails, L2ToL1LogProof, Proof, ProtocolVersion, TransactionDetails,
     },
     fee::Fee,
     fee_model::{FeeParams, PubdataIndependentBatchFeeModelInput},
@@ -190,4 +190,10 @@ impl ZksNamespaceServer for ZksNamespace {
         self.get_l2_multicall3_impl()
             .map_err(|err| self.current_method().map_err(err))
     }
+
+    async fn send_bundle(&self, bundle: BundleRequest) -> RpcResult<H256> {
+        self.send_bundle_impl(bundle)
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
 }
@@ -229,6 +229,20 @@ struct Web3ConfigLabels {
     websocket_requests_per_minute_limit: Option<u32>,
 }
 
+/// Result of proxying a historical state query for a pruned block to the archive node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue)]
+#[metrics(rename_all = "snake_case")]
+pub(crate) enum ArchiveProxyResult {
+    /// Archive node response was verified and returned to the caller.
+    Served,
+    /// Root hash for the requested block is not retained locally, so the query cannot be proxied.
+    NotRetained,
+    /// Archive node response failed Merkle proof verification.
+    VerificationFailed,
+    /// Requested L2 block is not the last block in its L1 batch, so its state cannot be verified.
+    Unverifiable,
+}
+
 /// Roughly exponential buckets for the `web3_call_block_diff` metric. The distribution should be skewed towards lower values.
 const BLOCK_DIFF_BUCKETS: Buckets = Buckets::values(&[
     0.0, 1.0, 2.0, 3.0, 4.0, 5.0, 10.0, 20.0, 50.0, 100.0, 200.0, 500.0, 1_000.0,
@@ -271,6 +285,9 @@ pub(crate) struct ApiMetrics {
     pub ws_open_sessions: Gauge,
     /// Number of currently inserted into DB transactions.
     pub inflight_tx_submissions: Gauge,
+    /// Number of historical state queries for pruned blocks proxied to the archive node, grouped by the result.
+    #[metrics(labels = ["result"])]
+    pub archive_proxy_requests: LabeledFamily<ArchiveProxyResult, Counter>,
 }
 
 impl ApiMetrics {
@@ -423,6 +440,7 @@ pub enum SubscriptionType {
     Blocks,
     Txs,
     Logs,
+    Preconfirmations,
 }
 
 #[derive(Debug, Metrics)]
@@ -13,7 +13,7 @@ use zksync_config::configs::api::{MaxResponseSize, MaxResponseSizeOverrides};
 use zksync_dal::{helpers::wait_for_l1_batch, ConnectionPool, Core};
 use zksync_health_check::{HealthStatus, HealthUpdater, ReactiveHealthCheck};
 use zksync_metadata_calculator::api_server::TreeApiClient;
-use zksync_shared_resources::api::{BridgeAddressesHandle, SyncState};
+use zksync_shared_resources::api::{BridgeAddressesHandle, Preconfirmations, SyncState};
 use zksync_types::{try_stoppable, L2BlockNumber, StopContext};
 use zksync_web3_decl::{
     client::{DynClient, L2},
@@ -32,6 +32,7 @@ use zksync_web3_decl::{
 };
 
 use self::{
+    archive_proxy::ArchiveProxy,
     backend_jsonrpsee::{
         CorrelationMiddleware, LimitMiddleware, MetadataLayer, MethodTracer, ShutdownMiddleware,
         TrafficTracker,
@@ -51,6 +52,7 @@ use crate::{
     utils::AccountTypesCache,
 };
 
+mod archive_proxy;
 pub mod backend_jsonrpsee;
 pub mod mempool_cache;
 pub(super) mod metrics;
@@ -139,6 +141,8 @@ struct OptionalApiParams {
     extended_tracing: bool,
     pub_sub_events_sender: Option<mpsc::UnboundedSender<PubSubEvent>>,
     l2_l1_log_proof_handler: Option<Box<DynClient<L2>>>,
+    archive_proxy: Option<ArchiveProxy>,
+    preconfirmations: Option<Preconfirmations>,
 }
 
 /// Structure capable of spawning a configured Web3 API server along with all the required
@@ -306,6 +310,18 @@ impl ApiBuilder {
         self
     }
 
+    /// Enables proxying historical state queries for pruned blocks to the specified archive node.
+    /// Responses are verified using Merkle proofs against L1 batch root hashes retained by the pruner.
+    pub fn with_archive_proxy(mut self, archive_client: Box<DynClient<L2>>) -> Self {
+        self.optional.archive_proxy = Some(ArchiveProxy::new(archive_client));
+        self
+    }
+
+    pub fn with_preconfirmations(mut self, preconfirmations: Preconfirmations) -> Self {
+        self.optional.preconfirmations = Some(preconfirmations);
+        self
+    }
+
     // Intended for tests only.
     #[doc(hidden)]
     fn with_pub_sub_events(mut self, sender: mpsc::UnboundedSender<PubSubEvent>) -> Self {
@@ -391,6 +407,8 @@ impl ApiServer {
             bridge_addresses_handle: self.bridge_addresses_handle,
             tree_api: self.optional.tree_api,
             l2_l1_log_proof_handler: self.optional.l2_l1_log_proof_handler,
+            archive_proxy: self.optional.archive_proxy,
+            preconfirmations: self.optional.preconfirmations,
         })
     }
 
@@ -514,6 +532,12 @@ impl ApiServer {
                 self.polling_interval,
                 stop_receiver.clone(),
             ));
+            if let Some(preconfirmations) = &self.optional.preconfirmations {
+                tasks.push(pub_sub.spawn_preconfirmations_notifier(
+                    preconfirmations.clone(),
+                    stop_receiver.clone(),
+                ));
+            }
             Some(pub_sub)
         } else {
             None
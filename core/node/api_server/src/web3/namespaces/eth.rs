@@ -1,5 +1,5 @@
 use anyhow::Context as _;
-use zksync_dal::{CoreDal, DalError};
+use zksync_dal::{Connection, Core, CoreDal, DalError};
 use zksync_system_constants::DEFAULT_L2_TX_GAS_PER_PUBDATA_BYTE;
 use zksync_types::{
     api::{
@@ -7,9 +7,11 @@ use zksync_types::{
         Transaction, TransactionId, TransactionReceipt, TransactionVariant,
     },
     bytecode::{trim_padded_evm_bytecode, BytecodeHash, BytecodeMarker},
+    h256_to_u256,
     l2::{L2Tx, TransactionType},
     transaction_request::CallRequest,
     u256_to_h256,
+    utils::storage_key_for_standard_token_balance,
     web3::{self, Bytes, SyncInfo, SyncState},
     AccountTreeId, L2BlockNumber, StorageKey, H256, L2_BASE_TOKEN_ADDRESS, U256,
 };
@@ -173,7 +175,25 @@ impl EthNamespace {
         self.current_method().set_block_id(block_id);
 
         let mut connection = self.state.acquire_connection().await?;
-        let block_number = self.state.resolve_block(&mut connection, block_id).await?;
+        let block_number = match self.state.resolve_block(&mut connection, block_id).await {
+            Ok(number) => number,
+            Err(Web3Error::PrunedBlock(first_retained_block)) => {
+                let balance_key = storage_key_for_standard_token_balance(
+                    AccountTreeId::new(L2_BASE_TOKEN_ADDRESS),
+                    &address,
+                );
+                let balance = self
+                    .get_pruned_storage_value(
+                        &mut connection,
+                        &balance_key,
+                        block_id,
+                        first_retained_block,
+                    )
+                    .await?;
+                return Ok(h256_to_u256(balance));
+            }
+            Err(err) => return Err(err),
+        };
 
         let balance = connection
             .storage_web3_dal()
@@ -189,6 +209,27 @@ impl EthNamespace {
         Ok(balance)
     }
 
+    /// Gets a storage value for a pruned block from the archive node, if the archive proxy is enabled.
+    /// Only blocks specified by number can be proxied; otherwise, a pruned block error is returned.
+    async fn get_pruned_storage_value(
+        &self,
+        connection: &mut Connection<'_, Core>,
+        storage_key: &StorageKey,
+        block_id: BlockId,
+        first_retained_block: L2BlockNumber,
+    ) -> Result<H256, Web3Error> {
+        let (Some(archive_proxy), BlockId::Number(BlockNumber::Number(number))) =
+            (&self.state.archive_proxy, block_id)
+        else {
+            return Err(Web3Error::PrunedBlock(first_retained_block));
+        };
+        let block_number = RpcState::u64_to_block_number(number);
+        archive_proxy
+            .get_historical_value(connection, storage_key, block_number)
+            .await?
+            .ok_or(Web3Error::PrunedBlock(first_retained_block))
+    }
+
     fn set_block_diff(&self, block_number: L2BlockNumber) {
         let diff = self.state.last_sealed_l2_block.diff(block_number);
         self.current_method().set_block_diff(diff);
@@ -444,7 +485,20 @@ impl EthNamespace {
 
         let storage_key = StorageKey::new(AccountTreeId::new(address), u256_to_h256(idx));
         let mut connection = self.state.acquire_connection().await?;
-        let block_number = self.state.resolve_block(&mut connection, block_id).await?;
+        let block_number = match self.state.resolve_block(&mut connection, block_id).await {
+            Ok(number) => number,
+            Err(Web3Error::PrunedBlock(first_retained_block)) => {
+                return self
+                    .get_pruned_storage_value(
+                        &mut connection,
+                        &storage_key,
+                        block_id,
+                        first_retained_block,
+                    )
+                    .await;
+            }
+            Err(err) => return Err(err),
+        };
         self.set_block_diff(block_number);
         let value = connection
             .storage_web3_dal()
@@ -1,7 +1,7 @@
 use anyhow::Context as _;
 use zksync_dal::{CoreDal, DalError};
 use zksync_types::{
-    snapshots::{AllSnapshots, SnapshotHeader, SnapshotStorageLogsChunkMetadata},
+    snapshots::{AllSnapshots, SnapshotHeader},
     L1BatchNumber,
 };
 use zksync_web3_decl::error::Web3Error;
@@ -46,23 +46,11 @@ impl SnapshotsNamespace {
             return Ok(None);
         };
 
-        let snapshot_files = snapshot_metadata.storage_logs_filepaths;
-        let is_complete = snapshot_files.iter().all(Option::is_some);
-        if !is_complete {
+        if !snapshot_metadata.is_complete() {
             // We don't return incomplete snapshots via API.
             return Ok(None);
         }
 
-        let chunks = snapshot_files
-            .into_iter()
-            .enumerate()
-            .filter_map(|(chunk_id, filepath)| {
-                Some(SnapshotStorageLogsChunkMetadata {
-                    chunk_id: chunk_id as u64,
-                    filepath: filepath?,
-                })
-            })
-            .collect();
         let (_, l2_block_number) = storage_processor
             .blocks_dal()
             .get_l2_block_range_of_l1_batch(l1_batch_number)
@@ -70,12 +58,6 @@ impl SnapshotsNamespace {
             .map_err(DalError::generalize)?
             .with_context(|| format!("missing L2 blocks for L1 batch #{l1_batch_number}"))?;
 
-        Ok(Some(SnapshotHeader {
-            version: snapshot_metadata.version.into(),
-            l1_batch_number: snapshot_metadata.l1_batch_number,
-            l2_block_number,
-            storage_logs_chunks: chunks,
-            factory_deps_filepath: snapshot_metadata.factory_deps_filepath,
-        }))
+        Ok(SnapshotHeader::new(snapshot_metadata, l2_block_number))
     }
 }
@@ -11,7 +11,7 @@ use zksync_types::{
     api,
     api::{
         ChainAggProof, DataAvailabilityDetails, GatewayMigrationStatus, L1ToL2TxsStatus, TeeProof,
-        TransactionDetailedResult, TransactionExecutionInfo,
+        TransactionDetailedResult, TransactionExecutionInfo, TransactionPreconfirmation,
     },
     server_notification::GatewayMigrationState,
     tee_types::TeeType,
@@ -55,12 +55,36 @@ impl UnstableNamespace {
             .map(|execution_info| TransactionExecutionInfo { execution_info }))
     }
 
+    pub fn transaction_preconfirmation_impl(
+        &self,
+        hash: H256,
+    ) -> Result<Option<TransactionPreconfirmation>, Web3Error> {
+        let preconfirmations = self
+            .state
+            .preconfirmations
+            .as_ref()
+            .ok_or(Web3Error::MethodNotImplemented)?;
+        Ok(preconfirmations.get(hash))
+    }
+
     pub async fn get_tee_proofs_impl(
         &self,
         l1_batch_number: L1BatchNumber,
         tee_type: Option<TeeType>,
     ) -> Result<Vec<TeeProof>, Web3Error> {
         let mut storage = self.state.acquire_connection().await?;
+        // The batch status depends on attested proofs of all TEE types, not only the requested one.
+        let attested_proofs = storage
+            .tee_proof_generation_dal()
+            .get_attested_tee_proofs(l1_batch_number..=l1_batch_number)
+            .await
+            .map_err(DalError::generalize)?;
+        let batch_status = self.state.api_config.tee_quorum_policy.batch_status(
+            attested_proofs
+                .iter()
+                .map(|proof| (proof.tee_type, proof.pubkey.as_slice())),
+        );
+
         let proofs = storage
             .tee_proof_generation_dal()
             .get_tee_proofs(l1_batch_number, tee_type)
@@ -69,13 +93,14 @@ impl UnstableNamespace {
             .into_iter()
             .map(|proof| TeeProof {
                 l1_batch_number,
-                tee_type,
+                tee_type: proof.tee_type.parse().ok(),
                 pubkey: proof.pubkey,
                 signature: proof.signature,
                 proof: proof.proof,
                 proved_at: DateTime::<Utc>::from_naive_utc_and_offset(proof.updated_at, Utc),
                 status: proof.status,
                 attestation: proof.attestation,
+                batch_status: Some(batch_status),
             })
             .collect::<Vec<_>>();
 
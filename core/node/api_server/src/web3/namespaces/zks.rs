@@ -7,8 +7,8 @@ use zksync_mini_merkle_tree::MiniMerkleTree;
 use zksync_system_constants::DEFAULT_L2_TX_GAS_PER_PUBDATA_BYTE;
 use zksync_types::{
     api::{
-        state_override::StateOverride, BlockDetails, BridgeAddresses, L1BatchDetails,
-        L2ToL1LogProof, Proof, ProtocolVersion, StorageProof, TransactionDetails,
+        state_override::StateOverride, BlockDetails, BridgeAddresses, BundleRequest,
+        L1BatchDetails, L2ToL1LogProof, Proof, ProtocolVersion, StorageProof, TransactionDetails,
     },
     fee::Fee,
     fee_model::{FeeParams, PubdataIndependentBatchFeeModelInput},
@@ -32,7 +32,7 @@ use crate::{
     execution_sandbox::BlockArgs,
     tx_sender::BinarySearchKind,
     utils::open_readonly_transaction,
-    web3::{backend_jsonrpsee::MethodTracer, RpcState},
+    web3::{backend_jsonrpsee::MethodTracer, metrics::API_METRICS, RpcState},
 };
 
 #[derive(Debug)]
@@ -621,4 +621,30 @@ impl ZksNamespace {
             .await?
             .into_pubdata_independent())
     }
+
+    pub async fn send_bundle_impl(&self, bundle: BundleRequest) -> Result<H256, Web3Error> {
+        let mut connection = self.state.acquire_connection().await?;
+        let block_args = BlockArgs::pending(&mut connection).await?;
+        drop(connection);
+
+        let mut txs = Vec::with_capacity(bundle.transactions.len());
+        for tx_bytes in bundle.transactions {
+            let (mut tx, hash) = self
+                .state
+                .parse_transaction_bytes(&tx_bytes.0, &block_args)?;
+            tx.set_input(tx_bytes.0, hash);
+            txs.push(tx);
+        }
+
+        let submit_result = self
+            .state
+            .tx_sender
+            .submit_bundle(txs, bundle.constraints, block_args)
+            .await;
+        submit_result.map_err(|err| {
+            tracing::debug!("Send bundle error: {err}");
+            API_METRICS.submit_tx_error[&err.prom_error_code()].inc();
+            err.into()
+        })
+    }
 }
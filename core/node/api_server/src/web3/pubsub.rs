@@ -9,6 +9,7 @@ use tokio::{
 };
 use tracing::Instrument as _;
 use zksync_dal::{ConnectionPool, Core, CoreDal};
+use zksync_shared_resources::api::Preconfirmations;
 use zksync_types::{L2BlockNumber, H128, H256};
 use zksync_web3_decl::{
     jsonrpsee::{
@@ -239,11 +240,53 @@ impl PubSubNotifier {
     }
 }
 
+/// Forwards preconfirmations published by the state keeper to subscribers. Unlike [`PubSubNotifier`],
+/// doesn't poll Postgres since preconfirmations are pushed by the state keeper.
+#[derive(Debug)]
+struct PreconfirmationsNotifier {
+    sender: broadcast::Sender<Vec<PubSubResult>>,
+    preconfirmations: Preconfirmations,
+}
+
+impl PreconfirmationsNotifier {
+    async fn run(self, mut stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
+        let mut receiver = self.preconfirmations.subscribe();
+        loop {
+            let preconfirmation = tokio::select! {
+                res = receiver.recv() => res,
+                _ = stop_receiver.changed() => break,
+            };
+            match preconfirmation {
+                Ok(preconfirmation) => {
+                    let results = vec![PubSubResult::Preconfirmation(preconfirmation)];
+                    // Errors only on 0 receivers, which is fine.
+                    self.sender.send(results).ok();
+                    PUB_SUB_METRICS[&SubscriptionType::Preconfirmations]
+                        .broadcast_channel_len
+                        .set(self.sender.len());
+                }
+                Err(broadcast::error::RecvError::Lagged(message_count)) => {
+                    tracing::warn!(
+                        "pubsub_preconfirmations_notifier lagged behind by {message_count} preconfirmations"
+                    );
+                    PUB_SUB_METRICS[&SubscriptionType::Preconfirmations]
+                        .skipped_broadcast_messages
+                        .observe(message_count);
+                }
+                Err(broadcast::error::RecvError::Closed) => break,
+            }
+        }
+        tracing::info!("Stop request received, pubsub_preconfirmations_notifier is shutting down");
+        Ok(())
+    }
+}
+
 /// Subscription support for Web3 APIs.
 pub(super) struct EthSubscribe {
     blocks: broadcast::Sender<Vec<PubSubResult>>,
     transactions: broadcast::Sender<Vec<PubSubResult>>,
     logs: broadcast::Sender<Vec<PubSubResult>>,
+    preconfirmations: broadcast::Sender<Vec<PubSubResult>>,
     events_sender: Option<mpsc::UnboundedSender<PubSubEvent>>,
 }
 
@@ -252,11 +295,13 @@ impl EthSubscribe {
         let (blocks, _) = broadcast::channel(BROADCAST_CHANNEL_CAPACITY);
         let (transactions, _) = broadcast::channel(BROADCAST_CHANNEL_CAPACITY);
         let (logs, _) = broadcast::channel(BROADCAST_CHANNEL_CAPACITY);
+        let (preconfirmations, _) = broadcast::channel(BROADCAST_CHANNEL_CAPACITY);
 
         Self {
             blocks,
             transactions,
             logs,
+            preconfirmations,
             events_sender: None,
         }
     }
@@ -405,6 +450,22 @@ impl EthSubscribe {
                     Some(SubscriptionType::Logs)
                 }
             }
+            "preconfirmations" => {
+                let Ok(sink) = pending_sink.accept().await else {
+                    return;
+                };
+                let preconfirmations_rx = self.preconfirmations.subscribe();
+                tokio::spawn(
+                    Self::run_subscriber(
+                        sink,
+                        SubscriptionType::Preconfirmations,
+                        preconfirmations_rx,
+                        None,
+                    )
+                    .in_current_span(),
+                );
+                Some(SubscriptionType::Preconfirmations)
+            }
             "syncing" => {
                 let Ok(sink) = pending_sink.accept().await else {
                     return;
@@ -470,6 +531,20 @@ impl EthSubscribe {
         notifier_tasks.push(notifier_task);
         notifier_tasks
     }
+
+    /// Spawns a task forwarding preconfirmations published by the state keeper to subscribers.
+    /// This should be called at most once per instance.
+    pub fn spawn_preconfirmations_notifier(
+        &self,
+        preconfirmations: Preconfirmations,
+        stop_receiver: watch::Receiver<bool>,
+    ) -> JoinHandle<anyhow::Result<()>> {
+        let notifier = PreconfirmationsNotifier {
+            sender: self.preconfirmations.clone(),
+            preconfirmations,
+        };
+        tokio::spawn(notifier.run(stop_receiver))
+    }
 }
 
 #[async_trait::async_trait]
@@ -25,11 +25,11 @@ use zksync_config::{
 };
 use zksync_dal::{Connection, ConnectionPool, Core, CoreDal, DalError};
 use zksync_metadata_calculator::api_server::TreeApiClient;
-use zksync_shared_resources::api::{BridgeAddressesHandle, SyncState};
+use zksync_shared_resources::api::{BridgeAddressesHandle, Preconfirmations, SyncState};
 use zksync_types::{
     api, commitment::L1BatchCommitmentMode, l2::L2Tx, settlement::SettlementLayer,
-    transaction_request::CallRequest, Address, L1BatchNumber, L1ChainId, L2BlockNumber, L2ChainId,
-    H256, U256, U64,
+    tee_types::TeeQuorumPolicy, transaction_request::CallRequest, Address, L1BatchNumber,
+    L1ChainId, L2BlockNumber, L2ChainId, H256, U256, U64,
 };
 use zksync_web3_decl::{
     client::{DynClient, L2},
@@ -38,6 +38,7 @@ use zksync_web3_decl::{
 };
 
 use super::{
+    archive_proxy::ArchiveProxy,
     backend_jsonrpsee::MethodTracer,
     mempool_cache::MempoolCache,
     metrics::{FilterType, FILTER_METRICS},
@@ -121,6 +122,7 @@ pub struct InternalApiConfigBase {
     pub fee_history_limit: u64,
     pub filters_disabled: bool,
     pub l1_to_l2_txs_paused: bool,
+    pub tee_quorum_policy: TeeQuorumPolicy,
 }
 
 impl InternalApiConfigBase {
@@ -139,6 +141,7 @@ impl InternalApiConfigBase {
             fee_history_limit: web3_config.fee_history_limit,
             filters_disabled: web3_config.filters_disabled,
             l1_to_l2_txs_paused: false,
+            tee_quorum_policy: TeeQuorumPolicy::default(),
         }
     }
 
@@ -146,6 +149,11 @@ impl InternalApiConfigBase {
         self.l1_to_l2_txs_paused = l1_to_l2_txs_paused;
         self
     }
+
+    pub fn with_tee_quorum_policy(mut self, policy: TeeQuorumPolicy) -> Self {
+        self.tee_quorum_policy = policy;
+        self
+    }
 }
 
 /// Configuration values for the API.
@@ -179,6 +187,8 @@ pub struct InternalApiConfig {
     pub l2_multicall3: Option<Address>,
     pub l1_to_l2_txs_paused: bool,
     pub settlement_layer: Option<SettlementLayer>,
+    /// Policy used to determine TEE verification status of L1 batches.
+    pub tee_quorum_policy: TeeQuorumPolicy,
 }
 
 impl InternalApiConfig {
@@ -224,6 +234,7 @@ impl InternalApiConfig {
             l2_multicall3: l2_contracts.multicall3,
             l1_to_l2_txs_paused: base.l1_to_l2_txs_paused,
             settlement_layer,
+            tee_quorum_policy: base.tee_quorum_policy,
         }
     }
 
@@ -304,6 +315,9 @@ pub(crate) struct RpcState {
     pub(super) last_sealed_l2_block: SealedL2BlockNumber,
     pub(super) bridge_addresses_handle: BridgeAddressesHandle,
     pub(super) l2_l1_log_proof_handler: Option<Box<DynClient<L2>>>,
+    /// Proxy for historical state queries for pruned blocks.
+    pub(super) archive_proxy: Option<ArchiveProxy>,
+    pub(super) preconfirmations: Option<Preconfirmations>,
 }
 
 impl RpcState {
@@ -108,6 +108,7 @@ pub struct TestServerBuilder {
     tx_executor: MockOneshotExecutor,
     executor_options: Option<SandboxExecutorOptions>,
     method_tracer: Arc<MethodTracer>,
+    archive_client: Option<Box<DynClient<L2>>>,
 }
 
 impl TestServerBuilder {
@@ -119,6 +120,7 @@ impl TestServerBuilder {
             tx_executor: MockOneshotExecutor::default(),
             executor_options: None,
             method_tracer: Arc::default(),
+            archive_client: None,
         }
     }
 
@@ -142,6 +144,13 @@ impl TestServerBuilder {
         self
     }
 
+    /// Sets an archive node client used to serve historical state for pruned blocks.
+    #[must_use]
+    pub fn with_archive_proxy(mut self, archive_client: Box<DynClient<L2>>) -> Self {
+        self.archive_client = Some(archive_client);
+        self
+    }
+
     /// Builds an HTTP server.
     pub async fn build_http(self, stop_receiver: watch::Receiver<bool>) -> ApiServerHandles {
         self.spawn_server(ApiTransportLabel::Http, None, stop_receiver)
@@ -175,6 +184,7 @@ impl TestServerBuilder {
             pool,
             api_config,
             method_tracer,
+            archive_client,
         } = self;
 
         let tx_executor = if let Some(options) = executor_options {
@@ -208,6 +218,11 @@ impl TestServerBuilder {
                 builder
             }
         };
+        let server_builder = if let Some(archive_client) = archive_client {
+            server_builder.with_archive_proxy(archive_client)
+        } else {
+            server_builder
+        };
         let server_handles = server_builder
             .with_polling_interval(POLL_INTERVAL)
             .with_tx_sender(tx_sender)
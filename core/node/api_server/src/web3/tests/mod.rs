@@ -13,6 +13,7 @@ use zksync_config::{
 };
 use zksync_contracts::BaseSystemContracts;
 use zksync_dal::{Connection, ConnectionPool, CoreDal};
+use zksync_merkle_tree::{MerkleTree, PatchSet, TreeEntry};
 use zksync_multivm::interface::{
     tracer::ValidationTraces, TransactionExecutionMetrics, TransactionExecutionResult, VmEvent,
 };
@@ -31,6 +32,7 @@ use zksync_types::{
         testonly::{PADDED_EVM_BYTECODE, PROCESSED_EVM_BYTECODE},
         BytecodeHash,
     },
+    commitment::L1BatchMetadata,
     fee_model::{BatchFeeInput, FeeParams},
     get_deployer_key, get_nonce_key,
     settlement::SettlementLayer,
@@ -44,7 +46,7 @@ use zksync_types::{
 };
 use zksync_vm_executor::oneshot::MockOneshotExecutor;
 use zksync_web3_decl::{
-    client::{Client, DynClient, L2},
+    client::{Client, DynClient, MockClient, L2},
     jsonrpsee::{
         core::{client::ClientT, params::BatchRequestBuilder, ClientError},
         http_client::HttpClient,
@@ -154,6 +156,11 @@ trait HttpTest: Send + Sync {
         Arc::default()
     }
 
+    /// Archive node client used to serve historical state for pruned blocks. Not set by default.
+    fn archive_client(&self) -> Option<Box<DynClient<L2>>> {
+        None
+    }
+
     async fn test(&self, client: &DynClient<L2>, pool: &ConnectionPool<Core>)
         -> anyhow::Result<()>;
 
@@ -173,11 +180,17 @@ enum StorageInitialization {
         logs: Vec<StorageLog>,
         factory_deps: HashMap<H256, Vec<u8>>,
     },
+    /// Genesis followed by L1 batch #1 with L2 blocks #1..=#3 (hard-pruned) and L1 batch #2 with L2 block #4.
+    PrunedBatch {
+        root_hash: H256,
+    },
 }
 
 impl StorageInitialization {
     const SNAPSHOT_RECOVERY_BATCH: L1BatchNumber = L1BatchNumber(23);
     const SNAPSHOT_RECOVERY_BLOCK: L2BlockNumber = L2BlockNumber(23);
+    const PRUNED_BATCH: L1BatchNumber = L1BatchNumber(1);
+    const PRUNED_BATCH_LAST_BLOCK: L2BlockNumber = L2BlockNumber(3);
 
     const fn genesis() -> Self {
         Self::Genesis {
@@ -259,6 +272,34 @@ impl StorageInitialization {
                 store_l2_block(storage, Self::SNAPSHOT_RECOVERY_BLOCK + 1, &[]).await?;
                 seal_l1_batch(storage, Self::SNAPSHOT_RECOVERY_BATCH + 1).await?;
             }
+            Self::PrunedBatch { root_hash } => {
+                let params = GenesisParams::load_genesis_params(mock_genesis_config())?;
+                insert_genesis_batch(storage, &params).await?;
+
+                for number in 1..=Self::PRUNED_BATCH_LAST_BLOCK.0 {
+                    store_l2_block(storage, L2BlockNumber(number), &[]).await?;
+                }
+                let mut metadata = create_l1_batch_metadata(Self::PRUNED_BATCH.0);
+                metadata.root_hash = root_hash;
+                seal_l1_batch_with_metadata(storage, Self::PRUNED_BATCH, metadata).await?;
+                store_l2_block(storage, Self::PRUNED_BATCH_LAST_BLOCK + 1, &[]).await?;
+                seal_l1_batch(storage, Self::PRUNED_BATCH + 1).await?;
+
+                let mut pruning_dal = storage.pruning_dal();
+                pruning_dal
+                    .insert_soft_pruning_log(Self::PRUNED_BATCH, Self::PRUNED_BATCH_LAST_BLOCK)
+                    .await?;
+                pruning_dal
+                    .hard_prune_batches_range(Self::PRUNED_BATCH, Self::PRUNED_BATCH_LAST_BLOCK)
+                    .await?;
+                pruning_dal
+                    .insert_hard_pruning_log(
+                        Self::PRUNED_BATCH,
+                        Self::PRUNED_BATCH_LAST_BLOCK,
+                        root_hash,
+                    )
+                    .await?;
+            }
         }
         Ok(())
     }
@@ -293,6 +334,9 @@ async fn test_http_server(test: impl HttpTest) {
     if let Some(executor_options) = test.executor_options() {
         server_builder = server_builder.with_executor_options(executor_options);
     }
+    if let Some(archive_client) = test.archive_client() {
+        server_builder = server_builder.with_archive_proxy(archive_client);
+    }
     let mut server_handles = server_builder.build_http(stop_receiver).await;
 
     let local_addr = server_handles.wait_until_ready().await;
@@ -353,6 +397,14 @@ async fn open_l1_batch(
 async fn seal_l1_batch(
     storage: &mut Connection<'_, Core>,
     number: L1BatchNumber,
+) -> anyhow::Result<()> {
+    seal_l1_batch_with_metadata(storage, number, create_l1_batch_metadata(number.0)).await
+}
+
+async fn seal_l1_batch_with_metadata(
+    storage: &mut Connection<'_, Core>,
+    number: L1BatchNumber,
+    metadata: L1BatchMetadata,
 ) -> anyhow::Result<()> {
     let header = create_l1_batch(number.0);
     storage.blocks_dal().insert_mock_l1_batch(&header).await?;
@@ -360,7 +412,6 @@ async fn seal_l1_batch(
         .blocks_dal()
         .mark_l2_blocks_as_executed_in_l1_batch(number)
         .await?;
-    let metadata = create_l1_batch_metadata(number.0);
     storage
         .blocks_dal()
         .save_l1_batch_tree_data(number, &metadata.tree_data())
@@ -682,6 +733,139 @@ async fn storage_access_with_snapshot_recovery() {
     test_http_server(StorageAccessWithSnapshotRecovery).await;
 }
 
+/// Storage access for pruned blocks served by a mock archive node.
+#[derive(Debug)]
+struct StorageAccessForPrunedBlocks {
+    root_hash: H256,
+    proofs: HashMap<StorageKey, api::Proof>,
+}
+
+impl StorageAccessForPrunedBlocks {
+    const ADDRESS: Address = Address::repeat_byte(1);
+    const BALANCE: u64 = 123;
+    const SLOT_VALUE: H256 = H256::repeat_byte(0xff);
+
+    fn slot_key() -> StorageKey {
+        StorageKey::new(AccountTreeId::new(Self::ADDRESS), H256::zero())
+    }
+
+    fn new() -> Self {
+        let entries = [
+            (
+                storage_key_for_eth_balance(&Self::ADDRESS),
+                H256::from_low_u64_be(Self::BALANCE),
+            ),
+            (Self::slot_key(), Self::SLOT_VALUE),
+        ];
+        let mut tree = MerkleTree::new(PatchSet::default()).unwrap();
+        let tree_entries = entries.iter().zip(1..).map(|((key, value), leaf_index)| {
+            TreeEntry::new(key.hashed_key_u256(), leaf_index, *value)
+        });
+        tree.extend(tree_entries.collect()).unwrap();
+
+        let proofs = entries.iter().map(|(key, _)| {
+            let [entry] = tree
+                .entries_with_proofs(0, &[key.hashed_key_u256()])
+                .unwrap()
+                .try_into()
+                .unwrap();
+            let proof = api::Proof {
+                address: *key.address(),
+                storage_proof: vec![api::StorageProof {
+                    key: *key.key(),
+                    proof: entry.merkle_path,
+                    value: entry.base.value,
+                    index: entry.base.leaf_index,
+                }],
+            };
+            (*key, proof)
+        });
+        Self {
+            root_hash: tree.latest_root_hash(),
+            proofs: proofs.collect(),
+        }
+    }
+}
+
+#[async_trait]
+impl HttpTest for StorageAccessForPrunedBlocks {
+    fn storage_initialization(&self) -> StorageInitialization {
+        StorageInitialization::PrunedBatch {
+            root_hash: self.root_hash,
+        }
+    }
+
+    fn archive_client(&self) -> Option<Box<DynClient<L2>>> {
+        let proofs = self.proofs.clone();
+        let client = MockClient::builder(L2::default())
+            .method(
+                "zks_getProof",
+                move |address: Address, keys: Vec<H256>, l1_batch_number: L1BatchNumber| {
+                    assert_eq!(l1_batch_number, StorageInitialization::PRUNED_BATCH);
+                    assert_eq!(keys.len(), 1);
+                    let key = StorageKey::new(AccountTreeId::new(address), keys[0]);
+                    Ok(proofs.get(&key).cloned())
+                },
+            )
+            .method(
+                "eth_getStorageAt",
+                |address: Address, idx: U256, _block: Option<api::BlockIdVariant>| {
+                    // Malicious archive node reporting end-of-batch values for all blocks in the batch; the slot
+                    // may well have had a different value in the middle of the batch.
+                    let key = StorageKey::new(AccountTreeId::new(address), u256_to_h256(idx));
+                    Ok(if key == Self::slot_key() {
+                        Self::SLOT_VALUE
+                    } else {
+                        H256::from_low_u64_be(Self::BALANCE)
+                    })
+                },
+            )
+            .build();
+        Some(Box::new(client))
+    }
+
+    async fn test(
+        &self,
+        client: &DynClient<L2>,
+        _pool: &ConnectionPool<Core>,
+    ) -> anyhow::Result<()> {
+        let first_retained_block = StorageInitialization::PRUNED_BATCH_LAST_BLOCK + 1;
+        // The first block in the pruned batch, a block in the middle of the batch, and the last block in the batch.
+        for number in 1..=StorageInitialization::PRUNED_BATCH_LAST_BLOCK.0 {
+            let block = Some(api::BlockIdVariant::BlockNumber(number.into()));
+            if number == StorageInitialization::PRUNED_BATCH_LAST_BLOCK.0 {
+                let balance = client.get_balance(Self::ADDRESS, block).await?;
+                assert_eq!(balance, Self::BALANCE.into());
+                let value = client
+                    .get_storage_at(Self::ADDRESS, 0.into(), block)
+                    .await?;
+                assert_eq!(value, Self::SLOT_VALUE);
+            } else {
+                // State in the middle of the batch cannot be verified, so values reported by the archive node
+                // must not be served (even though they match the proven values at the end of the batch).
+                let error = client.get_balance(Self::ADDRESS, block).await.unwrap_err();
+                assert_pruned_block_error(&error, first_retained_block);
+                let error = client
+                    .get_storage_at(Self::ADDRESS, 0.into(), block)
+                    .await
+                    .unwrap_err();
+                assert_pruned_block_error(&error, first_retained_block);
+            }
+        }
+
+        // Blocks specified by tag or hash cannot be proxied.
+        let block = Some(api::BlockIdVariant::BlockNumber(api::BlockNumber::Earliest));
+        let error = client.get_balance(Self::ADDRESS, block).await.unwrap_err();
+        assert_pruned_block_error(&error, first_retained_block);
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn storage_access_for_pruned_blocks_via_archive_node() {
+    test_http_server(StorageAccessForPrunedBlocks::new()).await;
+}
+
 #[derive(Debug)]
 struct TransactionCountTest;
 
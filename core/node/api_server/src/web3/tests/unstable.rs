@@ -1,6 +1,6 @@
 //! Tests for the `unstable` Web3 namespace.
 
-use zksync_types::tee_types::TeeType;
+use zksync_types::tee_types::{TeeBatchStatus, TeeType};
 use zksync_web3_decl::namespaces::UnstableNamespaceClient;
 
 use super::*;
@@ -53,6 +53,14 @@ impl HttpTest for GetTeeProofsTest {
         assert!(proof.signature.as_ref() == Some(&signature));
         assert!(proof.proof.as_ref() == Some(&proof_vec));
         assert!(proof.attestation.as_ref() == Some(&attestation));
+        // The default quorum policy requires a single proof of any TEE type.
+        assert_eq!(proof.batch_status, Some(TeeBatchStatus::Verified));
+
+        let proofs = client.tee_proofs(batch_no, None).await?;
+        assert_eq!(proofs.len(), 1);
+        assert_eq!(proofs[0].tee_type, Some(tee_type));
+        let proofs = client.tee_proofs(batch_no, Some(TeeType::Tdx)).await?;
+        assert!(proofs.is_empty());
 
         Ok(())
     }
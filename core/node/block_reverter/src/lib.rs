@@ -1,7 +1,7 @@
 use std::{
     path::{Path, PathBuf},
     sync::Arc,
-    time::Duration,
+    time::{Duration, Instant},
 };
 
 use anyhow::Context as _;
@@ -13,7 +13,7 @@ use zksync_dal::{ConnectionPool, Core, CoreDal};
 // Public re-export to simplify the API use.
 pub use zksync_eth_client as eth_client;
 use zksync_eth_client::{BoundEthInterface, CallFunctionArgs, EthInterface, Options};
-use zksync_merkle_tree::domain::ZkSyncTree;
+use zksync_merkle_tree::{checkpoints::TreeCheckpoints, domain::ZkSyncTree, TreeInstruction};
 use zksync_object_store::{ObjectStore, ObjectStoreError};
 use zksync_state::RocksdbStorage;
 use zksync_storage::RocksDB;
@@ -26,9 +26,12 @@ use zksync_types::{
         SnapshotStorageLogsStorageKey,
     },
     web3::BlockNumber,
-    Address, L1BatchNumber, L2ChainId, H160, H256, U256,
+    AccountTreeId, Address, L1BatchNumber, L2ChainId, StorageKey, H160, H256, U256,
 };
 
+use crate::metrics::{TreeRollbackStage, METRICS};
+
+mod metrics;
 pub mod node;
 #[cfg(test)]
 mod tests;
@@ -88,7 +91,7 @@ pub enum NodeRole {
 /// `BlockReverter` can roll back the following pieces of node state:
 ///
 /// - State of the Postgres database
-/// - State of the Merkle tree
+/// - State of the Merkle tree (optionally restoring it from a checkpoint if the target L1 batch is pruned in the tree)
 /// - State of the RocksDB storage cache
 /// - Object store for protocol snapshots
 ///
@@ -103,6 +106,7 @@ pub struct BlockReverter {
     should_roll_back_postgres: bool,
     storage_cache_paths: Vec<PathBuf>,
     merkle_tree_path: Option<PathBuf>,
+    merkle_tree_checkpoints: Option<TreeCheckpoints>,
     snapshots_object_store: Option<Arc<dyn ObjectStore>>,
 }
 
@@ -115,6 +119,7 @@ impl BlockReverter {
             should_roll_back_postgres: false,
             storage_cache_paths: Vec::new(),
             merkle_tree_path: None,
+            merkle_tree_checkpoints: None,
             snapshots_object_store: None,
         }
     }
@@ -139,6 +144,14 @@ impl BlockReverter {
         self
     }
 
+    /// Enables using Merkle tree checkpoints stored at the specified directory. If the L1 batch to roll back to
+    /// is pruned in the tree, the tree will be restored from the nearest preceding checkpoint, and the remaining
+    /// L1 batches will be replayed using tree writes persisted in Postgres.
+    pub fn use_merkle_tree_checkpoints(&mut self, checkpoints_path: PathBuf) -> &mut Self {
+        self.merkle_tree_checkpoints = Some(TreeCheckpoints::new(checkpoints_path));
+        self
+    }
+
     pub fn add_rocksdb_storage_path_to_rollback(&mut self, path: PathBuf) -> &mut Self {
         self.storage_cache_paths.push(path);
         self
@@ -213,16 +226,31 @@ impl BlockReverter {
                     "Rolling back Merkle tree at `{}`",
                     merkle_tree_path.display()
                 );
-                let merkle_tree_path = merkle_tree_path.to_path_buf();
-                tokio::task::spawn_blocking(move || {
-                    Self::roll_back_tree_blocking(
-                        last_l1_batch_to_keep,
-                        &merkle_tree_path,
-                        storage_root_hash,
-                    )
+                let started_at = Instant::now();
+                let path = merkle_tree_path.to_path_buf();
+                let rolled_back = tokio::task::spawn_blocking(move || {
+                    Self::roll_back_tree_blocking(last_l1_batch_to_keep, &path, storage_root_hash)
                 })
                 .await
                 .context("rolling back Merkle tree panicked")??;
+
+                if rolled_back {
+                    METRICS.tree_rollback_latency[&TreeRollbackStage::Truncate]
+                        .observe(started_at.elapsed());
+                } else {
+                    let checkpoints = self.merkle_tree_checkpoints.clone().with_context(|| {
+                        format!(
+                            "L1 batch #{last_l1_batch_to_keep} is pruned in the Merkle tree, and tree checkpoints are not configured; \
+                             the tree needs to be rebuilt"
+                        )
+                    })?;
+                    self.roll_back_tree_from_checkpoint(
+                        last_l1_batch_to_keep,
+                        merkle_tree_path,
+                        checkpoints,
+                    )
+                    .await?;
+                }
             } else {
                 tracing::info!(
                     "Merkle tree not found at `{}`; skipping",
@@ -231,6 +259,20 @@ impl BlockReverter {
             }
         }
 
+        if let Some(checkpoints) = self.merkle_tree_checkpoints.clone() {
+            // Checkpoints taken after the target L1 batch contain the rolled back state and must never be restored.
+            let removed_checkpoints = tokio::task::spawn_blocking(move || {
+                checkpoints.remove_after(last_l1_batch_to_keep)
+            })
+            .await
+            .context("removing Merkle tree checkpoints panicked")??;
+            if !removed_checkpoints.is_empty() {
+                tracing::info!(
+                    "Removed Merkle tree checkpoints for rolled back L1 batches {removed_checkpoints:?}"
+                );
+            }
+        }
+
         for storage_cache_path in &self.storage_cache_paths {
             let sk_cache_exists = fs::try_exists(storage_cache_path).await.with_context(|| {
                 format!("cannot check whether storage cache path `{storage_cache_path:?}` exists")
@@ -245,18 +287,27 @@ impl BlockReverter {
         Ok(())
     }
 
+    /// Rolls back the Merkle tree by truncating its recent versions. Returns `false` if this is impossible
+    /// because the target L1 batch is pruned in the tree.
     fn roll_back_tree_blocking(
         last_l1_batch_to_keep: L1BatchNumber,
         path: &Path,
         storage_root_hash: H256,
-    ) -> anyhow::Result<()> {
+    ) -> anyhow::Result<bool> {
         let db = RocksDB::new(path).context("failed initializing RocksDB for Merkle tree")?;
         let mut tree =
             ZkSyncTree::new_lightweight(db.into()).context("failed initializing Merkle tree")?;
 
         if tree.next_l1_batch_number() <= last_l1_batch_to_keep {
             tracing::info!("Tree is behind the L1 batch to roll back to; skipping");
-            return Ok(());
+            return Ok(true);
+        }
+        let min_l1_batch_number = tree.reader().min_l1_batch_number();
+        if min_l1_batch_number.is_some_and(|min| min > last_l1_batch_to_keep) {
+            tracing::info!(
+                "L1 batch #{last_l1_batch_to_keep} is pruned in the Merkle tree (min retained L1 batch: {min_l1_batch_number:?})"
+            );
+            return Ok(false);
         }
         tree.roll_back_logs(last_l1_batch_to_keep)
             .context("cannot roll back Merkle tree")?;
@@ -269,6 +320,102 @@ impl BlockReverter {
         );
         tracing::info!("Saving tree changes to disk");
         tree.save().context("failed saving tree changes")?;
+        Ok(true)
+    }
+
+    /// Restores the Merkle tree from the latest checkpoint not exceeding the target L1 batch, and then replays
+    /// the remaining L1 batches using tree writes persisted in Postgres.
+    async fn roll_back_tree_from_checkpoint(
+        &self,
+        last_l1_batch_to_keep: L1BatchNumber,
+        merkle_tree_path: &Path,
+        checkpoints: TreeCheckpoints,
+    ) -> anyhow::Result<()> {
+        let started_at = Instant::now();
+        let path = merkle_tree_path.to_path_buf();
+        let (checkpoint, mut tree) = tokio::task::spawn_blocking(move || {
+            let checkpoint = checkpoints
+                .nearest(last_l1_batch_to_keep)?
+                .with_context(|| {
+                    format!("no Merkle tree checkpoints for L1 batch #{last_l1_batch_to_keep} or earlier")
+                })?;
+            tracing::info!(
+                "Restoring Merkle tree at `{}` from checkpoint for L1 batch #{checkpoint}",
+                path.display()
+            );
+            checkpoints.restore(checkpoint, &path)?;
+
+            let db = RocksDB::new(&path).context("failed initializing RocksDB for Merkle tree")?;
+            let tree = ZkSyncTree::new_lightweight(db.into())
+                .context("failed initializing Merkle tree")?;
+            anyhow::ensure!(
+                tree.next_l1_batch_number() == checkpoint + 1,
+                "Merkle tree restored from checkpoint for L1 batch #{checkpoint} has unexpected next L1 batch #{}",
+                tree.next_l1_batch_number()
+            );
+            anyhow::Ok((checkpoint, tree))
+        })
+        .await
+        .context("restoring Merkle tree from checkpoint panicked")??;
+        METRICS.tree_rollback_latency[&TreeRollbackStage::RestoreCheckpoint]
+            .observe(started_at.elapsed());
+
+        let started_at = Instant::now();
+        let l1_batches_to_replay = (checkpoint.0 + 1)..=last_l1_batch_to_keep.0;
+        tracing::info!("Replaying L1 batches #{l1_batches_to_replay:?} in Merkle tree");
+        for l1_batch_number in l1_batches_to_replay.clone() {
+            let l1_batch_number = L1BatchNumber(l1_batch_number);
+            let mut storage = self.connection_pool.connection().await?;
+            let tree_writes = storage
+                .blocks_dal()
+                .get_tree_writes(l1_batch_number)
+                .await?
+                .with_context(|| {
+                    format!("tree writes for L1 batch #{l1_batch_number} are not persisted; cannot replay it in Merkle tree")
+                })?;
+            let expected_root_hash = storage
+                .blocks_dal()
+                .get_l1_batch_state_root(l1_batch_number)
+                .await?
+                .with_context(|| format!("no state root hash for L1 batch #{l1_batch_number}"))?;
+            drop(storage);
+
+            let mut instructions: Vec<_> = tree_writes
+                .into_iter()
+                .map(|write| {
+                    let storage_key = StorageKey::new(AccountTreeId::new(write.address), write.key);
+                    TreeInstruction::write(storage_key, write.leaf_index, write.value)
+                })
+                .collect();
+            instructions.sort_unstable_by_key(TreeInstruction::key);
+            let instructions: Vec<_> = instructions
+                .into_iter()
+                .map(TreeInstruction::with_hashed_key)
+                .collect();
+
+            tree = tokio::task::spawn_blocking(move || {
+                let output = tree.process_l1_batch(&instructions)?;
+                anyhow::ensure!(
+                    output.root_hash == expected_root_hash,
+                    "Mismatch between the tree root hash {:?} and storage root hash {expected_root_hash:?} \
+                     after replaying L1 batch #{l1_batch_number}",
+                    output.root_hash
+                );
+                tree.save().context("failed saving tree changes")?;
+                anyhow::Ok(tree)
+            })
+            .await
+            .with_context(|| {
+                format!("replaying L1 batch #{l1_batch_number} in Merkle tree panicked")
+            })??;
+        }
+
+        let replayed_count = l1_batches_to_replay.count();
+        METRICS.tree_replayed_l1_batches.set(replayed_count as u64);
+        METRICS.tree_rollback_latency[&TreeRollbackStage::Replay].observe(started_at.elapsed());
+        tracing::info!(
+            "Replayed {replayed_count} L1 batches in Merkle tree after restoring it from checkpoint for L1 batch #{checkpoint}"
+        );
         Ok(())
     }
 
@@ -0,0 +1,29 @@
+//! Metrics for the block reverter.
+
+use std::time::Duration;
+
+use vise::{Buckets, EncodeLabelSet, EncodeLabelValue, Family, Gauge, Histogram, Metrics, Unit};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue, EncodeLabelSet)]
+#[metrics(label = "stage", rename_all = "snake_case")]
+pub(crate) enum TreeRollbackStage {
+    /// Rolling back the tree by truncating its recent versions.
+    Truncate,
+    /// Restoring the tree from a checkpoint.
+    RestoreCheckpoint,
+    /// Replaying L1 batches after restoring the tree from a checkpoint.
+    Replay,
+}
+
+#[derive(Debug, Metrics)]
+#[metrics(prefix = "block_reverter")]
+pub(crate) struct BlockReverterMetrics {
+    /// Latency of a Merkle tree rollback stage.
+    #[metrics(buckets = Buckets::LATENCIES, unit = Unit::Seconds)]
+    pub tree_rollback_latency: Family<TreeRollbackStage, Histogram<Duration>>,
+    /// Number of L1 batches replayed in the Merkle tree after restoring it from a checkpoint during the latest rollback.
+    pub tree_replayed_l1_batches: Gauge<u64>,
+}
+
+#[vise::register]
+pub(crate) static METRICS: vise::Global<BlockReverterMetrics> = vise::Global::new();
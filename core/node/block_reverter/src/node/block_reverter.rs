@@ -15,6 +15,7 @@ pub struct BlockReverterLayer {
     should_roll_back_postgres: bool,
     state_keeper_cache_path: Option<PathBuf>,
     merkle_tree_path: Option<PathBuf>,
+    merkle_tree_checkpoints_path: Option<PathBuf>,
 }
 
 impl BlockReverterLayer {
@@ -25,6 +26,7 @@ impl BlockReverterLayer {
             should_roll_back_postgres: false,
             state_keeper_cache_path: None,
             merkle_tree_path: None,
+            merkle_tree_checkpoints_path: None,
         }
     }
 
@@ -43,6 +45,11 @@ impl BlockReverterLayer {
         self
     }
 
+    pub fn use_merkle_tree_checkpoints(&mut self, checkpoints_path: PathBuf) -> &mut Self {
+        self.merkle_tree_checkpoints_path = Some(checkpoints_path);
+        self
+    }
+
     pub fn enable_rolling_back_state_keeper_cache(&mut self, path: PathBuf) -> &mut Self {
         self.state_keeper_cache_path = Some(path);
         self
@@ -80,6 +87,9 @@ impl WiringLayer for BlockReverterLayer {
         if let Some(path) = self.merkle_tree_path {
             block_reverter.enable_rolling_back_merkle_tree(path);
         }
+        if let Some(path) = self.merkle_tree_checkpoints_path {
+            block_reverter.use_merkle_tree_checkpoints(path);
+        }
         if let Some(path) = self.state_keeper_cache_path {
             block_reverter.add_rocksdb_storage_path_to_rollback(path);
         }
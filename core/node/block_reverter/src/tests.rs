@@ -14,6 +14,7 @@ use zksync_types::{
     block::{L1BatchHeader, L2BlockHeader},
     fee_model::BatchFeeInput,
     snapshots::SnapshotVersion,
+    writes::TreeWrite,
     AccountTreeId, L2BlockNumber, ProtocolVersion, ProtocolVersionId, StorageKey, StorageLog,
 };
 
@@ -207,6 +208,89 @@ async fn block_reverter_basics(sync_merkle_tree: bool) {
     }
 }
 
+#[tokio::test]
+async fn rolling_back_pruned_merkle_tree_using_checkpoints() {
+    let storage_logs = gen_storage_logs();
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut storage = pool.connection().await.unwrap();
+    setup_storage(&mut storage, &storage_logs).await;
+    for (i, log) in storage_logs.iter().enumerate() {
+        let tree_write = TreeWrite {
+            address: *log.key.address(),
+            key: *log.key.key(),
+            value: log.value,
+            leaf_index: i as u64 + 1,
+        };
+        storage
+            .blocks_dal()
+            .set_tree_writes(L1BatchNumber(i as u32), vec![tree_write])
+            .await
+            .unwrap();
+    }
+
+    let temp_dir = tempfile::tempdir().unwrap();
+    let merkle_tree_path = temp_dir.path().join("tree");
+    let checkpoints_path = temp_dir.path().join("checkpoints");
+    let checkpoints = TreeCheckpoints::new(checkpoints_path.clone());
+    let mut l1_batch_hashes = initialize_merkle_tree(&merkle_tree_path, &storage_logs[..3]);
+    {
+        let db = RocksDB::new(&merkle_tree_path).unwrap().with_sync_writes();
+        let mut tree = ZkSyncTree::new(db.into()).unwrap();
+        checkpoints.create(&tree.reader()).unwrap();
+        for (i, log) in storage_logs.iter().enumerate().skip(3) {
+            let output = tree
+                .process_l1_batch(&[TreeInstruction::write(
+                    log.key.hashed_key_u256(),
+                    i as u64 + 1,
+                    log.value,
+                )])
+                .unwrap();
+            tree.save().unwrap();
+            l1_batch_hashes.push(output.root_hash);
+        }
+        checkpoints.create(&tree.reader()).unwrap();
+
+        let (mut pruner, _) = tree.pruner();
+        pruner.prune_up_to(9).unwrap();
+        assert_eq!(tree.reader().min_l1_batch_number(), Some(L1BatchNumber(9)));
+    }
+    assert_eq!(
+        checkpoints.list().unwrap(),
+        [L1BatchNumber(2), L1BatchNumber(9)]
+    );
+    for (number, hash) in (0..).zip(&l1_batch_hashes) {
+        storage
+            .blocks_dal()
+            .set_l1_batch_hash(L1BatchNumber(number), *hash)
+            .await
+            .unwrap();
+    }
+
+    // Without checkpoints, the tree cannot be rolled back to a pruned L1 batch.
+    let err = BlockReverter::new(NodeRole::External, pool.clone())
+        .enable_rolling_back_merkle_tree(merkle_tree_path.clone())
+        .roll_back(L1BatchNumber(5))
+        .await
+        .unwrap_err();
+    let err = format!("{err:#}");
+    assert!(err.contains("pruned in the Merkle tree"), "{err}");
+
+    BlockReverter::new(NodeRole::External, pool.clone())
+        .enable_rolling_back_postgres()
+        .enable_rolling_back_merkle_tree(merkle_tree_path.clone())
+        .use_merkle_tree_checkpoints(checkpoints_path)
+        .roll_back(L1BatchNumber(5))
+        .await
+        .unwrap();
+
+    let db = RocksDB::new(&merkle_tree_path).unwrap();
+    let tree = ZkSyncTree::new(db.into()).unwrap();
+    assert_eq!(tree.next_l1_batch_number(), L1BatchNumber(6));
+    assert_eq!(tree.root_hash(), l1_batch_hashes[5]);
+    // The checkpoint with the rolled back state must be removed.
+    assert_eq!(checkpoints.list().unwrap(), [L1BatchNumber(2)]);
+}
+
 async fn create_mock_snapshot(
     storage: &mut Connection<'_, Core>,
     object_store: &dyn ObjectStore,
@@ -235,6 +319,7 @@ async fn create_mock_snapshot(
             l1_batch_number,
             storage_logs_chunk_count,
             &factory_deps_key,
+            H256::zero(),
         )
         .await
         .unwrap();
@@ -244,18 +329,18 @@ async fn create_mock_snapshot(
             l1_batch_number,
             chunk_id,
         };
-        let key = object_store
-            .put(
-                key,
-                &SnapshotStorageLogsChunk::<H256> {
-                    storage_logs: vec![],
-                },
-            )
-            .await
-            .unwrap();
+        let chunk = SnapshotStorageLogsChunk::<H256> {
+            storage_logs: vec![],
+        };
+        let key = object_store.put(key, &chunk).await.unwrap();
         storage
             .snapshots_dal()
-            .add_storage_logs_filepath_for_snapshot(l1_batch_number, chunk_id, &key)
+            .add_storage_logs_filepath_for_snapshot(
+                l1_batch_number,
+                chunk_id,
+                &key,
+                chunk.content_hash(),
+            )
             .await
             .unwrap();
     }
@@ -0,0 +1,161 @@
+//! Estimating Postgres database size for size-based pruning.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use vise::{EncodeLabelSet, EncodeLabelValue};
+use zksync_dal::{Connection, Core, CoreDal};
+use zksync_types::L1BatchNumber;
+
+use crate::metrics::METRICS;
+
+/// Table that is significantly reduced by pruning and for which a size limit can be set.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Serialize,
+    Deserialize,
+    EncodeLabelValue,
+    EncodeLabelSet,
+)]
+#[serde(rename_all = "snake_case")]
+#[metrics(label = "table", rename_all = "snake_case")]
+pub enum PrunedTable {
+    StorageLogs,
+    Events,
+    Transactions,
+}
+
+impl PrunedTable {
+    const ALL: [Self; 3] = [Self::StorageLogs, Self::Events, Self::Transactions];
+
+    fn table_name(self) -> &'static str {
+        match self {
+            Self::StorageLogs => "storage_logs",
+            Self::Events => "events",
+            Self::Transactions => "transactions",
+        }
+    }
+}
+
+/// Size limits for the Postgres database and pruned tables in bytes. If any of the limits is exceeded,
+/// old L1 batches are pruned until all limits are satisfied.
+#[derive(Debug, Clone, Default)]
+pub struct DbSizeLimits {
+    /// Limit on the size of the entire database.
+    pub database: Option<u64>,
+    /// Limits on the size of specific tables (including indexes).
+    pub tables: BTreeMap<PrunedTable, u64>,
+}
+
+impl DbSizeLimits {
+    pub fn is_empty(&self) -> bool {
+        self.database.is_none() && self.tables.is_empty()
+    }
+}
+
+/// Estimated sizes of the database and pruned tables.
+#[derive(Debug, Clone)]
+pub(crate) struct DbSizeEstimate {
+    /// First L1 batch stored in the database.
+    pub first_l1_batch: L1BatchNumber,
+    /// Number of L1 batches stored in the database, which the data in pruned tables is attributed to.
+    pub l1_batch_count: u32,
+    /// Estimated size of the database in bytes.
+    pub database_size: u64,
+    /// Estimated sizes of pruned tables in bytes.
+    pub table_sizes: BTreeMap<PrunedTable, u64>,
+}
+
+impl DbSizeEstimate {
+    /// Estimates database size. Returns `None` if there are no L1 batches in the database.
+    ///
+    /// Sizes of pruned tables are estimated based on the live data in them, so that the estimates decrease
+    /// immediately after pruning, even though Postgres generally doesn't return the freed space to the OS.
+    /// The database size estimate is the on-disk database size adjusted for the pruned table estimates.
+    pub async fn new(storage: &mut Connection<'_, Core>) -> anyhow::Result<Option<Self>> {
+        let Some(first_l1_batch) = storage.blocks_dal().get_earliest_l1_batch_number().await?
+        else {
+            return Ok(None);
+        };
+        let Some(last_l1_batch) = storage.blocks_dal().get_sealed_l1_batch_number().await? else {
+            return Ok(None);
+        };
+
+        let mut database_size = storage.system_dal().get_database_size().await?;
+        let mut table_sizes = BTreeMap::new();
+        for table in PrunedTable::ALL {
+            let Some(stats) = storage
+                .system_dal()
+                .get_table_size_stats(table.table_name())
+                .await?
+            else {
+                continue;
+            };
+            let live_size = stats.estimated_live_size();
+            database_size = database_size.saturating_sub(stats.total_size) + live_size;
+            table_sizes.insert(table, live_size);
+            METRICS.estimated_table_size[&table].set(live_size);
+        }
+        METRICS.estimated_database_size.set(database_size);
+
+        Ok(Some(Self {
+            first_l1_batch,
+            l1_batch_count: last_l1_batch.0.saturating_sub(first_l1_batch.0) + 1,
+            database_size,
+            table_sizes,
+        }))
+    }
+
+    fn table_size_per_l1_batch(&self, table: PrunedTable) -> u64 {
+        self.table_sizes.get(&table).copied().unwrap_or(0) / u64::from(self.l1_batch_count)
+    }
+
+    /// Estimates the number of bytes freed in each pruned table by pruning the specified number of L1 batches.
+    pub fn freed_bytes(&self, l1_batch_count: u32) -> BTreeMap<PrunedTable, u64> {
+        let l1_batch_count = l1_batch_count.min(self.l1_batch_count);
+        self.table_sizes
+            .keys()
+            .map(|&table| {
+                let freed = self.table_size_per_l1_batch(table) * u64::from(l1_batch_count);
+                (table, freed)
+            })
+            .collect()
+    }
+
+    /// Computes the number of oldest L1 batches that need to be pruned in order to satisfy the specified limits.
+    pub fn l1_batches_to_prune(&self, limits: &DbSizeLimits) -> u32 {
+        let database_limit = limits.database.map(|limit| {
+            let batch_size = PrunedTable::ALL
+                .into_iter()
+                .map(|table| self.table_size_per_l1_batch(table))
+                .sum();
+            (self.database_size, limit, batch_size)
+        });
+        let table_limits = limits.tables.iter().map(|(&table, &limit)| {
+            let size = self.table_sizes.get(&table).copied().unwrap_or(0);
+            (size, limit, self.table_size_per_l1_batch(table))
+        });
+
+        let to_prune = database_limit
+            .into_iter()
+            .chain(table_limits)
+            .map(|(size, limit, batch_size)| {
+                let excess = size.saturating_sub(limit);
+                if excess == 0 || batch_size == 0 {
+                    0
+                } else {
+                    excess.div_ceil(batch_size)
+                }
+            })
+            .max()
+            .unwrap_or(0);
+        to_prune.min(self.l1_batch_count.into()) as u32
+    }
+}
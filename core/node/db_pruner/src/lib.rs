@@ -1,6 +1,7 @@
 //! Postgres pruning component.
 
 use std::{
+    collections::BTreeMap,
     sync::Arc,
     time::{Duration, Instant},
 };
@@ -15,14 +16,18 @@ use zksync_dal::{
 use zksync_health_check::{Health, HealthStatus, HealthUpdater, ReactiveHealthCheck};
 use zksync_types::{L1BatchNumber, L2BlockNumber, OrStopped};
 
+pub use self::db_size::{DbSizeLimits, PrunedTable};
 use self::{
+    db_size::DbSizeEstimate,
     metrics::{ConditionOutcome, PruneType, METRICS},
     prune_conditions::{
-        ConsistencyCheckerProcessedBatch, L1BatchExistsCondition, L1BatchOlderThanPruneCondition,
-        NextL1BatchHasMetadataCondition, NextL1BatchWasExecutedCondition, PruneCondition,
+        ConsistencyCheckerProcessedBatch, DbSizeExceedsLimitsCondition, L1BatchExistsCondition,
+        L1BatchOlderThanPruneCondition, NextL1BatchHasMetadataCondition,
+        NextL1BatchWasExecutedCondition, PruneCondition,
     },
 };
 
+mod db_size;
 mod metrics;
 pub mod node;
 mod prune_conditions;
@@ -40,6 +45,32 @@ pub struct DbPrunerConfig {
     /// Minimum age of an L1 batch in order for it to be eligible for pruning. Setting this to zero
     /// will effectively disable this pruning criterion.
     pub minimum_l1_batch_age: Duration,
+    /// Limits on the Postgres database size. If any limits are set, L1 batches will only be pruned
+    /// while the (estimated) database size exceeds them.
+    pub size_limits: DbSizeLimits,
+    /// If set, the pruner will not modify the database and will only report pruning steps it would perform.
+    pub dry_run: bool,
+}
+
+/// Maximum number of pruning steps reported in a single dry-run iteration.
+const MAX_DRY_RUN_STEPS: usize = 10;
+
+/// Single pruning step reported in the dry-run mode.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct DryRunStep {
+    /// Last L1 batch that would be pruned by the step.
+    last_l1_batch: L1BatchNumber,
+    /// Estimated number of bytes freed in each pruned table. Empty if the database size cannot be estimated.
+    estimated_freed_bytes: BTreeMap<PrunedTable, u64>,
+}
+
+/// Report produced by the pruner in the dry-run mode.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct DryRunReport {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    estimated_database_size: Option<u64>,
+    /// Pruning steps that would be performed given the current state of the database.
+    steps: Vec<DryRunStep>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -52,6 +83,8 @@ struct DbPrunerHealth {
     last_hard_pruned_l1_batch: Option<L1BatchNumber>,
     #[serde(skip_serializing_if = "Option::is_none")]
     last_hard_pruned_l2_block: Option<L2BlockNumber>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    dry_run: Option<DryRunReport>,
 }
 
 impl From<PruningInfo> for DbPrunerHealth {
@@ -61,6 +94,7 @@ impl From<PruningInfo> for DbPrunerHealth {
             last_soft_pruned_l2_block: info.last_soft_pruned.map(|info| info.l2_block),
             last_hard_pruned_l1_batch: info.last_hard_pruned.map(|info| info.l1_batch),
             last_hard_pruned_l2_block: info.last_hard_pruned.map(|info| info.l2_block),
+            dry_run: None,
         }
     }
 }
@@ -106,6 +140,12 @@ impl DbPruner {
                 pool: connection_pool.clone(),
             }));
         }
+        if !config.size_limits.is_empty() {
+            conditions.push(Arc::new(DbSizeExceedsLimitsCondition {
+                limits: config.size_limits.clone(),
+                pool: connection_pool.clone(),
+            }));
+        }
 
         Self::with_conditions(config, connection_pool, conditions)
     }
@@ -185,15 +225,65 @@ impl DbPruner {
         self.health_updater.update(health);
     }
 
+    fn next_l1_batch_to_prune(&self, last_pruned_l1_batch: Option<L1BatchNumber>) -> L1BatchNumber {
+        last_pruned_l1_batch.unwrap_or(L1BatchNumber(0)) + self.config.pruned_batch_chunk_size
+    }
+
+    /// Reports pruning steps that would be performed without modifying the database.
+    async fn report_dry_run(&self, storage: &mut Connection<'_, Core>) -> anyhow::Result<()> {
+        let pruning_info = storage.pruning_dal().get_pruning_info().await?;
+        let estimate = DbSizeEstimate::new(storage).await?;
+
+        let mut last_pruned_l1_batch = pruning_info.last_soft_pruned.map(|info| info.l1_batch);
+        let mut steps = vec![];
+        while steps.len() < MAX_DRY_RUN_STEPS {
+            let next_l1_batch_to_prune = self.next_l1_batch_to_prune(last_pruned_l1_batch);
+            if !self.is_l1_batch_prunable(next_l1_batch_to_prune).await {
+                break;
+            }
+
+            let estimated_freed_bytes = estimate.as_ref().map_or_else(BTreeMap::new, |estimate| {
+                let first_l1_batch = last_pruned_l1_batch
+                    .map_or(estimate.first_l1_batch, |number| number + 1)
+                    .max(estimate.first_l1_batch);
+                let l1_batch_count =
+                    (next_l1_batch_to_prune.0 + 1).saturating_sub(first_l1_batch.0);
+                estimate.freed_bytes(l1_batch_count)
+            });
+            tracing::info!(
+                "Dry run: would prune L1 batches up to #{next_l1_batch_to_prune}, freeing approximately \
+                 {estimated_freed_bytes:?} bytes"
+            );
+            steps.push(DryRunStep {
+                last_l1_batch: next_l1_batch_to_prune,
+                estimated_freed_bytes,
+            });
+            last_pruned_l1_batch = Some(next_l1_batch_to_prune);
+        }
+
+        if steps.is_empty() {
+            tracing::info!("Dry run: no L1 batches can be pruned at the moment");
+        }
+        let mut health = DbPrunerHealth::from(pruning_info);
+        health.dry_run = Some(DryRunReport {
+            estimated_database_size: estimate.map(|estimate| estimate.database_size),
+            steps,
+        });
+        self.health_updater
+            .update(Health::from(HealthStatus::Ready).with_details(health));
+        Ok(())
+    }
+
     async fn soft_prune(&self, storage: &mut Connection<'_, Core>) -> anyhow::Result<bool> {
         let start = Instant::now();
         let mut transaction = storage.start_transaction().await?;
 
         let mut current_pruning_info = transaction.pruning_dal().get_pruning_info().await?;
-        let next_l1_batch_to_prune = current_pruning_info
-            .last_soft_pruned
-            .map_or(L1BatchNumber(0), |info| info.l1_batch)
-            + self.config.pruned_batch_chunk_size;
+        let next_l1_batch_to_prune = self.next_l1_batch_to_prune(
+            current_pruning_info
+                .last_soft_pruned
+                .map(|info| info.l1_batch),
+        );
         if !self.is_l1_batch_prunable(next_l1_batch_to_prune).await {
             METRICS.pruning_chunk_duration[&PruneType::NoOp].observe(start.elapsed());
             return Ok(false);
@@ -291,6 +381,11 @@ impl DbPruner {
         stop_receiver: &mut watch::Receiver<bool>,
     ) -> Result<PruningIterationOutcome, OrStopped> {
         let mut storage = self.connection_pool.connection_tagged("db_pruner").await?;
+        if self.config.dry_run {
+            self.report_dry_run(&mut storage).await?;
+            return Ok(PruningIterationOutcome::NoOp);
+        }
+
         let current_pruning_info = storage.pruning_dal().get_pruning_info().await?;
         self.update_health(current_pruning_info);
 
@@ -5,7 +5,7 @@ use vise::{
 };
 use zksync_dal::pruning_dal::HardPruningStats;
 
-use crate::prune_conditions::PruneCondition;
+use crate::{db_size::PrunedTable, prune_conditions::PruneCondition};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue, EncodeLabelSet)]
 #[metrics(label = "prune_type", rename_all = "snake_case")]
@@ -58,6 +58,13 @@ pub(super) struct DbPrunerMetrics {
     deleted_entities: Family<PrunedEntityType, Histogram<u64>>,
     /// Number of times a certain condition has resulted in a specific outcome (succeeded, failed, or errored).
     condition_outcomes: Family<ConditionOutcomeLabels, Counter>,
+    /// Estimated size of the Postgres database. Only reported if size-based pruning or dry-run mode is enabled.
+    #[metrics(unit = Unit::Bytes)]
+    pub estimated_database_size: Gauge<u64>,
+    /// Estimated size of live data in pruned tables, including indexes. Only reported if size-based pruning
+    /// or dry-run mode is enabled.
+    #[metrics(unit = Unit::Bytes)]
+    pub estimated_table_size: Family<PrunedTable, Gauge<u64>>,
 }
 
 impl DbPrunerMetrics {
@@ -9,7 +9,7 @@ use zksync_node_framework::{
     FromContext, IntoContext,
 };
 
-use crate::{DbPruner, DbPrunerConfig};
+use crate::{DbPruner, DbPrunerConfig, DbSizeLimits};
 
 /// Wiring layer for node pruning layer.
 #[derive(Debug)]
@@ -17,6 +17,8 @@ pub struct PruningLayer {
     pruning_removal_delay: Duration,
     pruning_chunk_size: u32,
     minimum_l1_batch_age: Duration,
+    size_limits: DbSizeLimits,
+    dry_run: bool,
 }
 
 #[derive(Debug, FromContext)]
@@ -42,8 +44,22 @@ impl PruningLayer {
             pruning_removal_delay,
             pruning_chunk_size,
             minimum_l1_batch_age,
+            size_limits: DbSizeLimits::default(),
+            dry_run: false,
         }
     }
+
+    /// Sets limits on the Postgres database size. L1 batches will only be pruned while the database exceeds them.
+    pub fn with_size_limits(mut self, size_limits: DbSizeLimits) -> Self {
+        self.size_limits = size_limits;
+        self
+    }
+
+    /// Enables the dry-run mode, in which the pruner only reports pruning steps without modifying the database.
+    pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
 }
 
 #[async_trait::async_trait]
@@ -63,6 +79,8 @@ impl WiringLayer for PruningLayer {
                 removal_delay: self.pruning_removal_delay,
                 pruned_batch_chunk_size: self.pruning_chunk_size,
                 minimum_l1_batch_age: self.minimum_l1_batch_age,
+                size_limits: self.size_limits,
+                dry_run: self.dry_run,
             },
             main_pool,
         );
@@ -5,6 +5,8 @@ use chrono::Utc;
 use zksync_dal::{ConnectionPool, Core, CoreDal};
 use zksync_types::L1BatchNumber;
 
+use crate::db_size::{DbSizeEstimate, DbSizeLimits};
+
 #[async_trait]
 pub(crate) trait PruneCondition: fmt::Debug + fmt::Display + Send + Sync + 'static {
     fn metric_label(&self) -> &'static str;
@@ -171,3 +173,31 @@ impl PruneCondition for ConsistencyCheckerProcessedBatch {
         Ok(l1_batch_number <= last_processed_l1_batch)
     }
 }
+
+#[derive(Debug)]
+pub(super) struct DbSizeExceedsLimitsCondition {
+    pub limits: DbSizeLimits,
+    pub pool: ConnectionPool<Core>,
+}
+
+impl fmt::Display for DbSizeExceedsLimitsCondition {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(formatter, "database size exceeds limits {:?}", self.limits)
+    }
+}
+
+#[async_trait]
+impl PruneCondition for DbSizeExceedsLimitsCondition {
+    fn metric_label(&self) -> &'static str {
+        "db_size_exceeds_limits"
+    }
+
+    async fn is_batch_prunable(&self, l1_batch_number: L1BatchNumber) -> anyhow::Result<bool> {
+        let mut storage = self.pool.connection_tagged("db_pruner").await?;
+        let Some(estimate) = DbSizeEstimate::new(&mut storage).await? else {
+            return Ok(false);
+        };
+        let l1_batches_to_prune = estimate.l1_batches_to_prune(&self.limits);
+        Ok(l1_batch_number.0 < estimate.first_l1_batch.0 + l1_batches_to_prune)
+    }
+}
@@ -1,4 +1,7 @@
-use std::{collections::HashMap, fmt};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt, ops,
+};
 
 use assert_matches::assert_matches;
 use async_trait::async_trait;
@@ -78,6 +81,8 @@ async fn is_l1_batch_prunable_works() {
             removal_delay: Duration::ZERO,
             pruned_batch_chunk_size: 1,
             minimum_l1_batch_age: Duration::ZERO,
+            size_limits: DbSizeLimits::default(),
+            dry_run: false,
         },
         ConnectionPool::test_pool().await,
         vec![failing_check, other_failing_check],
@@ -148,6 +153,8 @@ async fn hard_pruning_ignores_conditions_checks() {
             removal_delay: Duration::ZERO,
             pruned_batch_chunk_size: 5,
             minimum_l1_batch_age: Duration::ZERO,
+            size_limits: DbSizeLimits::default(),
+            dry_run: false,
         },
         pool.clone(),
         vec![nothing_prunable_check],
@@ -197,6 +204,8 @@ async fn pruner_catches_up_with_hard_pruning_up_to_soft_pruning_boundary_ignorin
             removal_delay: Duration::ZERO,
             pruned_batch_chunk_size: 5,
             minimum_l1_batch_age: Duration::ZERO,
+            size_limits: DbSizeLimits::default(),
+            dry_run: false,
         },
         pool.clone(),
         vec![], //No checks, so every batch is prunable
@@ -235,6 +244,8 @@ async fn unconstrained_pruner_with_fresh_database() {
             removal_delay: Duration::ZERO,
             pruned_batch_chunk_size: 3,
             minimum_l1_batch_age: Duration::ZERO,
+            size_limits: DbSizeLimits::default(),
+            dry_run: false,
         },
         pool.clone(),
         vec![], //No checks, so every batch is prunable
@@ -275,6 +286,8 @@ async fn pruning_blocked_after_first_chunk() {
             removal_delay: Duration::ZERO,
             pruned_batch_chunk_size: 3,
             minimum_l1_batch_age: Duration::ZERO,
+            size_limits: DbSizeLimits::default(),
+            dry_run: false,
         },
         pool.clone(),
         vec![first_chunk_prunable_check],
@@ -315,6 +328,8 @@ async fn pruner_is_resistant_to_errors() {
             removal_delay: Duration::ZERO,
             pruned_batch_chunk_size: 3,
             minimum_l1_batch_age: Duration::ZERO,
+            size_limits: DbSizeLimits::default(),
+            dry_run: false,
         },
         pool.clone(),
         vec![erroneous_condition],
@@ -485,6 +500,8 @@ async fn pruner_with_real_conditions() {
         removal_delay: Duration::from_millis(10), // non-zero to not have a tight loop in `DbPruner::run()`
         pruned_batch_chunk_size: 1,
         minimum_l1_batch_age: Duration::ZERO,
+        size_limits: DbSizeLimits::default(),
+        dry_run: false,
     };
     let pruner = DbPruner::new(config, pool.clone());
     let mut health_check = pruner.health_check();
@@ -541,6 +558,8 @@ async fn pruning_iteration_timely_shuts_down() {
             removal_delay: Duration::MAX, // intentionally chosen so that pruning iterations stuck
             pruned_batch_chunk_size: 3,
             minimum_l1_batch_age: Duration::ZERO,
+            size_limits: DbSizeLimits::default(),
+            dry_run: false,
         },
         pool.clone(),
         vec![], //No checks, so every batch is prunable
@@ -570,6 +589,8 @@ async fn pruner_timely_shuts_down() {
             removal_delay: Duration::MAX, // intentionally chosen so that pruning iterations stuck
             pruned_batch_chunk_size: 3,
             minimum_l1_batch_age: Duration::ZERO,
+            size_limits: DbSizeLimits::default(),
+            dry_run: false,
         },
         pool.clone(),
         vec![], //No checks, so every batch is prunable
@@ -585,3 +606,167 @@ async fn pruner_timely_shuts_down() {
     stop_sender.send_replace(true);
     pruner_handle.await.unwrap().unwrap();
 }
+
+fn mock_estimate() -> DbSizeEstimate {
+    DbSizeEstimate {
+        first_l1_batch: L1BatchNumber(10),
+        l1_batch_count: 100,
+        database_size: 2_000,
+        table_sizes: BTreeMap::from([
+            (PrunedTable::StorageLogs, 1_000),
+            (PrunedTable::Events, 500),
+            (PrunedTable::Transactions, 100),
+        ]),
+    }
+}
+
+#[test]
+fn computing_l1_batches_to_prune() {
+    let estimate = mock_estimate();
+    assert_eq!(estimate.l1_batches_to_prune(&DbSizeLimits::default()), 0);
+
+    let limits = DbSizeLimits {
+        database: Some(2_000),
+        tables: BTreeMap::new(),
+    };
+    assert_eq!(estimate.l1_batches_to_prune(&limits), 0);
+    let limits = DbSizeLimits {
+        database: Some(1_900),
+        tables: BTreeMap::new(),
+    };
+    // Each L1 batch takes 10 + 5 + 1 = 16 bytes
+    assert_eq!(estimate.l1_batches_to_prune(&limits), 7);
+
+    let limits = DbSizeLimits {
+        database: Some(1_900),
+        tables: BTreeMap::from([(PrunedTable::Events, 300)]),
+    };
+    assert_eq!(estimate.l1_batches_to_prune(&limits), 40);
+
+    let limits = DbSizeLimits {
+        database: Some(0),
+        tables: BTreeMap::new(),
+    };
+    assert_eq!(estimate.l1_batches_to_prune(&limits), 100);
+}
+
+#[test]
+fn estimating_freed_bytes() {
+    let estimate = mock_estimate();
+    let freed = estimate.freed_bytes(10);
+    assert_eq!(
+        freed,
+        BTreeMap::from([
+            (PrunedTable::StorageLogs, 100),
+            (PrunedTable::Events, 50),
+            (PrunedTable::Transactions, 10),
+        ])
+    );
+}
+
+async fn seal_and_finalize_l1_batches(
+    storage: &mut Connection<'_, Core>,
+    numbers: ops::RangeInclusive<u32>,
+) {
+    for number in numbers {
+        seal_l1_batch(storage, number).await;
+        save_l1_batch_metadata(storage, number).await;
+        mark_l1_batch_as_consistent(storage, number).await;
+        mark_l1_batch_as_executed(storage, number).await;
+    }
+}
+
+#[tokio::test]
+async fn db_size_condition_works() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut storage = pool.connection().await.unwrap();
+    insert_genesis_batch(&mut storage, &GenesisParams::mock())
+        .await
+        .unwrap();
+    seal_and_finalize_l1_batches(&mut storage, 1..=3).await;
+
+    let lenient_condition = DbSizeExceedsLimitsCondition {
+        limits: DbSizeLimits {
+            database: Some(u64::MAX),
+            tables: BTreeMap::from([(PrunedTable::StorageLogs, u64::MAX)]),
+        },
+        pool: pool.clone(),
+    };
+    assert!(!lenient_condition
+        .is_batch_prunable(L1BatchNumber(1))
+        .await
+        .unwrap());
+
+    let strict_condition = DbSizeExceedsLimitsCondition {
+        limits: DbSizeLimits {
+            database: None,
+            tables: BTreeMap::from([(PrunedTable::StorageLogs, 0)]),
+        },
+        pool: pool.clone(),
+    };
+    for number in 0..=3 {
+        assert!(strict_condition
+            .is_batch_prunable(L1BatchNumber(number))
+            .await
+            .unwrap());
+    }
+    assert!(!strict_condition
+        .is_batch_prunable(L1BatchNumber(4))
+        .await
+        .unwrap());
+}
+
+#[tokio::test]
+async fn pruner_in_dry_run_mode() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut storage = pool.connection().await.unwrap();
+    insert_genesis_batch(&mut storage, &GenesisParams::mock())
+        .await
+        .unwrap();
+    seal_and_finalize_l1_batches(&mut storage, 1..=5).await;
+
+    let config = DbPrunerConfig {
+        removal_delay: Duration::from_millis(10),
+        pruned_batch_chunk_size: 1,
+        minimum_l1_batch_age: Duration::ZERO,
+        size_limits: DbSizeLimits {
+            database: Some(0),
+            tables: BTreeMap::new(),
+        },
+        dry_run: true,
+    };
+    let pruner = DbPruner::new(config, pool.clone());
+    let mut health_check = pruner.health_check();
+    let (stop_sender, stop_receiver) = watch::channel(false);
+    let pruner_handle = tokio::spawn(pruner.run(stop_receiver));
+
+    let health = health_check
+        .wait_for(|health| {
+            health
+                .details()
+                .is_some_and(|details| details.get("dry_run").is_some())
+        })
+        .await;
+    let details: DbPrunerHealth =
+        serde_json::from_value(health.details().unwrap().clone()).unwrap();
+    let report = details.dry_run.unwrap();
+    assert!(report.estimated_database_size.is_some());
+    let pruned_batches: Vec<_> = report.steps.iter().map(|step| step.last_l1_batch).collect();
+    // The last L1 batch cannot be pruned because the next batch is not executed.
+    assert_eq!(
+        pruned_batches,
+        (1..=4).map(L1BatchNumber).collect::<Vec<_>>()
+    );
+    for step in &report.steps {
+        assert!(step
+            .estimated_freed_bytes
+            .contains_key(&PrunedTable::StorageLogs));
+    }
+
+    stop_sender.send_replace(true);
+    pruner_handle.await.unwrap().unwrap();
+
+    // The database must not be modified.
+    let pruning_info = storage.pruning_dal().get_pruning_info().await.unwrap();
+    assert_eq!(pruning_info, PruningInfo::default());
+}
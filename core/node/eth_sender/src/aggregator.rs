@@ -1,4 +1,4 @@
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc};
 
 use zksync_config::configs::eth_sender::{ProofSendingMode, SenderConfig};
 use zksync_contracts::BaseSystemContractsHashes;
@@ -19,6 +19,7 @@ use zksync_types::{
     protocol_version::{L1VerifierConfig, ProtocolSemanticVersion},
     pubdata_da::PubdataSendingMode,
     settlement::SettlementLayer,
+    tee_types::{TeeBatchStatus, TeeQuorumPolicy},
     L1BatchNumber, ProtocolVersionId,
 };
 
@@ -49,6 +50,8 @@ pub struct Aggregator {
     commitment_mode: L1BatchCommitmentMode,
     priority_merkle_tree: Option<MiniMerkleTree<L1Tx>>,
     settlement_layer: SettlementLayer,
+    /// If set, batches are only executed once they are verified by TEE provers according to this policy.
+    tee_quorum_policy: Option<TeeQuorumPolicy>,
 }
 
 /// Denotes whether there are any restrictions on sending either
@@ -198,9 +201,17 @@ impl Aggregator {
             priority_merkle_tree: None,
             pool,
             settlement_layer,
+            tee_quorum_policy: None,
         })
     }
 
+    /// Makes the aggregator wait until batches are verified by TEE provers according to the specified policy
+    /// before executing them.
+    pub fn with_tee_quorum_policy(mut self, policy: TeeQuorumPolicy) -> Self {
+        self.tee_quorum_policy = Some(policy);
+        self
+    }
+
     pub(crate) async fn get_next_ready_operation(
         &mut self,
         storage: &mut Connection<'_, Core>,
@@ -286,11 +297,22 @@ impl Aggregator {
             .config
             .l1_batch_min_age_before_execute_seconds
             .map(|age| unix_timestamp_ms() - age.as_millis() as u64);
-        let ready_for_execute_batches = storage
+        let mut ready_for_execute_batches = storage
             .blocks_dal()
             .get_ready_for_execute_l1_batches(limit, max_l1_batch_timestamp_millis)
             .await
             .unwrap();
+        if let Some(policy) = &self.tee_quorum_policy {
+            let verified_count =
+                tee_verified_prefix_len(storage, policy, &ready_for_execute_batches).await;
+            if verified_count < ready_for_execute_batches.len() {
+                tracing::debug!(
+                    "L1 batch #{} is not TEE-verified yet; postponing its execution",
+                    ready_for_execute_batches[verified_count].header.number
+                );
+                ready_for_execute_batches.truncate(verified_count);
+            }
+        }
         let Some(l1_batches) = extract_ready_subrange(
             storage,
             &mut self.execute_criteria,
@@ -708,6 +730,39 @@ impl Aggregator {
     }
 }
 
+/// Returns the number of leading batches in `l1_batches` that are verified by TEE provers according to the `policy`.
+async fn tee_verified_prefix_len(
+    storage: &mut Connection<'_, Core>,
+    policy: &TeeQuorumPolicy,
+    l1_batches: &[L1BatchWithMetadata],
+) -> usize {
+    let (Some(first), Some(last)) = (l1_batches.first(), l1_batches.last()) else {
+        return 0;
+    };
+    let proofs = storage
+        .tee_proof_generation_dal()
+        .get_attested_tee_proofs(first.header.number..=last.header.number)
+        .await
+        .unwrap();
+    let mut proofs_by_batch = HashMap::<_, Vec<_>>::new();
+    for proof in &proofs {
+        proofs_by_batch
+            .entry(proof.l1_batch_number)
+            .or_default()
+            .push((proof.tee_type, proof.pubkey.as_slice()));
+    }
+
+    l1_batches
+        .iter()
+        .take_while(|batch| {
+            let proofs = proofs_by_batch
+                .get(&batch.header.number)
+                .map_or(&[][..], Vec::as_slice);
+            policy.batch_status(proofs.iter().copied()) == TeeBatchStatus::Verified
+        })
+        .count()
+}
+
 async fn extract_ready_subrange(
     storage: &mut Connection<'_, Core>,
     publish_criteria: &mut [Box<dyn L1BatchPublishCriterion>],
@@ -775,3 +830,111 @@ pub async fn load_wrapped_fri_proofs_for_range(
 
     None
 }
+
+#[cfg(test)]
+mod tests {
+    use zksync_node_test_utils::create_l1_batch;
+    use zksync_types::tee_types::TeeType;
+
+    use super::*;
+    use crate::tests::l1_batch_with_metadata;
+
+    const SGX_KEY: &[u8] = &[1; 33];
+    const TDX_KEY: &[u8] = &[2; 33];
+
+    fn quorum_policy() -> TeeQuorumPolicy {
+        TeeQuorumPolicy {
+            required_tee_types: vec![TeeType::Sgx, TeeType::Tdx],
+            min_proofs: 2,
+        }
+    }
+
+    fn mock_l1_batches(numbers: impl IntoIterator<Item = u32>) -> Vec<L1BatchWithMetadata> {
+        numbers
+            .into_iter()
+            .map(|number| l1_batch_with_metadata(create_l1_batch(number)))
+            .collect()
+    }
+
+    async fn save_tee_proof(
+        storage: &mut Connection<'_, Core>,
+        l1_batch_number: u32,
+        tee_type: TeeType,
+        pubkey: &[u8],
+    ) {
+        let l1_batch_number = L1BatchNumber(l1_batch_number);
+        let mut dal = storage.tee_proof_generation_dal();
+        dal.insert_tee_proof_generation_job(l1_batch_number, tee_type)
+            .await
+            .unwrap();
+        dal.save_attestation(pubkey, b"attestation").await.unwrap();
+        dal.save_proof_artifacts_metadata(
+            l1_batch_number,
+            tee_type,
+            pubkey,
+            b"signature",
+            b"proof",
+        )
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn tee_verified_prefix_with_full_quorum() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut storage = pool.connection().await.unwrap();
+        for number in 1..=3 {
+            save_tee_proof(&mut storage, number, TeeType::Sgx, SGX_KEY).await;
+            save_tee_proof(&mut storage, number, TeeType::Tdx, TDX_KEY).await;
+        }
+
+        let l1_batches = mock_l1_batches(1..=3);
+        let prefix_len = tee_verified_prefix_len(&mut storage, &quorum_policy(), &l1_batches).await;
+        assert_eq!(prefix_len, 3);
+        let prefix_len = tee_verified_prefix_len(&mut storage, &quorum_policy(), &[]).await;
+        assert_eq!(prefix_len, 0);
+    }
+
+    #[tokio::test]
+    async fn tee_verified_prefix_with_partial_quorum() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut storage = pool.connection().await.unwrap();
+        save_tee_proof(&mut storage, 1, TeeType::Sgx, SGX_KEY).await;
+        save_tee_proof(&mut storage, 1, TeeType::Tdx, TDX_KEY).await;
+        // Only one of the required TEE types has proven batch #2.
+        save_tee_proof(&mut storage, 2, TeeType::Sgx, SGX_KEY).await;
+        // Proofs signed by the same key count once.
+        save_tee_proof(&mut storage, 3, TeeType::Sgx, SGX_KEY).await;
+        save_tee_proof(&mut storage, 3, TeeType::Tdx, SGX_KEY).await;
+
+        let l1_batches = mock_l1_batches(1..=3);
+        let prefix_len = tee_verified_prefix_len(&mut storage, &quorum_policy(), &l1_batches).await;
+        assert_eq!(prefix_len, 1);
+        let prefix_len =
+            tee_verified_prefix_len(&mut storage, &quorum_policy(), &l1_batches[2..]).await;
+        assert_eq!(prefix_len, 0);
+
+        // The default policy is satisfied by a single proof.
+        let policy = TeeQuorumPolicy::default();
+        let prefix_len = tee_verified_prefix_len(&mut storage, &policy, &l1_batches).await;
+        assert_eq!(prefix_len, 3);
+    }
+
+    #[tokio::test]
+    async fn tee_verified_prefix_with_gap() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut storage = pool.connection().await.unwrap();
+        // Batch #3 has no proofs, so verified batches after it must not be included.
+        for number in [1, 2, 4, 5] {
+            save_tee_proof(&mut storage, number, TeeType::Sgx, SGX_KEY).await;
+            save_tee_proof(&mut storage, number, TeeType::Tdx, TDX_KEY).await;
+        }
+
+        let l1_batches = mock_l1_batches(1..=5);
+        let prefix_len = tee_verified_prefix_len(&mut storage, &quorum_policy(), &l1_batches).await;
+        assert_eq!(prefix_len, 2);
+        let prefix_len =
+            tee_verified_prefix_len(&mut storage, &quorum_policy(), &l1_batches[3..]).await;
+        assert_eq!(prefix_len, 2);
+    }
+}
@@ -16,7 +16,7 @@ use zksync_node_framework::{
     FromContext, IntoContext,
 };
 use zksync_object_store::node::ObjectStoreResource;
-use zksync_types::{commitment::L1BatchCommitmentMode, L2ChainId};
+use zksync_types::{commitment::L1BatchCommitmentMode, tee_types::TeeQuorumPolicy, L2ChainId};
 
 use crate::{Aggregator, EthTxAggregator};
 
@@ -42,6 +42,7 @@ use crate::{Aggregator, EthTxAggregator};
 pub struct EthTxAggregatorLayer {
     zksync_network_id: L2ChainId,
     l1_batch_commit_data_generator_mode: L1BatchCommitmentMode,
+    tee_quorum_policy: Option<TeeQuorumPolicy>,
 }
 
 #[derive(Debug, FromContext)]
@@ -75,8 +76,15 @@ impl EthTxAggregatorLayer {
         Self {
             zksync_network_id,
             l1_batch_commit_data_generator_mode,
+            tee_quorum_policy: None,
         }
     }
+
+    /// Makes the aggregator wait for TEE quorum before executing batches.
+    pub fn with_tee_quorum_policy(mut self, policy: TeeQuorumPolicy) -> Self {
+        self.tee_quorum_policy = Some(policy);
+        self
+    }
 }
 
 #[async_trait::async_trait]
@@ -130,7 +138,7 @@ impl WiringLayer for EthTxAggregatorLayer {
         // Create and add tasks.
 
         let config = input.sender_config.0;
-        let aggregator = Aggregator::new(
+        let mut aggregator = Aggregator::new(
             config.clone(),
             object_store,
             eth_client_blobs.is_some(),
@@ -139,6 +147,9 @@ impl WiringLayer for EthTxAggregatorLayer {
             input.settlement_mode.settlement_layer(),
         )
         .await?;
+        if let Some(policy) = self.tee_quorum_policy {
+            aggregator = aggregator.with_tee_quorum_policy(policy);
+        }
 
         let eth_tx_aggregator = EthTxAggregator::new(
             master_pool.clone(),
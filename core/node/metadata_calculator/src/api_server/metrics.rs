@@ -9,6 +9,7 @@ use vise::{Buckets, EncodeLabelSet, EncodeLabelValue, Family, Histogram, Metrics
 pub(super) enum MerkleTreeApiMethod {
     Info,
     GetProofs,
+    GetMultiProof,
     GetNodes,
     GetStaleKeys,
     GetBogusStaleKeys,
@@ -21,6 +22,9 @@ pub(super) struct MerkleTreeApiMetrics {
     /// Server latency of the Merkle tree API methods.
     #[metrics(buckets = Buckets::LATENCIES, unit = Unit::Seconds)]
     pub latency: Family<MerkleTreeApiMethod, Histogram<Duration>>,
+    /// Number of chunks in multiproof responses.
+    #[metrics(buckets = Buckets::exponential(1.0..=64.0, 2.0))]
+    pub multiproof_chunks: Histogram<usize>,
 }
 
 #[vise::register]
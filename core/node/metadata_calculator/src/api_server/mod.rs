@@ -1,10 +1,11 @@
 //! Primitive Merkle tree API used internally to fetch proofs.
 
-use std::{collections::HashMap, fmt, future::Future, net::SocketAddr, pin::Pin};
+use std::{collections::HashMap, fmt, future::Future, mem, net::SocketAddr, pin::Pin};
 
 use anyhow::Context as _;
 use async_trait::async_trait;
 use axum::{
+    body::Body,
     extract::State,
     http::{header, StatusCode},
     response::{IntoResponse, Response},
@@ -27,6 +28,13 @@ mod metrics;
 #[cfg(test)]
 mod tests;
 
+/// Default maximum size of a single multiproof chunk in the multiproof response.
+const DEFAULT_MULTIPROOF_CHUNK_SIZE: usize = 1 << 20; // 1 MiB
+const MIN_MULTIPROOF_CHUNK_SIZE: usize = 4 << 10; // 4 KiB
+const MAX_MULTIPROOF_CHUNK_SIZE: usize = 16 << 20; // 16 MiB
+/// Number of keys for which proofs are built at once when streaming a multiproof response.
+const MULTIPROOF_KEYS_BATCH_SIZE: usize = 1_024;
+
 #[derive(Debug, Serialize, Deserialize)]
 struct TreeProofsRequest {
     l1_batch_number: L1BatchNumber,
@@ -38,6 +46,15 @@ struct TreeProofsResponse {
     entries: Vec<TreeEntryWithProof>,
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+struct TreeMultiProofRequest {
+    l1_batch_number: L1BatchNumber,
+    hashed_keys: Vec<U256>,
+    /// Maximum size of a single multiproof in the response (in bytes).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    max_chunk_size: Option<usize>,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct TreeEntryWithProof {
     #[serde(default, skip_serializing_if = "H256::is_zero")]
@@ -80,6 +97,106 @@ impl TreeEntryWithProof {
     }
 }
 
+/// Merkle multiproof for several tree entries. Unlike separate [`TreeEntryWithProof`]s, a multiproof
+/// doesn't duplicate hashes shared by Merkle paths for different entries.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TreeMultiProof {
+    /// Proven entries ordered by the hashed key.
+    pub entries: Vec<TreeMultiProofEntry>,
+    /// Hashes necessary to restore the root hash. Unlike with [`TreeEntryWithProof`], hashes are ordered
+    /// by the tree level starting from the leaf level, and within a level by the hashed key.
+    pub hashes: Vec<H256>,
+}
+
+/// Entry in a [`TreeMultiProof`].
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TreeMultiProofEntry {
+    pub key: U256,
+    #[serde(default, skip_serializing_if = "H256::is_zero")]
+    pub value: H256,
+    #[serde(default, skip_serializing_if = "TreeEntryWithProof::is_zero")]
+    pub index: u64,
+    /// Length of the Merkle path for the entry if it were proven separately.
+    pub merkle_path_len: u16,
+}
+
+impl TreeMultiProof {
+    // Upper-bound estimates for JSON-serialized multiproof parts.
+    const BASE_JSON_SIZE: usize = 32;
+    const ENTRY_JSON_SIZE: usize = 256;
+    const HASH_JSON_SIZE: usize = 69;
+
+    fn new(src: zksync_merkle_tree::TreeMultiProof) -> Self {
+        let entries = src.entries.into_iter().zip(src.merkle_path_lengths);
+        Self {
+            entries: entries
+                .map(|(entry, merkle_path_len)| TreeMultiProofEntry {
+                    key: entry.key,
+                    value: entry.value,
+                    index: entry.leaf_index,
+                    merkle_path_len,
+                })
+                .collect(),
+            hashes: src.hashes,
+        }
+    }
+
+    /// Splits proofs ordered by key into chunks, so that the JSON-serialized multiproof for each chunk
+    /// doesn't exceed `max_chunk_size` bytes. Each chunk contains at least one proof.
+    fn split_proofs(
+        proofs: Vec<zksync_merkle_tree::TreeEntryWithProof>,
+        max_chunk_size: usize,
+    ) -> Vec<Vec<zksync_merkle_tree::TreeEntryWithProof>> {
+        let mut chunks = vec![];
+        let mut current_chunk: Vec<zksync_merkle_tree::TreeEntryWithProof> = vec![];
+        let mut current_size = Self::BASE_JSON_SIZE;
+        for proof in proofs {
+            // Hashes above the level at which the key diverges from the previous key are shared with
+            // the previous entry, so they don't contribute to the multiproof size.
+            let new_hash_count = match current_chunk.last() {
+                None => proof.merkle_path.len(),
+                Some(prev) => {
+                    let shared_levels =
+                        (prev.base.key ^ proof.base.key).leading_zeros() as usize + 1;
+                    proof.merkle_path.len().saturating_sub(shared_levels)
+                }
+            };
+            let added_size = Self::ENTRY_JSON_SIZE + new_hash_count * Self::HASH_JSON_SIZE;
+            if !current_chunk.is_empty() && current_size + added_size > max_chunk_size {
+                chunks.push(mem::take(&mut current_chunk));
+                current_size = Self::BASE_JSON_SIZE
+                    + Self::ENTRY_JSON_SIZE
+                    + proof.merkle_path.len() * Self::HASH_JSON_SIZE;
+            } else {
+                current_size += added_size;
+            }
+            current_chunk.push(proof);
+        }
+        if !current_chunk.is_empty() {
+            chunks.push(current_chunk);
+        }
+        chunks
+    }
+
+    /// Verifies the multiproof.
+    pub fn verify(&self, trusted_root_hash: H256) -> anyhow::Result<()> {
+        let (entries, merkle_path_lengths) = self
+            .entries
+            .iter()
+            .map(|entry| {
+                let base = zksync_merkle_tree::TreeEntry::new(entry.key, entry.index, entry.value);
+                (base, entry.merkle_path_len)
+            })
+            .unzip();
+        zksync_merkle_tree::TreeMultiProof {
+            entries,
+            merkle_path_lengths,
+            hashes: self.hashes.clone(),
+        }
+        .verify(&Blake2Hasher, trusted_root_hash)
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Hash)]
 struct HexNodeKey(NodeKey);
 
@@ -195,6 +312,13 @@ struct StaleKeysResponse {
 #[derive(Debug)]
 enum TreeApiServerError {
     NoTreeVersion(NoVersionError),
+    TooManyKeys(TooManyKeysData),
+}
+
+#[derive(Debug, Serialize)]
+struct TooManyKeysData {
+    key_count: usize,
+    max_key_count: usize,
 }
 
 // Contains the same fields as `NoVersionError` and is serializable.
@@ -233,6 +357,8 @@ struct Problem<T> {
 }
 
 const PROBLEM_CONTENT_TYPE: &str = "application/problem+json";
+/// Content type for streamed responses consisting of newline-delimited JSON values.
+const NDJSON_CONTENT_TYPE: &str = "application/x-ndjson";
 
 impl IntoResponse for TreeApiServerError {
     fn into_response(self) -> Response {
@@ -247,6 +373,18 @@ impl IntoResponse for TreeApiServerError {
                 };
                 (StatusCode::NOT_FOUND, headers, Json(body)).into_response()
             }
+            Self::TooManyKeys(data) => {
+                let body = Problem {
+                    r#type: "/errors#too-many-keys",
+                    title: "Too many keys requested",
+                    detail: format!(
+                        "requested {} keys, while at most {} are allowed",
+                        data.key_count, data.max_key_count
+                    ),
+                    data,
+                };
+                (StatusCode::BAD_REQUEST, headers, Json(body)).into_response()
+            }
         }
     }
 }
@@ -289,6 +427,15 @@ pub trait TreeApiClient: 'static + Send + Sync + fmt::Debug {
         l1_batch_number: L1BatchNumber,
         hashed_keys: Vec<U256>,
     ) -> Result<Vec<TreeEntryWithProof>, TreeApiError>;
+
+    /// Obtains a Merkle multiproof for the specified `hashed_keys` at the specified tree version (= L1 batch number).
+    /// The multiproof may be split into several chunks, each of which covers a subset of keys and can be verified
+    /// independently. Duplicate keys are proven only once.
+    async fn get_multiproof(
+        &self,
+        l1_batch_number: L1BatchNumber,
+        hashed_keys: Vec<U256>,
+    ) -> Result<Vec<TreeMultiProof>, TreeApiError>;
 }
 
 /// In-memory client implementation.
@@ -316,6 +463,25 @@ impl TreeApiClient for LazyAsyncTreeReader {
             Err(TreeApiError::NotReady(None))
         }
     }
+
+    async fn get_multiproof(
+        &self,
+        l1_batch_number: L1BatchNumber,
+        hashed_keys: Vec<U256>,
+    ) -> Result<Vec<TreeMultiProof>, TreeApiError> {
+        let Some(reader) = self.read() else {
+            return Err(TreeApiError::NotReady(None));
+        };
+        // Since there's no serialization overhead, the multiproof is never split.
+        let chunks = reader
+            .get_multiproof_chunks(l1_batch_number, hashed_keys, usize::MAX)
+            .await
+            .map_err(TreeApiError::NoVersion)?;
+        Ok(chunks
+            .into_iter()
+            .map(|chunk| TreeMultiProof::new(zksync_merkle_tree::TreeMultiProof::new(chunk)))
+            .collect())
+    }
 }
 
 /// [`TreeApiClient`] implementation requesting data from a Merkle tree API server.
@@ -324,6 +490,7 @@ pub struct TreeApiHttpClient {
     inner: reqwest::Client,
     info_url: String,
     proofs_url: String,
+    multiproof_url: String,
 }
 
 impl TreeApiHttpClient {
@@ -338,8 +505,28 @@ impl TreeApiHttpClient {
             inner: client,
             info_url: url_base.to_owned(),
             proofs_url: format!("{url_base}/proofs"),
+            multiproof_url: format!("{url_base}/proofs/multi"),
         }
     }
+
+    /// Converts a "not found" problem response into [`TreeApiError::NoVersion`].
+    async fn check_no_version(
+        response: reqwest::Response,
+    ) -> Result<reqwest::Response, TreeApiError> {
+        let is_problem = response
+            .headers()
+            .get(header::CONTENT_TYPE)
+            .is_some_and(|header| *header == PROBLEM_CONTENT_TYPE);
+        if response.status() == StatusCode::NOT_FOUND && is_problem {
+            // Try to parse `NoVersionError` from the response body.
+            let problem_data: NoVersionErrorData = response
+                .json()
+                .await
+                .context("failed parsing error response")?;
+            return Err(TreeApiError::NoVersion(problem_data.into()));
+        }
+        Ok(response)
+    }
 }
 
 #[async_trait]
@@ -400,19 +587,7 @@ impl TreeApiClient for TreeApiHttpClient {
                 )
             })?;
 
-        let is_problem = response
-            .headers()
-            .get(header::CONTENT_TYPE)
-            .is_some_and(|header| *header == PROBLEM_CONTENT_TYPE);
-        if response.status() == StatusCode::NOT_FOUND && is_problem {
-            // Try to parse `NoVersionError` from the response body.
-            let problem_data: NoVersionErrorData = response
-                .json()
-                .await
-                .context("failed parsing error response")?;
-            return Err(TreeApiError::NoVersion(problem_data.into()));
-        }
-
+        let response = Self::check_no_version(response).await?;
         let response = response.error_for_status().with_context(|| {
             format!("requesting proofs for L1 batch #{l1_batch_number} returned non-OK response")
         })?;
@@ -421,6 +596,52 @@ impl TreeApiClient for TreeApiHttpClient {
         })?;
         Ok(response.entries)
     }
+
+    async fn get_multiproof(
+        &self,
+        l1_batch_number: L1BatchNumber,
+        hashed_keys: Vec<U256>,
+    ) -> Result<Vec<TreeMultiProof>, TreeApiError> {
+        let request_description = format!("multiproof for L1 batch #{l1_batch_number}");
+        let response = self
+            .inner
+            .post(&self.multiproof_url)
+            .json(&TreeMultiProofRequest {
+                l1_batch_number,
+                hashed_keys,
+                max_chunk_size: None,
+            })
+            .send()
+            .await
+            .map_err(|err| TreeApiError::for_request(err, &request_description))?;
+
+        let response = Self::check_no_version(response).await?;
+        let mut response = response.error_for_status().with_context(|| {
+            format!("requesting {request_description} returned non-OK response")
+        })?;
+
+        // The response is streamed as newline-delimited JSON, with each line containing a multiproof chunk.
+        let mut multiproofs = vec![];
+        let mut buffer = vec![];
+        while let Some(bytes) = response
+            .chunk()
+            .await
+            .map_err(|err| TreeApiError::for_request(err, &request_description))?
+        {
+            buffer.extend_from_slice(&bytes);
+            while let Some(line_len) = buffer.iter().position(|&byte| byte == b'\n') {
+                let line: Vec<_> = buffer.drain(..=line_len).collect();
+                let multiproof = serde_json::from_slice(&line)
+                    .with_context(|| format!("failed deserializing {request_description}"))?;
+                multiproofs.push(multiproof);
+            }
+        }
+        if !buffer.iter().all(u8::is_ascii_whitespace) {
+            let err = anyhow::anyhow!("{request_description} is truncated");
+            return Err(err.into());
+        }
+        Ok(multiproofs)
+    }
 }
 
 impl AsyncTreeReader {
@@ -457,6 +678,74 @@ impl AsyncTreeReader {
         Ok(Json(response))
     }
 
+    async fn get_multiproof_chunks(
+        &self,
+        l1_batch_number: L1BatchNumber,
+        mut hashed_keys: Vec<U256>,
+        max_chunk_size: usize,
+    ) -> Result<Vec<Vec<zksync_merkle_tree::TreeEntryWithProof>>, NoVersionError> {
+        hashed_keys.sort_unstable();
+        hashed_keys.dedup();
+        let proofs = self
+            .clone()
+            .entries_with_proofs(l1_batch_number, hashed_keys)
+            .await?;
+        Ok(TreeMultiProof::split_proofs(proofs, max_chunk_size))
+    }
+
+    async fn get_multiproof_handler(
+        State(this): State<Self>,
+        max_key_count: usize,
+        Json(request): Json<TreeMultiProofRequest>,
+    ) -> Result<Response, TreeApiServerError> {
+        let latency = API_METRICS.latency[&MerkleTreeApiMethod::GetMultiProof].start();
+        if request.hashed_keys.len() > max_key_count {
+            return Err(TreeApiServerError::TooManyKeys(TooManyKeysData {
+                key_count: request.hashed_keys.len(),
+                max_key_count,
+            }));
+        }
+        let max_chunk_size = request
+            .max_chunk_size
+            .unwrap_or(DEFAULT_MULTIPROOF_CHUNK_SIZE)
+            .clamp(MIN_MULTIPROOF_CHUNK_SIZE, MAX_MULTIPROOF_CHUNK_SIZE);
+
+        let mut hashed_keys = request.hashed_keys;
+        hashed_keys.sort_unstable();
+        hashed_keys.dedup();
+        let mut key_batches: Vec<_> = hashed_keys
+            .chunks(MULTIPROOF_KEYS_BATCH_SIZE)
+            .map(<[_]>::to_vec)
+            .collect();
+        key_batches.reverse();
+        let mut stream = MultiProofStream {
+            reader: this,
+            l1_batch_number: request.l1_batch_number,
+            max_chunk_size,
+            key_batches,
+            pending_proofs: vec![],
+            ready_chunks: vec![],
+            chunk_count: 0,
+        };
+        // Build proofs for the first batch of keys before responding, so that a missing tree version
+        // is reported with a proper error rather than by aborting the response stream.
+        stream
+            .build_next_proofs()
+            .await
+            .map_err(TreeApiServerError::NoTreeVersion)?;
+        latency.observe();
+
+        // Remaining proofs are built, converted into multiproofs and serialized as the response is streamed,
+        // so that at most one batch of proofs is held in memory at a time.
+        let lines = futures::stream::unfold(stream, |mut stream| async move {
+            let line = stream.next_line().await.transpose()?;
+            Some((line, stream))
+        });
+        let body = Body::from_stream(lines);
+        let headers = [(header::CONTENT_TYPE, NDJSON_CONTENT_TYPE)];
+        Ok((headers, body).into_response())
+    }
+
     async fn get_nodes_handler(
         State(this): State<Self>,
         Json(request): Json<TreeNodesRequest>,
@@ -500,6 +789,7 @@ impl AsyncTreeReader {
     async fn create_api_server(
         self,
         bind_address: &SocketAddr,
+        max_multiproof_keys: usize,
         mut stop_receiver: watch::Receiver<bool>,
     ) -> anyhow::Result<MerkleTreeServer> {
         tracing::debug!("Starting Merkle tree API server on {bind_address}");
@@ -507,6 +797,12 @@ impl AsyncTreeReader {
         let app = Router::new()
             .route("/", routing::get(Self::info_handler))
             .route("/proofs", routing::post(Self::get_proofs_handler))
+            .route(
+                "/proofs/multi",
+                routing::post(move |state, request| {
+                    Self::get_multiproof_handler(state, max_multiproof_keys, request)
+                }),
+            )
             .route("/debug/nodes", routing::post(Self::get_nodes_handler))
             .route(
                 "/debug/stale-keys",
@@ -545,19 +841,81 @@ impl AsyncTreeReader {
         })
     }
 
-    /// Runs the HTTP API server.
+    /// Runs the HTTP API server. Multiproof requests with more than `max_multiproof_keys` keys are rejected.
     pub async fn run_api_server(
         self,
         bind_address: SocketAddr,
+        max_multiproof_keys: usize,
         stop_receiver: watch::Receiver<bool>,
     ) -> anyhow::Result<()> {
-        self.create_api_server(&bind_address, stop_receiver)
+        self.create_api_server(&bind_address, max_multiproof_keys, stop_receiver)
             .await?
             .run()
             .await
     }
 }
 
+/// Lazily built multiproof response. Proofs are built for batches of keys, and split into chunks
+/// that are emitted once they cannot be extended by the following proofs.
+#[derive(Debug)]
+struct MultiProofStream {
+    reader: AsyncTreeReader,
+    l1_batch_number: L1BatchNumber,
+    max_chunk_size: usize,
+    /// Remaining batches of sorted keys in the reverse order.
+    key_batches: Vec<Vec<U256>>,
+    /// Proofs for the last chunk, which may be extended by proofs for the following keys.
+    pending_proofs: Vec<zksync_merkle_tree::TreeEntryWithProof>,
+    /// Complete chunks in the reverse order.
+    ready_chunks: Vec<Vec<zksync_merkle_tree::TreeEntryWithProof>>,
+    chunk_count: usize,
+}
+
+impl MultiProofStream {
+    /// Builds proofs for the next batch of keys. Returns `false` if there are no more keys.
+    async fn build_next_proofs(&mut self) -> Result<bool, NoVersionError> {
+        let Some(keys) = self.key_batches.pop() else {
+            return Ok(false);
+        };
+        let proofs = self
+            .reader
+            .clone()
+            .entries_with_proofs(self.l1_batch_number, keys)
+            .await?;
+        self.pending_proofs.extend(proofs);
+        let proofs = mem::take(&mut self.pending_proofs);
+        let mut chunks = TreeMultiProof::split_proofs(proofs, self.max_chunk_size);
+        self.pending_proofs = chunks.pop().unwrap_or_default();
+        chunks.reverse();
+        // Chunks are ordered by key, so new chunks must be emitted after the existing ones.
+        chunks.append(&mut self.ready_chunks);
+        self.ready_chunks = chunks;
+        Ok(true)
+    }
+
+    /// Returns the next serialized multiproof chunk, or `None` if the response is complete.
+    async fn next_line(&mut self) -> anyhow::Result<Option<Vec<u8>>> {
+        let chunk = loop {
+            if let Some(chunk) = self.ready_chunks.pop() {
+                break chunk;
+            }
+            if !self.build_next_proofs().await? {
+                if self.pending_proofs.is_empty() {
+                    API_METRICS.multiproof_chunks.observe(self.chunk_count);
+                    return Ok(None);
+                }
+                break mem::take(&mut self.pending_proofs);
+            }
+        };
+        self.chunk_count += 1;
+
+        let multiproof = TreeMultiProof::new(zksync_merkle_tree::TreeMultiProof::new(chunk));
+        let mut line = serde_json::to_vec(&multiproof)?;
+        line.push(b'\n');
+        Ok(Some(line))
+    }
+}
+
 /// `axum`-powered REST server for Merkle tree API.
 #[must_use = "Server must be `run()`"]
 struct MerkleTreeServer {
@@ -13,6 +13,8 @@ use zksync_dal::{ConnectionPool, Core};
 use super::*;
 use crate::tests::{gen_storage_logs, reset_db_state, run_calculator, setup_calculator};
 
+const MAX_MULTIPROOF_KEYS: usize = 5_000;
+
 #[tokio::test]
 async fn merkle_tree_api() {
     let pool = ConnectionPool::<Core>::test_pool().await;
@@ -29,7 +31,7 @@ async fn merkle_tree_api() {
         .wait()
         .await
         .unwrap()
-        .create_api_server(&api_addr, stop_receiver.clone())
+        .create_api_server(&api_addr, MAX_MULTIPROOF_KEYS, stop_receiver.clone())
         .await
         .unwrap();
     let local_addr = *api_server.local_addr();
@@ -52,7 +54,7 @@ async fn merkle_tree_api() {
     hashed_keys.extend((0_u8..10).map(|byte| U256::from_big_endian(&[byte; 32])));
 
     let proofs = api_client
-        .get_proofs(L1BatchNumber(5), hashed_keys)
+        .get_proofs(L1BatchNumber(5), hashed_keys.clone())
         .await
         .unwrap();
     assert_eq!(proofs.len(), 20);
@@ -62,6 +64,8 @@ async fn merkle_tree_api() {
         assert!(!proof.merkle_path.is_empty());
     }
 
+    test_multiproofs(&api_client, &local_addr, hashed_keys, tree_info.root_hash).await;
+
     let err = api_client
         .get_proofs(L1BatchNumber(10), vec![])
         .await
@@ -116,6 +120,135 @@ async fn merkle_tree_api() {
     api_server_task.await.unwrap().unwrap();
 }
 
+async fn test_multiproofs(
+    api_client: &TreeApiHttpClient,
+    local_addr: &SocketAddr,
+    mut hashed_keys: Vec<U256>,
+    root_hash: H256,
+) {
+    // Add duplicate keys; they should be proven once.
+    hashed_keys.extend_from_within(..5);
+    let multiproofs = api_client
+        .get_multiproof(L1BatchNumber(5), hashed_keys.clone())
+        .await
+        .unwrap();
+    let [multiproof] = multiproofs.try_into().unwrap();
+    assert_eq!(multiproof.entries.len(), 20);
+    let present_count = multiproof
+        .entries
+        .iter()
+        .filter(|entry| entry.index != 0)
+        .count();
+    assert_eq!(present_count, 10);
+    multiproof.verify(root_hash).unwrap();
+    multiproof.verify(H256::zero()).unwrap_err();
+
+    // Request the multiproof split into small chunks.
+    let max_chunk_size = MIN_MULTIPROOF_CHUNK_SIZE;
+    let response = api_client
+        .inner
+        .post(format!("http://{local_addr}/proofs/multi"))
+        .json(&TreeMultiProofRequest {
+            l1_batch_number: L1BatchNumber(5),
+            hashed_keys,
+            max_chunk_size: Some(max_chunk_size),
+        })
+        .send()
+        .await
+        .unwrap()
+        .error_for_status()
+        .unwrap();
+    assert_eq!(
+        response.headers()[header::CONTENT_TYPE],
+        NDJSON_CONTENT_TYPE
+    );
+    let response = response.text().await.unwrap();
+    let lines: Vec<_> = response.lines().collect();
+    assert!(lines.len() > 1, "{lines:?}");
+
+    let mut proven_keys = vec![];
+    for line in lines {
+        assert!(line.len() < max_chunk_size, "{}", line.len());
+        let multiproof: TreeMultiProof = serde_json::from_str(line).unwrap();
+        multiproof.verify(root_hash).unwrap();
+        proven_keys.extend(multiproof.entries.iter().map(|entry| entry.key));
+    }
+    assert_eq!(proven_keys.len(), 20);
+    assert!(proven_keys.is_sorted());
+
+    let err = api_client
+        .get_multiproof(L1BatchNumber(10), vec![U256::zero()])
+        .await
+        .unwrap_err();
+    assert_matches!(
+        err,
+        TreeApiError::NoVersion(err) if err.missing_version == 10
+    );
+
+    test_multiproof_for_many_keys(api_client, local_addr, root_hash).await;
+}
+
+async fn test_multiproof_for_many_keys(
+    api_client: &TreeApiHttpClient,
+    local_addr: &SocketAddr,
+    root_hash: H256,
+) {
+    // Proofs for these keys are built in several batches.
+    let key_count = MULTIPROOF_KEYS_BATCH_SIZE * 2 + 100;
+    let hashed_keys: Vec<_> = (0..key_count as u64)
+        .map(|i| U256::from(i) * U256::from(u64::MAX) * U256::from(u64::MAX))
+        .collect();
+    let response = api_client
+        .inner
+        .post(format!("http://{local_addr}/proofs/multi"))
+        .json(&TreeMultiProofRequest {
+            l1_batch_number: L1BatchNumber(5),
+            hashed_keys: hashed_keys.clone(),
+            max_chunk_size: Some(MIN_MULTIPROOF_CHUNK_SIZE * 16),
+        })
+        .send()
+        .await
+        .unwrap()
+        .error_for_status()
+        .unwrap();
+    let response = response.text().await.unwrap();
+    let mut proven_keys = vec![];
+    for line in response.lines() {
+        let multiproof: TreeMultiProof = serde_json::from_str(line).unwrap();
+        multiproof.verify(root_hash).unwrap();
+        proven_keys.extend(multiproof.entries.iter().map(|entry| entry.key));
+    }
+    assert_eq!(proven_keys, hashed_keys);
+
+    let multiproofs = api_client
+        .get_multiproof(L1BatchNumber(5), hashed_keys)
+        .await
+        .unwrap();
+    let proven_key_count: usize = multiproofs
+        .iter()
+        .map(|multiproof| multiproof.entries.len())
+        .sum();
+    assert_eq!(proven_key_count, key_count);
+
+    // Requests with too many keys must be rejected.
+    let hashed_keys = (0..=MAX_MULTIPROOF_KEYS as u64).map(U256::from).collect();
+    let response = api_client
+        .inner
+        .post(format!("http://{local_addr}/proofs/multi"))
+        .json(&TreeMultiProofRequest {
+            l1_batch_number: L1BatchNumber(5),
+            hashed_keys,
+            max_chunk_size: None,
+        })
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let problem: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(problem["type"], "/errors#too-many-keys");
+    assert_eq!(problem["max_key_count"], MAX_MULTIPROOF_KEYS);
+}
+
 fn assert_raw_nodes_response(response: &serde_json::Value) {
     let response = response.as_object().expect("not an object");
     let response = response["nodes"].as_object().expect("not an object");
@@ -214,4 +347,17 @@ async fn local_merkle_tree_client() {
     };
     assert_eq!(err.version_count, 6);
     assert_eq!(err.missing_version, 10);
+
+    let hashed_keys: Vec<_> = gen_storage_logs(20..30, 1)[0]
+        .iter()
+        .map(|log| log.key.hashed_key_u256())
+        .collect();
+    let [multiproof] = tree_reader
+        .get_multiproof(L1BatchNumber(5), hashed_keys)
+        .await
+        .unwrap()
+        .try_into()
+        .unwrap();
+    assert_eq!(multiproof.entries.len(), 10);
+    multiproof.verify(tree_info.root_hash).unwrap();
 }
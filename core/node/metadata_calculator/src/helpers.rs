@@ -3,6 +3,7 @@
 use std::{
     collections::{BTreeMap, HashSet},
     future::Future,
+    num::NonZeroU32,
     sync::Arc,
     time::Duration,
 };
@@ -19,6 +20,7 @@ use zksync_config::configs::database::MerkleTreeMode;
 use zksync_dal::{Connection, Core, CoreDal};
 use zksync_health_check::{CheckHealth, Health, HealthStatus, ReactiveHealthCheck};
 use zksync_merkle_tree::{
+    checkpoints::TreeCheckpoints,
     domain::{TreeMetadata, ZkSyncTree, ZkSyncTreeReader},
     recovery::{MerkleTreeRecovery, PersistenceThreadHandle},
     repair::StaleKeysRepairTask,
@@ -35,8 +37,8 @@ use zksync_types::{
 };
 
 use super::{
-    pruning::PruningHandles, MerkleTreeReaderConfig, MetadataCalculatorConfig,
-    MetadataCalculatorRecoveryConfig,
+    pruning::PruningHandles, MerkleTreeCheckpointsConfig, MerkleTreeReaderConfig,
+    MetadataCalculatorConfig, MetadataCalculatorRecoveryConfig,
 };
 
 /// General information about the Merkle tree.
@@ -186,25 +188,32 @@ pub(super) async fn create_readonly_db(
             multi_get_chunk_size,
             block_cache_capacity,
             include_indices_and_filters_in_block_cache,
+            secondary_db_path,
+            ..
         } = config;
 
         tracing::info!(
-            "Initializing Merkle tree database at `{db_path:?}` (max open files: {max_open_files:?}) with {multi_get_chunk_size} multi-get chunk size, \
+            "Initializing Merkle tree database at `{db_path:?}` (max open files: {max_open_files:?}, secondary path: {secondary_db_path:?}) \
+             with {multi_get_chunk_size} multi-get chunk size, \
              {block_cache_capacity}B block cache (indices & filters included: {include_indices_and_filters_in_block_cache:?})"
         );
-        let mut db = RocksDB::with_options(
-            db_path.as_ref(),
-            RocksDBOptions {
-                block_cache_capacity: Some(block_cache_capacity),
-                include_indices_and_filters_in_block_cache,
-                max_open_files,
-                ..RocksDBOptions::default()
-            }
-        )?;
+        let options = RocksDBOptions {
+            block_cache_capacity: Some(block_cache_capacity),
+            include_indices_and_filters_in_block_cache,
+            max_open_files,
+            ..RocksDBOptions::default()
+        };
+        let mut db = if let Some(secondary_db_path) = &secondary_db_path {
+            RocksDB::open_secondary(&db_path, secondary_db_path, options)?
+        } else {
+            RocksDB::with_options(&db_path, options)?
+        };
         if cfg!(test) {
             db = db.with_sync_writes();
         }
-        Ok(RocksDBWrapper::from(db))
+        let mut db = RocksDBWrapper::from(db);
+        db.set_multi_get_chunk_size(multi_get_chunk_size);
+        Ok(db)
     })
     .await
     .context("panicked creating Merkle tree RocksDB")?
@@ -333,6 +342,70 @@ impl AsyncTree {
     }
 }
 
+/// Periodically creates Merkle tree checkpoints and removes obsolete ones.
+#[derive(Debug)]
+pub(super) struct Checkpointer {
+    checkpoints: TreeCheckpoints,
+    interval: NonZeroU32,
+    retained_count: usize,
+    last_checkpoint: Option<L1BatchNumber>,
+}
+
+impl Checkpointer {
+    pub async fn new(config: MerkleTreeCheckpointsConfig) -> anyhow::Result<Self> {
+        let checkpoints = TreeCheckpoints::new(config.path);
+        let checkpoints_clone = checkpoints.clone();
+        let last_checkpoint = tokio::task::spawn_blocking(move || checkpoints_clone.list())
+            .await
+            .context("panicked listing Merkle tree checkpoints")??
+            .last()
+            .copied();
+        tracing::info!(
+            "Initialized Merkle tree checkpoints at `{}` with interval {} and {} retained checkpoints; last checkpoint: {last_checkpoint:?}",
+            checkpoints.path().display(),
+            config.interval,
+            config.retained_count
+        );
+        Ok(Self {
+            checkpoints,
+            interval: config.interval,
+            retained_count: config.retained_count,
+            last_checkpoint,
+        })
+    }
+
+    /// Creates a checkpoint if enough L1 batches were processed by the tree since the last checkpoint.
+    /// Must be called after the tree changes are flushed to RocksDB.
+    pub async fn maybe_create_checkpoint(&mut self, tree: &AsyncTree) -> anyhow::Result<()> {
+        let Some(last_tree_l1_batch) = tree.next_l1_batch_number().0.checked_sub(1) else {
+            return Ok(());
+        };
+        if let Some(last_checkpoint) = self.last_checkpoint {
+            if last_tree_l1_batch < last_checkpoint.0 + self.interval.get() {
+                return Ok(());
+            }
+        }
+
+        let checkpoints = self.checkpoints.clone();
+        let retained_count = self.retained_count;
+        let reader = tree.as_ref().reader();
+        let latency = METRICS.start_stage(TreeUpdateStage::Checkpoint);
+        let created_checkpoint = tokio::task::spawn_blocking(move || {
+            let created_checkpoint = checkpoints.create(&reader)?;
+            checkpoints.retain_latest(retained_count)?;
+            anyhow::Ok(created_checkpoint)
+        })
+        .await
+        .context("panicked creating Merkle tree checkpoint")??;
+        latency.observe();
+
+        if created_checkpoint.is_some() {
+            self.last_checkpoint = created_checkpoint;
+        }
+        Ok(())
+    }
+}
+
 /// Async version of [`ZkSyncTreeReader`].
 #[derive(Debug, Clone)]
 pub struct AsyncTreeReader {
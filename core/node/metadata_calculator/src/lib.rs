@@ -11,6 +11,7 @@ use std::{
 use anyhow::Context as _;
 use tokio::sync::{oneshot, watch};
 use zksync_config::configs::{
+    api::MerkleTreeApiConfig,
     chain::{OperationsManagerConfig, StateKeeperConfig},
     database::{MerkleTreeConfig, MerkleTreeMode},
 };
@@ -21,7 +22,10 @@ use zksync_shared_metrics::tree::METRICS;
 use zksync_types::try_stoppable;
 
 use self::{
-    helpers::{create_db, Delayer, GenericAsyncTree, MerkleTreeHealth, MerkleTreeHealthCheck},
+    helpers::{
+        create_db, Checkpointer, Delayer, GenericAsyncTree, MerkleTreeHealth, MerkleTreeHealthCheck,
+    },
+    metrics::REPLICA_METRICS,
     pruning::PruningHandles,
     updater::TreeUpdater,
 };
@@ -67,6 +71,28 @@ impl Default for MetadataCalculatorRecoveryConfig {
     }
 }
 
+/// Configuration of Merkle tree checkpoints created by [`MetadataCalculator`].
+#[derive(Debug, Clone)]
+pub struct MerkleTreeCheckpointsConfig {
+    /// Filesystem path to the directory with checkpoints. Should be located on the same filesystem as the tree.
+    pub path: PathBuf,
+    /// Minimum interval in L1 batches between consecutive checkpoints.
+    pub interval: NonZeroU32,
+    /// Number of the latest checkpoints to retain.
+    pub retained_count: usize,
+}
+
+impl MerkleTreeCheckpointsConfig {
+    /// Extracts checkpoints configuration from the Merkle tree config. Returns `None` if checkpoints are disabled.
+    pub fn new(merkle_tree_config: &MerkleTreeConfig) -> Option<Self> {
+        Some(Self {
+            path: merkle_tree_config.checkpoints_path.clone()?,
+            interval: merkle_tree_config.checkpoint_interval,
+            retained_count: merkle_tree_config.retained_checkpoints,
+        })
+    }
+}
+
 /// Configuration of [`MetadataCalculator`].
 #[derive(Debug, Clone)]
 pub struct MetadataCalculatorConfig {
@@ -99,6 +125,8 @@ pub struct MetadataCalculatorConfig {
     pub sealed_batches_have_protective_reads: bool,
     /// Configuration specific to the Merkle tree recovery.
     pub recovery: MetadataCalculatorRecoveryConfig,
+    /// Configuration of Merkle tree checkpoints. If not set, checkpoints are not created.
+    pub checkpoints: Option<MerkleTreeCheckpointsConfig>,
 }
 
 impl MetadataCalculatorConfig {
@@ -122,6 +150,7 @@ impl MetadataCalculatorConfig {
                 .protective_reads_persistence_enabled,
             // The main node isn't supposed to be recovered yet, so this value doesn't matter much
             recovery: MetadataCalculatorRecoveryConfig::default(),
+            checkpoints: MerkleTreeCheckpointsConfig::new(merkle_tree_config),
         }
     }
 }
@@ -162,6 +191,12 @@ impl MetadataCalculator {
             config.max_l1_batches_per_iter > 0,
             "Maximum L1 batches per iteration is misconfigured to be 0; please update it to positive value"
         );
+        if let Some(checkpoints) = &config.checkpoints {
+            anyhow::ensure!(
+                checkpoints.retained_count > 0,
+                "Number of retained Merkle tree checkpoints is misconfigured to be 0; please update it to positive value"
+            );
+        }
         if matches!(config.mode, MerkleTreeMode::Lightweight) && object_store.is_some() {
             anyhow::bail!(
                 "Cannot run lightweight tree with an object store; the tree won't produce information to be stored in the store"
@@ -264,12 +299,15 @@ impl MetadataCalculator {
         self.health_updater
             .update(MerkleTreeHealth::MainLoop(tree_info).into());
 
-        let updater = TreeUpdater::new(
+        let mut updater = TreeUpdater::new(
             tree,
             self.max_l1_batches_per_iter,
             self.object_store,
             self.config.sealed_batches_have_protective_reads,
         );
+        if let Some(checkpoints_config) = self.config.checkpoints {
+            updater = updater.with_checkpointer(Checkpointer::new(checkpoints_config).await?);
+        }
         updater
             .loop_updating_tree(self.delayer, &self.pool, stop_receiver)
             .await
@@ -293,9 +331,38 @@ pub struct MerkleTreeReaderConfig {
     /// being loaded entirely into RAM on the RocksDB initialization. The block cache capacity should be increased
     /// correspondingly; otherwise, RocksDB performance can significantly degrade.
     pub include_indices_and_filters_in_block_cache: bool,
+    /// If specified, the tree RocksDB is opened in the secondary mode, with secondary instance data stored at this path.
+    /// In this mode, the tree can be read while it's updated by another process (e.g., by [`MetadataCalculator`]),
+    /// and the reader periodically catches up with these updates.
+    pub secondary_db_path: Option<PathBuf>,
+    /// Interval between catching up with the primary tree instance. Only used if `secondary_db_path` is specified.
+    pub catch_up_interval: Duration,
+}
+
+impl MerkleTreeReaderConfig {
+    /// Creates a reader configuration for the main node. The reader is opened in the secondary mode
+    /// if it's enabled in the tree API config.
+    pub fn for_main_node(
+        merkle_tree_config: &MerkleTreeConfig,
+        api_config: &MerkleTreeApiConfig,
+    ) -> Self {
+        Self {
+            db_path: merkle_tree_config.path.clone(),
+            max_open_files: None,
+            multi_get_chunk_size: merkle_tree_config.multi_get_chunk_size,
+            block_cache_capacity: merkle_tree_config.block_cache_size_mb.0 as usize,
+            include_indices_and_filters_in_block_cache: false,
+            secondary_db_path: api_config.secondary_path.clone(),
+            catch_up_interval: api_config.catch_up_interval,
+        }
+    }
 }
 
 /// Alternative to [`MetadataCalculator`] that provides readonly access to the Merkle tree.
+///
+/// If the tree RocksDB is opened [in the secondary mode](MerkleTreeReaderConfig::secondary_db_path), the task
+/// runs until it's stopped, periodically catching up with the primary tree instance. Otherwise, the task exits
+/// once the reader is initialized.
 #[derive(Debug)]
 pub struct TreeReaderTask {
     config: MerkleTreeReaderConfig,
@@ -318,12 +385,42 @@ impl TreeReaderTask {
 
     /// Runs this task. The task exits on error, or when the tree reader is successfully initialized.
     pub async fn run(self, mut stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
+        let is_secondary = self.config.secondary_db_path.is_some();
+        let catch_up_interval = self.config.catch_up_interval;
         let db = tokio::select! {
             db_result = create_readonly_db(self.config) => db_result?,
             _ = stop_receiver.changed() => return Ok(()),
         };
-        let reader = AsyncTreeReader::new(db, MerkleTreeMode::Lightweight)?;
-        self.tree_reader.send_replace(Some(reader));
+        let reader = AsyncTreeReader::new(db.clone(), MerkleTreeMode::Lightweight)?;
+        self.tree_reader.send_replace(Some(reader.clone()));
+        if !is_secondary {
+            return Ok(());
+        }
+
+        tracing::info!(
+            "Merkle tree reader is following the primary tree instance with {catch_up_interval:?} interval"
+        );
+        while !*stop_receiver.borrow_and_update() {
+            let latency = REPLICA_METRICS.catch_up_latency.start();
+            let db = db.clone();
+            tokio::task::spawn_blocking(move || db.try_catch_up_with_primary())
+                .await
+                .context("panicked catching up with primary Merkle tree")??;
+            latency.observe();
+
+            let next_l1_batch_number = reader.clone().info().await.next_l1_batch_number;
+            REPLICA_METRICS
+                .next_l1_batch_number
+                .set(next_l1_batch_number.0.into());
+
+            if tokio::time::timeout(catch_up_interval, stop_receiver.changed())
+                .await
+                .is_ok()
+            {
+                break;
+            }
+        }
+        tracing::info!("Stop request received, Merkle tree reader is shutting down");
         Ok(())
     }
 }
@@ -59,3 +59,17 @@ pub(super) struct MetadataCalculatorRecoveryMetrics {
 #[vise::register]
 pub(super) static RECOVERY_METRICS: vise::Global<MetadataCalculatorRecoveryMetrics> =
     vise::Global::new();
+
+/// Metrics for a Merkle tree reader following the primary tree instance in the RocksDB secondary mode.
+#[derive(Debug, Metrics)]
+#[metrics(prefix = "server_merkle_tree_replica")]
+pub(super) struct MerkleTreeReplicaMetrics {
+    /// Latency of catching up with the primary tree instance.
+    #[metrics(buckets = Buckets::LATENCIES, unit = Unit::Seconds)]
+    pub catch_up_latency: Histogram<Duration>,
+    /// Next L1 batch number to be processed by the tree, as observed by the reader.
+    pub next_l1_batch_number: Gauge<u64>,
+}
+
+#[vise::register]
+pub(super) static REPLICA_METRICS: vise::Global<MerkleTreeReplicaMetrics> = vise::Global::new();
@@ -122,6 +122,7 @@ impl WiringLayer for MetadataCalculatorLayer {
             let tree_reader = metadata_calculator.tree_reader();
             TreeApiTask {
                 bind_addr,
+                max_multiproof_keys: tree_api_config.max_multiproof_keys,
                 tree_reader,
             }
         });
@@ -15,6 +15,7 @@ use crate::{
 #[derive(Debug)]
 pub struct TreeApiTask {
     pub(super) bind_addr: SocketAddr,
+    pub(super) max_multiproof_keys: usize,
     pub(super) tree_reader: LazyAsyncTreeReader,
 }
 
@@ -26,7 +27,9 @@ impl Task for TreeApiTask {
 
     async fn run(self: Box<Self>, mut stop_receiver: StopReceiver) -> anyhow::Result<()> {
         if let Some(reader) = self.tree_reader.wait().await {
-            reader.run_api_server(self.bind_addr, stop_receiver.0).await
+            reader
+                .run_api_server(self.bind_addr, self.max_multiproof_keys, stop_receiver.0)
+                .await
         } else {
             // Tree is dropped before initialized, e.g. because the node is getting shut down.
             // We don't want to treat this as an error since it could mask the real shutdown cause in logs etc.
@@ -37,7 +40,10 @@ impl Task for TreeApiTask {
     }
 }
 
-/// Mutually exclusive with [`MetadataCalculatorLayer`].
+/// Mutually exclusive with [`MetadataCalculatorLayer`] in the same process, unless the tree reader is configured
+/// to use the RocksDB secondary mode (in which case, the tree can be updated by another process).
+///
+/// [`MetadataCalculatorLayer`]: super::MetadataCalculatorLayer
 #[derive(Debug)]
 pub struct TreeApiServerLayer {
     config: MerkleTreeReaderConfig,
@@ -73,6 +79,7 @@ impl WiringLayer for TreeApiServerLayer {
         let bind_addr = (Ipv4Addr::UNSPECIFIED, self.api_config.port).into();
         let tree_api_task = TreeApiTask {
             bind_addr,
+            max_multiproof_keys: self.api_config.max_multiproof_keys,
             tree_reader: tree_reader_task.tree_reader(),
         };
         Ok(TreeApiServerOutput {
@@ -86,7 +93,12 @@ impl WiringLayer for TreeApiServerLayer {
 #[async_trait::async_trait]
 impl Task for TreeReaderTask {
     fn kind(&self) -> TaskKind {
-        TaskKind::OneshotTask
+        if self.config.secondary_db_path.is_some() {
+            // The task continuously catches up with the primary tree instance.
+            TaskKind::Task
+        } else {
+            TaskKind::OneshotTask
+        }
     }
 
     fn id(&self) -> TaskId {
@@ -1,18 +1,19 @@
 //! Tests for the metadata calculator component life cycle.
 
-use std::{future::Future, ops, panic, path::Path, sync::Arc, time::Duration};
+use std::{future::Future, num::NonZeroU32, ops, panic, path::Path, sync::Arc, time::Duration};
 
 use assert_matches::assert_matches;
 use tempfile::TempDir;
 use test_casing::{test_casing, Product};
 use tokio::sync::{mpsc, watch};
 use zksync_config::configs::{
+    api::MerkleTreeApiConfig,
     chain::{OperationsManagerConfig, StateKeeperConfig},
     database::{MerkleTreeConfig, MerkleTreeMode},
 };
 use zksync_dal::{Connection, ConnectionPool, Core, CoreDal};
 use zksync_health_check::{CheckHealth, HealthStatus};
-use zksync_merkle_tree::domain::ZkSyncTree;
+use zksync_merkle_tree::{checkpoints::TreeCheckpoints, domain::ZkSyncTree};
 use zksync_node_genesis::{insert_genesis_batch, GenesisParams};
 use zksync_node_test_utils::{
     create_l1_batch, create_l2_block, generate_storage_logs, insert_initial_writes_for_batch,
@@ -26,8 +27,8 @@ use zksync_types::{
 };
 
 use super::{
-    helpers::L1BatchWithLogs, GenericAsyncTree, MetadataCalculator, MetadataCalculatorConfig,
-    MetadataCalculatorRecoveryConfig,
+    helpers::L1BatchWithLogs, GenericAsyncTree, MerkleTreeReaderConfig, MetadataCalculator,
+    MetadataCalculatorConfig, MetadataCalculatorRecoveryConfig, TreeReaderTask,
 };
 use crate::helpers::{AsyncTree, Delayer};
 
@@ -59,6 +60,7 @@ pub(super) fn mock_config(db_path: &Path) -> MetadataCalculatorConfig {
         stalled_writes_timeout: Duration::ZERO, // writes should never be stalled in tests
         sealed_batches_have_protective_reads: true,
         recovery: MetadataCalculatorRecoveryConfig::default(),
+        checkpoints: None,
     }
 }
 
@@ -387,6 +389,48 @@ async fn multi_l1_batch_workflow(sealed_protective_reads: bool) {
     }
 }
 
+#[tokio::test]
+async fn creating_tree_checkpoints() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let temp_dir = TempDir::new().expect("failed get temporary directory for RocksDB");
+    let checkpoints_path = temp_dir.path().join("checkpoints");
+    let (mut merkle_tree_config, operation_config) =
+        create_config(temp_dir.path(), MerkleTreeMode::Lightweight);
+    merkle_tree_config.max_l1_batches_per_iter = 1;
+    merkle_tree_config.checkpoints_path = Some(checkpoints_path.clone());
+    merkle_tree_config.checkpoint_interval = NonZeroU32::new(3).unwrap();
+    merkle_tree_config.retained_checkpoints = 2;
+    let calculator = setup_calculator_with_options(
+        &merkle_tree_config,
+        &operation_config,
+        &StateKeeperConfig::for_tests(),
+        pool.clone(),
+        None,
+    )
+    .await;
+    reset_db_state(&pool, 10).await;
+    run_calculator(calculator).await;
+
+    // Checkpoints should be created for L1 batches #1, #4, #7 and #10; only 2 latest ones are retained.
+    let checkpoints = TreeCheckpoints::new(checkpoints_path);
+    assert_eq!(
+        checkpoints.list().unwrap(),
+        [L1BatchNumber(7), L1BatchNumber(10)]
+    );
+    let tree_path = temp_dir.path().join("restored");
+    checkpoints.restore(L1BatchNumber(7), &tree_path).unwrap();
+    let tree = ZkSyncTree::new_lightweight(RocksDB::new(&tree_path).unwrap().into()).unwrap();
+    assert_eq!(tree.next_l1_batch_number(), L1BatchNumber(8));
+
+    let mut storage = pool.connection().await.unwrap();
+    let expected_root_hash = storage
+        .blocks_dal()
+        .get_l1_batch_state_root(L1BatchNumber(7))
+        .await
+        .unwrap();
+    assert_eq!(Some(tree.root_hash()), expected_root_hash);
+}
+
 #[test_casing(2, [false, true])]
 #[tokio::test]
 async fn error_on_pruned_next_l1_batch(sealed_protective_reads: bool) {
@@ -488,6 +532,79 @@ async fn running_metadata_calculator_with_additional_blocks(sealed_protective_re
     assert_eq!(root_hash_for_full_tree, updated_root_hash);
 }
 
+#[tokio::test]
+async fn tree_reader_in_secondary_mode_follows_primary_tree() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let temp_dir = TempDir::new().expect("failed get temporary directory for RocksDB");
+    let mut calculator = setup_lightweight_calculator(temp_dir.path(), pool.clone(), true).await;
+    reset_db_state(&pool, 5).await;
+
+    let (stop_sx, stop_rx) = watch::channel(false);
+    let (delay_sx, mut delay_rx) = mpsc::unbounded_channel();
+    calculator.delayer.delay_notifier = delay_sx;
+    let calculator_handle = tokio::spawn(calculator.run(stop_rx.clone()));
+    let (next_l1_batch, _) = tokio::time::timeout(RUN_TIMEOUT, delay_rx.recv())
+        .await
+        .expect("metadata calculator timed out processing initial blocks")
+        .unwrap();
+    assert_eq!(next_l1_batch, L1BatchNumber(6));
+
+    // Start a reader following the tree updated by the calculator.
+    let (merkle_tree_config, _) = create_config(temp_dir.path(), MerkleTreeMode::Lightweight);
+    let api_config = MerkleTreeApiConfig {
+        port: 0,
+        secondary_path: Some(temp_dir.path().join("secondary")),
+        catch_up_interval: POLL_INTERVAL,
+        max_multiproof_keys: 1_000,
+    };
+    let reader_task = TreeReaderTask::new(MerkleTreeReaderConfig::for_main_node(
+        &merkle_tree_config,
+        &api_config,
+    ));
+    let tree_reader = reader_task.tree_reader();
+    let reader_handle = tokio::spawn(reader_task.run(stop_rx));
+    let reader = run_with_timeout(RUN_TIMEOUT, tree_reader.wait())
+        .await
+        .expect("tree reader was not initialized");
+    let info = reader.clone().info().await;
+    assert_eq!(info.next_l1_batch_number, L1BatchNumber(6));
+
+    let new_logs = gen_storage_logs(100..200, 10);
+    extend_db_state(&mut pool.connection().await.unwrap(), new_logs).await;
+    let updated_root_hash = loop {
+        let (next_l1_batch, root_hash) = tokio::time::timeout(RUN_TIMEOUT, delay_rx.recv())
+            .await
+            .expect("metadata calculator shut down prematurely")
+            .unwrap();
+        if next_l1_batch == L1BatchNumber(16) {
+            break root_hash;
+        }
+    };
+
+    // Wait until the reader catches up with the calculator.
+    let info = run_with_timeout(RUN_TIMEOUT, async {
+        loop {
+            let info = reader.clone().info().await;
+            if info.next_l1_batch_number == L1BatchNumber(16) {
+                break info;
+            }
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+    })
+    .await;
+    assert_eq!(info.root_hash, updated_root_hash);
+
+    stop_sx.send_replace(true);
+    run_with_timeout(RUN_TIMEOUT, calculator_handle)
+        .await
+        .unwrap()
+        .unwrap();
+    run_with_timeout(RUN_TIMEOUT, reader_handle)
+        .await
+        .unwrap()
+        .unwrap();
+}
+
 #[tokio::test]
 async fn shutting_down_calculator() {
     let pool = ConnectionPool::<Core>::test_pool().await;
@@ -15,7 +15,7 @@ use zksync_types::{
     L1BatchNumber, OrStopped,
 };
 
-use super::helpers::{AsyncTree, Delayer, L1BatchWithLogs};
+use super::helpers::{AsyncTree, Checkpointer, Delayer, L1BatchWithLogs};
 
 #[derive(Debug)]
 pub(super) struct TreeUpdater {
@@ -23,6 +23,7 @@ pub(super) struct TreeUpdater {
     max_l1_batches_per_iter: usize,
     object_store: Option<Arc<dyn ObjectStore>>,
     sealed_batches_have_protective_reads: bool,
+    checkpointer: Option<Checkpointer>,
 }
 
 impl TreeUpdater {
@@ -37,9 +38,15 @@ impl TreeUpdater {
             max_l1_batches_per_iter,
             object_store,
             sealed_batches_have_protective_reads,
+            checkpointer: None,
         }
     }
 
+    pub fn with_checkpointer(mut self, checkpointer: Checkpointer) -> Self {
+        self.checkpointer = Some(checkpointer);
+        self
+    }
+
     async fn process_l1_batch(
         &mut self,
         l1_batch: L1BatchWithLogs,
@@ -170,6 +177,9 @@ impl TreeUpdater {
         let save_rocksdb_latency = METRICS.start_stage(TreeUpdateStage::SaveRocksdb);
         self.tree.save().await?;
         save_rocksdb_latency.observe();
+        if let Some(checkpointer) = &mut self.checkpointer {
+            checkpointer.maybe_create_checkpoint(&self.tree).await?;
+        }
         update_tree_metrics(&updated_batch_stats, total_logs, start);
 
         Ok(last_l1_batch_number + 1)
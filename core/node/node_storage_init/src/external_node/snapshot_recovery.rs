@@ -69,6 +69,10 @@ impl InitializeStorage for ExternalNodeSnapshotRecovery {
             tracing::info!("Dropping storage key preimages for snapshot storage logs");
             snapshots_applier_task.drop_storage_key_preimages();
         }
+        if let Some(header_signer) = self.recovery_config.header_signer {
+            tracing::info!("Verifying snapshot header signatures by {header_signer:?}");
+            snapshots_applier_task.set_header_signer(header_signer);
+        }
         self.app_health
             .insert_component(snapshots_applier_task.health_check())
             .map_err(OrStopped::internal)?;
@@ -133,6 +137,7 @@ mod tests {
                 snapshot_l1_batch_override: None,
                 drop_storage_key_preimages: false,
                 object_store_config: None,
+                header_signer: None,
             },
             app_health,
         };
@@ -3,7 +3,7 @@ use std::{future::Future, sync::Arc, time::Duration};
 use tokio::sync::watch;
 use zksync_config::ObjectStoreConfig;
 use zksync_dal::{ConnectionPool, Core, CoreDal as _};
-use zksync_types::{try_stoppable, L1BatchNumber, OrStopped, StopContext};
+use zksync_types::{try_stoppable, Address, L1BatchNumber, OrStopped, StopContext};
 
 pub use crate::traits::{InitializeStorage, RevertStorage};
 
@@ -18,6 +18,8 @@ pub struct SnapshotRecoveryConfig {
     pub snapshot_l1_batch_override: Option<L1BatchNumber>,
     pub drop_storage_key_preimages: bool,
     pub object_store_config: Option<ObjectStoreConfig>,
+    /// If specified, snapshot headers must be signed by this address.
+    pub header_signer: Option<Address>,
 }
 
 #[derive(Debug, Clone, Copy)]
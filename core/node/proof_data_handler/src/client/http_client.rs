@@ -1,23 +1,32 @@
+use std::time::Duration;
+
+use secrecy::ExposeSecret;
 use serde::{de::DeserializeOwned, Serialize};
-use zksync_prover_interface::api::{
-    PollGeneratedProofsRequest, PollGeneratedProofsResponse, ProofGenerationData,
-    SubmitProofGenerationDataResponse,
+use zksync_prover_interface::{
+    api::{
+        PollGeneratedProofsRequest, PollGeneratedProofsResponse, ProofGenerationData,
+        SubmitProofGenerationDataResponse, WaitForGeneratedProofRequest,
+    },
+    long_poll,
 };
-use zksync_types::L1BatchId;
+use zksync_types::{secrets::APIKey, L1BatchId};
 
 pub(crate) struct HttpClient {
     api_url: String,
     client: reqwest::Client,
+    auth_token: Option<APIKey>,
 }
 
 const SUBMIT_REQUEST_FOR_PROOFS_ENDPOINT: &str = "/submit_request_for_proofs";
 const POLL_GENERATED_PROOFS_ENDPOINT: &str = "/poll_generated_proofs";
+const WAIT_FOR_GENERATED_PROOF_ENDPOINT: &str = "/wait_for_generated_proof";
 
 impl HttpClient {
-    pub(crate) fn new(api_url: String) -> Self {
+    pub(crate) fn new(api_url: String, auth_token: Option<APIKey>) -> Self {
         Self {
             api_url,
             client: reqwest::Client::new(),
+            auth_token,
         }
     }
 
@@ -45,6 +54,33 @@ impl HttpClient {
         self.send_http_request(request, &endpoint).await
     }
 
+    /// Waits for the gateway to generate the proof for the batch. Returns `None` if the proof
+    /// wasn't generated within `timeout`.
+    pub(crate) async fn wait_for_proof(
+        &self,
+        l1_batch_id: L1BatchId,
+        timeout: Duration,
+    ) -> Result<Option<PollGeneratedProofsResponse>, reqwest::Error> {
+        tracing::info!("Sending request to {}", WAIT_FOR_GENERATED_PROOF_ENDPOINT);
+
+        let endpoint = self.api_url.clone() + WAIT_FOR_GENERATED_PROOF_ENDPOINT;
+
+        let request = WaitForGeneratedProofRequest {
+            l1_batch_id,
+            timeout_secs: timeout.as_secs(),
+        };
+
+        let mut builder = self
+            .client
+            .post(endpoint)
+            .json(&request)
+            .timeout(timeout + long_poll::RESPONSE_MARGIN);
+        if let Some(token) = &self.auth_token {
+            builder = builder.bearer_auth(token.expose_secret());
+        }
+        builder.send().await?.error_for_status()?.json().await
+    }
+
     async fn send_http_request<Req, Resp>(
         &self,
         request: Req,
@@ -6,7 +6,7 @@ use tokio::sync::watch;
 use zksync_config::configs::ProofDataHandlerConfig;
 use zksync_dal::{ConnectionPool, Core};
 use zksync_object_store::ObjectStore;
-use zksync_types::{commitment::L1BatchCommitmentMode, L2ChainId};
+use zksync_types::{commitment::L1BatchCommitmentMode, secrets::APIKey, L2ChainId};
 
 mod http_client;
 mod proof_fetcher;
@@ -16,6 +16,7 @@ pub struct ProofDataHandlerClient {
     pub blob_store: Arc<dyn ObjectStore>,
     pub pool: ConnectionPool<Core>,
     pub config: ProofDataHandlerConfig,
+    pub push_auth_token: Option<APIKey>,
     pub batch_commitment_mode: L1BatchCommitmentMode,
     pub l2_chain_id: L2ChainId,
 }
@@ -25,6 +26,7 @@ impl ProofDataHandlerClient {
         blob_store: Arc<dyn ObjectStore>,
         pool: ConnectionPool<Core>,
         config: ProofDataHandlerConfig,
+        push_auth_token: Option<APIKey>,
         batch_commitment_mode: L1BatchCommitmentMode,
         l2_chain_id: L2ChainId,
     ) -> Self {
@@ -32,6 +34,7 @@ impl ProofDataHandlerClient {
             blob_store,
             pool,
             config,
+            push_auth_token,
             batch_commitment_mode,
             l2_chain_id,
         }
@@ -52,6 +55,7 @@ impl ProofDataHandlerClient {
             self.blob_store,
             self.pool,
             self.config,
+            self.push_auth_token,
             self.batch_commitment_mode,
             self.l2_chain_id,
         )
@@ -1,10 +1,11 @@
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 
-use tokio::sync::watch;
+use tokio::{sync::watch, time::Instant};
 use zksync_config::configs::ProofDataHandlerConfig;
 use zksync_dal::{ConnectionPool, Core};
 use zksync_object_store::ObjectStore;
-use zksync_types::{commitment::L1BatchCommitmentMode, L1BatchId, L2ChainId};
+use zksync_prover_interface::{api::PollGeneratedProofsResponse, long_poll::PushMode};
+use zksync_types::{commitment::L1BatchCommitmentMode, secrets::APIKey, L1BatchId, L2ChainId};
 
 use super::http_client::HttpClient;
 use crate::processor::{Locking, Processor};
@@ -13,6 +14,9 @@ pub(crate) struct ProofFetcher {
     processor: Processor<Locking>,
     config: ProofDataHandlerConfig,
     client: HttpClient,
+    /// Whether proofs are awaited with long-poll requests. Disabled if the gateway doesn't
+    /// support them, in which case the fetcher falls back to polling.
+    push_mode: PushMode,
 }
 
 impl ProofFetcher {
@@ -20,6 +24,7 @@ impl ProofFetcher {
         blob_store: Arc<dyn ObjectStore>,
         pool: ConnectionPool<Core>,
         config: ProofDataHandlerConfig,
+        push_auth_token: Option<APIKey>,
         commitment_mode: L1BatchCommitmentMode,
         l2_chain_id: L2ChainId,
     ) -> Self {
@@ -35,9 +40,10 @@ impl ProofFetcher {
             panic!("Gateway API URL should be set if running in prover cluster mode");
         };
 
-        let client = HttpClient::new(api_url);
+        let client = HttpClient::new(api_url, push_auth_token);
         Self {
             processor,
+            push_mode: PushMode::new(config.push_mode),
             config,
             client,
         }
@@ -60,10 +66,22 @@ impl ProofFetcher {
                 continue;
             };
 
-            if let Err(e) = self
-                .fetch_proof(L1BatchId::new(self.processor.chain_id(), batch_to_fetch))
-                .await
-            {
+            let l1_batch_id = L1BatchId::new(self.processor.chain_id(), batch_to_fetch);
+            // Time the gateway has held a successful long-poll request for; it's subtracted from
+            // the fetch interval. Requests answered right away (e.g., rejected by a saturated gateway)
+            // don't shorten the interval.
+            let mut waited = Duration::ZERO;
+            if self.push_mode.is_enabled() {
+                let started_at = Instant::now();
+                match self.wait_for_proof(l1_batch_id).await {
+                    Ok(()) => waited = started_at.elapsed(),
+                    Err(e) => {
+                        tracing::error!(
+                            "Long-poll request failed to get proof for batch {batch_to_fetch}: {e}"
+                        );
+                    }
+                }
+            } else if let Err(e) = self.fetch_proof(l1_batch_id).await {
                 tracing::error!("Request failed to get proof for batch {batch_to_fetch}: {e}");
             }
 
@@ -76,18 +94,44 @@ impl ProofFetcher {
                 }
             }
 
-            tracing::info!(
-                "No proof was fetched, sleeping for {:?}",
-                self.config.proof_fetch_interval_in_secs
-            );
-            tokio::time::sleep(self.config.proof_fetch_interval_in_secs).await;
+            let sleep_duration = self
+                .config
+                .proof_fetch_interval_in_secs
+                .saturating_sub(waited);
+            if sleep_duration.is_zero() {
+                continue;
+            }
+            tracing::info!("No proof was fetched, sleeping for {sleep_duration:?}");
+            tokio::time::sleep(sleep_duration).await;
         }
 
         Ok(())
     }
 
+    async fn wait_for_proof(&self, l1_batch_id: L1BatchId) -> anyhow::Result<()> {
+        let response = self
+            .client
+            .wait_for_proof(l1_batch_id, self.config.long_poll_timeout_in_secs)
+            .await;
+        if let Err(e) = &response {
+            if self.push_mode.handle_error_status(e.status()) {
+                tracing::warn!("Gateway doesn't support long-polling, falling back to polling");
+            }
+        }
+        self.handle_response(l1_batch_id, response).await
+    }
+
     async fn fetch_proof(&self, l1_batch_id: L1BatchId) -> anyhow::Result<()> {
-        match self.client.fetch_proof(l1_batch_id).await {
+        let response = self.client.fetch_proof(l1_batch_id).await;
+        self.handle_response(l1_batch_id, response).await
+    }
+
+    async fn handle_response(
+        &self,
+        l1_batch_id: L1BatchId,
+        response: Result<Option<PollGeneratedProofsResponse>, reqwest::Error>,
+    ) -> anyhow::Result<()> {
+        match response {
             Ok(Some(response)) => {
                 if l1_batch_id.chain_id() != L2ChainId::zero()
                     && response.l1_batch_id.chain_id() != self.processor.chain_id()
@@ -35,7 +35,7 @@ impl ProofGenDataSubmitter {
             panic!("Gateway API URL should be set if running in prover cluster mode");
         };
 
-        let client = HttpClient::new(api_url);
+        let client = HttpClient::new(api_url, None);
         Self {
             processor,
             config,
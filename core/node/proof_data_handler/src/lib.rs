@@ -1,17 +1,28 @@
-use std::{net::SocketAddr, sync::Arc};
+use std::{net::SocketAddr, sync::Arc, time::Duration};
 
 use anyhow::Context as _;
-use axum::{extract::Path, http::StatusCode, response::IntoResponse, routing::post, Json, Router};
+use axum::{
+    extract::Path,
+    http::{HeaderMap, StatusCode},
+    response::IntoResponse,
+    routing::post,
+    Json, Router,
+};
 use processor::Locking;
 use tokio::sync::watch;
 use zksync_config::configs::{fri_prover_gateway::ApiMode, ProofDataHandlerConfig};
 use zksync_dal::{ConnectionPool, Core};
 use zksync_object_store::ObjectStore;
-use zksync_prover_interface::api::{
-    ProofGenerationDataRequest, ProofGenerationDataResponse, SubmitProofRequest,
-    SubmitProofResponse,
+use zksync_prover_interface::{
+    api::{
+        ProofGenerationDataRequest, ProofGenerationDataResponse, SubmitProofRequest,
+        SubmitProofResponse, WaitForProofGenerationDataRequest,
+    },
+    long_poll::{self, LongPollWaiter, WaitError},
+};
+use zksync_types::{
+    commitment::L1BatchCommitmentMode, secrets::APIKey, L1BatchId, L1BatchNumber, L2ChainId,
 };
-use zksync_types::{commitment::L1BatchCommitmentMode, L1BatchId, L1BatchNumber, L2ChainId};
 
 pub use crate::{
     client::ProofDataHandlerClient,
@@ -27,6 +38,7 @@ mod processor;
 
 pub async fn run_server(
     config: ProofDataHandlerConfig,
+    push_auth_token: Option<APIKey>,
     blob_store: Arc<dyn ObjectStore>,
     connection_pool: ConnectionPool<Core>,
     commitment_mode: L1BatchCommitmentMode,
@@ -40,6 +52,7 @@ pub async fn run_server(
         blob_store,
         connection_pool,
         config,
+        push_auth_token,
         api_mode,
         commitment_mode,
         l2_chain_id,
@@ -65,6 +78,7 @@ fn create_proof_processing_router(
     blob_store: Arc<dyn ObjectStore>,
     connection_pool: ConnectionPool<Core>,
     config: ProofDataHandlerConfig,
+    push_auth_token: Option<APIKey>,
     api_mode: ApiMode,
     commitment_mode: L1BatchCommitmentMode,
     l2_chain_id: L2ChainId,
@@ -80,6 +94,7 @@ fn create_proof_processing_router(
             l2_chain_id,
         );
         let submit_proof_processor = get_proof_gen_processor.clone();
+        let wait_proof_gen_processor = get_proof_gen_processor.clone();
 
         router = router.route(
             "/proof_generation_data",
@@ -115,6 +130,39 @@ fn create_proof_processing_router(
                 },
             ),
         );
+
+        // Long-poll version of `/proof_generation_data` used by the gateway in push mode. It's only served
+        // if the auth token is configured; otherwise, the gateway gets 404 and falls back to polling.
+        if let Some(push_auth_token) = push_auth_token {
+            let waiter = Arc::new(LongPollWaiter::default());
+            router = router.route(
+                "/wait_for_proof_generation_data",
+                post(
+                    move |headers: HeaderMap, Json(request): Json<WaitForProofGenerationDataRequest>| async move {
+                        if !long_poll::is_authorized(&headers, &push_auth_token) {
+                            return StatusCode::UNAUTHORIZED.into_response();
+                        }
+                        let timeout = Duration::from_secs(request.timeout_secs);
+                        let data = waiter
+                            .wait_for(timeout, || {
+                                wait_proof_gen_processor.get_proof_generation_data()
+                            })
+                            .await;
+                        match data {
+                            Ok(data) => {
+                                let response =
+                                    ProofGenerationDataResponse::Success(data.map(Box::new));
+                                (StatusCode::OK, Json(response)).into_response()
+                            }
+                            Err(WaitError::Saturated) => {
+                                long_poll::SATURATED_STATUS.into_response()
+                            }
+                            Err(WaitError::Check(e)) => e.into_response(),
+                        }
+                    },
+                ),
+            );
+        }
     }
 
     router
@@ -1,6 +1,8 @@
 use std::sync::Arc;
 
-use zksync_config::configs::{fri_prover_gateway::ApiMode, ProofDataHandlerConfig};
+use zksync_config::configs::{
+    fri_prover_gateway::ApiMode, ProofDataHandlerConfig, ProofDataHandlerSecrets,
+};
 use zksync_dal::{
     node::{MasterPool, PoolResource},
     ConnectionPool, Core,
@@ -12,7 +14,7 @@ use zksync_node_framework::{
     FromContext, IntoContext,
 };
 use zksync_object_store::{node::ObjectStoreResource, ObjectStore};
-use zksync_types::{commitment::L1BatchCommitmentMode, L2ChainId};
+use zksync_types::{commitment::L1BatchCommitmentMode, secrets::APIKey, L2ChainId};
 
 use crate::ProofDataHandlerClient;
 
@@ -20,6 +22,7 @@ use crate::ProofDataHandlerClient;
 #[derive(Debug)]
 pub struct ProofDataHandlerLayer {
     proof_data_handler_config: ProofDataHandlerConfig,
+    push_auth_token: Option<APIKey>,
     commitment_mode: L1BatchCommitmentMode,
     l2_chain_id: L2ChainId,
     api_mode: ApiMode,
@@ -40,12 +43,14 @@ pub struct Output {
 impl ProofDataHandlerLayer {
     pub fn new(
         proof_data_handler_config: ProofDataHandlerConfig,
+        secrets: ProofDataHandlerSecrets,
         commitment_mode: L1BatchCommitmentMode,
         l2_chain_id: L2ChainId,
         api_mode: ApiMode,
     ) -> Self {
         Self {
             proof_data_handler_config,
+            push_auth_token: secrets.push_auth_token,
             commitment_mode,
             l2_chain_id,
             api_mode,
@@ -63,11 +68,21 @@ impl WiringLayer for ProofDataHandlerLayer {
     }
 
     async fn wire(self, input: Self::Input) -> Result<Self::Output, WiringError> {
+        if self.api_mode == ApiMode::ProverCluster
+            && self.proof_data_handler_config.push_mode
+            && self.push_auth_token.is_none()
+        {
+            return Err(WiringError::Configuration(
+                "push mode requires `proof_data_handler.push_auth_token` secret".to_owned(),
+            ));
+        }
+
         let main_pool = input.master_pool.get().await?;
         let blob_store = input.object_store.0;
 
         let task = ProofDataHandlerTask {
             proof_data_handler_config: self.proof_data_handler_config,
+            push_auth_token: self.push_auth_token,
             blob_store,
             main_pool,
             commitment_mode: self.commitment_mode,
@@ -82,6 +97,7 @@ impl WiringLayer for ProofDataHandlerLayer {
 #[derive(Debug)]
 pub struct ProofDataHandlerTask {
     proof_data_handler_config: ProofDataHandlerConfig,
+    push_auth_token: Option<APIKey>,
     blob_store: Arc<dyn ObjectStore>,
     main_pool: ConnectionPool<Core>,
     api_mode: ApiMode,
@@ -98,6 +114,7 @@ impl Task for ProofDataHandlerTask {
     async fn run(self: Box<Self>, stop_receiver: StopReceiver) -> anyhow::Result<()> {
         let server_task = crate::run_server(
             self.proof_data_handler_config.clone(),
+            self.push_auth_token.clone(),
             self.blob_store.clone(),
             self.main_pool.clone(),
             self.commitment_mode,
@@ -113,6 +130,7 @@ impl Task for ProofDataHandlerTask {
                 self.blob_store,
                 self.main_pool,
                 self.proof_data_handler_config,
+                self.push_auth_token,
                 self.commitment_mode,
                 self.l2_chain_id,
             );
@@ -39,6 +39,7 @@ pub enum TreeUpdateStage {
     Compute,
     SavePostgres,
     SaveRocksdb,
+    Checkpoint,
     SaveGcs,
 }
 
@@ -377,6 +377,44 @@ async fn rollback(vm_mode: FastVmMode) {
     executor.finish_batch().await.unwrap();
 }
 
+/// Checks that a bundle of transactions can be rolled back as a whole without affecting preceding transactions.
+#[test_casing(3, FAST_VM_MODES)]
+#[tokio::test]
+async fn rollback_bundle(vm_mode: FastVmMode) {
+    let connection_pool = ConnectionPool::<Core>::constrained_test_pool(1).await;
+    let mut alice = Account::random();
+
+    let mut tester = Tester::new(connection_pool, vm_mode);
+    tester.genesis().await;
+    tester.fund(&[alice.address()]).await;
+    let mut executor = tester
+        .create_batch_executor(StorageType::AsyncRocksdbCache)
+        .await;
+
+    let res = executor.execute_tx(alice.execute()).await.unwrap();
+    assert_executed(&res);
+
+    let bundle = [alice.execute(), alice.execute()];
+    executor.start_bundle().await.unwrap();
+    for tx in &bundle {
+        let res = executor.execute_tx(tx.clone()).await.unwrap();
+        assert_executed(&res);
+    }
+    executor.finish_bundle(true).await.unwrap();
+
+    // The whole bundle must be rolled back, so its transactions can be executed again.
+    executor.start_bundle().await.unwrap();
+    for tx in &bundle {
+        let res = executor.execute_tx(tx.clone()).await.unwrap();
+        assert_executed(&res);
+    }
+    executor.finish_bundle(false).await.unwrap();
+
+    let res = executor.execute_tx(alice.execute()).await.unwrap();
+    assert_executed(&res);
+    executor.finish_batch().await.unwrap();
+}
+
 /// Checks that incorrect transactions are marked as rejected.
 #[test_casing(3, FAST_VM_MODES)]
 #[tokio::test]
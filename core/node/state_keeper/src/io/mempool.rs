@@ -1,6 +1,6 @@
 use std::{
     cmp,
-    collections::HashMap,
+    collections::{HashMap, HashSet, VecDeque},
     sync::Arc,
     time::{Duration, Instant},
 };
@@ -15,12 +15,16 @@ use zksync_multivm::{interface::Halt, utils::derive_base_fee_and_gas_per_pubdata
 use zksync_node_fee_model::BatchFeeModelInputProvider;
 use zksync_types::{
     block::UnsealedL1BatchHeader,
+    bundle::{BundleEligibility, TransactionBundle},
     commitment::{PubdataParams, PubdataType},
     protocol_upgrade::ProtocolUpgradeTx,
     utils::display_timestamp,
     Address, L1BatchNumber, L2BlockNumber, L2ChainId, ProtocolVersionId, Transaction, H256, U256,
 };
-use zksync_vm_executor::storage::{get_base_system_contracts_by_version_id, L1BatchParamsProvider};
+use zksync_vm_executor::{
+    deny_list::{log_denied_transaction, AddressScreener},
+    storage::{get_base_system_contracts_by_version_id, L1BatchParamsProvider},
+};
 
 use crate::{
     io::{
@@ -31,7 +35,10 @@ use crate::{
     mempool_actor::l2_tx_filter,
     metrics::{L2BlockSealReason, AGGREGATION_METRICS, KEEPER_METRICS},
     seal_criteria::{
-        io_criteria::{L2BlockMaxPayloadSizeSealer, ProtocolUpgradeSealer, TimeoutSealer},
+        io_criteria::{
+            DynamicL2BlockTimeSealer, L2BlockMaxPayloadSizeSealer, ProtocolUpgradeSealer,
+            TimeoutSealer,
+        },
         IoSealCriteria, UnexecutableReason,
     },
     updates::UpdatesManager,
@@ -39,6 +46,11 @@ use crate::{
     MempoolGuard,
 };
 
+/// Minimum interval between polling transaction bundles from the storage.
+const BUNDLES_POLL_INTERVAL: Duration = Duration::from_millis(500);
+/// Maximum number of bundles loaded from the storage at once.
+const MAX_BUNDLES_PER_POLL: usize = 16;
+
 /// Mempool-based sequencer for the state keeper.
 ///
 /// Receives transactions from the database through the mempool filtering logic.
@@ -49,6 +61,8 @@ pub struct MempoolIO {
     mempool: MempoolGuard,
     pool: ConnectionPool<Core>,
     timeout_sealer: TimeoutSealer,
+    /// If set, replaces `timeout_sealer` for L2 blocks.
+    dynamic_l2_block_time_sealer: Option<DynamicL2BlockTimeSealer>,
     l2_block_max_payload_size_sealer: L2BlockMaxPayloadSizeSealer,
     protocol_upgrade_sealer: ProtocolUpgradeSealer,
     filter: L2TxFilter,
@@ -62,6 +76,13 @@ pub struct MempoolIO {
     chain_id: L2ChainId,
     l2_da_validator_address: Option<Address>,
     pubdata_type: PubdataType,
+    /// Bundles loaded from the storage, but not yet returned to the state keeper.
+    pending_bundles: VecDeque<TransactionBundle>,
+    /// Hashes of bundles returned to the state keeper that aren't persisted as executed yet.
+    in_flight_bundles: HashSet<H256>,
+    last_bundles_poll: Option<Instant>,
+    /// Screener used to re-check bundled transactions right before execution.
+    address_screener: Option<AddressScreener>,
 }
 
 #[async_trait]
@@ -90,20 +111,29 @@ impl IoSealCriteria for MempoolIO {
     }
 
     fn should_seal_l2_block(&mut self, manager: &UpdatesManager) -> bool {
-        if self.timeout_sealer.should_seal_l2_block(manager) {
-            AGGREGATION_METRICS.l2_block_reason_inc(&L2BlockSealReason::Timeout);
-            return true;
-        }
-
-        if self
-            .l2_block_max_payload_size_sealer
-            .should_seal_l2_block(manager)
-        {
-            AGGREGATION_METRICS.l2_block_reason_inc(&L2BlockSealReason::PayloadSize);
-            return true;
+        let reason = if let Some(sealer) = &mut self.dynamic_l2_block_time_sealer {
+            sealer
+                .should_seal_l2_block(manager)
+                .then_some(L2BlockSealReason::DynamicTimeout)
+        } else {
+            self.timeout_sealer
+                .should_seal_l2_block(manager)
+                .then_some(L2BlockSealReason::Timeout)
+        };
+        let reason = reason.or_else(|| {
+            self.l2_block_max_payload_size_sealer
+                .should_seal_l2_block(manager)
+                .then_some(L2BlockSealReason::PayloadSize)
+        });
+
+        let Some(reason) = reason else {
+            return false;
+        };
+        AGGREGATION_METRICS.l2_block_reason_inc(&reason);
+        if let Some(sealer) = &self.dynamic_l2_block_time_sealer {
+            sealer.report_sealed_l2_block(manager);
         }
-
-        false
+        true
     }
 }
 
@@ -412,6 +442,91 @@ impl StateKeeperIO for MempoolIO {
         Ok(())
     }
 
+    async fn next_bundle(
+        &mut self,
+        l2_block_number: L2BlockNumber,
+        l2_block_timestamp: u64,
+    ) -> anyhow::Result<Option<TransactionBundle>> {
+        if self.pending_bundles.is_empty() {
+            self.load_pending_bundles().await?;
+        }
+
+        while let Some(bundle) = self.pending_bundles.pop_front() {
+            match bundle
+                .constraints
+                .check(l2_block_number, l2_block_timestamp)
+            {
+                BundleEligibility::Eligible => {
+                    let screening = match &self.address_screener {
+                        Some(screener) => screener.screen_transactions(&bundle.transactions).await,
+                        None => Ok(None),
+                    };
+                    match screening {
+                        Ok(None) => { /* Bundle is OK */ }
+                        Ok(Some((tx_hash, denied))) => {
+                            log_denied_transaction(tx_hash, &denied, "sealing");
+                            self.reject_bundle(&bundle, UnexecutableReason::AddressDenied)
+                                .await?;
+                            continue;
+                        }
+                        Err(err) => {
+                            // Bundles are not executed until the deny list becomes available.
+                            tracing::warn!("Postponing bundle {:?}: {err}", bundle.hash);
+                            self.pending_bundles.push_front(bundle);
+                            return Ok(None);
+                        }
+                    }
+                    self.in_flight_bundles.insert(bundle.hash);
+                    return Ok(Some(bundle));
+                }
+                // The bundle will be reloaded on the next poll.
+                BundleEligibility::TooEarly => continue,
+                BundleEligibility::Expired => {
+                    self.reject_bundle(&bundle, UnexecutableReason::BundleExpired)
+                        .await?;
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    async fn accept_bundle(&mut self, bundle: &TransactionBundle) -> anyhow::Result<()> {
+        let mut next_nonces = HashMap::new();
+        for tx in &bundle.transactions {
+            let nonce = tx
+                .nonce()
+                .context("bundle contains a transaction without nonce")?;
+            next_nonces.insert(tx.initiator_account(), nonce + 1);
+        }
+        for (address, next_nonce) in next_nonces {
+            self.mempool.advance_account_nonce(address, next_nonce);
+        }
+        Ok(())
+    }
+
+    async fn rollback_bundle(&mut self, bundle: TransactionBundle) -> anyhow::Result<()> {
+        self.in_flight_bundles.remove(&bundle.hash);
+        self.pending_bundles.push_front(bundle);
+        Ok(())
+    }
+
+    async fn reject_bundle(
+        &mut self,
+        bundle: &TransactionBundle,
+        reason: UnexecutableReason,
+    ) -> anyhow::Result<()> {
+        self.in_flight_bundles.remove(&bundle.hash);
+        KEEPER_METRICS.inc_rejected_txs(reason.as_metric_label());
+        tracing::warn!("Bundle {:?} is rejected with error: {reason}", bundle.hash);
+
+        let mut storage = self.pool.connection_tagged("state_keeper").await?;
+        storage
+            .bundles_dal()
+            .mark_bundle_as_rejected(bundle.hash, &format!("rejected: {reason}"))
+            .await?;
+        Ok(())
+    }
+
     async fn load_base_system_contracts(
         &self,
         protocol_version: ProtocolVersionId,
@@ -531,10 +646,15 @@ impl MempoolIO {
         l2_da_validator_address: Option<Address>,
         pubdata_type: PubdataType,
     ) -> anyhow::Result<Self> {
+        let dynamic_l2_block_time_sealer = config
+            .dynamic_l2_block_time
+            .as_ref()
+            .map(|dynamic_config| DynamicL2BlockTimeSealer::new(dynamic_config, mempool.clone()));
         Ok(Self {
             mempool,
             pool: pool.clone(),
             timeout_sealer: TimeoutSealer::new(config),
+            dynamic_l2_block_time_sealer,
             l2_block_max_payload_size_sealer: L2BlockMaxPayloadSizeSealer::new(config),
             protocol_upgrade_sealer: ProtocolUpgradeSealer::new(pool),
             filter: L2TxFilter::default(),
@@ -548,9 +668,65 @@ impl MempoolIO {
             chain_id,
             l2_da_validator_address,
             pubdata_type,
+            pending_bundles: VecDeque::new(),
+            in_flight_bundles: HashSet::new(),
+            last_bundles_poll: None,
+            address_screener: None,
         })
     }
 
+    /// Screens bundled transactions against the address deny list before returning them to the state keeper.
+    pub fn with_address_screener(mut self, screener: AddressScreener) -> Self {
+        self.address_screener = Some(screener);
+        self
+    }
+
+    /// Loads pending bundles from the storage. To not hammer the DB, bundles are polled
+    /// at most once per [`BUNDLES_POLL_INTERVAL`].
+    async fn load_pending_bundles(&mut self) -> anyhow::Result<()> {
+        if self
+            .last_bundles_poll
+            .is_some_and(|polled_at| polled_at.elapsed() < BUNDLES_POLL_INTERVAL)
+        {
+            return Ok(());
+        }
+        self.last_bundles_poll = Some(Instant::now());
+
+        let mut storage = self.pool.connection_tagged("state_keeper").await?;
+        let bundles = storage
+            .bundles_dal()
+            .get_pending_bundles(MAX_BUNDLES_PER_POLL)
+            .await?;
+        drop(storage);
+
+        // In-flight bundles that are no longer pending were persisted as executed. We can only conclude this
+        // if all pending bundles were loaded.
+        if bundles.len() < MAX_BUNDLES_PER_POLL {
+            let pending_hashes: HashSet<_> =
+                bundles.iter().map(|pending| pending.bundle.hash).collect();
+            self.in_flight_bundles
+                .retain(|hash| pending_hashes.contains(hash));
+        }
+
+        for pending in bundles {
+            if self.in_flight_bundles.contains(&pending.bundle.hash) {
+                continue;
+            }
+            if !pending.missing_tx_hashes.is_empty() {
+                tracing::info!(
+                    "Bundle {:?} misses transactions {:?}",
+                    pending.bundle.hash,
+                    pending.missing_tx_hashes
+                );
+                self.reject_bundle(&pending.bundle, UnexecutableReason::BundleIncomplete)
+                    .await?;
+                continue;
+            }
+            self.pending_bundles.push_back(pending.bundle);
+        }
+        Ok(())
+    }
+
     fn pubdata_params(&self, protocol_version: ProtocolVersionId) -> anyhow::Result<PubdataParams> {
         let pubdata_params = match (
             protocol_version.is_pre_gateway(),
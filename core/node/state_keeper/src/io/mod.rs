@@ -4,9 +4,9 @@ use async_trait::async_trait;
 use zksync_contracts::BaseSystemContracts;
 use zksync_multivm::interface::{L1BatchEnv, SystemEnv};
 use zksync_types::{
-    block::L2BlockExecutionData, commitment::PubdataParams, fee_model::BatchFeeInput,
-    protocol_upgrade::ProtocolUpgradeTx, Address, L1BatchNumber, L2ChainId, ProtocolVersionId,
-    Transaction, H256,
+    block::L2BlockExecutionData, bundle::TransactionBundle, commitment::PubdataParams,
+    fee_model::BatchFeeInput, protocol_upgrade::ProtocolUpgradeTx, Address, L1BatchNumber,
+    L2BlockNumber, L2ChainId, ProtocolVersionId, Transaction, H256,
 };
 use zksync_vm_executor::storage::l1_batch_params;
 
@@ -150,6 +150,37 @@ pub trait StateKeeperIO: 'static + Send + Sync + fmt::Debug + IoSealCriteria {
     /// Marks the transaction as "rejected", e.g. one that is not correct and can't be executed.
     async fn reject(&mut self, tx: &Transaction, reason: UnexecutableReason) -> anyhow::Result<()>;
 
+    /// Returns the next transaction bundle eligible for inclusion into the L2 block with the specified
+    /// number and timestamp. Bundles are executed atomically; see [`TransactionBundle`] for details.
+    ///
+    /// The default implementation doesn't support bundles.
+    async fn next_bundle(
+        &mut self,
+        _l2_block_number: L2BlockNumber,
+        _l2_block_timestamp: u64,
+    ) -> anyhow::Result<Option<TransactionBundle>> {
+        Ok(None)
+    }
+    /// Notifies the IO that the bundle was included into the current L2 block.
+    async fn accept_bundle(&mut self, _bundle: &TransactionBundle) -> anyhow::Result<()> {
+        Ok(())
+    }
+    /// Marks the bundle as "not executed", so it can be retrieved from the IO again.
+    async fn rollback_bundle(&mut self, _bundle: TransactionBundle) -> anyhow::Result<()> {
+        Ok(())
+    }
+    /// Marks the bundle and all its transactions as "rejected".
+    async fn reject_bundle(
+        &mut self,
+        bundle: &TransactionBundle,
+        reason: UnexecutableReason,
+    ) -> anyhow::Result<()> {
+        anyhow::bail!(
+            "I/O doesn't support bundles, but bundle {:?} was rejected: {reason}",
+            bundle.hash
+        )
+    }
+
     /// Loads base system contracts with the specified version.
     async fn load_base_system_contracts(
         &self,
@@ -455,6 +455,8 @@ impl L2BlockSealSubtask for InsertL2ToL1LogsSubtask {
 
 #[cfg(test)]
 mod tests {
+    use std::time::Instant;
+
     use zksync_dal::{ConnectionPool, Core};
     use zksync_multivm::{
         interface::{tracer::ValidationTraces, TransactionExecutionResult, TxExecutionStatus},
@@ -541,6 +543,7 @@ mod tests {
                 payload_encoding_size: Default::default(),
                 l1_tx_count: 0,
                 timestamp: 1,
+                opened_at: Instant::now(),
                 number: L2BlockNumber(1),
                 prev_block_hash: Default::default(),
                 virtual_blocks: Default::default(),
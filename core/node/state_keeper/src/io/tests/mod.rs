@@ -10,10 +10,11 @@ use zksync_multivm::{
     },
     utils::derive_base_fee_and_gas_per_pubdata,
 };
-use zksync_node_test_utils::prepare_recovery_snapshot;
+use zksync_node_test_utils::{create_l2_transaction, prepare_recovery_snapshot};
 use zksync_system_constants::KNOWN_CODES_STORAGE_ADDRESS;
 use zksync_types::{
     block::L2BlockHasher,
+    bundle::{BundleConstraints, TransactionBundle},
     bytecode::BytecodeHash,
     commitment::{L1BatchCommitmentMode, PubdataParams},
     fee_model::{BatchFeeInput, PubdataIndependentBatchFeeModelInput},
@@ -23,6 +24,7 @@ use zksync_types::{
     AccountTreeId, Address, L1BatchNumber, L2BlockNumber, L2ChainId, ProtocolVersion,
     ProtocolVersionId, StorageKey, TransactionTimeRangeConstraint, H256, U256,
 };
+use zksync_vm_executor::deny_list::{AddressScreener, SharedDenyList};
 
 use self::tester::Tester;
 use crate::{
@@ -850,6 +852,120 @@ async fn test_mempool_with_timestamp_assertion() {
     );
 }
 
+#[tokio::test]
+async fn mempool_io_returns_eligible_bundles() {
+    let connection_pool = ConnectionPool::<Core>::constrained_test_pool(2).await;
+    let tester = Tester::new(L1BatchCommitmentMode::Rollup);
+    tester.genesis(&connection_pool).await;
+    let mut storage = connection_pool.connection().await.unwrap();
+
+    let (mut mempool, _) = tester.create_test_mempool_io(connection_pool.clone()).await;
+    mempool.initialize().await.unwrap();
+
+    let mut bundle_hashes = vec![];
+    for constraints in [
+        BundleConstraints {
+            max_l2_block_number: Some(L2BlockNumber(1)),
+            ..BundleConstraints::default()
+        },
+        BundleConstraints {
+            min_timestamp: Some(100),
+            ..BundleConstraints::default()
+        },
+        BundleConstraints::default(),
+    ] {
+        let txs = [
+            create_l2_transaction(10, 100),
+            create_l2_transaction(10, 100),
+        ];
+        for tx in &txs {
+            insert_l2_transaction(&mut storage, tx).await;
+        }
+        let tx_hashes: Vec<_> = txs.iter().map(L2Tx::hash).collect();
+        let bundle_hash = TransactionBundle::compute_hash(tx_hashes.iter().copied());
+        storage
+            .bundles_dal()
+            .insert_bundle(bundle_hash, &tx_hashes, &constraints)
+            .await
+            .unwrap();
+        bundle_hashes.push(bundle_hash);
+    }
+
+    let bundle = mempool
+        .next_bundle(L2BlockNumber(2), 50)
+        .await
+        .unwrap()
+        .expect("no eligible bundle");
+    assert_eq!(bundle.hash, bundle_hashes[2]);
+    assert_eq!(bundle.transactions.len(), 2);
+    // The returned bundle is in flight, so it must not be returned again.
+    let next_bundle = mempool.next_bundle(L2BlockNumber(2), 50).await.unwrap();
+    assert!(next_bundle.is_none(), "{next_bundle:?}");
+
+    // The first bundle is expired and must be rejected. The second one is not yet eligible and must stay pending.
+    let pending_bundles = storage.bundles_dal().get_pending_bundles(10).await.unwrap();
+    let pending_hashes: Vec<_> = pending_bundles
+        .iter()
+        .map(|pending| pending.bundle.hash)
+        .collect();
+    assert_eq!(pending_hashes, bundle_hashes[1..]);
+
+    mempool.rollback_bundle(bundle).await.unwrap();
+    let bundle = mempool
+        .next_bundle(L2BlockNumber(2), 50)
+        .await
+        .unwrap()
+        .expect("rolled back bundle is not returned");
+    assert_eq!(bundle.hash, bundle_hashes[2]);
+}
+
+#[tokio::test]
+async fn mempool_io_screens_bundles() {
+    let connection_pool = ConnectionPool::<Core>::constrained_test_pool(2).await;
+    let tester = Tester::new(L1BatchCommitmentMode::Rollup);
+    tester.genesis(&connection_pool).await;
+    let mut storage = connection_pool.connection().await.unwrap();
+
+    let txs = [
+        create_l2_transaction(10, 100),
+        create_l2_transaction(10, 100),
+    ];
+    for tx in &txs {
+        insert_l2_transaction(&mut storage, tx).await;
+    }
+    let tx_hashes: Vec<_> = txs.iter().map(L2Tx::hash).collect();
+    let bundle_hash = TransactionBundle::compute_hash(tx_hashes.iter().copied());
+    storage
+        .bundles_dal()
+        .insert_bundle(bundle_hash, &tx_hashes, &BundleConstraints::default())
+        .await
+        .unwrap();
+
+    let deny_list = SharedDenyList::refreshable(Duration::from_secs(60));
+    let screener = AddressScreener::new(deny_list.clone(), false);
+    let (mempool, _) = tester.create_test_mempool_io(connection_pool.clone()).await;
+    let mut mempool = mempool.with_address_screener(screener);
+    mempool.initialize().await.unwrap();
+
+    // The deny list is not loaded yet, so the bundle must be postponed.
+    let bundle = mempool.next_bundle(L2BlockNumber(2), 50).await.unwrap();
+    assert!(bundle.is_none(), "{bundle:?}");
+    let pending_bundles = storage.bundles_dal().get_pending_bundles(10).await.unwrap();
+    assert_eq!(pending_bundles.len(), 1);
+
+    // Deny the sender of the second transaction; the entire bundle must be rejected.
+    deny_list
+        .writer()
+        .write()
+        .await
+        .insert(txs[1].initiator_account());
+    deny_list.mark_refreshed();
+    let bundle = mempool.next_bundle(L2BlockNumber(2), 50).await.unwrap();
+    assert!(bundle.is_none(), "{bundle:?}");
+    let pending_bundles = storage.bundles_dal().get_pending_bundles(10).await.unwrap();
+    assert!(pending_bundles.is_empty(), "{pending_bundles:?}");
+}
+
 #[tokio::test]
 async fn test_batch_params_with_protocol_upgrade_tx() {
     let connection_pool = ConnectionPool::<Core>::constrained_test_pool(2).await;
@@ -11,16 +11,17 @@ use zksync_health_check::{HealthUpdater, ReactiveHealthCheck};
 use zksync_multivm::{
     interface::{
         executor::{BatchExecutor, BatchExecutorFactory},
-        Halt, L1BatchEnv, SystemEnv,
+        Call, Halt, L1BatchEnv, SystemEnv, VmExecutionMetrics, VmExecutionResultAndLogs,
     },
     utils::StorageWritesDeduplicator,
 };
 use zksync_shared_metrics::{TxStage, APP_METRICS};
+use zksync_shared_resources::api::Preconfirmations;
 use zksync_state::{OwnedStorage, ReadStorageFactory};
 use zksync_types::{
-    block::L2BlockExecutionData, commitment::PubdataParams, l2::TransactionType,
-    protocol_upgrade::ProtocolUpgradeTx, protocol_version::ProtocolVersionId, try_stoppable,
-    utils::display_timestamp, L1BatchNumber, OrStopped, StopContext, Transaction,
+    api, block::L2BlockExecutionData, bundle::TransactionBundle, commitment::PubdataParams,
+    l2::TransactionType, protocol_upgrade::ProtocolUpgradeTx, protocol_version::ProtocolVersionId,
+    try_stoppable, utils::display_timestamp, L1BatchNumber, OrStopped, StopContext, Transaction,
 };
 use zksync_vm_executor::whitelist::DeploymentTxFilter;
 
@@ -38,6 +39,15 @@ use crate::{
 /// we only need it to not block on waiting indefinitely and be able to process cancellation requests.
 pub(super) const POLL_WAIT_DURATION: Duration = Duration::from_secs(1);
 
+/// Bundle transaction executed in the VM, together with its execution artifacts.
+type ExecutedBundleTx = (
+    Transaction,
+    Box<VmExecutionResultAndLogs>,
+    Box<VmExecutionMetrics>,
+    Vec<Call>,
+    u32,
+);
+
 /// State keeper represents a logic layer of L1 batch / L2 block processing flow.
 ///
 /// It's responsible for taking all the data from the `StateKeeperIO`, feeding it into `BatchExecutor` objects
@@ -57,6 +67,10 @@ pub struct ZkSyncStateKeeper {
     storage_factory: Arc<dyn ReadStorageFactory>,
     health_updater: HealthUpdater,
     deployment_tx_filter: Option<DeploymentTxFilter>,
+    preconfirmations: Option<Preconfirmations>,
+    /// Preconfirmations published for transactions in the current unsealed L2 block. The first entries correspond
+    /// to transactions included into the block; the remaining ones are for executed transactions pending inclusion.
+    unsealed_preconfirmations: Vec<api::TransactionPreconfirmation>,
 }
 
 impl ZkSyncStateKeeper {
@@ -76,11 +90,22 @@ impl ZkSyncStateKeeper {
             storage_factory,
             health_updater: ReactiveHealthCheck::new("state_keeper").1,
             deployment_tx_filter,
+            preconfirmations: None,
+            unsealed_preconfirmations: Vec::new(),
         }
     }
 
+    /// Enables publishing preconfirmations for executed transactions before their L2 block is sealed.
+    pub fn with_preconfirmations(mut self, preconfirmations: Preconfirmations) -> Self {
+        self.preconfirmations = Some(preconfirmations);
+        self
+    }
+
     pub async fn run(mut self, stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
-        try_stoppable!(self.run_inner(stop_receiver).await);
+        let result = self.run_inner(stop_receiver).await;
+        // Transactions in the unsealed L2 block are lost on state keeper termination.
+        self.roll_back_preconfirmations(0);
+        try_stoppable!(result);
         Ok(())
     }
 
@@ -454,7 +479,84 @@ impl ZkSyncStateKeeper {
                     "handling L2 block #{} failed",
                     updates_manager.l2_block.number
                 )
+            })?;
+        self.unsealed_preconfirmations.clear();
+        Ok(())
+    }
+
+    /// Publishes a preconfirmation for a transaction executed on top of `updates_manager` if enabled. The transaction
+    /// must be executed after all transactions with already published preconfirmations in the current L2 block.
+    fn preconfirm_tx(
+        &mut self,
+        updates_manager: &UpdatesManager,
+        tx: &Transaction,
+        tx_result: &VmExecutionResultAndLogs,
+    ) {
+        let Some(preconfirmations) = &self.preconfirmations else {
+            return;
+        };
+        let l2_block = &updates_manager.l2_block;
+        // Account for preceding transactions that are executed, but not included into the block yet (e.g., in a bundle).
+        let (pending_tx_count, pending_log_count) = self
+            .unsealed_preconfirmations
+            .iter()
+            .skip(l2_block.executed_transactions.len())
+            .fold((0, 0), |(txs, logs), preconfirmation| {
+                (txs + 1, logs + preconfirmation.logs.len())
+            });
+        let tx_index = l2_block.executed_transactions.len() + pending_tx_count;
+        let first_event_index = l2_block.events.len() + pending_log_count;
+
+        let tx_hash = tx.hash();
+        let logs = tx_result
+            .logs
+            .events
+            .iter()
+            .enumerate()
+            .map(|(i, event)| api::Log {
+                address: event.address,
+                topics: event.indexed_topics.clone(),
+                data: event.value.clone().into(),
+                block_hash: None,
+                block_number: Some(l2_block.number.0.into()),
+                l1_batch_number: Some(updates_manager.l1_batch.number.0.into()),
+                transaction_hash: Some(tx_hash),
+                transaction_index: Some(tx_index.into()),
+                log_index: Some((first_event_index + i).into()),
+                transaction_log_index: Some(i.into()),
+                log_type: None,
+                removed: Some(false),
+                block_timestamp: Some(l2_block.timestamp.into()),
             })
+            .collect();
+        let preconfirmation = api::TransactionPreconfirmation {
+            transaction_hash: tx_hash,
+            status: api::PreconfirmationStatus::Preconfirmed,
+            block_number: l2_block.number.0.into(),
+            transaction_index: tx_index.into(),
+            success: !tx_result.result.is_failed(),
+            gas_used: tx
+                .gas_limit()
+                .saturating_sub(tx_result.refunds.gas_refunded.into()),
+            logs,
+        };
+        preconfirmations.publish(preconfirmation.clone());
+        self.unsealed_preconfirmations.push(preconfirmation);
+    }
+
+    /// Notifies subscribers that preconfirmed transactions in the unsealed L2 block are rolled back, except for
+    /// the first `retained_count` ones.
+    fn roll_back_preconfirmations(&mut self, retained_count: usize) {
+        let Some(preconfirmations) = &self.preconfirmations else {
+            return;
+        };
+        if retained_count >= self.unsealed_preconfirmations.len() {
+            return;
+        }
+        for mut preconfirmation in self.unsealed_preconfirmations.drain(retained_count..) {
+            preconfirmation.status = api::PreconfirmationStatus::RolledBack;
+            preconfirmations.publish(preconfirmation);
+        }
     }
 
     /// Applies the "pending state" on the `UpdatesManager`.
@@ -629,6 +731,43 @@ impl ZkSyncStateKeeper {
                     .update_next_l2_block_timestamp(next_l2_block_timestamp);
             }
 
+            let next_l2_block_number = if updates_manager.has_next_block_params() {
+                updates_manager.l2_block.number + 1
+            } else {
+                updates_manager.l2_block.number
+            };
+            let next_l2_block_timestamp = updates_manager
+                .get_next_l2_block_params_or_batch_params()
+                .timestamp;
+            let maybe_bundle = self
+                .io
+                .next_bundle(next_l2_block_number, next_l2_block_timestamp)
+                .await
+                .context("error getting next bundle")?;
+            if let Some(bundle) = maybe_bundle {
+                waiting_latency.observe();
+                let bundle_hash = bundle.hash;
+                if updates_manager.has_next_block_params() {
+                    Self::start_next_l2_block(updates_manager, batch_executor).await?;
+                }
+
+                let seal_resolution = self
+                    .process_bundle(batch_executor, updates_manager, bundle)
+                    .await?;
+                if seal_resolution.should_seal() {
+                    tracing::debug!(
+                        "L1 batch #{} should be sealed with resolution {seal_resolution:?} after executing \
+                         bundle {bundle_hash:?}",
+                        updates_manager.l1_batch.number
+                    );
+                    self.report_seal_criteria_capacity(updates_manager);
+                    full_latency.observe();
+                    return Ok(());
+                }
+                full_latency.observe();
+                continue;
+            }
+
             let Some(tx) = self
                 .io
                 .wait_for_next_tx(
@@ -683,6 +822,9 @@ impl ZkSyncStateKeeper {
                     batch_executor.rollback_last_tx().await.with_context(|| {
                         format!("failed rolling back transaction {tx_hash:?} in batch executor")
                     })?;
+                    self.roll_back_preconfirmations(
+                        updates_manager.l2_block.executed_transactions.len(),
+                    );
                     self.io.rollback(tx).await.with_context(|| {
                         format!("failed rolling back transaction {tx_hash:?} in I/O")
                     })?;
@@ -691,6 +833,9 @@ impl ZkSyncStateKeeper {
                     batch_executor.rollback_last_tx().await.with_context(|| {
                         format!("failed rolling back transaction {tx_hash:?} in batch executor")
                     })?;
+                    self.roll_back_preconfirmations(
+                        updates_manager.l2_block.executed_transactions.len(),
+                    );
                     self.io
                         .reject(&tx, reason.clone())
                         .await
@@ -790,6 +935,10 @@ impl ZkSyncStateKeeper {
 
         APP_METRICS.processed_txs[&TxStage::StateKeeper].inc();
         APP_METRICS.processed_l1_txs[&TxStage::StateKeeper].inc_by(tx.is_l1().into());
+        // The preconfirmation is rolled back if the transaction doesn't end up included into the block.
+        if let TxExecutionResult::Success { tx_result, .. } = &exec_result {
+            self.preconfirm_tx(updates_manager, &tx, tx_result);
+        }
 
         let latency = KEEPER_METRICS.determine_seal_resolution.start();
         // All of `TxExecutionResult::BootloaderOutOfGasForTx`,
@@ -913,6 +1062,198 @@ impl ZkSyncStateKeeper {
         Ok((resolution, exec_result))
     }
 
+    /// Executes a transaction bundle atomically: either all bundle transactions are included into the current L2 block,
+    /// or none of them are. A bundle is unexecutable if any of its transactions reverts or is rejected by the VM.
+    /// Otherwise, the bundle is treated as a single transaction by the seal criteria.
+    #[tracing::instrument(skip_all, fields(bundle = ?bundle.hash))]
+    async fn process_bundle(
+        &mut self,
+        batch_executor: &mut dyn BatchExecutor<OwnedStorage>,
+        updates_manager: &mut UpdatesManager,
+        bundle: TransactionBundle,
+    ) -> anyhow::Result<SealResolution> {
+        let bundle_hash = bundle.hash;
+        batch_executor
+            .start_bundle()
+            .await
+            .with_context(|| format!("failed starting bundle {bundle_hash:?}"))?;
+
+        let is_first_tx = updates_manager.pending_executed_transactions_len() == 0;
+        let mut executed_txs = Vec::with_capacity(bundle.transactions.len());
+        let mut failure = None;
+        for tx in &bundle.transactions {
+            let latency = KEEPER_METRICS.execute_tx_outer_time.start();
+            let exec_result = batch_executor
+                .execute_tx(tx.clone())
+                .await
+                .with_context(|| format!("failed executing transaction {:?}", tx.hash()))?;
+            let exec_result = TxExecutionResult::new(exec_result);
+            latency.observe();
+            APP_METRICS.processed_txs[&TxStage::StateKeeper].inc();
+
+            let resolution = match exec_result {
+                // Same logic as for standalone transactions: the bundle may fit into the next batch.
+                TxExecutionResult::BootloaderOutOfGasForTx if !is_first_tx => {
+                    SealResolution::ExcludeAndSeal
+                }
+                TxExecutionResult::BootloaderOutOfGasForTx => {
+                    UnexecutableReason::BootloaderOutOfGas.into()
+                }
+                TxExecutionResult::RejectedByVm {
+                    reason: Halt::NotEnoughGasProvided,
+                } if !is_first_tx => SealResolution::ExcludeAndSeal,
+                TxExecutionResult::RejectedByVm {
+                    reason: Halt::NotEnoughGasProvided,
+                } => UnexecutableReason::NotEnoughGasProvided.into(),
+                TxExecutionResult::RejectedByVm { reason } => {
+                    UnexecutableReason::Halt(reason).into()
+                }
+                TxExecutionResult::Success {
+                    tx_result,
+                    tx_metrics,
+                    call_tracer_result,
+                    gas_remaining,
+                } => {
+                    if tx_result.result.is_failed() {
+                        UnexecutableReason::BundleTxReverted.into()
+                    } else if self.is_deployment_not_allowed(tx, &tx_result).await {
+                        UnexecutableReason::DeploymentNotAllowed.into()
+                    } else {
+                        self.preconfirm_tx(updates_manager, tx, &tx_result);
+                        executed_txs.push((
+                            tx.clone(),
+                            tx_result,
+                            tx_metrics,
+                            call_tracer_result,
+                            gas_remaining,
+                        ));
+                        continue;
+                    }
+                }
+            };
+            tracing::info!(
+                "Transaction {:?} in bundle {bundle_hash:?} failed with resolution {resolution:?}",
+                tx.hash()
+            );
+            failure = Some(resolution);
+            break;
+        }
+
+        let resolution = match failure {
+            Some(resolution) => resolution,
+            None => self.bundle_seal_resolution(updates_manager, &executed_txs),
+        };
+
+        match &resolution {
+            SealResolution::NoSeal | SealResolution::IncludeAndSeal => {
+                batch_executor
+                    .finish_bundle(false)
+                    .await
+                    .with_context(|| format!("failed finishing bundle {bundle_hash:?}"))?;
+                self.io
+                    .accept_bundle(&bundle)
+                    .await
+                    .with_context(|| format!("failed accepting bundle {bundle_hash:?}"))?;
+                for (tx, tx_result, tx_metrics, call_tracer_result, _) in executed_txs {
+                    updates_manager.extend_from_executed_transaction(
+                        tx,
+                        *tx_result,
+                        *tx_metrics,
+                        call_tracer_result,
+                    );
+                }
+            }
+            SealResolution::ExcludeAndSeal => {
+                batch_executor
+                    .finish_bundle(true)
+                    .await
+                    .with_context(|| format!("failed rolling back bundle {bundle_hash:?}"))?;
+                self.roll_back_preconfirmations(
+                    updates_manager.l2_block.executed_transactions.len(),
+                );
+                self.io.rollback_bundle(bundle).await.with_context(|| {
+                    format!("failed rolling back bundle {bundle_hash:?} in I/O")
+                })?;
+            }
+            SealResolution::Unexecutable(reason) => {
+                batch_executor
+                    .finish_bundle(true)
+                    .await
+                    .with_context(|| format!("failed rolling back bundle {bundle_hash:?}"))?;
+                self.roll_back_preconfirmations(
+                    updates_manager.l2_block.executed_transactions.len(),
+                );
+                self.io
+                    .reject_bundle(&bundle, reason.clone())
+                    .await
+                    .with_context(|| format!("cannot reject bundle {bundle_hash:?}"))?;
+            }
+        }
+        Ok(resolution)
+    }
+
+    /// Decides whether the batch should be sealed after successfully executing all bundle transactions.
+    /// The bundle is treated as a single transaction with aggregated metrics.
+    fn bundle_seal_resolution(
+        &self,
+        updates_manager: &mut UpdatesManager,
+        executed_txs: &[ExecutedBundleTx],
+    ) -> SealResolution {
+        let mut execution_metrics = VmExecutionMetrics::default();
+        let mut encoding_size = 0;
+        for (tx, _, tx_metrics, ..) in executed_txs {
+            execution_metrics += **tx_metrics;
+            encoding_size += tx.encoding_len();
+        }
+        let gas_remaining = executed_txs
+            .last()
+            .map_or(u32::MAX, |(.., gas_remaining)| *gas_remaining);
+
+        let logs_to_apply = executed_txs
+            .iter()
+            .flat_map(|(_, tx_result, ..)| &tx_result.logs.storage_logs);
+        let block_writes_metrics = updates_manager
+            .storage_writes_deduplicator
+            .apply_and_rollback(logs_to_apply.clone());
+        let bundle_writes_metrics = StorageWritesDeduplicator::apply_on_empty_state(logs_to_apply);
+
+        let bundle_data = SealData {
+            execution_metrics,
+            cumulative_size: encoding_size,
+            writes_metrics: bundle_writes_metrics,
+            gas_remaining,
+        };
+        let block_data = SealData {
+            execution_metrics: execution_metrics + updates_manager.pending_execution_metrics(),
+            cumulative_size: encoding_size + updates_manager.pending_txs_encoding_size(),
+            writes_metrics: block_writes_metrics,
+            gas_remaining,
+        };
+        self.sealer.should_seal_l1_batch(
+            updates_manager.l1_batch.number.0,
+            updates_manager.pending_executed_transactions_len() + executed_txs.len(),
+            updates_manager.pending_l1_transactions_len(),
+            &block_data,
+            &bundle_data,
+            updates_manager.protocol_version(),
+        )
+    }
+
+    async fn is_deployment_not_allowed(
+        &self,
+        tx: &Transaction,
+        tx_result: &VmExecutionResultAndLogs,
+    ) -> bool {
+        let Some(tx_filter) = &self.deployment_tx_filter else {
+            return false;
+        };
+        !tx.is_l1()
+            && tx_filter
+                .find_not_allowed_deployer(tx.initiator_account(), &tx_result.logs.events)
+                .await
+                .is_some()
+    }
+
     /// Returns the health check for state keeper.
     pub fn health_check(&self) -> ReactiveHealthCheck {
         self.health_updater.subscribe()
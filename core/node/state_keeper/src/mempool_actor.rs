@@ -12,6 +12,7 @@ use zksync_node_fee_model::BatchFeeModelInputProvider;
 #[cfg(test)]
 use zksync_types::H256;
 use zksync_types::{get_nonce_key, vm::VmVersion, Address, Nonce, Transaction};
+use zksync_vm_executor::deny_list::{log_denied_transaction, AddressScreener};
 
 use super::{metrics::KEEPER_METRICS, types::MempoolGuard};
 use crate::v26_utils::find_unsafe_deposit;
@@ -42,6 +43,7 @@ pub struct MempoolFetcher {
     stuck_tx_timeout: Option<Duration>,
     skip_unsafe_deposit_checks: bool,
     l1_to_l2_txs_paused: bool,
+    address_screener: Option<AddressScreener>,
     #[cfg(test)]
     transaction_hashes_sender: mpsc::UnboundedSender<Vec<H256>>,
 }
@@ -61,11 +63,19 @@ impl MempoolFetcher {
             stuck_tx_timeout: config.remove_stuck_txs.then_some(config.stuck_tx_timeout),
             skip_unsafe_deposit_checks: config.skip_unsafe_deposit_checks,
             l1_to_l2_txs_paused: config.l1_to_l2_txs_paused,
+            address_screener: None,
             #[cfg(test)]
             transaction_hashes_sender: mpsc::unbounded_channel().0,
         }
     }
 
+    /// Screens L2 transactions fetched into the mempool against the address deny list. Denied transactions
+    /// are rejected instead of being added to the mempool.
+    pub fn with_address_screener(mut self, screener: AddressScreener) -> Self {
+        self.address_screener = Some(screener);
+        self
+    }
+
     pub async fn run(mut self, stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
         let mut storage = self.pool.connection_tagged("state_keeper").await?;
         if let Some(stuck_tx_timeout) = self.stuck_tx_timeout {
@@ -84,6 +94,16 @@ impl MempoolFetcher {
                 tracing::info!("Stop request received, mempool is shutting down");
                 break;
             }
+            if let Some(Err(err)) = self
+                .address_screener
+                .as_ref()
+                .map(AddressScreener::check_available)
+            {
+                tracing::warn!("Not fetching transactions into mempool: {err}");
+                tokio::time::sleep(self.sync_interval).await;
+                continue;
+            }
+
             let latency = KEEPER_METRICS.mempool_sync.start();
             let mut connection = self.pool.connection_tagged("state_keeper").await?;
             let mut storage_transaction = connection.start_transaction().await?;
@@ -178,6 +198,48 @@ impl MempoolFetcher {
                 transactions_with_constraints
             };
 
+            let transactions_with_constraints = if let Some(screener) = &self.address_screener {
+                let mut screened = Vec::with_capacity(transactions_with_constraints.len());
+                let mut screening_unavailable = None;
+                for (tx, constraint) in transactions_with_constraints {
+                    // Priority transactions cannot be rejected, so we only screen L2 transactions.
+                    let denied = if tx.is_l1() {
+                        None
+                    } else {
+                        match screener.screen_transaction(&tx).await {
+                            Ok(denied) => denied,
+                            Err(err) => {
+                                screening_unavailable = Some(err);
+                                break;
+                            }
+                        }
+                    };
+                    if let Some(denied) = denied {
+                        log_denied_transaction(tx.hash(), &denied, "mempool");
+                        KEEPER_METRICS.inc_rejected_txs("address_denied");
+                        storage_transaction
+                            .transactions_dal()
+                            .mark_tx_as_rejected(tx.hash(), &format!("rejected: {denied}"))
+                            .await
+                            .context("failed rejecting denied transaction")?;
+                    } else {
+                        screened.push((tx, constraint));
+                    }
+                }
+
+                if let Some(err) = screening_unavailable {
+                    // Dropping the DB transaction without committing returns fetched transactions to the mempool.
+                    tracing::warn!("Not fetching transactions into mempool: {err}");
+                    drop(storage_transaction);
+                    drop(connection);
+                    tokio::time::sleep(self.sync_interval).await;
+                    continue;
+                }
+                screened
+            } else {
+                transactions_with_constraints
+            };
+
             let transactions: Vec<_> = transactions_with_constraints
                 .iter()
                 .map(|(t, _c)| t)
@@ -243,8 +305,10 @@ mod tests {
     use zksync_node_genesis::{insert_genesis_batch, GenesisParams};
     use zksync_node_test_utils::create_l2_transaction;
     use zksync_types::{
-        u256_to_h256, L2BlockNumber, PriorityOpId, ProtocolVersionId, StorageLog, H256,
+        api::TransactionStatus, u256_to_h256, L2BlockNumber, PriorityOpId, ProtocolVersionId,
+        StorageLog, H256,
     };
+    use zksync_vm_executor::deny_list::SharedDenyList;
 
     use super::*;
 
@@ -347,6 +411,69 @@ mod tests {
         fetcher_task.await.unwrap().expect("fetcher errored");
     }
 
+    #[tokio::test]
+    async fn rejecting_transactions_from_denied_senders() {
+        let pool = ConnectionPool::constrained_test_pool(1).await;
+        let mut storage = pool.connection().await.unwrap();
+        insert_genesis_batch(&mut storage, &GenesisParams::mock())
+            .await
+            .unwrap();
+        drop(storage);
+
+        let mempool = MempoolGuard::new(PriorityOpId(0), 100);
+        let fee_params_provider: Arc<dyn BatchFeeModelInputProvider> =
+            Arc::new(MockBatchFeeParamsProvider::default());
+        let fee_input = fee_params_provider.get_batch_fee_input().await.unwrap();
+        let (base_fee, gas_per_pubdata) =
+            derive_base_fee_and_gas_per_pubdata(fee_input, ProtocolVersionId::latest().into());
+
+        let denied_transaction = create_l2_transaction(base_fee, gas_per_pubdata);
+        let transaction = create_l2_transaction(base_fee, gas_per_pubdata);
+        let deny_list = SharedDenyList::from(vec![denied_transaction.initiator_account()]);
+
+        let mut fetcher = MempoolFetcher::new(
+            mempool.clone(),
+            fee_params_provider,
+            &TEST_MEMPOOL_CONFIG,
+            pool.clone(),
+        )
+        .with_address_screener(AddressScreener::new(deny_list, false));
+        let (tx_hashes_sender, mut tx_hashes_receiver) = mpsc::unbounded_channel();
+        fetcher.transaction_hashes_sender = tx_hashes_sender;
+        let (stop_sender, stop_receiver) = watch::channel(false);
+        let fetcher_task = tokio::spawn(fetcher.run(stop_receiver));
+
+        let mut storage = pool.connection().await.unwrap();
+        for tx in [&denied_transaction, &transaction] {
+            storage
+                .transactions_dal()
+                .insert_transaction_l2(
+                    tx,
+                    TransactionExecutionMetrics::default(),
+                    ValidationTraces::default(),
+                )
+                .await
+                .unwrap();
+        }
+        drop(storage);
+
+        let tx_hashes = wait_for_new_transactions(&mut tx_hashes_receiver).await;
+        assert_eq!(tx_hashes, [transaction.hash()]);
+        assert_eq!(mempool.stats().l2_transaction_count, 1);
+
+        stop_sender.send_replace(true);
+        fetcher_task.await.unwrap().expect("fetcher errored");
+
+        let mut storage = pool.connection().await.unwrap();
+        let details = storage
+            .transactions_web3_dal()
+            .get_transaction_details(denied_transaction.hash())
+            .await
+            .unwrap()
+            .expect("no transaction details");
+        assert!(matches!(details.status, TransactionStatus::Failed));
+    }
+
     async fn wait_for_new_transactions(
         tx_hashes_receiver: &mut mpsc::UnboundedReceiver<Vec<H256>>,
     ) -> Vec<H256> {
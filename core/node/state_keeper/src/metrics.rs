@@ -206,6 +206,7 @@ impl From<&SealResolution> for SealResolutionLabel {
 #[metrics(label = "reason", rename_all = "snake_case")]
 pub(super) enum L2BlockSealReason {
     Timeout,
+    DynamicTimeout,
     PayloadSize,
 }
 
@@ -374,6 +375,11 @@ pub(super) struct L2BlockMetrics {
     /// Total latency of sealing an L2 block.
     #[metrics(buckets = Buckets::LATENCIES)]
     pub sealed_time: Histogram<Duration>,
+    /// Target L2 block time computed by the dynamic block time sealer based on the mempool load.
+    pub target_block_time: Gauge<Duration>,
+    /// Time between opening an L2 block and the decision to seal it. Only reported if dynamic block time is enabled.
+    #[metrics(buckets = Buckets::LATENCIES)]
+    pub achieved_block_time: Histogram<Duration>,
     /// Latency of sealing an L2 block split by the stage.
     #[metrics(buckets = Buckets::LATENCIES)]
     sealed_time_stage: Family<L2BlockSealLabels, Histogram<Duration>>,
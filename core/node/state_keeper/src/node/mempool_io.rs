@@ -13,6 +13,7 @@ use zksync_node_framework::{
 };
 use zksync_shared_resources::contracts::{L2ContractsResource, SettlementLayerContractsResource};
 use zksync_types::{commitment::PubdataType, L2ChainId};
+use zksync_vm_executor::deny_list::AddressScreener;
 
 use super::resources::{ConditionalSealerResource, StateKeeperIOResource};
 use crate::{MempoolFetcher, MempoolGuard, MempoolIO, SequencerSealer};
@@ -23,6 +24,7 @@ use crate::{MempoolFetcher, MempoolGuard, MempoolIO, SequencerSealer};
 ///
 /// - `FeeInputResource`
 /// - `PoolResource<MasterPool>`
+/// - `AddressScreener` (optional)
 ///
 /// ## Adds resources
 ///
@@ -47,6 +49,7 @@ pub struct Input {
     pub master_pool: PoolResource<MasterPool>,
     pub sl_contracts: SettlementLayerContractsResource,
     pub l2_contracts: L2ContractsResource,
+    pub address_screener: Option<AddressScreener>,
 }
 
 #[derive(Debug, IntoContext)]
@@ -111,19 +114,22 @@ impl WiringLayer for MempoolIOLayer {
             .get_singleton()
             .await
             .context("Get master pool")?;
-        let mempool_fetcher = MempoolFetcher::new(
+        let mut mempool_fetcher = MempoolFetcher::new(
             mempool_guard.clone(),
             batch_fee_input_provider.clone(),
             &self.mempool_config,
             mempool_fetcher_pool,
         );
+        if let Some(screener) = input.address_screener.clone() {
+            mempool_fetcher = mempool_fetcher.with_address_screener(screener);
+        }
 
         // Create mempool IO resource.
         let mempool_db_pool = master_pool
             .get_singleton()
             .await
             .context("Get master pool")?;
-        let io = MempoolIO::new(
+        let mut io = MempoolIO::new(
             mempool_guard,
             batch_fee_input_provider,
             mempool_db_pool,
@@ -134,6 +140,9 @@ impl WiringLayer for MempoolIOLayer {
             input.l2_contracts.0.da_validator_addr,
             self.pubdata_type,
         )?;
+        if let Some(screener) = input.address_screener {
+            io = io.with_address_screener(screener);
+        }
 
         // Create sealer.
         let sealer = SequencerSealer::new(self.state_keeper_config);
@@ -7,6 +7,7 @@ use zksync_node_framework::{
     service::ShutdownHook, task::TaskKind, FromContext, IntoContext, StopReceiver, Task, TaskId,
     WiringError, WiringLayer,
 };
+use zksync_shared_resources::api::Preconfirmations;
 use zksync_state::{AsyncCatchupTask, RocksdbStorageOptions};
 use zksync_storage::RocksDB;
 use zksync_vm_executor::whitelist::{DeploymentTxFilter, SharedAllowList};
@@ -38,6 +39,7 @@ pub struct Input {
 
 #[derive(Debug, IntoContext)]
 pub struct Output {
+    pub preconfirmations: Preconfirmations,
     #[context(task)]
     state_keeper: StateKeeperTask,
     #[context(task)]
@@ -91,6 +93,7 @@ impl WiringLayer for StateKeeperLayer {
         let recovery_pool = input.replica_pool.get_custom(10).await?;
         rocksdb_catchup = rocksdb_catchup.with_recovery_pool(recovery_pool);
 
+        let preconfirmations = Preconfirmations::default();
         let state_keeper = ZkSyncStateKeeper::new(
             io,
             batch_executor_base,
@@ -98,7 +101,8 @@ impl WiringLayer for StateKeeperLayer {
             sealer,
             Arc::new(storage_factory),
             input.shared_allow_list.map(DeploymentTxFilter::new),
-        );
+        )
+        .with_preconfirmations(preconfirmations.clone());
 
         let state_keeper = StateKeeperTask { state_keeper };
 
@@ -115,6 +119,7 @@ impl WiringLayer for StateKeeperLayer {
                 .context("failed terminating RocksDB instances")
         });
         Ok(Output {
+            preconfirmations,
             state_keeper,
             rocksdb_catchup: AsyncCatchupTaskWrapper(rocksdb_catchup),
             rocksdb_termination_hook,
@@ -3,14 +3,14 @@ use std::time::Duration;
 use anyhow::Context;
 use async_trait::async_trait;
 use tokio::time::Instant;
-use zksync_config::configs::chain::StateKeeperConfig;
+use zksync_config::configs::chain::{DynamicL2BlockTimeConfig, StateKeeperConfig};
 use zksync_dal::{ConnectionPool, Core, CoreDal};
 use zksync_types::utils::display_timestamp;
 
 use crate::{
-    metrics::AGGREGATION_METRICS,
+    metrics::{AGGREGATION_METRICS, L2_BLOCK_METRICS},
     utils::{millis_since, millis_since_epoch},
-    UpdatesManager,
+    MempoolGuard, UpdatesManager,
 };
 
 /// I/O-dependent seal criteria.
@@ -76,6 +76,67 @@ impl IoSealCriteria for TimeoutSealer {
     }
 }
 
+/// Seals L2 blocks once they reach the target block time, which adapts to the mempool load: it is shortened
+/// when the mempool is busy (to keep latency low) and lengthened when the mempool is idle (to produce fewer blocks).
+#[derive(Debug)]
+pub(crate) struct DynamicL2BlockTimeSealer {
+    min_l2_block_time_ms: u64,
+    max_l2_block_time_ms: u64,
+    busy_mempool_size: u64,
+    mempool: MempoolGuard,
+}
+
+impl DynamicL2BlockTimeSealer {
+    pub fn new(config: &DynamicL2BlockTimeConfig, mempool: MempoolGuard) -> Self {
+        let min_l2_block_time_ms = config.min_l2_block_time.as_millis() as u64;
+        Self {
+            min_l2_block_time_ms,
+            max_l2_block_time_ms: (config.max_l2_block_time.as_millis() as u64)
+                .max(min_l2_block_time_ms),
+            busy_mempool_size: config.busy_mempool_size.max(1),
+            mempool,
+        }
+    }
+
+    /// Interpolates the target block time linearly between the max value (empty mempool)
+    /// and the min value (busy mempool).
+    fn target_l2_block_time_ms(&self, mempool_size: u64) -> u64 {
+        let load = mempool_size.min(self.busy_mempool_size);
+        let range = self.max_l2_block_time_ms - self.min_l2_block_time_ms;
+        self.max_l2_block_time_ms - range * load / self.busy_mempool_size
+    }
+
+    /// Reports the time it took to fill the L2 block that is being sealed.
+    pub fn report_sealed_l2_block(&self, manager: &UpdatesManager) {
+        let achieved = manager.l2_block.opened_at.elapsed();
+        L2_BLOCK_METRICS.achieved_block_time.observe(achieved);
+    }
+}
+
+#[async_trait]
+impl IoSealCriteria for DynamicL2BlockTimeSealer {
+    async fn should_seal_l1_batch_unconditionally(
+        &mut self,
+        _manager: &UpdatesManager,
+    ) -> anyhow::Result<bool> {
+        Ok(false)
+    }
+
+    fn should_seal_l2_block(&mut self, manager: &UpdatesManager) -> bool {
+        if manager.l2_block.executed_transactions.is_empty() {
+            return false;
+        }
+
+        let mempool_size = self.mempool.stats().l2_transaction_count;
+        let target_ms = self.target_l2_block_time_ms(mempool_size);
+        L2_BLOCK_METRICS
+            .target_block_time
+            .set(Duration::from_millis(target_ms));
+        // L2 block timestamps have 1s resolution, which is too coarse for sub-second block times.
+        manager.l2_block.opened_at.elapsed() > Duration::from_millis(target_ms)
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 pub(crate) struct L2BlockMaxPayloadSizeSealer {
     max_payload_size: usize,
@@ -162,6 +223,8 @@ impl ProtocolUpgradeSealer {
 
 #[cfg(test)]
 mod tests {
+    use std::{collections::HashMap, time::Instant};
+
     use zksync_contracts::BaseSystemContracts;
     use zksync_multivm::{
         interface::{SystemEnv, TxExecutionMode, VmExecutionMetrics},
@@ -170,8 +233,8 @@ mod tests {
     use zksync_node_test_utils::default_l1_batch_env;
     use zksync_system_constants::ZKPORTER_IS_AVAILABLE;
     use zksync_types::{
-        protocol_version::ProtocolSemanticVersion, L2ChainId, ProtocolVersion, ProtocolVersionId,
-        Transaction,
+        protocol_version::ProtocolSemanticVersion, L2ChainId, PriorityOpId, ProtocolVersion,
+        ProtocolVersionId, Transaction,
     };
 
     use super::*;
@@ -221,6 +284,68 @@ mod tests {
         );
     }
 
+    #[test]
+    fn dynamic_l2_block_time_sealer() {
+        let config = DynamicL2BlockTimeConfig {
+            min_l2_block_time: Duration::from_secs(1),
+            max_l2_block_time: Duration::from_secs(5),
+            busy_mempool_size: 2,
+        };
+        let mut mempool = MempoolGuard::new(PriorityOpId(0), 100);
+        let mut sealer = DynamicL2BlockTimeSealer::new(&config, mempool.clone());
+        assert_eq!(sealer.target_l2_block_time_ms(0), 5_000);
+        assert_eq!(sealer.target_l2_block_time_ms(1), 3_000);
+        assert_eq!(sealer.target_l2_block_time_ms(2), 1_000);
+        assert_eq!(sealer.target_l2_block_time_ms(100), 1_000);
+
+        let mut manager = create_updates_manager();
+        manager.l2_block.opened_at = Instant::now() - Duration::from_secs(3);
+        assert!(
+            !sealer.should_seal_l2_block(&manager),
+            "Empty L2 block shouldn't be sealed"
+        );
+        apply_tx_to_manager(create_transaction(10, 100), &mut manager);
+        assert!(
+            !sealer.should_seal_l2_block(&manager),
+            "L2 block shouldn't be sealed before the max block time if the mempool is idle"
+        );
+
+        let txs = [create_transaction(10, 100), create_transaction(10, 100)];
+        mempool.insert_without_constraint(txs.into(), HashMap::new());
+        assert!(
+            sealer.should_seal_l2_block(&manager),
+            "L2 block should be sealed after the min block time if the mempool is busy"
+        );
+    }
+
+    #[test]
+    fn dynamic_l2_block_time_sealer_with_sub_second_block_time() {
+        let config = DynamicL2BlockTimeConfig {
+            min_l2_block_time: Duration::from_millis(200),
+            max_l2_block_time: Duration::from_millis(500),
+            busy_mempool_size: 1,
+        };
+        let mut mempool = MempoolGuard::new(PriorityOpId(0), 100);
+        let mut sealer = DynamicL2BlockTimeSealer::new(&config, mempool.clone());
+        mempool.insert_without_constraint(vec![create_transaction(10, 100)], HashMap::new());
+
+        let mut manager = create_updates_manager();
+        // The block timestamp is the current second, so it cannot tell whether 200ms have passed.
+        manager.l2_block.timestamp = seconds_since_epoch();
+        apply_tx_to_manager(create_transaction(10, 100), &mut manager);
+        manager.l2_block.opened_at = Instant::now();
+        assert!(
+            !sealer.should_seal_l2_block(&manager),
+            "L2 block shouldn't be sealed before the min block time"
+        );
+
+        manager.l2_block.opened_at = Instant::now() - Duration::from_millis(300);
+        assert!(
+            sealer.should_seal_l2_block(&manager),
+            "L2 block should be sealed after the min block time even if its timestamp is recent"
+        );
+    }
+
     #[test]
     fn max_size_l2_block_sealer() {
         let tx = create_transaction(10, 100);
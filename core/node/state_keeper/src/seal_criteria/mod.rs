@@ -28,6 +28,7 @@ use crate::metrics::AGGREGATION_METRICS;
 mod conditional_sealer;
 pub(super) mod criteria;
 pub(super) mod io_criteria;
+pub mod simulation;
 
 fn halt_as_metric_label(halt: &Halt) -> &'static str {
     match halt {
@@ -68,6 +69,10 @@ pub enum UnexecutableReason {
     NotEnoughGasProvided,
     TooMuchUserL2L1Logs,
     DeploymentNotAllowed,
+    BundleTxReverted,
+    BundleExpired,
+    BundleIncomplete,
+    AddressDenied,
 }
 
 impl UnexecutableReason {
@@ -84,6 +89,10 @@ impl UnexecutableReason {
             UnexecutableReason::NotEnoughGasProvided => "NotEnoughGasProvided",
             UnexecutableReason::TooMuchUserL2L1Logs => "TooMuchUserL2L1Logs",
             UnexecutableReason::DeploymentNotAllowed => "DeploymentNotAllowed",
+            UnexecutableReason::BundleTxReverted => "BundleTxReverted",
+            UnexecutableReason::BundleExpired => "BundleExpired",
+            UnexecutableReason::BundleIncomplete => "BundleIncomplete",
+            UnexecutableReason::AddressDenied => "AddressDenied",
         }
     }
 }
@@ -110,6 +119,12 @@ impl fmt::Display for UnexecutableReason {
             UnexecutableReason::NotEnoughGasProvided => write!(f, "Not enough gas provided"),
             UnexecutableReason::TooMuchUserL2L1Logs => write!(f, "Too much user l2 l1 logs"),
             UnexecutableReason::DeploymentNotAllowed => write!(f, "Deployment not allowed"),
+            UnexecutableReason::BundleTxReverted => write!(f, "Bundle transaction reverted"),
+            UnexecutableReason::BundleExpired => write!(f, "Bundle expired"),
+            UnexecutableReason::BundleIncomplete => write!(f, "Bundle is incomplete"),
+            UnexecutableReason::AddressDenied => {
+                write!(f, "Transaction interacts with a denied address")
+            }
         }
     }
 }
@@ -0,0 +1,319 @@
+//! Offline simulation of L1 batch sealing.
+//!
+//! [`SealSimulator`] feeds already executed transactions through a [`SequencerSealer`] built from an arbitrary
+//! [`StateKeeperConfig`] and records how L1 batches would have been cut. It's used to evaluate alternative
+//! seal criteria configurations against historic load without running a real state keeper.
+//!
+//! The simulation is approximate: transaction execution metrics are taken as is (i.e., they are not re-measured
+//! in the context of the simulated batch), and only conditional seal criteria are considered; I/O criteria
+//! (timeouts, protocol upgrades etc.) are not simulated.
+
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+use zksync_config::configs::chain::StateKeeperConfig;
+use zksync_multivm::{
+    interface::{VmExecutionMetrics, VmExecutionResultAndLogs},
+    utils::StorageWritesDeduplicator,
+};
+use zksync_types::{ProtocolVersionId, Transaction};
+
+use super::{ConditionalSealer, SealData, SealResolution, SequencerSealer};
+
+/// Summary of a single simulated L1 batch.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SimulatedL1Batch {
+    /// Protocol version of transactions in the batch.
+    pub protocol_version: ProtocolVersionId,
+    /// Number of transactions in the batch.
+    pub tx_count: usize,
+    /// Number of L1 transactions in the batch.
+    pub l1_tx_count: usize,
+    /// Estimated pubdata published by the batch (excluding the bootloader batch tip).
+    pub pubdata_bytes: usize,
+    /// Estimated number of base layer prover circuits used by the batch (excluding the bootloader batch tip).
+    pub circuits: usize,
+    /// Fractions of capacity filled for each seal criterion, keyed by the criterion name.
+    pub capacity_filled: BTreeMap<&'static str, f64>,
+}
+
+/// Report produced by [`SealSimulator::finish()`].
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SealSimulationReport {
+    /// Total number of processed transactions.
+    pub tx_count: usize,
+    /// Number of transactions that would be rejected as unexecutable under the simulated config,
+    /// keyed by the rejection reason.
+    pub unexecutable_txs: BTreeMap<String, usize>,
+    /// Simulated L1 batches in the order they would be sealed.
+    pub batches: Vec<SimulatedL1Batch>,
+    /// Average fractions of capacity filled for each seal criterion across all simulated batches.
+    pub average_capacity_filled: BTreeMap<&'static str, f64>,
+}
+
+#[derive(Debug, Default)]
+struct PendingBatch {
+    tx_count: usize,
+    l1_tx_count: usize,
+    execution_metrics: VmExecutionMetrics,
+    encoding_size: usize,
+    writes_deduplicator: StorageWritesDeduplicator,
+    protocol_version: Option<ProtocolVersionId>,
+}
+
+/// Simulates L1 batch sealing for a stream of executed transactions.
+#[derive(Debug)]
+pub struct SealSimulator {
+    sealer: SequencerSealer,
+    pending: PendingBatch,
+    tx_count: usize,
+    unexecutable_txs: BTreeMap<String, usize>,
+    batches: Vec<SimulatedL1Batch>,
+}
+
+impl SealSimulator {
+    /// Creates a simulator using the default seal criteria parameterized by the provided `config`.
+    pub fn new(config: StateKeeperConfig) -> Self {
+        Self {
+            sealer: SequencerSealer::new(config),
+            pending: PendingBatch::default(),
+            tx_count: 0,
+            unexecutable_txs: BTreeMap::new(),
+            batches: vec![],
+        }
+    }
+
+    /// Returns the number of batches sealed so far (not counting the currently open batch).
+    pub fn sealed_batch_count(&self) -> usize {
+        self.batches.len()
+    }
+
+    /// Processes an executed transaction, sealing the current simulated batch if necessary.
+    pub fn push_transaction(
+        &mut self,
+        tx: &Transaction,
+        tx_result: &VmExecutionResultAndLogs,
+        protocol_version: ProtocolVersionId,
+    ) {
+        self.tx_count += 1;
+        if self
+            .pending
+            .protocol_version
+            .is_some_and(|version| version != protocol_version)
+        {
+            // Batches cannot span protocol versions.
+            self.seal_batch();
+        }
+
+        let tx_data = SealData {
+            execution_metrics: tx_result.get_execution_metrics(),
+            cumulative_size: tx.encoding_len(),
+            writes_metrics: StorageWritesDeduplicator::apply_on_empty_state(
+                &tx_result.logs.storage_logs,
+            ),
+            gas_remaining: tx_result.statistics.gas_remaining,
+        };
+
+        let mut resolution = self.resolve(tx, tx_result, &tx_data, protocol_version);
+        if matches!(resolution, SealResolution::ExcludeAndSeal) {
+            self.seal_batch();
+            resolution = self.resolve(tx, tx_result, &tx_data, protocol_version);
+        }
+
+        match resolution {
+            SealResolution::NoSeal => self.include(tx, tx_result, &tx_data, protocol_version),
+            SealResolution::IncludeAndSeal | SealResolution::ExcludeAndSeal => {
+                // `ExcludeAndSeal` for an empty batch means that the transaction doesn't fit into a batch on its own;
+                // the state keeper would include it and seal the batch in this case.
+                self.include(tx, tx_result, &tx_data, protocol_version);
+                self.seal_batch();
+            }
+            SealResolution::Unexecutable(reason) => {
+                *self.unexecutable_txs.entry(reason.to_string()).or_default() += 1;
+            }
+        }
+    }
+
+    fn resolve(
+        &mut self,
+        tx: &Transaction,
+        tx_result: &VmExecutionResultAndLogs,
+        tx_data: &SealData,
+        protocol_version: ProtocolVersionId,
+    ) -> SealResolution {
+        let block_writes_metrics = self
+            .pending
+            .writes_deduplicator
+            .apply_and_rollback(&tx_result.logs.storage_logs);
+        let block_data = SealData {
+            execution_metrics: tx_data.execution_metrics + self.pending.execution_metrics,
+            cumulative_size: tx_data.cumulative_size + self.pending.encoding_size,
+            writes_metrics: block_writes_metrics,
+            gas_remaining: tx_data.gas_remaining,
+        };
+        self.sealer.should_seal_l1_batch(
+            self.batches.len() as u32,
+            self.pending.tx_count + 1,
+            self.pending.l1_tx_count + tx.is_l1() as usize,
+            &block_data,
+            tx_data,
+            protocol_version,
+        )
+    }
+
+    fn include(
+        &mut self,
+        tx: &Transaction,
+        tx_result: &VmExecutionResultAndLogs,
+        tx_data: &SealData,
+        protocol_version: ProtocolVersionId,
+    ) {
+        let pending = &mut self.pending;
+        pending.tx_count += 1;
+        pending.l1_tx_count += tx.is_l1() as usize;
+        pending.execution_metrics += tx_data.execution_metrics;
+        pending.encoding_size += tx_data.cumulative_size;
+        pending
+            .writes_deduplicator
+            .apply(&tx_result.logs.storage_logs);
+        pending.protocol_version = Some(protocol_version);
+    }
+
+    fn seal_batch(&mut self) {
+        let pending = std::mem::take(&mut self.pending);
+        let Some(protocol_version) = pending.protocol_version else {
+            return; // The batch is empty
+        };
+
+        let writes_metrics = pending.writes_deduplicator.metrics();
+        let pubdata_bytes =
+            pending.execution_metrics.size() + writes_metrics.size(protocol_version);
+        let circuits = pending.execution_metrics.circuit_statistic.total();
+        let block_data = SealData {
+            execution_metrics: pending.execution_metrics,
+            cumulative_size: pending.encoding_size,
+            writes_metrics,
+            gas_remaining: u32::MAX, // not used
+        };
+        let capacity_filled = self.sealer.capacity_filled(
+            pending.tx_count,
+            pending.l1_tx_count,
+            &block_data,
+            protocol_version,
+        );
+
+        self.batches.push(SimulatedL1Batch {
+            protocol_version,
+            tx_count: pending.tx_count,
+            l1_tx_count: pending.l1_tx_count,
+            pubdata_bytes,
+            circuits,
+            capacity_filled: capacity_filled.into_iter().collect(),
+        });
+    }
+
+    /// Seals the last simulated batch (if it's not empty) and returns the simulation report.
+    pub fn finish(mut self) -> SealSimulationReport {
+        self.seal_batch();
+
+        let mut average_capacity_filled = BTreeMap::<_, f64>::new();
+        for batch in &self.batches {
+            for (&name, &filled) in &batch.capacity_filled {
+                *average_capacity_filled.entry(name).or_default() += filled;
+            }
+        }
+        for filled in average_capacity_filled.values_mut() {
+            *filled /= self.batches.len() as f64;
+        }
+
+        SealSimulationReport {
+            tx_count: self.tx_count,
+            unexecutable_txs: self.unexecutable_txs,
+            batches: self.batches,
+            average_capacity_filled,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_node_test_utils::create_l2_transaction;
+    use zksync_types::{Execute, ExecuteTransactionCommon, L1TxCommonData};
+
+    use super::*;
+
+    fn l2_tx() -> Transaction {
+        create_l2_transaction(10, 100).into()
+    }
+
+    fn tx_result() -> VmExecutionResultAndLogs {
+        let mut result = VmExecutionResultAndLogs::mock_success();
+        result.statistics.gas_remaining = u32::MAX;
+        result
+    }
+
+    fn l1_tx() -> Transaction {
+        Transaction {
+            common_data: ExecuteTransactionCommon::L1(L1TxCommonData::default()),
+            execute: Execute::default(),
+            received_timestamp_ms: 0,
+            raw_bytes: None,
+        }
+    }
+
+    #[test]
+    fn simulating_seal_by_tx_slots() {
+        let config = StateKeeperConfig {
+            transaction_slots: 3,
+            ..StateKeeperConfig::for_tests()
+        };
+        let mut simulator = SealSimulator::new(config);
+        let protocol_version = ProtocolVersionId::latest();
+        for i in 0..7 {
+            let tx = if i == 0 { l1_tx() } else { l2_tx() };
+            simulator.push_transaction(&tx, &tx_result(), protocol_version);
+        }
+        assert_eq!(simulator.sealed_batch_count(), 2);
+
+        let report = simulator.finish();
+        assert_eq!(report.tx_count, 7);
+        assert!(report.unexecutable_txs.is_empty());
+        let tx_counts: Vec<_> = report.batches.iter().map(|batch| batch.tx_count).collect();
+        assert_eq!(tx_counts, [3, 3, 1]);
+        assert_eq!(report.batches[0].l1_tx_count, 1);
+        assert_eq!(report.batches[1].l1_tx_count, 0);
+
+        let slots_filled = report.batches[0].capacity_filled["slots"];
+        assert!((slots_filled - 1.0).abs() < 1e-6, "{slots_filled}");
+        assert!(report.average_capacity_filled.contains_key("slots"));
+    }
+
+    #[test]
+    fn protocol_version_change_seals_batch() {
+        let mut simulator = SealSimulator::new(StateKeeperConfig::for_tests());
+        let old_version = ProtocolVersionId::Version27;
+        simulator.push_transaction(&l2_tx(), &tx_result(), old_version);
+        simulator.push_transaction(&l2_tx(), &tx_result(), old_version);
+        simulator.push_transaction(&l2_tx(), &tx_result(), ProtocolVersionId::Version28);
+
+        let report = simulator.finish();
+        assert_eq!(report.batches.len(), 2);
+        assert_eq!(report.batches[0].protocol_version, old_version);
+        assert_eq!(report.batches[0].tx_count, 2);
+        assert_eq!(report.batches[1].tx_count, 1);
+    }
+
+    #[test]
+    fn unexecutable_transactions_are_reported() {
+        let mut simulator = SealSimulator::new(StateKeeperConfig::for_tests());
+        simulator.push_transaction(&l2_tx(), &tx_result(), ProtocolVersionId::latest());
+        let out_of_gas_result = VmExecutionResultAndLogs::mock_success();
+        simulator.push_transaction(&l2_tx(), &out_of_gas_result, ProtocolVersionId::latest());
+
+        let report = simulator.finish();
+        assert_eq!(report.tx_count, 2);
+        assert_eq!(report.batches.len(), 1);
+        assert_eq!(report.batches[0].tx_count, 1);
+        assert_eq!(report.unexecutable_txs.values().sum::<usize>(), 1);
+    }
+}
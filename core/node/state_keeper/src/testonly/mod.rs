@@ -64,6 +64,14 @@ impl BatchExecutor<OwnedStorage> for MockBatchExecutor {
         panic!("unexpected rollback");
     }
 
+    async fn start_bundle(&mut self) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    async fn finish_bundle(&mut self, _rollback: bool) -> anyhow::Result<()> {
+        Ok(())
+    }
+
     async fn start_next_l2_block(&mut self, _env: L2BlockEnv) -> anyhow::Result<()> {
         Ok(())
     }
@@ -20,16 +20,21 @@ use zksync_multivm::{
         executor::{BatchExecutor, BatchExecutorFactory},
         storage::InMemoryStorage,
         BatchTransactionExecutionResult, ExecutionResult, FinishedL1Batch, Halt, L1BatchEnv,
-        L2BlockEnv, SystemEnv, VmExecutionLogs, VmExecutionResultAndLogs,
+        L2BlockEnv, SystemEnv, VmExecutionLogs, VmExecutionResultAndLogs, VmRevertReason,
     },
     vm_latest::constants::BATCH_COMPUTATIONAL_GAS_LIMIT,
 };
 use zksync_node_test_utils::create_l2_transaction;
+use zksync_shared_resources::api::Preconfirmations;
 use zksync_state::{interface::StorageView, OwnedStorage, ReadStorageFactory};
 use zksync_types::{
-    commitment::PubdataParams, fee_model::BatchFeeInput, l2_to_l1_log::UserL2ToL1Log,
-    protocol_upgrade::ProtocolUpgradeTx, Address, L1BatchNumber, L2BlockNumber, L2ChainId,
-    OrStopped, ProtocolVersionId, Transaction, H256,
+    bundle::{BundleConstraints, TransactionBundle},
+    commitment::PubdataParams,
+    fee_model::BatchFeeInput,
+    l2_to_l1_log::UserL2ToL1Log,
+    protocol_upgrade::ProtocolUpgradeTx,
+    Address, L1BatchNumber, L2BlockNumber, L2ChainId, OrStopped, ProtocolVersionId, Transaction,
+    H256,
 };
 
 use crate::{
@@ -59,6 +64,7 @@ pub(crate) struct TestScenario {
     pending_batch: Option<PendingBatchData>,
     l1_batch_seal_fn: Box<SealFn>,
     l2_block_seal_fn: Box<SealFn>,
+    preconfirmations: Option<Preconfirmations>,
 }
 
 type SealFn = dyn FnMut(&UpdatesManager) -> bool + Send + Sync;
@@ -80,9 +86,16 @@ impl TestScenario {
             pending_batch: None,
             l1_batch_seal_fn: Box::new(|_| false),
             l2_block_seal_fn: Box::new(|_| false),
+            preconfirmations: None,
         }
     }
 
+    /// Makes the state keeper publish preconfirmations to the provided stream.
+    pub(crate) fn with_preconfirmations(mut self, preconfirmations: Preconfirmations) -> Self {
+        self.preconfirmations = Some(preconfirmations);
+        self
+    }
+
     /// Adds a pending batch data that would be fed into the state keeper.
     /// Note that during processing pending batch, state keeper do *not* call `seal_l2_block` method on the IO (since
     /// it only recovers the temporary state).
@@ -119,6 +132,40 @@ impl TestScenario {
         self
     }
 
+    /// Expect the state keeper to request a bundle from IO.
+    /// Adds both a bundle and outcomes of its transactions (that would be returned to the state keeper from the
+    /// batch executor). `results` must have the same length as the bundle transactions.
+    pub(crate) fn next_bundle(
+        mut self,
+        description: &'static str,
+        bundle: TransactionBundle,
+        results: Vec<BatchTransactionExecutionResult>,
+    ) -> Self {
+        assert_eq!(bundle.transactions.len(), results.len());
+        self.actions
+            .push_back(ScenarioItem::Bundle(description, bundle, results));
+        self
+    }
+
+    /// Expect the state keeper to rollback the bundle (i.e. return to the mempool).
+    pub(crate) fn bundle_rollback(mut self, description: &'static str, bundle_hash: H256) -> Self {
+        self.actions
+            .push_back(ScenarioItem::BundleRollback(description, bundle_hash));
+        self
+    }
+
+    /// Expect the state keeper to reject the bundle.
+    pub(crate) fn bundle_rejected(
+        mut self,
+        description: &'static str,
+        bundle_hash: H256,
+        err: UnexecutableReason,
+    ) -> Self {
+        self.actions
+            .push_back(ScenarioItem::BundleReject(description, bundle_hash, err));
+        self
+    }
+
     /// Expect the state keeper to rollback the transaction (i.e. return to the mempool).
     pub(crate) fn tx_rollback(mut self, description: &'static str, tx: Transaction) -> Self {
         self.actions
@@ -209,13 +256,14 @@ impl TestScenario {
 
     /// Launches the test.
     /// Provided `SealManager` is expected to be externally configured to adhere the written scenario logic.
-    pub(crate) async fn run(self, sealer: SequencerSealer) {
+    pub(crate) async fn run(mut self, sealer: SequencerSealer) {
         assert!(!self.actions.is_empty(), "Test scenario can't be empty");
 
         let batch_executor = TestBatchExecutorBuilder::new(&self);
+        let preconfirmations = self.preconfirmations.take();
         let (stop_sender, stop_receiver) = watch::channel(false);
         let (io, output_handler) = TestIO::new(stop_sender, self);
-        let state_keeper = ZkSyncStateKeeper::new(
+        let mut state_keeper = ZkSyncStateKeeper::new(
             Box::new(io),
             Box::new(batch_executor),
             output_handler,
@@ -223,6 +271,9 @@ impl TestScenario {
             Arc::new(MockReadStorageFactory),
             None,
         );
+        if let Some(preconfirmations) = preconfirmations {
+            state_keeper = state_keeper.with_preconfirmations(preconfirmations);
+        }
         let sk_thread = tokio::spawn(state_keeper.run(stop_receiver));
 
         // We must assume that *theoretically* state keeper may ignore the stop request from IO once scenario is
@@ -281,6 +332,29 @@ pub(crate) fn successful_exec_with_log() -> BatchTransactionExecutionResult {
     }
 }
 
+/// Creates a bundle without constraints from the provided transactions.
+pub(crate) fn bundle_from_txs(transactions: Vec<Transaction>) -> TransactionBundle {
+    TransactionBundle {
+        hash: TransactionBundle::compute_hash(transactions.iter().map(Transaction::hash)),
+        transactions,
+        constraints: BundleConstraints::default(),
+    }
+}
+
+/// Creates a `TxExecutionResult` object denoting a tx that was reverted.
+pub(crate) fn reverted_exec() -> BatchTransactionExecutionResult {
+    BatchTransactionExecutionResult {
+        tx_result: Box::new(VmExecutionResultAndLogs::mock(ExecutionResult::Revert {
+            output: VmRevertReason::General {
+                msg: "reverted".to_owned(),
+                data: vec![],
+            },
+        })),
+        compression_result: Ok(()),
+        call_traces: vec![],
+    }
+}
+
 /// Creates a `TxExecutionResult` object denoting a tx that was rejected.
 pub(crate) fn rejected_exec(reason: Halt) -> BatchTransactionExecutionResult {
     BatchTransactionExecutionResult {
@@ -301,6 +375,13 @@ enum ScenarioItem {
     Tx(&'static str, Transaction, BatchTransactionExecutionResult),
     Rollback(&'static str, Transaction),
     Reject(&'static str, Transaction, UnexecutableReason),
+    Bundle(
+        &'static str,
+        TransactionBundle,
+        Vec<BatchTransactionExecutionResult>,
+    ),
+    BundleRollback(&'static str, H256),
+    BundleReject(&'static str, H256, UnexecutableReason),
     L2BlockSeal(
         &'static str,
         Option<Box<dyn FnOnce(&UpdatesManager) + Send>>,
@@ -341,6 +422,23 @@ impl fmt::Debug for ScenarioItem {
                 .field(tx)
                 .field(err)
                 .finish(),
+            Self::Bundle(descr, bundle, results) => formatter
+                .debug_tuple("Bundle")
+                .field(descr)
+                .field(bundle)
+                .field(results)
+                .finish(),
+            Self::BundleRollback(descr, bundle_hash) => formatter
+                .debug_tuple("BundleRollback")
+                .field(descr)
+                .field(bundle_hash)
+                .finish(),
+            Self::BundleReject(descr, bundle_hash, err) => formatter
+                .debug_tuple("BundleReject")
+                .field(descr)
+                .field(bundle_hash)
+                .field(err)
+                .finish(),
             Self::L2BlockSeal(descr, _) => {
                 formatter.debug_tuple("L2BlockSeal").field(descr).finish()
             }
@@ -402,6 +500,16 @@ impl TestBatchExecutorBuilder {
                         batch_txs.insert(tx.hash(), VecDeque::from([result]));
                     }
                 }
+                ScenarioItem::Bundle(_, bundle, results) => {
+                    for (tx, result) in bundle.transactions.iter().zip(results) {
+                        let result = BatchTransactionExecutionResult {
+                            tx_result: result.tx_result.clone(),
+                            compression_result: Ok(()),
+                            call_traces: result.call_traces.clone(),
+                        };
+                        batch_txs.entry(tx.hash()).or_default().push_back(result);
+                    }
+                }
                 ScenarioItem::Rollback(_, tx) => {
                     rollback_set.insert(tx.hash());
                 }
@@ -509,6 +617,14 @@ impl BatchExecutor<OwnedStorage> for TestBatchExecutor {
         Ok(())
     }
 
+    async fn start_bundle(&mut self) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    async fn finish_bundle(&mut self, _rollback: bool) -> anyhow::Result<()> {
+        Ok(())
+    }
+
     async fn start_next_l2_block(&mut self, _env: L2BlockEnv) -> anyhow::Result<()> {
         Ok(())
     }
@@ -807,6 +923,56 @@ impl StateKeeperIO for TestIO {
         Ok(())
     }
 
+    async fn next_bundle(
+        &mut self,
+        _l2_block_number: L2BlockNumber,
+        _l2_block_timestamp: u64,
+    ) -> anyhow::Result<Option<TransactionBundle>> {
+        let is_bundle_next = matches!(
+            self.actions.lock().unwrap().front(),
+            Some(ScenarioItem::Bundle(..))
+        );
+        if self.skipping_txs || !is_bundle_next {
+            return Ok(None);
+        }
+        let ScenarioItem::Bundle(_, bundle, _) = self.pop_next_item("next_bundle") else {
+            unreachable!();
+        };
+        Ok(Some(bundle))
+    }
+
+    async fn rollback_bundle(&mut self, bundle: TransactionBundle) -> anyhow::Result<()> {
+        let action = self.pop_next_item("rollback_bundle");
+        let ScenarioItem::BundleRollback(_, expected_hash) = action else {
+            panic!("Unexpected action: {:?}", action);
+        };
+        assert_eq!(
+            bundle.hash, expected_hash,
+            "Incorrect bundle has been rolled back"
+        );
+        self.skipping_txs = false;
+        Ok(())
+    }
+
+    async fn reject_bundle(
+        &mut self,
+        bundle: &TransactionBundle,
+        reason: UnexecutableReason,
+    ) -> anyhow::Result<()> {
+        let action = self.pop_next_item("reject_bundle");
+        let ScenarioItem::BundleReject(_, expected_hash, expected_err) = action else {
+            panic!("Unexpected action: {:?}", action);
+        };
+        assert_eq!(
+            bundle.hash, expected_hash,
+            "Incorrect bundle has been rejected"
+        );
+        assert_eq!(reason, expected_err);
+
+        self.skipping_txs = false;
+        Ok(())
+    }
+
     async fn load_base_system_contracts(
         &self,
         _protocol_version: ProtocolVersionId,
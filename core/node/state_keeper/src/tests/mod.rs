@@ -16,7 +16,9 @@ use zksync_multivm::{
     vm_latest::constants::BATCH_COMPUTATIONAL_GAS_LIMIT,
 };
 use zksync_node_test_utils::{create_l2_transaction, default_l1_batch_env, default_system_env};
+use zksync_shared_resources::api::Preconfirmations;
 use zksync_types::{
+    api::PreconfirmationStatus,
     block::{L2BlockExecutionData, L2BlockHasher},
     u256_to_h256, AccountTreeId, Address, L1BatchNumber, L2BlockNumber, L2ChainId,
     ProtocolVersionId, StorageKey, StorageLog, StorageLogKind, StorageLogWithPreviousValue,
@@ -30,8 +32,8 @@ use crate::{
     testonly::{
         successful_exec,
         test_batch_executor::{
-            random_tx, random_upgrade_tx, rejected_exec, MockReadStorageFactory,
-            TestBatchExecutorBuilder, TestIO, TestScenario, FEE_ACCOUNT,
+            bundle_from_txs, random_tx, random_upgrade_tx, rejected_exec, reverted_exec,
+            MockReadStorageFactory, TestBatchExecutorBuilder, TestIO, TestScenario, FEE_ACCOUNT,
         },
         BASE_SYSTEM_CONTRACTS,
     },
@@ -243,6 +245,62 @@ async fn bootloader_tip_out_of_gas_flow() {
         .await;
 }
 
+#[tokio::test]
+async fn bundle_is_executed_atomically() {
+    let config = StateKeeperConfig {
+        transaction_slots: 3,
+        ..StateKeeperConfig::for_tests()
+    };
+    let sealer = SequencerSealer::with_sealers(config, vec![Box::new(SlotsCriterion)]);
+
+    let reverted_bundle = bundle_from_txs(vec![random_tx(1), random_tx(2)]);
+    let reverted_bundle_hash = reverted_bundle.hash;
+    let standalone_tx = random_tx(3);
+    let standalone_tx_hash = standalone_tx.hash();
+    let bundle = bundle_from_txs(vec![random_tx(4), random_tx(5)]);
+    let bundle_tx_hashes: Vec<_> = bundle.tx_hashes().collect();
+
+    TestScenario::new()
+        .seal_l2_block_when(|updates| updates.l2_block.executed_transactions.len() == 1)
+        .next_bundle(
+            "Bundle with the last tx reverted",
+            reverted_bundle,
+            vec![successful_exec(), reverted_exec()],
+        )
+        .bundle_rejected(
+            "Bundle got rejected",
+            reverted_bundle_hash,
+            UnexecutableReason::BundleTxReverted,
+        )
+        .next_tx("Standalone tx", standalone_tx, successful_exec())
+        .l2_block_sealed_with("L2 block without bundle txs", move |updates| {
+            let tx_hashes: Vec<_> = updates
+                .l2_block
+                .executed_transactions
+                .iter()
+                .map(|tx| tx.hash)
+                .collect();
+            assert_eq!(tx_hashes, [standalone_tx_hash]);
+        })
+        .next_bundle(
+            "Successful bundle",
+            bundle,
+            vec![successful_exec(), successful_exec()],
+        )
+        .l2_block_sealed_with("L2 block with all bundle txs", move |updates| {
+            let tx_hashes: Vec<_> = updates
+                .l2_block
+                .executed_transactions
+                .iter()
+                .map(|tx| tx.hash)
+                .collect();
+            assert_eq!(tx_hashes, bundle_tx_hashes);
+        })
+        .batch_sealed("Batch sealed with the standalone tx and the bundle")
+        .run(sealer)
+        .await;
+}
+
 #[tokio::test]
 async fn pending_batch_is_applied() {
     let config = StateKeeperConfig {
@@ -530,3 +588,114 @@ async fn l2_block_timestamp_updated_after_first_tx() {
         .run(sealer)
         .await;
 }
+
+#[tokio::test]
+async fn preconfirmations_are_published_and_rolled_back() {
+    let config = StateKeeperConfig {
+        transaction_slots: 3,
+        ..StateKeeperConfig::for_tests()
+    };
+    let sealer = SequencerSealer::with_sealers(config, vec![Box::new(SlotsCriterion)]);
+    let preconfirmations = Preconfirmations::default();
+    let mut receiver = preconfirmations.subscribe();
+    let (first_tx, second_tx, third_tx) = (random_tx(1), random_tx(2), random_tx(3));
+
+    TestScenario::new()
+        .with_preconfirmations(preconfirmations.clone())
+        .seal_l2_block_when(|updates| updates.l2_block.executed_transactions.len() == 2)
+        .next_tx("First tx", first_tx.clone(), successful_exec())
+        .next_tx("Second tx", second_tx.clone(), successful_exec())
+        .l2_block_sealed("L2 block 1")
+        // The state keeper is stopped before this transaction gets sealed.
+        .next_tx("Third tx", third_tx.clone(), successful_exec())
+        .run(sealer)
+        .await;
+
+    let mut published = vec![];
+    while let Ok(preconfirmation) = receiver.try_recv() {
+        published.push((preconfirmation.transaction_hash, preconfirmation.status));
+    }
+    assert_eq!(
+        published,
+        [
+            (first_tx.hash(), PreconfirmationStatus::Preconfirmed),
+            (second_tx.hash(), PreconfirmationStatus::Preconfirmed),
+            (third_tx.hash(), PreconfirmationStatus::Preconfirmed),
+            (third_tx.hash(), PreconfirmationStatus::RolledBack),
+        ]
+    );
+
+    let second_preconfirmation = preconfirmations.get(second_tx.hash()).unwrap();
+    assert_eq!(
+        second_preconfirmation.status,
+        PreconfirmationStatus::Preconfirmed
+    );
+    assert_eq!(second_preconfirmation.block_number, 1.into());
+    assert_eq!(second_preconfirmation.transaction_index, 1.into());
+    assert!(second_preconfirmation.success);
+    let third_preconfirmation = preconfirmations.get(third_tx.hash()).unwrap();
+    assert_eq!(
+        third_preconfirmation.status,
+        PreconfirmationStatus::RolledBack
+    );
+    assert_eq!(third_preconfirmation.block_number, 2.into());
+}
+
+#[tokio::test]
+async fn preconfirmations_are_rolled_back_for_rejected_bundle() {
+    let config = StateKeeperConfig {
+        transaction_slots: 3,
+        ..StateKeeperConfig::for_tests()
+    };
+    let sealer = SequencerSealer::with_sealers(config, vec![Box::new(SlotsCriterion)]);
+    let preconfirmations = Preconfirmations::default();
+    let mut receiver = preconfirmations.subscribe();
+    let (first_tx, second_tx) = (random_tx(1), random_tx(2));
+    let reverted_bundle = bundle_from_txs(vec![first_tx.clone(), second_tx]);
+    let reverted_bundle_hash = reverted_bundle.hash;
+    let standalone_tx = random_tx(3);
+
+    TestScenario::new()
+        .with_preconfirmations(preconfirmations.clone())
+        .seal_l2_block_when(|updates| updates.l2_block.executed_transactions.len() == 1)
+        .next_bundle(
+            "Bundle with the last tx reverted",
+            reverted_bundle,
+            vec![successful_exec(), reverted_exec()],
+        )
+        .bundle_rejected(
+            "Bundle got rejected",
+            reverted_bundle_hash,
+            UnexecutableReason::BundleTxReverted,
+        )
+        .next_tx("Standalone tx", standalone_tx.clone(), successful_exec())
+        .l2_block_sealed("L2 block without bundle txs")
+        .run(sealer)
+        .await;
+
+    let mut published = vec![];
+    while let Ok(preconfirmation) = receiver.try_recv() {
+        published.push((
+            preconfirmation.transaction_hash,
+            preconfirmation.status,
+            preconfirmation.transaction_index,
+        ));
+    }
+    // The bundle tx is rolled back as soon as the bundle is rejected, rather than on state keeper shutdown.
+    assert_eq!(
+        published,
+        [
+            (
+                first_tx.hash(),
+                PreconfirmationStatus::Preconfirmed,
+                0.into()
+            ),
+            (first_tx.hash(), PreconfirmationStatus::RolledBack, 0.into()),
+            (
+                standalone_tx.hash(),
+                PreconfirmationStatus::Preconfirmed,
+                0.into()
+            ),
+        ]
+    );
+}
@@ -76,6 +76,13 @@ impl MempoolGuard {
             .rollback(rejected)
     }
 
+    pub fn advance_account_nonce(&mut self, address: Address, next_nonce: Nonce) {
+        self.0
+            .lock()
+            .expect("failed to acquire mempool lock")
+            .advance_account_nonce(address, next_nonce);
+    }
+
     pub fn get_mempool_info(&mut self) -> MempoolInfo {
         self.0
             .lock()
@@ -83,7 +90,6 @@ impl MempoolGuard {
             .get_mempool_info()
     }
 
-    #[cfg(test)]
     pub fn stats(&self) -> zksync_mempool::MempoolStats {
         self.0
             .lock()
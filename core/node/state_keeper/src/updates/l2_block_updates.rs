@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, time::Instant};
 
 use zksync_multivm::{
     interface::{
@@ -29,6 +29,8 @@ pub struct L2BlockUpdates {
     pub payload_encoding_size: usize,
     pub l1_tx_count: usize,
     pub timestamp: u64,
+    /// Time the block was opened at. Unlike `timestamp`, has sub-second resolution, so it can be used to measure block time.
+    pub opened_at: Instant,
     pub number: L2BlockNumber,
     pub prev_block_hash: H256,
     pub virtual_blocks: u32,
@@ -55,6 +57,7 @@ impl L2BlockUpdates {
             payload_encoding_size: 0,
             l1_tx_count: 0,
             timestamp,
+            opened_at: Instant::now(),
             number,
             prev_block_hash,
             virtual_blocks,
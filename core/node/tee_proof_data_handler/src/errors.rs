@@ -4,6 +4,8 @@ use axum::{
 };
 use zksync_dal::DalError;
 use zksync_object_store::ObjectStoreError;
+use zksync_tee_prover_interface::signatures::SignatureError;
+use zksync_types::{tee_types::TeeType, L1BatchNumber};
 
 #[derive(Debug, thiserror::Error)]
 pub enum TeeProcessorError {
@@ -16,6 +18,29 @@ pub enum TeeProcessorError {
     },
     #[error("Failed fetching/saving from db: {0}")]
     Dal(#[from] DalError),
+    #[error("Invalid public key: {0}")]
+    InvalidPubkey(String),
+    #[error("No attestation is registered for the public key")]
+    UnregisteredPubkey,
+    #[error("Invalid proof signature: {0}")]
+    InvalidSignature(#[from] SignatureError),
+    #[error("Verifier inputs for batch {0} are not available")]
+    InputsNotFound(L1BatchNumber),
+    #[error("Root hash for batch {0} is not computed yet")]
+    RootHashNotFound(L1BatchNumber),
+    #[error("Invalid attestation quote: {0}")]
+    InvalidAttestation(String),
+    #[error("Attestation quote is produced by {actual} TEE, while {expected} was expected")]
+    TeeTypeMismatch { expected: TeeType, actual: TeeType },
+    #[error("{tee_type} measurement {measurement} is not in the allowlist")]
+    MeasurementNotAllowed {
+        tee_type: TeeType,
+        measurement: String,
+    },
+    #[error("{tee_type} attestation quote has TCB status {status}, which is not allowed")]
+    TcbStatusNotAllowed { tee_type: TeeType, status: String },
+    #[error("Attestation quote report data doesn't bind the public key")]
+    ReportDataMismatch,
 }
 
 impl TeeProcessorError {
@@ -24,6 +49,15 @@ impl TeeProcessorError {
             Self::GeneralError(_) => StatusCode::INTERNAL_SERVER_ERROR,
             Self::ObjectStore { .. } => StatusCode::INTERNAL_SERVER_ERROR,
             Self::Dal(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            Self::InvalidPubkey(_)
+            | Self::InvalidSignature(_)
+            | Self::InvalidAttestation(_)
+            | Self::TeeTypeMismatch { .. }
+            | Self::ReportDataMismatch => StatusCode::BAD_REQUEST,
+            Self::UnregisteredPubkey
+            | Self::MeasurementNotAllowed { .. }
+            | Self::TcbStatusNotAllowed { .. } => StatusCode::FORBIDDEN,
+            Self::InputsNotFound(_) | Self::RootHashNotFound(_) => StatusCode::NOT_FOUND,
         }
     }
 }
@@ -1,9 +1,16 @@
 use std::{net::SocketAddr, sync::Arc};
 
 use anyhow::Context as _;
-use axum::{extract::Path, http::StatusCode, response::IntoResponse, routing::post, Json, Router};
+use axum::{
+    extract::Path,
+    http::StatusCode,
+    response::IntoResponse,
+    routing::{get, post},
+    Json, Router,
+};
 use tee_request_processor::TeeRequestProcessor;
 use tokio::sync::watch;
+use verification::AttestationVerifier;
 use zksync_config::configs::TeeProofDataHandlerConfig;
 use zksync_dal::{ConnectionPool, Core};
 use zksync_object_store::ObjectStore;
@@ -18,6 +25,7 @@ pub mod node;
 mod tee_request_processor;
 #[cfg(test)]
 mod tests;
+mod verification;
 
 pub async fn run_server(
     config: TeeProofDataHandlerConfig,
@@ -29,10 +37,13 @@ pub async fn run_server(
 ) -> anyhow::Result<()> {
     let bind_address = SocketAddr::from(([0, 0, 0, 0], config.http_port));
     tracing::info!("Starting proof data handler server on {bind_address}");
+    let attestation_verifier =
+        AttestationVerifier::new(&config).context("failed initializing attestation verifier")?;
     let app = create_proof_processing_router(
         blob_store,
         connection_pool,
         config,
+        Arc::new(attestation_verifier),
         commitment_mode,
         l2_chain_id,
     );
@@ -57,11 +68,18 @@ fn create_proof_processing_router(
     blob_store: Arc<dyn ObjectStore>,
     connection_pool: ConnectionPool<Core>,
     config: TeeProofDataHandlerConfig,
+    attestation_verifier: Arc<AttestationVerifier>,
     _commitment_mode: L1BatchCommitmentMode,
     l2_chain_id: L2ChainId,
 ) -> Router {
-    let get_tee_proof_gen_processor =
-        TeeRequestProcessor::new(blob_store, connection_pool, config.clone(), l2_chain_id);
+    let get_tee_proof_gen_processor = TeeRequestProcessor::new(
+        blob_store,
+        connection_pool,
+        config.clone(),
+        attestation_verifier,
+        l2_chain_id,
+    );
+    let get_tee_batch_inputs_processor = get_tee_proof_gen_processor.clone();
     let submit_tee_proof_processor = get_tee_proof_gen_processor.clone();
     let register_tee_attestation_processor = get_tee_proof_gen_processor.clone();
 
@@ -82,6 +100,14 @@ fn create_proof_processing_router(
                 },
             ),
         )
+        .route(
+            "/tee/proof_inputs/:l1_batch_number",
+            get(move |l1_batch_number: Path<u32>| async move {
+                get_tee_batch_inputs_processor
+                    .get_proof_generation_data_for_batch(l1_batch_number)
+                    .await
+            }),
+        )
         .route(
             "/tee/submit_proofs/:l1_batch_number",
             post(
@@ -18,17 +18,23 @@ use zksync_tee_prover_interface::{
         SubmitTeeProofResponse, TeeProofGenerationDataRequest, TeeProofGenerationDataResponse,
     },
     inputs::{TeeVerifierInput, V1TeeVerifierInput},
+    signatures::verify_root_hash_signature,
 };
 use zksync_types::{tee_types::TeeType, L1BatchNumber, L2ChainId};
 use zksync_vm_executor::storage::L1BatchParamsProvider;
 
-use crate::{errors::TeeProcessorError, metrics::METRICS};
+use crate::{
+    errors::TeeProcessorError,
+    metrics::METRICS,
+    verification::{parse_pubkey, AttestationVerifier},
+};
 
 #[derive(Clone)]
 pub(crate) struct TeeRequestProcessor {
     blob_store: Arc<dyn ObjectStore>,
     pool: ConnectionPool<Core>,
     config: TeeProofDataHandlerConfig,
+    attestation_verifier: Arc<AttestationVerifier>,
     l2_chain_id: L2ChainId,
 }
 
@@ -37,12 +43,14 @@ impl TeeRequestProcessor {
         blob_store: Arc<dyn ObjectStore>,
         pool: ConnectionPool<Core>,
         config: TeeProofDataHandlerConfig,
+        attestation_verifier: Arc<AttestationVerifier>,
         l2_chain_id: L2ChainId,
     ) -> Self {
         Self {
             blob_store,
             pool,
             config,
+            attestation_verifier,
             l2_chain_id,
         }
     }
@@ -111,6 +119,28 @@ impl TeeRequestProcessor {
         }
     }
 
+    /// Returns verifier inputs for the specified batch without locking it for proving. Used to audit
+    /// already proven batches offline.
+    pub(crate) async fn get_proof_generation_data_for_batch(
+        &self,
+        Path(l1_batch_number): Path<u32>,
+    ) -> Result<Json<TeeProofGenerationDataResponse>, TeeProcessorError> {
+        let l1_batch_number = L1BatchNumber(l1_batch_number);
+        tracing::info!("Received request for proof generation data for batch {l1_batch_number}");
+
+        match self
+            .tee_verifier_input_for_existing_batch(l1_batch_number)
+            .await
+        {
+            Ok(input) => Ok(Json(TeeProofGenerationDataResponse(Box::new(input)))),
+            Err(TeeProcessorError::ObjectStore {
+                source: ObjectStoreError::KeyNotFound(_),
+                ..
+            }) => Err(TeeProcessorError::InputsNotFound(l1_batch_number)),
+            Err(err) => Err(err),
+        }
+    }
+
     #[tracing::instrument(skip(self))]
     async fn tee_verifier_input_for_existing_batch(
         &self,
@@ -221,7 +251,29 @@ impl TeeRequestProcessor {
         Json(proof): Json<SubmitTeeProofRequest>,
     ) -> Result<Json<SubmitTeeProofResponse>, TeeProcessorError> {
         let l1_batch_number = L1BatchNumber(l1_batch_number);
+        let pubkey = parse_pubkey(&proof.0.pubkey)?;
         let mut connection = self.pool.connection_tagged("tee_request_processor").await?;
+
+        let attestation = connection
+            .tee_proof_generation_dal()
+            .get_attestation(&proof.0.pubkey)
+            .await?
+            .ok_or(TeeProcessorError::UnregisteredPubkey)?;
+        // The attestation may have been registered before quote verification was enabled, or by another handler
+        // instance, so it's verified unless there's a fresh cached verification result.
+        self.attestation_verifier.verify_registered_quote(
+            &attestation,
+            &pubkey,
+            proof.0.tee_type,
+        )?;
+
+        let root_hash = connection
+            .blocks_dal()
+            .get_l1_batch_state_root(l1_batch_number)
+            .await?
+            .ok_or(TeeProcessorError::RootHashNotFound(l1_batch_number))?;
+        verify_root_hash_signature(&pubkey, &proof.0.signature, root_hash)?;
+
         let mut dal = connection.tee_proof_generation_dal();
         dal.save_proof_artifacts_metadata(
             l1_batch_number,
@@ -246,9 +298,20 @@ impl TeeRequestProcessor {
             f64::NAN
         };
 
+        let attested_proofs = connection
+            .tee_proof_generation_dal()
+            .get_attested_tee_proofs(l1_batch_number..=l1_batch_number)
+            .await?;
+        let batch_status = self.config.quorum.policy().batch_status(
+            attested_proofs
+                .iter()
+                .map(|proof| (proof.tee_type, proof.pubkey.as_slice())),
+        );
+
         tracing::info!(
             l1_batch_number = %l1_batch_number,
             sealed_to_proven_in_secs = duration_secs_f64,
+            ?batch_status,
             "Received proof {:?}",
             proof
         );
@@ -262,6 +325,10 @@ impl TeeRequestProcessor {
     ) -> Result<Json<RegisterTeeAttestationResponse>, TeeProcessorError> {
         tracing::info!("Received attestation: {:?}", payload);
 
+        let pubkey = parse_pubkey(&payload.pubkey)?;
+        self.attestation_verifier
+            .verify_quote(&payload.attestation, &pubkey, None)?;
+
         let mut connection = self.pool.connection_tagged("tee_request_processor").await?;
         let mut dal = connection.tee_proof_generation_dal();
 
@@ -1,4 +1,4 @@
-use std::time::Duration;
+use std::{sync::Arc, time::Duration};
 
 use axum::{
     body::Body,
@@ -6,17 +6,23 @@ use axum::{
     response::Response,
     Router,
 };
+use secp256k1::{PublicKey, SecretKey, SECP256K1};
 use serde_json::json;
 use tower::ServiceExt;
-use zksync_config::configs::TeeProofDataHandlerConfig;
+use zksync_config::configs::{TeeProofDataHandlerConfig, TeeQuorumConfig};
 use zksync_dal::{ConnectionPool, CoreDal};
 use zksync_object_store::MockObjectStore;
-use zksync_tee_prover_interface::api::SubmitTeeProofRequest;
+use zksync_tee_prover_interface::{
+    api::{RegisterTeeAttestationRequest, SubmitTeeProofRequest},
+    outputs::L1BatchTeeProofForL1,
+    signatures::sign_root_hash,
+};
 use zksync_types::{
-    commitment::L1BatchCommitmentMode, tee_types::TeeType, L1BatchNumber, L2ChainId,
+    block::L1BatchHeader, commitment::L1BatchCommitmentMode, tee_types::TeeType, L1BatchNumber,
+    L2ChainId, ProtocolVersion, ProtocolVersionId, H256,
 };
 
-use crate::create_proof_processing_router;
+use crate::{create_proof_processing_router, verification::AttestationVerifier};
 
 fn test_config() -> TeeProofDataHandlerConfig {
     TeeProofDataHandlerConfig {
@@ -24,20 +30,37 @@ fn test_config() -> TeeProofDataHandlerConfig {
         first_processed_batch: L1BatchNumber(0),
         proof_generation_timeout_in_secs: Duration::from_secs(600),
         batch_permanently_ignored_timeout_in_hours: Duration::from_secs(10 * 24 * 3_600),
+        verify_attestation_quotes: false,
+        attestation_cache_ttl: Duration::from_secs(3_600),
+        sgx_collateral_path: None,
+        tdx_collateral_path: None,
+        sgx_mrenclave_allowlist: vec![],
+        tdx_mrtd_allowlist: vec![],
+        allowed_tcb_statuses: vec![],
+        quorum: TeeQuorumConfig {
+            required_tee_types: vec![],
+            min_proofs: 1,
+            wait_before_execute: false,
+        },
     }
 }
 
-#[tokio::test]
-async fn request_tee_proof_inputs() {
-    let db_conn_pool = ConnectionPool::test_pool().await;
-
-    let app = create_proof_processing_router(
+fn create_test_router(db_conn_pool: ConnectionPool<zksync_dal::Core>) -> Router {
+    create_proof_processing_router(
         MockObjectStore::arc(),
-        db_conn_pool.clone(),
+        db_conn_pool,
         test_config(),
+        Arc::new(AttestationVerifier::default()),
         L1BatchCommitmentMode::Rollup,
         L2ChainId::default(),
-    );
+    )
+}
+
+#[tokio::test]
+async fn request_tee_proof_inputs() {
+    let db_conn_pool = ConnectionPool::test_pool().await;
+
+    let app = create_test_router(db_conn_pool.clone());
     let test_cases = vec![
         (json!({ "tee_type": "sgx" }), StatusCode::NO_CONTENT),
         (
@@ -65,35 +88,50 @@ async fn request_tee_proof_inputs() {
     }
 }
 
+#[tokio::test]
+async fn request_tee_proof_inputs_for_missing_batch() {
+    let db_conn_pool = ConnectionPool::test_pool().await;
+    let app = create_test_router(db_conn_pool);
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .method(Method::GET)
+                .uri("/tee/proof_inputs/1")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
 // Test /tee/submit_proofs endpoint using a mocked TEE proof and verify response and db state
 #[tokio::test]
 async fn submit_tee_proof() {
     let batch_number = L1BatchNumber::from(1);
+    let root_hash = H256::repeat_byte(0x23);
     let db_conn_pool = ConnectionPool::test_pool().await;
 
-    mock_tee_batch_status(db_conn_pool.clone(), batch_number).await;
-
-    let tee_proof_request_str = r#"{
-        "signature": "0001020304",
-        "pubkey": "0506070809",
-        "proof": "0A0B0C0D0E",
-        "tee_type": "sgx"
-    }"#;
-    let tee_proof_request =
-        serde_json::from_str::<SubmitTeeProofRequest>(tee_proof_request_str).unwrap();
+    mock_tee_batch_status(db_conn_pool.clone(), batch_number, root_hash).await;
+
+    let secret_key = SecretKey::from_slice(&[0x42; 32]).unwrap();
+    let tee_proof_request = SubmitTeeProofRequest(Box::new(L1BatchTeeProofForL1 {
+        signature: sign_root_hash(&secret_key, root_hash),
+        pubkey: PublicKey::from_secret_key(SECP256K1, &secret_key)
+            .serialize()
+            .to_vec(),
+        proof: vec![10, 11, 12, 13, 14],
+        tee_type: TeeType::Sgx,
+    }));
     let uri = format!("/tee/submit_proofs/{}", batch_number.0);
-    let app = create_proof_processing_router(
-        MockObjectStore::arc(),
-        db_conn_pool.clone(),
-        test_config(),
-        L1BatchCommitmentMode::Rollup,
-        L2ChainId::default(),
-    );
+    let app = create_test_router(db_conn_pool.clone());
 
     // this should fail because we haven't saved the attestation for the pubkey yet
 
     let response = send_submit_tee_proof_request(&app, &uri, &tee_proof_request).await;
-    assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
 
     // save the attestation for the pubkey
 
@@ -105,7 +143,14 @@ async fn submit_tee_proof() {
         .await
         .expect("Failed to save attestation");
 
-    // resend the same request; this time, it should be successful
+    // a proof with a signature over another root hash should be rejected
+
+    let mut invalid_request = SubmitTeeProofRequest(tee_proof_request.0.clone());
+    invalid_request.0.signature = sign_root_hash(&secret_key, H256::zero());
+    let response = send_submit_tee_proof_request(&app, &uri, &invalid_request).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    // resend the original request; this time, it should be successful
 
     let response = send_submit_tee_proof_request(&app, &uri, &tee_proof_request).await;
     assert_eq!(response.status(), StatusCode::OK);
@@ -146,8 +191,34 @@ async fn submit_tee_proof() {
 async fn mock_tee_batch_status(
     db_conn_pool: ConnectionPool<zksync_dal::Core>,
     batch_number: L1BatchNumber,
+    root_hash: H256,
 ) {
     let mut proof_db_conn = db_conn_pool.connection().await.unwrap();
+
+    // mock the sealed batch with the root hash signed by TEE provers
+
+    proof_db_conn
+        .protocol_versions_dal()
+        .save_protocol_version_with_tx(&ProtocolVersion::default())
+        .await
+        .unwrap();
+    let header = L1BatchHeader::new(
+        batch_number,
+        0,
+        Default::default(),
+        ProtocolVersionId::latest(),
+    );
+    proof_db_conn
+        .blocks_dal()
+        .insert_mock_l1_batch(&header)
+        .await
+        .unwrap();
+    proof_db_conn
+        .blocks_dal()
+        .set_l1_batch_hash(batch_number, root_hash)
+        .await
+        .unwrap();
+
     let mut proof_dal = proof_db_conn.tee_proof_generation_dal();
 
     // there should not be any batches awaiting proof in the db yet
@@ -190,3 +261,37 @@ async fn send_submit_tee_proof_request(
         .await
         .unwrap()
 }
+
+#[tokio::test]
+async fn register_tee_attestation_with_invalid_pubkey() {
+    let db_conn_pool = ConnectionPool::test_pool().await;
+    let app = create_test_router(db_conn_pool.clone());
+
+    let request = RegisterTeeAttestationRequest {
+        attestation: vec![15, 16, 17, 18, 19],
+        pubkey: vec![5, 6, 7, 8, 9],
+    };
+    let req_body = Body::from(serde_json::to_vec(&request).unwrap());
+    let response = app
+        .oneshot(
+            Request::builder()
+                .method(Method::POST)
+                .uri("/tee/register_attestation")
+                .header(http::header::CONTENT_TYPE, "application/json")
+                .body(req_body)
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let attestation = db_conn_pool
+        .connection()
+        .await
+        .unwrap()
+        .tee_proof_generation_dal()
+        .get_attestation(&request.pubkey)
+        .await
+        .unwrap();
+    assert!(attestation.is_none());
+}
@@ -0,0 +1,513 @@
+//! Verification of TEE proof signatures and DCAP attestation quotes.
+
+use std::{
+    collections::{HashMap, HashSet},
+    fs,
+    path::Path,
+    sync::Mutex,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::Context as _;
+use dcap_qvl::{quote::Report, QuoteCollateralV3};
+use secp256k1::PublicKey;
+use zksync_config::configs::TeeProofDataHandlerConfig;
+use zksync_types::{tee_types::TeeType, web3::keccak256};
+
+use crate::errors::TeeProcessorError;
+
+/// Length of the common header of SGX / TDX quotes.
+const QUOTE_HEADER_LEN: usize = 48;
+/// `tee_type` value in the header of v4+ quotes produced by SGX enclaves.
+const QUOTE_TEE_TYPE_SGX: u32 = 0x0000_0000;
+/// `tee_type` value in the header of v4+ quotes produced by TDX trust domains.
+const QUOTE_TEE_TYPE_TDX: u32 = 0x0000_0081;
+/// TCB status of quotes produced by platforms that are up to date; such quotes are always accepted.
+const TCB_STATUS_UP_TO_DATE: &str = "UpToDate";
+const MRENCLAVE_LEN: usize = 32;
+const MRTD_LEN: usize = 48;
+
+/// Parses a secp256k1 public key submitted by a TEE prover.
+pub(crate) fn parse_pubkey(pubkey: &[u8]) -> Result<PublicKey, TeeProcessorError> {
+    PublicKey::from_slice(pubkey).map_err(|err| TeeProcessorError::InvalidPubkey(err.to_string()))
+}
+
+/// Determines the TEE type of a DCAP quote based on its header.
+pub(crate) fn quote_tee_type(quote: &[u8]) -> Result<TeeType, TeeProcessorError> {
+    if quote.len() < QUOTE_HEADER_LEN {
+        return Err(TeeProcessorError::InvalidAttestation(format!(
+            "quote is too short ({} bytes)",
+            quote.len()
+        )));
+    }
+
+    let version = u16::from_le_bytes([quote[0], quote[1]]);
+    match version {
+        // v3 quotes can only be produced by SGX enclaves.
+        3 => Ok(TeeType::Sgx),
+        4 | 5 => {
+            let tee_type = u32::from_le_bytes(quote[4..8].try_into().unwrap());
+            match tee_type {
+                QUOTE_TEE_TYPE_SGX => Ok(TeeType::Sgx),
+                QUOTE_TEE_TYPE_TDX => Ok(TeeType::Tdx),
+                _ => Err(TeeProcessorError::InvalidAttestation(format!(
+                    "unsupported TEE type in quote header: {tee_type:#x}"
+                ))),
+            }
+        }
+        _ => Err(TeeProcessorError::InvalidAttestation(format!(
+            "unsupported quote version: {version}"
+        ))),
+    }
+}
+
+/// Checks that the report data of a quote binds the specified public key. The following layouts
+/// of the 64-byte report data are supported:
+///
+/// - Compressed public key (33 bytes) followed by zero padding.
+/// - Ethereum address derived from the public key (20 bytes) followed by zero padding, with the last byte
+///   set to 1 (the layout version).
+fn report_data_binds_pubkey(report_data: &[u8; 64], pubkey: &PublicKey) -> bool {
+    let compressed = pubkey.serialize();
+    if report_data[..compressed.len()] == compressed
+        && report_data[compressed.len()..]
+            .iter()
+            .all(|&byte| byte == 0)
+    {
+        return true;
+    }
+
+    let uncompressed = pubkey.serialize_uncompressed();
+    let address = &keccak256(&uncompressed[1..])[12..];
+    report_data[..address.len()] == *address
+        && report_data[address.len()..63].iter().all(|&byte| byte == 0)
+        && report_data[63] == 1
+}
+
+/// Verifier of DCAP attestation quotes registered by TEE provers.
+///
+/// Quotes are verified against collateral loaded from the filesystem, so verification doesn't
+/// require access to Intel PCS. Besides checking the quote signature chain, the verifier checks that the TCB status
+/// is `UpToDate` or explicitly allowed in the config, that the enclave measurement (MRENCLAVE for SGX, MRTD for TDX)
+/// is in the configured allowlist, and that the quote report data binds the public key used to sign proofs.
+///
+/// Verification results are cached per public key for the configured TTL, so that quotes are verified once
+/// when registered rather than for each submitted proof.
+#[derive(Debug, Default)]
+pub(crate) struct AttestationVerifier {
+    enabled: bool,
+    sgx_collateral: Option<QuoteCollateralV3>,
+    tdx_collateral: Option<QuoteCollateralV3>,
+    sgx_mrenclaves: HashSet<Vec<u8>>,
+    tdx_mrtds: HashSet<Vec<u8>>,
+    /// TCB statuses accepted in addition to `UpToDate`.
+    allowed_tcb_statuses: HashSet<String>,
+    cache_ttl: Duration,
+    verified_quotes: Mutex<HashMap<PublicKey, VerifiedQuote>>,
+}
+
+/// Cached result of a successful quote verification.
+#[derive(Debug, Clone, Copy)]
+struct VerifiedQuote {
+    tee_type: TeeType,
+    verified_at: Instant,
+}
+
+impl AttestationVerifier {
+    pub(crate) fn new(config: &TeeProofDataHandlerConfig) -> anyhow::Result<Self> {
+        if !config.verify_attestation_quotes {
+            tracing::warn!(
+                "Verification of TEE attestation quotes is disabled; attestations will be stored as is"
+            );
+            return Ok(Self::default());
+        }
+
+        let sgx_collateral = config
+            .sgx_collateral_path
+            .as_deref()
+            .map(load_collateral)
+            .transpose()?;
+        let tdx_collateral = config
+            .tdx_collateral_path
+            .as_deref()
+            .map(load_collateral)
+            .transpose()?;
+        let sgx_mrenclaves = parse_allowlist(&config.sgx_mrenclave_allowlist, MRENCLAVE_LEN)
+            .context("invalid `sgx_mrenclave_allowlist`")?;
+        let tdx_mrtds = parse_allowlist(&config.tdx_mrtd_allowlist, MRTD_LEN)
+            .context("invalid `tdx_mrtd_allowlist`")?;
+        if sgx_mrenclaves.is_empty() && tdx_mrtds.is_empty() {
+            tracing::warn!(
+                "TEE measurement allowlists are empty; all attestation quotes will be rejected"
+            );
+        }
+
+        Ok(Self {
+            enabled: true,
+            sgx_collateral,
+            tdx_collateral,
+            sgx_mrenclaves,
+            tdx_mrtds,
+            allowed_tcb_statuses: config.allowed_tcb_statuses.iter().cloned().collect(),
+            cache_ttl: config.attestation_cache_ttl,
+            verified_quotes: Mutex::default(),
+        })
+    }
+
+    /// Verifies the attestation `quote` registered for the public key that signed a proof produced by a TEE
+    /// of the specified type. Unlike [`Self::verify_quote()`], uses a cached verification result if it's not expired.
+    pub(crate) fn verify_registered_quote(
+        &self,
+        quote: &[u8],
+        pubkey: &PublicKey,
+        tee_type: TeeType,
+    ) -> Result<(), TeeProcessorError> {
+        if !self.enabled {
+            return Ok(());
+        }
+
+        let cached = self
+            .verified_quotes
+            .lock()
+            .expect("attestation cache is poisoned")
+            .get(pubkey)
+            .copied();
+        if let Some(cached) = cached {
+            if cached.tee_type != tee_type {
+                return Err(TeeProcessorError::TeeTypeMismatch {
+                    expected: tee_type,
+                    actual: cached.tee_type,
+                });
+            }
+            if cached.verified_at.elapsed() < self.cache_ttl {
+                return Ok(());
+            }
+        }
+        self.verify_quote(quote, pubkey, Some(tee_type))
+    }
+
+    /// Verifies the attestation `quote` for the specified public key. If `expected_tee_type` is specified,
+    /// also checks that the quote was produced by a TEE of this type. This is a no-op if quote verification
+    /// is disabled in the config.
+    pub(crate) fn verify_quote(
+        &self,
+        quote: &[u8],
+        pubkey: &PublicKey,
+        expected_tee_type: Option<TeeType>,
+    ) -> Result<(), TeeProcessorError> {
+        if !self.enabled {
+            return Ok(());
+        }
+
+        let tee_type = quote_tee_type(quote)?;
+        if let Some(expected) = expected_tee_type {
+            if expected != tee_type {
+                return Err(TeeProcessorError::TeeTypeMismatch {
+                    expected,
+                    actual: tee_type,
+                });
+            }
+        }
+
+        let collateral = match tee_type {
+            TeeType::Sgx => self.sgx_collateral.as_ref(),
+            TeeType::Tdx => self.tdx_collateral.as_ref(),
+            _ => None,
+        };
+        let collateral = collateral.ok_or_else(|| {
+            TeeProcessorError::GeneralError(format!(
+                "no DCAP collateral configured for {tee_type} quotes"
+            ))
+        })?;
+
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("current time is before Unix epoch")
+            .as_secs();
+        let verified = dcap_qvl::verify::verify(quote, collateral, now).map_err(|err| {
+            TeeProcessorError::InvalidAttestation(format!(
+                "DCAP quote verification failed: {err:?}"
+            ))
+        })?;
+        tracing::debug!(
+            tcb_status = %verified.status,
+            advisory_ids = ?verified.advisory_ids,
+            "Verified {tee_type} attestation quote"
+        );
+        self.check_tcb_status(tee_type, &verified.status)?;
+
+        let (measurement, report_data) = match &verified.report {
+            Report::SgxEnclave(report) => (&report.mr_enclave[..], &report.report_data),
+            Report::TD10(report) => (&report.mr_td[..], &report.report_data),
+            Report::TD15(report) => (&report.base.mr_td[..], &report.base.report_data),
+        };
+        self.check_report(tee_type, measurement, report_data, pubkey)?;
+        self.cache_verified_quote(*pubkey, tee_type, Instant::now());
+        Ok(())
+    }
+
+    fn cache_verified_quote(&self, pubkey: PublicKey, tee_type: TeeType, verified_at: Instant) {
+        let mut verified_quotes = self
+            .verified_quotes
+            .lock()
+            .expect("attestation cache is poisoned");
+        verified_quotes.retain(|_, quote| quote.verified_at.elapsed() < self.cache_ttl);
+        verified_quotes.insert(
+            pubkey,
+            VerifiedQuote {
+                tee_type,
+                verified_at,
+            },
+        );
+    }
+
+    fn check_tcb_status(&self, tee_type: TeeType, status: &str) -> Result<(), TeeProcessorError> {
+        if status == TCB_STATUS_UP_TO_DATE || self.allowed_tcb_statuses.contains(status) {
+            return Ok(());
+        }
+        Err(TeeProcessorError::TcbStatusNotAllowed {
+            tee_type,
+            status: status.to_owned(),
+        })
+    }
+
+    fn check_report(
+        &self,
+        tee_type: TeeType,
+        measurement: &[u8],
+        report_data: &[u8; 64],
+        pubkey: &PublicKey,
+    ) -> Result<(), TeeProcessorError> {
+        let allowlist = match tee_type {
+            TeeType::Sgx => &self.sgx_mrenclaves,
+            TeeType::Tdx => &self.tdx_mrtds,
+            _ => {
+                return Err(TeeProcessorError::InvalidAttestation(format!(
+                    "unsupported TEE type: {tee_type}"
+                )))
+            }
+        };
+        if !allowlist.contains(measurement) {
+            return Err(TeeProcessorError::MeasurementNotAllowed {
+                tee_type,
+                measurement: hex::encode(measurement),
+            });
+        }
+        if !report_data_binds_pubkey(report_data, pubkey) {
+            return Err(TeeProcessorError::ReportDataMismatch);
+        }
+        Ok(())
+    }
+}
+
+fn load_collateral(path: &Path) -> anyhow::Result<QuoteCollateralV3> {
+    let raw = fs::read(path)
+        .with_context(|| format!("failed reading DCAP collateral from `{}`", path.display()))?;
+    serde_json::from_slice(&raw)
+        .with_context(|| format!("failed parsing DCAP collateral from `{}`", path.display()))
+}
+
+fn parse_allowlist(entries: &[String], expected_len: usize) -> anyhow::Result<HashSet<Vec<u8>>> {
+    entries
+        .iter()
+        .map(|entry| {
+            let entry = entry.strip_prefix("0x").unwrap_or(entry);
+            let measurement =
+                hex::decode(entry).with_context(|| format!("`{entry}` is not a hex string"))?;
+            anyhow::ensure!(
+                measurement.len() == expected_len,
+                "`{entry}` has unexpected length; expected {expected_len} bytes"
+            );
+            Ok(measurement)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use secp256k1::{SecretKey, SECP256K1};
+
+    use super::*;
+
+    fn test_key() -> PublicKey {
+        let secret_key = SecretKey::from_slice(&[0x42; 32]).unwrap();
+        PublicKey::from_secret_key(SECP256K1, &secret_key)
+    }
+
+    #[test]
+    fn parsing_quote_header() {
+        let mut quote = vec![0_u8; QUOTE_HEADER_LEN];
+        quote[0] = 3;
+        assert_eq!(quote_tee_type(&quote).unwrap(), TeeType::Sgx);
+        quote[0] = 4;
+        assert_eq!(quote_tee_type(&quote).unwrap(), TeeType::Sgx);
+        quote[4] = 0x81;
+        assert_eq!(quote_tee_type(&quote).unwrap(), TeeType::Tdx);
+
+        quote[4] = 0x42;
+        assert!(quote_tee_type(&quote).is_err());
+        quote[0] = 2;
+        assert!(quote_tee_type(&quote).is_err());
+        assert!(quote_tee_type(&quote[..16]).is_err());
+    }
+
+    #[test]
+    fn report_data_binding() {
+        let pubkey = test_key();
+        let mut report_data = [0_u8; 64];
+        report_data[..33].copy_from_slice(&pubkey.serialize());
+        assert!(report_data_binds_pubkey(&report_data, &pubkey));
+        report_data[40] = 1;
+        assert!(!report_data_binds_pubkey(&report_data, &pubkey));
+
+        let mut report_data = [0_u8; 64];
+        let address = &keccak256(&pubkey.serialize_uncompressed()[1..])[12..];
+        report_data[..20].copy_from_slice(address);
+        assert!(!report_data_binds_pubkey(&report_data, &pubkey));
+        report_data[63] = 1;
+        assert!(report_data_binds_pubkey(&report_data, &pubkey));
+
+        let other_key = SecretKey::from_slice(&[0x43; 32]).unwrap();
+        let other_pubkey = PublicKey::from_secret_key(SECP256K1, &other_key);
+        assert!(!report_data_binds_pubkey(&report_data, &other_pubkey));
+    }
+
+    #[test]
+    fn checking_report_against_allowlist() {
+        let pubkey = test_key();
+        let mrenclave = [0x11_u8; MRENCLAVE_LEN];
+        let verifier = AttestationVerifier {
+            enabled: true,
+            sgx_mrenclaves: HashSet::from([mrenclave.to_vec()]),
+            ..AttestationVerifier::default()
+        };
+        let mut report_data = [0_u8; 64];
+        report_data[..33].copy_from_slice(&pubkey.serialize());
+
+        verifier
+            .check_report(TeeType::Sgx, &mrenclave, &report_data, &pubkey)
+            .unwrap();
+        let err = verifier
+            .check_report(TeeType::Sgx, &[0x22; MRENCLAVE_LEN], &report_data, &pubkey)
+            .unwrap_err();
+        assert!(
+            matches!(err, TeeProcessorError::MeasurementNotAllowed { .. }),
+            "{err}"
+        );
+        let err = verifier
+            .check_report(TeeType::Tdx, &[0x11; MRTD_LEN], &report_data, &pubkey)
+            .unwrap_err();
+        assert!(
+            matches!(err, TeeProcessorError::MeasurementNotAllowed { .. }),
+            "{err}"
+        );
+
+        report_data[0] ^= 1;
+        let err = verifier
+            .check_report(TeeType::Sgx, &mrenclave, &report_data, &pubkey)
+            .unwrap_err();
+        assert!(
+            matches!(err, TeeProcessorError::ReportDataMismatch),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn checking_tcb_status() {
+        let verifier = AttestationVerifier {
+            enabled: true,
+            ..AttestationVerifier::default()
+        };
+        verifier
+            .check_tcb_status(TeeType::Sgx, TCB_STATUS_UP_TO_DATE)
+            .unwrap();
+        for status in ["SWHardeningNeeded", "OutOfDate", "Revoked"] {
+            let err = verifier.check_tcb_status(TeeType::Sgx, status).unwrap_err();
+            assert!(
+                matches!(err, TeeProcessorError::TcbStatusNotAllowed { .. }),
+                "{err}"
+            );
+        }
+
+        let verifier = AttestationVerifier {
+            enabled: true,
+            allowed_tcb_statuses: HashSet::from(["SWHardeningNeeded".to_owned()]),
+            ..AttestationVerifier::default()
+        };
+        verifier
+            .check_tcb_status(TeeType::Tdx, "SWHardeningNeeded")
+            .unwrap();
+        let err = verifier
+            .check_tcb_status(TeeType::Tdx, "OutOfDate")
+            .unwrap_err();
+        assert!(
+            matches!(err, TeeProcessorError::TcbStatusNotAllowed { .. }),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn parsing_measurement_allowlist() {
+        let entries = ["0x".to_owned() + &"ab".repeat(32), "cd".repeat(32)];
+        let allowlist = parse_allowlist(&entries, MRENCLAVE_LEN).unwrap();
+        assert_eq!(allowlist.len(), 2);
+        assert!(allowlist.contains(&vec![0xab; 32]));
+
+        assert!(parse_allowlist(&["ab".repeat(31)], MRENCLAVE_LEN).is_err());
+        assert!(parse_allowlist(&["xyz".to_owned()], MRENCLAVE_LEN).is_err());
+    }
+
+    #[test]
+    fn verified_quotes_are_cached() {
+        let pubkey = test_key();
+        let verifier = AttestationVerifier {
+            enabled: true,
+            cache_ttl: Duration::from_secs(3_600),
+            ..AttestationVerifier::default()
+        };
+        let invalid_quote = [1, 2, 3];
+        let err = verifier
+            .verify_registered_quote(&invalid_quote, &pubkey, TeeType::Sgx)
+            .unwrap_err();
+        assert!(
+            matches!(err, TeeProcessorError::InvalidAttestation(_)),
+            "{err}"
+        );
+
+        verifier.cache_verified_quote(pubkey, TeeType::Sgx, Instant::now());
+        verifier
+            .verify_registered_quote(&invalid_quote, &pubkey, TeeType::Sgx)
+            .unwrap();
+        let err = verifier
+            .verify_registered_quote(&invalid_quote, &pubkey, TeeType::Tdx)
+            .unwrap_err();
+        assert!(
+            matches!(err, TeeProcessorError::TeeTypeMismatch { .. }),
+            "{err}"
+        );
+
+        // Expired results must lead to re-verification.
+        let verifier = AttestationVerifier {
+            enabled: true,
+            cache_ttl: Duration::ZERO,
+            ..AttestationVerifier::default()
+        };
+        verifier.cache_verified_quote(pubkey, TeeType::Sgx, Instant::now());
+        let err = verifier
+            .verify_registered_quote(&invalid_quote, &pubkey, TeeType::Sgx)
+            .unwrap_err();
+        assert!(
+            matches!(err, TeeProcessorError::InvalidAttestation(_)),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn disabled_verifier_accepts_any_quote() {
+        let pubkey = test_key();
+        let verifier = AttestationVerifier::default();
+        verifier
+            .verify_quote(&[1, 2, 3], &pubkey, Some(TeeType::Sgx))
+            .unwrap();
+    }
+}
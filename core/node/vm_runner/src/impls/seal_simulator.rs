@@ -0,0 +1,243 @@
+use std::{collections::BTreeMap, num::NonZeroU32, ops::RangeInclusive, sync::Arc};
+
+use anyhow::Context as _;
+use async_trait::async_trait;
+use tokio::sync::{mpsc, watch};
+use zksync_config::configs::chain::StateKeeperConfig;
+use zksync_dal::{Connection, ConnectionPool, Core, CoreDal};
+use zksync_state_keeper::seal_criteria::simulation::{SealSimulationReport, SealSimulator};
+use zksync_types::{L1BatchNumber, L2ChainId, ProtocolVersionId, Transaction};
+use zksync_vm_executor::batch::MainBatchExecutorFactory;
+use zksync_vm_interface::{L1BatchEnv, L2BlockEnv, SystemEnv, VmExecutionResultAndLogs};
+
+use crate::{
+    storage::PostgresLoader, L1BatchOutput, L2BlockOutput, OutputHandler, OutputHandlerFactory,
+    VmRunner, VmRunnerIo,
+};
+
+/// Replays historic L1 batches and simulates how they would be sealed with an alternative [`StateKeeperConfig`].
+///
+/// Transactions are re-executed by the VM runner in the context of their original batches, and the resulting execution
+/// metrics are fed into a [`SealSimulator`]. The simulator doesn't persist anything in Postgres; it only reads
+/// batch data and storage.
+#[derive(Debug)]
+pub struct SealCriteriaSimulator {
+    pool: ConnectionPool<Core>,
+    chain_id: L2ChainId,
+    config: StateKeeperConfig,
+    l1_batches: RangeInclusive<L1BatchNumber>,
+    window_size: NonZeroU32,
+}
+
+impl SealCriteriaSimulator {
+    /// Creates a new simulator for the specified range of L1 batches. `window_size` is the maximum number
+    /// of L1 batches executed in parallel.
+    pub fn new(
+        pool: ConnectionPool<Core>,
+        chain_id: L2ChainId,
+        config: StateKeeperConfig,
+        l1_batches: RangeInclusive<L1BatchNumber>,
+        window_size: NonZeroU32,
+    ) -> Self {
+        Self {
+            pool,
+            chain_id,
+            config,
+            l1_batches,
+            window_size,
+        }
+    }
+
+    /// Runs the simulation until all L1 batches in the range are processed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the range is invalid or not yet sealed, if the simulation is interrupted via `stop_receiver`,
+    /// or propagates VM runner errors.
+    pub async fn run(
+        self,
+        mut stop_receiver: watch::Receiver<bool>,
+    ) -> anyhow::Result<SealSimulationReport> {
+        let (&first_batch, &last_batch) = (self.l1_batches.start(), self.l1_batches.end());
+        anyhow::ensure!(
+            first_batch > L1BatchNumber(0) && first_batch <= last_batch,
+            "invalid L1 batch range: {first_batch}..={last_batch}"
+        );
+        let mut conn = self.pool.connection_tagged("seal_simulator").await?;
+        let sealed_batch = conn
+            .blocks_dal()
+            .get_sealed_l1_batch_number()
+            .await?
+            .context("no L1 batches in Postgres")?;
+        anyhow::ensure!(
+            last_batch <= sealed_batch,
+            "L1 batch #{last_batch} is not sealed yet; last sealed L1 batch is #{sealed_batch}"
+        );
+        drop(conn);
+
+        tracing::info!(
+            "Simulating seal criteria for L1 batches #{first_batch}..=#{last_batch} with config {:?}",
+            self.config
+        );
+        let io = SealSimulatorIo {
+            last_batch,
+            window_size: self.window_size.get(),
+            latest_processed_batch: Arc::new(watch::channel(first_batch - 1).0),
+        };
+        let (batch_sender, mut batch_receiver) = mpsc::unbounded_channel();
+        let output_handler_factory = SealSimulatorOutputHandlerFactory { batch_sender };
+        let mut loader = PostgresLoader::new(self.pool.clone(), self.chain_id).await?;
+        loader.shadow_snapshots(false);
+        let vm_runner = VmRunner::new(
+            self.pool,
+            Arc::new(io.clone()),
+            Arc::new(loader),
+            Arc::new(output_handler_factory),
+            Box::new(MainBatchExecutorFactory::<()>::new(false)),
+        );
+        let (runner_stop_sender, runner_stop_receiver) = watch::channel(false);
+        let mut runner_task =
+            tokio::spawn(async move { vm_runner.run(&runner_stop_receiver).await });
+
+        let mut simulator = SealSimulator::new(self.config);
+        let mut pending_batches = BTreeMap::new();
+        let mut next_batch = first_batch;
+        while next_batch <= last_batch {
+            let batch = tokio::select! {
+                _ = stop_receiver.changed() => {
+                    runner_stop_sender.send_replace(true);
+                    anyhow::bail!("seal criteria simulation was interrupted");
+                }
+                res = &mut runner_task => {
+                    res.context("VM runner panicked")??;
+                    anyhow::bail!("VM runner terminated unexpectedly");
+                }
+                batch = batch_receiver.recv() => batch.context("VM runner terminated unexpectedly")?,
+            };
+            pending_batches.insert(batch.number, batch);
+
+            // Batches can be executed out of order, but they must be fed to the simulator sequentially.
+            while let Some(batch) = pending_batches.remove(&next_batch) {
+                for (tx, tx_result) in &batch.transactions {
+                    simulator.push_transaction(tx, tx_result, batch.protocol_version);
+                }
+                tracing::info!(
+                    "Processed L1 batch #{next_batch} with {} transactions; {} simulated L1 batches sealed so far",
+                    batch.transactions.len(),
+                    simulator.sealed_batch_count()
+                );
+                io.latest_processed_batch.send_replace(next_batch);
+                next_batch += 1;
+            }
+        }
+
+        runner_stop_sender.send_replace(true);
+        runner_task.await.context("VM runner panicked")??;
+        Ok(simulator.finish())
+    }
+}
+
+/// I/O powering [`SealCriteriaSimulator`]. Keeps the cursor in memory.
+#[derive(Debug, Clone)]
+struct SealSimulatorIo {
+    last_batch: L1BatchNumber,
+    window_size: u32,
+    latest_processed_batch: Arc<watch::Sender<L1BatchNumber>>,
+}
+
+#[async_trait]
+impl VmRunnerIo for SealSimulatorIo {
+    fn name(&self) -> &'static str {
+        "seal_simulator"
+    }
+
+    async fn latest_processed_batch(
+        &self,
+        _conn: &mut Connection<'_, Core>,
+    ) -> anyhow::Result<L1BatchNumber> {
+        Ok(*self.latest_processed_batch.borrow())
+    }
+
+    async fn last_ready_to_be_loaded_batch(
+        &self,
+        conn: &mut Connection<'_, Core>,
+    ) -> anyhow::Result<L1BatchNumber> {
+        let last_processed_batch = self.latest_processed_batch(conn).await?;
+        Ok(self.last_batch.min(last_processed_batch + self.window_size))
+    }
+
+    async fn mark_l1_batch_as_processing(
+        &self,
+        _conn: &mut Connection<'_, Core>,
+        _l1_batch_number: L1BatchNumber,
+    ) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    async fn mark_l1_batch_as_completed(
+        &self,
+        _conn: &mut Connection<'_, Core>,
+        _l1_batch_number: L1BatchNumber,
+    ) -> anyhow::Result<()> {
+        Ok(())
+    }
+}
+
+/// Transactions of a re-executed L1 batch.
+#[derive(Debug)]
+struct ExecutedL1Batch {
+    number: L1BatchNumber,
+    protocol_version: ProtocolVersionId,
+    transactions: Vec<(Transaction, VmExecutionResultAndLogs)>,
+}
+
+#[derive(Debug)]
+struct SealSimulatorOutputHandlerFactory {
+    batch_sender: mpsc::UnboundedSender<ExecutedL1Batch>,
+}
+
+#[async_trait]
+impl OutputHandlerFactory for SealSimulatorOutputHandlerFactory {
+    async fn create_handler(
+        &self,
+        system_env: SystemEnv,
+        l1_batch_env: L1BatchEnv,
+    ) -> anyhow::Result<Box<dyn OutputHandler>> {
+        Ok(Box::new(SealSimulatorOutputHandler {
+            batch: ExecutedL1Batch {
+                number: l1_batch_env.number,
+                protocol_version: system_env.version,
+                transactions: vec![],
+            },
+            batch_sender: self.batch_sender.clone(),
+        }))
+    }
+}
+
+#[derive(Debug)]
+struct SealSimulatorOutputHandler {
+    batch: ExecutedL1Batch,
+    batch_sender: mpsc::UnboundedSender<ExecutedL1Batch>,
+}
+
+#[async_trait]
+impl OutputHandler for SealSimulatorOutputHandler {
+    async fn handle_l2_block(
+        &mut self,
+        _env: L2BlockEnv,
+        output: &L2BlockOutput,
+    ) -> anyhow::Result<()> {
+        let transactions = output
+            .transactions
+            .iter()
+            .map(|(tx, result)| (tx.clone(), (*result.tx_result).clone()));
+        self.batch.transactions.extend(transactions);
+        Ok(())
+    }
+
+    async fn handle_l1_batch(self: Box<Self>, _output: Arc<L1BatchOutput>) -> anyhow::Result<()> {
+        // The receiver may be dropped if the simulation is interrupted; this is fine.
+        self.batch_sender.send(self.batch).ok();
+        Ok(())
+    }
+}
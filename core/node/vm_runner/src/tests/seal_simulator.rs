@@ -0,0 +1,80 @@
+use std::num::NonZeroU32;
+
+use tokio::sync::watch;
+use zksync_config::configs::chain::StateKeeperConfig;
+use zksync_dal::{ConnectionPool, Core};
+use zksync_node_genesis::{insert_genesis_batch, GenesisParams};
+use zksync_test_contracts::Account;
+use zksync_types::{L1BatchNumber, L2ChainId};
+
+use super::*;
+use crate::impls::SealCriteriaSimulator;
+
+async fn prepare_pool(batch_count: u32) -> ConnectionPool<Core> {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut conn = pool.connection().await.unwrap();
+    let genesis_params = GenesisParams::mock();
+    insert_genesis_batch(&mut conn, &genesis_params)
+        .await
+        .unwrap();
+    let mut accounts = vec![Account::random(), Account::random()];
+    fund(&mut conn, &accounts).await;
+    store_l1_batches(&mut conn, 1..=batch_count, &genesis_params, &mut accounts)
+        .await
+        .unwrap();
+    drop(conn);
+
+    storage_writer::write_storage_logs(pool.clone(), true).await;
+    pool
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn simulating_seal_criteria() {
+    // Each stored L1 batch contains a single transaction.
+    let pool = prepare_pool(5).await;
+    let config = StateKeeperConfig {
+        transaction_slots: 2,
+        ..StateKeeperConfig::for_tests()
+    };
+    let simulator = SealCriteriaSimulator::new(
+        pool,
+        L2ChainId::default(),
+        config,
+        L1BatchNumber(1)..=L1BatchNumber(5),
+        NonZeroU32::new(2).unwrap(),
+    );
+    let (_stop_sender, stop_receiver) = watch::channel(false);
+    let report = tokio::time::timeout(TEST_TIMEOUT, simulator.run(stop_receiver))
+        .await
+        .expect("simulation timed out")
+        .unwrap();
+
+    assert_eq!(report.tx_count, 5);
+    assert!(report.unexecutable_txs.is_empty(), "{report:?}");
+    let tx_counts: Vec<_> = report.batches.iter().map(|batch| batch.tx_count).collect();
+    assert_eq!(tx_counts, [2, 2, 1]);
+    for batch in &report.batches {
+        assert_eq!(batch.l1_tx_count, 0);
+        assert!(batch.pubdata_bytes > 0, "{batch:?}");
+        let slots_filled = batch.capacity_filled["slots"];
+        assert!(
+            (slots_filled - batch.tx_count as f64 / 2.0).abs() < 1e-6,
+            "{batch:?}"
+        );
+    }
+}
+
+#[tokio::test]
+async fn simulation_errors_on_unsealed_batches() {
+    let pool = prepare_pool(2).await;
+    let simulator = SealCriteriaSimulator::new(
+        pool,
+        L2ChainId::default(),
+        StateKeeperConfig::for_tests(),
+        L1BatchNumber(1)..=L1BatchNumber(3),
+        NonZeroU32::new(1).unwrap(),
+    );
+    let (_stop_sender, stop_receiver) = watch::channel(false);
+    let err = simulator.run(stop_receiver).await.unwrap_err();
+    assert!(err.to_string().contains("not sealed"), "{err:#}");
+}
@@ -9,7 +9,10 @@ use clap::Parser;
 use shivini::{ProverContext, ProverContextConfig};
 use tokio_util::sync::CancellationToken;
 use zksync_circuit_prover::{FinalizationHintsCache, SetupDataCache, PROVER_BINARY_METRICS};
-use zksync_circuit_prover_service::job_runner::{circuit_prover_runner, WvgRunnerBuilder};
+use zksync_circuit_prover_service::{
+    job_runner::{circuit_prover_runner, WvgRunnerBuilder},
+    prover_job_lease_renewer::LeaseSettings,
+};
 use zksync_config::{
     configs::{DatabaseSecrets, GeneralConfig},
     full_config_schema,
@@ -102,6 +105,10 @@ async fn main() -> anyhow::Result<()> {
     let mut tasks = vec![tokio::spawn(exporter_config.run(metrics_stop_receiver))];
 
     let (witness_vector_sender, witness_vector_receiver) = tokio::sync::mpsc::channel(CHANNEL_SIZE);
+    let lease_settings = LeaseSettings {
+        lease_duration: prover_config.job_lease_duration,
+        renewal_interval: prover_config.job_lease_renewal_interval,
+    };
 
     tracing::info!(
         "Starting {} light WVGs and {} heavy WVGs.",
@@ -116,6 +123,7 @@ async fn main() -> anyhow::Result<()> {
         hints.clone(),
         witness_vector_sender,
         cancellation_token.clone(),
+        lease_settings,
     );
 
     let light_wvg_runner = builder.light_wvg_runner(opt.light_wvg_count);
@@ -134,6 +142,7 @@ async fn main() -> anyhow::Result<()> {
         setup_data_cache,
         witness_vector_receiver,
         prover_context,
+        lease_settings,
     );
 
     tasks.extend(circuit_prover_runner.run());
@@ -56,6 +56,7 @@ pub struct ScaleEvent {
 pub struct Namespace {
     #[serde(serialize_with = "ordered_map")]
     pub deployments: HashMap<DeploymentName, Deployment>,
+    #[serde(default)]
     pub pods: HashMap<String, Pod>,
     #[serde(default)]
     pub scale_errors: Vec<ScaleEvent>,
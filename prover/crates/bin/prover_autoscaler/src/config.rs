@@ -1,4 +1,4 @@
-use std::{collections::HashMap, hash::Hash, time::Duration};
+use std::{collections::HashMap, hash::Hash, path::PathBuf, time::Duration};
 
 use serde::{Deserialize, Serialize};
 use smart_config::{
@@ -51,6 +51,23 @@ pub struct ProverAutoscalerAgentConfig {
     /// Interval for periodic pod checks against the K8s API to remove stale pods.
     #[config(default_t = 1 * TimeUnit::Hours)]
     pub pod_check_interval: Duration,
+    /// Backend used to watch and scale Deployments.
+    #[config(nest)]
+    pub backend: ScalerBackendConfig,
+}
+
+/// Backend used by Agent to watch and scale Deployments.
+#[derive(Debug, Clone, PartialEq, DescribeConfig, DeserializeConfig)]
+#[config(tag = "type", rename_all = "snake_case", derive(Default))]
+pub enum ScalerBackendConfig {
+    /// Deployments are watched and patched via Kubernetes API.
+    #[config(default)]
+    K8s,
+    /// Deployments are watched and scaled via a generic HTTP webhook.
+    Webhook {
+        /// Base URL of the webhook serving `GET /cluster` and `POST /scale`.
+        url: String,
+    },
 }
 
 #[derive(Debug, Clone, PartialEq, DescribeConfig, DeserializeConfig)]
@@ -83,6 +100,8 @@ pub struct ProverAutoscalerScalerConfig {
     /// If dry-run enabled don't send any scale requests.
     #[config(default)]
     pub dry_run: bool,
+    /// Directory to record queue history to, for offline simulation of scaling policies.
+    pub queue_history_path: Option<PathBuf>,
 }
 
 // TODO: generate this enum by QueueReport from https://github.com/matter-labs/zksync-era/blob/main/prover/crates/bin/prover_job_monitor/src/autoscaler_queue_reporter.rs#L23
@@ -168,6 +187,10 @@ pub struct ScalerTarget {
     /// For Simple targets, this is a list of ClusterName.
     #[serde(default)]
     pub priority: Option<PriorityConfig>,
+    /// Optional predictive scaling policy. If set, the target is calculated from the forecasted
+    /// queue instead of the current one and pools are preferred by cost per job.
+    #[serde(default)]
+    pub predictive: Option<PredictiveScalingConfig>,
 }
 
 impl WellKnown for ScalerTarget {
@@ -181,11 +204,42 @@ impl ScalerTarget {
     }
 }
 
+/// Predictive, cost-aware scaling policy for a ScalerTarget.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PredictiveScalingConfig {
+    /// Average number of jobs in the queue produced by a single sealed batch.
+    pub jobs_per_batch: usize,
+    /// Time needed for a new replica to start processing jobs, including Node provisioning.
+    #[serde(default = "PredictiveScalingConfig::default_startup_latency_secs")]
+    pub startup_latency_secs: u64,
+    /// Target time in which a job should be processed after it's added to the queue.
+    pub latency_slo_secs: u64,
+    /// Number of jobs processed by a single replica per hour.
+    pub throughput: ScalarOrMap,
+    /// Cost of running a single replica for an hour, in arbitrary units.
+    pub hourly_cost: ScalarOrMap,
+}
+
+impl PredictiveScalingConfig {
+    pub fn default_startup_latency_secs() -> u64 {
+        300
+    }
+
+    pub fn startup_latency(&self) -> Duration {
+        Duration::from_secs(self.startup_latency_secs)
+    }
+
+    pub fn latency_slo(&self) -> Duration {
+        Duration::from_secs(self.latency_slo_secs)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use smart_config::{testing::test_complete, Yaml};
 
     use super::*;
+    use crate::key::Gpu;
 
     #[test]
     fn deserializing_config() {
@@ -199,6 +253,9 @@ mod tests {
                 - prover-red
               pod_check_interval: 10m
               dry_run: false
+              backend:
+                type: webhook
+                url: http://scaler-webhook:3000/
             scaler_config:
               dry_run: false
               prometheus_port: 8080
@@ -229,6 +286,15 @@ mod tests {
                   speed:
                     L4: 1500
                     T4: 700
+                  predictive:
+                    jobs_per_batch: 3000
+                    latency_slo_secs: 1800
+                    throughput:
+                      L4: 2000
+                      T4: 900
+                    hourly_cost:
+                      L4: 70
+                      T4: 35
                 - queue_report_field: basic_witness_jobs
                   deployment: witness-generator-basic-fri
                   max_replicas:
@@ -275,6 +341,12 @@ mod tests {
         );
         assert_eq!(agent_config.pod_check_interval, Duration::from_secs(600));
         assert!(!agent_config.dry_run);
+        assert_eq!(
+            agent_config.backend,
+            ScalerBackendConfig::Webhook {
+                url: "http://scaler-webhook:3000/".to_owned()
+            }
+        );
 
         let scaler_config = config.scaler_config.unwrap();
         assert_eq!(scaler_config.prometheus_port, 8_080);
@@ -299,5 +371,14 @@ mod tests {
             Duration::from_secs(600)
         );
         assert_eq!(scaler_config.scaler_targets.len(), 7);
+        let predictive = scaler_config.scaler_targets[0].predictive.clone().unwrap();
+        assert_eq!(predictive.jobs_per_batch, 3_000);
+        assert_eq!(predictive.startup_latency(), Duration::from_secs(300));
+        assert_eq!(predictive.latency_slo(), Duration::from_secs(1_800));
+        assert_eq!(
+            predictive.hourly_cost,
+            ScalarOrMap::Map([(GpuKey(Gpu::L4), 70), (GpuKey(Gpu::T4), 35)].into())
+        );
+        assert_eq!(scaler_config.scaler_targets[1].predictive, None);
     }
 }
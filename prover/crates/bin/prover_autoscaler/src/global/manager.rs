@@ -10,14 +10,16 @@ use anyhow::Context;
 use zksync_prover_task::Task;
 
 use super::{
+    predictive::PredictivePolicy,
     queuer,
     scaler::{Scaler, ScalerConfig, ScalerTrait},
+    simulator::QueueHistoryRecorder,
     watcher,
 };
 use crate::{
     agent::ScaleRequest,
     cluster_types::{ClusterName, NamespaceName},
-    config::{ProverAutoscalerScalerConfig, QueueReportFields, ScalerTargetType},
+    config::{ProverAutoscalerScalerConfig, QueueReportFields, ScalarOrMap, ScalerTargetType},
     key::{GpuKey, NoKey},
     metrics::AUTOSCALER_METRICS,
 };
@@ -30,6 +32,7 @@ pub struct Manager {
     first_invoke_skipped: AtomicBool,
     jobs: Vec<QueueReportFields>,
     scalers: Vec<Box<dyn ScalerTrait + Sync + Send>>,
+    queue_history: Option<QueueHistoryRecorder>,
 }
 
 impl Manager {
@@ -49,44 +52,53 @@ impl Manager {
         let mut scalers: Vec<Box<dyn ScalerTrait + Sync + Send>> = Vec::default();
         let mut jobs = Vec::default();
 
-        let scaler_config = Arc::new(ScalerConfig {
-            cluster_priorities: config.cluster_priorities,
-            apply_min_to_namespace: config.apply_min_to_namespace,
-            long_pending_duration: chrono::Duration::seconds(
-                config.long_pending_duration.as_secs() as i64,
-            ),
-            scale_errors_duration: chrono::Duration::seconds(
-                config.scale_errors_duration.as_secs() as i64,
-            ),
-        });
+        let scaler_config = Arc::new(ScalerConfig::from(&config));
 
         for c in &config.scaler_targets {
             jobs.push(c.queue_report_field);
             match c.scaler_target_type {
-                ScalerTargetType::Gpu => scalers.push(Box::new(Scaler::<GpuKey>::new(
-                    c.queue_report_field,
-                    c.deployment.clone(),
-                    c.min_replicas,
-                    c.max_replicas
-                        .iter()
-                        .map(|(k, v)| (k.clone(), v.into_map_gpukey()))
-                        .collect(),
-                    c.speed.into_map_gpukey(),
-                    scaler_config.clone(),
-                    c.priority.clone(),
-                ))),
-                ScalerTargetType::Simple => scalers.push(Box::new(Scaler::<NoKey>::new(
-                    c.queue_report_field,
-                    c.deployment.clone(),
-                    c.min_replicas,
-                    c.max_replicas
-                        .iter()
-                        .map(|(k, v)| (k.clone(), v.into_map_nokey()))
-                        .collect(),
-                    c.speed.into_map_nokey(),
-                    scaler_config.clone(),
-                    c.priority.clone(),
-                ))),
+                ScalerTargetType::Gpu => {
+                    let mut scaler = Scaler::<GpuKey>::new(
+                        c.queue_report_field,
+                        c.deployment.clone(),
+                        c.min_replicas,
+                        c.max_replicas
+                            .iter()
+                            .map(|(k, v)| (k.clone(), v.into_map_gpukey()))
+                            .collect(),
+                        c.speed.into_map_gpukey(),
+                        scaler_config.clone(),
+                        c.priority.clone(),
+                    );
+                    if let Some(predictive) = &c.predictive {
+                        scaler = scaler.with_predictive_policy(PredictivePolicy::from_config(
+                            predictive,
+                            ScalarOrMap::into_map_gpukey,
+                        ));
+                    }
+                    scalers.push(Box::new(scaler));
+                }
+                ScalerTargetType::Simple => {
+                    let mut scaler = Scaler::<NoKey>::new(
+                        c.queue_report_field,
+                        c.deployment.clone(),
+                        c.min_replicas,
+                        c.max_replicas
+                            .iter()
+                            .map(|(k, v)| (k.clone(), v.into_map_nokey()))
+                            .collect(),
+                        c.speed.into_map_nokey(),
+                        scaler_config.clone(),
+                        c.priority.clone(),
+                    );
+                    if let Some(predictive) = &c.predictive {
+                        scaler = scaler.with_predictive_policy(PredictivePolicy::from_config(
+                            predictive,
+                            ScalarOrMap::into_map_nokey,
+                        ));
+                    }
+                    scalers.push(Box::new(scaler));
+                }
             };
         }
         Self {
@@ -96,8 +108,14 @@ impl Manager {
             first_invoke_skipped: AtomicBool::new(false),
             jobs,
             scalers,
+            queue_history: None,
         }
     }
+
+    pub fn with_queue_history(mut self, recorder: QueueHistoryRecorder) -> Self {
+        self.queue_history = Some(recorder);
+        self
+    }
 }
 
 #[async_trait::async_trait]
@@ -108,7 +126,7 @@ impl Task for Manager {
             return Ok(());
         }
 
-        let queue = self
+        let (queue, sealed_batches) = self
             .queuer
             .get_queue(&self.jobs)
             .await
@@ -124,6 +142,8 @@ impl Task for Manager {
             }
 
             for (ns, ppv) in &self.namespaces {
+                let batches_sealed_last_hour = sealed_batches.get(ppv).cloned().unwrap_or(0);
+                AUTOSCALER_METRICS.batches_sealed_last_hour[ns].set(batches_sealed_last_hour);
                 for scaler in &self.scalers {
                     let q = queue
                         .get(&(ppv.to_string(), scaler.queue_report_field()))
@@ -134,7 +154,20 @@ impl Task for Manager {
                         "Running eval for namespace {ns}, PPV {ppv}, scaler {} found queue {q}",
                         scaler.deployment()
                     );
-                    scaler.run(ns, q, &guard.clusters, &mut scale_requests);
+                    if let Some(recorder) = &self.queue_history {
+                        if let Err(err) =
+                            recorder.record(ns, &scaler.deployment(), q, batches_sealed_last_hour)
+                        {
+                            tracing::warn!("Failed to record queue history: {err:#}");
+                        }
+                    }
+                    scaler.run(
+                        ns,
+                        q,
+                        batches_sealed_last_hour,
+                        &guard.clusters,
+                        &mut scale_requests,
+                    );
                 }
             }
         } // Unlock self.watcher.data.
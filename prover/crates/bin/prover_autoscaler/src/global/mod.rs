@@ -1,4 +1,6 @@
 pub mod manager;
+pub mod predictive;
 pub mod queuer;
 pub mod scaler;
+pub mod simulator;
 pub mod watcher;
@@ -0,0 +1,159 @@
+use std::{cmp::Ordering, collections::HashMap, time::Duration};
+
+use crate::{
+    config::{PredictiveScalingConfig, ScalarOrMap},
+    key::Key,
+};
+
+const SECONDS_IN_HOUR: u128 = 3600;
+
+/// Predictive, cost-aware scaling policy.
+///
+/// Forecasts queue growth from the recent batch sealing rate, so replicas are requested before
+/// the queue actually grows, taking into account the time new replicas need to start. Replica
+/// capacity is defined by the number of jobs it can process within the latency SLO, and pools
+/// are preferred by the cost of processing a single job.
+#[derive(Debug, Clone)]
+pub struct PredictivePolicy<K> {
+    jobs_per_batch: usize,
+    startup_latency: Duration,
+    latency_slo: Duration,
+    /// Jobs per hour processed by a single replica.
+    throughput: HashMap<K, usize>,
+    /// Hourly cost of a single replica.
+    hourly_cost: HashMap<K, usize>,
+}
+
+impl<K: Key> PredictivePolicy<K> {
+    pub fn new(
+        jobs_per_batch: usize,
+        startup_latency: Duration,
+        latency_slo: Duration,
+        throughput: HashMap<K, usize>,
+        hourly_cost: HashMap<K, usize>,
+    ) -> Self {
+        Self {
+            jobs_per_batch,
+            startup_latency,
+            latency_slo,
+            throughput,
+            hourly_cost,
+        }
+    }
+
+    pub fn from_config(
+        config: &PredictiveScalingConfig,
+        into_map: fn(&ScalarOrMap) -> HashMap<K, usize>,
+    ) -> Self {
+        Self::new(
+            config.jobs_per_batch,
+            config.startup_latency(),
+            config.latency_slo(),
+            into_map(&config.throughput),
+            into_map(&config.hourly_cost),
+        )
+    }
+
+    pub fn startup_latency(&self) -> Duration {
+        self.startup_latency
+    }
+
+    pub fn latency_slo(&self) -> Duration {
+        self.latency_slo
+    }
+
+    pub fn throughput(&self, key: K) -> Option<usize> {
+        self.throughput.get(&key).copied()
+    }
+
+    pub fn hourly_cost(&self, key: K) -> Option<usize> {
+        self.hourly_cost.get(&key).copied()
+    }
+
+    /// Expected number of new jobs per hour.
+    pub fn arrival_rate(&self, batches_sealed_last_hour: usize) -> usize {
+        batches_sealed_last_hour * self.jobs_per_batch
+    }
+
+    /// Number of jobs which have to be covered by replicas: the current queue plus jobs expected
+    /// to arrive while new replicas are starting and within the latency SLO.
+    pub fn forecast_demand(&self, queue: usize, batches_sealed_last_hour: usize) -> usize {
+        let horizon = (self.startup_latency + self.latency_slo).as_secs() as u128;
+        let arrivals = self.arrival_rate(batches_sealed_last_hour) as u128 * horizon;
+        queue + arrivals.div_ceil(SECONDS_IN_HOUR) as usize
+    }
+
+    /// Number of jobs a single replica processes within the latency SLO. Returns None if
+    /// throughput of the key isn't configured.
+    pub fn capacity(&self, key: K) -> Option<usize> {
+        let throughput = self.throughput(key)? as u128;
+        let jobs = throughput * self.latency_slo.as_secs() as u128 / SECONDS_IN_HOUR;
+        Some((jobs as usize).max(1))
+    }
+
+    /// Compares keys by the cost of a single job, cheaper first. Keys without configured cost or
+    /// throughput go last.
+    pub fn cost_cmp(&self, a: K, b: K) -> Ordering {
+        let cost = |key: K| -> Option<(u128, u128)> {
+            let throughput = self.throughput(key).filter(|t| *t > 0)?;
+            Some((self.hourly_cost(key)? as u128, throughput as u128))
+        };
+        match (cost(a), cost(b)) {
+            // Compare cost_a / throughput_a with cost_b / throughput_b without division.
+            (Some((cost_a, tp_a)), Some((cost_b, tp_b))) => (cost_a * tp_b).cmp(&(cost_b * tp_a)),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => Ordering::Equal,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::key::{Gpu, GpuKey};
+
+    fn policy() -> PredictivePolicy<GpuKey> {
+        PredictivePolicy::new(
+            100,
+            Duration::from_secs(600),
+            Duration::from_secs(1800),
+            [(GpuKey(Gpu::L4), 1000), (GpuKey(Gpu::H100), 4000)].into(),
+            [(GpuKey(Gpu::L4), 10), (GpuKey(Gpu::H100), 30)].into(),
+        )
+    }
+
+    #[test]
+    fn test_forecast_demand() {
+        let policy = policy();
+        assert_eq!(policy.forecast_demand(500, 0), 500, "No arrivals");
+        // 6 batches * 100 jobs per hour, 40 minutes horizon.
+        assert_eq!(policy.forecast_demand(500, 6), 900);
+    }
+
+    #[test]
+    fn test_capacity() {
+        let policy = policy();
+        assert_eq!(policy.capacity(GpuKey(Gpu::L4)), Some(500));
+        assert_eq!(policy.capacity(GpuKey(Gpu::H100)), Some(2000));
+        assert_eq!(policy.capacity(GpuKey(Gpu::T4)), None);
+    }
+
+    #[test]
+    fn test_cost_cmp() {
+        let policy = policy();
+        // H100 costs 30/4000 per job, cheaper than L4 10/1000.
+        assert_eq!(
+            policy.cost_cmp(GpuKey(Gpu::H100), GpuKey(Gpu::L4)),
+            Ordering::Less
+        );
+        assert_eq!(
+            policy.cost_cmp(GpuKey(Gpu::T4), GpuKey(Gpu::L4)),
+            Ordering::Greater
+        );
+        assert_eq!(
+            policy.cost_cmp(GpuKey(Gpu::T4), GpuKey(Gpu::A100)),
+            Ordering::Equal
+        );
+    }
+}
@@ -7,6 +7,8 @@ use zksync_prover_job_monitor::autoscaler_queue_reporter::{QueueReport, Versione
 use crate::{config::QueueReportFields, http_client::HttpClient};
 
 pub type Queue = HashMap<(String, QueueReportFields), usize>;
+/// Number of batches sealed during the last hour per protocol version.
+pub type SealedBatches = HashMap<String, usize>;
 
 #[derive(Default)]
 pub struct Queuer {
@@ -35,8 +37,11 @@ impl Queuer {
     }
 
     /// Requests queue report from prover-job-monitor and parse it into Queue HashMap for provided
-    /// list of jobs.
-    pub async fn get_queue(&self, jobs: &[QueueReportFields]) -> anyhow::Result<Queue> {
+    /// list of jobs, along with the recent batch sealing rate.
+    pub async fn get_queue(
+        &self,
+        jobs: &[QueueReportFields],
+    ) -> anyhow::Result<(Queue, SealedBatches)> {
         let url = &self.prover_job_monitor_url;
         let response = self
             .http_client
@@ -49,7 +54,7 @@ impl Queuer {
             .json::<Vec<VersionedQueueReport>>()
             .await
             .context("Failed to read response as json")?;
-        Ok(response
+        let queue = response
             .iter()
             .flat_map(|versioned_report| {
                 jobs.iter().map(move |j| {
@@ -59,6 +64,16 @@ impl Queuer {
                     )
                 })
             })
-            .collect::<HashMap<_, _>>())
+            .collect::<HashMap<_, _>>();
+        let sealed_batches = response
+            .iter()
+            .map(|versioned_report| {
+                (
+                    versioned_report.version.to_string(),
+                    versioned_report.report.batches_sealed_last_hour,
+                )
+            })
+            .collect::<HashMap<_, _>>();
+        Ok((queue, sealed_batches))
     }
 }
@@ -3,10 +3,11 @@ use std::{collections::HashMap, fmt::Debug, hash::Hash, str::FromStr, sync::Arc}
 use chrono::Utc;
 use debug_map_sorted::SortedOutputExt;
 
+use super::predictive::PredictivePolicy;
 use crate::{
     agent::{ScaleDeploymentRequest, ScaleRequest},
     cluster_types::{Cluster, ClusterName, Clusters, DeploymentName, NamespaceName, PodStatus},
-    config::{PriorityConfig, QueueReportFields},
+    config::{PriorityConfig, ProverAutoscalerScalerConfig, QueueReportFields},
     key::{Gpu, GpuKey, Key},
     metrics::{JobLabels, AUTOSCALER_METRICS},
 };
@@ -50,6 +51,21 @@ pub struct ScalerConfig {
     pub scale_errors_duration: chrono::Duration,
 }
 
+impl From<&ProverAutoscalerScalerConfig> for ScalerConfig {
+    fn from(config: &ProverAutoscalerScalerConfig) -> Self {
+        Self {
+            cluster_priorities: config.cluster_priorities.clone(),
+            apply_min_to_namespace: config.apply_min_to_namespace.clone(),
+            long_pending_duration: chrono::Duration::seconds(
+                config.long_pending_duration.as_secs() as i64,
+            ),
+            scale_errors_duration: chrono::Duration::seconds(
+                config.scale_errors_duration.as_secs() as i64,
+            ),
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct Scaler<K> {
     pub queue_report_field: QueueReportFields,
@@ -60,6 +76,7 @@ pub struct Scaler<K> {
     speed: HashMap<K, usize>,
     config: Arc<ScalerConfig>,
     target_priority: Option<PriorityConfig>,
+    predictive: Option<PredictivePolicy<K>>,
 }
 
 impl<K: Key> Scaler<K> {
@@ -80,9 +97,17 @@ impl<K: Key> Scaler<K> {
             speed,
             config,
             target_priority,
+            predictive: None,
         }
     }
 
+    /// Enables predictive scaling: replicas are calculated for the forecasted queue and pools
+    /// are preferred by cost per job.
+    pub fn with_predictive_policy(mut self, policy: PredictivePolicy<K>) -> Self {
+        self.predictive = Some(policy);
+        self
+    }
+
     fn convert_to_pool(&self, namespace: &NamespaceName, cluster: &Cluster) -> Vec<Pool<K>> {
         let Some(namespace_value) = &cluster.namespaces.get(namespace) else {
             // No namespace in config, ignoring.
@@ -90,7 +115,7 @@ impl<K: Key> Scaler<K> {
         };
 
         let mut pool_map = HashMap::new(); // <key, Pool>
-        for deployment in namespace_value.deployments.keys() {
+        for (deployment, deployment_value) in &namespace_value.deployments {
             // Processing only selected deployment(s).
             let Some(key) = K::new(self.deployment.to_str(), deployment) else {
                 continue;
@@ -112,8 +137,14 @@ impl<K: Key> Scaler<K> {
                 ..Default::default()
             });
 
-            // Initialize pool only if we have ready deployments.
-            e.pods.insert(PodStatus::Running, 0);
+            // Initialize pool only if we have ready deployments. Backends not reporting pods (e.g., webhooks)
+            // only provide the number of running replicas of each deployment.
+            let running = if namespace_value.pods.is_empty() {
+                deployment_value.running
+            } else {
+                0
+            };
+            *e.pods.entry(PodStatus::Running).or_default() += running;
         }
 
         for (pod, pod_value) in namespace_value.pods.iter() {
@@ -219,7 +250,10 @@ impl<K: Key> Scaler<K> {
         };
 
         pools.sort_by(|a, b| {
-            if self.target_priority.is_some() {
+            if let Some(policy) = &self.predictive {
+                // Prefer pools with cheaper cost per job.
+                policy.cost_cmp(a.key, b.key)
+            } else if self.target_priority.is_some() {
                 // Use target_priority for sorting, which includes GPU key.
                 // This is needed to keep old behavior and use a new one if target_priority is set.
                 std::cmp::Ordering::Equal
@@ -248,6 +282,13 @@ impl<K: Key> Scaler<K> {
     }
 
     fn speed(&self, key: K) -> usize {
+        if let Some(capacity) = self
+            .predictive
+            .as_ref()
+            .and_then(|policy| policy.capacity(key))
+        {
+            return capacity;
+        }
         *self.speed.get(&key).unwrap_or(&DEFAULT_SPEED)
     }
 
@@ -261,6 +302,15 @@ impl<K: Key> Scaler<K> {
         queue.div_ceil(speed) * speed
     }
 
+    /// Returns the queue size replicas should be calculated for. Without predictive policy it's
+    /// the current queue.
+    pub fn target_queue(&self, queue: usize, batches_sealed_last_hour: usize) -> usize {
+        match &self.predictive {
+            Some(policy) => policy.forecast_demand(queue, batches_sealed_last_hour),
+            None => queue,
+        }
+    }
+
     pub fn calculate(
         &self,
         namespace: &NamespaceName,
@@ -395,6 +445,7 @@ impl<K: Key> Scaler<K> {
                                     .push(ScaleDeploymentRequest {
                                         namespace: namespace.clone(),
                                         name: deployment_name.clone(),
+                                        gpu: key.gpu(),
                                         size: replicas,
                                     });
                             }
@@ -411,6 +462,7 @@ pub trait ScalerTrait {
         &self,
         namespace: &NamespaceName,
         queue: usize,
+        batches_sealed_last_hour: usize,
         clusters: &Clusters,
         requests: &mut HashMap<ClusterName, ScaleRequest>,
     );
@@ -428,10 +480,22 @@ impl<K: Key> ScalerTrait for Scaler<K> {
         &self,
         namespace: &NamespaceName,
         queue: usize,
+        batches_sealed_last_hour: usize,
         clusters: &Clusters,
         requests: &mut HashMap<ClusterName, ScaleRequest>,
     ) {
+        let queue = self.target_queue(queue, batches_sealed_last_hour);
         let replicas = self.calculate(namespace, queue, clusters);
+        if let Some(policy) = &self.predictive {
+            let hourly_cost: usize = replicas
+                .iter()
+                .map(|(k, num)| policy.hourly_cost(k.key).unwrap_or(0) * num)
+                .sum();
+            AUTOSCALER_METRICS.forecast_queue[&(namespace.clone(), self.deployment.clone())]
+                .set(queue);
+            AUTOSCALER_METRICS.planned_hourly_cost[&(namespace.clone(), self.deployment.clone())]
+                .set(hourly_cost);
+        }
         for (k, num) in &replicas {
             let labels = JobLabels {
                 job: self.deployment.clone(),
@@ -1481,6 +1545,79 @@ mod tests {
         );
     }
 
+    #[tracing_test::traced_test]
+    #[test]
+    fn test_calculate_predictive() {
+        let scaler = Scaler::new(
+            QueueReportFields::prover_jobs,
+            "circuit-prover-gpu".into(),
+            0,
+            [(
+                "foo".into(),
+                [(GpuKey(Gpu::L4), 100), (GpuKey(Gpu::H100), 100)].into(),
+            )]
+            .into(),
+            [(GpuKey(Gpu::L4), 500), (GpuKey(Gpu::H100), 500)].into(),
+            scaler_config("prover"),
+            None,
+        )
+        .with_predictive_policy(PredictivePolicy::new(
+            100,
+            std::time::Duration::from_secs(600),
+            std::time::Duration::from_secs(1800),
+            [(GpuKey(Gpu::L4), 1000), (GpuKey(Gpu::H100), 4000)].into(),
+            [(GpuKey(Gpu::L4), 10), (GpuKey(Gpu::H100), 30)].into(),
+        ));
+
+        let clusters = Clusters {
+            clusters: [(
+                "foo".into(),
+                Cluster {
+                    name: "foo".into(),
+                    namespaces: [(
+                        "prover".into(),
+                        Namespace {
+                            deployments: [
+                                ("circuit-prover-gpu".into(), Deployment::default()),
+                                ("circuit-prover-gpu-h100".into(), Deployment::default()),
+                            ]
+                            .into(),
+                            ..Default::default()
+                        },
+                    )]
+                    .into(),
+                },
+            )]
+            .into(),
+            ..Default::default()
+        };
+
+        // 1000 in the queue and 6 batches per hour forecasts 400 more jobs.
+        let queue = scaler.target_queue(1000, 6);
+        assert_eq!(queue, 1400);
+        assert_eq!(
+            scaler.calculate(&"prover".into(), queue, &clusters),
+            [
+                (
+                    PoolKey {
+                        cluster: "foo".into(),
+                        key: GpuKey(Gpu::L4),
+                    },
+                    0,
+                ),
+                (
+                    PoolKey {
+                        cluster: "foo".into(),
+                        key: GpuKey(Gpu::H100),
+                    },
+                    1,
+                )
+            ]
+            .into(),
+            "Cheaper per job H100 is used, it processes 2000 jobs within SLO"
+        );
+    }
+
     #[tracing_test::traced_test]
     #[test]
     fn test_convert_to_pool() {
@@ -1551,4 +1688,47 @@ mod tests {
             }]
         );
     }
+
+    #[test]
+    fn test_convert_to_pool_without_pods() {
+        let scaler = Scaler::new(
+            QueueReportFields::prover_jobs,
+            "circuit-prover-gpu".into(),
+            2,
+            [("foo".into(), [(GpuKey(Gpu::L4), 100)].into())].into(),
+            [(GpuKey(Gpu::L4), 500)].into(),
+            scaler_config("prover"),
+            None,
+        );
+
+        let cluster = &Cluster {
+            name: "foo".into(),
+            namespaces: [(
+                "prover".into(),
+                Namespace {
+                    deployments: [(
+                        "circuit-prover-gpu".into(),
+                        Deployment {
+                            running: 3,
+                            desired: 4,
+                        },
+                    )]
+                    .into(),
+                    ..Default::default()
+                },
+            )]
+            .into(),
+        };
+        assert_eq!(
+            scaler.convert_to_pool(&"prover".into(), cluster),
+            vec![Pool {
+                name: "foo".into(),
+                key: GpuKey(Gpu::L4),
+                pods: [(PodStatus::Running, 3)].into(),
+                scale_errors: 0,
+                max_pool_size: 100,
+            }],
+            "Running replicas are taken from the deployment if there are no pods"
+        );
+    }
 }
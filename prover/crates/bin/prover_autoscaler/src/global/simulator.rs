@@ -0,0 +1,509 @@
+//! Offline evaluation of scaling policies by replaying recorded queue histories.
+
+use std::{
+    collections::{HashMap, VecDeque},
+    fs,
+    io::{BufRead, Write},
+    path::PathBuf,
+    sync::{Arc, Mutex},
+};
+
+use anyhow::Context;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::{
+    predictive::PredictivePolicy,
+    scaler::{PoolKey, Scaler, ScalerConfig},
+};
+use crate::{
+    cluster_types::{
+        Cluster, ClusterName, Clusters, Deployment, DeploymentName, Namespace, NamespaceName, Pod,
+    },
+    config::{PredictiveScalingConfig, ScalarOrMap, ScalerTarget, ScalerTargetType},
+    key::{GpuKey, Key, NoKey},
+};
+
+const SIMULATION_NAMESPACE: &str = "simulation";
+
+/// Single record of the queue history, recorded every scaler run by [`QueueHistoryRecorder`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QueueHistoryRecord {
+    pub timestamp: DateTime<Utc>,
+    /// Queue size at the time of the record. Only the first record is used to set the initial
+    /// backlog, later the queue is simulated.
+    pub queue: usize,
+    /// Number of batches sealed since the previous record.
+    pub sealed_batches: usize,
+}
+
+/// Reads queue history in JSON lines format.
+pub fn read_history(reader: impl BufRead) -> anyhow::Result<Vec<QueueHistoryRecord>> {
+    let mut history = Vec::new();
+    for (n, line) in reader.lines().enumerate() {
+        let line = line.context("Failed to read queue history")?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let record: QueueHistoryRecord = serde_json::from_str(&line)
+            .with_context(|| format!("Failed to parse queue history line {}", n + 1))?;
+        history.push(record);
+    }
+    history.sort_by_key(|r| r.timestamp);
+    Ok(history)
+}
+
+/// Records queue history every scaler run, one JSON lines file per namespace and deployment
+/// (`<namespace>.<deployment>.jsonl`), so that it can be replayed by [`compare()`].
+#[derive(Debug)]
+pub struct QueueHistoryRecorder {
+    dir: PathBuf,
+    /// Timestamp of the previous record and the fractional part of sealed batches not yet
+    /// recorded, per namespace and deployment.
+    previous: Mutex<HashMap<(NamespaceName, DeploymentName), (DateTime<Utc>, f64)>>,
+}
+
+impl QueueHistoryRecorder {
+    pub fn new(dir: PathBuf) -> anyhow::Result<Self> {
+        fs::create_dir_all(&dir)
+            .with_context(|| format!("Failed to create queue history dir {}", dir.display()))?;
+        Ok(Self {
+            dir,
+            previous: Mutex::default(),
+        })
+    }
+
+    pub fn path(&self, namespace: &NamespaceName, deployment: &DeploymentName) -> PathBuf {
+        self.dir.join(format!("{namespace}.{deployment}.jsonl"))
+    }
+
+    /// Appends a record for the current scaler run.
+    pub fn record(
+        &self,
+        namespace: &NamespaceName,
+        deployment: &DeploymentName,
+        queue: usize,
+        batches_sealed_last_hour: usize,
+    ) -> anyhow::Result<()> {
+        let record = self.next_record(
+            namespace,
+            deployment,
+            Utc::now(),
+            queue,
+            batches_sealed_last_hour,
+        );
+        let path = self.path(namespace, deployment);
+        let mut file = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .with_context(|| format!("Failed to open {}", path.display()))?;
+        let mut line = serde_json::to_string(&record)?;
+        line.push('\n');
+        file.write_all(line.as_bytes())
+            .with_context(|| format!("Failed to write to {}", path.display()))
+    }
+
+    /// Queue reports only provide the number of batches sealed during the last hour, so batches
+    /// sealed since the previous record are estimated from this rate.
+    fn next_record(
+        &self,
+        namespace: &NamespaceName,
+        deployment: &DeploymentName,
+        now: DateTime<Utc>,
+        queue: usize,
+        batches_sealed_last_hour: usize,
+    ) -> QueueHistoryRecord {
+        let mut previous = self.previous.lock().unwrap();
+        let key = (namespace.clone(), deployment.clone());
+        let sealed_batches = match previous.get(&key) {
+            Some((prev_time, carry)) => {
+                let millis = (now - *prev_time).num_milliseconds().max(0) as f64;
+                let sealed = batches_sealed_last_hour as f64 * millis / 3_600_000.0 + carry;
+                previous.insert(key, (now, sealed.fract()));
+                sealed.trunc() as usize
+            }
+            None => {
+                previous.insert(key, (now, 0.0));
+                0
+            }
+        };
+        QueueHistoryRecord {
+            timestamp: now,
+            queue,
+            sealed_batches,
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Serialize)]
+pub struct SimulationReport {
+    pub steps: usize,
+    /// Total cost of running and starting replicas.
+    pub total_cost: f64,
+    /// Number of steps where the queue couldn't be processed within latency SLO.
+    pub slo_violations: usize,
+    pub max_queue: usize,
+    pub max_replicas: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SimulationComparison {
+    pub recorded_max_queue: usize,
+    pub reactive: SimulationReport,
+    pub predictive: SimulationReport,
+}
+
+/// Replays `history` for `target` with and without predictive policy.
+pub fn compare(
+    target: &ScalerTarget,
+    config: Arc<ScalerConfig>,
+    history: &[QueueHistoryRecord],
+) -> anyhow::Result<SimulationComparison> {
+    let predictive = target.predictive.as_ref().with_context(|| {
+        format!(
+            "Scaler target {} doesn't have predictive config",
+            target.deployment
+        )
+    })?;
+    let (reactive, predictive) = match target.scaler_target_type {
+        ScalerTargetType::Gpu => compare_with_key::<GpuKey>(
+            target,
+            predictive,
+            config,
+            history,
+            ScalarOrMap::into_map_gpukey,
+        ),
+        ScalerTargetType::Simple => compare_with_key::<NoKey>(
+            target,
+            predictive,
+            config,
+            history,
+            ScalarOrMap::into_map_nokey,
+        ),
+    };
+    Ok(SimulationComparison {
+        recorded_max_queue: history.iter().map(|r| r.queue).max().unwrap_or(0),
+        reactive,
+        predictive,
+    })
+}
+
+fn compare_with_key<K: Key>(
+    target: &ScalerTarget,
+    predictive: &PredictiveScalingConfig,
+    config: Arc<ScalerConfig>,
+    history: &[QueueHistoryRecord],
+    into_map: fn(&ScalarOrMap) -> HashMap<K, usize>,
+) -> (SimulationReport, SimulationReport) {
+    let policy = PredictivePolicy::from_config(predictive, into_map);
+    let max_replicas: HashMap<ClusterName, HashMap<K, usize>> = target
+        .max_replicas
+        .iter()
+        .map(|(k, v)| (k.clone(), into_map(v)))
+        .collect();
+    let scaler = |config: Arc<ScalerConfig>| {
+        Scaler::<K>::new(
+            target.queue_report_field,
+            target.deployment.clone(),
+            target.min_replicas,
+            max_replicas.clone(),
+            into_map(&target.speed),
+            config,
+            target.priority.clone(),
+        )
+    };
+
+    let simulation = Simulation {
+        policy: &policy,
+        jobs_per_batch: predictive.jobs_per_batch,
+        max_replicas: &max_replicas,
+    };
+    let reactive = simulation.run(&scaler(config.clone()), history);
+    let predictive = simulation.run(
+        &scaler(config).with_predictive_policy(policy.clone()),
+        history,
+    );
+    (reactive, predictive)
+}
+
+struct Simulation<'a, K> {
+    /// Used for real throughput, cost and start-up latency of replicas.
+    policy: &'a PredictivePolicy<K>,
+    jobs_per_batch: usize,
+    max_replicas: &'a HashMap<ClusterName, HashMap<K, usize>>,
+}
+
+impl<K: Key> Simulation<'_, K> {
+    fn run(&self, scaler: &Scaler<K>, history: &[QueueHistoryRecord]) -> SimulationReport {
+        let namespace: NamespaceName = SIMULATION_NAMESPACE.into();
+        let slo_secs = self.policy.latency_slo().as_secs_f64();
+        let startup_latency = chrono::Duration::from_std(self.policy.startup_latency())
+            .unwrap_or_else(|_| chrono::Duration::zero());
+
+        let mut report = SimulationReport::default();
+        // Time when each replica is ready to process jobs.
+        let mut replicas: HashMap<PoolKey<K>, Vec<DateTime<Utc>>> = HashMap::new();
+        // Sealed batches within the last hour.
+        let mut sealed_window: VecDeque<(DateTime<Utc>, usize)> = VecDeque::new();
+        let mut queue = history.first().map_or(0.0, |r| r.queue as f64);
+        let mut prev_time = history.first().map_or_else(Utc::now, |r| r.timestamp);
+
+        for record in history {
+            let now = record.timestamp;
+            let hours_since = |t: DateTime<Utc>| -> f64 {
+                (now - t.max(prev_time)).num_milliseconds().max(0) as f64 / 3_600_000.0
+            };
+
+            // Process jobs and pay for replicas since the previous step.
+            let mut processed = 0.0;
+            for (pool, ready_at) in &replicas {
+                let throughput = self.policy.throughput(pool.key).unwrap_or(0) as f64;
+                let cost = self.policy.hourly_cost(pool.key).unwrap_or(0) as f64;
+                report.total_cost += cost * ready_at.len() as f64 * hours_since(prev_time);
+                processed += ready_at
+                    .iter()
+                    .map(|t| throughput * hours_since(*t))
+                    .sum::<f64>();
+            }
+            prev_time = now;
+            queue = (queue - processed).max(0.0);
+            if report.steps > 0 {
+                queue += (record.sealed_batches * self.jobs_per_batch) as f64;
+            }
+
+            sealed_window.push_back((now, record.sealed_batches));
+            while sealed_window
+                .front()
+                .is_some_and(|(t, _)| *t <= now - chrono::Duration::hours(1))
+            {
+                sealed_window.pop_front();
+            }
+            let batches_sealed_last_hour: usize = sealed_window.iter().map(|(_, n)| n).sum();
+
+            let active_throughput: f64 = replicas
+                .iter()
+                .map(|(pool, ready_at)| {
+                    ready_at.iter().filter(|t| **t <= now).count() as f64
+                        * self.policy.throughput(pool.key).unwrap_or(0) as f64
+                })
+                .sum();
+            if queue >= 1.0 && queue / active_throughput * 3600.0 > slo_secs {
+                report.slo_violations += 1;
+            }
+
+            let target = scaler.target_queue(queue.ceil() as usize, batches_sealed_last_hour);
+            let clusters = self.clusters(scaler, &namespace, &replicas, now);
+            let desired = scaler.calculate(&namespace, target, &clusters);
+            for (pool, n) in desired {
+                let ready_at = replicas.entry(pool).or_default();
+                if ready_at.len() < n {
+                    ready_at.resize(n, now + startup_latency);
+                } else {
+                    // Stop starting replicas first.
+                    ready_at.sort();
+                    ready_at.truncate(n);
+                }
+            }
+
+            report.steps += 1;
+            report.max_queue = report.max_queue.max(queue.ceil() as usize);
+            report.max_replicas = report
+                .max_replicas
+                .max(replicas.values().map(Vec::len).sum());
+        }
+        report
+    }
+
+    /// Builds synthetic cluster state, as it would be reported by Agents.
+    fn clusters(
+        &self,
+        scaler: &Scaler<K>,
+        namespace: &NamespaceName,
+        replicas: &HashMap<PoolKey<K>, Vec<DateTime<Utc>>>,
+        now: DateTime<Utc>,
+    ) -> Clusters {
+        let clusters = self
+            .max_replicas
+            .iter()
+            .map(|(cluster, keys)| {
+                let mut ns = Namespace::default();
+                for key in keys.keys() {
+                    let deployment = key.to_deployment(scaler.deployment.to_str());
+                    let ready_at = replicas
+                        .get(&PoolKey {
+                            cluster: cluster.clone(),
+                            key: *key,
+                        })
+                        .cloned()
+                        .unwrap_or_default();
+                    ns.deployments.insert(
+                        deployment.clone(),
+                        Deployment {
+                            running: ready_at.iter().filter(|t| **t <= now).count(),
+                            desired: ready_at.len(),
+                        },
+                    );
+                    for (i, t) in ready_at.iter().enumerate() {
+                        ns.pods.insert(
+                            format!("{}-sim-{}", deployment, i),
+                            Pod {
+                                owner: deployment.to_string(),
+                                status: if *t <= now { "Running" } else { "Pending" }.into(),
+                                changed: Utc::now(),
+                                out_of_resources: false,
+                            },
+                        );
+                    }
+                }
+                (
+                    cluster.clone(),
+                    Cluster {
+                        name: cluster.clone(),
+                        namespaces: [(namespace.clone(), ns)].into(),
+                    },
+                )
+            })
+            .collect();
+        Clusters {
+            clusters,
+            ..Default::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        config::QueueReportFields,
+        key::{Gpu, GpuKey},
+    };
+
+    fn target() -> ScalerTarget {
+        ScalerTarget {
+            scaler_target_type: ScalerTargetType::Gpu,
+            queue_report_field: QueueReportFields::prover_jobs,
+            deployment: "circuit-prover-gpu".into(),
+            min_replicas: 0,
+            max_replicas: [(
+                "foo".into(),
+                ScalarOrMap::Map([(GpuKey(Gpu::L4), 100), (GpuKey(Gpu::H100), 100)].into()),
+            )]
+            .into(),
+            speed: ScalarOrMap::Map([(GpuKey(Gpu::L4), 500), (GpuKey(Gpu::H100), 2000)].into()),
+            priority: None,
+            predictive: Some(PredictiveScalingConfig {
+                jobs_per_batch: 1000,
+                startup_latency_secs: 600,
+                latency_slo_secs: 1800,
+                throughput: ScalarOrMap::Map(
+                    [(GpuKey(Gpu::L4), 1000), (GpuKey(Gpu::H100), 4000)].into(),
+                ),
+                hourly_cost: ScalarOrMap::Map(
+                    [(GpuKey(Gpu::L4), 10), (GpuKey(Gpu::H100), 30)].into(),
+                ),
+            }),
+        }
+    }
+
+    fn config() -> Arc<ScalerConfig> {
+        Arc::new(ScalerConfig {
+            cluster_priorities: [("foo".into(), 0)].into(),
+            long_pending_duration: chrono::Duration::seconds(3600),
+            scale_errors_duration: chrono::Duration::seconds(3600),
+            ..Default::default()
+        })
+    }
+
+    /// Steady load: 2 batches every 10 minutes for 6 hours.
+    fn history() -> Vec<QueueHistoryRecord> {
+        let start = DateTime::parse_from_rfc3339("2025-01-01T00:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        (0..=36)
+            .map(|i| QueueHistoryRecord {
+                timestamp: start + chrono::Duration::minutes(10 * i),
+                queue: 0,
+                sealed_batches: 2,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_read_history() {
+        let input = r#"{"timestamp":"2025-01-01T00:10:00Z","queue":5,"sealed_batches":1}
+
+{"timestamp":"2025-01-01T00:00:00Z","queue":0,"sealed_batches":0}
+"#;
+        let history = read_history(input.as_bytes()).unwrap();
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].queue, 0, "History is sorted by timestamp");
+        assert_eq!(history[1].sealed_batches, 1);
+
+        assert!(read_history("not json".as_bytes()).is_err());
+    }
+
+    #[tracing_test::traced_test]
+    #[test]
+    fn test_compare() {
+        let history = history();
+        let comparison = compare(&target(), config(), &history).unwrap();
+
+        assert_eq!(comparison.recorded_max_queue, 0);
+        assert_eq!(comparison.reactive.steps, history.len());
+        assert_eq!(comparison.predictive.steps, history.len());
+        assert!(
+            comparison.predictive.slo_violations < comparison.reactive.slo_violations,
+            "Predictive policy should start replicas ahead of the load: {:?}",
+            comparison
+        );
+        assert!(
+            comparison.predictive.max_queue < comparison.reactive.max_queue,
+            "{:?}",
+            comparison
+        );
+    }
+
+    #[test]
+    fn test_recorded_history() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let recorder = QueueHistoryRecorder::new(dir.path().join("history")).unwrap();
+        let namespace = NamespaceName::from("prover");
+        let deployment = DeploymentName::from("circuit-prover-gpu");
+        let start = DateTime::parse_from_rfc3339("2025-01-01T00:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+
+        // 3 batches per hour, recorded every 10 minutes: 0.5 batches per record.
+        let records: Vec<_> = (0..5)
+            .map(|i| {
+                recorder.next_record(
+                    &namespace,
+                    &deployment,
+                    start + chrono::Duration::minutes(10 * i),
+                    100,
+                    3,
+                )
+            })
+            .collect();
+        let sealed: Vec<_> = records.iter().map(|r| r.sealed_batches).collect();
+        assert_eq!(sealed, [0, 0, 1, 0, 1]);
+
+        recorder.record(&namespace, &deployment, 42, 3).unwrap();
+        recorder.record(&namespace, &deployment, 43, 3).unwrap();
+        let file = fs::File::open(recorder.path(&namespace, &deployment)).unwrap();
+        let history = read_history(std::io::BufReader::new(file)).unwrap();
+        let queues: Vec<_> = history.iter().map(|r| r.queue).collect();
+        assert_eq!(queues, [42, 43]);
+    }
+
+    #[test]
+    fn test_compare_requires_predictive_config() {
+        let target = ScalerTarget {
+            predictive: None,
+            ..target()
+        };
+        assert!(compare(&target, config(), &history()).is_err());
+    }
+}
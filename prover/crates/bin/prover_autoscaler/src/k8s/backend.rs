@@ -0,0 +1,34 @@
+use async_trait::async_trait;
+
+use crate::{
+    agent::ScaleDeploymentRequest,
+    cluster_types::Cluster,
+    k8s::{Scaler, Watcher},
+    scaler_backend::ScalerBackend,
+};
+
+/// Kubernetes backend: cluster state is collected by [`Watcher`] and Deployments are patched by [`Scaler`].
+#[derive(Clone)]
+pub struct K8sBackend {
+    watcher: Watcher,
+    scaler: Scaler,
+}
+
+impl K8sBackend {
+    pub fn new(watcher: Watcher, scaler: Scaler) -> Self {
+        Self { watcher, scaler }
+    }
+}
+
+#[async_trait]
+impl ScalerBackend for K8sBackend {
+    async fn cluster(&self) -> anyhow::Result<Cluster> {
+        Ok(self.watcher.cluster.lock().await.clone())
+    }
+
+    async fn scale(&self, request: &ScaleDeploymentRequest) -> anyhow::Result<()> {
+        self.scaler
+            .scale(&request.namespace, &request.name, request.size)
+            .await
+    }
+}
@@ -8,3 +8,5 @@ pub mod http_client;
 pub mod k8s;
 pub(crate) mod key;
 pub(crate) mod metrics;
+pub mod scaler_backend;
+pub mod webhook;
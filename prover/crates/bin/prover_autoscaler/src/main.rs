@@ -1,15 +1,20 @@
+use std::sync::Arc;
+
 use anyhow::Context;
 use smart_config::{ConfigSchema, DescribeConfig};
 use structopt::StructOpt;
 use tokio::sync::{oneshot, watch};
+use url::Url;
 use zksync_config::{sources::ConfigSources, ConfigRepositoryExt};
 use zksync_prover_autoscaler::{
     agent,
-    cluster_types::ClusterName,
-    config::ProverAutoscalerConfig,
-    global::{manager::Manager, queuer::Queuer, watcher},
+    cluster_types::{ClusterName, DeploymentName},
+    config::{ProverAutoscalerConfig, ScalerBackendConfig},
+    global::{manager::Manager, queuer::Queuer, scaler::ScalerConfig, simulator, watcher},
     http_client::HttpClient,
-    k8s::{Scaler, Watcher},
+    k8s::{K8sBackend, Scaler, Watcher},
+    scaler_backend::ScalerBackend,
+    webhook::WebhookBackend,
 };
 use zksync_prover_task::TaskRunner;
 use zksync_task_management::ManagedTasks;
@@ -20,6 +25,7 @@ use zksync_vlog::prometheus::PrometheusExporterConfig;
 pub enum AutoscalerType {
     Scaler,
     Agent,
+    Simulate,
 }
 
 impl std::str::FromStr for AutoscalerType {
@@ -29,6 +35,7 @@ impl std::str::FromStr for AutoscalerType {
         match s {
             "scaler" => Ok(AutoscalerType::Scaler),
             "agent" => Ok(AutoscalerType::Agent),
+            "simulate" => Ok(AutoscalerType::Simulate),
             other => Err(format!("{} is not a valid AutoscalerType", other)),
         }
     }
@@ -37,9 +44,9 @@ impl std::str::FromStr for AutoscalerType {
 #[derive(Debug, StructOpt)]
 #[structopt(name = "Prover Autoscaler", about = "Run Prover Autoscaler components")]
 struct Opt {
-    /// Prover Autoscaler can run Agent or Scaler type.
+    /// Prover Autoscaler can run Agent or Scaler type, or simulate scaling offline.
     ///
-    /// Specify `agent` or `scaler`
+    /// Specify `agent`, `scaler` or `simulate`
     #[structopt(short, long, default_value = "agent")]
     job: AutoscalerType,
     /// Name of the cluster Agent is watching.
@@ -48,6 +55,12 @@ struct Opt {
     /// Path to the configuration file.
     #[structopt(long)]
     config_path: std::path::PathBuf,
+    /// Path to the queue history in JSON lines format, used by `simulate`.
+    #[structopt(long)]
+    history_path: Option<std::path::PathBuf>,
+    /// Deployment of the scaler target to simulate.
+    #[structopt(long)]
+    target: Option<DeploymentName>,
 }
 
 #[tokio::main]
@@ -61,6 +74,10 @@ async fn main() -> anyhow::Result<()> {
     let config_repo = config_sources.build_repository(&full_config_schema);
     let general_config: ProverAutoscalerConfig = config_repo.parse()?;
 
+    if opt.job == AutoscalerType::Simulate {
+        return simulate(&opt, general_config);
+    }
+
     let (stop_signal_sender, stop_signal_receiver) = oneshot::channel();
     let mut stop_signal_sender = Some(stop_signal_sender);
     ctrlc::set_handler(move || {
@@ -83,26 +100,46 @@ async fn main() -> anyhow::Result<()> {
             let exporter_config = PrometheusExporterConfig::pull(agent_config.prometheus_port);
             tasks.push(tokio::spawn(exporter_config.run(stop_receiver.clone())));
 
-            let _ = rustls::crypto::ring::default_provider().install_default();
-            let client = kube::Client::try_default().await?;
-
-            let watcher = Watcher::new(
-                http_client,
-                client.clone(),
-                opt.cluster_name,
-                agent_config.namespaces,
-                agent_config.pod_check_interval,
-            )
-            .await;
-            let scaler = Scaler::new(client, agent_config.dry_run);
-            tasks.push(tokio::spawn(watcher.clone().run(stop_receiver.clone())));
+            let backend: Arc<dyn ScalerBackend> = match agent_config.backend {
+                ScalerBackendConfig::K8s => {
+                    let _ = rustls::crypto::ring::default_provider().install_default();
+                    let client = kube::Client::try_default().await?;
+
+                    let watcher = Watcher::new(
+                        http_client,
+                        client.clone(),
+                        opt.cluster_name,
+                        agent_config.namespaces,
+                        agent_config.pod_check_interval,
+                    )
+                    .await;
+                    let scaler = Scaler::new(client, agent_config.dry_run);
+                    tasks.push(tokio::spawn(watcher.clone().run(stop_receiver.clone())));
+                    Arc::new(K8sBackend::new(watcher, scaler))
+                }
+                ScalerBackendConfig::Webhook { url } => {
+                    let url = Url::parse(&url)
+                        .with_context(|| format!("Unparsable webhook URL {url}"))?;
+                    let cluster_name = opt
+                        .cluster_name
+                        .context("--cluster-name is required for webhook backend")?;
+                    tracing::info!("Agent cluster name is {cluster_name}, using webhook {url}");
+                    Arc::new(WebhookBackend::new(
+                        http_client,
+                        url,
+                        cluster_name,
+                        agent_config.namespaces,
+                        agent_config.dry_run,
+                    ))
+                }
+            };
             tasks.push(tokio::spawn(agent::run_server(
                 agent_config.http_port,
-                watcher,
-                scaler,
+                backend,
                 stop_receiver.clone(),
             )))
         }
+        AutoscalerType::Simulate => unreachable!("simulation doesn't run any tasks"),
         AutoscalerType::Scaler => {
             tracing::info!("Starting ProverAutoscaler Scaler");
             let scaler_config = general_config.scaler_config.context("scaler_config")?;
@@ -116,7 +153,15 @@ async fn main() -> anyhow::Result<()> {
                 scaler_config.dry_run,
             );
             let queuer = Queuer::new(http_client, scaler_config.prover_job_monitor_url.clone());
-            let manager = Manager::new(watcher.clone(), queuer, scaler_config);
+            let queue_history = scaler_config
+                .queue_history_path
+                .clone()
+                .map(simulator::QueueHistoryRecorder::new)
+                .transpose()?;
+            let mut manager = Manager::new(watcher.clone(), queuer, scaler_config);
+            if let Some(recorder) = queue_history {
+                manager = manager.with_queue_history(recorder);
+            }
 
             let mut task_runner = TaskRunner::default();
             task_runner.extend("AgentPoller", interval, watcher.create_poller_tasks());
@@ -140,3 +185,33 @@ async fn main() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+/// Replays queue history for a scaler target and prints comparison of reactive and predictive
+/// policies.
+fn simulate(opt: &Opt, general_config: ProverAutoscalerConfig) -> anyhow::Result<()> {
+    let scaler_config = general_config.scaler_config.context("scaler_config")?;
+    let history_path = opt
+        .history_path
+        .as_ref()
+        .context("--history-path is required for simulate")?;
+    let target_name = opt
+        .target
+        .as_ref()
+        .context("--target is required for simulate")?;
+    let target = scaler_config
+        .scaler_targets
+        .iter()
+        .find(|t| t.deployment == *target_name)
+        .with_context(|| format!("Scaler target {target_name} not found in config"))?;
+
+    let file = std::fs::File::open(history_path)
+        .with_context(|| format!("Failed to open {}", history_path.display()))?;
+    let history = simulator::read_history(std::io::BufReader::new(file))?;
+    let comparison = simulator::compare(
+        target,
+        Arc::new(ScalerConfig::from(&scaler_config)),
+        &history,
+    )?;
+    println!("{}", serde_json::to_string_pretty(&comparison)?);
+    Ok(())
+}
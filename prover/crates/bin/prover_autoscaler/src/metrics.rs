@@ -30,6 +30,14 @@ pub(crate) struct AutoscalerMetrics {
     pub scale_errors: LabeledFamily<ClusterName, Gauge<u64>>,
     #[metrics(labels = ["target_namespace", "job"])]
     pub queue: LabeledFamily<(NamespaceName, DeploymentName), Gauge<usize>, 2>,
+    /// Queue forecasted by predictive scaling policy.
+    #[metrics(labels = ["target_namespace", "job"])]
+    pub forecast_queue: LabeledFamily<(NamespaceName, DeploymentName), Gauge<usize>, 2>,
+    /// Hourly cost of replicas planned by predictive scaling policy.
+    #[metrics(labels = ["target_namespace", "job"])]
+    pub planned_hourly_cost: LabeledFamily<(NamespaceName, DeploymentName), Gauge<usize>, 2>,
+    #[metrics(labels = ["target_namespace"])]
+    pub batches_sealed_last_hour: LabeledFamily<NamespaceName, Gauge<usize>>,
     #[metrics(labels = ["pod_name"])]
     pub stale_pods: LabeledFamily<String, Counter, 1>,
 }
@@ -0,0 +1,16 @@
+use async_trait::async_trait;
+
+use crate::{agent::ScaleDeploymentRequest, cluster_types::Cluster};
+
+/// Backend used by Agent to watch and scale prover Deployments in a cluster.
+///
+/// Kubernetes is supported via [`crate::k8s::K8sBackend`]; fleets managed by other schedulers (e.g. bare metal)
+/// can be plugged in via [`crate::webhook::WebhookBackend`].
+#[async_trait]
+pub trait ScalerBackend: Send + Sync + 'static {
+    /// Returns the current state of the watched namespaces in the cluster.
+    async fn cluster(&self) -> anyhow::Result<Cluster>;
+
+    /// Scales the Deployment to the requested number of replicas.
+    async fn scale(&self, request: &ScaleDeploymentRequest) -> anyhow::Result<()>;
+}
@@ -0,0 +1,287 @@
+//! Generic webhook backend, allowing Agent to scale prover fleets that are not managed by Kubernetes.
+//!
+//! The webhook must serve 2 paths relative to its base URL:
+//! - `GET cluster` returning the current state of namespaces as [`WebhookClusterState`];
+//! - `POST scale` accepting the desired number of replicas as [`WebhookScaleRequest`].
+//!
+//! The webhook may not report pods; in this case, the number of running replicas is taken from deployments.
+
+use std::collections::HashMap;
+
+use anyhow::Context;
+use async_trait::async_trait;
+use reqwest::{
+    header::{HeaderMap, HeaderValue, CONTENT_TYPE},
+    Method,
+};
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+use crate::{
+    agent::ScaleDeploymentRequest,
+    cluster_types::{Cluster, ClusterName, DeploymentName, Namespace, NamespaceName},
+    http_client::HttpClient,
+    key::Gpu,
+    scaler_backend::ScalerBackend,
+};
+
+/// Response of the webhook `/cluster` path.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct WebhookClusterState {
+    pub namespaces: HashMap<NamespaceName, Namespace>,
+}
+
+/// Body of the webhook `/scale` request.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WebhookScaleRequest {
+    pub cluster: ClusterName,
+    pub namespace: NamespaceName,
+    pub deployment: DeploymentName,
+    /// GPU type of the Deployment, if it's a GPU scaler target.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gpu: Option<Gpu>,
+    pub replicas: usize,
+}
+
+#[derive(Clone)]
+pub struct WebhookBackend {
+    http_client: HttpClient,
+    base_url: Url,
+    cluster_name: ClusterName,
+    namespaces: Vec<NamespaceName>,
+    dry_run: bool,
+}
+
+impl WebhookBackend {
+    pub fn new(
+        http_client: HttpClient,
+        mut base_url: Url,
+        cluster_name: ClusterName,
+        namespaces: Vec<NamespaceName>,
+        dry_run: bool,
+    ) -> Self {
+        // Without the trailing slash, the last base path segment would be replaced when joining paths.
+        if !base_url.path().ends_with('/') {
+            let path = format!("{}/", base_url.path());
+            base_url.set_path(&path);
+        }
+        Self {
+            http_client,
+            base_url,
+            cluster_name,
+            namespaces,
+            dry_run,
+        }
+    }
+
+    fn url(&self, path: &str) -> anyhow::Result<String> {
+        Ok(self
+            .base_url
+            .join(path)
+            .with_context(|| format!("Failed to build webhook URL for {path}"))?
+            .to_string())
+    }
+}
+
+#[async_trait]
+impl ScalerBackend for WebhookBackend {
+    async fn cluster(&self) -> anyhow::Result<Cluster> {
+        let url = self.url("cluster")?;
+        let response = self
+            .http_client
+            .send_request_with_retries(&url, Method::GET, None, None)
+            .await
+            .map_err(|err| anyhow::anyhow!("Failed fetching cluster state from {url}: {err:?}"))?;
+        let mut state: WebhookClusterState = response
+            .json()
+            .await
+            .context("Failed to read cluster state response as json")?;
+
+        // Report only watched namespaces, so that the state is consistent with the Kubernetes backend.
+        let namespaces = self
+            .namespaces
+            .iter()
+            .map(|ns| (ns.clone(), state.namespaces.remove(ns).unwrap_or_default()))
+            .collect();
+        Ok(Cluster {
+            name: self.cluster_name.clone(),
+            namespaces,
+        })
+    }
+
+    async fn scale(&self, request: &ScaleDeploymentRequest) -> anyhow::Result<()> {
+        if self.dry_run {
+            tracing::info!(
+                "Dry run of scaled {}/{} to {} replica(s) via webhook.",
+                request.namespace,
+                request.name,
+                request.size
+            );
+            return Ok(());
+        }
+
+        let url = self.url("scale")?;
+        let body = WebhookScaleRequest {
+            cluster: self.cluster_name.clone(),
+            namespace: request.namespace.clone(),
+            deployment: request.name.clone(),
+            gpu: request.gpu,
+            replicas: request.size,
+        };
+        let mut headers = HeaderMap::new();
+        headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+        self.http_client
+            .send_request_with_retries(
+                &url,
+                Method::POST,
+                Some(headers),
+                Some(serde_json::to_vec(&body)?),
+            )
+            .await
+            .map_err(|err| anyhow::anyhow!("Failed sending scale request to {url}: {err:?}"))?;
+        tracing::info!(
+            "Scaled {}/{} to {} replica(s) via webhook.",
+            request.namespace,
+            request.name,
+            request.size
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use axum::{
+        extract::State,
+        routing::{get, post},
+        Json, Router,
+    };
+    use tokio::sync::Mutex;
+
+    use super::*;
+    use crate::cluster_types::Deployment;
+
+    #[derive(Clone, Default)]
+    struct MockWebhook {
+        state: Arc<Mutex<WebhookClusterState>>,
+        requests: Arc<Mutex<Vec<WebhookScaleRequest>>>,
+    }
+
+    async fn mock_cluster(State(mock): State<MockWebhook>) -> Json<WebhookClusterState> {
+        Json(mock.state.lock().await.clone())
+    }
+
+    async fn mock_scale(State(mock): State<MockWebhook>, Json(request): Json<WebhookScaleRequest>) {
+        let mut state = mock.state.lock().await;
+        let deployment = state
+            .namespaces
+            .entry(request.namespace.clone())
+            .or_default()
+            .deployments
+            .entry(request.deployment.clone())
+            .or_default();
+        deployment.desired = request.replicas;
+        mock.requests.lock().await.push(request);
+    }
+
+    async fn spawn_mock_webhook(mock: MockWebhook) -> Url {
+        let webhook = Router::new()
+            .route("/cluster", get(mock_cluster))
+            .route("/scale", post(mock_scale))
+            .with_state(mock);
+        let app = Router::new().nest("/autoscaler", webhook);
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        // Intentionally without the trailing slash.
+        format!("http://{addr}/autoscaler").parse().unwrap()
+    }
+
+    #[tokio::test]
+    async fn webhook_backend() {
+        let mock = MockWebhook::default();
+        mock.state.lock().await.namespaces.insert(
+            "prover-red".into(),
+            Namespace {
+                deployments: [(
+                    "circuit-prover-gpu".into(),
+                    Deployment {
+                        running: 1,
+                        desired: 2,
+                    },
+                )]
+                .into(),
+                ..Namespace::default()
+            },
+        );
+        let url = spawn_mock_webhook(mock.clone()).await;
+        let backend = WebhookBackend::new(
+            HttpClient::default(),
+            url,
+            "bare-metal".into(),
+            vec!["prover-blue".into(), "prover-red".into()],
+            false,
+        );
+
+        let cluster = backend.cluster().await.unwrap();
+        assert_eq!(cluster.name, ClusterName::from("bare-metal"));
+        assert_eq!(cluster.namespaces.len(), 2);
+        assert!(cluster.namespaces[&NamespaceName::from("prover-blue")]
+            .deployments
+            .is_empty());
+        let deployment = &cluster.namespaces[&NamespaceName::from("prover-red")].deployments
+            [&DeploymentName::from("circuit-prover-gpu")];
+        assert_eq!((deployment.running, deployment.desired), (1, 2));
+
+        backend
+            .scale(&ScaleDeploymentRequest {
+                namespace: "prover-red".into(),
+                name: "circuit-prover-gpu-t4".into(),
+                gpu: Some(Gpu::T4),
+                size: 3,
+            })
+            .await
+            .unwrap();
+        assert_eq!(
+            *mock.requests.lock().await,
+            [WebhookScaleRequest {
+                cluster: "bare-metal".into(),
+                namespace: "prover-red".into(),
+                deployment: "circuit-prover-gpu-t4".into(),
+                gpu: Some(Gpu::T4),
+                replicas: 3,
+            }]
+        );
+
+        let cluster = backend.cluster().await.unwrap();
+        let deployment = &cluster.namespaces[&NamespaceName::from("prover-red")].deployments
+            [&DeploymentName::from("circuit-prover-gpu-t4")];
+        assert_eq!(deployment.desired, 3);
+    }
+
+    #[tokio::test]
+    async fn webhook_backend_dry_run() {
+        let mock = MockWebhook::default();
+        let url = spawn_mock_webhook(mock.clone()).await;
+        let backend = WebhookBackend::new(
+            HttpClient::default(),
+            url,
+            "bare-metal".into(),
+            vec!["prover-red".into()],
+            true,
+        );
+
+        backend
+            .scale(&ScaleDeploymentRequest {
+                namespace: "prover-red".into(),
+                name: "witness-generator-basic-fri".into(),
+                gpu: None,
+                size: 5,
+            })
+            .await
+            .unwrap();
+        assert!(mock.requests.lock().await.is_empty());
+    }
+}
@@ -2,8 +2,8 @@ use clap::{command, Args, Parser, Subcommand};
 use zksync_types::url::SensitiveUrl;
 
 use crate::commands::{
-    config, debug_proof, delete, get_file_info, insert_batch, insert_version, requeue, restart,
-    stats, status::StatusCommand,
+    config, debug_proof, delete, get_file_info, insert_batch, insert_version, priority, requeue,
+    restart, stats, status::StatusCommand, timeline, verify,
 };
 
 pub const VERSION_STRING: &str = env!("CARGO_PKG_VERSION");
@@ -30,6 +30,9 @@ impl ProverCLI {
             ProverCommand::Stats(args) => stats::run(args, self.config).await?,
             ProverCommand::InsertVersion(args) => insert_version::run(args, self.config).await?,
             ProverCommand::InsertBatch(args) => insert_batch::run(args, self.config).await?,
+            ProverCommand::Timeline(args) => timeline::run(args, self.config).await?,
+            ProverCommand::Priority(args) => priority::run(args, self.config).await?,
+            ProverCommand::Verify(args) => verify::run(args, self.config).await?,
         };
         Ok(())
     }
@@ -62,4 +65,10 @@ pub enum ProverCommand {
     Stats(stats::Options),
     InsertVersion(insert_version::Args),
     InsertBatch(insert_batch::Args),
+    #[command(about = "Displays proving timeline and bottlenecks for L1 batches")]
+    Timeline(timeline::Args),
+    #[command(about = "Displays or sets priority lanes of L1 batches in prover job queues")]
+    Priority(priority::Args),
+    #[command(about = "Verifies scheduler FRI or compressed proofs against verification keys")]
+    Verify(verify::Args),
 }
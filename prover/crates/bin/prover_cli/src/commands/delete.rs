@@ -65,6 +65,10 @@ async fn delete_prover_db(mut conn: Connection<'_, Prover>) -> anyhow::Result<()
         .delete()
         .await
         .context("failed to delete witness generator")?;
+    conn.fri_batch_priority_dal()
+        .delete()
+        .await
+        .context("failed to delete batch priorities")?;
     Ok(())
 }
 
@@ -85,5 +89,9 @@ async fn delete_batch_data(
         .delete_batch_data(batch_id)
         .await
         .context("failed to delete witness generator data")?;
+    conn.fri_batch_priority_dal()
+        .delete_batch_data(batch_id)
+        .await
+        .context("failed to delete batch priority")?;
     Ok(())
 }
@@ -4,7 +4,10 @@ pub(crate) mod delete;
 pub(crate) mod get_file_info;
 pub(crate) mod insert_batch;
 pub(crate) mod insert_version;
+pub(crate) mod priority;
 pub(crate) mod requeue;
 pub(crate) mod restart;
 pub(crate) mod stats;
 pub mod status;
+pub(crate) mod timeline;
+pub(crate) mod verify;
@@ -0,0 +1,47 @@
+use anyhow::Context as _;
+use clap::Args as ClapArgs;
+use zksync_prover_dal::{ConnectionPool, Prover, ProverDal};
+use zksync_types::{prover_dal::BatchPriority, L1BatchId, L1BatchNumber, L2ChainId};
+
+use crate::cli::ProverCLIConfig;
+
+#[derive(ClapArgs)]
+pub struct Args {
+    /// Batches to get or set the priority lane for.
+    #[clap(short = 'n', num_args = 1.., required = true)]
+    batches: Vec<L1BatchNumber>,
+    /// Priority lane to move the batches to (`normal`, `high` or `urgent`). Jobs of batches in higher lanes
+    /// are picked first by all prover components. If not specified, current lanes are displayed.
+    #[clap(long)]
+    set: Option<BatchPriority>,
+}
+
+pub(crate) async fn run(args: Args, config: ProverCLIConfig) -> anyhow::Result<()> {
+    let prover_connection_pool = ConnectionPool::<Prover>::singleton(config.db_url)
+        .build()
+        .await
+        .context("failed to build a prover_connection_pool")?;
+    let mut conn = prover_connection_pool
+        .connection()
+        .await
+        .context("failed to get a connection")?;
+
+    for batch in args.batches {
+        let batch_id = L1BatchId::new(L2ChainId::zero(), batch);
+        if let Some(priority) = args.set {
+            conn.fri_batch_priority_dal()
+                .set_batch_priority(batch_id, priority)
+                .await
+                .context("failed to set batch priority")?;
+            println!("Batch {batch} moved to {priority} priority lane");
+        } else {
+            let priority = conn
+                .fri_batch_priority_dal()
+                .get_batch_priority(batch_id)
+                .await
+                .context("failed to get batch priority")?;
+            println!("Batch {batch}: {priority} priority lane");
+        }
+    }
+    Ok(())
+}
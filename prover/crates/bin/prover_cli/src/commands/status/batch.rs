@@ -134,7 +134,7 @@ async fn get_batches_data(
     Ok(batches_data)
 }
 
-async fn get_prover_jobs_info_for_batch<'a>(
+pub(crate) async fn get_prover_jobs_info_for_batch<'a>(
     batch_number: L1BatchNumber,
     aggregation_round: AggregationRound,
     conn: &mut Connection<'a, Prover>,
@@ -147,7 +147,7 @@ async fn get_prover_jobs_info_for_batch<'a>(
         .await
 }
 
-async fn get_proof_basic_witness_generator_into_for_batch<'a>(
+pub(crate) async fn get_proof_basic_witness_generator_into_for_batch<'a>(
     batch_number: L1BatchNumber,
     conn: &mut Connection<'a, Prover>,
 ) -> Option<BasicWitnessGeneratorJobInfo> {
@@ -156,7 +156,7 @@ async fn get_proof_basic_witness_generator_into_for_batch<'a>(
         .await
 }
 
-async fn get_proof_leaf_witness_generator_info_for_batch<'a>(
+pub(crate) async fn get_proof_leaf_witness_generator_info_for_batch<'a>(
     batch_number: L1BatchNumber,
     conn: &mut Connection<'a, Prover>,
 ) -> Vec<LeafWitnessGeneratorJobInfo> {
@@ -165,7 +165,7 @@ async fn get_proof_leaf_witness_generator_info_for_batch<'a>(
         .await
 }
 
-async fn get_proof_node_witness_generator_info_for_batch<'a>(
+pub(crate) async fn get_proof_node_witness_generator_info_for_batch<'a>(
     batch_number: L1BatchNumber,
     conn: &mut Connection<'a, Prover>,
 ) -> Vec<NodeWitnessGeneratorJobInfo> {
@@ -174,7 +174,7 @@ async fn get_proof_node_witness_generator_info_for_batch<'a>(
         .await
 }
 
-async fn get_proof_recursion_tip_witness_generator_info_for_batch<'a>(
+pub(crate) async fn get_proof_recursion_tip_witness_generator_info_for_batch<'a>(
     batch_number: L1BatchNumber,
     conn: &mut Connection<'a, Prover>,
 ) -> Option<RecursionTipWitnessGeneratorJobInfo> {
@@ -186,7 +186,7 @@ async fn get_proof_recursion_tip_witness_generator_info_for_batch<'a>(
         .await
 }
 
-async fn get_proof_scheduler_witness_generator_info_for_batch<'a>(
+pub(crate) async fn get_proof_scheduler_witness_generator_info_for_batch<'a>(
     batch_number: L1BatchNumber,
     conn: &mut Connection<'a, Prover>,
 ) -> Option<SchedulerWitnessGeneratorJobInfo> {
@@ -198,7 +198,7 @@ async fn get_proof_scheduler_witness_generator_info_for_batch<'a>(
         .await
 }
 
-async fn get_proof_compression_job_info_for_batch<'a>(
+pub(crate) async fn get_proof_compression_job_info_for_batch<'a>(
     batch_number: L1BatchNumber,
     conn: &mut Connection<'a, Prover>,
 ) -> Option<ProofCompressionJobInfo> {
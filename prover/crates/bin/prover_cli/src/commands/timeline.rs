@@ -0,0 +1,707 @@
+use std::collections::BTreeMap;
+
+use anyhow::Context as _;
+use chrono::{NaiveDateTime, NaiveTime, Utc};
+use clap::Args as ClapArgs;
+use colored::*;
+use serde::Serialize;
+use zksync_prover_dal::{Connection, ConnectionPool, Prover};
+use zksync_types::{
+    basic_fri_types::AggregationRound,
+    prover_dal::{ProofCompressionJobStatus, ProverJobFriInfo, ProverJobStatus, WitnessJobStatus},
+    L1BatchNumber,
+};
+
+use crate::{
+    cli::ProverCLIConfig,
+    commands::status::batch::{
+        get_proof_basic_witness_generator_into_for_batch, get_proof_compression_job_info_for_batch,
+        get_proof_leaf_witness_generator_info_for_batch,
+        get_proof_node_witness_generator_info_for_batch,
+        get_proof_recursion_tip_witness_generator_info_for_batch,
+        get_proof_scheduler_witness_generator_info_for_batch, get_prover_jobs_info_for_batch,
+    },
+};
+
+#[derive(ClapArgs)]
+pub struct Args {
+    /// Batches to reconstruct the timeline for.
+    #[clap(short = 'n', num_args = 1.., required_unless_present = "from")]
+    batches: Vec<L1BatchNumber>,
+    /// First batch of the range to reconstruct the timeline for (inclusive).
+    #[clap(long, requires = "to", conflicts_with = "batches")]
+    from: Option<L1BatchNumber>,
+    /// Last batch of the range to reconstruct the timeline for (inclusive).
+    #[clap(long, requires = "from")]
+    to: Option<L1BatchNumber>,
+    /// Output timelines as JSON instead of a text Gantt chart.
+    #[clap(long)]
+    json: bool,
+    /// Width of the Gantt chart bars in characters.
+    #[clap(long, default_value_t = 60)]
+    width: usize,
+}
+
+/// Proving stage. Stages are ordered according to their dependencies: each stage can only start
+/// after (some of) the jobs of the previous stage are finished.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum Stage {
+    BasicWitnessGeneration,
+    BasicCircuitsProving,
+    LeafWitnessGeneration,
+    LeafAggregationProving,
+    NodeWitnessGeneration,
+    NodeAggregationProving,
+    RecursionTipWitnessGeneration,
+    RecursionTipProving,
+    SchedulerWitnessGeneration,
+    SchedulerProving,
+    Compression,
+    GatewaySubmission,
+}
+
+impl Stage {
+    fn proving(round: AggregationRound) -> Self {
+        match round {
+            AggregationRound::BasicCircuits => Self::BasicCircuitsProving,
+            AggregationRound::LeafAggregation => Self::LeafAggregationProving,
+            AggregationRound::NodeAggregation => Self::NodeAggregationProving,
+            AggregationRound::RecursionTip => Self::RecursionTipProving,
+            AggregationRound::Scheduler => Self::SchedulerProving,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::BasicWitnessGeneration => "Basic WG",
+            Self::BasicCircuitsProving => "Basic proving",
+            Self::LeafWitnessGeneration => "Leaf WG",
+            Self::LeafAggregationProving => "Leaf proving",
+            Self::NodeWitnessGeneration => "Node WG",
+            Self::NodeAggregationProving => "Node proving",
+            Self::RecursionTipWitnessGeneration => "Recursion tip WG",
+            Self::RecursionTipProving => "Recursion tip proving",
+            Self::SchedulerWitnessGeneration => "Scheduler WG",
+            Self::SchedulerProving => "Scheduler proving",
+            Self::Compression => "Compression",
+            Self::GatewaySubmission => "Gateway submission",
+        }
+    }
+
+    fn label(self, circuit_id: Option<u32>) -> String {
+        match circuit_id {
+            Some(circuit_id) => format!("{} (circuit {circuit_id})", self.name()),
+            None => self.name().to_owned(),
+        }
+    }
+}
+
+/// Timing of a single job reconstructed from its DB row.
+#[derive(Debug, Clone)]
+struct JobTiming {
+    stage: Stage,
+    circuit_id: Option<u32>,
+    created_at: NaiveDateTime,
+    started_at: Option<NaiveDateTime>,
+    finished_at: Option<NaiveDateTime>,
+    attempts: u32,
+    successful: bool,
+}
+
+impl JobTiming {
+    #[allow(clippy::too_many_arguments)]
+    fn new(
+        stage: Stage,
+        circuit_id: Option<u32>,
+        created_at: NaiveDateTime,
+        updated_at: NaiveDateTime,
+        processing_started_at: Option<NaiveDateTime>,
+        time_taken: Option<NaiveTime>,
+        attempts: u32,
+        state: JobState,
+    ) -> Self {
+        let finished_at = match state {
+            JobState::Pending => None,
+            // `time_taken` is only recorded for successful jobs; fall back to the last update otherwise.
+            JobState::Successful => Some(
+                processing_started_at
+                    .zip(time_taken)
+                    .map_or(updated_at, |(started_at, time_taken)| {
+                        started_at + (time_taken - NaiveTime::MIN)
+                    }),
+            ),
+            JobState::Failed | JobState::Skipped => Some(updated_at),
+        };
+        Self {
+            stage,
+            circuit_id,
+            created_at,
+            started_at: processing_started_at,
+            finished_at,
+            attempts,
+            successful: state == JobState::Successful,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum JobState {
+    Pending,
+    Successful,
+    Failed,
+    Skipped,
+}
+
+impl From<&WitnessJobStatus> for JobState {
+    fn from(status: &WitnessJobStatus) -> Self {
+        match status {
+            WitnessJobStatus::Successful(_) => Self::Successful,
+            WitnessJobStatus::Failed(_) => Self::Failed,
+            WitnessJobStatus::Skipped => Self::Skipped,
+            WitnessJobStatus::WaitingForArtifacts
+            | WitnessJobStatus::WaitingForProofs
+            | WitnessJobStatus::InProgress
+            | WitnessJobStatus::Queued => Self::Pending,
+        }
+    }
+}
+
+impl From<&ProverJobStatus> for JobState {
+    fn from(status: &ProverJobStatus) -> Self {
+        match status {
+            ProverJobStatus::Successful(_) => Self::Successful,
+            ProverJobStatus::Failed(_) => Self::Failed,
+            ProverJobStatus::Skipped | ProverJobStatus::Ignored => Self::Skipped,
+            ProverJobStatus::Queued
+            | ProverJobStatus::InProgress(_)
+            | ProverJobStatus::InGPUProof => Self::Pending,
+        }
+    }
+}
+
+/// Group of jobs of the same stage (and circuit, for prover jobs) displayed as a single row of the Gantt chart.
+#[derive(Debug, Clone, Serialize)]
+struct TimelineSpan {
+    stage: Stage,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    circuit_id: Option<u32>,
+    jobs: usize,
+    successful_jobs: usize,
+    attempts: u32,
+    queued_at: NaiveDateTime,
+    /// `None` if none of the jobs is picked up yet.
+    started_at: Option<NaiveDateTime>,
+    /// `None` if some of the jobs are not finished yet.
+    finished_at: Option<NaiveDateTime>,
+}
+
+impl TimelineSpan {
+    fn label(&self) -> String {
+        self.stage.label(self.circuit_id)
+    }
+}
+
+/// Segment of the critical path: the last finished span of a stage.
+#[derive(Debug, Clone, Serialize)]
+struct CriticalPathSegment {
+    stage: Stage,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    circuit_id: Option<u32>,
+    /// Time between the end of the previous segment (or the batch being queued) and the start of processing.
+    wait_ms: i64,
+    /// Processing time of the segment; for unfinished segments, measured until now.
+    processing_ms: i64,
+    finished: bool,
+}
+
+impl CriticalPathSegment {
+    fn label(&self) -> String {
+        self.stage.label(self.circuit_id)
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct BatchTimeline {
+    l1_batch_number: L1BatchNumber,
+    started_at: Option<NaiveDateTime>,
+    finished_at: Option<NaiveDateTime>,
+    spans: Vec<TimelineSpan>,
+    critical_path: Vec<CriticalPathSegment>,
+}
+
+impl BatchTimeline {
+    fn new(l1_batch_number: L1BatchNumber, jobs: Vec<JobTiming>, now: NaiveDateTime) -> Self {
+        let mut groups = BTreeMap::<_, Vec<_>>::new();
+        for job in jobs {
+            groups
+                .entry((job.stage, job.circuit_id))
+                .or_default()
+                .push(job);
+        }
+        let spans: Vec<_> = groups
+            .into_iter()
+            .map(|((stage, circuit_id), jobs)| TimelineSpan {
+                stage,
+                circuit_id,
+                jobs: jobs.len(),
+                successful_jobs: jobs.iter().filter(|job| job.successful).count(),
+                attempts: jobs.iter().map(|job| job.attempts).sum(),
+                queued_at: jobs.iter().map(|job| job.created_at).min().unwrap(),
+                started_at: jobs.iter().filter_map(|job| job.started_at).min(),
+                finished_at: jobs
+                    .iter()
+                    .map(|job| job.finished_at)
+                    .collect::<Option<Vec<_>>>()
+                    .and_then(|finished| finished.into_iter().max()),
+            })
+            .collect();
+
+        let started_at = spans.iter().map(|span| span.queued_at).min();
+        let finished_at = spans
+            .iter()
+            .find(|span| span.stage == Stage::GatewaySubmission)
+            .and_then(|span| span.finished_at);
+        let critical_path = Self::critical_path(&spans, now);
+        Self {
+            l1_batch_number,
+            started_at,
+            finished_at,
+            spans,
+            critical_path,
+        }
+    }
+
+    /// Approximates the critical path by taking the span finishing last in each stage; since each stage waits
+    /// for the previous one, these spans determine the end-to-end proving latency.
+    fn critical_path(spans: &[TimelineSpan], now: NaiveDateTime) -> Vec<CriticalPathSegment> {
+        let mut last_spans = BTreeMap::<Stage, &TimelineSpan>::new();
+        for span in spans {
+            let entry = last_spans.entry(span.stage).or_insert(span);
+            if span.finished_at.unwrap_or(now) > entry.finished_at.unwrap_or(now) {
+                *entry = span;
+            }
+        }
+
+        let mut prev_finished_at = None;
+        let mut segments = vec![];
+        for span in last_spans.into_values() {
+            let ready_at = prev_finished_at
+                .unwrap_or(span.queued_at)
+                .max(span.queued_at);
+            let started_at = span.started_at.unwrap_or(now);
+            let finished_at = span.finished_at.unwrap_or(now);
+            segments.push(CriticalPathSegment {
+                stage: span.stage,
+                circuit_id: span.circuit_id,
+                wait_ms: (started_at - ready_at).num_milliseconds().max(0),
+                processing_ms: (finished_at - started_at).num_milliseconds().max(0),
+                finished: span.finished_at.is_some(),
+            });
+            prev_finished_at = Some(finished_at);
+        }
+        segments
+    }
+
+    /// Returns the longest wait or processing period on the critical path.
+    fn bottleneck(&self) -> Option<(&CriticalPathSegment, &'static str, i64)> {
+        self.critical_path
+            .iter()
+            .flat_map(|segment| {
+                [
+                    (segment, "waiting", segment.wait_ms),
+                    (segment, "processing", segment.processing_ms),
+                ]
+            })
+            .max_by_key(|(_, _, duration_ms)| *duration_ms)
+    }
+}
+
+pub(crate) async fn run(args: Args, config: ProverCLIConfig) -> anyhow::Result<()> {
+    let batches = match (args.from, args.to) {
+        (Some(from), Some(to)) => (from.0..=to.0).map(L1BatchNumber).collect(),
+        _ => args.batches,
+    };
+
+    let prover_connection_pool = ConnectionPool::<Prover>::singleton(config.db_url)
+        .build()
+        .await
+        .context("failed to build a prover_connection_pool")?;
+    let mut conn = prover_connection_pool
+        .connection()
+        .await
+        .context("failed to get a connection")?;
+
+    let now = Utc::now().naive_utc();
+    let mut timelines = Vec::with_capacity(batches.len());
+    for batch in batches {
+        let jobs = get_job_timings(batch, &mut conn).await;
+        timelines.push(BatchTimeline::new(batch, jobs, now));
+    }
+
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&timelines)?);
+        return Ok(());
+    }
+    for timeline in &timelines {
+        display_timeline(timeline, args.width, now);
+    }
+    if timelines.len() > 1 {
+        display_bottleneck_summary(&timelines);
+    }
+    Ok(())
+}
+
+async fn get_job_timings(
+    batch_number: L1BatchNumber,
+    conn: &mut Connection<'_, Prover>,
+) -> Vec<JobTiming> {
+    let mut jobs = vec![];
+
+    if let Some(job) = get_proof_basic_witness_generator_into_for_batch(batch_number, conn).await {
+        jobs.push(JobTiming::new(
+            Stage::BasicWitnessGeneration,
+            None,
+            job.created_at,
+            job.updated_at,
+            job.processing_started_at,
+            job.time_taken,
+            job.attempts,
+            (&job.status).into(),
+        ));
+    }
+    for job in get_proof_leaf_witness_generator_info_for_batch(batch_number, conn).await {
+        jobs.push(JobTiming::new(
+            Stage::LeafWitnessGeneration,
+            None,
+            job.created_at,
+            job.updated_at,
+            job.processing_started_at,
+            job.time_taken,
+            job.attempts,
+            (&job.status).into(),
+        ));
+    }
+    for job in get_proof_node_witness_generator_info_for_batch(batch_number, conn).await {
+        jobs.push(JobTiming::new(
+            Stage::NodeWitnessGeneration,
+            None,
+            job.created_at,
+            job.updated_at,
+            job.processing_started_at,
+            job.time_taken,
+            job.attempts,
+            (&job.status).into(),
+        ));
+    }
+    if let Some(job) =
+        get_proof_recursion_tip_witness_generator_info_for_batch(batch_number, conn).await
+    {
+        jobs.push(JobTiming::new(
+            Stage::RecursionTipWitnessGeneration,
+            None,
+            job.created_at,
+            job.updated_at,
+            job.processing_started_at,
+            job.time_taken,
+            job.attempts,
+            (&job.status).into(),
+        ));
+    }
+    if let Some(job) =
+        get_proof_scheduler_witness_generator_info_for_batch(batch_number, conn).await
+    {
+        jobs.push(JobTiming::new(
+            Stage::SchedulerWitnessGeneration,
+            None,
+            job.created_at,
+            job.updated_at,
+            job.processing_started_at,
+            job.time_taken,
+            job.attempts,
+            (&job.status).into(),
+        ));
+    }
+
+    for round in [
+        AggregationRound::BasicCircuits,
+        AggregationRound::LeafAggregation,
+        AggregationRound::NodeAggregation,
+        AggregationRound::RecursionTip,
+        AggregationRound::Scheduler,
+    ] {
+        let prover_jobs = get_prover_jobs_info_for_batch(batch_number, round, conn).await;
+        jobs.extend(prover_jobs.iter().map(prover_job_timing));
+    }
+
+    if let Some(job) = get_proof_compression_job_info_for_batch(batch_number, conn).await {
+        let sent_to_server = job.status == ProofCompressionJobStatus::SentToServer;
+        let state = match job.status {
+            ProofCompressionJobStatus::Successful | ProofCompressionJobStatus::SentToServer => {
+                JobState::Successful
+            }
+            ProofCompressionJobStatus::Failed => JobState::Failed,
+            ProofCompressionJobStatus::Skipped => JobState::Skipped,
+            ProofCompressionJobStatus::Queued | ProofCompressionJobStatus::InProgress => {
+                JobState::Pending
+            }
+        };
+        let compression = JobTiming::new(
+            Stage::Compression,
+            None,
+            job.created_at,
+            job.updated_at,
+            job.processing_started_at,
+            job.time_taken,
+            job.attempts,
+            state,
+        );
+
+        // The proof is picked up by the prover gateway once compressed; the job is updated
+        // when the gateway submits the proof to the server.
+        if let Some(compressed_at) = compression.finished_at.filter(|_| compression.successful) {
+            jobs.push(JobTiming {
+                stage: Stage::GatewaySubmission,
+                circuit_id: None,
+                created_at: compressed_at,
+                started_at: sent_to_server.then_some(job.updated_at),
+                finished_at: sent_to_server.then_some(job.updated_at),
+                attempts: 0,
+                successful: sent_to_server,
+            });
+        }
+        jobs.push(compression);
+    }
+    jobs
+}
+
+fn prover_job_timing(job: &ProverJobFriInfo) -> JobTiming {
+    JobTiming::new(
+        Stage::proving(job.aggregation_round),
+        Some(job.circuit_id),
+        job.created_at,
+        job.updated_at,
+        job.processing_started_at,
+        job.time_taken,
+        job.attempts.into(),
+        (&job.status).into(),
+    )
+}
+
+fn format_duration_ms(duration_ms: i64) -> String {
+    let secs = duration_ms / 1_000;
+    match secs {
+        0..60 => format!("{}.{:01}s", secs, duration_ms % 1_000 / 100),
+        60..3_600 => format!("{}m{:02}s", secs / 60, secs % 60),
+        _ => format!("{}h{:02}m", secs / 3_600, secs % 3_600 / 60),
+    }
+}
+
+fn display_timeline(timeline: &BatchTimeline, width: usize, now: NaiveDateTime) {
+    println!(
+        "== {} ==",
+        format!("Batch {} Timeline", timeline.l1_batch_number).bold()
+    );
+    let Some(started_at) = timeline.started_at else {
+        println!("> No batch found. 🚫");
+        return;
+    };
+    let finished_at = timeline.finished_at.unwrap_or(now);
+    let total_ms = (finished_at - started_at).num_milliseconds().max(1);
+    let column = |time: NaiveDateTime| {
+        let offset_ms = (time - started_at).num_milliseconds().clamp(0, total_ms);
+        (offset_ms as u128 * width as u128 / total_ms as u128) as usize
+    };
+
+    let label_width = timeline
+        .spans
+        .iter()
+        .map(|span| span.label().len())
+        .max()
+        .unwrap_or(0);
+    for span in &timeline.spans {
+        let queued_col = column(span.queued_at);
+        let started_col = span.started_at.map_or(width, column);
+        let finished_col = span.finished_at.map_or(width, column).max(started_col);
+        let mut bar = String::with_capacity(width);
+        bar.extend((0..queued_col).map(|_| ' '));
+        bar.extend((queued_col..started_col).map(|_| '.'));
+        let processing_char = if span.finished_at.is_some() { '#' } else { '>' };
+        bar.extend(
+            (started_col..finished_col.max(started_col + 1).min(width)).map(|_| processing_char),
+        );
+        bar.extend((bar.chars().count()..width).map(|_| ' '));
+
+        let duration = match (span.started_at, span.finished_at) {
+            (Some(started_at), Some(finished_at)) => {
+                format_duration_ms((finished_at - started_at).num_milliseconds())
+            }
+            (Some(_), None) => "in progress".to_owned(),
+            (None, _) => "queued".to_owned(),
+        };
+        println!(
+            "{:label_width$} |{bar}| {duration} ({}/{} jobs, {} attempts)",
+            span.label(),
+            span.successful_jobs,
+            span.jobs,
+            span.attempts
+        );
+    }
+
+    let status = if timeline.finished_at.is_some() {
+        "proof sent to server"
+    } else {
+        "in progress"
+    };
+    println!(
+        "Total: {} ({status}); '.' = queued, '#' = processing, '>' = still processing",
+        format_duration_ms(total_ms)
+    );
+
+    println!("{}", "Critical path:".bold());
+    for segment in &timeline.critical_path {
+        println!(
+            "  {:label_width$} wait {:>8}  processing {:>8}{}",
+            segment.label(),
+            format_duration_ms(segment.wait_ms),
+            format_duration_ms(segment.processing_ms),
+            if segment.finished {
+                ""
+            } else {
+                " (unfinished)"
+            }
+        );
+    }
+    if let Some((segment, kind, duration_ms)) = timeline.bottleneck() {
+        println!(
+            "{} {kind} for {} ({}, {}% of total)\n",
+            "Bottleneck:".bold(),
+            segment.label(),
+            format_duration_ms(duration_ms),
+            duration_ms * 100 / total_ms
+        );
+    }
+}
+
+fn display_bottleneck_summary(timelines: &[BatchTimeline]) {
+    let mut bottlenecks = BTreeMap::<(Stage, &str), (usize, i64)>::new();
+    for timeline in timelines {
+        if let Some((segment, kind, duration_ms)) = timeline.bottleneck() {
+            let entry = bottlenecks.entry((segment.stage, kind)).or_default();
+            entry.0 += 1;
+            entry.1 += duration_ms;
+        }
+    }
+
+    println!("== {} ==", "Bottleneck summary".bold());
+    let mut bottlenecks: Vec<_> = bottlenecks.into_iter().collect();
+    bottlenecks.sort_by_key(|(_, (count, _))| std::cmp::Reverse(*count));
+    for ((stage, kind), (count, total_ms)) in bottlenecks {
+        println!(
+            "{} {kind}: bottleneck for {count} batch(es), {} on average",
+            stage.name(),
+            format_duration_ms(total_ms / count as i64)
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::DateTime;
+
+    use super::*;
+
+    fn time(secs: i64) -> NaiveDateTime {
+        DateTime::from_timestamp(secs, 0).unwrap().naive_utc()
+    }
+
+    fn job(stage: Stage, circuit_id: Option<u32>, times: (i64, i64, Option<i64>)) -> JobTiming {
+        let (created_at, started_at, finished_at) = times;
+        JobTiming {
+            stage,
+            circuit_id,
+            created_at: time(created_at),
+            started_at: Some(time(started_at)),
+            finished_at: finished_at.map(time),
+            attempts: 1,
+            successful: finished_at.is_some(),
+        }
+    }
+
+    #[test]
+    fn job_timing_uses_time_taken() {
+        let timing = JobTiming::new(
+            Stage::Compression,
+            None,
+            time(0),
+            time(100),
+            Some(time(10)),
+            NaiveTime::from_num_seconds_from_midnight_opt(30, 0),
+            1,
+            JobState::Successful,
+        );
+        assert_eq!(timing.finished_at, Some(time(40)));
+
+        let timing = JobTiming::new(
+            Stage::Compression,
+            None,
+            time(0),
+            time(100),
+            Some(time(10)),
+            None,
+            3,
+            JobState::Failed,
+        );
+        assert_eq!(timing.finished_at, Some(time(100)));
+    }
+
+    #[test]
+    fn building_timeline() {
+        let jobs = vec![
+            job(Stage::BasicWitnessGeneration, None, (0, 5, Some(20))),
+            job(Stage::BasicCircuitsProving, Some(1), (20, 25, Some(40))),
+            job(Stage::BasicCircuitsProving, Some(1), (20, 30, Some(50))),
+            job(Stage::BasicCircuitsProving, Some(3), (20, 22, Some(200))),
+            job(Stage::LeafWitnessGeneration, None, (200, 210, Some(220))),
+            job(Stage::LeafAggregationProving, Some(3), (220, 230, None)),
+        ];
+        let timeline = BatchTimeline::new(L1BatchNumber(1), jobs, time(300));
+
+        assert_eq!(timeline.spans.len(), 5);
+        let basic_span = &timeline.spans[1];
+        assert_eq!(basic_span.circuit_id, Some(1));
+        assert_eq!(basic_span.jobs, 2);
+        assert_eq!(basic_span.started_at, Some(time(25)));
+        assert_eq!(basic_span.finished_at, Some(time(50)));
+        assert_eq!(timeline.started_at, Some(time(0)));
+        assert_eq!(timeline.finished_at, None);
+
+        let path: Vec<_> = timeline
+            .critical_path
+            .iter()
+            .map(|segment| {
+                (
+                    segment.stage,
+                    segment.circuit_id,
+                    segment.wait_ms / 1_000,
+                    segment.processing_ms / 1_000,
+                )
+            })
+            .collect();
+        assert_eq!(
+            path,
+            [
+                (Stage::BasicWitnessGeneration, None, 5, 15),
+                (Stage::BasicCircuitsProving, Some(3), 2, 178),
+                (Stage::LeafWitnessGeneration, None, 10, 10),
+                (Stage::LeafAggregationProving, Some(3), 10, 70),
+            ]
+        );
+        assert!(!timeline.critical_path[3].finished);
+
+        let (segment, kind, duration_ms) = timeline.bottleneck().unwrap();
+        assert_eq!(segment.stage, Stage::BasicCircuitsProving);
+        assert_eq!(kind, "processing");
+        assert_eq!(duration_ms, 178_000);
+    }
+}
@@ -0,0 +1,351 @@
+use std::{path::PathBuf, str::FromStr};
+
+use anyhow::Context as _;
+use clap::Args as ClapArgs;
+use colored::Colorize;
+use zksync_config::{
+    configs::GeneralConfig, full_config_schema, sources::ConfigFilePaths, ConfigRepositoryExt,
+};
+use zksync_object_store::{ObjectStore, ObjectStoreFactory, StoredObject};
+use zksync_prover_dal::{Connection, ConnectionPool, Prover, ProverDal};
+use zksync_prover_fri_types::{
+    circuit_definitions::circuit_definitions::recursion_layer::ZkSyncRecursionLayerProof,
+    FriProofWrapper,
+};
+use zksync_prover_interface::outputs::{
+    L1BatchProofForL1, L1BatchProofForL1Key, TypedL1BatchProofForL1,
+};
+use zksync_prover_keystore::{keystore::Keystore, VkCommitments};
+use zksync_types::{
+    protocol_version::{L1VerifierConfig, ProtocolSemanticVersion},
+    L1BatchId, L1BatchNumber, L2ChainId, H256,
+};
+
+use crate::cli::ProverCLIConfig;
+
+#[derive(ClapArgs)]
+pub struct Args {
+    /// Batch to verify the proof of. The proof is loaded from the object store.
+    #[clap(
+        short = 'n',
+        long,
+        required_unless_present = "file",
+        conflicts_with = "file"
+    )]
+    batch: Option<L1BatchNumber>,
+    /// File with a scheduler FRI proof or a compressed (Plonk/Fflonk) proof.
+    #[clap(short, long)]
+    file: Option<PathBuf>,
+    /// Verify the scheduler FRI proof of the batch instead of the compressed one.
+    #[clap(long, requires = "batch")]
+    fri: bool,
+    /// Path to the general config file with the prover object store config (`prover.prover_object_store`)
+    /// to load the proof from. The config can also be set with `ZKSYNC_` env vars.
+    #[clap(long)]
+    config_path: Option<PathBuf>,
+    /// Path to the directory with verification keys. If not specified, keys from the workspace
+    /// are used.
+    #[clap(long)]
+    keys_path: Option<PathBuf>,
+    /// Protocol version to check verification keys against. If not specified, taken from the proof.
+    #[clap(long)]
+    protocol_version: Option<ProtocolSemanticVersion>,
+}
+
+enum Proof {
+    Fri(ZkSyncRecursionLayerProof),
+    Compressed(L1BatchProofForL1),
+}
+
+impl Proof {
+    fn from_bytes(bytes: Vec<u8>) -> anyhow::Result<Self> {
+        if let Ok(proof) = bincode::deserialize::<FriProofWrapper>(&bytes) {
+            return Self::from_fri_wrapper(proof);
+        }
+        if let Ok(proof) = <L1BatchProofForL1 as StoredObject>::deserialize(bytes.clone()) {
+            return Ok(Self::Compressed(proof));
+        }
+        bincode::deserialize::<L1BatchProofForL1>(&bytes)
+            .map(Self::Compressed)
+            .context("File doesn't contain a scheduler FRI proof or a compressed proof")
+    }
+
+    fn from_fri_wrapper(proof: FriProofWrapper) -> anyhow::Result<Self> {
+        match proof {
+            FriProofWrapper::Base(_) => anyhow::bail!("Must be a scheduler proof not base layer"),
+            FriProofWrapper::Recursive(proof) => Ok(Self::Fri(proof)),
+        }
+    }
+
+    fn vk(&self) -> ProofVk {
+        match self {
+            Self::Fri(_) => ProofVk::Scheduler,
+            Self::Compressed(proof) => match proof.inner() {
+                TypedL1BatchProofForL1::Plonk(_) => ProofVk::SnarkWrapper,
+                TypedL1BatchProofForL1::Fflonk(_) => ProofVk::FflonkSnarkWrapper,
+            },
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            Self::Fri(_) => "scheduler FRI",
+            Self::Compressed(proof) => match proof.inner() {
+                TypedL1BatchProofForL1::Plonk(_) => "Plonk",
+                TypedL1BatchProofForL1::Fflonk(_) => "Fflonk",
+            },
+        }
+    }
+}
+
+/// Verification key a proof is verified with.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ProofVk {
+    Scheduler,
+    SnarkWrapper,
+    FflonkSnarkWrapper,
+}
+
+impl ProofVk {
+    fn name(self) -> &'static str {
+        match self {
+            Self::Scheduler => "Scheduler",
+            Self::SnarkWrapper | Self::FflonkSnarkWrapper => "SNARK wrapper",
+        }
+    }
+
+    fn hash(self, commitments: &VkCommitments) -> anyhow::Result<H256> {
+        let hash = match self {
+            Self::Scheduler => &commitments.scheduler,
+            Self::SnarkWrapper => &commitments.snark_wrapper,
+            Self::FflonkSnarkWrapper => &commitments.fflonk_snark_wrapper,
+        };
+        H256::from_str(hash).with_context(|| format!("invalid {} VK hash", self.name()))
+    }
+}
+
+/// Result of checking that a proof was made with the verification key of a protocol version.
+#[derive(Debug, PartialEq)]
+enum VkCheck {
+    Matches,
+    Mismatch {
+        expected: Option<H256>,
+    },
+    /// The protocol version has no VK hash to compare with.
+    Unverifiable,
+}
+
+/// Checks the VK a valid proof was made with against the protocol version. The prover DB only stores
+/// SNARK wrapper VK hashes, so scheduler FRI proofs cannot be checked.
+fn check_vk(
+    vk: ProofVk,
+    commitments: &VkCommitments,
+    expected: &L1VerifierConfig,
+) -> anyhow::Result<VkCheck> {
+    let expected = match vk {
+        ProofVk::Scheduler => return Ok(VkCheck::Unverifiable),
+        ProofVk::SnarkWrapper => Some(expected.snark_wrapper_vk_hash),
+        ProofVk::FflonkSnarkWrapper => expected.fflonk_snark_wrapper_vk_hash,
+    };
+    Ok(if expected == Some(vk.hash(commitments)?) {
+        VkCheck::Matches
+    } else {
+        VkCheck::Mismatch { expected }
+    })
+}
+
+pub(crate) async fn run(args: Args, config: ProverCLIConfig) -> anyhow::Result<()> {
+    let prover_connection_pool = ConnectionPool::<Prover>::singleton(config.db_url)
+        .build()
+        .await
+        .context("failed to build a prover_connection_pool")?;
+    let mut conn = prover_connection_pool
+        .connection()
+        .await
+        .context("failed to get a connection")?;
+
+    let proof = match (&args.file, args.batch) {
+        (Some(path), _) => {
+            let bytes = std::fs::read(path)
+                .with_context(|| format!("Failed to read proof from {}", path.display()))?;
+            Proof::from_bytes(bytes)?
+        }
+        (None, Some(batch)) => {
+            let config_sources = ConfigFilePaths {
+                general: args.config_path.clone(),
+                ..ConfigFilePaths::default()
+            }
+            .into_config_sources("ZKSYNC_")?;
+            let schema = full_config_schema(false);
+            let general_config: GeneralConfig = config_sources.build_repository(&schema).parse()?;
+            let prover_config = general_config.prover_config.context("prover config")?;
+            let object_store = ObjectStoreFactory::new(prover_config.prover_object_store)
+                .create_store()
+                .await
+                .context("failed to create object store")?;
+            let batch_id = L1BatchId::new(L2ChainId::zero(), batch);
+            load_proof(&mut conn, object_store.as_ref(), batch_id, args.fri).await?
+        }
+        (None, None) => unreachable!("enforced by clap"),
+    };
+
+    let protocol_version = match (&proof, args.protocol_version) {
+        (_, Some(protocol_version)) => Some(protocol_version),
+        (Proof::Compressed(proof), None) => Some(proof.protocol_version()),
+        (Proof::Fri(_), None) => None,
+    };
+
+    let keystore = args.keys_path.map_or_else(Keystore::locate, Keystore::new);
+    let commitments = keystore
+        .generate_commitments()
+        .context("failed to generate VK commitments")?;
+
+    println!("Proof type: {}", proof.name());
+    let verified = match &proof {
+        Proof::Fri(proof) => keystore.verify_scheduler_proof(proof)?,
+        Proof::Compressed(proof) => match proof.inner() {
+            TypedL1BatchProofForL1::Plonk(proof) => {
+                keystore.verify_plonk_snark_proof(&proof.scheduler_proof)?
+            }
+            TypedL1BatchProofForL1::Fflonk(proof) => {
+                keystore.verify_fflonk_snark_proof(&proof.scheduler_proof)?
+            }
+        },
+    };
+    let vk = proof.vk();
+    let vk_hash = vk.hash(&commitments)?;
+    println!("{} VK hash: {vk_hash:?}", vk.name());
+
+    let vk_check = match protocol_version {
+        Some(protocol_version) => {
+            let expected = conn
+                .fri_protocol_versions_dal()
+                .vk_commitments_for(protocol_version)
+                .await
+                .with_context(|| format!("Protocol version {protocol_version} is not found"))?;
+            check_vk(vk, &commitments, &expected)?
+        }
+        None => VkCheck::Unverifiable,
+    };
+    match &vk_check {
+        VkCheck::Matches => println!(
+            "Protocol version VK hash: {vk_hash:?} ({})",
+            "matches".green()
+        ),
+        VkCheck::Mismatch { expected } => println!(
+            "Protocol version VK hash: {} ({})",
+            expected.map_or("missing".to_owned(), |hash| format!("{hash:?}")),
+            "doesn't match".red()
+        ),
+        VkCheck::Unverifiable => println!(
+            "Protocol version VK hash: {} (the prover DB has no {} VK hashes)",
+            "unverifiable".yellow(),
+            vk.name()
+        ),
+    }
+
+    if verified {
+        println!("Verification: {}", "OK".green());
+    } else {
+        println!("Verification: {}", "FAILED".red());
+    }
+    anyhow::ensure!(verified, "Proof verification failed");
+    anyhow::ensure!(
+        !matches!(vk_check, VkCheck::Mismatch { .. }),
+        "Verification keys don't match the protocol version"
+    );
+    anyhow::ensure!(
+        vk_check != VkCheck::Unverifiable,
+        "Proof is unverifiable: it cannot be checked that it was made with verification keys of the protocol version"
+    );
+    Ok(())
+}
+
+async fn load_proof(
+    conn: &mut Connection<'_, Prover>,
+    object_store: &dyn ObjectStore,
+    batch_id: L1BatchId,
+    fri: bool,
+) -> anyhow::Result<Proof> {
+    if fri {
+        let fri_proof_id = conn
+            .fri_prover_jobs_dal()
+            .get_scheduler_proof_job_id(batch_id)
+            .await
+            .with_context(|| format!("Scheduler proof is missing from database for {batch_id}"))?;
+        let proof: FriProofWrapper = object_store
+            .get((fri_proof_id, batch_id.chain_id()))
+            .await
+            .with_context(|| format!("Failed to get FRI proof with id {fri_proof_id}"))?;
+        return Proof::from_fri_wrapper(proof);
+    }
+
+    let protocol_version = conn
+        .fri_witness_generator_dal()
+        .protocol_version_for_l1_batch(batch_id)
+        .await
+        .with_context(|| format!("Protocol version is unknown for {batch_id}"))?;
+    let proof: L1BatchProofForL1 = object_store
+        .get(L1BatchProofForL1Key::Prover((batch_id, protocol_version)))
+        .await
+        .with_context(|| format!("Failed to get compressed proof for {batch_id}"))?;
+    Ok(Proof::Compressed(proof))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn commitments() -> VkCommitments {
+        VkCommitments {
+            leaf: format!("{:?}", H256::repeat_byte(1)),
+            node: format!("{:?}", H256::repeat_byte(2)),
+            scheduler: format!("{:?}", H256::repeat_byte(3)),
+            snark_wrapper: format!("{:?}", H256::repeat_byte(4)),
+            fflonk_snark_wrapper: format!("{:?}", H256::repeat_byte(5)),
+        }
+    }
+
+    #[test]
+    fn fri_proofs_are_unverifiable() {
+        let commitments = commitments();
+        // The SNARK wrapper VK hash matches, but it says nothing about the VK of a scheduler FRI proof.
+        let expected = L1VerifierConfig {
+            snark_wrapper_vk_hash: H256::repeat_byte(4),
+            fflonk_snark_wrapper_vk_hash: Some(H256::repeat_byte(5)),
+        };
+        assert_eq!(
+            ProofVk::Scheduler.hash(&commitments).unwrap(),
+            H256::repeat_byte(3)
+        );
+        let check = check_vk(ProofVk::Scheduler, &commitments, &expected).unwrap();
+        assert_eq!(check, VkCheck::Unverifiable);
+    }
+
+    #[test]
+    fn snark_proofs_are_checked_against_protocol_version() {
+        let commitments = commitments();
+        let expected = L1VerifierConfig {
+            snark_wrapper_vk_hash: H256::repeat_byte(4),
+            fflonk_snark_wrapper_vk_hash: None,
+        };
+        let check = check_vk(ProofVk::SnarkWrapper, &commitments, &expected).unwrap();
+        assert_eq!(check, VkCheck::Matches);
+        let check = check_vk(ProofVk::FflonkSnarkWrapper, &commitments, &expected).unwrap();
+        assert_eq!(check, VkCheck::Mismatch { expected: None });
+
+        let expected = L1VerifierConfig {
+            snark_wrapper_vk_hash: H256::repeat_byte(0xff),
+            fflonk_snark_wrapper_vk_hash: Some(H256::repeat_byte(5)),
+        };
+        let check = check_vk(ProofVk::SnarkWrapper, &commitments, &expected).unwrap();
+        assert_eq!(
+            check,
+            VkCheck::Mismatch {
+                expected: Some(H256::repeat_byte(0xff))
+            }
+        );
+        let check = check_vk(ProofVk::FflonkSnarkWrapper, &commitments, &expected).unwrap();
+        assert_eq!(check, VkCheck::Matches);
+    }
+}
@@ -1,4 +1,9 @@
+use std::time::Duration;
+
+use secrecy::ExposeSecret;
 use serde::{de::DeserializeOwned, Serialize};
+use zksync_prover_interface::long_poll;
+use zksync_types::secrets::APIKey;
 
 pub mod proof_gen_data_fetcher;
 pub mod proof_submitter;
@@ -9,6 +14,7 @@ pub mod proof_submitter;
 pub(crate) struct ProverApiClient {
     pub(crate) api_url: String,
     pub(crate) client: reqwest::Client,
+    pub(crate) auth_token: Option<APIKey>,
 }
 
 impl ProverApiClient {
@@ -16,7 +22,42 @@ impl ProverApiClient {
         Self {
             api_url,
             client: reqwest::Client::new(),
+            auth_token: None,
+        }
+    }
+
+    pub(crate) fn with_auth_token(mut self, auth_token: Option<APIKey>) -> Self {
+        self.auth_token = auth_token;
+        self
+    }
+
+    /// Sends a long-poll request, which the server holds for up to `timeout`.
+    pub(crate) async fn send_long_poll_request<Req, Resp>(
+        &self,
+        request: Req,
+        endpoint: &str,
+        timeout: Duration,
+    ) -> Result<Resp, reqwest::Error>
+    where
+        Req: Serialize,
+        Resp: DeserializeOwned,
+    {
+        tracing::info!("Sending long-poll request to {}", endpoint);
+
+        let mut builder = self
+            .client
+            .post(endpoint)
+            .json(&request)
+            .timeout(timeout + long_poll::RESPONSE_MARGIN);
+        if let Some(token) = &self.auth_token {
+            builder = builder.bearer_auth(token.expose_secret());
         }
+        builder
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<Resp>()
+            .await
     }
 
     pub(crate) async fn send_http_request<Req, Resp>(
@@ -1,33 +1,57 @@
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 
 use async_trait::async_trait;
+use zksync_config::configs::FriProverGatewayConfig;
 use zksync_object_store::ObjectStore;
 use zksync_prover_dal::{ConnectionPool, Prover};
-use zksync_prover_interface::api::{ProofGenerationDataRequest, ProofGenerationDataResponse};
+use zksync_prover_interface::{
+    api::{
+        ProofGenerationDataRequest, ProofGenerationDataResponse, WaitForProofGenerationDataRequest,
+    },
+    long_poll::PushMode,
+};
+use zksync_types::secrets::APIKey;
 
 use crate::{client::ProverApiClient, proof_data_manager::ProofDataManager, traits::PeriodicApi};
 
 /// Poller structure that will periodically check the prover API for new proof generation data.
 /// Fetched data is stored to the database/object store for further processing.
+///
+/// In push mode, the data is awaited with long-poll requests, falling back to polling if the
+/// prover API doesn't support them.
 #[derive(Debug)]
 pub struct ProofGenDataFetcher {
     manager: ProofDataManager,
     client: ProverApiClient,
+    wait_url: String,
+    push_mode: PushMode,
+    long_poll_timeout: Duration,
 }
 
 /// The path to the API endpoint that returns the next proof generation data.
 const PROOF_GENERATION_DATA_PATH: &str = "/proof_generation_data";
+/// The path to the API endpoint that waits for the next proof generation data.
+const WAIT_FOR_PROOF_GENERATION_DATA_PATH: &str = "/wait_for_proof_generation_data";
 
 impl ProofGenDataFetcher {
     pub(crate) fn new(
         blob_store: Arc<dyn ObjectStore>,
-        base_url: String,
+        config: &FriProverGatewayConfig,
+        push_auth_token: Option<APIKey>,
         pool: ConnectionPool<Prover>,
     ) -> Self {
+        let base_url = &config.api_url;
         let api_url = format!("{base_url}{PROOF_GENERATION_DATA_PATH}");
-        let client = ProverApiClient::new(api_url);
+        let wait_url = format!("{base_url}{WAIT_FOR_PROOF_GENERATION_DATA_PATH}");
+        let client = ProverApiClient::new(api_url).with_auth_token(push_auth_token);
         let manager = ProofDataManager::new(blob_store.clone(), pool.clone());
-        Self { manager, client }
+        Self {
+            manager,
+            client,
+            wait_url,
+            push_mode: PushMode::new(config.push_mode),
+            long_poll_timeout: config.long_poll_timeout_secs,
+        }
     }
 }
 
@@ -50,9 +74,30 @@ impl PeriodicApi for ProofGenDataFetcher {
         _: (),
         request: ProofGenerationDataRequest,
     ) -> reqwest::Result<Self::Response> {
-        self.client
-            .send_http_request(request, &self.client.api_url)
-            .await
+        if !self.is_long_poll() {
+            return self
+                .client
+                .send_http_request(request, &self.client.api_url)
+                .await;
+        }
+
+        let request = WaitForProofGenerationDataRequest {
+            timeout_secs: self.long_poll_timeout.as_secs(),
+        };
+        let response = self
+            .client
+            .send_long_poll_request(request, &self.wait_url, self.long_poll_timeout)
+            .await;
+        if let Err(err) = &response {
+            if self.push_mode.handle_error_status(err.status()) {
+                tracing::warn!("Prover API doesn't support long-polling, falling back to polling");
+            }
+        }
+        response
+    }
+
+    fn is_long_poll(&self) -> bool {
+        self.push_mode.is_enabled()
     }
 
     async fn handle_response(&self, _: (), response: Self::Response) -> anyhow::Result<()> {
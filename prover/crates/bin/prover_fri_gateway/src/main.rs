@@ -7,7 +7,9 @@ use proof_data_manager::ProofDataManager;
 use tokio::sync::{oneshot, watch};
 use traits::PeriodicApi as _;
 use zksync_config::{
-    configs::{fri_prover_gateway::ApiMode, DatabaseSecrets, GeneralConfig},
+    configs::{
+        fri_prover_gateway::ApiMode, DatabaseSecrets, GeneralConfig, ProofDataHandlerSecrets,
+    },
     full_config_schema,
     sources::ConfigFilePaths,
     ConfigRepositoryExt,
@@ -40,10 +42,15 @@ async fn main() -> anyhow::Result<()> {
     let repo = config_sources.build_repository(&schema);
     let general_config: GeneralConfig = repo.parse()?;
     let database_secrets: DatabaseSecrets = repo.parse()?;
+    let push_auth_token = repo.parse::<ProofDataHandlerSecrets>()?.push_auth_token;
 
     let config = general_config
         .prover_gateway
         .context("prover gateway config")?;
+    anyhow::ensure!(
+        !(config.api_mode == ApiMode::Legacy && config.push_mode && push_auth_token.is_none()),
+        "push mode requires `proof_data_handler.push_auth_token` secret"
+    );
 
     let postgres_config = general_config.postgres_config;
     let pool = ConnectionPool::<Prover>::builder(
@@ -81,7 +88,8 @@ async fn main() -> anyhow::Result<()> {
             );
             let proof_gen_data_fetcher = ProofGenDataFetcher::new(
                 store_factory.create_store().await?,
-                config.api_url.clone(),
+                &config,
+                push_auth_token,
                 pool,
             );
 
@@ -104,7 +112,7 @@ async fn main() -> anyhow::Result<()> {
 
             let processor = ProofDataManager::new(store_factory.create_store().await?, pool);
 
-            let api = server::Api::new(processor.clone(), port);
+            let api = server::Api::new(processor.clone(), port, push_auth_token);
 
             vec![
                 tokio::spawn(
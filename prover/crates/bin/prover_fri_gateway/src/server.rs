@@ -1,16 +1,22 @@
-use std::net::SocketAddr;
+use std::{net::SocketAddr, sync::Arc, time::Duration};
 
 use anyhow::Context as _;
 use axum::{
     extract::{DefaultBodyLimit, State},
+    http::{HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
     routing::post,
     Json, Router,
 };
 use tokio::sync::watch;
-use zksync_prover_interface::api::{
-    PollGeneratedProofsRequest, PollGeneratedProofsResponse, ProofGenerationData,
-    SubmitProofGenerationDataResponse,
+use zksync_prover_interface::{
+    api::{
+        PollGeneratedProofsRequest, PollGeneratedProofsResponse, ProofGenerationData,
+        SubmitProofGenerationDataResponse, WaitForGeneratedProofRequest,
+    },
+    long_poll::{self, LongPollWaiter, WaitError},
 };
+use zksync_types::secrets::APIKey;
 
 use crate::{error::ProcessorError, proof_data_manager::ProofDataManager};
 
@@ -19,16 +25,46 @@ pub struct Api {
     port: u16,
 }
 
+#[derive(Debug, Clone)]
+struct ApiState {
+    processor: ProofDataManager,
+}
+
+#[derive(Debug, Clone)]
+struct LongPollState {
+    processor: ProofDataManager,
+    push_auth_token: APIKey,
+    waiter: Arc<LongPollWaiter>,
+}
+
 impl Api {
-    pub fn new(processor: ProofDataManager, port: u16) -> Self {
-        let router = Router::new()
+    pub fn new(processor: ProofDataManager, port: u16, push_auth_token: Option<APIKey>) -> Self {
+        let mut router = Router::new()
             .route("/poll_generated_proofs", post(Api::get_generated_proofs))
             .route(
                 "/submit_request_for_proofs",
                 post(Api::save_proof_generation_data),
             )
-            .layer(DefaultBodyLimit::disable())
-            .with_state(processor);
+            .with_state(ApiState {
+                processor: processor.clone(),
+            });
+
+        // The long-poll endpoint is only served if the auth token is configured; otherwise, the server
+        // gets 404 and falls back to polling.
+        if let Some(push_auth_token) = push_auth_token {
+            let long_poll_router = Router::new()
+                .route(
+                    "/wait_for_generated_proof",
+                    post(Api::wait_for_generated_proof),
+                )
+                .with_state(LongPollState {
+                    processor,
+                    push_auth_token,
+                    waiter: Arc::default(),
+                });
+            router = router.merge(long_poll_router);
+        }
+        let router = router.layer(DefaultBodyLimit::disable());
 
         Self { router, port }
     }
@@ -57,7 +93,7 @@ impl Api {
     }
 
     async fn get_generated_proofs(
-        State(processor): State<ProofDataManager>,
+        State(ApiState { processor }): State<ApiState>,
         Json(request): Json<PollGeneratedProofsRequest>,
     ) -> Result<Json<Option<PollGeneratedProofsResponse>>, ProcessorError> {
         tracing::info!("Received request for proof: {:?}", request);
@@ -78,8 +114,52 @@ impl Api {
         Ok(Json(response))
     }
 
+    /// Long-poll version of `/poll_generated_proofs` used by the server in push mode.
+    async fn wait_for_generated_proof(
+        State(state): State<LongPollState>,
+        headers: HeaderMap,
+        Json(request): Json<WaitForGeneratedProofRequest>,
+    ) -> Result<Response, ProcessorError> {
+        if !long_poll::is_authorized(&headers, &state.push_auth_token) {
+            return Ok(StatusCode::UNAUTHORIZED.into_response());
+        }
+        tracing::info!("Received long-poll request for proof: {:?}", request);
+
+        let timeout = Duration::from_secs(request.timeout_secs);
+        let proof = state
+            .waiter
+            .wait_for(timeout, || {
+                state.processor.get_proof_for_batch(request.l1_batch_id)
+            })
+            .await;
+        let proof = match proof {
+            Ok(proof) => proof,
+            Err(WaitError::Saturated) => {
+                tracing::info!("Too many long-poll requests are held, rejecting request");
+                return Ok(long_poll::SATURATED_STATUS.into_response());
+            }
+            Err(WaitError::Check(err)) => return Err(err),
+        };
+
+        let response = proof.map(|proof| PollGeneratedProofsResponse {
+            l1_batch_id: request.l1_batch_id,
+            proof: proof.into(),
+        });
+
+        if response.is_none() {
+            tracing::info!(
+                "Proof for batch {} wasn't generated within {timeout:?}",
+                request.l1_batch_id
+            );
+        } else {
+            tracing::info!("Proof is ready for batch {}", request.l1_batch_id);
+        }
+
+        Ok(Json(response).into_response())
+    }
+
     async fn save_proof_generation_data(
-        State(processor): State<ProofDataManager>,
+        State(ApiState { processor }): State<ApiState>,
         Json(data): Json<ProofGenerationData>,
     ) -> Result<Json<SubmitProofGenerationDataResponse>, ProcessorError> {
         tracing::info!(
@@ -1,6 +1,6 @@
 use std::time::Duration;
 
-use tokio::sync::watch;
+use tokio::{sync::watch, time::Instant};
 
 use crate::metrics::METRICS;
 
@@ -30,6 +30,12 @@ pub(crate) trait PeriodicApi: Sync + Send + 'static + Sized {
         response: Self::Response,
     ) -> anyhow::Result<()>;
 
+    /// Whether requests are long-polled, i.e. the API waits for new data before responding. In this
+    /// case the time the API has held a successful request for is subtracted from the poll duration.
+    fn is_long_poll(&self) -> bool {
+        false
+    }
+
     /// Runs `get_next_request` -> `send_request` -> `handle_response` in a loop.
     async fn run(
         self,
@@ -51,9 +57,14 @@ pub(crate) trait PeriodicApi: Sync + Send + 'static + Sized {
                 return Ok(());
             }
 
+            let mut wait_duration = poll_duration;
             if let Some((job_id, request)) = self.get_next_request().await? {
+                let started_at = Instant::now();
                 match self.send_request(job_id, request).await {
                     Ok(response) => {
+                        if self.is_long_poll() {
+                            wait_duration = poll_duration.saturating_sub(started_at.elapsed());
+                        }
                         self.handle_response(job_id, response).await?;
                     }
                     Err(err) => {
@@ -63,9 +74,11 @@ pub(crate) trait PeriodicApi: Sync + Send + 'static + Sized {
                 }
             }
             // Exit condition will be checked on the next iteration.
-            tokio::time::timeout(poll_duration, stop_receiver.changed())
-                .await
-                .ok();
+            if !wait_duration.is_zero() {
+                tokio::time::timeout(wait_duration, stop_receiver.changed())
+                    .await
+                    .ok();
+            }
         }
     }
 }
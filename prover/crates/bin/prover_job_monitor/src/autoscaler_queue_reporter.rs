@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, time::Duration};
 
 use axum::{
     http::StatusCode,
@@ -28,6 +28,9 @@ pub struct QueueReport {
     pub scheduler_witness_jobs: JobCountStatistics,
     pub prover_jobs: JobCountStatistics,
     pub proof_compressor_jobs: JobCountStatistics,
+    /// Number of batches sealed during the last hour, used for predictive scaling.
+    #[serde(default)]
+    pub batches_sealed_last_hour: usize,
 }
 
 #[derive(Default, Debug, Serialize, Deserialize)]
@@ -52,6 +55,7 @@ impl AutoscalerQueueReporter {
 
         self.get_prover_jobs_report(&mut result).await?;
         self.get_proof_compressor_jobs_report(&mut result).await?;
+        self.get_sealed_batches_report(&mut result).await?;
 
         Ok(Json(
             result
@@ -128,6 +132,27 @@ impl AutoscalerQueueReporter {
 
         Ok(())
     }
+
+    async fn get_sealed_batches_report(
+        &self,
+        state: &mut HashMap<ProtocolSemanticVersion, QueueReport>,
+    ) -> anyhow::Result<()> {
+        let counts = self
+            .connection_pool
+            .connection()
+            .await?
+            .fri_witness_generator_dal()
+            .count_recently_sealed_batches(Duration::from_secs(3600))
+            .await;
+
+        for (protocol_version, count) in counts {
+            let report = state.entry(protocol_version).or_default();
+
+            report.batches_sealed_last_hour = count;
+        }
+
+        Ok(())
+    }
 }
 
 pub fn get_queue_reporter_router(connection_pool: ConnectionPool<Prover>) -> Router {
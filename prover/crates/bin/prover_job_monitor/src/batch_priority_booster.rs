@@ -0,0 +1,58 @@
+use std::time::Duration;
+
+use anyhow::Context;
+use zksync_prover_dal::{ConnectionPool, Prover, ProverDal};
+use zksync_prover_task::Task;
+
+use crate::metrics::PROVER_JOB_MONITOR_METRICS;
+
+/// `BatchPriorityBooster` is a task that provides starvation protection for batch priority lanes.
+/// Batches that have jobs queued for longer than the starvation timeout behind jobs in higher lanes are moved one lane up
+/// (at most once per timeout, and never to the highest lane), so that they cannot be delayed indefinitely by expedited batches.
+/// Once starved jobs are picked, the batch is moved back to the lane set by the operator. Priorities of fully proven batches
+/// are removed.
+#[derive(Debug)]
+pub struct BatchPriorityBooster {
+    pool: ConnectionPool<Prover>,
+    /// the amount of time a job can stay queued after which its batch is considered starved
+    starvation_timeout: Duration,
+}
+
+impl BatchPriorityBooster {
+    pub fn new(pool: ConnectionPool<Prover>, starvation_timeout: Duration) -> Self {
+        Self {
+            pool,
+            starvation_timeout,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl Task for BatchPriorityBooster {
+    async fn invoke(&self) -> anyhow::Result<()> {
+        let mut connection = self
+            .pool
+            .connection()
+            .await
+            .context("failed to get database connection")?;
+        let mut dal = connection.fri_batch_priority_dal();
+        for batch_id in dal.reset_starvation_boosts(self.starvation_timeout).await? {
+            tracing::info!("Batch {batch_id:?} is no longer starved; moved it back to its lane");
+        }
+        let boosted_batches = dal.boost_starved_batches(self.starvation_timeout).await?;
+        for (batch_id, lane) in &boosted_batches {
+            tracing::info!(
+                "Batch {batch_id:?} has jobs queued for longer than {:?}; moved it to the {lane} lane",
+                self.starvation_timeout
+            );
+        }
+        let removed = dal.delete_finished_batches().await?;
+        if removed > 0 {
+            tracing::info!("Removed priorities of {removed} proven batches");
+        }
+        PROVER_JOB_MONITOR_METRICS
+            .starved_batches_boosted
+            .inc_by(boosted_batches.len() as u64);
+        Ok(())
+    }
+}
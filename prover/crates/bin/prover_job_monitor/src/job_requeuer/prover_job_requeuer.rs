@@ -4,9 +4,11 @@ use anyhow::Context;
 use zksync_prover_dal::{ConnectionPool, Prover, ProverDal};
 use zksync_prover_task::Task;
 
-use crate::metrics::SERVER_METRICS;
+use crate::metrics::{FRI_PROVER_METRICS, SERVER_METRICS};
 
-/// `ProverJobRequeuer` is a task that requeues prover jobs that have not made progress in a given unit of time.
+/// `ProverJobRequeuer` is a task that requeues prover jobs whose lease has expired.
+/// Additionally, all jobs (including leased ones, so that jobs of hung provers are reclaimed as well) are requeued
+/// if they have not made progress in a given unit of time.
 #[derive(Debug)]
 pub struct ProverJobRequeuer {
     pool: ConnectionPool<Prover>,
@@ -38,11 +40,20 @@ impl Task for ProverJobRequeuer {
             .connection()
             .await
             .context("failed to get database connection")?;
+        let expired_lease_jobs = connection
+            .fri_prover_jobs_dal()
+            .requeue_jobs_with_expired_leases(self.max_attempts)
+            .await;
         let stuck_jobs = connection
             .fri_prover_jobs_dal()
             .requeue_stuck_jobs(self.processing_timeout, self.max_attempts)
             .await;
-        let job_len = stuck_jobs.len();
+        let job_len = expired_lease_jobs.len() + stuck_jobs.len();
+        for job in expired_lease_jobs {
+            tracing::info!("requeued circuit prover job with expired lease {:?}", job);
+            let circuit_id = job.circuit_id.map_or_else(String::new, |id| id.to_string());
+            FRI_PROVER_METRICS.expired_leases[&circuit_id].inc();
+        }
         for stuck_job in stuck_jobs {
             tracing::info!("requeued circuit prover job {:?}", stuck_job);
         }
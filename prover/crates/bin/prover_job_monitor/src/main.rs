@@ -19,10 +19,12 @@ use zksync_prover_dal::{ConnectionPool, Prover};
 use zksync_prover_job_monitor::{
     attempts_reporter::ProverJobAttemptsReporter,
     autoscaler_queue_reporter::get_queue_reporter_router,
+    batch_priority_booster::BatchPriorityBooster,
     job_requeuer::{ProofCompressorJobRequeuer, ProverJobRequeuer, WitnessGeneratorJobRequeuer},
     prover_jobs_archiver::ProverJobsArchiver,
     queue_reporter::{
-        ProofCompressorQueueReporter, ProverQueueReporter, WitnessGeneratorQueueReporter,
+        BatchPriorityQueueReporter, ProofCompressorQueueReporter, ProverQueueReporter,
+        WitnessGeneratorQueueReporter,
     },
     witness_job_queuer::WitnessJobQueuer,
 };
@@ -215,6 +217,24 @@ fn get_tasks(
         witness_generator_queue_reporter,
     );
 
+    let batch_priority_queue_reporter = BatchPriorityQueueReporter::new(connection_pool.clone());
+    task_runner.add(
+        "BatchPriorityQueueReporter",
+        prover_job_monitor_config.batch_priority_queue_reporter_run_interval,
+        batch_priority_queue_reporter,
+    );
+
+    // starvation protection for batch priority lanes
+    let batch_priority_booster = BatchPriorityBooster::new(
+        connection_pool.clone(),
+        prover_job_monitor_config.batch_priority_starvation_timeout,
+    );
+    task_runner.add(
+        "BatchPriorityBooster",
+        prover_job_monitor_config.batch_priority_booster_run_interval,
+        batch_priority_booster,
+    );
+
     // witness job queuer
     let witness_job_queuer = WitnessJobQueuer::new(connection_pool.clone());
     task_runner.add(
@@ -1,4 +1,8 @@
-use vise::{Counter, EncodeLabelSet, EncodeLabelValue, Family, Gauge, LabeledFamily, Metrics};
+use std::time::Duration;
+
+use vise::{
+    Counter, EncodeLabelSet, EncodeLabelValue, Family, Gauge, LabeledFamily, Metrics, Unit,
+};
 use zksync_types::protocol_version::ProtocolSemanticVersion;
 
 #[derive(Debug, Metrics)]
@@ -8,6 +12,14 @@ pub(crate) struct ProverJobMonitorMetrics {
     pub gpu_prover_archived: Counter,
     #[metrics(labels = ["job_type"])]
     pub reached_max_attempts: LabeledFamily<JobType, Gauge>,
+    /// Number of times a batch was moved one lane up by starvation protection.
+    pub starved_batches_boosted: Counter,
+    /// Number of queued jobs per job type and batch priority lane.
+    #[metrics(labels = ["job_type", "lane"])]
+    pub queued_jobs_by_lane: LabeledFamily<(String, String), Gauge<u64>, 2>,
+    /// Age of the oldest batch with queued jobs per job type and batch priority lane.
+    #[metrics(labels = ["job_type", "lane"], unit = Unit::Seconds)]
+    pub oldest_queued_batch_age_by_lane: LabeledFamily<(String, String), Gauge<Duration>, 2>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue)]
@@ -77,6 +89,9 @@ pub(crate) struct FriProverMetrics {
     pub oldest_not_generated_batch: Gauge<u64>,
     #[metrics(labels = ["round"])]
     pub oldest_unprocessed_block_by_round: LabeledFamily<String, Gauge<u64>>,
+    /// Number of prover jobs requeued because their lease has expired.
+    #[metrics(labels = ["circuit_id"])]
+    pub expired_leases: LabeledFamily<String, Counter>,
 }
 
 impl FriProverMetrics {
@@ -0,0 +1,80 @@
+use std::collections::HashMap;
+
+use anyhow::Context;
+use chrono::Utc;
+use zksync_prover_dal::{ConnectionPool, Prover, ProverDal};
+use zksync_prover_task::Task;
+use zksync_types::prover_dal::BatchPriority;
+
+use crate::metrics::PROVER_JOB_MONITOR_METRICS;
+
+/// Job types reported by the prover DAL for priority lanes.
+const JOB_TYPES: [&str; 7] = [
+    "basic_witness_generator",
+    "leaf_witness_generator",
+    "node_witness_generator",
+    "recursion_tip_witness_generator",
+    "scheduler_witness_generator",
+    "prover_fri",
+    "proof_compressor",
+];
+
+/// `BatchPriorityQueueReporter` is a task that reports queued jobs of all types per batch priority lane.
+#[derive(Debug)]
+pub struct BatchPriorityQueueReporter {
+    pool: ConnectionPool<Prover>,
+}
+
+impl BatchPriorityQueueReporter {
+    pub fn new(pool: ConnectionPool<Prover>) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait::async_trait]
+impl Task for BatchPriorityQueueReporter {
+    async fn invoke(&self) -> anyhow::Result<()> {
+        let mut connection = self
+            .pool
+            .connection()
+            .await
+            .context("failed to get database connection")?;
+        let stats = connection
+            .fri_batch_priority_dal()
+            .get_queued_jobs_by_lane()
+            .await?;
+        let mut stats: HashMap<_, _> = stats
+            .into_iter()
+            .map(|stat| ((stat.job_type.clone(), stat.priority), stat))
+            .collect();
+
+        let now = Utc::now().naive_utc();
+        // Report all lanes, so that lanes without queued jobs are reset to zero.
+        for job_type in JOB_TYPES {
+            for priority in BatchPriority::ALL {
+                let labels = (job_type.to_owned(), priority.to_string());
+                let stat = stats.remove(&(job_type.to_owned(), priority));
+                let (queued, oldest_batch_age) = stat.map_or((0, Default::default()), |stat| {
+                    let age = (now - stat.oldest_batch_sealed_at)
+                        .to_std()
+                        .unwrap_or_default();
+                    (stat.queued as u64, age)
+                });
+                if queued > 0 && priority != BatchPriority::Normal {
+                    tracing::info!(
+                        "Found {queued} queued {job_type} jobs in {priority} priority lane, oldest batch age {oldest_batch_age:?}"
+                    );
+                }
+
+                PROVER_JOB_MONITOR_METRICS.queued_jobs_by_lane[&labels].set(queued);
+                PROVER_JOB_MONITOR_METRICS.oldest_queued_batch_age_by_lane[&labels]
+                    .set(oldest_batch_age);
+            }
+        }
+
+        for (job_type, priority) in stats.into_keys() {
+            tracing::warn!("Unexpected job type `{job_type}` in {priority} priority lane");
+        }
+        Ok(())
+    }
+}
@@ -3,7 +3,7 @@ use std::{collections::HashMap, sync::Arc, time::Instant};
 use anyhow::Context;
 use async_trait::async_trait;
 use zksync_prover_fri_types::ProverServiceDataKey;
-use zksync_prover_job_processor::JobPicker;
+use zksync_prover_job_processor::{JobPicker, LeaseGuard};
 use zksync_prover_keystore::GoldilocksGpuProverSetupData;
 use zksync_types::prover_dal::FriProverJobMetadata;
 
@@ -17,11 +17,15 @@ use crate::{
 };
 
 /// GpuCircuitProver job picker implementation.
-/// Retrieves job & data from WVG job saver.
+/// Retrieves job & data from WVG job saver. The lease handed over by WVG is held until the job is picked;
+/// from then on, the lease is renewed by the circuit prover runner.
 #[derive(Debug)]
 pub struct GpuCircuitProverJobPicker {
-    receiver:
-        tokio::sync::mpsc::Receiver<(WitnessVectorGeneratorExecutionOutput, FriProverJobMetadata)>,
+    receiver: tokio::sync::mpsc::Receiver<(
+        WitnessVectorGeneratorExecutionOutput,
+        FriProverJobMetadata,
+        Option<LeaseGuard>,
+    )>,
     setup_data_cache: HashMap<ProverServiceDataKey, Arc<GoldilocksGpuProverSetupData>>,
 }
 
@@ -30,6 +34,7 @@ impl GpuCircuitProverJobPicker {
         receiver: tokio::sync::mpsc::Receiver<(
             WitnessVectorGeneratorExecutionOutput,
             FriProverJobMetadata,
+            Option<LeaseGuard>,
         )>,
         setup_data_cache: HashMap<ProverServiceDataKey, Arc<GoldilocksGpuProverSetupData>>,
     ) -> Self {
@@ -50,7 +55,7 @@ impl JobPicker for GpuCircuitProverJobPicker {
         let start_time = Instant::now();
         tracing::info!("Started picking gpu circuit prover job");
 
-        let (wvg_output, metadata) = self
+        let (wvg_output, metadata, _wvg_lease) = self
             .receiver
             .recv()
             .await
@@ -8,7 +8,7 @@ use zksync_prover_fri_types::{
     circuit_definitions::boojum::cs::implementations::setup::FinalizationHintsForProver,
     get_current_pod_name, ProverServiceDataKey,
 };
-use zksync_prover_job_processor::{Backoff, BackoffAndCancellable, JobRunner};
+use zksync_prover_job_processor::{Backoff, BackoffAndCancellable, JobRunner, LeaseGuard};
 use zksync_prover_keystore::GoldilocksGpuProverSetupData;
 use zksync_types::{protocol_version::ProtocolSemanticVersion, prover_dal::FriProverJobMetadata};
 
@@ -16,6 +16,7 @@ use crate::{
     gpu_circuit_prover::{
         GpuCircuitProverExecutor, GpuCircuitProverJobPicker, GpuCircuitProverJobSaver,
     },
+    prover_job_lease_renewer::{LeaseSettings, ProverJobLeaseRenewer},
     types::witness_vector_generator_execution_output::WitnessVectorGeneratorExecutionOutput,
     witness_vector_generator::{
         HeavyWitnessVectorMetadataLoader, LightWitnessVectorMetadataLoader,
@@ -31,9 +32,13 @@ pub struct WvgRunnerBuilder {
     object_store: Arc<dyn ObjectStore>,
     protocol_version: ProtocolSemanticVersion,
     finalization_hints_cache: HashMap<ProverServiceDataKey, Arc<FinalizationHintsForProver>>,
-    sender:
-        tokio::sync::mpsc::Sender<(WitnessVectorGeneratorExecutionOutput, FriProverJobMetadata)>,
+    sender: tokio::sync::mpsc::Sender<(
+        WitnessVectorGeneratorExecutionOutput,
+        FriProverJobMetadata,
+        Option<LeaseGuard>,
+    )>,
     cancellation_token: CancellationToken,
+    lease_settings: LeaseSettings,
     pod_name: String,
 }
 
@@ -46,8 +51,10 @@ impl WvgRunnerBuilder {
         sender: tokio::sync::mpsc::Sender<(
             WitnessVectorGeneratorExecutionOutput,
             FriProverJobMetadata,
+            Option<LeaseGuard>,
         )>,
         cancellation_token: CancellationToken,
+        lease_settings: LeaseSettings,
     ) -> Self {
         Self {
             connection_pool,
@@ -56,6 +63,7 @@ impl WvgRunnerBuilder {
             finalization_hints_cache,
             sender,
             cancellation_token,
+            lease_settings,
             pod_name: get_current_pod_name(),
         }
     }
@@ -110,6 +118,11 @@ impl WvgRunnerBuilder {
         );
         let job_saver =
             WitnessVectorGeneratorJobSaver::new(self.connection_pool.clone(), self.sender.clone());
+        let lease_renewer = ProverJobLeaseRenewer::new(
+            self.connection_pool.clone(),
+            self.lease_settings,
+            self.pod_name.clone(),
+        );
         let backoff = Backoff::default();
 
         JobRunner::new(
@@ -122,6 +135,7 @@ impl WvgRunnerBuilder {
                 self.cancellation_token.clone(),
             )),
         )
+        .with_lease_renewer(lease_renewer)
     }
 }
 
@@ -134,11 +148,19 @@ pub fn circuit_prover_runner(
     receiver: tokio::sync::mpsc::Receiver<(
         WitnessVectorGeneratorExecutionOutput,
         FriProverJobMetadata,
+        Option<LeaseGuard>,
     )>,
     prover_context: ProverContext,
+    lease_settings: LeaseSettings,
 ) -> JobRunner<GpuCircuitProverExecutor, GpuCircuitProverJobPicker, GpuCircuitProverJobSaver> {
     let executor = GpuCircuitProverExecutor::new(prover_context);
     let job_picker = GpuCircuitProverJobPicker::new(receiver, setup_data_cache);
+    // Jobs are picked by witness vector generators running in the same pod.
+    let lease_renewer = ProverJobLeaseRenewer::new(
+        connection_pool.clone(),
+        lease_settings,
+        get_current_pod_name(),
+    );
     let job_saver = GpuCircuitProverJobSaver::new(connection_pool, object_store, protocol_version);
-    JobRunner::new(executor, job_picker, job_saver, 1, None)
+    JobRunner::new(executor, job_picker, job_saver, 1, None).with_lease_renewer(lease_renewer)
 }
@@ -5,5 +5,6 @@
 pub mod gpu_circuit_prover;
 pub mod job_runner;
 mod metrics;
+pub mod prover_job_lease_renewer;
 pub mod types;
 pub mod witness_vector_generator;
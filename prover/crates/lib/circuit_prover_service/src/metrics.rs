@@ -1,6 +1,6 @@
 use std::time::Duration;
 
-use vise::{Buckets, Histogram, Metrics};
+use vise::{Buckets, Counter, EncodeLabelValue, Histogram, LabeledFamily, Metrics};
 
 /// Metrics for witness vector generator execution
 #[derive(Debug, Metrics)]
@@ -44,3 +44,27 @@ pub struct CircuitProverMetrics {
 
 #[vise::register]
 pub static CIRCUIT_PROVER_METRICS: vise::Global<CircuitProverMetrics> = vise::Global::new();
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue)]
+#[metrics(rename_all = "snake_case")]
+pub(crate) enum LeaseRenewalOutcome {
+    /// Lease was extended.
+    Renewed,
+    /// Job is no longer in progress, e.g. it was requeued after its lease had expired.
+    Lost,
+    /// Lease couldn't be renewed, e.g. because of database errors.
+    Failed,
+}
+
+/// Metrics for prover job leases
+#[derive(Debug, Metrics)]
+#[metrics(prefix = "prover_job_lease")]
+pub(crate) struct ProverJobLeaseMetrics {
+    /// Number of lease renewals per circuit and outcome.
+    #[metrics(labels = ["circuit_id", "outcome"])]
+    pub renewals: LabeledFamily<(String, LeaseRenewalOutcome), Counter, 2>,
+}
+
+#[vise::register]
+pub(crate) static PROVER_JOB_LEASE_METRICS: vise::Global<ProverJobLeaseMetrics> =
+    vise::Global::new();
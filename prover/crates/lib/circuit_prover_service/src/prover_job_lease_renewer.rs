@@ -0,0 +1,104 @@
+use std::{fmt, marker::PhantomData, time::Duration};
+
+use anyhow::Context;
+use async_trait::async_trait;
+use zksync_prover_dal::{ConnectionPool, Prover, ProverDal};
+use zksync_prover_job_processor::{Executor, LeaseRenewer};
+use zksync_types::prover_dal::FriProverJobMetadata;
+
+use crate::metrics::{LeaseRenewalOutcome, PROVER_JOB_LEASE_METRICS};
+
+/// Settings of prover job leases.
+#[derive(Debug, Clone, Copy)]
+pub struct LeaseSettings {
+    /// For how long a job is leased after each renewal.
+    pub lease_duration: Duration,
+    /// How often leases are renewed while jobs are being processed.
+    pub renewal_interval: Duration,
+}
+
+/// Prover job lease renewer implementation, shared by witness vector generators and GPU circuit provers.
+/// Renews leases of prover jobs in database, so that Prover Job Monitor requeues them only once the lease expires.
+pub struct ProverJobLeaseRenewer<E> {
+    connection_pool: ConnectionPool<Prover>,
+    settings: LeaseSettings,
+    /// Name the jobs are picked by; only leases of jobs picked by this name are renewed.
+    picked_by: String,
+    _executor: PhantomData<fn() -> E>,
+}
+
+impl<E> fmt::Debug for ProverJobLeaseRenewer<E> {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("ProverJobLeaseRenewer")
+            .field("settings", &self.settings)
+            .field("picked_by", &self.picked_by)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<E> ProverJobLeaseRenewer<E> {
+    pub fn new(
+        connection_pool: ConnectionPool<Prover>,
+        settings: LeaseSettings,
+        picked_by: String,
+    ) -> Self {
+        Self {
+            connection_pool,
+            settings,
+            picked_by,
+            _executor: PhantomData,
+        }
+    }
+}
+
+#[async_trait]
+impl<E> LeaseRenewer for ProverJobLeaseRenewer<E>
+where
+    E: Executor<Metadata = FriProverJobMetadata>,
+{
+    type ExecutorType = E;
+
+    fn renewal_interval(&self) -> Duration {
+        self.settings.renewal_interval
+    }
+
+    async fn renew_lease(&self, metadata: FriProverJobMetadata) -> anyhow::Result<bool> {
+        let circuit_id = metadata.circuit_id.to_string();
+        let renewed = async {
+            let mut connection = self
+                .connection_pool
+                .connection()
+                .await
+                .context("failed to get db connection")?;
+            connection
+                .fri_prover_jobs_dal()
+                .renew_lease(
+                    metadata.id,
+                    metadata.batch_id.chain_id(),
+                    &self.picked_by,
+                    self.settings.lease_duration,
+                )
+                .await
+                .context("failed to renew lease")
+        }
+        .await;
+
+        let outcome = match &renewed {
+            Ok(true) => LeaseRenewalOutcome::Renewed,
+            Ok(false) => {
+                tracing::warn!(
+                    "Lost lease of prover job {}, on batch {}, for circuit {}, at round {}; it was reclaimed or is no longer in progress",
+                    metadata.id,
+                    metadata.batch_id,
+                    metadata.circuit_id,
+                    metadata.aggregation_round
+                );
+                LeaseRenewalOutcome::Lost
+            }
+            Err(_) => LeaseRenewalOutcome::Failed,
+        };
+        PROVER_JOB_LEASE_METRICS.renewals[&(circuit_id, outcome)].inc();
+        renewed
+    }
+}
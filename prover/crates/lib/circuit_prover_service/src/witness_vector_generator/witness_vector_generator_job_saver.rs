@@ -3,7 +3,7 @@ use std::time::Instant;
 use anyhow::Context;
 use async_trait::async_trait;
 use zksync_prover_dal::{ConnectionPool, Prover, ProverDal};
-use zksync_prover_job_processor::JobSaver;
+use zksync_prover_job_processor::{JobSaver, LeaseGuard};
 use zksync_types::prover_dal::FriProverJobMetadata;
 
 use crate::{
@@ -13,13 +13,16 @@ use crate::{
 };
 
 /// WitnessVectorGenerator job saver implementation.
-/// On successful execution, sends data further to gpu circuit prover.
+/// On successful execution, sends data further to gpu circuit prover, together with the job lease.
 /// On error, marks the job as failed in database.
 #[derive(Debug)]
 pub struct WitnessVectorGeneratorJobSaver {
     connection_pool: ConnectionPool<Prover>,
-    sender:
-        tokio::sync::mpsc::Sender<(WitnessVectorGeneratorExecutionOutput, FriProverJobMetadata)>,
+    sender: tokio::sync::mpsc::Sender<(
+        WitnessVectorGeneratorExecutionOutput,
+        FriProverJobMetadata,
+        Option<LeaseGuard>,
+    )>,
 }
 
 impl WitnessVectorGeneratorJobSaver {
@@ -28,6 +31,7 @@ impl WitnessVectorGeneratorJobSaver {
         sender: tokio::sync::mpsc::Sender<(
             WitnessVectorGeneratorExecutionOutput,
             FriProverJobMetadata,
+            Option<LeaseGuard>,
         )>,
     ) -> Self {
         Self {
@@ -41,17 +45,30 @@ impl WitnessVectorGeneratorJobSaver {
 impl JobSaver for WitnessVectorGeneratorJobSaver {
     type ExecutorType = WitnessVectorGeneratorExecutor;
 
+    async fn save_job_result(
+        &self,
+        data: (
+            anyhow::Result<WitnessVectorGeneratorExecutionOutput>,
+            FriProverJobMetadata,
+        ),
+    ) -> anyhow::Result<()> {
+        self.save_job_result_with_lease(data, None).await
+    }
+
+    /// The job isn't finished until it's proven, so the lease is handed over to gpu circuit prover
+    /// instead of being released.
     #[tracing::instrument(
         name = "witness_vector_generator_save_job",
         skip_all,
         fields(l1_batch = % data.1.batch_id)
     )]
-    async fn save_job_result(
+    async fn save_job_result_with_lease(
         &self,
         data: (
             anyhow::Result<WitnessVectorGeneratorExecutionOutput>,
             FriProverJobMetadata,
         ),
+        lease: Option<LeaseGuard>,
     ) -> anyhow::Result<()> {
         let start_time = Instant::now();
         let (result, metadata) = data;
@@ -64,7 +81,7 @@ impl JobSaver for WitnessVectorGeneratorJobSaver {
                     metadata.circuit_id,
                     metadata.aggregation_round
                 );
-                if self.sender.send((payload, metadata)).await.is_err() {
+                if self.sender.send((payload, metadata, lease)).await.is_err() {
                     tracing::warn!("circuit prover shut down prematurely");
                     return Ok(());
                 }
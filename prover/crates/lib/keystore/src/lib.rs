@@ -25,6 +25,7 @@ use zksync_prover_fri_types::circuit_definitions::boojum::{
 
 pub mod commitment_utils;
 pub mod keystore;
+pub mod proof_verification;
 pub mod setup_data_generator;
 pub mod utils;
 
@@ -0,0 +1,73 @@
+use anyhow::Context as _;
+use circuit_definitions::{
+    circuit_definitions::aux_layer::{
+        ZkSyncSnarkWrapperCircuit, ZkSyncSnarkWrapperCircuitNoLookupCustomGate,
+    },
+    snark_wrapper::franklin_crypto::bellman::{
+        pairing::bn256::{Bn256, Fr},
+        plonk::{
+            better_better_cs::{
+                proof::Proof as PlonkProof, setup::VerificationKey as SnarkVK,
+                verifier::verify as verify_plonk,
+            },
+            commitments::transcript::keccak_transcript::RollingKeccakTranscript,
+        },
+    },
+};
+use fflonk::{verify as verify_fflonk, FflonkProof, FflonkVerificationKey};
+use zkevm_test_harness::prover_utils::verify_recursion_layer_proof_for_type;
+use zksync_prover_fri_types::circuit_definitions::{
+    boojum::cs::implementations::pow::NoPow,
+    circuit_definitions::recursion_layer::{
+        ZkSyncRecursionLayerProof, ZkSyncRecursionLayerStorageType,
+    },
+};
+
+use crate::keystore::Keystore;
+
+impl Keystore {
+    /// Verifies a scheduler FRI proof against the scheduler verification key.
+    pub fn verify_scheduler_proof(
+        &self,
+        proof: &ZkSyncRecursionLayerProof,
+    ) -> anyhow::Result<bool> {
+        let circuit_type = ZkSyncRecursionLayerStorageType::SchedulerCircuit;
+        anyhow::ensure!(
+            proof.numeric_circuit_type() == circuit_type as u8,
+            "Expected scheduler proof, got proof for recursive circuit {}",
+            proof.numeric_circuit_type()
+        );
+        let vk = self
+            .load_recursive_layer_verification_key(circuit_type as u8)
+            .context("load_recursive_layer_verification_key(SchedulerCircuit)")?;
+        Ok(verify_recursion_layer_proof_for_type::<NoPow>(
+            circuit_type,
+            proof,
+            &vk,
+        ))
+    }
+
+    /// Verifies a Plonk SNARK-wrapped proof against the snark verification key.
+    pub fn verify_plonk_snark_proof(
+        &self,
+        proof: &PlonkProof<Bn256, ZkSyncSnarkWrapperCircuit>,
+    ) -> anyhow::Result<bool> {
+        let vk: SnarkVK<Bn256, ZkSyncSnarkWrapperCircuit> =
+            serde_json::from_str(&self.load_snark_verification_key()?)
+                .context("Failed parsing Snark verification key")?;
+        verify_plonk::<_, _, RollingKeccakTranscript<Fr>>(&vk, proof, None)
+            .map_err(|err| anyhow::anyhow!("Plonk proof verification error: {err:?}"))
+    }
+
+    /// Verifies a FFLONK SNARK-wrapped proof against the FFLONK snark verification key.
+    pub fn verify_fflonk_snark_proof(
+        &self,
+        proof: &FflonkProof<Bn256, ZkSyncSnarkWrapperCircuitNoLookupCustomGate>,
+    ) -> anyhow::Result<bool> {
+        let vk: FflonkVerificationKey<Bn256, ZkSyncSnarkWrapperCircuitNoLookupCustomGate> =
+            serde_json::from_str(&self.load_fflonk_snark_verification_key()?)
+                .context("Failed parsing FFLONK Snark verification key")?;
+        verify_fflonk::<_, _, RollingKeccakTranscript<Fr>>(&vk, proof, None)
+            .map_err(|err| anyhow::anyhow!("FFLONK proof verification error: {err:?}"))
+    }
+}
@@ -0,0 +1,12 @@
+CREATE TABLE IF NOT EXISTS prover_batch_priorities (
+    l1_batch_number BIGINT NOT NULL,
+    chain_id INTEGER NOT NULL,
+    lane SMALLINT NOT NULL,
+    -- Lane set by an operator (0 if not set); `lane` is reset to it once the starvation boost is no longer needed.
+    base_lane SMALLINT NOT NULL DEFAULT 0,
+    -- Whether the batch was moved to its lane by starvation protection rather than by an operator.
+    is_starvation_boost BOOLEAN NOT NULL DEFAULT FALSE,
+    created_at TIMESTAMP NOT NULL,
+    updated_at TIMESTAMP NOT NULL,
+    PRIMARY KEY (l1_batch_number, chain_id)
+);
@@ -0,0 +1,2 @@
+ALTER TABLE prover_jobs_fri DROP COLUMN IF EXISTS lease_expires_at;
+ALTER TABLE prover_jobs_fri_archive DROP COLUMN IF EXISTS lease_expires_at;
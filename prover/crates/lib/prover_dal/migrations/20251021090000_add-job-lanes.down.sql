@@ -0,0 +1,16 @@
+DROP INDEX IF EXISTS idx_witness_inputs_fri_queued_lane_order;
+DROP INDEX IF EXISTS idx_leaf_aggregation_witness_jobs_fri_queued_lane_order;
+DROP INDEX IF EXISTS idx_node_aggregation_witness_jobs_fri_queued_lane_order;
+DROP INDEX IF EXISTS idx_recursion_tip_witness_jobs_fri_queued_lane_order;
+DROP INDEX IF EXISTS idx_scheduler_witness_jobs_fri_queued_lane_order;
+DROP INDEX IF EXISTS idx_prover_jobs_fri_queued_lane_order;
+DROP INDEX IF EXISTS idx_proof_compression_jobs_fri_queued_lane_order;
+
+ALTER TABLE witness_inputs_fri DROP COLUMN IF EXISTS lane;
+ALTER TABLE leaf_aggregation_witness_jobs_fri DROP COLUMN IF EXISTS lane;
+ALTER TABLE node_aggregation_witness_jobs_fri DROP COLUMN IF EXISTS lane;
+ALTER TABLE recursion_tip_witness_jobs_fri DROP COLUMN IF EXISTS lane;
+ALTER TABLE scheduler_witness_jobs_fri DROP COLUMN IF EXISTS lane;
+ALTER TABLE prover_jobs_fri DROP COLUMN IF EXISTS lane;
+ALTER TABLE prover_jobs_fri_archive DROP COLUMN IF EXISTS lane;
+ALTER TABLE proof_compression_jobs_fri DROP COLUMN IF EXISTS lane;
@@ -0,0 +1,62 @@
+-- Priority lane of the job's batch, denormalized from `prover_batch_priorities` so that job pickers can order queued jobs
+-- by lane using an index.
+ALTER TABLE witness_inputs_fri ADD COLUMN IF NOT EXISTS lane SMALLINT NOT NULL DEFAULT 0;
+ALTER TABLE leaf_aggregation_witness_jobs_fri ADD COLUMN IF NOT EXISTS lane SMALLINT NOT NULL DEFAULT 0;
+ALTER TABLE node_aggregation_witness_jobs_fri ADD COLUMN IF NOT EXISTS lane SMALLINT NOT NULL DEFAULT 0;
+ALTER TABLE recursion_tip_witness_jobs_fri ADD COLUMN IF NOT EXISTS lane SMALLINT NOT NULL DEFAULT 0;
+ALTER TABLE scheduler_witness_jobs_fri ADD COLUMN IF NOT EXISTS lane SMALLINT NOT NULL DEFAULT 0;
+ALTER TABLE prover_jobs_fri ADD COLUMN IF NOT EXISTS lane SMALLINT NOT NULL DEFAULT 0;
+ALTER TABLE prover_jobs_fri_archive ADD COLUMN IF NOT EXISTS lane SMALLINT NOT NULL DEFAULT 0;
+ALTER TABLE proof_compression_jobs_fri ADD COLUMN IF NOT EXISTS lane SMALLINT NOT NULL DEFAULT 0;
+
+UPDATE witness_inputs_fri SET lane = prover_batch_priorities.lane
+FROM prover_batch_priorities
+WHERE witness_inputs_fri.l1_batch_number = prover_batch_priorities.l1_batch_number
+    AND witness_inputs_fri.chain_id = prover_batch_priorities.chain_id;
+UPDATE leaf_aggregation_witness_jobs_fri SET lane = prover_batch_priorities.lane
+FROM prover_batch_priorities
+WHERE leaf_aggregation_witness_jobs_fri.l1_batch_number = prover_batch_priorities.l1_batch_number
+    AND leaf_aggregation_witness_jobs_fri.chain_id = prover_batch_priorities.chain_id;
+UPDATE node_aggregation_witness_jobs_fri SET lane = prover_batch_priorities.lane
+FROM prover_batch_priorities
+WHERE node_aggregation_witness_jobs_fri.l1_batch_number = prover_batch_priorities.l1_batch_number
+    AND node_aggregation_witness_jobs_fri.chain_id = prover_batch_priorities.chain_id;
+UPDATE recursion_tip_witness_jobs_fri SET lane = prover_batch_priorities.lane
+FROM prover_batch_priorities
+WHERE recursion_tip_witness_jobs_fri.l1_batch_number = prover_batch_priorities.l1_batch_number
+    AND recursion_tip_witness_jobs_fri.chain_id = prover_batch_priorities.chain_id;
+UPDATE scheduler_witness_jobs_fri SET lane = prover_batch_priorities.lane
+FROM prover_batch_priorities
+WHERE scheduler_witness_jobs_fri.l1_batch_number = prover_batch_priorities.l1_batch_number
+    AND scheduler_witness_jobs_fri.chain_id = prover_batch_priorities.chain_id;
+UPDATE prover_jobs_fri SET lane = prover_batch_priorities.lane
+FROM prover_batch_priorities
+WHERE prover_jobs_fri.l1_batch_number = prover_batch_priorities.l1_batch_number
+    AND prover_jobs_fri.chain_id = prover_batch_priorities.chain_id;
+UPDATE proof_compression_jobs_fri SET lane = prover_batch_priorities.lane
+FROM prover_batch_priorities
+WHERE proof_compression_jobs_fri.l1_batch_number = prover_batch_priorities.l1_batch_number
+    AND proof_compression_jobs_fri.chain_id = prover_batch_priorities.chain_id;
+
+-- Indexes matching the order in which job pickers pick queued jobs.
+CREATE INDEX IF NOT EXISTS idx_witness_inputs_fri_queued_lane_order
+    ON witness_inputs_fri USING btree (lane DESC, priority DESC, batch_sealed_at ASC)
+    WHERE (status = 'queued'::text);
+CREATE INDEX IF NOT EXISTS idx_leaf_aggregation_witness_jobs_fri_queued_lane_order
+    ON leaf_aggregation_witness_jobs_fri USING btree (lane DESC, priority DESC, batch_sealed_at ASC)
+    WHERE (status = 'queued'::text);
+CREATE INDEX IF NOT EXISTS idx_node_aggregation_witness_jobs_fri_queued_lane_order
+    ON node_aggregation_witness_jobs_fri USING btree (lane DESC, priority DESC, batch_sealed_at ASC, depth ASC, id ASC)
+    WHERE (status = 'queued'::text);
+CREATE INDEX IF NOT EXISTS idx_recursion_tip_witness_jobs_fri_queued_lane_order
+    ON recursion_tip_witness_jobs_fri USING btree (lane DESC, priority DESC, batch_sealed_at ASC)
+    WHERE (status = 'queued'::text);
+CREATE INDEX IF NOT EXISTS idx_scheduler_witness_jobs_fri_queued_lane_order
+    ON scheduler_witness_jobs_fri USING btree (lane DESC, priority DESC, batch_sealed_at ASC)
+    WHERE (status = 'queued'::text);
+CREATE INDEX IF NOT EXISTS idx_prover_jobs_fri_queued_lane_order
+    ON prover_jobs_fri USING btree (lane DESC, priority DESC, batch_sealed_at ASC, aggregation_round ASC, circuit_id ASC, id ASC)
+    WHERE (status = 'queued'::text);
+CREATE INDEX IF NOT EXISTS idx_proof_compression_jobs_fri_queued_lane_order
+    ON proof_compression_jobs_fri USING btree (lane DESC, priority DESC, batch_sealed_at ASC)
+    WHERE (status = 'queued'::text);
@@ -0,0 +1,905 @@
+use std::time::Duration;
+
+use zksync_basic_types::{
+    prover_dal::{BatchPriority, ProofCompressionJobStatus, QueuedJobsInLane},
+    L1BatchId,
+};
+use zksync_db_connection::{
+    connection::Connection, error::DalResult, instrument::InstrumentExt,
+    utils::pg_interval_from_duration,
+};
+
+use crate::Prover;
+
+/// Manages per-batch priority lanes. Job pickers for all prover subsystems order queued jobs by the lane
+/// of their batch first; batches without an explicitly set lane belong to [`BatchPriority::Normal`].
+///
+/// Lanes are stored in `prover_batch_priorities` and denormalized into the `lane` column of job tables, so that
+/// pickers can use an index. Jobs inherit the lane of their batch on insertion; all methods changing lanes
+/// propagate them to existing jobs.
+#[derive(Debug)]
+pub struct FriBatchPriorityDal<'a, 'c> {
+    pub storage: &'a mut Connection<'c, Prover>,
+}
+
+impl FriBatchPriorityDal<'_, '_> {
+    pub async fn set_batch_priority(
+        &mut self,
+        batch_id: L1BatchId,
+        priority: BatchPriority,
+    ) -> DalResult<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO
+            prover_batch_priorities (
+                l1_batch_number,
+                chain_id,
+                lane,
+                base_lane,
+                is_starvation_boost,
+                created_at,
+                updated_at
+            )
+            VALUES
+            ($1, $2, $3, $3, FALSE, NOW(), NOW())
+            ON CONFLICT (l1_batch_number, chain_id) DO
+            UPDATE
+            SET
+            lane = excluded.lane,
+            base_lane = excluded.base_lane,
+            is_starvation_boost = FALSE,
+            updated_at = NOW()
+            "#,
+            batch_id.batch_number().0 as i64,
+            batch_id.chain_id().inner() as i32,
+            priority as i16,
+        )
+        .instrument("set_batch_priority")
+        .with_arg("batch_id", &batch_id)
+        .with_arg("priority", &priority)
+        .execute(self.storage)
+        .await?;
+        self.sync_job_lanes().await
+    }
+
+    pub async fn get_batch_priority(&mut self, batch_id: L1BatchId) -> DalResult<BatchPriority> {
+        let lane = sqlx::query_scalar!(
+            r#"
+            SELECT
+                lane
+            FROM
+                prover_batch_priorities
+            WHERE
+                l1_batch_number = $1
+                AND chain_id = $2
+            "#,
+            batch_id.batch_number().0 as i64,
+            batch_id.chain_id().inner() as i32,
+        )
+        .instrument("get_batch_priority")
+        .with_arg("batch_id", &batch_id)
+        .fetch_optional(self.storage)
+        .await?;
+        Ok(lane.and_then(BatchPriority::from_db).unwrap_or_default())
+    }
+
+    /// Starvation protection: moves batches that have jobs queued for more than `starvation_timeout` behind jobs
+    /// in higher lanes one lane up, so that they cannot be indefinitely delayed by batches in higher lanes. A batch is
+    /// moved at most once per `starvation_timeout`, and never to the highest lane, which is reserved for operators.
+    /// Returns the boosted batches together with their new lanes.
+    pub async fn boost_starved_batches(
+        &mut self,
+        starvation_timeout: Duration,
+    ) -> DalResult<Vec<(L1BatchId, BatchPriority)>> {
+        let starvation_timeout = pg_interval_from_duration(starvation_timeout);
+        let max_boosted_lane = BatchPriority::MAX_BOOSTED;
+        let rows = sqlx::query!(
+            r#"
+            INSERT INTO
+            prover_batch_priorities (
+                l1_batch_number,
+                chain_id,
+                lane,
+                base_lane,
+                is_starvation_boost,
+                created_at,
+                updated_at
+            )
+            SELECT
+                l1_batch_number,
+                chain_id,
+                $2::SMALLINT,
+                $3::SMALLINT,
+                TRUE,
+                NOW(),
+                NOW()
+            FROM
+                (
+                    SELECT
+                        l1_batch_number,
+                        chain_id
+                    FROM
+                        witness_inputs_fri starved
+                    WHERE
+                        status = 'queued'
+                        AND updated_at <= NOW() - $1::INTERVAL
+                        AND lane < $4
+                        AND EXISTS (
+                            SELECT
+                                1
+                            FROM
+                                witness_inputs_fri ahead
+                            WHERE
+                                ahead.status = 'queued'
+                                AND ahead.lane > starved.lane
+                        )
+                    UNION
+                    SELECT
+                        l1_batch_number,
+                        chain_id
+                    FROM
+                        leaf_aggregation_witness_jobs_fri starved
+                    WHERE
+                        status = 'queued'
+                        AND updated_at <= NOW() - $1::INTERVAL
+                        AND lane < $4
+                        AND EXISTS (
+                            SELECT
+                                1
+                            FROM
+                                leaf_aggregation_witness_jobs_fri ahead
+                            WHERE
+                                ahead.status = 'queued'
+                                AND ahead.lane > starved.lane
+                        )
+                    UNION
+                    SELECT
+                        l1_batch_number,
+                        chain_id
+                    FROM
+                        node_aggregation_witness_jobs_fri starved
+                    WHERE
+                        status = 'queued'
+                        AND updated_at <= NOW() - $1::INTERVAL
+                        AND lane < $4
+                        AND EXISTS (
+                            SELECT
+                                1
+                            FROM
+                                node_aggregation_witness_jobs_fri ahead
+                            WHERE
+                                ahead.status = 'queued'
+                                AND ahead.lane > starved.lane
+                        )
+                    UNION
+                    SELECT
+                        l1_batch_number,
+                        chain_id
+                    FROM
+                        recursion_tip_witness_jobs_fri starved
+                    WHERE
+                        status = 'queued'
+                        AND updated_at <= NOW() - $1::INTERVAL
+                        AND lane < $4
+                        AND EXISTS (
+                            SELECT
+                                1
+                            FROM
+                                recursion_tip_witness_jobs_fri ahead
+                            WHERE
+                                ahead.status = 'queued'
+                                AND ahead.lane > starved.lane
+                        )
+                    UNION
+                    SELECT
+                        l1_batch_number,
+                        chain_id
+                    FROM
+                        scheduler_witness_jobs_fri starved
+                    WHERE
+                        status = 'queued'
+                        AND updated_at <= NOW() - $1::INTERVAL
+                        AND lane < $4
+                        AND EXISTS (
+                            SELECT
+                                1
+                            FROM
+                                scheduler_witness_jobs_fri ahead
+                            WHERE
+                                ahead.status = 'queued'
+                                AND ahead.lane > starved.lane
+                        )
+                    UNION
+                    SELECT
+                        l1_batch_number,
+                        chain_id
+                    FROM
+                        prover_jobs_fri starved
+                    WHERE
+                        status = 'queued'
+                        AND updated_at <= NOW() - $1::INTERVAL
+                        AND lane < $4
+                        AND EXISTS (
+                            SELECT
+                                1
+                            FROM
+                                prover_jobs_fri ahead
+                            WHERE
+                                ahead.status = 'queued'
+                                AND ahead.lane > starved.lane
+                        )
+                    UNION
+                    SELECT
+                        l1_batch_number,
+                        chain_id
+                    FROM
+                        proof_compression_jobs_fri starved
+                    WHERE
+                        status = 'queued'
+                        AND updated_at <= NOW() - $1::INTERVAL
+                        AND lane < $4
+                        AND EXISTS (
+                            SELECT
+                                1
+                            FROM
+                                proof_compression_jobs_fri ahead
+                            WHERE
+                                ahead.status = 'queued'
+                                AND ahead.lane > starved.lane
+                        )
+                ) starved_batches
+            ON CONFLICT (l1_batch_number, chain_id) DO
+            UPDATE
+            SET
+            lane = prover_batch_priorities.lane + 1,
+            is_starvation_boost = TRUE,
+            updated_at = NOW()
+            WHERE
+                prover_batch_priorities.lane < $4
+                AND prover_batch_priorities.updated_at <= NOW() - $1::INTERVAL
+            RETURNING
+            l1_batch_number,
+            chain_id,
+            lane
+            "#,
+            &starvation_timeout,
+            BatchPriority::default().boosted() as i16,
+            BatchPriority::default() as i16,
+            max_boosted_lane as i16,
+        )
+        .instrument("boost_starved_batches")
+        .with_arg("starvation_timeout", &starvation_timeout)
+        .fetch_all(self.storage)
+        .await?;
+        self.sync_job_lanes().await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| {
+                let batch_id = L1BatchId::from_raw(row.chain_id as u64, row.l1_batch_number as u32);
+                let lane = BatchPriority::from_db(row.lane).unwrap_or(max_boosted_lane);
+                (batch_id, lane)
+            })
+            .collect())
+    }
+
+    /// Moves boosted batches that no longer have jobs queued for more than `starvation_timeout` (i.e., their starved
+    /// jobs were picked) back to the lane set by the operator. Returns the batches that were moved back.
+    pub async fn reset_starvation_boosts(
+        &mut self,
+        starvation_timeout: Duration,
+    ) -> DalResult<Vec<L1BatchId>> {
+        let starvation_timeout = pg_interval_from_duration(starvation_timeout);
+        let rows = sqlx::query!(
+            r#"
+            UPDATE prover_batch_priorities
+            SET
+                lane = base_lane,
+                is_starvation_boost = FALSE,
+                updated_at = NOW()
+            WHERE
+                is_starvation_boost
+                AND (l1_batch_number, chain_id) NOT IN (
+                    SELECT
+                        l1_batch_number,
+                        chain_id
+                    FROM
+                        witness_inputs_fri
+                    WHERE
+                        status = 'queued'
+                        AND updated_at <= NOW() - $1::INTERVAL
+                    UNION
+                    SELECT
+                        l1_batch_number,
+                        chain_id
+                    FROM
+                        leaf_aggregation_witness_jobs_fri
+                    WHERE
+                        status = 'queued'
+                        AND updated_at <= NOW() - $1::INTERVAL
+                    UNION
+                    SELECT
+                        l1_batch_number,
+                        chain_id
+                    FROM
+                        node_aggregation_witness_jobs_fri
+                    WHERE
+                        status = 'queued'
+                        AND updated_at <= NOW() - $1::INTERVAL
+                    UNION
+                    SELECT
+                        l1_batch_number,
+                        chain_id
+                    FROM
+                        recursion_tip_witness_jobs_fri
+                    WHERE
+                        status = 'queued'
+                        AND updated_at <= NOW() - $1::INTERVAL
+                    UNION
+                    SELECT
+                        l1_batch_number,
+                        chain_id
+                    FROM
+                        scheduler_witness_jobs_fri
+                    WHERE
+                        status = 'queued'
+                        AND updated_at <= NOW() - $1::INTERVAL
+                    UNION
+                    SELECT
+                        l1_batch_number,
+                        chain_id
+                    FROM
+                        prover_jobs_fri
+                    WHERE
+                        status = 'queued'
+                        AND updated_at <= NOW() - $1::INTERVAL
+                    UNION
+                    SELECT
+                        l1_batch_number,
+                        chain_id
+                    FROM
+                        proof_compression_jobs_fri
+                    WHERE
+                        status = 'queued'
+                        AND updated_at <= NOW() - $1::INTERVAL
+                )
+            RETURNING
+            l1_batch_number,
+            chain_id
+            "#,
+            &starvation_timeout,
+        )
+        .instrument("reset_starvation_boosts")
+        .with_arg("starvation_timeout", &starvation_timeout)
+        .fetch_all(self.storage)
+        .await?;
+        self.sync_job_lanes().await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| L1BatchId::from_raw(row.chain_id as u64, row.l1_batch_number as u32))
+            .collect())
+    }
+
+    /// Propagates lanes of batches to their jobs. Since this affects all batches with a set lane, it also fixes lanes
+    /// left stale by an earlier interrupted update.
+    async fn sync_job_lanes(&mut self) -> DalResult<()> {
+        sqlx::query!(
+            r#"
+            WITH
+                updated_witness_inputs_fri AS (
+                    UPDATE witness_inputs_fri
+                    SET
+                        lane = prover_batch_priorities.lane
+                    FROM
+                        prover_batch_priorities
+                    WHERE
+                        witness_inputs_fri.l1_batch_number = prover_batch_priorities.l1_batch_number
+                        AND witness_inputs_fri.chain_id = prover_batch_priorities.chain_id
+                        AND witness_inputs_fri.lane <> prover_batch_priorities.lane
+                    RETURNING
+                    1
+                ),
+                updated_leaf_aggregation_witness_jobs_fri AS (
+                    UPDATE leaf_aggregation_witness_jobs_fri
+                    SET
+                        lane = prover_batch_priorities.lane
+                    FROM
+                        prover_batch_priorities
+                    WHERE
+                        leaf_aggregation_witness_jobs_fri.l1_batch_number = prover_batch_priorities.l1_batch_number
+                        AND leaf_aggregation_witness_jobs_fri.chain_id = prover_batch_priorities.chain_id
+                        AND leaf_aggregation_witness_jobs_fri.lane <> prover_batch_priorities.lane
+                    RETURNING
+                    1
+                ),
+                updated_node_aggregation_witness_jobs_fri AS (
+                    UPDATE node_aggregation_witness_jobs_fri
+                    SET
+                        lane = prover_batch_priorities.lane
+                    FROM
+                        prover_batch_priorities
+                    WHERE
+                        node_aggregation_witness_jobs_fri.l1_batch_number = prover_batch_priorities.l1_batch_number
+                        AND node_aggregation_witness_jobs_fri.chain_id = prover_batch_priorities.chain_id
+                        AND node_aggregation_witness_jobs_fri.lane <> prover_batch_priorities.lane
+                    RETURNING
+                    1
+                ),
+                updated_recursion_tip_witness_jobs_fri AS (
+                    UPDATE recursion_tip_witness_jobs_fri
+                    SET
+                        lane = prover_batch_priorities.lane
+                    FROM
+                        prover_batch_priorities
+                    WHERE
+                        recursion_tip_witness_jobs_fri.l1_batch_number = prover_batch_priorities.l1_batch_number
+                        AND recursion_tip_witness_jobs_fri.chain_id = prover_batch_priorities.chain_id
+                        AND recursion_tip_witness_jobs_fri.lane <> prover_batch_priorities.lane
+                    RETURNING
+                    1
+                ),
+                updated_scheduler_witness_jobs_fri AS (
+                    UPDATE scheduler_witness_jobs_fri
+                    SET
+                        lane = prover_batch_priorities.lane
+                    FROM
+                        prover_batch_priorities
+                    WHERE
+                        scheduler_witness_jobs_fri.l1_batch_number = prover_batch_priorities.l1_batch_number
+                        AND scheduler_witness_jobs_fri.chain_id = prover_batch_priorities.chain_id
+                        AND scheduler_witness_jobs_fri.lane <> prover_batch_priorities.lane
+                    RETURNING
+                    1
+                ),
+                updated_prover_jobs_fri AS (
+                    UPDATE prover_jobs_fri
+                    SET
+                        lane = prover_batch_priorities.lane
+                    FROM
+                        prover_batch_priorities
+                    WHERE
+                        prover_jobs_fri.l1_batch_number = prover_batch_priorities.l1_batch_number
+                        AND prover_jobs_fri.chain_id = prover_batch_priorities.chain_id
+                        AND prover_jobs_fri.lane <> prover_batch_priorities.lane
+                    RETURNING
+                    1
+                )
+            UPDATE proof_compression_jobs_fri
+            SET
+                lane = prover_batch_priorities.lane
+            FROM
+                prover_batch_priorities
+            WHERE
+                proof_compression_jobs_fri.l1_batch_number = prover_batch_priorities.l1_batch_number
+                AND proof_compression_jobs_fri.chain_id = prover_batch_priorities.chain_id
+                AND proof_compression_jobs_fri.lane <> prover_batch_priorities.lane
+            "#
+        )
+        .instrument("sync_job_lanes")
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+
+    /// Removes priorities of batches that have finished proving, i.e. whose proof compression job
+    /// has completed or was skipped. Returns the number of removed priorities.
+    pub async fn delete_finished_batches(&mut self) -> DalResult<u64> {
+        let result = sqlx::query!(
+            r#"
+            DELETE FROM prover_batch_priorities
+            USING proof_compression_jobs_fri
+            WHERE
+                prover_batch_priorities.l1_batch_number = proof_compression_jobs_fri.l1_batch_number
+                AND prover_batch_priorities.chain_id = proof_compression_jobs_fri.chain_id
+                AND proof_compression_jobs_fri.status = ANY($1)
+            "#,
+            &[
+                ProofCompressionJobStatus::Successful.to_string(),
+                ProofCompressionJobStatus::SentToServer.to_string(),
+                ProofCompressionJobStatus::Skipped.to_string(),
+            ],
+        )
+        .instrument("delete_finished_batch_priorities")
+        .execute(self.storage)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// Returns the number of queued jobs per job type and priority lane.
+    pub async fn get_queued_jobs_by_lane(&mut self) -> DalResult<Vec<QueuedJobsInLane>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                job_type AS "job_type!",
+                lane AS "lane!",
+                COUNT(*) AS "queued!",
+                MIN(batch_sealed_at) AS "oldest_batch_sealed_at!"
+            FROM
+                (
+                    SELECT
+                        'basic_witness_generator' AS job_type,
+                        lane,
+                        batch_sealed_at
+                    FROM
+                        witness_inputs_fri
+                    WHERE
+                        status = 'queued'
+                    UNION ALL
+                    SELECT
+                        'leaf_witness_generator' AS job_type,
+                        lane,
+                        batch_sealed_at
+                    FROM
+                        leaf_aggregation_witness_jobs_fri
+                    WHERE
+                        status = 'queued'
+                    UNION ALL
+                    SELECT
+                        'node_witness_generator' AS job_type,
+                        lane,
+                        batch_sealed_at
+                    FROM
+                        node_aggregation_witness_jobs_fri
+                    WHERE
+                        status = 'queued'
+                    UNION ALL
+                    SELECT
+                        'recursion_tip_witness_generator' AS job_type,
+                        lane,
+                        batch_sealed_at
+                    FROM
+                        recursion_tip_witness_jobs_fri
+                    WHERE
+                        status = 'queued'
+                    UNION ALL
+                    SELECT
+                        'scheduler_witness_generator' AS job_type,
+                        lane,
+                        batch_sealed_at
+                    FROM
+                        scheduler_witness_jobs_fri
+                    WHERE
+                        status = 'queued'
+                    UNION ALL
+                    SELECT
+                        'prover_fri' AS job_type,
+                        lane,
+                        batch_sealed_at
+                    FROM
+                        prover_jobs_fri
+                    WHERE
+                        status = 'queued'
+                    UNION ALL
+                    SELECT
+                        'proof_compressor' AS job_type,
+                        lane,
+                        batch_sealed_at
+                    FROM
+                        proof_compression_jobs_fri
+                    WHERE
+                        status = 'queued'
+                ) queued_jobs
+            GROUP BY
+                1,
+                2
+            "#
+        )
+        .instrument("get_queued_jobs_by_lane")
+        .fetch_all(self.storage)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| QueuedJobsInLane {
+                job_type: row.job_type,
+                priority: BatchPriority::from_db(row.lane).unwrap_or_default(),
+                queued: row.queued as usize,
+                oldest_batch_sealed_at: row.oldest_batch_sealed_at,
+            })
+            .collect())
+    }
+
+    pub async fn delete_batch_data(&mut self, batch_id: L1BatchId) -> DalResult<()> {
+        sqlx::query!(
+            r#"
+            DELETE FROM prover_batch_priorities
+            WHERE
+                l1_batch_number = $1
+                AND chain_id = $2
+            "#,
+            batch_id.batch_number().0 as i64,
+            batch_id.chain_id().inner() as i32,
+        )
+        .instrument("delete_batch_priority")
+        .with_arg("batch_id", &batch_id)
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn delete(&mut self) -> DalResult<()> {
+        sqlx::query!(
+            r#"
+            DELETE FROM prover_batch_priorities
+            "#
+        )
+        .instrument("delete_batch_priorities")
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sqlx::types::chrono::{DateTime, Utc};
+    use zksync_basic_types::{
+        basic_fri_types::AggregationRound,
+        protocol_version::{L1VerifierConfig, ProtocolSemanticVersion},
+        L1BatchNumber,
+    };
+    use zksync_db_connection::connection_pool::ConnectionPool;
+
+    use super::*;
+    use crate::ProverDal;
+
+    async fn insert_batch_with_prover_job(conn: &mut Connection<'_, Prover>, batch_id: L1BatchId) {
+        conn.fri_basic_witness_generator_dal()
+            .save_witness_inputs(
+                batch_id,
+                "",
+                ProtocolSemanticVersion::default(),
+                DateTime::<Utc>::default(),
+            )
+            .await
+            .unwrap();
+        conn.fri_prover_jobs_dal()
+            .insert_prover_jobs(
+                batch_id,
+                vec![(1, "circuit".to_owned())],
+                AggregationRound::Scheduler,
+                1,
+                ProtocolSemanticVersion::default(),
+                DateTime::<Utc>::default(),
+            )
+            .await;
+    }
+
+    fn batch_numbers(
+        batches: &[(L1BatchId, BatchPriority)],
+    ) -> Vec<(L1BatchNumber, BatchPriority)> {
+        batches
+            .iter()
+            .map(|(batch_id, lane)| (batch_id.batch_number(), *lane))
+            .collect()
+    }
+
+    async fn prepare_storage(conn: &mut Connection<'_, Prover>, batch_ids: &[L1BatchId]) {
+        conn.fri_protocol_versions_dal()
+            .save_prover_protocol_version(
+                ProtocolSemanticVersion::default(),
+                L1VerifierConfig::default(),
+            )
+            .await
+            .unwrap();
+        for &batch_id in batch_ids {
+            insert_batch_with_prover_job(conn, batch_id).await;
+        }
+    }
+
+    #[tokio::test]
+    async fn jobs_are_picked_by_lane() {
+        let pool = ConnectionPool::<Prover>::prover_test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        let (first_batch, second_batch) = (L1BatchId::from_raw(1, 1), L1BatchId::from_raw(1, 2));
+        prepare_storage(&mut conn, &[first_batch, second_batch]).await;
+
+        conn.fri_batch_priority_dal()
+            .set_batch_priority(second_batch, BatchPriority::High)
+            .await
+            .unwrap();
+        let job = conn
+            .fri_prover_jobs_dal()
+            .get_light_job(ProtocolSemanticVersion::default(), "test")
+            .await
+            .expect("no job picked");
+        assert_eq!(job.batch_id.batch_number(), second_batch.batch_number());
+        let job = conn
+            .fri_prover_jobs_dal()
+            .get_light_job(ProtocolSemanticVersion::default(), "test")
+            .await
+            .expect("no job picked");
+        assert_eq!(job.batch_id.batch_number(), first_batch.batch_number());
+    }
+
+    #[tokio::test]
+    async fn witness_jobs_are_picked_by_lane() {
+        let pool = ConnectionPool::<Prover>::prover_test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        let (first_batch, second_batch) = (L1BatchId::from_raw(1, 1), L1BatchId::from_raw(1, 2));
+        prepare_storage(&mut conn, &[first_batch, second_batch]).await;
+
+        conn.fri_batch_priority_dal()
+            .set_batch_priority(second_batch, BatchPriority::Urgent)
+            .await
+            .unwrap();
+        for expected_batch in [second_batch, first_batch] {
+            let batch_id = conn
+                .fri_basic_witness_generator_dal()
+                .get_next_basic_circuit_witness_job(ProtocolSemanticVersion::default(), "test")
+                .await
+                .expect("no job picked");
+            assert_eq!(batch_id.batch_number(), expected_batch.batch_number());
+        }
+    }
+
+    #[tokio::test]
+    async fn compression_jobs_inherit_lane_of_batch() {
+        let pool = ConnectionPool::<Prover>::prover_test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        let (first_batch, second_batch) = (L1BatchId::from_raw(1, 1), L1BatchId::from_raw(1, 2));
+        prepare_storage(&mut conn, &[]).await;
+
+        conn.fri_batch_priority_dal()
+            .set_batch_priority(second_batch, BatchPriority::High)
+            .await
+            .unwrap();
+        for batch_id in [first_batch, second_batch] {
+            conn.fri_proof_compressor_dal()
+                .insert_proof_compression_job(
+                    batch_id,
+                    "",
+                    ProtocolSemanticVersion::default(),
+                    DateTime::<Utc>::default(),
+                )
+                .await;
+        }
+        for expected_batch in [second_batch, first_batch] {
+            let batch_id = conn
+                .fri_proof_compressor_dal()
+                .get_next_proof_compression_job("test", ProtocolSemanticVersion::default())
+                .await
+                .expect("no job picked");
+            assert_eq!(batch_id.batch_number(), expected_batch.batch_number());
+        }
+    }
+
+    #[tokio::test]
+    async fn starved_batches_are_boosted_one_lane_at_a_time() {
+        let pool = ConnectionPool::<Prover>::prover_test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        let normal_batch = L1BatchId::from_raw(1, 1);
+        let high_batch = L1BatchId::from_raw(1, 2);
+        let urgent_batch = L1BatchId::from_raw(1, 3);
+        prepare_storage(&mut conn, &[normal_batch, high_batch, urgent_batch]).await;
+        let mut dal = conn.fri_batch_priority_dal();
+        dal.set_batch_priority(high_batch, BatchPriority::High)
+            .await
+            .unwrap();
+        dal.set_batch_priority(urgent_batch, BatchPriority::Urgent)
+            .await
+            .unwrap();
+
+        // Jobs haven't been queued for long enough yet.
+        let boosted = dal
+            .boost_starved_batches(Duration::from_secs(3_600))
+            .await
+            .unwrap();
+        assert!(boosted.is_empty(), "{boosted:?}");
+
+        // Batches are never boosted to the highest lane, which is reserved for operators.
+        let boosted = dal.boost_starved_batches(Duration::ZERO).await.unwrap();
+        assert_eq!(
+            batch_numbers(&boosted),
+            [(normal_batch.batch_number(), BatchPriority::High)]
+        );
+        let boosted = dal.boost_starved_batches(Duration::ZERO).await.unwrap();
+        assert!(boosted.is_empty(), "{boosted:?}");
+
+        // Boosted lanes are propagated to queued jobs.
+        let lanes: Vec<_> = dal
+            .get_queued_jobs_by_lane()
+            .await
+            .unwrap()
+            .into_iter()
+            .filter(|jobs| jobs.job_type == "prover_fri")
+            .map(|jobs| (jobs.priority, jobs.queued))
+            .collect();
+        assert!(lanes.contains(&(BatchPriority::High, 2)), "{lanes:?}");
+        assert!(lanes.contains(&(BatchPriority::Urgent, 1)), "{lanes:?}");
+
+        // Once queued jobs of the batch are picked, the boost is reset to the lane set by the operator.
+        for batch_id in [normal_batch, high_batch, urgent_batch] {
+            conn.fri_basic_witness_generator_dal()
+                .mark_witness_job_as_successful(batch_id, Duration::ZERO)
+                .await;
+        }
+        let job = conn
+            .fri_prover_jobs_dal()
+            .get_light_job(ProtocolSemanticVersion::default(), "test")
+            .await
+            .expect("no job picked");
+        assert_eq!(job.batch_id.batch_number(), urgent_batch.batch_number());
+        for _ in 0..2 {
+            conn.fri_prover_jobs_dal()
+                .get_light_job(ProtocolSemanticVersion::default(), "test")
+                .await
+                .expect("no job picked");
+        }
+
+        let mut dal = conn.fri_batch_priority_dal();
+        let reset = dal.reset_starvation_boosts(Duration::ZERO).await.unwrap();
+        assert_eq!(reset.len(), 1);
+        assert_eq!(reset[0].batch_number(), normal_batch.batch_number());
+        assert_eq!(
+            dal.get_batch_priority(normal_batch).await.unwrap(),
+            BatchPriority::Normal
+        );
+        assert_eq!(
+            dal.get_batch_priority(high_batch).await.unwrap(),
+            BatchPriority::High
+        );
+        let reset = dal.reset_starvation_boosts(Duration::ZERO).await.unwrap();
+        assert!(reset.is_empty(), "{reset:?}");
+    }
+
+    #[tokio::test]
+    async fn batches_are_not_boosted_without_higher_lane_jobs() {
+        let pool = ConnectionPool::<Prover>::prover_test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        let (first_batch, second_batch) = (L1BatchId::from_raw(1, 1), L1BatchId::from_raw(1, 2));
+        prepare_storage(&mut conn, &[first_batch, second_batch]).await;
+
+        let boosted = conn
+            .fri_batch_priority_dal()
+            .boost_starved_batches(Duration::ZERO)
+            .await
+            .unwrap();
+        assert!(boosted.is_empty(), "{boosted:?}");
+    }
+
+    #[tokio::test]
+    async fn priorities_of_proven_batches_are_removed() {
+        let pool = ConnectionPool::<Prover>::prover_test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        let batch_id = L1BatchId::from_raw(1, 1);
+        prepare_storage(&mut conn, &[batch_id]).await;
+        conn.fri_batch_priority_dal()
+            .set_batch_priority(batch_id, BatchPriority::Urgent)
+            .await
+            .unwrap();
+
+        conn.fri_proof_compressor_dal()
+            .insert_proof_compression_job(
+                batch_id,
+                "",
+                ProtocolSemanticVersion::default(),
+                DateTime::<Utc>::default(),
+            )
+            .await;
+        let removed = conn
+            .fri_batch_priority_dal()
+            .delete_finished_batches()
+            .await
+            .unwrap();
+        assert_eq!(removed, 0);
+
+        conn.fri_proof_compressor_dal()
+            .mark_proof_sent_to_server(batch_id)
+            .await
+            .unwrap();
+        let removed = conn
+            .fri_batch_priority_dal()
+            .delete_finished_batches()
+            .await
+            .unwrap();
+        assert_eq!(removed, 1);
+        let priority = conn
+            .fri_batch_priority_dal()
+            .get_batch_priority(batch_id)
+            .await
+            .unwrap();
+        assert_eq!(priority, BatchPriority::Normal);
+    }
+}
@@ -38,10 +38,33 @@ impl FriProofCompressorDal<'_, '_> {
                 updated_at,
                 protocol_version,
                 protocol_version_patch,
-                batch_sealed_at
+                batch_sealed_at,
+                lane
             )
             VALUES
-            ($1, $2, $3, 'queued', NOW(), NOW(), $4, $5, $6)
+            (
+                $1,
+                $2,
+                $3,
+                'queued',
+                NOW(),
+                NOW(),
+                $4,
+                $5,
+                $6,
+                COALESCE(
+                    (
+                        SELECT
+                            lane
+                        FROM
+                            prover_batch_priorities
+                        WHERE
+                            l1_batch_number = $1
+                            AND chain_id = $2
+                    ),
+                    0
+                )
+            )
             ON CONFLICT (l1_batch_number, chain_id) DO NOTHING
             "#,
             batch_id.batch_number().0 as i64,
@@ -82,6 +105,7 @@ impl FriProofCompressorDal<'_, '_> {
                         AND protocol_version = $4
                         AND protocol_version_patch = $5
                     ORDER BY
+                        lane DESC,
                         priority DESC,
                         batch_sealed_at ASC
                     LIMIT
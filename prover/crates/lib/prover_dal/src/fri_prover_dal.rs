@@ -21,10 +21,10 @@ use zksync_basic_types::{
     L1BatchId, L1BatchNumber, L2ChainId,
 };
 use zksync_db_connection::{
-    connection::Connection, instrument::InstrumentExt, metrics::MethodLatency,
+    connection::Connection, error::DalResult, instrument::InstrumentExt, metrics::MethodLatency,
 };
 
-use crate::{duration_to_naive_time, pg_interval_from_duration, Prover};
+use crate::{duration_to_naive_time, pg_interval_from_duration, Prover, ProverDal};
 
 /// Among the zoo of circuits each circuit type has its own peak RAM utilization,
 /// average execution time and proportional share. Here we pay attention to
@@ -81,6 +81,14 @@ impl FriProverDal<'_, '_> {
             return;
         }
 
+        // Jobs inherit the priority lane of their batch.
+        let lane = self
+            .storage
+            .fri_batch_priority_dal()
+            .get_batch_priority(batch_id)
+            .await
+            .unwrap();
+
         for (chunk_index, chunk) in circuit_ids_and_urls
             .chunks(Self::INSERT_JOBS_CHUNK_SIZE)
             .enumerate()
@@ -102,7 +110,8 @@ impl FriProverDal<'_, '_> {
                     created_at,
                     updated_at,
                     protocol_version_patch,
-                    batch_sealed_at
+                    batch_sealed_at,
+                    lane
                 )
                 "#,
             );
@@ -123,7 +132,8 @@ impl FriProverDal<'_, '_> {
                         .push("NOW()") // created_at
                         .push("NOW()") // updated_at
                         .push_bind(protocol_version_id.patch.0 as i32)
-                        .push_bind(batch_sealed_at.naive_utc()); // batch_sealed_at
+                        .push_bind(batch_sealed_at.naive_utc()) // batch_sealed_at
+                        .push_bind(lane as i16);
                 },
             );
 
@@ -168,6 +178,7 @@ impl FriProverDal<'_, '_> {
                 attempts = attempts + 1,
                 updated_at = NOW(),
                 processing_started_at = NOW(),
+                lease_expires_at = NULL,
                 picked_by = $3
             WHERE
                 (id, chain_id) = (
@@ -183,6 +194,7 @@ impl FriProverDal<'_, '_> {
                         AND aggregation_round = $4
                         AND circuit_id = ANY($5)
                     ORDER BY
+                        lane DESC,
                         priority DESC,
                         batch_sealed_at ASC,
                         circuit_id ASC,
@@ -251,6 +263,7 @@ impl FriProverDal<'_, '_> {
                 attempts = attempts + 1,
                 updated_at = NOW(),
                 processing_started_at = NOW(),
+                lease_expires_at = NULL,
                 picked_by = $3
             WHERE
                 (id, chain_id) = (
@@ -265,6 +278,7 @@ impl FriProverDal<'_, '_> {
                         AND protocol_version_patch = $2
                         AND NOT (aggregation_round = $4 AND circuit_id = ANY($5))
                     ORDER BY
+                        lane DESC,
                         priority DESC,
                         batch_sealed_at ASC,
                         aggregation_round ASC,
@@ -386,6 +400,100 @@ impl FriProverDal<'_, '_> {
         .unwrap()
     }
 
+    /// Extends the lease of an in-progress job picked by `picked_by` to `lease_duration` from now. Returns `false`
+    /// if the job is no longer in progress or was picked by another prover, e.g. because it was reclaimed after
+    /// its lease expired.
+    pub async fn renew_lease(
+        &mut self,
+        id: u32,
+        chain_id: L2ChainId,
+        picked_by: &str,
+        lease_duration: Duration,
+    ) -> DalResult<bool> {
+        let lease_duration = pg_interval_from_duration(lease_duration);
+        let renewed = sqlx::query!(
+            r#"
+            UPDATE prover_jobs_fri
+            SET
+                lease_expires_at = NOW() + $4::INTERVAL,
+                updated_at = NOW()
+            WHERE
+                id = $1
+                AND chain_id = $2
+                AND picked_by = $3
+                AND status IN ('in_progress', 'in_gpu_proof')
+            "#,
+            i64::from(id),
+            chain_id.inner() as i32,
+            picked_by,
+            &lease_duration,
+        )
+        .instrument("renew_lease")
+        .with_arg("id", &id)
+        .with_arg("chain_id", &chain_id)
+        .with_arg("picked_by", &picked_by)
+        .execute(self.storage)
+        .await?
+        .rows_affected();
+        Ok(renewed > 0)
+    }
+
+    /// Requeues in-progress jobs whose lease has expired, i.e. jobs that were not renewed by their prover
+    /// in time (most likely because it has crashed).
+    pub async fn requeue_jobs_with_expired_leases(&mut self, max_attempts: u32) -> Vec<StuckJobs> {
+        sqlx::query!(
+            r#"
+            UPDATE prover_jobs_fri
+            SET
+                status = 'queued',
+                updated_at = NOW(),
+                processing_started_at = NOW(),
+                lease_expires_at = NULL,
+                priority = priority + 1
+            WHERE
+                (id, chain_id) IN (
+                    SELECT
+                        id,
+                        chain_id
+                    FROM
+                        prover_jobs_fri
+                    WHERE
+                        status IN ('in_progress', 'in_gpu_proof')
+                        AND lease_expires_at <= NOW()
+                        AND attempts < $1
+                    FOR UPDATE
+                    SKIP LOCKED
+                )
+            RETURNING
+            id,
+            chain_id,
+            status,
+            attempts,
+            circuit_id,
+            error,
+            picked_by
+            "#,
+            max_attempts as i32,
+        )
+        .fetch_all(self.storage.conn())
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|row| StuckJobs {
+            id: row.id as u64,
+            chain_id: L2ChainId::new(row.chain_id as u64).unwrap(),
+            status: row.status,
+            attempts: row.attempts as u64,
+            circuit_id: Some(row.circuit_id as u32),
+            error: row.error,
+            picked_by: row.picked_by,
+        })
+        .collect()
+    }
+
+    /// Requeues failed jobs and in-progress jobs that exceeded `processing_timeout`. This applies to jobs with a lease
+    /// as well, so that jobs of hung provers that keep renewing their leases are eventually reclaimed; jobs of crashed
+    /// provers are reclaimed earlier, once their lease expires (see [`Self::requeue_jobs_with_expired_leases()`]).
     pub async fn requeue_stuck_jobs(
         &mut self,
         processing_timeout: Duration,
@@ -400,6 +508,7 @@ impl FriProverDal<'_, '_> {
                     status = 'queued',
                     updated_at = NOW(),
                     processing_started_at = NOW(),
+                    lease_expires_at = NULL,
                     priority = priority + 1
                 WHERE
                     (id, chain_id) IN (
@@ -480,10 +589,38 @@ impl FriProverDal<'_, '_> {
                 created_at,
                 updated_at,
                 protocol_version_patch,
-                batch_sealed_at
+                batch_sealed_at,
+                lane
             )
             VALUES
-            ($1, $2, $3, $4, $5, $6, $7, $8, $9, 'queued', NOW(), NOW(), $10, $11)
+            (
+                $1,
+                $2,
+                $3,
+                $4,
+                $5,
+                $6,
+                $7,
+                $8,
+                $9,
+                'queued',
+                NOW(),
+                NOW(),
+                $10,
+                $11,
+                COALESCE(
+                    (
+                        SELECT
+                            lane
+                        FROM
+                            prover_batch_priorities
+                        WHERE
+                            l1_batch_number = $1
+                            AND chain_id = $2
+                    ),
+                    0
+                )
+            )
             ON CONFLICT (
                 l1_batch_number,
                 chain_id,
@@ -873,7 +1010,8 @@ impl FriProverDal<'_, '_> {
                     error = 'Manually requeued',
                     attempts = 2,
                     updated_at = NOW(),
-                    processing_started_at = NOW()
+                    processing_started_at = NOW(),
+                    lease_expires_at = NULL
                 WHERE
                     l1_batch_number = $1
                     AND attempts >= $2
@@ -1018,4 +1156,175 @@ mod tests {
 
         transaction.commit().await.unwrap();
     }
+
+    async fn insert_and_pick_job(conn: &mut Connection<'_, Prover>) -> FriProverJobMetadata {
+        let batch_id = L1BatchId::from_raw(1, 1);
+        conn.fri_protocol_versions_dal()
+            .save_prover_protocol_version(
+                ProtocolSemanticVersion::default(),
+                L1VerifierConfig::default(),
+            )
+            .await
+            .unwrap();
+        conn.fri_basic_witness_generator_dal()
+            .save_witness_inputs(
+                batch_id,
+                "",
+                ProtocolSemanticVersion::default(),
+                DateTime::<Utc>::default(),
+            )
+            .await
+            .unwrap();
+        conn.fri_prover_jobs_dal()
+            .insert_prover_jobs(
+                batch_id,
+                mock_circuit_ids_and_urls(1),
+                AggregationRound::Scheduler,
+                1,
+                ProtocolSemanticVersion::default(),
+                DateTime::<Utc>::default(),
+            )
+            .await;
+        conn.fri_prover_jobs_dal()
+            .get_light_job(ProtocolSemanticVersion::default(), "test")
+            .await
+            .expect("no job picked")
+    }
+
+    #[tokio::test]
+    async fn renewing_lease() {
+        let pool = ConnectionPool::<Prover>::prover_test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        let job = insert_and_pick_job(&mut conn).await;
+        let chain_id = job.batch_id.chain_id();
+
+        let renewed = conn
+            .fri_prover_jobs_dal()
+            .renew_lease(job.id, chain_id, "test", Duration::from_secs(60))
+            .await
+            .unwrap();
+        assert!(renewed);
+        // Only the prover that picked the job can renew its lease.
+        let renewed = conn
+            .fri_prover_jobs_dal()
+            .renew_lease(job.id, chain_id, "other", Duration::from_secs(60))
+            .await
+            .unwrap();
+        assert!(!renewed);
+
+        let requeued = conn
+            .fri_prover_jobs_dal()
+            .requeue_stuck_jobs(Duration::from_secs(3_600), 10)
+            .await;
+        assert!(requeued.is_empty(), "{requeued:?}");
+        let requeued = conn
+            .fri_prover_jobs_dal()
+            .requeue_jobs_with_expired_leases(10)
+            .await;
+        assert!(requeued.is_empty(), "{requeued:?}");
+
+        // Leases of jobs that are no longer in progress cannot be renewed.
+        conn.fri_prover_jobs_dal()
+            .update_status(job.id, chain_id, "successful")
+            .await;
+        let renewed = conn
+            .fri_prover_jobs_dal()
+            .renew_lease(job.id, chain_id, "test", Duration::from_secs(60))
+            .await
+            .unwrap();
+        assert!(!renewed);
+    }
+
+    #[tokio::test]
+    async fn requeuing_leased_jobs_after_processing_timeout() {
+        let pool = ConnectionPool::<Prover>::prover_test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        let job = insert_and_pick_job(&mut conn).await;
+        let chain_id = job.batch_id.chain_id();
+
+        let renewed = conn
+            .fri_prover_jobs_dal()
+            .renew_lease(job.id, chain_id, "test", Duration::from_secs(3_600))
+            .await
+            .unwrap();
+        assert!(renewed);
+
+        // A job with a valid lease is still requeued once it exceeds the processing timeout (e.g., if its prover hung).
+        let requeued = conn
+            .fri_prover_jobs_dal()
+            .requeue_stuck_jobs(Duration::ZERO, 10)
+            .await;
+        assert_eq!(requeued.len(), 1, "{requeued:?}");
+        assert_eq!(requeued[0].id, u64::from(job.id));
+
+        let renewed = conn
+            .fri_prover_jobs_dal()
+            .renew_lease(job.id, chain_id, "test", Duration::from_secs(3_600))
+            .await
+            .unwrap();
+        assert!(!renewed);
+    }
+
+    #[tokio::test]
+    async fn requeuing_jobs_with_expired_leases() {
+        let pool = ConnectionPool::<Prover>::prover_test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        let job = insert_and_pick_job(&mut conn).await;
+        let chain_id = job.batch_id.chain_id();
+
+        // Jobs without a lease are not affected.
+        let requeued = conn
+            .fri_prover_jobs_dal()
+            .requeue_jobs_with_expired_leases(10)
+            .await;
+        assert!(requeued.is_empty(), "{requeued:?}");
+
+        let renewed = conn
+            .fri_prover_jobs_dal()
+            .renew_lease(job.id, chain_id, "test", Duration::ZERO)
+            .await
+            .unwrap();
+        assert!(renewed);
+
+        // Jobs that reached max attempts are not requeued.
+        let requeued = conn
+            .fri_prover_jobs_dal()
+            .requeue_jobs_with_expired_leases(1)
+            .await;
+        assert!(requeued.is_empty(), "{requeued:?}");
+
+        let requeued = conn
+            .fri_prover_jobs_dal()
+            .requeue_jobs_with_expired_leases(10)
+            .await;
+        assert_eq!(requeued.len(), 1, "{requeued:?}");
+        assert_eq!(requeued[0].id, u64::from(job.id));
+        assert_eq!(requeued[0].status, "queued");
+
+        // The requeued job can no longer be renewed by the previous prover, even after it's picked again.
+        let renewed = conn
+            .fri_prover_jobs_dal()
+            .renew_lease(job.id, chain_id, "test", Duration::from_secs(60))
+            .await
+            .unwrap();
+        assert!(!renewed);
+        let picked_job = conn
+            .fri_prover_jobs_dal()
+            .get_light_job(ProtocolSemanticVersion::default(), "other")
+            .await
+            .expect("requeued job is not picked");
+        assert_eq!(picked_job.id, job.id);
+        let renewed = conn
+            .fri_prover_jobs_dal()
+            .renew_lease(job.id, chain_id, "test", Duration::from_secs(60))
+            .await
+            .unwrap();
+        assert!(!renewed);
+        let renewed = conn
+            .fri_prover_jobs_dal()
+            .renew_lease(job.id, chain_id, "other", Duration::from_secs(60))
+            .await
+            .unwrap();
+        assert!(renewed);
+    }
 }
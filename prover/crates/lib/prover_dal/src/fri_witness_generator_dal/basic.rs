@@ -1,4 +1,4 @@
-use std::time::Duration;
+use std::{collections::HashMap, time::Duration};
 
 use sqlx::types::chrono::{self, DateTime, Utc};
 use zksync_basic_types::{
@@ -63,10 +63,33 @@ impl FriBasicWitnessGeneratorDal<'_, '_> {
                 created_at,
                 updated_at,
                 protocol_version_patch,
-                batch_sealed_at
+                batch_sealed_at,
+                lane
             )
             VALUES
-            ($1, $2, $3, $4, 'queued', NOW(), NOW(), $5, $6)
+            (
+                $1,
+                $2,
+                $3,
+                $4,
+                'queued',
+                NOW(),
+                NOW(),
+                $5,
+                $6,
+                COALESCE(
+                    (
+                        SELECT
+                            lane
+                        FROM
+                            prover_batch_priorities
+                        WHERE
+                            l1_batch_number = $1
+                            AND chain_id = $2
+                    ),
+                    0
+                )
+            )
             ON CONFLICT (l1_batch_number, chain_id) DO NOTHING
             "#,
             batch_id.batch_number().0 as i64,
@@ -111,6 +134,7 @@ impl FriBasicWitnessGeneratorDal<'_, '_> {
                         AND protocol_version = $1
                         AND protocol_version_patch = $3
                     ORDER BY
+                        lane DESC,
                         priority DESC,
                         batch_sealed_at ASC
                     LIMIT
@@ -363,4 +387,41 @@ impl FriBasicWitnessGeneratorDal<'_, '_> {
         .unwrap()
         .unwrap_or(0) as usize
     }
+
+    /// Returns the number of batches sealed within `window` from now, per protocol version.
+    pub async fn count_recently_sealed_batches(
+        &mut self,
+        window: Duration,
+    ) -> HashMap<ProtocolSemanticVersion, usize> {
+        let window = pg_interval_from_duration(window);
+        sqlx::query!(
+            r#"
+            SELECT
+                protocol_version AS "protocol_version!",
+                protocol_version_patch,
+                COUNT(*) AS "count!"
+            FROM
+                witness_inputs_fri
+            WHERE
+                protocol_version IS NOT NULL
+                AND batch_sealed_at > NOW() - $1::INTERVAL
+            GROUP BY
+                protocol_version,
+                protocol_version_patch
+            "#,
+            &window
+        )
+        .fetch_all(self.storage.conn())
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|row| {
+            let protocol_version = ProtocolSemanticVersion::new(
+                ProtocolVersionId::try_from(row.protocol_version as u16).unwrap(),
+                VersionPatch(row.protocol_version_patch as u32),
+            );
+            (protocol_version, row.count as usize)
+        })
+        .collect()
+    }
 }
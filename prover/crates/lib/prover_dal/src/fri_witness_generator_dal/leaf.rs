@@ -74,6 +74,7 @@ impl FriLeafWitnessGeneratorDal<'_, '_> {
                         AND protocol_version = $1
                         AND protocol_version_patch = $2
                     ORDER BY
+                        lane DESC,
                         priority DESC,
                         batch_sealed_at ASC
                     LIMIT
@@ -278,7 +279,8 @@ impl FriLeafWitnessGeneratorDal<'_, '_> {
                 created_at,
                 updated_at,
                 protocol_version_patch,
-                batch_sealed_at
+                batch_sealed_at,
+                lane
             )
             VALUES
             (
@@ -292,7 +294,19 @@ impl FriLeafWitnessGeneratorDal<'_, '_> {
                 NOW(),
                 NOW(),
                 $7,
-                $8
+                $8,
+                COALESCE(
+                    (
+                        SELECT
+                            lane
+                        FROM
+                            prover_batch_priorities
+                        WHERE
+                            l1_batch_number = $1
+                            AND chain_id = $2
+                    ),
+                    0
+                )
             )
             ON CONFLICT (l1_batch_number, chain_id, circuit_id) DO
             UPDATE
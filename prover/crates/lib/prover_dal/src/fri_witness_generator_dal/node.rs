@@ -81,6 +81,7 @@ impl FriNodeWitnessGeneratorDal<'_, '_> {
                         AND protocol_version = $1
                         AND protocol_version_patch = $2
                     ORDER BY
+                        lane DESC,
                         priority DESC,
                         batch_sealed_at ASC,
                         depth ASC,
@@ -176,10 +177,36 @@ impl FriNodeWitnessGeneratorDal<'_, '_> {
                 created_at,
                 updated_at,
                 protocol_version_patch,
-                batch_sealed_at
+                batch_sealed_at,
+                lane
             )
             VALUES
-            ($1, $2, $3, $4, $5, $6, $7, 'waiting_for_proofs', NOW(), NOW(), $8, $9)
+            (
+                $1,
+                $2,
+                $3,
+                $4,
+                $5,
+                $6,
+                $7,
+                'waiting_for_proofs',
+                NOW(),
+                NOW(),
+                $8,
+                $9,
+                COALESCE(
+                    (
+                        SELECT
+                            lane
+                        FROM
+                            prover_batch_priorities
+                        WHERE
+                            l1_batch_number = $1
+                            AND chain_id = $2
+                    ),
+                    0
+                )
+            )
             ON CONFLICT (l1_batch_number, chain_id, circuit_id, depth) DO
             UPDATE
             SET
@@ -141,6 +141,7 @@ impl FriRecursionTipWitnessGeneratorDal<'_, '_> {
                         AND protocol_version = $1
                         AND protocol_version_patch = $2
                     ORDER BY
+                        lane DESC,
                         priority DESC,
                         batch_sealed_at ASC
                     LIMIT
@@ -298,10 +299,33 @@ impl FriRecursionTipWitnessGeneratorDal<'_, '_> {
                 created_at,
                 updated_at,
                 protocol_version_patch,
-                batch_sealed_at
+                batch_sealed_at,
+                lane
             )
             VALUES
-            ($1, $2, 'waiting_for_proofs', $3, $4, NOW(), NOW(), $5, $6)
+            (
+                $1,
+                $2,
+                'waiting_for_proofs',
+                $3,
+                $4,
+                NOW(),
+                NOW(),
+                $5,
+                $6,
+                COALESCE(
+                    (
+                        SELECT
+                            lane
+                        FROM
+                            prover_batch_priorities
+                        WHERE
+                            l1_batch_number = $1
+                            AND chain_id = $2
+                    ),
+                    0
+                )
+            )
             ON CONFLICT (l1_batch_number, chain_id) DO
             UPDATE
             SET
@@ -5,7 +5,7 @@
 pub trait Executor: Send + Sync + 'static {
     type Input: Send;
     type Output: Send;
-    type Metadata: Send + Clone;
+    type Metadata: Send + Clone + 'static;
 
     fn execute(&self, input: Self::Input, metadata: Self::Metadata)
         -> anyhow::Result<Self::Output>;
@@ -1,8 +1,10 @@
+use std::sync::Arc;
+
 use tokio::task::JoinHandle;
 
 use crate::{
     task_wiring::{JobPickerTask, JobSaverTask, Task, WorkerPool},
-    BackoffAndCancellable, Executor, JobPicker, JobSaver,
+    BackoffAndCancellable, Executor, JobPicker, JobSaver, LeaseGuard, LeaseRenewer,
 };
 
 /// It's preferred to have a minimal amount of jobs in flight at any given time.
@@ -24,6 +26,7 @@ where
     saver: S,
     num_workers: usize,
     picker_backoff_and_cancellable: Option<BackoffAndCancellable>,
+    lease_renewer: Option<Arc<dyn LeaseRenewer<ExecutorType = E>>>,
 }
 
 impl<E, P, S> JobRunner<E, P, S>
@@ -45,18 +48,35 @@ where
             saver,
             num_workers,
             picker_backoff_and_cancellable,
+            lease_renewer: None,
         }
     }
 
+    /// Renews leases of jobs from the moment they are picked until their results are saved.
+    pub fn with_lease_renewer(
+        mut self,
+        lease_renewer: impl LeaseRenewer<ExecutorType = E>,
+    ) -> Self {
+        self.lease_renewer = Some(Arc::new(lease_renewer));
+        self
+    }
+
     /// Runs job runner tasks.
     pub fn run(self) -> Vec<JoinHandle<anyhow::Result<()>>> {
         let (input_tx, input_rx) =
-            tokio::sync::mpsc::channel::<(E::Input, E::Metadata)>(CHANNEL_SIZE);
-        let (result_tx, result_rx) =
-            tokio::sync::mpsc::channel::<(anyhow::Result<E::Output>, E::Metadata)>(CHANNEL_SIZE);
+            tokio::sync::mpsc::channel::<(E::Input, E::Metadata, Option<LeaseGuard>)>(CHANNEL_SIZE);
+        let (result_tx, result_rx) = tokio::sync::mpsc::channel::<(
+            anyhow::Result<E::Output>,
+            E::Metadata,
+            Option<LeaseGuard>,
+        )>(CHANNEL_SIZE);
 
-        let picker_task =
-            JobPickerTask::new(self.picker, input_tx, self.picker_backoff_and_cancellable);
+        let picker_task = JobPickerTask::new(
+            self.picker,
+            input_tx,
+            self.picker_backoff_and_cancellable,
+            self.lease_renewer,
+        );
         let worker_pool = WorkerPool::new(self.executor, self.num_workers, input_rx, result_tx);
         let saver_task = JobSaverTask::new(self.saver, result_rx);
 
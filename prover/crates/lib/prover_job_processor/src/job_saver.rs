@@ -1,6 +1,6 @@
 use async_trait::async_trait;
 
-use crate::Executor;
+use crate::{Executor, LeaseGuard};
 
 /// Job Saver trait, in charge of getting the result from the executor and dispatching it.
 ///
@@ -17,4 +17,20 @@ pub trait JobSaver: Send + Sync + 'static {
             <Self::ExecutorType as Executor>::Metadata,
         ),
     ) -> anyhow::Result<()>;
+
+    /// Saves the job result while holding the job lease, if any. By default, the lease is released once the result
+    /// is saved. Savers that hand the job over to another component should pass the lease along instead,
+    /// so that the job stays leased until it's finished.
+    async fn save_job_result_with_lease(
+        &self,
+        data: (
+            anyhow::Result<<Self::ExecutorType as Executor>::Output>,
+            <Self::ExecutorType as Executor>::Metadata,
+        ),
+        lease: Option<LeaseGuard>,
+    ) -> anyhow::Result<()> {
+        let result = self.save_job_result(data).await;
+        drop(lease);
+        result
+    }
 }
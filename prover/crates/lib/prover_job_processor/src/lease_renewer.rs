@@ -0,0 +1,142 @@
+use std::{fmt, sync::Arc, time::Duration};
+
+use async_trait::async_trait;
+use tokio::{task::JoinHandle, time::MissedTickBehavior};
+
+use crate::Executor;
+
+/// Lease Renewer trait, in charge of keeping a job leased while the job is in flight.
+///
+/// The lease is renewed every `renewal_interval()` from the moment the job is picked until its result is saved, so the
+/// job can be reclaimed as soon as the lease expires (e.g. the worker crashed), instead of after a static timeout.
+/// NOTE: Lease Renewers are tied to an executor, which ensures metadata types match.
+#[async_trait]
+pub trait LeaseRenewer: fmt::Debug + Send + Sync + 'static {
+    type ExecutorType: Executor;
+
+    /// How often the lease must be renewed while the job is in flight.
+    fn renewal_interval(&self) -> Duration;
+
+    /// Renews the lease of the job. Returns `false` if the lease is lost (e.g., the job was reclaimed and possibly
+    /// picked by another worker), in which case it's no longer renewed.
+    async fn renew_lease(
+        &self,
+        metadata: <Self::ExecutorType as Executor>::Metadata,
+    ) -> anyhow::Result<bool>;
+}
+
+/// Keeps the lease of a single job alive by renewing it in the background until dropped.
+///
+/// The guard is not tied to an executor type, so it can be handed over between components processing the same job
+/// (e.g. from witness vector generator to circuit prover).
+/// Failing to renew the lease doesn't interrupt processing; the job will be reclaimed once the lease expires.
+/// Once the lease is lost, the guard stops renewing it, so that it doesn't interfere with the worker that owns the job now.
+#[derive(Debug)]
+pub struct LeaseGuard {
+    renewal_task: JoinHandle<()>,
+}
+
+impl LeaseGuard {
+    /// Starts renewing the lease of the job with the specified metadata.
+    pub fn new<E: Executor>(
+        lease_renewer: Arc<dyn LeaseRenewer<ExecutorType = E>>,
+        metadata: E::Metadata,
+    ) -> Self {
+        let renewal_task = tokio::spawn(async move {
+            let mut renewal_interval = tokio::time::interval(lease_renewer.renewal_interval());
+            renewal_interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+            // The first tick completes immediately, so the lease is established right after the job is picked.
+            loop {
+                renewal_interval.tick().await;
+                match lease_renewer.renew_lease(metadata.clone()).await {
+                    Ok(true) => {}
+                    Ok(false) => {
+                        tracing::info!("job lease is lost, stopping renewal");
+                        return;
+                    }
+                    Err(err) => tracing::warn!("failed renewing job lease: {err:#}"),
+                }
+            }
+        });
+        Self { renewal_task }
+    }
+}
+
+impl Drop for LeaseGuard {
+    fn drop(&mut self) {
+        self.renewal_task.abort();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+
+    #[derive(Debug)]
+    struct NoopExecutor;
+
+    impl Executor for NoopExecutor {
+        type Input = ();
+        type Output = ();
+        type Metadata = ();
+
+        fn execute(&self, _input: (), _metadata: ()) -> anyhow::Result<()> {
+            Ok(())
+        }
+    }
+
+    /// Counts renewals; the lease is lost after `lost_after` renewals.
+    #[derive(Debug)]
+    struct CountingRenewer {
+        count: AtomicUsize,
+        lost_after: usize,
+    }
+
+    impl CountingRenewer {
+        fn new(lost_after: usize) -> Self {
+            Self {
+                count: AtomicUsize::new(0),
+                lost_after,
+            }
+        }
+    }
+
+    #[async_trait]
+    impl LeaseRenewer for CountingRenewer {
+        type ExecutorType = NoopExecutor;
+
+        fn renewal_interval(&self) -> Duration {
+            Duration::from_secs(1)
+        }
+
+        async fn renew_lease(&self, _metadata: ()) -> anyhow::Result<bool> {
+            let count = self.count.fetch_add(1, Ordering::SeqCst) + 1;
+            Ok(count <= self.lost_after)
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn lease_is_renewed_until_guard_is_dropped() {
+        let renewer = Arc::new(CountingRenewer::new(usize::MAX));
+        let guard = LeaseGuard::new::<NoopExecutor>(renewer.clone(), ());
+
+        tokio::time::sleep(Duration::from_millis(3_500)).await;
+        assert_eq!(renewer.count.load(Ordering::SeqCst), 4);
+
+        drop(guard);
+        tokio::time::sleep(Duration::from_secs(5)).await;
+        assert_eq!(renewer.count.load(Ordering::SeqCst), 4);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn lease_is_not_renewed_once_lost() {
+        let renewer = Arc::new(CountingRenewer::new(2));
+        let _guard = LeaseGuard::new::<NoopExecutor>(renewer.clone(), ());
+
+        tokio::time::sleep(Duration::from_secs(10)).await;
+        // 2 successful renewals + 1 renewal that discovered the lease is lost.
+        assert_eq!(renewer.count.load(Ordering::SeqCst), 3);
+    }
+}
@@ -3,12 +3,14 @@ pub use executor::Executor;
 pub use job_picker::JobPicker;
 pub use job_runner::JobRunner;
 pub use job_saver::JobSaver;
+pub use lease_renewer::{LeaseGuard, LeaseRenewer};
 
 mod backoff_and_cancellable;
 mod executor;
 mod job_picker;
 mod job_runner;
 mod job_saver;
+mod lease_renewer;
 mod task_wiring;
 
 // convenience aliases to simplify declarations
@@ -1,26 +1,36 @@
+use std::sync::Arc;
+
 use anyhow::Context;
 use async_trait::async_trait;
 
-use crate::{task_wiring::task::Task, BackoffAndCancellable, Input, JobPicker, PickerMetadata};
+use crate::{
+    task_wiring::task::Task, BackoffAndCancellable, Input, JobPicker, LeaseGuard, LeaseRenewer,
+    PickerMetadata,
+};
 
 /// Wrapper over JobPicker. Makes it a continuous task, picking tasks until cancelled.
+/// If a lease renewer is provided, starts renewing the lease of each picked job; the lease travels with the job
+/// until its result is saved.
 #[derive(Debug)]
 pub struct JobPickerTask<P: JobPicker> {
     picker: P,
-    input_tx: tokio::sync::mpsc::Sender<(Input<P>, PickerMetadata<P>)>,
+    input_tx: tokio::sync::mpsc::Sender<(Input<P>, PickerMetadata<P>, Option<LeaseGuard>)>,
     backoff_and_cancellable: Option<BackoffAndCancellable>,
+    lease_renewer: Option<Arc<dyn LeaseRenewer<ExecutorType = P::ExecutorType>>>,
 }
 
 impl<P: JobPicker> JobPickerTask<P> {
     pub fn new(
         picker: P,
-        input_tx: tokio::sync::mpsc::Sender<(Input<P>, PickerMetadata<P>)>,
+        input_tx: tokio::sync::mpsc::Sender<(Input<P>, PickerMetadata<P>, Option<LeaseGuard>)>,
         backoff_and_cancellable: Option<BackoffAndCancellable>,
+        lease_renewer: Option<Arc<dyn LeaseRenewer<ExecutorType = P::ExecutorType>>>,
     ) -> Self {
         Self {
             picker,
             input_tx,
             backoff_and_cancellable,
+            lease_renewer,
         }
     }
 
@@ -61,9 +71,16 @@ impl<P: JobPicker> Task for JobPickerTask<P> {
         while !self.is_cancelled() {
             match self.picker.pick_job().await.context("failed to pick job")? {
                 Some((input, metadata)) => {
-                    self.input_tx.send((input, metadata)).await.map_err(|err| {
-                        anyhow::anyhow!("job picker failed to pass job to executor: {}", err)
-                    })?;
+                    let lease = self
+                        .lease_renewer
+                        .clone()
+                        .map(|renewer| LeaseGuard::new(renewer, metadata.clone()));
+                    self.input_tx
+                        .send((input, metadata, lease))
+                        .await
+                        .map_err(|err| {
+                            anyhow::anyhow!("job picker failed to pass job to executor: {}", err)
+                        })?;
                     self.reset_backoff();
                 }
                 None => {
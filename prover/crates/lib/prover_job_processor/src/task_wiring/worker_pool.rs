@@ -4,10 +4,11 @@ use async_trait::async_trait;
 use futures::stream::StreamExt;
 use tokio_stream::wrappers::ReceiverStream;
 
-use crate::{executor::Executor, task_wiring::Task};
+use crate::{executor::Executor, task_wiring::Task, LeaseGuard};
 
 /// Wrapper over Executor. Makes it a continuous task, picking tasks until picker channel is closed.
 /// It can execute multiple concurrent executors, up to specified limit.
+/// Job leases (if any) are passed along with the results, so that they are held until results are saved.
 #[derive(Debug)]
 pub struct WorkerPool<E>
 where
@@ -15,16 +16,21 @@ where
 {
     executor: E,
     num_workers: usize,
-    input_rx: tokio::sync::mpsc::Receiver<(E::Input, E::Metadata)>,
-    result_tx: tokio::sync::mpsc::Sender<(anyhow::Result<E::Output>, E::Metadata)>,
+    input_rx: tokio::sync::mpsc::Receiver<(E::Input, E::Metadata, Option<LeaseGuard>)>,
+    result_tx:
+        tokio::sync::mpsc::Sender<(anyhow::Result<E::Output>, E::Metadata, Option<LeaseGuard>)>,
 }
 
 impl<E: Executor> WorkerPool<E> {
     pub fn new(
         executor: E,
         num_workers: usize,
-        input_rx: tokio::sync::mpsc::Receiver<(E::Input, E::Metadata)>,
-        result_tx: tokio::sync::mpsc::Sender<(anyhow::Result<E::Output>, E::Metadata)>,
+        input_rx: tokio::sync::mpsc::Receiver<(E::Input, E::Metadata, Option<LeaseGuard>)>,
+        result_tx: tokio::sync::mpsc::Sender<(
+            anyhow::Result<E::Output>,
+            E::Metadata,
+            Option<LeaseGuard>,
+        )>,
     ) -> Self {
         Self {
             executor,
@@ -43,7 +49,7 @@ impl<E: Executor> Task for WorkerPool<E> {
         let stream = ReceiverStream::new(self.input_rx);
 
         stream
-            .for_each_concurrent(num_workers, move |(input, metadata)| {
+            .for_each_concurrent(num_workers, move |(input, metadata, lease)| {
                 let executor = executor.clone();
                 let result_tx = self.result_tx.clone();
                 let exec_metadata = metadata.clone();
@@ -53,7 +59,7 @@ impl<E: Executor> Task for WorkerPool<E> {
                             .await
                             .expect("failed executing");
                     result_tx
-                        .send((payload, metadata))
+                        .send((payload, metadata, lease))
                         .await
                         .expect("job saver channel has been closed unexpectedly");
                 }